@@ -0,0 +1,38 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use anyhow::Context;
+use std::path::Path;
+
+fn main() -> Result<(), anyhow::Error> {
+    let crate_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap();
+
+    let output_dir = std::env::var_os("SLINT_GENERATED_INCLUDE_DIR").unwrap_or_else(|| {
+        Path::new(&std::env::var_os("OUT_DIR").unwrap()).join("generated_include").into()
+    });
+    let output_dir = Path::new(&output_dir);
+    std::fs::create_dir_all(output_dir).context("Could not create the include directory")?;
+
+    println!("cargo:GENERATED_INCLUDE_DIR={}", output_dir.display());
+    println!("cargo:rerun-if-changed=lib.rs");
+
+    let config = cbindgen::Config {
+        pragma_once: true,
+        include_guard: Some("SLINT_INTERPRETER_C_H".into()),
+        include_version: true,
+        line_length: 100,
+        tab_width: 4,
+        language: cbindgen::Language::C,
+        cpp_compat: true,
+        documentation: true,
+        enumeration: cbindgen::EnumConfig { prefix_with_name: true, ..Default::default() },
+        ..Default::default()
+    };
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_crate(crate_dir)
+        .generate()
+        .context("Unable to generate bindings for slint_interpreter_c.h")?
+        .write_to_file(output_dir.join("slint_interpreter_c.h"));
+    Ok(())
+}
@@ -0,0 +1,718 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    The C API of the Slint interpreter, to load .slint files at runtime from C and from the
+    languages that can call C functions. The `slint_interpreter_c.h` header is generated in the
+    build, see the README.
+
+    The objects are created and destroyed with the functions of this API: each `slint_*_new`,
+    `slint_*_create`, `slint_*_build_*`, or `slint_*_clone` function, and each function that
+    returns a `SlintValue *`, a `SlintStruct *`, or a `char *`, gives the ownership of the result
+    to the caller, who must release it with the matching `slint_*_free` function. The pointers
+    passed as arguments are only borrowed, and must not be NULL, unless stated otherwise.
+
+    The strings passed as arguments are NUL-terminated UTF-8.
+*/
+
+#![allow(clippy::missing_safety_doc)]
+
+use i_slint_core::model::{Model, ModelRc, VecModel};
+use slint_interpreter::{
+    Brush, Color, ComponentCompiler, ComponentDefinition, ComponentHandle, ComponentInstance,
+    DiagnosticLevel, SharedString, Value, ValueType,
+};
+use std::borrow::Cow;
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_char;
+
+/// A value of a property, of an argument of a callback, or of a field of a struct
+pub struct SlintValue(Value);
+
+/// A struct of the .slint language, with its fields by name
+pub struct SlintStruct(slint_interpreter::Struct);
+
+/// Compiles .slint files into component definitions
+pub struct SlintCompiler(ComponentCompiler);
+
+/// A compiled component, from which instances are created
+pub struct SlintComponentDefinition(ComponentDefinition);
+
+/// An instance of a component, with its own window
+pub struct SlintComponentInstance(ComponentInstance);
+
+/// The type of a value
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SlintValueType {
+    /// No value, such as the result of a callback that returns nothing
+    Void,
+    /// An `int`, a `float`, or a number with a unit, such as a `length`
+    Number,
+    /// A `string`
+    String,
+    /// A `bool`
+    Bool,
+    /// An array or a model
+    Array,
+    /// A struct
+    Struct,
+    /// A `brush` or a `color`
+    Brush,
+    /// An `image`
+    Image,
+    /// A value of an `enum`, which is converted from and to the string of its name
+    Enumeration,
+    /// Another type, which cannot be converted by this API
+    Other,
+}
+
+/// The severity of a diagnostic of the compilation
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SlintDiagnosticLevel {
+    /// An error: the component cannot be compiled
+    Error,
+    /// A warning: the component is compiled, but it may not be what is intended
+    Warning,
+}
+
+/// The function called when a callback of a component instance is invoked, with the `user_data`
+/// passed to `slint_component_instance_set_callback`, and the arguments of the callback.
+/// It returns the result of the callback, of which it gives the ownership, or NULL.
+pub type SlintCallbackFn = extern "C" fn(
+    user_data: *mut c_void,
+    args: *const *const SlintValue,
+    arg_count: usize,
+) -> *mut SlintValue;
+
+unsafe fn to_str<'a>(string: *const c_char) -> Cow<'a, str> {
+    CStr::from_ptr(string).to_string_lossy()
+}
+
+fn to_c_string(string: &str) -> *mut c_char {
+    // The strings of Slint can contain a NUL character, where the C string is truncated
+    let string = string.split('\0').next().unwrap_or_default();
+    CString::new(string).unwrap_or_default().into_raw()
+}
+
+fn new_value(value: Value) -> Box<SlintValue> {
+    Box::new(SlintValue(value))
+}
+
+/// Release a string returned by this API. Does nothing if the string is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn slint_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string))
+    }
+}
+
+/// Create a value without content, of the `Void` type
+#[no_mangle]
+pub extern "C" fn slint_value_new_void() -> Box<SlintValue> {
+    new_value(Value::Void)
+}
+
+/// Create a number
+#[no_mangle]
+pub extern "C" fn slint_value_new_number(number: f64) -> Box<SlintValue> {
+    new_value(Value::Number(number))
+}
+
+/// Create a string
+#[no_mangle]
+pub unsafe extern "C" fn slint_value_new_string(string: *const c_char) -> Box<SlintValue> {
+    new_value(Value::String(SharedString::from(&*to_str(string))))
+}
+
+/// Create a boolean
+#[no_mangle]
+pub extern "C" fn slint_value_new_bool(boolean: bool) -> Box<SlintValue> {
+    new_value(Value::Bool(boolean))
+}
+
+/// Create a color, from its alpha, red, green, and blue components in the `0xAARRGGBB` format
+#[no_mangle]
+pub extern "C" fn slint_value_new_color(argb: u32) -> Box<SlintValue> {
+    let [a, r, g, b] = argb.to_be_bytes();
+    new_value(Value::Brush(Brush::SolidColor(Color::from_argb_u8(a, r, g, b))))
+}
+
+/// Create an array with copies of the `count` values of `values`
+#[no_mangle]
+pub unsafe extern "C" fn slint_value_new_array(
+    values: *const *const SlintValue,
+    count: usize,
+) -> Box<SlintValue> {
+    let values: &[*const SlintValue] =
+        if count == 0 { &[] } else { std::slice::from_raw_parts(values, count) };
+    let values = values.iter().map(|v| (**v).0.clone()).collect::<Vec<_>>();
+    new_value(Value::Model(ModelRc::new(VecModel::from(values))))
+}
+
+/// Create a copy of a struct
+#[no_mangle]
+pub extern "C" fn slint_value_new_struct(structure: &SlintStruct) -> Box<SlintValue> {
+    new_value(Value::Struct(structure.0.clone()))
+}
+
+/// Create a copy of a value
+#[no_mangle]
+pub extern "C" fn slint_value_clone(value: &SlintValue) -> Box<SlintValue> {
+    new_value(value.0.clone())
+}
+
+/// Release a value. Does nothing if the value is NULL.
+#[no_mangle]
+pub extern "C" fn slint_value_free(value: Option<Box<SlintValue>>) {
+    drop(value)
+}
+
+/// The type of a value
+#[no_mangle]
+pub extern "C" fn slint_value_type(value: &SlintValue) -> SlintValueType {
+    match value.0.value_type() {
+        ValueType::Void => SlintValueType::Void,
+        ValueType::Number => SlintValueType::Number,
+        ValueType::String => SlintValueType::String,
+        ValueType::Bool => SlintValueType::Bool,
+        ValueType::Model => SlintValueType::Array,
+        ValueType::Struct => SlintValueType::Struct,
+        ValueType::Brush => SlintValueType::Brush,
+        ValueType::Image => SlintValueType::Image,
+        ValueType::Enumeration => SlintValueType::Enumeration,
+        _ => SlintValueType::Other,
+    }
+}
+
+/// Whether two values are equal
+#[no_mangle]
+pub extern "C" fn slint_value_eq(a: &SlintValue, b: &SlintValue) -> bool {
+    a.0 == b.0
+}
+
+/// Write the number of a value in `number`. Returns false if the value is not a number.
+#[no_mangle]
+pub extern "C" fn slint_value_to_number(value: &SlintValue, number: &mut f64) -> bool {
+    match value.0 {
+        Value::Number(n) => {
+            *number = n;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Write the boolean of a value in `boolean`. Returns false if the value is not a boolean.
+#[no_mangle]
+pub extern "C" fn slint_value_to_bool(value: &SlintValue, boolean: &mut bool) -> bool {
+    match value.0 {
+        Value::Bool(b) => {
+            *boolean = b;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Returns the string of a value, or the name of an enumeration value, or NULL if the value is
+/// not a string. The string must be released with `slint_string_free`.
+#[no_mangle]
+pub extern "C" fn slint_value_to_string(value: &SlintValue) -> *mut c_char {
+    match &value.0 {
+        Value::String(s) => to_c_string(s),
+        Value::EnumerationValue(_, name) => to_c_string(name),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// Write the color of a value in `argb`, in the `0xAARRGGBB` format. Returns false if the value
+/// is not a brush with a single color.
+#[no_mangle]
+pub extern "C" fn slint_value_to_color(value: &SlintValue, argb: &mut u32) -> bool {
+    match &value.0 {
+        Value::Brush(Brush::SolidColor(color)) => {
+            *argb = u32::from_be_bytes([color.alpha(), color.red(), color.green(), color.blue()]);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// The number of rows of an array, or 0 if the value is not an array
+#[no_mangle]
+pub extern "C" fn slint_value_array_len(value: &SlintValue) -> usize {
+    match &value.0 {
+        Value::Model(model) => model.row_count(),
+        _ => 0,
+    }
+}
+
+/// A copy of a row of an array, or NULL if the value is not an array or if the index is out of
+/// bounds
+#[no_mangle]
+pub extern "C" fn slint_value_array_get(
+    value: &SlintValue,
+    index: usize,
+) -> Option<Box<SlintValue>> {
+    match &value.0 {
+        Value::Model(model) => model.row_data(index).map(new_value),
+        _ => None,
+    }
+}
+
+/// A copy of the struct of a value, or NULL if the value is not a struct
+#[no_mangle]
+pub extern "C" fn slint_value_to_struct(value: &SlintValue) -> Option<Box<SlintStruct>> {
+    match &value.0 {
+        Value::Struct(s) => Some(Box::new(SlintStruct(s.clone()))),
+        _ => None,
+    }
+}
+
+/// Create a struct without fields
+#[no_mangle]
+pub extern "C" fn slint_struct_new() -> Box<SlintStruct> {
+    Box::new(SlintStruct(Default::default()))
+}
+
+/// Release a struct. Does nothing if the struct is NULL.
+#[no_mangle]
+pub extern "C" fn slint_struct_free(structure: Option<Box<SlintStruct>>) {
+    drop(structure)
+}
+
+/// Set a field of a struct to a copy of the value
+#[no_mangle]
+pub unsafe extern "C" fn slint_struct_set_field(
+    structure: &mut SlintStruct,
+    name: *const c_char,
+    value: &SlintValue,
+) {
+    structure.0.set_field(to_str(name).into_owned(), value.0.clone())
+}
+
+/// A copy of the value of a field of a struct, or NULL if the struct has no such field
+#[no_mangle]
+pub unsafe extern "C" fn slint_struct_get_field(
+    structure: &SlintStruct,
+    name: *const c_char,
+) -> Option<Box<SlintValue>> {
+    structure.0.get_field(&to_str(name)).cloned().map(new_value)
+}
+
+/// The number of fields of a struct
+#[no_mangle]
+pub extern "C" fn slint_struct_field_count(structure: &SlintStruct) -> usize {
+    structure.0.iter().count()
+}
+
+/// The name of a field of a struct, in the alphabetical order of the names, or NULL if the index
+/// is out of bounds. The string must be released with `slint_string_free`.
+#[no_mangle]
+pub extern "C" fn slint_struct_field_name(structure: &SlintStruct, index: usize) -> *mut c_char {
+    let mut names = structure.0.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    names.sort_unstable();
+    names.get(index).map_or(std::ptr::null_mut(), |name| to_c_string(name))
+}
+
+/// Create a compiler
+#[no_mangle]
+pub extern "C" fn slint_compiler_new() -> Box<SlintCompiler> {
+    Box::new(SlintCompiler(ComponentCompiler::default()))
+}
+
+/// Release a compiler. Does nothing if the compiler is NULL.
+#[no_mangle]
+pub extern "C" fn slint_compiler_free(compiler: Option<Box<SlintCompiler>>) {
+    drop(compiler)
+}
+
+/// Set the style of the widgets, such as `fluent`
+#[no_mangle]
+pub unsafe extern "C" fn slint_compiler_set_style(
+    compiler: &mut SlintCompiler,
+    style: *const c_char,
+) {
+    compiler.0.set_style(to_str(style).into_owned())
+}
+
+/// Add a directory where the imported .slint files and the images are looked up
+#[no_mangle]
+pub unsafe extern "C" fn slint_compiler_add_include_path(
+    compiler: &mut SlintCompiler,
+    path: *const c_char,
+) {
+    let mut include_paths = compiler.0.include_paths().clone();
+    include_paths.push(to_str(path).into_owned().into());
+    compiler.0.set_include_paths(include_paths)
+}
+
+/// Compile the last component of a .slint file. Returns NULL if the compilation failed, and the
+/// errors are then in the diagnostics of the compiler.
+#[no_mangle]
+pub unsafe extern "C" fn slint_compiler_build_from_path(
+    compiler: &mut SlintCompiler,
+    path: *const c_char,
+) -> Option<Box<SlintComponentDefinition>> {
+    let path = to_str(path).into_owned();
+    spin_on::spin_on(compiler.0.build_from_path(path))
+        .map(|definition| Box::new(SlintComponentDefinition(definition)))
+}
+
+/// Compile the last component of the source. `path` is used for the diagnostics and to resolve
+/// the relative paths of the imports and of the images, and can be NULL.
+/// Returns NULL if the compilation failed, and the errors are then in the diagnostics of the
+/// compiler.
+#[no_mangle]
+pub unsafe extern "C" fn slint_compiler_build_from_source(
+    compiler: &mut SlintCompiler,
+    source: *const c_char,
+    path: *const c_char,
+) -> Option<Box<SlintComponentDefinition>> {
+    let source = to_str(source).into_owned();
+    let path = if path.is_null() { Default::default() } else { to_str(path).into_owned().into() };
+    spin_on::spin_on(compiler.0.build_from_source(source, path))
+        .map(|definition| Box::new(SlintComponentDefinition(definition)))
+}
+
+/// The number of diagnostics of the last compilation
+#[no_mangle]
+pub extern "C" fn slint_compiler_diagnostic_count(compiler: &SlintCompiler) -> usize {
+    compiler.0.diagnostics().len()
+}
+
+/// Write the level of a diagnostic of the last compilation in `level`. Returns false if the index
+/// is out of bounds.
+#[no_mangle]
+pub extern "C" fn slint_compiler_diagnostic_level(
+    compiler: &SlintCompiler,
+    index: usize,
+    level: &mut SlintDiagnosticLevel,
+) -> bool {
+    match compiler.0.diagnostics().get(index) {
+        Some(diagnostic) => {
+            *level = match diagnostic.level() {
+                DiagnosticLevel::Warning => SlintDiagnosticLevel::Warning,
+                _ => SlintDiagnosticLevel::Error,
+            };
+            true
+        }
+        None => false,
+    }
+}
+
+/// The message of a diagnostic of the last compilation, such as `file.slint:3:12: error: ...`,
+/// or NULL if the index is out of bounds. The string must be released with `slint_string_free`.
+#[no_mangle]
+pub extern "C" fn slint_compiler_diagnostic_message(
+    compiler: &SlintCompiler,
+    index: usize,
+) -> *mut c_char {
+    compiler
+        .0
+        .diagnostics()
+        .get(index)
+        .map_or(std::ptr::null_mut(), |d| to_c_string(&d.to_string()))
+}
+
+/// Create a copy of a component definition
+#[no_mangle]
+pub extern "C" fn slint_component_definition_clone(
+    definition: &SlintComponentDefinition,
+) -> Box<SlintComponentDefinition> {
+    Box::new(SlintComponentDefinition(definition.0.clone()))
+}
+
+/// Release a component definition. Does nothing if the definition is NULL.
+#[no_mangle]
+pub extern "C" fn slint_component_definition_free(
+    definition: Option<Box<SlintComponentDefinition>>,
+) {
+    drop(definition)
+}
+
+/// The name of the component. The string must be released with `slint_string_free`.
+#[no_mangle]
+pub extern "C" fn slint_component_definition_name(
+    definition: &SlintComponentDefinition,
+) -> *mut c_char {
+    to_c_string(definition.0.name())
+}
+
+/// The number of public properties of the component
+#[no_mangle]
+pub extern "C" fn slint_component_definition_property_count(
+    definition: &SlintComponentDefinition,
+) -> usize {
+    definition.0.properties().count()
+}
+
+/// The name of a public property of the component, or NULL if the index is out of bounds.
+/// The string must be released with `slint_string_free`.
+#[no_mangle]
+pub extern "C" fn slint_component_definition_property_name(
+    definition: &SlintComponentDefinition,
+    index: usize,
+) -> *mut c_char {
+    definition
+        .0
+        .properties()
+        .nth(index)
+        .map_or(std::ptr::null_mut(), |(name, _)| to_c_string(&name))
+}
+
+/// The number of public callbacks of the component
+#[no_mangle]
+pub extern "C" fn slint_component_definition_callback_count(
+    definition: &SlintComponentDefinition,
+) -> usize {
+    definition.0.callbacks().count()
+}
+
+/// The name of a public callback of the component, or NULL if the index is out of bounds.
+/// The string must be released with `slint_string_free`.
+#[no_mangle]
+pub extern "C" fn slint_component_definition_callback_name(
+    definition: &SlintComponentDefinition,
+    index: usize,
+) -> *mut c_char {
+    definition.0.callbacks().nth(index).map_or(std::ptr::null_mut(), |name| to_c_string(&name))
+}
+
+/// Create an instance of the component
+#[no_mangle]
+pub extern "C" fn slint_component_definition_create(
+    definition: &SlintComponentDefinition,
+) -> Box<SlintComponentInstance> {
+    Box::new(SlintComponentInstance(definition.0.create()))
+}
+
+/// Release a component instance. Does nothing if the instance is NULL.
+/// The component is destroyed when it is not shown anymore.
+#[no_mangle]
+pub extern "C" fn slint_component_instance_free(instance: Option<Box<SlintComponentInstance>>) {
+    drop(instance)
+}
+
+/// A copy of the value of a public property, or NULL if there is no such property
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_get_property(
+    instance: &SlintComponentInstance,
+    name: *const c_char,
+) -> Option<Box<SlintValue>> {
+    instance.0.get_property(&to_str(name)).ok().map(new_value)
+}
+
+/// Set a public property to a copy of the value. Returns false if there is no such property or
+/// if the value has the wrong type.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_set_property(
+    instance: &SlintComponentInstance,
+    name: *const c_char,
+    value: &SlintValue,
+) -> bool {
+    instance.0.set_property(&to_str(name), value.0.clone()).is_ok()
+}
+
+/// Invoke a public callback with the `arg_count` values of `args`, and return its result, or
+/// NULL if there is no such callback
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_invoke_callback(
+    instance: &SlintComponentInstance,
+    name: *const c_char,
+    args: *const *const SlintValue,
+    arg_count: usize,
+) -> Option<Box<SlintValue>> {
+    let args: &[*const SlintValue] =
+        if arg_count == 0 { &[] } else { std::slice::from_raw_parts(args, arg_count) };
+    let args = args.iter().map(|v| (**v).0.clone()).collect::<Vec<_>>();
+    instance.0.invoke_callback(&to_str(name), &args).ok().map(new_value)
+}
+
+/// Set the handler of a public callback. `drop_user_data`, which can be NULL, is called with the
+/// `user_data` when the handler is replaced or when the component is destroyed.
+/// Returns false if there is no such callback, and `drop_user_data` is then called right away.
+#[no_mangle]
+pub unsafe extern "C" fn slint_component_instance_set_callback(
+    instance: &SlintComponentInstance,
+    name: *const c_char,
+    callback: SlintCallbackFn,
+    user_data: *mut c_void,
+    drop_user_data: Option<extern "C" fn(user_data: *mut c_void)>,
+) -> bool {
+    struct UserData {
+        user_data: *mut c_void,
+        drop_user_data: Option<extern "C" fn(*mut c_void)>,
+    }
+    impl Drop for UserData {
+        fn drop(&mut self) {
+            if let Some(x) = self.drop_user_data {
+                x(self.user_data)
+            }
+        }
+    }
+    let ud = UserData { user_data, drop_user_data };
+
+    instance
+        .0
+        .set_callback(&to_str(name), move |args| {
+            let args = args.iter().map(|v| SlintValue(v.clone())).collect::<Vec<_>>();
+            let arg_pointers = args.iter().map(|v| v as *const SlintValue).collect::<Vec<_>>();
+            let result = callback(ud.user_data, arg_pointers.as_ptr(), arg_pointers.len());
+            if result.is_null() {
+                Value::Void
+            } else {
+                Box::from_raw(result).0
+            }
+        })
+        .is_ok()
+}
+
+/// Show the window of the component instance
+#[no_mangle]
+pub extern "C" fn slint_component_instance_show(instance: &SlintComponentInstance) {
+    instance.0.show()
+}
+
+/// Hide the window of the component instance
+#[no_mangle]
+pub extern "C" fn slint_component_instance_hide(instance: &SlintComponentInstance) {
+    instance.0.hide()
+}
+
+/// Show the window of the component instance, run the event loop until the window is closed,
+/// and hide the window
+#[no_mangle]
+pub extern "C" fn slint_component_instance_run(instance: &SlintComponentInstance) {
+    instance.0.run()
+}
+
+/// Run the event loop until the last window is closed or until `slint_event_loop_quit` is called
+#[no_mangle]
+pub extern "C" fn slint_event_loop_run() {
+    slint_interpreter::run_event_loop()
+}
+
+/// Quit the event loop started by `slint_event_loop_run` or `slint_component_instance_run`
+#[no_mangle]
+pub extern "C" fn slint_event_loop_quit() {
+    i_slint_backend_selector::backend().quit_event_loop()
+}
+
+#[test]
+fn test_compile_and_use_properties() {
+    i_slint_backend_testing::init();
+    let source = CString::new(
+        r#"
+    export Counter := Rectangle {
+        property <int> counter: 1;
+        property <{name: string, tint: color}> info: { name: "count", tint: #ff000080 };
+        callback add(int) -> int;
+        add(step) => { counter += step; counter }
+    }"#,
+    )
+    .unwrap();
+    unsafe {
+        let mut compiler = slint_compiler_new();
+        let definition =
+            slint_compiler_build_from_source(&mut compiler, source.as_ptr(), std::ptr::null())
+                .unwrap();
+        assert_eq!(slint_compiler_diagnostic_count(&compiler), 0);
+        assert_eq!(slint_component_definition_property_count(&definition), 2);
+        assert_eq!(slint_component_definition_callback_count(&definition), 1);
+        let instance = slint_component_definition_create(&definition);
+
+        let counter = CString::new("counter").unwrap();
+        let value = slint_value_new_number(40.);
+        assert!(slint_component_instance_set_property(&instance, counter.as_ptr(), &value));
+
+        let add = CString::new("add").unwrap();
+        let step = slint_value_new_number(2.);
+        let args = [&*step as *const SlintValue];
+        let result =
+            slint_component_instance_invoke_callback(&instance, add.as_ptr(), args.as_ptr(), 1)
+                .unwrap();
+        let mut number = 0.;
+        assert!(slint_value_to_number(&result, &mut number));
+        assert_eq!(number, 42.);
+
+        let info = CString::new("info").unwrap();
+        let info = slint_component_instance_get_property(&instance, info.as_ptr()).unwrap();
+        let info = slint_value_to_struct(&info).unwrap();
+        assert_eq!(slint_struct_field_count(&info), 2);
+        let name = slint_struct_field_name(&info, 0);
+        assert_eq!(CStr::from_ptr(name).to_str(), Ok("name"));
+        let tint = CString::new("tint").unwrap();
+        let tint = slint_struct_get_field(&info, tint.as_ptr()).unwrap();
+        let mut argb = 0;
+        assert!(slint_value_to_color(&tint, &mut argb));
+        assert_eq!(argb, 0x80ff0000);
+        slint_string_free(name);
+
+        let unknown = CString::new("unknown").unwrap();
+        assert!(slint_component_instance_get_property(&instance, unknown.as_ptr()).is_none());
+        assert!(!slint_component_instance_set_property(&instance, unknown.as_ptr(), &value));
+    }
+}
+
+#[test]
+fn test_callback_handler_and_diagnostics() {
+    i_slint_backend_testing::init();
+    extern "C" fn double(
+        user_data: *mut c_void,
+        args: *const *const SlintValue,
+        arg_count: usize,
+    ) -> *mut SlintValue {
+        let calls = unsafe { &*(user_data as *const std::cell::Cell<u32>) };
+        calls.set(calls.get() + 1);
+        assert_eq!(arg_count, 1);
+        let mut number = 0.;
+        assert!(slint_value_to_number(unsafe { &**args }, &mut number));
+        Box::into_raw(slint_value_new_number(number * 2.))
+    }
+
+    let source =
+        CString::new("export Doubler := Rectangle { callback double(int) -> int; }").unwrap();
+    let error_source = CString::new("export Broken := Rectangle { foo: 42; }").unwrap();
+    unsafe {
+        let mut compiler = slint_compiler_new();
+        assert!(slint_compiler_build_from_source(
+            &mut compiler,
+            error_source.as_ptr(),
+            std::ptr::null()
+        )
+        .is_none());
+        assert_eq!(slint_compiler_diagnostic_count(&compiler), 1);
+        let mut level = SlintDiagnosticLevel::Warning;
+        assert!(slint_compiler_diagnostic_level(&compiler, 0, &mut level));
+        assert_eq!(level, SlintDiagnosticLevel::Error);
+        assert!(!slint_compiler_diagnostic_level(&compiler, 1, &mut level));
+        assert!(slint_compiler_diagnostic_message(&compiler, 1).is_null());
+        let message = slint_compiler_diagnostic_message(&compiler, 0);
+        assert!(CStr::from_ptr(message).to_str().unwrap().contains("foo"));
+        slint_string_free(message);
+
+        let definition =
+            slint_compiler_build_from_source(&mut compiler, source.as_ptr(), std::ptr::null())
+                .unwrap();
+        let instance = slint_component_definition_create(&definition);
+        let calls = std::cell::Cell::new(0u32);
+        let name = CString::new("double").unwrap();
+        assert!(slint_component_instance_set_callback(
+            &instance,
+            name.as_ptr(),
+            double,
+            &calls as *const _ as *mut c_void,
+            None
+        ));
+        let arg = slint_value_new_number(21.);
+        let args = [&*arg as *const SlintValue];
+        let result =
+            slint_component_instance_invoke_callback(&instance, name.as_ptr(), args.as_ptr(), 1)
+                .unwrap();
+        assert!(slint_value_eq(&result, &slint_value_new_number(42.)));
+        assert_eq!(calls.get(), 1);
+    }
+}
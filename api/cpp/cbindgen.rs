@@ -102,6 +102,7 @@ fn gen_corelib(
         "BoxShadow",
         "Rotate",
         "Opacity",
+        "Accessible",
     ];
 
     config.export.include = [
@@ -122,7 +123,9 @@ fn gen_corelib(
         "DialogButtonRole",
         "PointerEventKind",
         "PointerEventButton",
+        "LogicalKey",
         "PointerEvent",
+        "AccessibleRole",
     ]
     .iter()
     .chain(items.iter())
@@ -260,12 +263,21 @@ fn gen_corelib(
             "slint_windowrc_set_focus_item",
             "slint_windowrc_set_component",
             "slint_windowrc_show_popup",
+            "slint_windowrc_close_popup",
+            "slint_animation_tick",
+            "slint_high_contrast",
+            "slint_reduced_motion",
             "slint_windowrc_set_rendering_notifier",
             "slint_windowrc_request_redraw",
+            "slint_windowrc_focus_next_item",
+            "slint_windowrc_focus_previous_item",
             "slint_new_path_elements",
             "slint_new_path_events",
             "slint_color_brighter",
             "slint_color_darker",
+            "slint_color_mix",
+            "slint_color_with_alpha",
+            "slint_color_from_hsva",
             "slint_image_size",
             "slint_image_path",
         ]
@@ -373,12 +385,22 @@ fn gen_corelib(
         "StandardListViewItem".to_owned(),
         "friend bool operator==(const StandardListViewItem&, const StandardListViewItem&) = default;".into(),
     );
+    config.export.body.insert(
+        "Date".to_owned(),
+        "friend bool operator==(const Date&, const Date&) = default;".into(),
+    );
+    config.export.body.insert(
+        "Time".to_owned(),
+        "friend bool operator==(const Time&, const Time&) = default;".into(),
+    );
     config
         .export
         .body
         .insert("Flickable".to_owned(), "    inline Flickable(); inline ~Flickable();".into());
     config.export.pre_body.insert("FlickableDataBox".to_owned(), "struct FlickableData;".into());
     config.export.include.push("StandardListViewItem".into());
+    config.export.include.push("Date".into());
+    config.export.include.push("Time".into());
     cbindgen::Builder::new()
         .with_config(config)
         .with_src(crate_dir.join("lib.rs"))
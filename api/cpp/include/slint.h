@@ -9,6 +9,7 @@
 #endif
 
 #include <vector>
+#include <map>
 #include <memory>
 #include <algorithm>
 #include <iostream> // FIXME: remove: iostream always bring it lots of code so we should not have it in this header
@@ -28,6 +29,7 @@ struct ItemVTable;
 #include "slint_backend_internal.h"
 #include "slint_qt_internal.h"
 #include "slint_selector_internal.h"
+#include "slint_date_time.h"
 
 /// \rst
 /// The :code:`slint` namespace is the primary entry point into the Slint C++ API.
@@ -56,6 +58,8 @@ using cbindgen_private::KeyboardModifiers;
 using cbindgen_private::KeyEvent;
 using cbindgen_private::PointerEvent;
 using cbindgen_private::StandardListViewItem;
+using cbindgen_private::Date;
+using cbindgen_private::Time;
 
 /// Internal function that checks that the API that must be called from the main
 /// thread is indeed called from the main thread, or abort the program otherwise
@@ -107,6 +111,14 @@ public:
     float scale_factor() const { return slint_windowrc_get_scale_factor(&inner); }
     void set_scale_factor(float value) const { slint_windowrc_set_scale_factor(&inner, value); }
 
+    float base_font_size() const { return slint_windowrc_get_base_font_size(&inner); }
+    void set_base_font_size(float value) const
+    {
+        slint_windowrc_set_base_font_size(&inner, value);
+    }
+    float viewport_width() const { return slint_windowrc_get_viewport_width(&inner); }
+    float viewport_height() const { return slint_windowrc_get_viewport_height(&inner); }
+
     template<typename Component, typename ItemTree>
     void free_graphics_resources(Component *c, ItemTree items) const
     {
@@ -142,6 +154,8 @@ public:
         cbindgen_private::slint_windowrc_show_popup(&inner, &popup, p, &parent_item);
     }
 
+    void close_popup() const { cbindgen_private::slint_windowrc_close_popup(&inner); }
+
     template<typename F>
     std::optional<SetRenderingNotifierError> set_rendering_notifier(F callback) const
     {
@@ -161,6 +175,12 @@ public:
 
     void request_redraw() const { cbindgen_private::slint_windowrc_request_redraw(&inner); }
 
+    void focus_next_item() const { cbindgen_private::slint_windowrc_focus_next_item(&inner); }
+    void focus_previous_item() const
+    {
+        cbindgen_private::slint_windowrc_focus_previous_item(&inner);
+    }
+
 private:
     cbindgen_private::WindowRcOpaque inner;
 };
@@ -347,6 +367,19 @@ public:
     /// This function issues a request to the windowing system to redraw the contents of the window.
     void request_redraw() const { inner.request_redraw(); }
 
+    /// Returns the size of the `rem` unit of the `.slint` language, in logical pixels.
+    float base_font_size() const { return inner.base_font_size(); }
+    /// Sets the size of the `rem` unit of the `.slint` language, in logical pixels. The default
+    /// is 16. The lengths expressed in `rem` are updated accordingly.
+    void set_base_font_size(float size) const { inner.set_base_font_size(size); }
+
+    /// Moves the keyboard focus to the next item of the focus chain, like pressing the Tab key.
+    /// Elements with a positive `tab-index` come first, elements with a negative `tab-index`
+    /// are skipped.
+    void focus_next_item() const { inner.focus_next_item(); }
+    /// Moves the keyboard focus to the previous item of the focus chain, like pressing Shift+Tab.
+    void focus_previous_item() const { inner.focus_previous_item(); }
+
     /// \private
     private_api::WindowRc &window_handle() { return inner; }
     /// \private
@@ -508,6 +541,16 @@ auto access_array_index(const M &model, int index)
     }
 }
 
+template<typename V>
+V access_map_value(const std::map<SharedString, V> &map, const SharedString &key)
+{
+    if (auto it = map.find(key); it != map.end()) {
+        return it->second;
+    } else {
+        return V {};
+    }
+}
+
 } // namespace private_api
 
 /// \rst
@@ -707,6 +750,62 @@ public:
 
 namespace private_api {
 
+/// Splits \a str at each occurrence of \a separator and returns the parts in a model.
+/// An empty separator does not split the string.
+inline std::shared_ptr<VectorModel<SharedString>> string_split(const SharedString &str,
+                                                               const SharedString &separator)
+{
+    std::string_view view = str;
+    std::string_view sep = separator;
+    std::vector<SharedString> parts;
+    if (sep.empty()) {
+        parts.push_back(str);
+    } else {
+        std::size_t pos;
+        while ((pos = view.find(sep)) != std::string_view::npos) {
+            parts.push_back(view.substr(0, pos));
+            view.remove_prefix(pos + sep.size());
+        }
+        parts.push_back(view);
+    }
+    return std::make_shared<VectorModel<SharedString>>(std::move(parts));
+}
+
+/// Returns the index of the first row of \a model, starting at the row \a from and wrapping
+/// around, whose text starts with \a prefix, ignoring the case. \a text returns the text of a row.
+/// Returns -1 if no row matches or if the prefix is empty.
+template<typename M, typename F>
+inline int find_row_starting_with(const M &model, const SharedString &prefix, int from, F text)
+{
+    (*model).track_row_count_changes();
+    int row_count = (*model).row_count();
+    auto lower_prefix = prefix.to_lowercase();
+    if (row_count == 0 || std::string_view(lower_prefix).empty()) {
+        return -1;
+    }
+    from = std::max(from, 0) % row_count;
+    for (int i = 0; i < row_count; ++i) {
+        int row = (from + i) % row_count;
+        if (auto data = (*model).row_data(row)) {
+            if (SharedString(text(*data)).to_lowercase().starts_with(lower_prefix)) {
+                return row;
+            }
+        }
+    }
+    return -1;
+}
+
+/// Returns a model with one row for each entry of \a map, in the order of the keys.
+template<typename V>
+auto map_to_model(const std::map<SharedString, V> &map)
+{
+    std::vector<std::tuple<SharedString, V>> entries;
+    for (const auto &[key, value] : map) {
+        entries.emplace_back(key, value);
+    }
+    return std::make_shared<VectorModel<std::tuple<SharedString, V>>>(std::move(entries));
+}
+
 template<typename C, typename ModelData>
 class Repeater
 {
@@ -115,6 +115,11 @@ public:
         return Color::from_argb_float(1.0, red, green, blue);
     }
 
+    /// Construct a color from the hue in degrees, and the saturation, value (brightness) and
+    /// alpha channel in the range 0..1, in the HSV color space. The hue wraps around, and the
+    /// other parameters are clamped.
+    inline static Color from_hsva(float hue, float saturation, float value, float alpha);
+
     /// Converts this color to an RgbaColor struct for easy destructuring.
     inline RgbaColor<uint8_t> to_argb_uint() const;
 
@@ -146,6 +151,12 @@ public:
     /// result is converted back to RGB and the alpha channel is unchanged.
     /// So for example `darker(0.3)` will decrease the brightness by 30%.
     inline Color darker(float factor) const;
+    /// Returns a mix of this color and \a other. The factor is clamped to the range 0..1 and is
+    /// the proportion of this color, while `1 - factor` is the proportion of \a other.
+    inline Color mix(const Color &other, float factor) const;
+    /// Returns this color with the alpha channel replaced by \a alpha, which is clamped to the
+    /// range 0..1.
+    inline Color with_alpha(float alpha) const;
 
     /// Returns true if \a lhs has the same values for the individual color channels as \a rhs;
     /// false otherwise.
@@ -186,6 +197,27 @@ inline Color Color::darker(float factor) const
     return result;
 }
 
+inline Color Color::mix(const Color &other, float factor) const
+{
+    Color result;
+    cbindgen_private::types::slint_color_mix(&inner, &other.inner, factor, &result.inner);
+    return result;
+}
+
+inline Color Color::with_alpha(float alpha) const
+{
+    Color result;
+    cbindgen_private::types::slint_color_with_alpha(&inner, alpha, &result.inner);
+    return result;
+}
+
+inline Color Color::from_hsva(float hue, float saturation, float value, float alpha)
+{
+    Color result;
+    cbindgen_private::types::slint_color_from_hsva(hue, saturation, value, alpha, &result.inner);
+    return result;
+}
+
 /// Constructs a new RgbaColor<uint8_t> from the color \a color.
 template<>
 inline RgbaColor<uint8_t>::RgbaColor(const Color &color)
@@ -221,10 +253,12 @@ namespace private_api {
 template<>
 inline void
 Property<Color>::set_animated_value(const Color &new_value,
-                                    const cbindgen_private::PropertyAnimation &animation_data) const
+                                    const cbindgen_private::PropertyAnimation &animation_data,
+                                    const AnimationFinishedCallback &finished) const
 {
-    cbindgen_private::slint_property_set_animated_value_color(&inner, value, new_value,
-                                                              &animation_data);
+    cbindgen_private::slint_property_set_animated_value_color(
+            &inner, value, new_value, &animation_data, finished.finished, finished.user_data,
+            finished.drop_user_data);
 }
 
 } // namespace private_api
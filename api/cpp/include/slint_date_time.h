@@ -0,0 +1,73 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+#pragma once
+
+#include <chrono>
+#include <compare>
+#include <cstdint>
+
+namespace slint {
+
+/// A point in time with the precision of a millisecond, such as the value of a `date-time`
+/// property. It is stored as the number of milliseconds since the Unix epoch, in UTC, and a
+/// default constructed DateTime is the epoch itself.
+///
+/// The `duration` values, which are milliseconds, can be added to it and subtracted from it.
+struct DateTime
+{
+    /// The number of milliseconds since the Unix epoch, which is negative for the date-times
+    /// before the epoch.
+    std::int64_t millis_since_epoch = 0;
+
+    /// Creates a date-time from a number of milliseconds since the Unix epoch.
+    static constexpr DateTime from_millis_since_epoch(std::int64_t millis)
+    {
+        return DateTime { millis };
+    }
+
+    /// Creates a date-time from a time point of the system clock.
+    template<typename Duration>
+    static DateTime
+    from_time_point(std::chrono::time_point<std::chrono::system_clock, Duration> time_point)
+    {
+        return from_millis_since_epoch(
+                std::chrono::duration_cast<std::chrono::milliseconds>(
+                        time_point.time_since_epoch())
+                        .count());
+    }
+
+    /// Returns the current date-time of the system clock.
+    static DateTime now() { return from_time_point(std::chrono::system_clock::now()); }
+
+    /// Returns the date-time as a time point of the system clock.
+    std::chrono::time_point<std::chrono::system_clock, std::chrono::milliseconds>
+    to_time_point() const
+    {
+        return std::chrono::time_point<std::chrono::system_clock, std::chrono::milliseconds>(
+                std::chrono::milliseconds(millis_since_epoch));
+    }
+
+    /// Compares this date-time with \a other.
+    auto operator<=>(const DateTime &other) const = default;
+
+    /// Returns the date-time \a millis milliseconds after \a date_time.
+    friend constexpr DateTime operator+(DateTime date_time, std::int64_t millis)
+    {
+        return from_millis_since_epoch(date_time.millis_since_epoch + millis);
+    }
+
+    /// Returns the date-time \a millis milliseconds before \a date_time.
+    friend constexpr DateTime operator-(DateTime date_time, std::int64_t millis)
+    {
+        return from_millis_since_epoch(date_time.millis_since_epoch - millis);
+    }
+
+    /// Returns the number of milliseconds from \a rhs to \a lhs.
+    friend constexpr std::int64_t operator-(DateTime lhs, DateTime rhs)
+    {
+        return lhs.millis_since_epoch - rhs.millis_since_epoch;
+    }
+};
+
+}
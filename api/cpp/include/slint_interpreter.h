@@ -645,6 +645,36 @@ public:
         }
     }
 
+    /// Call a function declared in .slint with the given name and arguments.
+    ///
+    /// Example: imagine the .slint file contains the given function declaration:
+    /// ```
+    ///     function foo(a: string, b: int) -> string { ... }
+    /// ```
+    /// Then one can call it with this function
+    /// ```
+    ///     slint::Value args[] = { SharedString("Hello"), 42. };
+    ///     instance->invoke("foo", { args, 2 });
+    /// ```
+    ///
+    /// Returns an null optional if the function doesn't exist or if the arguments don't match.
+    /// Otherwise return the value returned by the function, which may be an empty Value if
+    /// the function does not return a value.
+    std::optional<Value> invoke(std::string_view name, std::span<const Value> args) const
+    {
+        using namespace cbindgen_private;
+        Slice<ValueOpaque> args_view { const_cast<ValueOpaque *>(
+                                               reinterpret_cast<const ValueOpaque *>(args.data())),
+                                       args.size() };
+        ValueOpaque out;
+        if (slint_interpreter_component_instance_invoke(
+                    inner(), slint::private_api::string_to_slice(name), args_view, &out)) {
+            return Value(out);
+        } else {
+            return {};
+        }
+    }
+
     /// Set a handler for the callback with the given name.
     ///
     /// A callback with that name must be defined in the document otherwise the function
@@ -845,6 +875,15 @@ public:
         return callbacks;
     }
 
+    /// Returns a vector of strings that describe the list of functions that can be invoked
+    /// using ComponentInstance::invoke.
+    slint::SharedVector<slint::SharedString> functions() const
+    {
+        slint::SharedVector<slint::SharedString> functions;
+        cbindgen_private::slint_interpreter_component_definition_functions(&inner, &functions);
+        return functions;
+    }
+
     /// Returns the name of this Component as written in the .slint file
     slint::SharedString name() const
     {
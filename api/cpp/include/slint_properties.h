@@ -15,44 +15,68 @@ namespace slint::private_api {
 
 using cbindgen_private::StateInfo;
 
+/// The function called once an animation is finished, with its user data.
+/// A default constructed AnimationFinishedCallback does nothing.
+struct AnimationFinishedCallback
+{
+    void (*finished)(void *) = nullptr;
+    void *user_data = nullptr;
+    void (*drop_user_data)(void *) = nullptr;
+
+    template<typename F>
+    static AnimationFinishedCallback from(F finished)
+    {
+        return { [](void *user_data) { (*reinterpret_cast<F *>(user_data))(); }, new F(finished),
+                 [](void *user_data) { delete reinterpret_cast<F *>(user_data); } };
+    }
+};
+
 inline void slint_property_set_animated_binding_helper(
         const cbindgen_private::PropertyHandleOpaque *handle, void (*binding)(void *, int32_t *),
         void *user_data, void (*drop_user_data)(void *),
         const cbindgen_private::PropertyAnimation *animation_data,
-        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *))
+        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *),
+        const AnimationFinishedCallback &finished)
 {
     cbindgen_private::slint_property_set_animated_binding_int(
-            handle, binding, user_data, drop_user_data, animation_data, transition_data);
+            handle, binding, user_data, drop_user_data, animation_data, transition_data,
+            finished.finished, finished.user_data, finished.drop_user_data);
 }
 
 inline void slint_property_set_animated_binding_helper(
         const cbindgen_private::PropertyHandleOpaque *handle, void (*binding)(void *, float *),
         void *user_data, void (*drop_user_data)(void *),
         const cbindgen_private::PropertyAnimation *animation_data,
-        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *))
+        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *),
+        const AnimationFinishedCallback &finished)
 {
     cbindgen_private::slint_property_set_animated_binding_float(
-            handle, binding, user_data, drop_user_data, animation_data, transition_data);
+            handle, binding, user_data, drop_user_data, animation_data, transition_data,
+            finished.finished, finished.user_data, finished.drop_user_data);
 }
 
 inline void slint_property_set_animated_binding_helper(
         const cbindgen_private::PropertyHandleOpaque *handle, void (*binding)(void *, Color *),
         void *user_data, void (*drop_user_data)(void *),
         const cbindgen_private::PropertyAnimation *animation_data,
-        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *))
+        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *),
+        const AnimationFinishedCallback &finished)
 {
     cbindgen_private::slint_property_set_animated_binding_color(
-            handle, binding, user_data, drop_user_data, animation_data, transition_data);
+            handle, binding, user_data, drop_user_data, animation_data, transition_data,
+            finished.finished, finished.user_data, finished.drop_user_data);
 }
 
 inline void slint_property_set_animated_binding_helper(
         const cbindgen_private::PropertyHandleOpaque *handle, void (*binding)(void *, Brush *),
         void *user_data, void (*drop_user_data)(void *),
         const cbindgen_private::PropertyAnimation *animation_data,
-        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *))
+        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *),
+        const AnimationFinishedCallback &finished)
 {
     cbindgen_private::slint_property_set_animated_binding_brush(
-            handle, binding, user_data, drop_user_data, animation_data, transition_data);
+            handle, binding, user_data, drop_user_data, animation_data, transition_data,
+            finished.finished, finished.user_data, finished.drop_user_data);
 }
 
 template<typename T>
@@ -100,10 +124,12 @@ struct Property
     }
 
     inline void set_animated_value(const T &value,
-                                   const cbindgen_private::PropertyAnimation &animation_data) const;
+                                   const cbindgen_private::PropertyAnimation &animation_data,
+                                   const AnimationFinishedCallback &finished = {}) const;
     template<typename F>
-    inline void
-    set_animated_binding(F binding, const cbindgen_private::PropertyAnimation &animation_data) const
+    inline void set_animated_binding(F binding,
+                                     const cbindgen_private::PropertyAnimation &animation_data,
+                                     const AnimationFinishedCallback &finished = {}) const
     {
         private_api::slint_property_set_animated_binding_helper(
                 &inner,
@@ -111,11 +137,12 @@ struct Property
                     *reinterpret_cast<T *>(value) = (*reinterpret_cast<F *>(user_data))();
                 },
                 new F(binding), [](void *user_data) { delete reinterpret_cast<F *>(user_data); },
-                &animation_data, nullptr);
+                &animation_data, nullptr, finished);
     }
 
     template<typename F, typename Trans>
-    inline void set_animated_binding_for_transition(F binding, Trans animation) const
+    inline void set_animated_binding_for_transition(
+            F binding, Trans animation, const AnimationFinishedCallback &finished = {}) const
     {
         struct UserData
         {
@@ -132,7 +159,8 @@ struct Property
                 [](void *user_data) { delete reinterpret_cast<UserData *>(user_data); }, nullptr,
                 [](void *user_data, uint64_t *instant) {
                     return reinterpret_cast<UserData *>(user_data)->animation(instant);
-                });
+                },
+                finished);
     }
 
     bool is_dirty() const { return cbindgen_private::slint_property_is_dirty(&inner); }
@@ -188,19 +216,23 @@ private:
 
 template<>
 inline void Property<int32_t>::set_animated_value(
-        const int32_t &new_value, const cbindgen_private::PropertyAnimation &animation_data) const
+        const int32_t &new_value, const cbindgen_private::PropertyAnimation &animation_data,
+        const AnimationFinishedCallback &finished) const
 {
-    cbindgen_private::slint_property_set_animated_value_int(&inner, value, new_value,
-                                                            &animation_data);
+    cbindgen_private::slint_property_set_animated_value_int(
+            &inner, value, new_value, &animation_data, finished.finished, finished.user_data,
+            finished.drop_user_data);
 }
 
 template<>
 inline void
 Property<float>::set_animated_value(const float &new_value,
-                                    const cbindgen_private::PropertyAnimation &animation_data) const
+                                    const cbindgen_private::PropertyAnimation &animation_data,
+                                    const AnimationFinishedCallback &finished) const
 {
-    cbindgen_private::slint_property_set_animated_value_float(&inner, value, new_value,
-                                                              &animation_data);
+    cbindgen_private::slint_property_set_animated_value_float(
+            &inner, value, new_value, &animation_data, finished.finished, finished.user_data,
+            finished.drop_user_data);
 }
 
 template<typename F>
@@ -114,6 +114,53 @@ struct SharedString
                 == 0;
     }
 
+    /// Returns a copy of this string where all the characters are converted to upper case.
+    SharedString to_uppercase() const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_drop(&result);
+        cbindgen_private::slint_shared_string_to_uppercase(&result, this);
+        return result;
+    }
+
+    /// Returns a copy of this string where all the characters are converted to lower case.
+    SharedString to_lowercase() const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_drop(&result);
+        cbindgen_private::slint_shared_string_to_lowercase(&result, this);
+        return result;
+    }
+
+    /// Returns a copy of this string without the leading and trailing whitespace.
+    SharedString trim() const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_drop(&result);
+        cbindgen_private::slint_shared_string_trim(&result, this);
+        return result;
+    }
+
+    /// Returns a copy of this string where all the occurrences of \a from are replaced by \a to.
+    SharedString replace(const SharedString &from, const SharedString &to) const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_drop(&result);
+        cbindgen_private::slint_shared_string_replace(&result, this, &from, &to);
+        return result;
+    }
+
+    /// Returns the characters of this string from index \a start up to, but excluding, index
+    /// \a end. The indexes count UTF-8 encoded characters, not bytes, and they are clamped to
+    /// the length of the string.
+    SharedString slice(int start, int end) const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_drop(&result);
+        cbindgen_private::slint_shared_string_slice(&result, this, start, end);
+        return result;
+    }
+
     /// Creates a new SharedString from the given number \a n. The string representation of the
     /// number uses a minimal formatting scheme: If \a n has no fractional part, the number will be
     /// formatted as an integer.
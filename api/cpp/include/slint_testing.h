@@ -23,6 +23,24 @@ inline void send_mouse_click(const Component *component, float x, float y)
     cbindgen_private::slint_send_mouse_click(&crc, x, y, &component->m_window.window_handle());
 }
 
+template<typename Component>
+inline void send_mouse_press(const Component *component, float x, float y)
+{
+    cbindgen_private::slint_send_mouse_press(x, y, &component->m_window.window_handle());
+}
+
+template<typename Component>
+inline void send_mouse_move(const Component *component, float x, float y)
+{
+    cbindgen_private::slint_send_mouse_move(x, y, &component->m_window.window_handle());
+}
+
+template<typename Component>
+inline void send_mouse_release(const Component *component, float x, float y)
+{
+    cbindgen_private::slint_send_mouse_release(x, y, &component->m_window.window_handle());
+}
+
 template<typename Component>
 inline void send_keyboard_string_sequence(const Component *component,
                                           const slint::SharedString &str,
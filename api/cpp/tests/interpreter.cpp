@@ -390,6 +390,39 @@ SCENARIO("Invoke callback")
     }
 }
 
+SCENARIO("Invoke function")
+{
+    using namespace slint::interpreter;
+    using namespace slint;
+
+    ComponentCompiler compiler;
+    auto result = compiler.build_from_source(
+            "export Dummy := Rectangle { property <int> offset: 100;"
+            "    function add(a: int, b: int) -> int { a + b + offset } }",
+            "");
+    REQUIRE(result.has_value());
+
+    auto function_names = result->functions();
+    REQUIRE(function_names.size() == 1);
+    REQUIRE(function_names[0] == "add");
+
+    auto instance = result->create();
+
+    SECTION("valid")
+    {
+        Value args[] = { 1., 2. };
+        auto res = instance->invoke("add", args);
+        REQUIRE(res.has_value());
+        REQUIRE(*res->to_number() == 103.);
+    }
+
+    SECTION("invalid")
+    {
+        Value args[] = { 1., 2. };
+        REQUIRE(!instance->invoke("sub", args).has_value());
+    }
+}
+
 SCENARIO("Array between .slint and C++")
 {
     using namespace slint::interpreter;
@@ -67,11 +67,49 @@ interface Callback {
     setHandler(cb: any): void;
 }
 
+/**
+ * @hidden
+ */
+function isPromise(value: any): boolean {
+    return value !== null && typeof value === 'object' && typeof value.then === 'function';
+}
+
+/**
+ * Wraps the handler of a callback for the `.slint` code, which cannot wait for a Promise and
+ * gets the default value instead: the rejections of the Promise are reported on the console.
+ * @hidden
+ */
+function handlerForSlint(name: string, handler: Function): Function {
+    return function (...args: any[]) {
+        let result = handler(...args);
+        if (isPromise(result)) {
+            result.then(undefined, (error: any) => {
+                console.error(`The Promise returned by the handler of ${name} was rejected:`, error);
+            });
+        }
+        return result;
+    };
+}
+
 require.extensions['.60'] = require.extensions['.slint'] =
     function (module, filename) {
         var c = native.load(filename);
         module.exports[c.name().replace(/-/g, '_')] = function (init_properties: any) {
-            let comp = c.create(init_properties);
+            let callback_names = c.callbacks().map((x: string) => x.replace(/_/g, '-'));
+            // The JavaScript handlers of the callbacks. When JavaScript emits a callback, its
+            // handler is called directly, so that a Promise it returns is given to the caller.
+            let handlers = new Map<string, Function>();
+            let properties: any = {};
+            for (let key of Object.keys(init_properties ?? {})) {
+                let value = init_properties[key];
+                let name = key.replace(/_/g, '-');
+                if (callback_names.indexOf(name) >= 0 && typeof value === 'function') {
+                    handlers.set(name, value);
+                    value = handlerForSlint(name, value);
+                }
+                properties[key] = value;
+            }
+            let comp = c.create(properties);
             let ret = new Component(comp);
             c.properties().forEach((x: string) => {
                 Object.defineProperty(ret, x.replace(/-/g, '_'), {
@@ -83,13 +121,35 @@ require.extensions['.60'] = require.extensions['.slint'] =
             c.callbacks().forEach((x: string) => {
                 Object.defineProperty(ret, x.replace(/-/g, '_'), {
                     get() {
-                        let callback = function () { return comp.invoke_callback(x, [...arguments]); } as Callback;
-                        callback.setHandler = function (callback) { comp.connect_callback(x, callback) };
+                        let callback = function () {
+                            let handler = handlers.get(x.replace(/_/g, '-'));
+                            if (handler === undefined) {
+                                return comp.invoke_callback(x, [...arguments]);
+                            }
+                            let result = handler(...comp.convert_callback_arguments(x, [...arguments]));
+                            if (isPromise(result)) {
+                                // Resolves to the value converted to the return type of the callback
+                                return result.then((value: any) => comp.convert_callback_result(x, value));
+                            }
+                            return comp.convert_callback_result(x, result);
+                        } as Callback;
+                        callback.setHandler = function (callback) {
+                            handlers.set(x.replace(/_/g, '-'), callback);
+                            comp.connect_callback(x, handlerForSlint(x, callback));
+                        };
                         return callback;
                     },
                     enumerable: true,
                 })
             });
+            c.functions().forEach((x: string) => {
+                Object.defineProperty(ret, x.replace(/-/g, '_'), {
+                    get() {
+                        return function () { return comp.invoke(x, [...arguments]); };
+                    },
+                    enumerable: true,
+                })
+            });
             return ret;
         }
     }
@@ -203,18 +263,57 @@ class ArrayModel<T> implements Model<T> {
         Array.prototype.push.apply(this.a, values);
         this.notify.rowAdded(size, arguments.length);
     }
-    // FIXME: should this be named splice and have the splice api?
+    /**
+     * Inserts new values in the array that's backing the model at the specified
+     * index and notifies the run-time about the added rows.
+     * @param index
+     * @param values
+     */
+    insert(index: number, ...values: T[]) {
+        this.splice(index, 0, ...values);
+    }
     /**
      * Removes the specified number of element from the array that's backing
      * the model, starting at the specified index. This is equivalent to calling
-     * Array.slice() on the array and notifying the run-time about the removed
+     * Array.splice() on the array and notifying the run-time about the removed
      * rows.
      * @param index
      * @param size
      */
     remove(index: number, size: number) {
-        let r = this.a.splice(index, size);
-        this.notify.rowRemoved(index, size);
+        this.splice(index, size);
+    }
+    /**
+     * Removes elements from the array that's backing the model and inserts new ones
+     * in their place, like Array.splice(), and returns the removed elements.
+     *
+     * Only the affected rows are notified to the run-time: the rows that got replaced
+     * are reported as changed, and the remaining ones as added or removed, so that
+     * the repeaters don't re-create the elements of the other rows.
+     * @param start
+     * @param deleteCount
+     * @param values
+     */
+    splice(start: number, deleteCount?: number, ...values: T[]): T[] {
+        // Same as what Array.splice() does with a negative or out of bound start
+        let size = this.a.length;
+        start = start < 0 ? Math.max(size + start, 0) : Math.min(start, size);
+        let removed = deleteCount === undefined
+            ? this.a.splice(start)
+            : this.a.splice(start, deleteCount, ...values);
+        if (deleteCount === undefined) {
+            values = [];
+        }
+        let changed = Math.min(removed.length, values.length);
+        for (let row = start; row < start + changed; ++row) {
+            this.notify.rowDataChanged(row);
+        }
+        if (removed.length > changed) {
+            this.notify.rowRemoved(start + changed, removed.length - changed);
+        } else if (values.length > changed) {
+            this.notify.rowAdded(start + changed, values.length - changed);
+        }
+        return removed;
     }
 
     get length(): number {
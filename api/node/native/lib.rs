@@ -72,6 +72,15 @@ fn load(mut cx: FunctionContext) -> JsResult<JsValue> {
     Ok(obj.as_value(&mut cx))
 }
 
+/// Returns true if the value is a Promise, or any object with a `then` function
+fn is_promise<'cx>(value: Handle<'cx, JsValue>, cx: &mut impl Context<'cx>) -> bool {
+    value
+        .downcast::<JsObject>()
+        .ok()
+        .and_then(|obj| obj.get(cx, "then").ok())
+        .map_or(false, |then| then.is_a::<JsFunction>())
+}
+
 fn make_callback_handler<'cx>(
     cx: &mut impl Context<'cx>,
     persistent_context: &persistent_context::PersistentContext<'cx>,
@@ -97,10 +106,15 @@ fn make_callback_handler<'cx>(
                 .unwrap()
                 .call::<_, _, JsValue, _>(cx, JsUndefined::new(), args)
                 .unwrap();
+            // The .slint code cannot wait for the result of a Promise, so it gets the default
+            // value. The JS code that emits the callback calls the handler directly, and gets
+            // the Promise.
             if let Some(return_type) = return_type {
-                borrow_ret.set(
-                    to_eval_value(ret, (**return_type).clone(), cx, persistent_context).unwrap(),
-                );
+                borrow_ret.set(if is_promise(ret, cx) {
+                    slint_interpreter::default_value_for_type(return_type)
+                } else {
+                    to_eval_value(ret, (**return_type).clone(), cx, persistent_context).unwrap()
+                });
             }
         });
         ret.into_inner()
@@ -170,6 +184,11 @@ fn to_eval_value<'cx>(
         | Type::UnitProduct(_) => {
             Ok(Value::Number(val.downcast_or_throw::<JsNumber, _>(cx)?.value()))
         }
+        Type::DateTime => {
+            Ok(Value::DateTime(slint_interpreter::DateTime::from_millis_since_epoch(
+                val.downcast_or_throw::<JsNumber, _>(cx)?.value() as i64,
+            )))
+        }
         Type::String => Ok(Value::String(val.to_string(cx)?.value().into())),
         Type::Color | Type::Brush => {
             let c = val
@@ -223,7 +242,29 @@ fn to_eval_value<'cx>(
                     .collect::<Result<_, _>>()?,
             ))
         }
-        Type::Enumeration(_) => todo!(),
+        Type::Map(value_ty) => {
+            let obj = val.downcast_or_throw::<JsObject, _>(cx)?;
+            let keys = obj.get_own_property_names(cx)?.to_vec(cx)?;
+            Ok(Value::Map(
+                keys.into_iter()
+                    .map(|key| {
+                        let key = key.to_string(cx)?.value();
+                        let value = obj.get(cx, key.as_str())?;
+                        Ok((
+                            key,
+                            to_eval_value(value, (*value_ty).clone(), cx, persistent_context)?,
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ))
+        }
+        Type::Enumeration(en) => {
+            let value = val.to_string(cx)?.value().replace('_', "-");
+            if !en.values.contains(&value) {
+                return cx.throw_error(format!("{} is not a valid value for {}", value, en.name));
+            }
+            Ok(Value::EnumerationValue(en.name.clone(), value))
+        }
         Type::Invalid
         | Type::Void
         | Type::InferredProperty
@@ -237,6 +278,7 @@ fn to_eval_value<'cx>(
         | Type::Component(_)
         | Type::PathData
         | Type::LayoutCache
+        | Type::ComponentFactory
         | Type::ElementReference => cx.throw_error("Cannot convert to a Slint property value"),
     }
 }
@@ -289,6 +331,16 @@ fn to_js_value<'cx>(
             &format!("#{:02x}{:02x}{:02x}{:02x}", c.red(), c.green(), c.blue(), c.alpha()),
         )
         .as_value(cx),
+        Value::EnumerationValue(_, value) => JsString::new(cx, value.as_str()).as_value(cx),
+        Value::DateTime(d) => JsNumber::new(cx, d.millis_since_epoch() as f64).as_value(cx),
+        Value::Map(m) => {
+            let js_object = JsObject::new(cx);
+            for (k, e) in m.iter() {
+                let v = to_js_value(e.clone(), cx, persistent_context)?;
+                js_object.set(cx, k, v)?;
+            }
+            js_object.as_value(cx)
+        }
         _ => todo!("converting {:?} to js has not been implemented", val),
     })
 }
@@ -334,6 +386,18 @@ fn to_js_value<'cx>(
             }
             Ok(array.as_value(&mut cx))
         }
+        method functions(mut cx) {
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let functions = ct.properties_and_callbacks().filter(|(_, prop_type)| matches!(prop_type, Type::Function{..}));
+            let array = JsArray::new(&mut cx, 0);
+            for (len , (p, _)) in functions.enumerate() {
+                let prop_name = JsString::new(&mut cx, p);
+                array.set(&mut cx, len as u32, prop_name)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
     }
 
     class SlintComponent for WrappedComponentRc {
@@ -432,6 +496,106 @@ fn to_js_value<'cx>(
             to_js_value(res, &mut cx, &persistent_context)
         }
 
+        /// Converts the arguments given to a callback emitted from JavaScript to the types of the
+        /// arguments of the callback, like when the callback is emitted with invoke_callback, so
+        /// that the JavaScript handler can be called directly with them
+        method convert_callback_arguments(mut cx) {
+            let callback_name = cx.argument::<JsString>(0)?.value();
+            let arguments = cx.argument::<JsArray>(1)?.to_vec(&mut cx)?;
+            let this = cx.this();
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let ty = component.definition().properties_and_callbacks()
+                .find_map(|(name, proptype)| if name == callback_name { Some(proptype) } else { None })
+                .ok_or(())
+                .or_else(|()| {
+                    cx.throw_error(format!("Callback {} not found in the component", callback_name))
+                })?;
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            let args = if let Type::Callback {args, ..} = ty {
+                if arguments.len() != args.len() {
+                    cx.throw_error(format!("{} expect {} arguments, but {} where provided", callback_name, args.len(), arguments.len()))?;
+                }
+                args
+            } else {
+                cx.throw_error(format!("{} is not a callback", callback_name))?;
+                unreachable!()
+            };
+            let result = JsArray::new(&mut cx, args.len() as u32);
+            for (i, (a, ty)) in arguments.into_iter().zip(args.into_iter()).enumerate() {
+                let value = to_eval_value(a, ty, &mut cx, &persistent_context)?;
+                let value = to_js_value(value, &mut cx, &persistent_context)?;
+                result.set(&mut cx, i as u32, value)?;
+            }
+            Ok(result.as_value(&mut cx))
+        }
+
+        /// Converts the value that a Promise returned by a callback handler resolved to, to the
+        /// return type of the callback, like the value returned by a handler is converted
+        method convert_callback_result(mut cx) {
+            let callback_name = cx.argument::<JsString>(0)?.value();
+            let value = cx.argument::<JsValue>(1)?;
+            let this = cx.this();
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let ty = component.definition().properties_and_callbacks()
+                .find_map(|(name, proptype)| if name == callback_name { Some(proptype) } else { None })
+                .ok_or(())
+                .or_else(|()| {
+                    cx.throw_error(format!("Callback {} not found in the component", callback_name))
+                })?;
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            match ty {
+                Type::Callback { return_type: Some(return_type), .. } => {
+                    let value = to_eval_value(value, *return_type, &mut cx, &persistent_context)?;
+                    to_js_value(value, &mut cx, &persistent_context)
+                }
+                Type::Callback { return_type: None, .. } => Ok(JsUndefined::new().as_value(&mut cx)),
+                _ => {
+                    cx.throw_error(format!("{} is not a callback", callback_name))?;
+                    unreachable!()
+                }
+            }
+        }
+
+        method invoke(mut cx) {
+            let function_name = cx.argument::<JsString>(0)?.value();
+            let arguments = cx.argument::<JsArray>(1)?.to_vec(&mut cx)?;
+            let this = cx.this();
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let ty = component.definition().properties_and_callbacks()
+                .find_map(|(name, proptype)| if name == function_name { Some(proptype) } else { None })
+                .ok_or(())
+                .or_else(|()| {
+                    cx.throw_error(format!("Function {} not found in the component", function_name))
+                })?;
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            let args = if let Type::Function {args, ..} = ty {
+                let count = args.len();
+                let args = arguments.into_iter()
+                    .zip(args.into_iter())
+                    .map(|(a, ty)| to_eval_value(a, ty, &mut cx, &persistent_context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if args.len() != count {
+                    cx.throw_error(format!("{} expect {} arguments, but {} where provided", function_name, count, args.len()))?;
+                }
+                args
+            } else {
+                cx.throw_error(format!("{} is not a function", function_name))?;
+                unreachable!()
+            };
+
+            let res = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.invoke(function_name.as_str(), args.as_slice())
+                    .map_err(|_| "Cannot call function".to_string())
+            })?;
+            to_js_value(res, &mut cx, &persistent_context)
+        }
+
         method connect_callback(mut cx) {
             let callback_name = cx.argument::<JsString>(0)?.value();
             let handler = cx.argument::<JsFunction>(1)?;
@@ -472,6 +636,45 @@ fn to_js_value<'cx>(
             Ok(JsUndefined::new().as_value(&mut cx))
         }
 
+        method send_mouse_press(mut cx) {
+            let x = cx.argument::<JsNumber>(0)?.value() as f32;
+            let y = cx.argument::<JsNumber>(1)?.value() as f32;
+            let this = cx.this();
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                slint_interpreter::testing::send_mouse_press(&component, x, y);
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
+        method send_mouse_move(mut cx) {
+            let x = cx.argument::<JsNumber>(0)?.value() as f32;
+            let y = cx.argument::<JsNumber>(1)?.value() as f32;
+            let this = cx.this();
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                slint_interpreter::testing::send_mouse_move(&component, x, y);
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
+        method send_mouse_release(mut cx) {
+            let x = cx.argument::<JsNumber>(0)?.value() as f32;
+            let y = cx.argument::<JsNumber>(1)?.value() as f32;
+            let this = cx.this();
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                slint_interpreter::testing::send_mouse_release(&component, x, y);
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
         method send_keyboard_string_sequence(mut cx) {
             let sequence = cx.argument::<JsString>(0)?.value();
             let this = cx.this();
@@ -52,13 +52,19 @@ fn main() {
 
 use std::env;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use i_slint_compiler::diagnostics::BuildDiagnostics;
 
+pub use i_slint_compiler::DefineValue;
+
 /// The structure for configuring aspects of the compilation of `.slint` markup files to Rust.
 pub struct CompilerConfiguration {
     config: i_slint_compiler::CompilerConfiguration,
+    /// Set by `with_embed_resources`, so that the resources are not embedded anyway when
+    /// cross compiling
+    embed_resources_set: bool,
+    dependency_file: Option<PathBuf>,
 }
 
 impl Default for CompilerConfiguration {
@@ -67,6 +73,8 @@ fn default() -> Self {
             config: i_slint_compiler::CompilerConfiguration::new(
                 i_slint_compiler::generator::OutputFormat::Rust,
             ),
+            embed_resources_set: false,
+            dependency_file: None,
         }
     }
 }
@@ -80,18 +88,76 @@ pub fn new() -> Self {
     /// Create a new configuration that includes sets the include paths used for looking up
     /// `.slint` imports to the specified vector of paths.
     #[must_use]
-    pub fn with_include_paths(self, include_paths: Vec<std::path::PathBuf>) -> Self {
-        let mut config = self.config;
-        config.include_paths = include_paths;
-        Self { config }
+    pub fn with_include_paths(mut self, include_paths: Vec<std::path::PathBuf>) -> Self {
+        self.config.include_paths = include_paths;
+        self
+    }
+
+    /// Create a new configuration that sets the paths of the libraries, that the `.slint` files
+    /// import with `@name/path/to/file.slint`.
+    #[must_use]
+    pub fn with_library_paths(
+        mut self,
+        library_paths: std::collections::HashMap<String, std::path::PathBuf>,
+    ) -> Self {
+        self.config.library_paths = library_paths;
+        self
     }
 
     /// Create a new configuration that selects the style to be used for widgets.
     #[must_use]
-    pub fn with_style(self, style: String) -> Self {
-        let mut config = self.config;
-        config.style = Some(style);
-        Self { config }
+    pub fn with_style(mut self, style: String) -> Self {
+        self.config.style = Some(style);
+        self
+    }
+
+    /// Create a new configuration that sets the flags which enable the matching
+    /// `@if (flag) { ... }` blocks in the `.slint` files.
+    #[must_use]
+    pub fn with_conditional_flags(mut self, flags: Vec<String>) -> Self {
+        self.config.conditional_flags = flags;
+        self
+    }
+
+    /// Create a new configuration that makes the given constants available to the expressions
+    /// in the `.slint` files, as `Defines.name`.
+    ///
+    /// ```
+    /// use slint_build::DefineValue;
+    /// let mut defines = std::collections::HashMap::new();
+    /// defines.insert("brand-name".to_string(), DefineValue::from("ACME"));
+    /// defines.insert("max-items".to_string(), DefineValue::Number(12.));
+    /// let config = slint_build::CompilerConfiguration::new().with_defines(defines);
+    /// ```
+    #[must_use]
+    pub fn with_defines(mut self, defines: std::collections::HashMap<String, DefineValue>) -> Self {
+        self.config.defines = defines;
+        self
+    }
+
+    /// Create a new configuration that sets whether the images and fonts used by the `.slint`
+    /// files are embedded in the generated code, instead of being loaded from their path at
+    /// runtime.
+    ///
+    /// By default, they are embedded when cross compiling, or when the `SLINT_EMBED_RESOURCES`
+    /// environment variable is `true`.
+    #[must_use]
+    pub fn with_embed_resources(mut self, embed_resources: bool) -> Self {
+        self.config.embed_resources = embed_resources;
+        self.embed_resources_set = true;
+        self
+    }
+
+    /// Create a new configuration that writes a dependency file at the given path, in the
+    /// format of make, with the generated file as target and the `.slint` files, images, and
+    /// fonts it was generated from as dependencies. This is the same list of files as the one
+    /// printed in the `cargo:rerun-if-changed` lines, for the other build systems.
+    ///
+    /// A relative path is relative to the `OUT_DIR`.
+    #[must_use]
+    pub fn with_dependency_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dependency_file = Some(path.into());
+        self
     }
 }
 
@@ -200,7 +266,7 @@ pub fn compile_with_config(
     let mut compiler_config = config.config;
 
     if let (Ok(target), Ok(host)) = (env::var("TARGET"), env::var("HOST")) {
-        if target != host {
+        if target != host && !config.embed_resources_set {
             compiler_config.embed_resources = true;
         }
     };
@@ -234,22 +300,33 @@ pub fn compile_with_config(
         return Err(CompileError::CompileError(vec));
     }
 
-    let output_file_path = Path::new(&env::var_os("OUT_DIR").ok_or(CompileError::NotRunViaCargo)?)
-        .join(
-            path.file_stem()
-                .map(Path::new)
-                .unwrap_or_else(|| Path::new("slint_out"))
-                .with_extension("rs"),
-        );
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").ok_or(CompileError::NotRunViaCargo)?);
+    let output_file_path = out_dir.join(
+        path.file_stem()
+            .map(Path::new)
+            .unwrap_or_else(|| Path::new("slint_out"))
+            .with_extension("rs"),
+    );
 
     let file = std::fs::File::create(&output_file_path).map_err(CompileError::SaveError)?;
     let mut code_formatter = CodeFormatter { indentation: 0, in_string: false, sink: file };
     let generated = i_slint_compiler::generator::rust::generate(&doc);
 
-    for x in &diag.all_loaded_files {
-        if x.is_absolute() {
-            println!("cargo:rerun-if-changed={}", x.display());
+    let dependencies = doc.root_component.dependencies.borrow();
+    let dependencies = dependencies.iter().filter(|x| x.is_absolute());
+    for x in dependencies.clone() {
+        println!("cargo:rerun-if-changed={}", x.display());
+    }
+
+    if let Some(dependency_file) = config.dependency_file {
+        let mut file = std::fs::File::create(out_dir.join(dependency_file))
+            .map_err(CompileError::SaveError)?;
+        write!(file, "{}: {}", output_file_path.display(), path.display())
+            .map_err(CompileError::SaveError)?;
+        for x in dependencies {
+            write!(file, " {}", x.display()).map_err(CompileError::SaveError)?;
         }
+        writeln!(file).map_err(CompileError::SaveError)?;
     }
 
     // print warnings
@@ -261,18 +338,13 @@ pub fn compile_with_config(
 
     write!(code_formatter, "{}", generated).map_err(CompileError::SaveError)?;
     println!("{}\ncargo:rerun-if-changed={}", rerun_if_changed, path.display());
-
-    for resource in doc.root_component.embedded_file_resources.borrow().keys() {
-        if !resource.starts_with("builtin:") {
-            println!("cargo:rerun-if-changed={}", resource);
-        }
-    }
     println!("cargo:rerun-if-env-changed=SLINT_STYLE");
     println!("cargo:rerun-if-env-changed=SIXTYFPS_STYLE");
     println!("cargo:rerun-if-env-changed=SLINT_EMBED_GLYPHS");
     println!("cargo:rerun-if-env-changed=SLINT_FONT_SIZES");
     println!("cargo:rerun-if-env-changed=SLINT_PROCESS_IMAGES");
     println!("cargo:rerun-if-env-changed=SLINT_SCALE_FACTOR");
+    println!("cargo:rerun-if-env-changed=SLINT_EMBED_RESOURCES");
 
     println!("cargo:rustc-env=SLINT_INCLUDE_GENERATED={}", output_file_path.display());
 
@@ -15,6 +15,8 @@
 use proc_macro::{Spacing, TokenStream, TokenTree};
 use quote::quote;
 
+mod property_binding;
+
 /// Returns true if the two token are touching. For example the two token `foo`and `-` are touching if
 /// it was written like so in the source code: `foo-` but not when written like so `foo -`
 fn are_token_touching(token1: proc_macro::Span, token2: proc_macro::Span) -> bool {
@@ -387,3 +389,47 @@ pub fn slint(stream: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+/// Derives the functions that copy the fields of a struct to the properties of a component
+/// generated by the [`slint!`] macro, and back.
+///
+/// The component is specified with `#[slint(component = ComponentName)]`. Each field is bound to
+/// the property with the same name, or with the name given by `#[slint(name = "property-name")]`.
+/// The fields marked with `#[slint(skip)]` are not bound. The type of the fields must be
+/// convertible to and from the type of the properties with [`Into`].
+///
+/// The derive implements these functions on the struct:
+///  * `fn apply_to(&self, component: &ComponentName)` sets the properties to the values of the fields.
+///  * `fn read_from(component: &ComponentName) -> Self` returns the values of the properties. The
+///    skipped fields have their default value.
+///  * `fn watch(component: &ComponentName, changed: impl Fn(Self) + 'static) -> slint::PropertyWatcher`
+///    calls `changed` when the properties change, until the returned watcher is dropped.
+///
+/// ```rust,no_run
+/// slint::slint!{
+///     Preferences := Window {
+///         property <string> user-name;
+///         property <bool> dark-mode;
+///     }
+/// }
+///
+/// #[derive(slint::PropertyBinding)]
+/// #[slint(component = Preferences)]
+/// struct Settings {
+///     #[slint(name = "user-name")]
+///     name: slint::SharedString,
+///     dark_mode: bool,
+///     #[slint(skip)]
+///     save_count: u32,
+/// }
+///
+/// let preferences = Preferences::new();
+/// Settings { name: "Alice".into(), dark_mode: true, save_count: 0 }.apply_to(&preferences);
+/// let _watcher = Settings::watch(&preferences, |settings| println!("{}", settings.name));
+/// ```
+#[proc_macro_derive(PropertyBinding, attributes(slint))]
+pub fn derive_property_binding(stream: TokenStream) -> TokenStream {
+    property_binding::derive_property_binding(stream).unwrap_or_else(|(message, span)| {
+        quote::quote_spanned!(span.into()=> ::core::compile_error!{ #message }).into()
+    })
+}
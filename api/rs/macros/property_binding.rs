@@ -0,0 +1,244 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Implementation of the `PropertyBinding` derive macro
+
+// cSpell:ignore punct
+
+use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+
+/// A field of the struct
+struct Field {
+    name: String,
+    span: Span,
+    /// The name of the property, with underscores, or None if the field is skipped
+    property: Option<String>,
+}
+
+/// The content of the `#[slint(...)]` attributes: the keys with their value, if any
+type SlintAttribute = Vec<(String, Span, Option<TokenStream>)>;
+
+/// Parses the `#[...]` attributes at the current position of the iterator, and returns the content
+/// of the `#[slint(...)]` ones
+fn parse_attributes(
+    tokens: &mut std::iter::Peekable<proc_macro::token_stream::IntoIter>,
+) -> Result<SlintAttribute, (String, Span)> {
+    let mut result = SlintAttribute::new();
+    while matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '#') {
+        tokens.next();
+        let group = match tokens.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+            _ => continue,
+        };
+        let mut attr = group.stream().into_iter();
+        match (attr.next(), attr.next()) {
+            (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(args)))
+                if ident.to_string() == "slint" && args.delimiter() == Delimiter::Parenthesis =>
+            {
+                let mut args = args.stream().into_iter().peekable();
+                while let Some(token) = args.next() {
+                    let key = match token {
+                        TokenTree::Ident(key) => key,
+                        TokenTree::Punct(p) if p.as_char() == ',' => continue,
+                        token => return Err(("expected an identifier".into(), token.span())),
+                    };
+                    let value = match args.peek() {
+                        Some(TokenTree::Punct(p)) if p.as_char() == '=' => {
+                            args.next();
+                            let mut value = TokenStream::new();
+                            while let Some(token) = args.next_if(
+                                |t| !matches!(t, TokenTree::Punct(p) if p.as_char() == ','),
+                            ) {
+                                value.extend(std::iter::once(token));
+                            }
+                            Some(value)
+                        }
+                        _ => None,
+                    };
+                    result.push((key.to_string(), key.span(), value));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(result)
+}
+
+/// Skips the `pub`, `pub(crate)`, ... visibility at the current position of the iterator
+fn skip_visibility(tokens: &mut std::iter::Peekable<proc_macro::token_stream::IntoIter>) {
+    if matches!(tokens.peek(), Some(TokenTree::Ident(i)) if i.to_string() == "pub") {
+        tokens.next();
+        tokens.next_if(
+            |t| matches!(t, TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis),
+        );
+    }
+}
+
+fn parse_fields(body: TokenStream) -> Result<Vec<Field>, (String, Span)> {
+    let mut tokens = body.into_iter().peekable();
+    let mut fields = Vec::new();
+    while tokens.peek().is_some() {
+        let attributes = parse_attributes(&mut tokens)?;
+        skip_visibility(&mut tokens);
+        let ident = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(token) => return Err(("expected a field name".into(), token.span())),
+            None => break,
+        };
+        let name = ident.to_string();
+        let mut property = Some(name.trim_start_matches("r#").to_string());
+        for (key, span, value) in attributes {
+            match (key.as_str(), value) {
+                ("skip", None) => property = None,
+                ("name", Some(value)) => {
+                    let value = value.to_string();
+                    let value = value.trim_matches('"');
+                    property = Some(value.replace('-', "_"));
+                }
+                _ => {
+                    return Err((
+                        "expected `skip` or `name = \"...\"` in the slint attribute of a field"
+                            .into(),
+                        span,
+                    ))
+                }
+            }
+        }
+        fields.push(Field { name, span: ident.span(), property });
+
+        // Skip the type, until the comma that is not within the generic arguments of the type
+        let mut angle_depth = 0;
+        let mut previous_punct = ' ';
+        for token in tokens.by_ref() {
+            if let TokenTree::Punct(p) = &token {
+                match p.as_char() {
+                    ',' if angle_depth == 0 => break,
+                    '<' => angle_depth += 1,
+                    // The `>` of `->` doesn't close a generic argument list
+                    '>' if previous_punct != '-' => angle_depth -= 1,
+                    _ => {}
+                }
+                previous_punct = p.as_char();
+            } else {
+                previous_punct = ' ';
+            }
+        }
+    }
+    Ok(fields)
+}
+
+pub fn derive_property_binding(stream: TokenStream) -> Result<TokenStream, (String, Span)> {
+    let mut tokens = stream.into_iter().peekable();
+    let attributes = parse_attributes(&mut tokens)?;
+    skip_visibility(&mut tokens);
+
+    let mut component = None;
+    for (key, span, value) in attributes {
+        match (key.as_str(), value) {
+            ("component", Some(value)) => component = Some(proc_macro2::TokenStream::from(value)),
+            _ => {
+                return Err((
+                    "expected `component = ComponentName` in the slint attribute of the struct"
+                        .into(),
+                    span,
+                ))
+            }
+        }
+    }
+
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => {}
+        token => {
+            return Err((
+                "PropertyBinding can only be derived for structs".into(),
+                token.map_or_else(Span::call_site, |t| t.span()),
+            ))
+        }
+    }
+    let struct_name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident,
+        token => {
+            return Err((
+                "expected the name of the struct".into(),
+                token.map_or_else(Span::call_site, |t| t.span()),
+            ))
+        }
+    };
+    let component = component.ok_or_else(|| {
+        (
+            "the component must be specified with `#[slint(component = ComponentName)]`".into(),
+            struct_name.span(),
+        )
+    })?;
+    let body = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group.stream(),
+        token => {
+            return Err((
+                "PropertyBinding can only be derived for the structs with named fields and without generic parameters".into(),
+                token.map_or_else(Span::call_site, |t| t.span()),
+            ))
+        }
+    };
+    let fields = parse_fields(body)?;
+
+    let struct_name = proc_macro2::Ident::new(&struct_name.to_string(), struct_name.span().into());
+    let field_ident = |f: &Field| -> proc_macro2::TokenStream {
+        f.name
+            .parse::<proc_macro2::TokenStream>()
+            .unwrap()
+            .into_iter()
+            .map(|mut t| {
+                t.set_span(f.span.into());
+                t
+            })
+            .collect()
+    };
+    let bound = fields.iter().filter(|f| f.property.is_some()).collect::<Vec<_>>();
+    let bound_fields = bound.iter().map(|f| field_ident(f)).collect::<Vec<_>>();
+    let setters = bound
+        .iter()
+        .map(|f| format_ident!("set_{}", f.property.as_ref().unwrap(), span = f.span.into()));
+    let getters = bound
+        .iter()
+        .map(|f| format_ident!("get_{}", f.property.as_ref().unwrap(), span = f.span.into()));
+    let skipped_fields =
+        fields.iter().filter(|f| f.property.is_none()).map(field_ident).collect::<Vec<_>>();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #struct_name {
+            /// Sets the properties of the component to the values of the fields
+            pub fn apply_to(&self, component: &#component) {
+                #(component.#setters(::core::convert::Into::into(::core::clone::Clone::clone(&self.#bound_fields)));)*
+            }
+
+            /// Returns a struct with the values of the properties of the component. The skipped
+            /// fields have their default value.
+            pub fn read_from(component: &#component) -> Self {
+                Self {
+                    #(#bound_fields: ::core::convert::Into::into(component.#getters()),)*
+                    #(#skipped_fields: ::core::default::Default::default(),)*
+                }
+            }
+
+            /// Calls `changed` with the new values each time the properties of the component
+            /// change, until the returned watcher is dropped
+            pub fn watch(
+                component: &#component,
+                changed: impl Fn(Self) + 'static,
+            ) -> slint::PropertyWatcher {
+                let component = slint::ComponentHandle::as_weak(component);
+                slint::PropertyWatcher::new(
+                    move || component.upgrade().map(|component| Self::read_from(&component)),
+                    move |value| {
+                        if let Some(value) = value {
+                            changed(value)
+                        }
+                    },
+                )
+            }
+        }
+    }
+    .into())
+}
@@ -236,13 +236,15 @@ struct MyStruct {
 );
 
 pub use slint_macros::slint;
+pub use slint_macros::PropertyBinding;
 
 pub use i_slint_core::api::*;
 pub use i_slint_core::graphics::{
     Brush, Color, Image, LoadImageError, Rgb8Pixel, Rgba8Pixel, RgbaColor, SharedPixelBuffer,
 };
 pub use i_slint_core::model::{
-    Model, ModelNotify, ModelPeer, ModelRc, ModelTracker, StandardListViewItem, VecModel,
+    Date, DebugModel, Model, ModelNotify, ModelPeer, ModelRc, ModelTracker, StandardListViewItem,
+    Time, VecModel,
 };
 pub use i_slint_core::sharedvector::SharedVector;
 pub use i_slint_core::string::SharedString;
@@ -272,6 +274,7 @@ pub fn register_font_from_path<P: AsRef<std::path::Path>>(
 #[doc(hidden)]
 pub mod re_exports {
     pub use alloc::boxed::Box;
+    pub use alloc::collections::BTreeMap;
     pub use alloc::format;
     pub use alloc::rc::{Rc, Weak};
     pub use alloc::string::String;
@@ -279,7 +282,8 @@ pub mod re_exports {
     pub use const_field_offset::{self, FieldOffsets, PinnedDrop};
     pub use core::iter::FromIterator;
     pub use i_slint_backend_selector::native_widgets::*;
-    pub use i_slint_core::animations::EasingCurve;
+    pub use i_slint_core::accessibility::{high_contrast, reduced_motion};
+    pub use i_slint_core::animations::{animation_tick, EasingCurve};
     pub use i_slint_core::callbacks::Callback;
     pub use i_slint_core::component::{
         free_component_item_graphics_resources, init_component_items, Component, ComponentRefPin,
@@ -287,7 +291,8 @@ pub mod re_exports {
     };
     pub use i_slint_core::graphics::*;
     pub use i_slint_core::input::{
-        FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers, MouseEvent,
+        FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers, LogicalKey,
+        MouseEvent,
     };
     pub use i_slint_core::item_tree::{
         visit_item_tree, ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, TraversalOrder,
@@ -298,6 +303,7 @@ pub mod re_exports {
     pub use i_slint_core::model::*;
     pub use i_slint_core::properties::{set_state_binding, Property, PropertyTracker, StateInfo};
     pub use i_slint_core::slice::Slice;
+    pub use i_slint_core::string::{slice_string, split_string};
     pub use i_slint_core::window::{Window, WindowHandleAccess, WindowRc};
     pub use i_slint_core::Color;
     pub use i_slint_core::ComponentVTable_static;
@@ -367,6 +373,19 @@ pub fn set_property_binding<T: Clone + 'static, StrongRef: StrongComponentRef +
         })
     }
 
+    pub fn animation_finished_callback<StrongRef: StrongComponentRef + 'static>(
+        component_strong: &StrongRef,
+        finished: Option<fn(StrongRef)>,
+    ) -> Option<i_slint_core::animations::AnimationFinishedCallback> {
+        let finished = finished?;
+        let weak = component_strong.to_weak();
+        Some(Rc::new(move || {
+            if let Some(strong) = <StrongRef as StrongComponentRef>::from_weak(&weak) {
+                finished(strong)
+            }
+        }))
+    }
+
     pub fn set_animated_property_binding<
         T: Clone + i_slint_core::properties::InterpolatedPropertyValue + 'static,
         StrongRef: StrongComponentRef + 'static,
@@ -375,11 +394,13 @@ pub fn set_animated_property_binding<
         component_strong: &StrongRef,
         binding: fn(StrongRef) -> T,
         animation_data: PropertyAnimation,
+        finished: Option<fn(StrongRef)>,
     ) {
         let weak = component_strong.to_weak();
-        property.set_animated_binding(
+        property.set_animated_binding_with_finished_callback(
             move || binding(<StrongRef as StrongComponentRef>::from_weak(&weak).unwrap()),
             animation_data,
+            animation_finished_callback(component_strong, finished),
         )
     }
 
@@ -394,16 +415,18 @@ pub fn set_animated_property_binding_for_transition<
             StrongRef,
         )
             -> (PropertyAnimation, i_slint_core::animations::Instant),
+        finished: Option<fn(StrongRef)>,
     ) {
         let weak_1 = component_strong.to_weak();
         let weak_2 = weak_1.clone();
-        property.set_animated_binding_for_transition(
+        property.set_animated_binding_for_transition_with_finished_callback(
             move || binding(<StrongRef as StrongComponentRef>::from_weak(&weak_1).unwrap()),
             move || {
                 compute_animation_details(
                     <StrongRef as StrongComponentRef>::from_weak(&weak_2).unwrap(),
                 )
             },
+            animation_finished_callback(component_strong, finished),
         )
     }
 
@@ -462,6 +485,31 @@ pub fn quit_event_loop() {
     i_slint_backend_selector::backend().quit_event_loop();
 }
 
+/// Sets the menus of the global menu bar of the application, replacing the previous ones.
+/// `activated` is called with the [`MenuBarEntry`] that the user activated.
+///
+/// On macOS, the menus are shown after the application menu, which has the standard About,
+/// Hide, and Quit entries. Quitting from that menu quits the event loop. Other platforms don't
+/// have a global menu bar and ignore this call.
+pub fn set_menu_bar(
+    menus: alloc::vec::Vec<MenuBarEntry>,
+    activated: impl Fn(&MenuBarEntry) + 'static,
+) {
+    i_slint_backend_selector::backend().set_menu_bar(menus, alloc::boxed::Box::new(activated));
+}
+
+/// Shows `label` in a badge on the icon of the application in the dock, or removes the badge
+/// if `label` is `None`. This is only implemented on macOS.
+pub fn set_application_badge(label: Option<&str>) {
+    i_slint_backend_selector::backend().set_application_badge(label);
+}
+
+/// Shows a progress bar on the icon of the application in the dock, with a `progress` between
+/// 0 and 1, or removes it if `progress` is `None`. This is only implemented on macOS.
+pub fn set_application_progress(progress: Option<f32>) {
+    i_slint_backend_selector::backend().set_application_progress(progress);
+}
+
 /// This module contains functions useful for unit tests
 #[cfg(feature = "std")]
 pub mod testing {
@@ -488,6 +536,49 @@ pub fn send_mouse_click<
         i_slint_core::tests::slint_send_mouse_click(&dyn_rc, x, y, &rc.window_handle().clone());
     }
 
+    /// Simulate pressing the left mouse button. It stays pressed until [`send_mouse_release`],
+    /// and [`send_mouse_move`] moves the mouse in between, for example to drag an item.
+    pub fn send_mouse_press<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        x: f32,
+        y: f32,
+    ) {
+        let component = component.clone_strong().into();
+        i_slint_core::tests::slint_send_mouse_press(x, y, &component.window_handle().clone());
+    }
+
+    /// Simulate moving the mouse
+    pub fn send_mouse_move<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        x: f32,
+        y: f32,
+    ) {
+        let component = component.clone_strong().into();
+        i_slint_core::tests::slint_send_mouse_move(x, y, &component.window_handle().clone());
+    }
+
+    /// Simulate releasing the left mouse button
+    pub fn send_mouse_release<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        x: f32,
+        y: f32,
+    ) {
+        let component = component.clone_strong().into();
+        i_slint_core::tests::slint_send_mouse_release(x, y, &component.window_handle().clone());
+    }
+
     /// Simulate a change in keyboard modifiers being pressed
     pub fn set_current_keyboard_modifiers<
         X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
@@ -13,3 +13,34 @@ fn empty_stuff() {
     slint!();
     slint!(struct Hei := { abcd: bool });
 }
+
+#[test]
+fn property_binding() {
+    slint!(Preferences := Window {
+        property <string> user-name;
+        property <bool> dark-mode;
+        property <int> volume: 5;
+    });
+
+    #[derive(slint::PropertyBinding, Debug, PartialEq)]
+    #[slint(component = Preferences)]
+    struct Settings {
+        #[slint(name = "user-name")]
+        pub name: slint::SharedString,
+        dark_mode: bool,
+        volume: i32,
+        #[slint(skip)]
+        save_count: Option<Vec<u32>>,
+    }
+
+    let preferences = Preferences::new();
+    assert_eq!(
+        Settings::read_from(&preferences),
+        Settings { name: "".into(), dark_mode: false, volume: 5, save_count: None }
+    );
+    Settings { name: "Alice".into(), dark_mode: true, volume: 8, save_count: Some(vec![1]) }
+        .apply_to(&preferences);
+    assert_eq!(preferences.get_user_name(), "Alice");
+    assert!(preferences.get_dark_mode());
+    assert_eq!(preferences.get_volume(), 8);
+}
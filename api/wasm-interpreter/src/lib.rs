@@ -4,10 +4,13 @@
 //! This wasm library can be loaded from JS to load and display the content of .slint files
 #![cfg(target_arch = "wasm32")]
 
+use i_slint_compiler::langtype::Type;
+use i_slint_core::model::{Model, ModelRc};
 use std::path::Path;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
-use slint_interpreter::ComponentHandle;
+use slint_interpreter::{ComponentHandle, SharedVector, Value};
 
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
@@ -138,7 +141,8 @@ pub async fn compile_from_string_with_style(
 impl WrappedCompiledComp {
     /// Run this compiled component in a canvas.
     /// The HTML must contains a <canvas> element with the given `canvas_id`
-    /// where the result is gonna be rendered
+    /// where the result is gonna be rendered. If there is no element with that id,
+    /// `canvas_id` is used as a CSS selector, like `"#main canvas"`.
     #[wasm_bindgen]
     pub fn run(&self, canvas_id: String) {
         let component = self.0.create_with_canvas_id(&canvas_id);
@@ -153,6 +157,33 @@ pub fn run(&self, canvas_id: String) {
     pub fn create(&self, canvas_id: String) -> Result<WrappedInstance, JsValue> {
         Ok(WrappedInstance(self.0.create_with_canvas_id(&canvas_id)))
     }
+    /// Creates this compiled component in the given canvas element, like `create()`.
+    /// An id is set on the canvas if it doesn't have one.
+    #[wasm_bindgen]
+    pub fn create_in_canvas(
+        &self,
+        canvas: web_sys::HtmlCanvasElement,
+    ) -> Result<WrappedInstance, JsValue> {
+        if canvas.id().is_empty() {
+            let document = web_sys::window().unwrap().document().unwrap();
+            let id = (0..)
+                .map(|i| format!("slint-canvas-{}", i))
+                .find(|id| document.get_element_by_id(id).is_none())
+                .unwrap();
+            canvas.set_id(&id);
+        }
+        Ok(WrappedInstance(self.0.create_with_canvas_id(&canvas.id())))
+    }
+    /// Returns the names of the public properties of this component.
+    #[wasm_bindgen]
+    pub fn properties(&self) -> js_sys::Array {
+        self.0.properties().map(|(name, _)| JsValue::from_str(&name)).collect()
+    }
+    /// Returns the names of the public callbacks of this component.
+    #[wasm_bindgen]
+    pub fn callbacks(&self) -> js_sys::Array {
+        self.0.callbacks().map(|name| JsValue::from_str(&name)).collect()
+    }
 }
 
 #[wasm_bindgen]
@@ -176,6 +207,202 @@ pub fn show(&self) {
     pub fn hide(&self) {
         self.0.hide();
     }
+    /// Returns the value of the property with the given name.
+    /// Structs are returned as objects, and arrays and models as arrays.
+    #[wasm_bindgen]
+    pub fn get_property(&self, name: &str) -> Result<JsValue, JsValue> {
+        let value = self.0.get_property(name).map_err(|_| {
+            JsValue::from_str(&format!("Property {} not found in the component", name))
+        })?;
+        Ok(to_js_value(&value))
+    }
+    /// Sets the property with the given name to a value converted from the JavaScript value.
+    /// Colors and brushes are set with strings like `"#rrggbb"` or `"#rrggbbaa"`.
+    #[wasm_bindgen]
+    pub fn set_property(&self, name: &str, value: JsValue) -> Result<(), JsValue> {
+        let value = to_eval_value(&value, &self.property_type(name)?)?;
+        self.0
+            .set_property(name, value)
+            .map_err(|_| JsValue::from_str(&format!("Cannot assign property {}", name)))
+    }
+    /// Sets the handler of the callback with the given name to a JavaScript function, which is
+    /// called with the arguments of the callback.
+    #[wasm_bindgen]
+    pub fn set_callback(&self, name: &str, handler: js_sys::Function) -> Result<(), JsValue> {
+        let return_type = match self.property_type(name)? {
+            Type::Callback { return_type, .. } => return_type,
+            _ => return Err(JsValue::from_str(&format!("{} is not a callback", name))),
+        };
+        self.0
+            .set_callback(name, move |args| {
+                let args = args.iter().map(to_js_value).collect::<js_sys::Array>();
+                let result =
+                    handler.apply(&JsValue::UNDEFINED, &args).and_then(
+                        |result| match &return_type {
+                            Some(return_type) => to_eval_value(&result, return_type),
+                            None => Ok(Value::Void),
+                        },
+                    );
+                result.unwrap_or_else(|err| {
+                    web_sys::console::error_1(&err);
+                    return_type
+                        .as_ref()
+                        .map_or(Value::Void, |ty| slint_interpreter::default_value_for_type(ty))
+                })
+            })
+            .map_err(|_| JsValue::from_str(&format!("Cannot set callback {}", name)))
+    }
+    /// Invokes the callback with the given name with the arguments of the array, and returns
+    /// its result.
+    #[wasm_bindgen]
+    pub fn invoke_callback(&self, name: &str, args: js_sys::Array) -> Result<JsValue, JsValue> {
+        let arg_types = match self.property_type(name)? {
+            Type::Callback { args, .. } => args,
+            _ => return Err(JsValue::from_str(&format!("{} is not a callback", name))),
+        };
+        if args.length() as usize != arg_types.len() {
+            return Err(JsValue::from_str(&format!(
+                "{} expect {} arguments, but {} where provided",
+                name,
+                arg_types.len(),
+                args.length()
+            )));
+        }
+        let args = args
+            .iter()
+            .zip(arg_types.iter())
+            .map(|(arg, ty)| to_eval_value(&arg, ty))
+            .collect::<Result<Vec<_>, _>>()?;
+        let result = self
+            .0
+            .invoke_callback(name, &args)
+            .map_err(|_| JsValue::from_str(&format!("Cannot invoke callback {}", name)))?;
+        Ok(to_js_value(&result))
+    }
+}
+
+impl WrappedInstance {
+    fn property_type(&self, name: &str) -> Result<Type, JsValue> {
+        let name = name.replace('_', "-");
+        self.0
+            .definition()
+            .properties_and_callbacks()
+            .find_map(|(prop_name, ty)| (prop_name == name).then(|| ty))
+            .ok_or_else(|| JsValue::from_str(&format!("{} not found in the component", name)))
+    }
+}
+
+/// Converts a JavaScript value to a value of the given type
+fn to_eval_value(val: &JsValue, ty: &Type) -> Result<Value, JsValue> {
+    let invalid = || JsValue::from(js_sys::TypeError::new(&format!("Cannot convert to {}", ty)));
+    match ty {
+        Type::Float32
+        | Type::Int32
+        | Type::Duration
+        | Type::Angle
+        | Type::PhysicalLength
+        | Type::LogicalLength
+        | Type::Percent
+        | Type::UnitProduct(_) => val.as_f64().map(Value::Number).ok_or_else(invalid),
+        Type::String => val.as_string().map(|s| Value::String(s.into())).ok_or_else(invalid),
+        Type::Color | Type::Brush => val
+            .as_string()
+            .and_then(|s| i_slint_compiler::literals::parse_color_literal(&s))
+            .map(|c| slint_interpreter::Color::from_argb_encoded(c).into())
+            .ok_or_else(invalid),
+        Type::Bool => val.as_bool().map(Value::Bool).ok_or_else(invalid),
+        Type::Array(a) => {
+            if !js_sys::Array::is_array(val) {
+                return Err(invalid());
+            }
+            let array: &js_sys::Array = val.unchecked_ref();
+            Ok(Value::Model(ModelRc::new(i_slint_core::model::SharedVectorModel::from(
+                array
+                    .iter()
+                    .map(|v| to_eval_value(&v, a))
+                    .collect::<Result<SharedVector<_>, _>>()?,
+            ))))
+        }
+        Type::Struct { fields, .. } => {
+            if !val.is_object() {
+                return Err(invalid());
+            }
+            Ok(Value::Struct(
+                fields
+                    .iter()
+                    .map(|(field_name, field_ty)| {
+                        let field =
+                            js_sys::Reflect::get(val, &field_name.replace('-', "_").into())?;
+                        Ok((field_name.clone(), to_eval_value(&field, field_ty)?))
+                    })
+                    .collect::<Result<_, JsValue>>()?,
+            ))
+        }
+        Type::Map(value_ty) => {
+            let obj = val.dyn_ref::<js_sys::Object>().ok_or_else(invalid)?;
+            Ok(Value::Map(
+                js_sys::Object::keys(obj)
+                    .iter()
+                    .map(|key| {
+                        let value = js_sys::Reflect::get(val, &key)?;
+                        Ok((key.as_string().unwrap_or_default(), to_eval_value(&value, value_ty)?))
+                    })
+                    .collect::<Result<_, JsValue>>()?,
+            ))
+        }
+        Type::Enumeration(en) => {
+            let value = val.as_string().ok_or_else(invalid)?.replace('_', "-");
+            if !en.values.contains(&value) {
+                return Err(JsValue::from_str(&format!(
+                    "{} is not a valid value for {}",
+                    value, en.name
+                )));
+            }
+            Ok(Value::EnumerationValue(en.name.clone(), value))
+        }
+        _ => Err(JsValue::from_str("Cannot convert to a Slint property value")),
+    }
+}
+
+/// Converts a value to a JavaScript value. Colors are converted to strings in the
+/// `#rrggbbaa` notation, and the enumeration values to their name.
+fn to_js_value(val: &Value) -> JsValue {
+    match val {
+        Value::Number(n) => JsValue::from_f64(*n),
+        Value::String(s) => JsValue::from_str(s.as_str()),
+        Value::Bool(b) => JsValue::from_bool(*b),
+        Value::Image(image) => match image.path() {
+            Some(path) => JsValue::from_str(&path.to_string_lossy()),
+            None => JsValue::NULL,
+        },
+        Value::Model(model) => (0..model.row_count())
+            .map(|i| to_js_value(&model.row_data(i).unwrap()))
+            .collect::<js_sys::Array>()
+            .into(),
+        Value::Struct(st) => {
+            let obj = js_sys::Object::new();
+            for (k, v) in st.iter() {
+                js_sys::Reflect::set(&obj, &k.replace('-', "_").into(), &to_js_value(v)).unwrap();
+            }
+            obj.into()
+        }
+        Value::Map(map) => {
+            let obj = js_sys::Object::new();
+            for (k, v) in map.iter() {
+                js_sys::Reflect::set(&obj, &k.into(), &to_js_value(v)).unwrap();
+            }
+            obj.into()
+        }
+        Value::Brush(slint_interpreter::Brush::SolidColor(c)) => JsValue::from_str(&format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            c.red(),
+            c.green(),
+            c.blue(),
+            c.alpha()
+        )),
+        Value::EnumerationValue(_, value) => JsValue::from_str(value),
+        _ => JsValue::UNDEFINED,
+    }
 }
 
 /// Register DOM event handlers on all instance and set up the event loop for that.
@@ -151,6 +151,16 @@ export function activate(context: vscode.ExtensionContext) {
         client.sendNotification("slint/showPreview", ae.document.uri.fsPath.toString());
     }));
 
+    context.subscriptions.push(vscode.commands.registerCommand('slint.showDesignPreview', function () {
+        let ae = vscode.window.activeTextEditor;
+        if (!ae) {
+            return;
+        }
+        let pos = ae.selection.active;
+        client.sendNotification("slint/showDesignPreview",
+            [ae.document.uri.fsPath.toString(), pos.line, pos.character]);
+    }));
+
     context.subscriptions.push(vscode.commands.registerCommand('slint.reload', async function () {
         statusBar.hide();
         await client.stop();
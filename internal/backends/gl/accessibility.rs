@@ -0,0 +1,119 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Detection of the high contrast and reduced motion preferences of the user in the system settings
+
+use i_slint_core::accessibility::{set_system_accessibility_preferences, AccessibilityPreferences};
+
+/// Reads the preferences of the user from the system settings, and makes them the system
+/// preferences that the `high-contrast()` and `reduced-motion()` functions return
+pub fn update_system_preferences() {
+    let (high_contrast, reduced_motion) = detect();
+    let mut preferences = AccessibilityPreferences::default();
+    preferences.high_contrast = high_contrast;
+    preferences.reduced_motion = reduced_motion;
+    set_system_accessibility_preferences(preferences);
+}
+
+/// Returns whether the user wants a high contrast and reduced motion
+#[cfg(target_os = "windows")]
+fn detect() -> (bool, bool) {
+    use winapi::shared::minwindef::{BOOL, FALSE, TRUE};
+    use winapi::um::winuser::{
+        SystemParametersInfoW, HCF_HIGHCONTRASTON, HIGHCONTRASTW, SPI_GETCLIENTAREAANIMATION,
+        SPI_GETHIGHCONTRAST,
+    };
+
+    let mut high_contrast = HIGHCONTRASTW {
+        cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+        dwFlags: 0,
+        lpszDefaultScheme: std::ptr::null_mut(),
+    };
+    let mut animations: BOOL = TRUE;
+    // Safety: the pointers are valid for the duration of the calls, and point to the types that
+    // these actions expect
+    unsafe {
+        if SystemParametersInfoW(
+            SPI_GETHIGHCONTRAST,
+            high_contrast.cbSize,
+            &mut high_contrast as *mut _ as *mut _,
+            0,
+        ) == FALSE
+        {
+            high_contrast.dwFlags = 0;
+        }
+        if SystemParametersInfoW(
+            SPI_GETCLIENTAREAANIMATION,
+            0,
+            &mut animations as *mut _ as *mut _,
+            0,
+        ) == FALSE
+        {
+            animations = TRUE;
+        }
+    }
+    (high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0, animations == FALSE)
+}
+
+#[cfg(target_os = "macos")]
+fn detect() -> (bool, bool) {
+    use cocoa::base::{id, BOOL, NO};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    // Safety: NSWorkspace's sharedWorkspace is never nil, and these methods exist since macOS 10.10
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let high_contrast: BOOL = msg_send![workspace, accessibilityDisplayShouldIncreaseContrast];
+        let reduced_motion: BOOL = msg_send![workspace, accessibilityDisplayShouldReduceMotion];
+        (high_contrast != NO, reduced_motion != NO)
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn detect() -> (bool, bool) {
+    // Ask the settings of GNOME, that other desktops also follow
+    let gsettings = |schema: &str, key: &str| {
+        std::process::Command::new("gsettings")
+            .args(["get", schema, key])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "true")
+    };
+    let high_contrast = gsettings("org.gnome.desktop.a11y.interface", "high-contrast")
+        .unwrap_or(false)
+        || std::env::var("GTK_THEME").map_or(false, |theme| theme.contains("HighContrast"));
+    let reduced_motion =
+        gsettings("org.gnome.desktop.interface", "enable-animations").map_or(false, |a| !a);
+    (high_contrast, reduced_motion)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn detect() -> (bool, bool) {
+    let matches = |query: &str| {
+        web_sys::window()
+            .and_then(|window| window.match_media(query).ok().flatten())
+            .map_or(false, |list| list.matches())
+    };
+    (matches("(prefers-contrast: more)"), matches("(prefers-reduced-motion: reduce)"))
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_arch = "wasm32"
+)))]
+fn detect() -> (bool, bool) {
+    (false, false)
+}
@@ -20,14 +20,25 @@
 use std::rc::{Rc, Weak};
 use winit::event::WindowEvent;
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
 use winit::platform::run_return::EventLoopExtRunReturn;
 
 pub trait WinitWindow: PlatformWindow {
     fn runtime_window(&self) -> Rc<corelib::window::Window>;
     fn currently_pressed_key_code(&self) -> &Cell<Option<winit::event::VirtualKeyCode>>;
+    /// The scancode of the key that is pressed, and whether its last press was repeated, for the
+    /// text that follows it in a `ReceivedCharacter` event
+    fn currently_pressed_scancode(&self) -> &Cell<(i32, bool)>;
+    /// The scancodes of the keys that are held down, to tell the key repeat of the system, which
+    /// sends a pressed event again, from the first press
+    fn pressed_scancodes(&self) -> &RefCell<std::collections::HashSet<u32>>;
     fn current_keyboard_modifiers(&self) -> &Cell<KeyboardModifiers>;
     fn draw(self: Rc<Self>);
+    /// Returns true if the window is shown as a layer-shell surface, which receives its input
+    /// events from the layer_shell module instead of winit.
+    fn is_layer_shell(&self) -> bool {
+        false
+    }
     fn with_window_handle(&self, callback: &mut dyn FnMut(&winit::window::Window));
     fn constraints(&self) -> (corelib::layout::LayoutInfo, corelib::layout::LayoutInfo);
     fn set_constraints(
@@ -177,12 +188,70 @@ struct NotRunningEventLoop {
 
 impl NotRunningEventLoop {
     fn new() -> Self {
-        let instance = winit::event_loop::EventLoop::with_user_event();
+        let instance = if EVENT_LOOP_IN_ANY_THREAD.load(std::sync::atomic::Ordering::Relaxed) {
+            new_event_loop_in_any_thread()
+        } else {
+            winit::event_loop::EventLoop::with_user_event()
+        };
         let event_loop_proxy = instance.create_proxy();
         Self { instance, event_loop_proxy }
     }
 }
 
+/// Set by [`allow_event_loop_in_any_thread()`], so that winit does not panic when the event loop
+/// is created in a thread other than the main thread
+static EVENT_LOOP_IN_ANY_THREAD: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Allows the event loop to be created in a thread other than the main thread, on the platforms
+/// where winit supports it.
+pub fn allow_event_loop_in_any_thread() -> Result<(), corelib::api::EventLoopThreadError> {
+    if cfg!(any(
+        target_os = "windows",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )) {
+        EVENT_LOOP_IN_ANY_THREAD.store(true, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    } else {
+        Err(corelib::api::EventLoopThreadError::PlatformRequiresMainThread)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn new_event_loop_in_any_thread() -> winit::event_loop::EventLoop<CustomEvent> {
+    use winit::platform::windows::EventLoopExtWindows;
+    winit::event_loop::EventLoop::new_any_thread()
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn new_event_loop_in_any_thread() -> winit::event_loop::EventLoop<CustomEvent> {
+    use winit::platform::unix::EventLoopExtUnix;
+    winit::event_loop::EventLoop::new_any_thread()
+}
+
+// allow_event_loop_in_any_thread() never succeeds on the other platforms
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn new_event_loop_in_any_thread() -> winit::event_loop::EventLoop<CustomEvent> {
+    winit::event_loop::EventLoop::with_user_event()
+}
+
 struct RunningEventLoop<'a> {
     event_loop_target: &'a winit::event_loop::EventLoopWindowTarget<CustomEvent>,
     event_loop_proxy: &'a winit::event_loop::EventLoopProxy<CustomEvent>,
@@ -355,8 +424,10 @@ fn key_event(
         event_type: KeyEventType,
         text: SharedString,
         modifiers: KeyboardModifiers,
+        (scancode, repeat): (i32, bool),
     ) -> KeyEvent {
-        let mut event = KeyEvent { event_type, text, modifiers };
+        let mut event =
+            KeyEvent { event_type, text, modifiers, scancode, repeat, ..Default::default() };
 
         let tab = String::from(corelib::input::key_codes::Tab);
 
@@ -364,6 +435,7 @@ fn key_event(
         if event.text == tab && modifiers.shift {
             event.text = SharedString::from(String::from(corelib::input::key_codes::Backtab));
         }
+        event.key = corelib::input::LogicalKey::from_text(&event.text);
 
         event
     }
@@ -373,9 +445,17 @@ fn key_event(
         WindowEvent::Resized(size) => {
             let size = size.to_logical(runtime_window.scale_factor() as f64);
             runtime_window.set_window_item_geometry(size.width, size.height);
+            #[cfg(target_os = "ios")]
+            update_safe_area(&*window);
+        }
+        WindowEvent::Moved(_) => {
+            // When the device is rotated by 180 degrees, the size stays the same but the safe area
+            // moves to the other side, which winit reports as a new inner position.
+            #[cfg(target_os = "ios")]
+            update_safe_area(&*window);
         }
         WindowEvent::CloseRequested => {
-            window.hide();
+            runtime_window.hide();
             match quit_behavior {
                 corelib::backend::EventLoopQuitBehavior::QuitOnLastWindowClosed => {
                     let window_count = ALL_WINDOWS.with(|windows| windows.borrow().len());
@@ -410,7 +490,12 @@ fn key_event(
 
             let modifiers = window.current_keyboard_modifiers().get();
 
-            let mut event = key_event(KeyEventType::KeyPressed, text, modifiers);
+            let mut event = key_event(
+                KeyEventType::KeyPressed,
+                text,
+                modifiers,
+                window.currently_pressed_scancode().get(),
+            );
 
             runtime_window.clone().process_key_input(&event);
             event.event_type = KeyEventType::KeyReleased;
@@ -421,21 +506,31 @@ fn key_event(
             // focus to be the same as being active.
             runtime_window.set_active(have_focus);
             runtime_window.set_focus(have_focus);
+            if !have_focus {
+                // The keys released while the window doesn't have the focus aren't reported
+                window.pressed_scancodes().borrow_mut().clear();
+            }
         }
         WindowEvent::KeyboardInput { ref input, .. } => {
             corelib::animations::update_animations();
-            window.currently_pressed_key_code().set(match input.state {
-                winit::event::ElementState::Pressed => input.virtual_keycode,
-                _ => None,
-            });
+            let pressed = input.state == winit::event::ElementState::Pressed;
+            // The key repeat of the system sends the pressed event of the key again, without
+            // releasing it first
+            let repeat = if pressed {
+                !window.pressed_scancodes().borrow_mut().insert(input.scancode)
+            } else {
+                window.pressed_scancodes().borrow_mut().remove(&input.scancode);
+                false
+            };
+            let scancode = (input.scancode as i32, repeat);
+            window.currently_pressed_key_code().set(input.virtual_keycode.filter(|_| pressed));
+            window.currently_pressed_scancode().set(if pressed { scancode } else { (0, false) });
             if let Some(text) = input.virtual_keycode.and_then(key_codes::winit_key_to_string) {
                 let event = key_event(
-                    match input.state {
-                        winit::event::ElementState::Pressed => KeyEventType::KeyPressed,
-                        winit::event::ElementState::Released => KeyEventType::KeyReleased,
-                    },
+                    if pressed { KeyEventType::KeyPressed } else { KeyEventType::KeyReleased },
                     text,
                     window.current_keyboard_modifiers().get(),
+                    scancode,
                 );
                 runtime_window.process_key_input(&event);
             };
@@ -521,6 +616,9 @@ fn key_event(
                 runtime_window.set_window_item_geometry(size.width, size.height);
                 runtime_window.set_scale_factor(scale_factor as f32);
             }
+            // The insets are in logical pixels, they change with the scale factor
+            #[cfg(target_os = "ios")]
+            update_safe_area(&*window);
         }
         _ => {}
     }
@@ -562,8 +660,17 @@ pub fn run(quit_behavior: i_slint_core::backend::EventLoopQuitBehavior) {
             *control_flow = ControlFlow::Wait;
 
             match event {
+                // winit replaces the main menu with its default one when the application
+                // finishes launching, just before this event
+                #[cfg(target_os = "macos")]
+                winit::event::Event::NewEvents(winit::event::StartCause::Init) => {
+                    crate::macos::install_menu_bar();
+                }
+
                 winit::event::Event::WindowEvent { event, window_id } => {
-                    if let Some(window) = window_by_id(window_id) {
+                    if let Some(window) =
+                        window_by_id(window_id).filter(|window| !window.is_layer_shell())
+                    {
                         process_window_event(
                             window,
                             event,
@@ -596,6 +703,18 @@ pub fn run(quit_behavior: i_slint_core::backend::EventLoopQuitBehavior) {
                     user();
                 }
 
+                #[cfg(all(
+                    feature = "wayland",
+                    not(any(
+                        target_family = "windows",
+                        target_os = "macos",
+                        target_os = "ios",
+                        target_os = "android",
+                        target_arch = "wasm32"
+                    ))
+                ))]
+                winit::event::Event::MainEventsCleared => crate::layer_shell::dispatch_pending(),
+
                 #[cfg(target_arch = "wasm32")]
                 winit::event::Event::UserEvent(CustomEvent::RedrawAllWindows) => {
                     redraw_all_windows()
@@ -621,19 +740,48 @@ pub fn run(quit_behavior: i_slint_core::backend::EventLoopQuitBehavior) {
         })
     };
 
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
     {
         winit_loop.run_return(run_fn);
 
         *GLOBAL_PROXY.get_or_init(Default::default).lock().unwrap() = Default::default();
     }
 
-    #[cfg(target_arch = "wasm32")]
+    // On iOS, the application can only be run by UIKit, so this never returns
+    #[cfg(any(target_arch = "wasm32", target_os = "ios"))]
     {
         winit_loop.run(run_fn)
     }
 }
 
+/// On iOS, winit reports the safe area of the screen as the inner position and size of the
+/// window, while the window and its OpenGL surface cover the whole screen.
+///
+/// This is called whenever the size, the position or the scale factor of the window change,
+/// which covers the changes of the orientation of the device.
+#[cfg(target_os = "ios")]
+fn update_safe_area(window: &dyn WinitWindow) {
+    let runtime_window = window.runtime_window();
+    // The scale factor of the window, which is the one of winit unless SLINT_SCALE_FACTOR is set
+    let scale_factor = runtime_window.scale_factor() as f64;
+    window.with_window_handle(&mut |winit_window| {
+        let position = winit_window.inner_position().unwrap_or_default();
+        let inner_size = winit_window.inner_size();
+        let outer_size = winit_window.outer_size();
+        let top = position.y as f64;
+        let left = position.x as f64;
+        let bottom = outer_size.height as f64 - inner_size.height as f64 - top;
+        let right = outer_size.width as f64 - inner_size.width as f64 - left;
+        let logical = |inset: f64| (inset.max(0.) / scale_factor) as f32;
+        runtime_window.set_window_item_safe_area(
+            logical(top),
+            logical(bottom),
+            logical(left),
+            logical(right),
+        );
+    });
+}
+
 // This function is called when we receive a control character via WindowEvent::ReceivedCharacter and
 // instead want to use the last virtual key code. That happens when for example pressing Ctrl+some_key
 // on Windows/X11/Wayland. This function may be missing mappings, it's trying to cover what we may be
@@ -196,6 +196,9 @@ fn default() -> Self {
         #[cfg(not(target_arch = "wasm32"))]
         {
             font_db.load_system_fonts();
+            // fontdb only knows the font directories of macOS, not the one of iOS
+            #[cfg(target_os = "ios")]
+            font_db.load_fonts_dir("/System/Library/Fonts");
             #[cfg(any(
                 target_family = "windows",
                 target_os = "macos",
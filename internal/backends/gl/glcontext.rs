@@ -14,6 +14,35 @@ enum OpenGLContextState {
     Current(glutin::WindowedContext<glutin::PossiblyCurrent>),
     #[cfg(target_arch = "wasm32")]
     Current { window: Rc<winit::window::Window>, canvas: web_sys::HtmlCanvasElement },
+    // The context is declared first, to be dropped before the surface
+    #[cfg(all(
+        feature = "wayland",
+        not(any(
+            target_family = "windows",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "android",
+            target_arch = "wasm32"
+        ))
+    ))]
+    LayerShellNotCurrent(
+        glutin::RawContext<glutin::NotCurrent>,
+        crate::layer_shell::LayerShellSurface,
+    ),
+    #[cfg(all(
+        feature = "wayland",
+        not(any(
+            target_family = "windows",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "android",
+            target_arch = "wasm32"
+        ))
+    ))]
+    LayerShellCurrent(
+        glutin::RawContext<glutin::PossiblyCurrent>,
+        crate::layer_shell::LayerShellSurface,
+    ),
 }
 
 pub struct OpenGLContext(RefCell<Option<OpenGLContextState>>);
@@ -27,9 +56,67 @@ pub fn window(&self) -> std::cell::Ref<winit::window::Window> {
             OpenGLContextState::Current(context) => context.window(),
             #[cfg(target_arch = "wasm32")]
             OpenGLContextState::Current { window, .. } => window.as_ref(),
+            #[cfg(all(
+                feature = "wayland",
+                not(any(
+                    target_family = "windows",
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android",
+                    target_arch = "wasm32"
+                ))
+            ))]
+            OpenGLContextState::LayerShellNotCurrent(_, surface)
+            | OpenGLContextState::LayerShellCurrent(_, surface) => surface.window(),
         })
     }
 
+    /// The size of the surface in physical pixels
+    pub fn inner_size(&self) -> winit::dpi::PhysicalSize<u32> {
+        match self.0.borrow().as_ref().unwrap() {
+            #[cfg(all(
+                feature = "wayland",
+                not(any(
+                    target_family = "windows",
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android",
+                    target_arch = "wasm32"
+                ))
+            ))]
+            OpenGLContextState::LayerShellNotCurrent(_, surface)
+            | OpenGLContextState::LayerShellCurrent(_, surface) => surface.size(),
+            #[allow(unreachable_patterns)]
+            _ => self.window().inner_size(),
+        }
+    }
+
+    /// The scale factor of a layer-shell surface, which doesn't follow the one of its winit window
+    pub fn surface_scale_factor(&self) -> Option<f64> {
+        match self.0.borrow().as_ref().unwrap() {
+            #[cfg(all(
+                feature = "wayland",
+                not(any(
+                    target_family = "windows",
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android",
+                    target_arch = "wasm32"
+                ))
+            ))]
+            OpenGLContextState::LayerShellNotCurrent(_, surface)
+            | OpenGLContextState::LayerShellCurrent(_, surface) => Some(surface.scale_factor()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns true if the window receives its events from a layer-shell surface rather than
+    /// from winit
+    pub fn is_layer_shell(&self) -> bool {
+        self.surface_scale_factor().is_some()
+    }
+
     #[cfg(target_arch = "wasm32")]
     pub fn html_canvas_element(&self) -> std::cell::Ref<web_sys::HtmlCanvasElement> {
         std::cell::Ref::map(self.0.borrow(), |state| match state.as_ref().unwrap() {
@@ -45,7 +132,21 @@ pub fn make_current(&self) {
                 let current_ctx = unsafe { not_current_ctx.make_current().unwrap() };
                 OpenGLContextState::Current(current_ctx)
             }
-            state @ OpenGLContextState::Current { .. } => state,
+            #[cfg(all(
+                feature = "wayland",
+                not(any(
+                    target_family = "windows",
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android",
+                    target_arch = "wasm32"
+                ))
+            ))]
+            OpenGLContextState::LayerShellNotCurrent(not_current_ctx, surface) => {
+                let current_ctx = unsafe { not_current_ctx.make_current().unwrap() };
+                OpenGLContextState::LayerShellCurrent(current_ctx, surface)
+            }
+            state => state,
         });
     }
 
@@ -54,18 +155,53 @@ pub fn make_not_current(&self) {
         {
             let mut ctx = self.0.borrow_mut();
             *ctx = Some(match ctx.take().unwrap() {
-                state @ OpenGLContextState::NotCurrent(_) => state,
                 OpenGLContextState::Current(current_ctx_rc) => {
                     OpenGLContextState::NotCurrent(unsafe {
                         current_ctx_rc.make_not_current().unwrap()
                     })
                 }
+                #[cfg(all(
+                    feature = "wayland",
+                    not(any(
+                        target_family = "windows",
+                        target_os = "macos",
+                        target_os = "ios",
+                        target_os = "android",
+                        target_arch = "wasm32"
+                    ))
+                ))]
+                OpenGLContextState::LayerShellCurrent(current_ctx, surface) => {
+                    OpenGLContextState::LayerShellNotCurrent(
+                        unsafe { current_ctx.make_not_current().unwrap() },
+                        surface,
+                    )
+                }
+                state => state,
             });
         }
     }
 
+    fn is_current(&self) -> bool {
+        match self.0.borrow().as_ref().unwrap() {
+            OpenGLContextState::Current { .. } => true,
+            #[cfg(all(
+                feature = "wayland",
+                not(any(
+                    target_family = "windows",
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android",
+                    target_arch = "wasm32"
+                ))
+            ))]
+            OpenGLContextState::LayerShellCurrent(..) => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
     pub fn with_current_context<T>(&self, cb: impl FnOnce(&Self) -> T) -> T {
-        if matches!(self.0.borrow().as_ref().unwrap(), OpenGLContextState::Current { .. }) {
+        if self.is_current() {
             cb(self)
         } else {
             self.make_current();
@@ -78,29 +214,58 @@ pub fn with_current_context<T>(&self, cb: impl FnOnce(&Self) -> T) -> T {
     pub fn swap_buffers(&self) {
         #[cfg(not(target_arch = "wasm32"))]
         match &self.0.borrow().as_ref().unwrap() {
-            OpenGLContextState::NotCurrent(_) => {}
             OpenGLContextState::Current(current_ctx) => {
                 current_ctx.swap_buffers().unwrap();
             }
+            #[cfg(all(
+                feature = "wayland",
+                not(any(
+                    target_family = "windows",
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android",
+                    target_arch = "wasm32"
+                ))
+            ))]
+            OpenGLContextState::LayerShellCurrent(current_ctx, _) => {
+                current_ctx.swap_buffers().unwrap();
+            }
+            _ => {}
         }
     }
 
     pub fn ensure_resized(&self) {
         #[cfg(not(target_arch = "wasm32"))]
         match &self.0.borrow().as_ref().unwrap() {
-            OpenGLContextState::NotCurrent(_) => {
-                i_slint_core::debug_log!("internal error: cannot call OpenGLContext::ensure_resized without context being current!")
-            }
             OpenGLContextState::Current(_current) => {
                 _current.resize(_current.window().inner_size());
             }
+            #[cfg(all(
+                feature = "wayland",
+                not(any(
+                    target_family = "windows",
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android",
+                    target_arch = "wasm32"
+                ))
+            ))]
+            OpenGLContextState::LayerShellCurrent(current_ctx, surface) => {
+                current_ctx.resize(surface.size());
+            }
+            _ => {
+                i_slint_core::debug_log!("internal error: cannot call OpenGLContext::ensure_resized without context being current!")
+            }
         }
     }
 
+    /// Creates the window with its OpenGL context. On the web, the window renders into the canvas
+    /// found with `canvas_id`. Returns an error if the canvas or the context can't be found or
+    /// created.
     pub fn new_context_and_renderer(
         window_builder: winit::window::WindowBuilder,
         #[cfg(target_arch = "wasm32")] canvas_id: &str,
-    ) -> (Self, femtovg::renderer::OpenGl) {
+    ) -> Result<(Self, femtovg::renderer::OpenGl), String> {
         #[cfg(not(target_arch = "wasm32"))]
         {
             use crate::event_loop::EventLoopInterface;
@@ -152,24 +317,27 @@ pub fn new_context_and_renderer(
                     },
                 ];
 
-                let mut last_err = None;
+                let mut errors = Vec::new();
                 for factory_fn in context_factory_fns {
                     match factory_fn(window_builder.clone(), event_loop) {
                         Ok(new_context) => {
-                            return new_context;
+                            return Ok(new_context);
                         }
                         Err(e) => {
-                            last_err = Some(e);
+                            errors.push(e);
                         }
                     }
                 }
 
-                panic!("Failed to create OpenGL context: {}", last_err.unwrap())
-            });
-            let windowed_context = unsafe { windowed_context.make_current().unwrap() };
+                Err(format!("Failed to create OpenGL context: {}", errors.join(", ")))
+            })?;
+            let windowed_context =
+                unsafe { windowed_context.make_current() }.map_err(|(_, err)| {
+                    format!("Failed to make the OpenGL context current: {}", err)
+                })?;
 
-            let renderer =
-                femtovg::renderer::OpenGl::new_from_glutin_context(&windowed_context).unwrap();
+            let renderer = femtovg::renderer::OpenGl::new_from_glutin_context(&windowed_context)
+                .map_err(|err| format!("Failed to create the OpenGL renderer: {:?}", err))?;
 
             #[cfg(target_os = "macos")]
             {
@@ -182,39 +350,53 @@ pub fn new_context_and_renderer(
                 }
             }
 
-            (Self(RefCell::new(Some(OpenGLContextState::Current(windowed_context)))), renderer)
+            Ok((Self(RefCell::new(Some(OpenGLContextState::Current(windowed_context)))), renderer))
         }
 
         #[cfg(target_arch = "wasm32")]
         {
             use wasm_bindgen::JsCast;
 
-            let canvas = web_sys::window()
-                .unwrap()
-                .document()
-                .unwrap()
+            // The canvas is found by its id, or otherwise with `canvas_id` as a CSS selector
+            let document = web_sys::window()
+                .and_then(|window| window.document())
+                .ok_or("There is no document in which to look for the canvas")?;
+            let canvas = document
                 .get_element_by_id(canvas_id)
-                .unwrap()
+                .or_else(|| document.query_selector(canvas_id).ok().flatten())
+                .ok_or_else(|| format!("No canvas element found for {:?}", canvas_id))?
                 .dyn_into::<web_sys::HtmlCanvasElement>()
-                .unwrap();
+                .map_err(|_| format!("The element {:?} is not a canvas", canvas_id))?;
+
+            // Without this, the browser uses the touch events to scroll or zoom the page and
+            // cancels the pointer events, so the UI would not get any touch input
+            canvas
+                .style()
+                .set_property("touch-action", "none")
+                .map_err(|err| format!("Failed to set the style of the canvas: {:?}", err))?;
 
             let renderer = match femtovg::renderer::OpenGl::new_from_html_canvas(&canvas) {
                 Ok(renderer) => renderer,
                 Err(_) => {
                     // I don't believe that there's a way of disabling the 2D canvas.
-                    let context_2d = canvas
-                        .get_context("2d")
-                        .unwrap()
-                        .unwrap()
-                        .dyn_into::<web_sys::CanvasRenderingContext2d>()
-                        .unwrap();
-                    context_2d.set_font("20px serif");
-                    // We don't know if we're rendering on dark or white background, so choose a "color" in the middle for the text.
-                    context_2d.set_fill_style(&wasm_bindgen::JsValue::from_str("red"));
-                    context_2d
-                        .fill_text("Slint requires WebGL to be enabled in your browser", 0., 30.)
-                        .unwrap();
-                    panic!("Cannot proceed without WebGL - aborting")
+                    if let Ok(Some(context_2d)) = canvas.get_context("2d") {
+                        if let Ok(context_2d) =
+                            context_2d.dyn_into::<web_sys::CanvasRenderingContext2d>()
+                        {
+                            context_2d.set_font("20px serif");
+                            // We don't know if we're rendering on dark or white background, so choose a "color" in the middle for the text.
+                            context_2d.set_fill_style(&wasm_bindgen::JsValue::from_str("red"));
+                            // The message is only a hint, the error is returned anyway
+                            context_2d
+                                .fill_text(
+                                    "Slint requires WebGL to be enabled in your browser",
+                                    0.,
+                                    30.,
+                                )
+                                .ok();
+                        }
+                    }
+                    return Err("Cannot proceed without WebGL".into());
                 }
             };
 
@@ -225,20 +407,24 @@ pub fn new_context_and_renderer(
                 canvas.client_height() as u32,
             );
 
-            let window = Rc::new(crate::event_loop::with_window_target(|event_loop| {
-                window_builder
-                    .with_canvas(Some(canvas.clone()))
-                    .build(&event_loop.event_loop_target())
-                    .unwrap()
-            }));
+            let window = Rc::new(
+                crate::event_loop::with_window_target(|event_loop| {
+                    window_builder
+                        .with_canvas(Some(canvas.clone()))
+                        .build(&event_loop.event_loop_target())
+                })
+                .map_err(|err| format!("Failed to create the window in the canvas: {}", err))?,
+            );
 
             // Try to maintain the existing size of the canvas element. A window created with winit
-            // on the web will always have 1024x768 as size otherwise.
+            // on the web will always have 1024x768 as size otherwise. The canvas can be resized by
+            // the layout of the page without the browser window being resized, so observe the
+            // canvas itself.
 
             let resize_canvas = {
                 let window = window.clone();
                 let canvas = canvas.clone();
-                move |_: web_sys::Event| {
+                move |_: js_sys::Array| {
                     let existing_canvas_size = winit::dpi::LogicalSize::new(
                         canvas.client_width() as u32,
                         canvas.client_height() as u32,
@@ -257,10 +443,8 @@ pub fn new_context_and_renderer(
 
             let resize_closure =
                 wasm_bindgen::closure::Closure::wrap(Box::new(resize_canvas) as Box<dyn FnMut(_)>);
-            web_sys::window()
-                .unwrap()
-                .add_event_listener_with_callback("resize", resize_closure.as_ref().unchecked_ref())
-                .unwrap();
+            resize_observer::ResizeObserver::new(resize_closure.as_ref().unchecked_ref())
+                .observe(&canvas);
             resize_closure.forget();
 
             {
@@ -282,15 +466,81 @@ pub fn new_context_and_renderer(
                 }
             }
 
-            (Self(RefCell::new(Some(OpenGLContextState::Current { window, canvas }))), renderer)
+            Ok((Self(RefCell::new(Some(OpenGLContextState::Current { window, canvas }))), renderer))
         }
     }
 
+    /// Creates the OpenGL context of a window that is shown as a layer-shell surface. Returns None
+    /// when the surface can't be created, to show a regular window instead.
+    #[cfg(all(
+        feature = "wayland",
+        not(any(
+            target_family = "windows",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "android",
+            target_arch = "wasm32"
+        ))
+    ))]
+    pub fn new_layer_shell_context_and_renderer(
+        window_builder: winit::window::WindowBuilder,
+        config: &i_slint_core::api::LayerShell,
+        size: winit::dpi::LogicalSize<f32>,
+        scale_factor_override: Option<f64>,
+        runtime_window: std::rc::Weak<i_slint_core::window::Window>,
+    ) -> Option<(Self, femtovg::renderer::OpenGl)> {
+        let context_builder = glutin::ContextBuilder::new()
+            .with_vsync(true)
+            .with_gl(glutin::GlRequest::Specific(glutin::Api::OpenGlEs, (2, 0)));
+        let (surface, context) = crate::layer_shell::LayerShellSurface::new(
+            window_builder,
+            context_builder,
+            config,
+            size,
+            scale_factor_override,
+            runtime_window,
+        )?;
+        let context = unsafe { context.make_current().unwrap() };
+        let renderer = femtovg::renderer::OpenGl::new_from_glutin_context(&context).unwrap();
+        Some((
+            Self(RefCell::new(Some(OpenGLContextState::LayerShellCurrent(context, surface)))),
+            renderer,
+        ))
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn get_proc_address(&self, name: &str) -> *const std::ffi::c_void {
         match &self.0.borrow().as_ref().unwrap() {
-            OpenGLContextState::NotCurrent(_) => std::ptr::null(),
             OpenGLContextState::Current(current_ctx) => current_ctx.get_proc_address(name),
+            #[cfg(all(
+                feature = "wayland",
+                not(any(
+                    target_family = "windows",
+                    target_os = "macos",
+                    target_os = "ios",
+                    target_os = "android",
+                    target_arch = "wasm32"
+                ))
+            ))]
+            OpenGLContextState::LayerShellCurrent(current_ctx, _) => {
+                current_ctx.get_proc_address(name)
+            }
+            _ => std::ptr::null(),
         }
     }
 }
+
+#[cfg(target_arch = "wasm32")]
+mod resize_observer {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        /// The ResizeObserver of the browser, which web-sys only exposes as an unstable API
+        pub type ResizeObserver;
+        #[wasm_bindgen(constructor)]
+        pub fn new(callback: &js_sys::Function) -> ResizeObserver;
+        #[wasm_bindgen(method)]
+        pub fn observe(this: &ResizeObserver, target: &web_sys::Element);
+    }
+}
@@ -13,7 +13,10 @@
 use crate::event_loop::WinitWindow;
 use crate::glcontext::OpenGLContext;
 use const_field_offset::FieldOffsets;
-use corelib::api::{GraphicsAPI, RenderingNotifier, RenderingState, SetRenderingNotifierError};
+use corelib::api::{
+    GraphicsAPI, RendererStatistics, RenderingNotifier, RenderingState, SetRenderingNotifierError,
+    WindowBackdrop, WindowType,
+};
 use corelib::component::ComponentRc;
 use corelib::graphics::*;
 use corelib::input::KeyboardModifiers;
@@ -35,6 +38,8 @@ pub struct GLWindow {
     map_state: RefCell<GraphicsWindowBackendState>,
     keyboard_modifiers: std::cell::Cell<KeyboardModifiers>,
     currently_pressed_key_code: std::cell::Cell<Option<winit::event::VirtualKeyCode>>,
+    currently_pressed_scancode: std::cell::Cell<(i32, bool)>,
+    pressed_scancodes: RefCell<std::collections::HashSet<u32>>,
 
     pub(crate) graphics_cache: RefCell<ItemGraphicsCache>,
     // This cache only contains textures. The cache for decoded CPU side images is in crate::IMAGE_CACHE.
@@ -64,6 +69,8 @@ pub(crate) fn new(
             map_state: RefCell::new(GraphicsWindowBackendState::Unmapped),
             keyboard_modifiers: Default::default(),
             currently_pressed_key_code: Default::default(),
+            currently_pressed_scancode: Default::default(),
+            pressed_scancodes: Default::default(),
             graphics_cache: Default::default(),
             texture_cache: Default::default(),
             fps_counter: FPSCounter::new(),
@@ -73,6 +80,69 @@ pub(crate) fn new(
         })
     }
 
+    /// Creates the OpenGL context of a window that has a layer-shell configuration. Returns None
+    /// for the other windows, and when the window can't be shown as a layer-shell surface.
+    #[cfg(all(
+        feature = "wayland",
+        not(any(
+            target_family = "windows",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "android",
+            target_arch = "wasm32"
+        ))
+    ))]
+    fn new_layer_shell_context(
+        &self,
+        window_builder: &winit::window::WindowBuilder,
+        scale_factor_override: Option<f64>,
+    ) -> Option<(OpenGLContext, femtovg::renderer::OpenGl)> {
+        let runtime_window = self.runtime_window();
+        let config = runtime_window.layer_shell()?;
+        let component_rc = runtime_window.component();
+        let component = ComponentRc::borrow_pin(&component_rc);
+        let size = LogicalSize::new(
+            component.as_ref().layout_info(Orientation::Horizontal).preferred_bounded(),
+            component.as_ref().layout_info(Orientation::Vertical).preferred_bounded(),
+        );
+        let context = OpenGLContext::new_layer_shell_context_and_renderer(
+            window_builder.clone(),
+            &config,
+            size,
+            scale_factor_override,
+            self.self_weak.clone(),
+        );
+        if context.is_none() {
+            corelib::debug_log!(
+                "The layer-shell surface could not be created, showing a regular window instead"
+            );
+        }
+        context
+    }
+
+    #[cfg(all(
+        not(target_arch = "wasm32"),
+        not(all(
+            feature = "wayland",
+            not(any(
+                target_family = "windows",
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "android"
+            ))
+        ))
+    ))]
+    fn new_layer_shell_context(
+        &self,
+        _window_builder: &winit::window::WindowBuilder,
+        _scale_factor_override: Option<f64>,
+    ) -> Option<(OpenGLContext, femtovg::renderer::OpenGl)> {
+        if self.runtime_window().layer_shell().is_some() {
+            corelib::debug_log!("Layer-shell surfaces are only supported on Wayland, showing a regular window instead");
+        }
+        None
+    }
+
     fn with_current_context<T>(&self, cb: impl FnOnce(&OpenGLContext) -> T) -> Option<T> {
         match &*self.map_state.borrow() {
             GraphicsWindowBackendState::Unmapped => None,
@@ -147,32 +217,23 @@ fn invoke_rendering_notifier(&self, state: RenderingState, opengl_context: &Open
     fn has_rendering_notifier(&self) -> bool {
         self.rendering_notifier.borrow().is_some()
     }
-}
-
-impl WinitWindow for GLWindow {
-    fn runtime_window(&self) -> Rc<corelib::window::Window> {
-        self.self_weak.upgrade().unwrap()
-    }
-
-    fn currently_pressed_key_code(&self) -> &Cell<Option<winit::event::VirtualKeyCode>> {
-        &self.currently_pressed_key_code
-    }
-
-    fn current_keyboard_modifiers(&self) -> &Cell<KeyboardModifiers> {
-        &self.keyboard_modifiers
-    }
 
-    /// Draw the items of the specified `component` in the given window.
-    fn draw(self: Rc<Self>) {
+    /// Draw the items of the component in the window. When `take_snapshot` is true, the rendered
+    /// pixels are read back before the buffers are swapped and returned.
+    fn draw_frame(
+        self: Rc<Self>,
+        take_snapshot: bool,
+    ) -> Option<corelib::graphics::SharedPixelBuffer<corelib::graphics::Rgba8Pixel>> {
         let runtime_window = self.self_weak.upgrade().unwrap();
         let scale_factor = runtime_window.scale_factor();
+        let mut snapshot = None;
         runtime_window.clone().draw_contents(|components| {
             let window = match self.borrow_mapped_window() {
                 Some(window) => window,
                 None => return, // caller bug, doesn't make sense to call draw() when not mapped
             };
 
-            let size = window.opengl_context.window().inner_size();
+            let size = window.opengl_context.inner_size();
 
             window.opengl_context.make_current();
             window.opengl_context.ensure_resized();
@@ -183,12 +244,16 @@ fn draw(self: Rc<Self>) {
                 // text metrics. Since we do the entire translation from logical pixels to physical
                 // pixels on our end, we don't need femtovg to scale a second time.
                 canvas.set_size(size.width, size.height, 1.0);
+                let clear_color = match window.backdrop_fallback_color {
+                    Some(under) => blend_over(window.clear_color, under),
+                    None => window.clear_color,
+                };
                 canvas.clear_rect(
                     0,
                     0,
                     size.width,
                     size.height,
-                    crate::to_femtovg_color(&window.clear_color),
+                    crate::to_femtovg_color(&clear_color),
                 );
                 // For the BeforeRendering rendering notifier callback it's important that this happens *after* clearing
                 // the back buffer, in order to allow the callback to provide its own rendering of the background.
@@ -209,6 +274,7 @@ fn draw(self: Rc<Self>) {
                 layer_images_to_delete_after_flush: Default::default(),
                 graphics_window: self.clone(),
                 scale_factor,
+                rendering_quality: runtime_window.rendering_quality(),
                 state: vec![crate::State {
                     scissor: Rect::new(
                         Point::default(),
@@ -216,6 +282,7 @@ fn draw(self: Rc<Self>) {
                     ),
                     global_alpha: 1.,
                     layer: None,
+                    offset: Some(Default::default()),
                 }],
             };
 
@@ -229,6 +296,17 @@ fn draw(self: Rc<Self>) {
 
             renderer.canvas.borrow_mut().flush();
 
+            if take_snapshot {
+                snapshot = renderer.canvas.borrow_mut().screenshot().ok().map(|image| {
+                    let mut buffer = corelib::graphics::SharedPixelBuffer::new(
+                        image.width() as u32,
+                        image.height() as u32,
+                    );
+                    buffer.make_mut_slice().copy_from_slice(image.buf());
+                    buffer
+                });
+            }
+
             // Delete any images and layer images (and their FBOs) before making the context not current anymore, to
             // avoid GPU memory leaks.
             renderer.graphics_window.texture_cache.borrow_mut().drain();
@@ -240,6 +318,38 @@ fn draw(self: Rc<Self>) {
             window.opengl_context.swap_buffers();
             window.opengl_context.make_not_current();
         });
+        snapshot
+    }
+}
+
+impl WinitWindow for GLWindow {
+    fn runtime_window(&self) -> Rc<corelib::window::Window> {
+        self.self_weak.upgrade().unwrap()
+    }
+
+    fn currently_pressed_key_code(&self) -> &Cell<Option<winit::event::VirtualKeyCode>> {
+        &self.currently_pressed_key_code
+    }
+
+    fn currently_pressed_scancode(&self) -> &Cell<(i32, bool)> {
+        &self.currently_pressed_scancode
+    }
+
+    fn pressed_scancodes(&self) -> &RefCell<std::collections::HashSet<u32>> {
+        &self.pressed_scancodes
+    }
+
+    fn current_keyboard_modifiers(&self) -> &Cell<KeyboardModifiers> {
+        &self.keyboard_modifiers
+    }
+
+    /// Draw the items of the specified `component` in the given window.
+    fn draw(self: Rc<Self>) {
+        self.draw_frame(false);
+    }
+
+    fn is_layer_shell(&self) -> bool {
+        self.borrow_mapped_window().map_or(false, |window| window.opengl_context.is_layer_shell())
     }
 
     fn with_window_handle(&self, callback: &mut dyn FnMut(&winit::window::Window)) {
@@ -325,6 +435,127 @@ fn set_rendering_notifier(
         }
     }
 
+    fn take_snapshot(
+        self: Rc<Self>,
+    ) -> Option<corelib::graphics::SharedPixelBuffer<corelib::graphics::Rgba8Pixel>> {
+        if !self.is_mapped() {
+            return None;
+        }
+        self.draw_frame(true)
+    }
+
+    fn render_item_to_image(
+        self: Rc<Self>,
+        item: &corelib::items::ItemRc,
+    ) -> Option<corelib::graphics::SharedPixelBuffer<corelib::graphics::Rgba8Pixel>> {
+        let runtime_window = self.self_weak.upgrade().unwrap();
+        let scale_factor = runtime_window.scale_factor();
+        let window = self.borrow_mapped_window()?;
+
+        let logical_size = item.borrow().as_ref().geometry().size;
+        let width = (logical_size.width * scale_factor).ceil() as u32;
+        let height = (logical_size.height * scale_factor).ceil() as u32;
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        window.opengl_context.make_current();
+        let canvas = window.canvas.as_ref().unwrap().clone();
+        let image = match crate::images::CachedImage::new_empty_on_gpu(&canvas, width, height) {
+            Some(image) => image,
+            None => {
+                window.opengl_context.make_not_current();
+                return None;
+            }
+        };
+
+        {
+            let mut canvas = canvas.borrow_mut();
+            canvas.set_size(width, height, 1.0);
+            canvas.save();
+            canvas.set_render_target(image.as_render_target());
+            canvas.reset();
+            canvas.clear_rect(0, 0, width, height, femtovg::Color::rgba(0, 0, 0, 0));
+        }
+
+        let mut renderer = crate::GLItemRenderer {
+            canvas: canvas.clone(),
+            layer_images_to_delete_after_flush: Default::default(),
+            graphics_window: self.clone(),
+            scale_factor,
+            rendering_quality: runtime_window.rendering_quality(),
+            state: vec![crate::State {
+                scissor: Rect::new(Point::default(), logical_size),
+                global_alpha: 1.,
+                // The layer makes the image the render target of the renderer
+                layer: Some(Rc::new(crate::Layer { image, target_path: femtovg::Path::new() })),
+                offset: Some(Default::default()),
+            }],
+        };
+
+        corelib::item_rendering::render_item_tree(item, &mut renderer, Point::default());
+
+        let buffer = {
+            let mut canvas = canvas.borrow_mut();
+            canvas.flush();
+            let buffer = canvas.screenshot().ok().map(|image| {
+                let mut buffer = corelib::graphics::SharedPixelBuffer::new(
+                    image.width() as u32,
+                    image.height() as u32,
+                );
+                buffer.make_mut_slice().copy_from_slice(image.buf());
+                buffer
+            });
+            canvas.restore();
+            canvas.set_render_target(femtovg::RenderTarget::Screen);
+            buffer
+        };
+
+        // Delete the image and the layers before making the context not current, like after
+        // drawing a frame
+        drop(renderer);
+        self.texture_cache.borrow_mut().drain();
+
+        window.opengl_context.make_not_current();
+        buffer
+    }
+
+    fn renderer_statistics(&self) -> RendererStatistics {
+        let mut statistics = RendererStatistics::default();
+        let graphics_cache = self.graphics_cache.borrow();
+        statistics.cached_item_count = graphics_cache.iter().count();
+
+        let canvas = match self.borrow_mapped_window().and_then(|window| window.canvas.clone()) {
+            Some(canvas) => canvas,
+            None => return statistics,
+        };
+        let canvas = canvas.borrow();
+
+        // The cache entries of the items share the textures of the texture cache
+        let mut textures = std::collections::HashSet::new();
+        for entry in graphics_cache.iter().flatten() {
+            textures.extend(entry.as_image().texture_id());
+            if let crate::ItemGraphicsCacheEntry::ColorizedImage {
+                _original_image: original_image,
+                ..
+            } = entry
+            {
+                textures.extend(original_image.texture_id());
+            }
+        }
+        textures
+            .extend(self.texture_cache.borrow().images().filter_map(|image| image.texture_id()));
+        statistics.texture_count = textures.len();
+        statistics.texture_memory =
+            textures.into_iter().map(|id| crate::images::texture_memory(&canvas, id)).sum();
+
+        for id in canvas.debug_inspector_get_font_textures() {
+            statistics.glyph_atlas_texture_count += 1;
+            statistics.glyph_atlas_memory += crate::images::texture_memory(&canvas, id);
+        }
+        statistics
+    }
+
     fn show_popup(&self, popup: &ComponentRc, position: Point) {
         let runtime_window = self.self_weak.upgrade().unwrap();
         let size = runtime_window.set_active_popup(PopupWindow {
@@ -436,13 +667,29 @@ fn show(self: Rc<Self>) {
 
         let window_builder =
             if no_frame { window_builder.with_decorations(false) } else { window_builder };
+        let window_builder = apply_window_type(window_builder, runtime_window.window_type());
+        let dark_title_bar = runtime_window.dark_title_bar();
+        let backdrop = runtime_window.backdrop();
+        // The backdrop shows through the transparent pixels of the window
+        #[cfg(target_os = "windows")]
+        let window_builder = window_builder.with_transparent(backdrop != WindowBackdrop::None);
 
         #[cfg(target_arch = "wasm32")]
-        let (opengl_context, renderer) =
+        let context =
             crate::OpenGLContext::new_context_and_renderer(window_builder, &self.canvas_id);
         #[cfg(not(target_arch = "wasm32"))]
-        let (opengl_context, renderer) =
-            crate::OpenGLContext::new_context_and_renderer(window_builder);
+        let context = match self.new_layer_shell_context(&window_builder, scale_factor_override) {
+            Some(context) => Ok(context),
+            None => crate::OpenGLContext::new_context_and_renderer(window_builder),
+        };
+        // The window stays hidden if its context cannot be created
+        let (opengl_context, renderer) = match context {
+            Ok(context) => context,
+            Err(err) => {
+                corelib::debug_log!("Cannot show the window: {}", err);
+                return;
+            }
+        };
 
         let canvas = femtovg::Canvas::new_with_text_context(
             renderer,
@@ -457,9 +704,25 @@ fn show(self: Rc<Self>) {
         let canvas = Rc::new(RefCell::new(canvas));
 
         let platform_window = opengl_context.window();
+        #[cfg(target_os = "windows")]
+        let has_backdrop = apply_windows_style(&platform_window, dark_title_bar, backdrop);
+        #[cfg(not(target_os = "windows"))]
+        let has_backdrop = false;
+        let backdrop_fallback_color =
+            (backdrop != WindowBackdrop::None && !has_backdrop).then(|| {
+                if dark_title_bar {
+                    Color::from_rgb_u8(0, 0, 0)
+                } else {
+                    Color::from_rgb_u8(255, 255, 255)
+                }
+            });
+
         let runtime_window = self.self_weak.upgrade().unwrap();
         runtime_window.set_scale_factor(
-            scale_factor_override.unwrap_or_else(|| platform_window.scale_factor()) as _,
+            opengl_context
+                .surface_scale_factor()
+                .or(scale_factor_override)
+                .unwrap_or_else(|| platform_window.scale_factor()) as _,
         );
         let id = platform_window.id();
 
@@ -504,6 +767,7 @@ fn show(self: Rc<Self>) {
             canvas: Some(canvas),
             opengl_context,
             clear_color: RgbaColor { red: 255_u8, green: 255, blue: 255, alpha: 255 }.into(),
+            backdrop_fallback_color,
             constraints: Default::default(),
         }));
 
@@ -579,47 +843,94 @@ fn text_input_byte_offset_for_position(
         text_input: Pin<&i_slint_core::items::TextInput>,
         pos: Point,
     ) -> usize {
+        let text = text_input.text();
+        let is_password = matches!(text_input.input_type(), corelib::items::InputType::password);
+        let password_string;
+        let actual_text = if is_password {
+            password_string = PASSWORD_CHARACTER.repeat(text.chars().count());
+            password_string.as_str()
+        } else {
+            text.as_str()
+        };
+
+        let result = self
+            .byte_offset_for_position(&TextLayout::from_text_input(text_input, actual_text), pos);
+
+        if is_password {
+            text.char_indices()
+                .nth(result / PASSWORD_CHARACTER.len())
+                .map_or(text.len(), |(r, _)| r)
+        } else {
+            result
+        }
+    }
+
+    fn text_input_position_for_byte_offset(
+        &self,
+        text_input: Pin<&corelib::items::TextInput>,
+        byte_offset: usize,
+    ) -> Point {
+        let text = text_input.text();
+        self.position_for_byte_offset(&TextLayout::from_text_input(text_input, &text), byte_offset)
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&corelib::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        let string = text.text();
+        Some(self.byte_offset_for_position(&TextLayout::from_text(text, &string), pos))
+    }
+
+    fn text_position_for_byte_offset(
+        &self,
+        text: Pin<&corelib::items::Text>,
+        byte_offset: usize,
+    ) -> Option<Point> {
+        let string = text.text();
+        Some(self.position_for_byte_offset(&TextLayout::from_text(text, &string), byte_offset))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl GLWindow {
+    /// Returns the byte offset of the glyph of the laid out text that is nearest to the position
+    fn byte_offset_for_position(&self, layout: &TextLayout, pos: Point) -> usize {
         let scale_factor = self.self_weak.upgrade().unwrap().scale_factor();
         let pos = pos * scale_factor;
-        let text = text_input.text();
 
-        let mut result = text.len();
+        let mut result = layout.text.len();
 
-        let width = text_input.width() * scale_factor;
-        let height = text_input.height() * scale_factor;
+        let width = layout.size.width * scale_factor;
+        let height = layout.size.height * scale_factor;
         if width <= 0. || height <= 0. {
             return 0;
         }
 
         let font = crate::fonts::FONT_CACHE.with(|cache| {
             cache.borrow_mut().font(
-                text_input.unresolved_font_request().merge(&self.default_font_properties()),
+                layout.font_request.clone().merge(&self.default_font_properties()),
                 scale_factor,
-                &text_input.text(),
+                layout.text,
             )
         });
 
-        let is_password = matches!(text_input.input_type(), corelib::items::InputType::password);
-        let password_string;
-        let actual_text = if is_password {
-            password_string = PASSWORD_CHARACTER.repeat(text.chars().count());
-            password_string.as_str()
-        } else {
-            text.as_str()
-        };
-
-        let paint = font.init_paint(text_input.letter_spacing() * scale_factor, Default::default());
+        let paint = font.init_paint(layout.letter_spacing * scale_factor, Default::default());
         let text_context =
             crate::fonts::FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
         let font_height = text_context.measure_font(paint).unwrap().height();
         crate::fonts::layout_text_lines(
-            actual_text,
+            layout.text,
             &font,
             Size::new(width, height),
-            (text_input.horizontal_alignment(), text_input.vertical_alignment()),
-            text_input.wrap(),
-            i_slint_core::items::TextOverflow::clip,
-            text_input.single_line(),
+            layout.alignment,
+            layout.wrap,
+            layout.overflow,
+            layout.single_line,
             paint,
             |line_text, line_pos, start, metrics| {
                 if (line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
@@ -635,49 +946,38 @@ fn text_input_byte_offset_for_position(
                 }
             },
         );
-
-        if is_password {
-            text.char_indices()
-                .nth(result / PASSWORD_CHARACTER.len())
-                .map_or(text.len(), |(r, _)| r)
-        } else {
-            result
-        }
+        result
     }
 
-    fn text_input_position_for_byte_offset(
-        &self,
-        text_input: Pin<&corelib::items::TextInput>,
-        byte_offset: usize,
-    ) -> Point {
+    /// Returns the position of the glyph at the byte offset of the laid out text
+    fn position_for_byte_offset(&self, layout: &TextLayout, byte_offset: usize) -> Point {
         let scale_factor = self.self_weak.upgrade().unwrap().scale_factor();
-        let text = text_input.text();
 
         let mut result = Point::default();
 
-        let width = text_input.width() * scale_factor;
-        let height = text_input.height() * scale_factor;
+        let width = layout.size.width * scale_factor;
+        let height = layout.size.height * scale_factor;
         if width <= 0. || height <= 0. {
             return result;
         }
 
         let font = crate::fonts::FONT_CACHE.with(|cache| {
             cache.borrow_mut().font(
-                text_input.unresolved_font_request().merge(&self.default_font_properties()),
+                layout.font_request.clone().merge(&self.default_font_properties()),
                 scale_factor,
-                &text_input.text(),
+                layout.text,
             )
         });
 
-        let paint = font.init_paint(text_input.letter_spacing() * scale_factor, Default::default());
+        let paint = font.init_paint(layout.letter_spacing * scale_factor, Default::default());
         crate::fonts::layout_text_lines(
-            text.as_str(),
+            layout.text,
             &font,
             Size::new(width, height),
-            (text_input.horizontal_alignment(), text_input.vertical_alignment()),
-            text_input.wrap(),
-            i_slint_core::items::TextOverflow::clip,
-            text_input.single_line(),
+            layout.alignment,
+            layout.wrap,
+            layout.overflow,
+            layout.single_line,
             paint,
             |line_text, line_pos, start, metrics| {
                 if (start..=(start + line_text.len())).contains(&byte_offset) {
@@ -696,9 +996,46 @@ fn text_input_position_for_byte_offset(
 
         result / scale_factor
     }
+}
 
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+/// The properties of a `Text` or a `TextInput` that determine where its glyphs are laid out
+struct TextLayout<'a> {
+    text: &'a str,
+    font_request: corelib::graphics::FontRequest,
+    letter_spacing: f32,
+    size: Size,
+    alignment: (corelib::items::TextHorizontalAlignment, corelib::items::TextVerticalAlignment),
+    wrap: corelib::items::TextWrap,
+    overflow: corelib::items::TextOverflow,
+    single_line: bool,
+}
+
+impl<'a> TextLayout<'a> {
+    fn from_text(text: Pin<&corelib::items::Text>, string: &'a str) -> Self {
+        Self {
+            text: string,
+            font_request: text.unresolved_font_request(),
+            letter_spacing: text.letter_spacing(),
+            size: Size::new(text.width(), text.height()),
+            alignment: (text.horizontal_alignment(), text.vertical_alignment()),
+            wrap: text.wrap(),
+            overflow: text.overflow(),
+            single_line: false,
+        }
+    }
+
+    /// `string` is the text that is shown, which is not the text of the element for a password
+    fn from_text_input(text_input: Pin<&corelib::items::TextInput>, string: &'a str) -> Self {
+        Self {
+            text: string,
+            font_request: text_input.unresolved_font_request(),
+            letter_spacing: text_input.letter_spacing(),
+            size: Size::new(text_input.width(), text_input.height()),
+            alignment: (text_input.horizontal_alignment(), text_input.vertical_alignment()),
+            wrap: text_input.wrap(),
+            overflow: corelib::items::TextOverflow::clip,
+            single_line: text_input.single_line(),
+        }
     }
 }
 
@@ -712,6 +1049,9 @@ struct MappedWindow {
     canvas: Option<CanvasRc>,
     opengl_context: crate::OpenGLContext,
     clear_color: Color,
+    /// When a backdrop was requested but isn't supported, the background is blended over this
+    /// opaque color instead of showing what is behind the window
+    backdrop_fallback_color: Option<Color>,
     constraints: Cell<(corelib::layout::LayoutInfo, corelib::layout::LayoutInfo)>,
 }
 
@@ -747,3 +1087,119 @@ fn default() -> Self {
         Self { scale_factor: Property::new(1.0) }
     }
 }
+
+/// Pass the type of the window to the windowing system, so that the windows that are not normal
+/// are not shown in the task bar, and the tooltips and notifications don't take the focus.
+fn apply_window_type(
+    window_builder: winit::window::WindowBuilder,
+    window_type: WindowType,
+) -> winit::window::WindowBuilder {
+    if window_type == WindowType::Normal {
+        return window_builder;
+    }
+    let is_popup =
+        matches!(window_type, WindowType::Splash | WindowType::Tooltip | WindowType::Notification);
+    let window_builder = if is_popup {
+        window_builder.with_decorations(false).with_always_on_top(true)
+    } else {
+        window_builder
+    };
+
+    #[cfg(all(
+        unix,
+        not(any(
+            target_os = "macos",
+            target_os = "android",
+            target_os = "ios",
+            target_os = "emscripten"
+        )),
+        feature = "x11"
+    ))]
+    let window_builder = {
+        use winit::platform::unix::{WindowBuilderExtUnix, XWindowType};
+        let x11_window_type = match window_type {
+            WindowType::Splash => XWindowType::Splash,
+            WindowType::Tooltip => XWindowType::Tooltip,
+            WindowType::Utility => XWindowType::Utility,
+            WindowType::Notification => XWindowType::Notification,
+            _ => XWindowType::Normal,
+        };
+        // The window manager doesn't manage, and so doesn't focus, the override-redirect windows
+        window_builder.with_x11_window_type(vec![x11_window_type]).with_override_redirect(matches!(
+            window_type,
+            WindowType::Tooltip | WindowType::Notification
+        ))
+    };
+
+    #[cfg(target_os = "windows")]
+    let window_builder = {
+        use winit::platform::windows::WindowBuilderExtWindows;
+        window_builder.with_skip_taskbar(true)
+    };
+
+    window_builder
+}
+
+/// Blends `color` over the opaque color `under`.
+fn blend_over(color: Color, under: Color) -> Color {
+    let color = color.to_argb_f32();
+    let under = under.to_argb_f32();
+    let blend = |c: f32, u: f32| c * color.alpha + u * (1. - color.alpha);
+    Color::from_rgb_f32(
+        blend(color.red, under.red),
+        blend(color.green, under.green),
+        blend(color.blue, under.blue),
+    )
+}
+
+/// Draws the title bar in dark mode and sets the backdrop of the window with the attributes of
+/// the desktop window manager. Returns whether the backdrop is shown, which needs Windows 11.
+#[cfg(target_os = "windows")]
+fn apply_windows_style(
+    window: &winit::window::Window,
+    dark_title_bar: bool,
+    backdrop: WindowBackdrop,
+) -> bool {
+    use winapi::shared::minwindef::{DWORD, TRUE};
+    use winapi::shared::winerror::SUCCEEDED;
+    use winapi::um::dwmapi::{DwmExtendFrameIntoClientArea, DwmSetWindowAttribute};
+    use winapi::um::uxtheme::MARGINS;
+    use winit::platform::windows::WindowExtWindows;
+
+    // These attributes are only declared by the recent versions of the Windows SDK
+    const DWMWA_USE_IMMERSIVE_DARK_MODE: DWORD = 20;
+    // The value of DWMWA_USE_IMMERSIVE_DARK_MODE before Windows 10 20H1
+    const DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1: DWORD = 19;
+    const DWMWA_SYSTEMBACKDROP_TYPE: DWORD = 38;
+    // The undocumented attribute that enables Mica before Windows 11 22H2
+    const DWMWA_MICA_EFFECT: DWORD = 1029;
+    const DWMSBT_MAINWINDOW: DWORD = 2;
+    const DWMSBT_TRANSIENTWINDOW: DWORD = 3;
+
+    let hwnd = window.hwnd() as winapi::shared::windef::HWND;
+    let set_attribute = |attribute: DWORD, value: DWORD| unsafe {
+        SUCCEEDED(DwmSetWindowAttribute(
+            hwnd,
+            attribute,
+            &value as *const DWORD as _,
+            std::mem::size_of::<DWORD>() as DWORD,
+        ))
+    };
+
+    if dark_title_bar && !set_attribute(DWMWA_USE_IMMERSIVE_DARK_MODE, TRUE as DWORD) {
+        set_attribute(DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1, TRUE as DWORD);
+    }
+
+    let has_backdrop = match backdrop {
+        WindowBackdrop::None => return false,
+        WindowBackdrop::Mica => {
+            set_attribute(DWMWA_SYSTEMBACKDROP_TYPE, DWMSBT_MAINWINDOW)
+                || set_attribute(DWMWA_MICA_EFFECT, TRUE as DWORD)
+        }
+        WindowBackdrop::Acrylic => set_attribute(DWMWA_SYSTEMBACKDROP_TYPE, DWMSBT_TRANSIENTWINDOW),
+    };
+    // The backdrop is only drawn in the frame, unless it covers the whole window
+    let margins =
+        MARGINS { cxLeftWidth: -1, cxRightWidth: -1, cyTopHeight: -1, cyBottomHeight: -1 };
+    has_backdrop && SUCCEEDED(unsafe { DwmExtendFrameIntoClientArea(hwnd, &margins) })
+}
@@ -435,6 +435,13 @@ pub(crate) fn as_paint(&self) -> femtovg::Paint {
         }
     }
 
+    pub(crate) fn texture_id(&self) -> Option<femtovg::ImageId> {
+        match &*self.0.borrow() {
+            ImageData::Texture(tex) => Some(tex.id),
+            _ => None,
+        }
+    }
+
     pub(crate) fn is_on_gpu(&self) -> bool {
         matches!(&*self.0.borrow(), ImageData::Texture(_))
     }
@@ -557,6 +564,10 @@ pub(crate) fn lookup_image_in_cache_or_create(
         })
     }
 
+    pub(crate) fn images(&self) -> impl Iterator<Item = &Rc<CachedImage>> {
+        self.0.values()
+    }
+
     pub(crate) fn drain(&mut self) {
         self.0.retain(|_, cached_image| {
             // * Retain images that are used by elements, so that they can be effectively
@@ -576,6 +587,18 @@ pub(crate) fn clear(&mut self) {
     }
 }
 
+/// Returns the memory that the texture uses on the GPU, in bytes
+pub(crate) fn texture_memory(canvas: &super::Canvas, image_id: femtovg::ImageId) -> usize {
+    canvas.image_info(image_id).map_or(0, |info| {
+        let bytes_per_pixel = match info.format() {
+            femtovg::PixelFormat::Rgb8 => 3,
+            femtovg::PixelFormat::Rgba8 => 4,
+            femtovg::PixelFormat::Gray8 => 1,
+        };
+        info.width() * info.height() * bytes_per_pixel
+    })
+}
+
 fn image_buffer_to_image_source(
     buffer: &SharedImageBuffer,
 ) -> (femtovg::ImageSource<'_>, femtovg::ImageFlags) {
@@ -0,0 +1,567 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Shows the windows that have a [`LayerShell`] configuration as surfaces of the wlr-layer-shell
+protocol of the Wayland compositors.
+
+winit can only create regular windows (xdg-shell toplevels). A layer-shell window still has a
+winit window, which identifies it in the event loop and receives its redraw requests, but that
+window is never mapped because nothing is rendered into it. The rendering goes to a `wl_surface`
+with the layer-shell role that is created here, with a raw EGL context. winit doesn't know this
+surface, so its pointer and keyboard events are received here from the seats, on a separate
+event queue of the Wayland connection of winit, and dispatched by [`dispatch_pending()`] from
+the event loop.
+*/
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
+
+use corelib::api::{LayerShell, LayerShellKeyboardInteractivity, LayerShellLayer};
+use corelib::graphics::Point;
+use corelib::input::{key_codes, KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent};
+use corelib::items::PointerEventButton;
+use corelib::SharedString;
+use i_slint_core as corelib;
+use smithay_client_toolkit::seat::keyboard;
+use wayland_client::protocol::{wl_compositor, wl_pointer, wl_seat, wl_surface};
+use wayland_client::{Display, EventQueue, GlobalManager, Main};
+use wayland_protocols::wlr::unstable::layer_shell::v1::client::{
+    zwlr_layer_shell_v1, zwlr_layer_surface_v1,
+};
+use winit::dpi::PhysicalSize;
+
+/// The objects of the Wayland connection of winit that are needed to create the layer surfaces
+struct Connection {
+    display: Display,
+    event_queue: RefCell<EventQueue>,
+    compositor: Main<wl_compositor::WlCompositor>,
+    layer_shell: Main<zwlr_layer_shell_v1::ZwlrLayerShellV1>,
+    // The seats keep sending the input events as long as they are alive
+    _seats: Vec<Main<wl_seat::WlSeat>>,
+}
+
+impl Connection {
+    /// Binds the globals on the display of winit. Returns None if the compositor doesn't
+    /// implement the wlr-layer-shell protocol.
+    fn new(display_ptr: *mut std::ffi::c_void) -> Option<Self> {
+        // Safety: the display is the one of the winit event loop, which outlives the windows
+        let display = unsafe { Display::from_external_display(display_ptr as *mut _) };
+        let mut event_queue = display.create_event_queue();
+        let attached_display = display.attach(event_queue.token());
+        let globals = GlobalManager::new(&attached_display);
+        event_queue.sync_roundtrip(&mut (), |_, _, _| {}).ok()?;
+
+        let compositor = globals.instantiate_range::<wl_compositor::WlCompositor>(1, 4).ok()?;
+        let layer_shell =
+            match globals.instantiate_range::<zwlr_layer_shell_v1::ZwlrLayerShellV1>(1, 4) {
+                Ok(layer_shell) => layer_shell,
+                Err(_) => {
+                    corelib::debug_log!(
+                        "The Wayland compositor doesn't support the wlr-layer-shell protocol"
+                    );
+                    return None;
+                }
+            };
+
+        let seats = globals
+            .list()
+            .into_iter()
+            .filter(|(_, interface, _)| interface == "wl_seat")
+            .map(|(id, _, version)| {
+                let seat =
+                    attached_display.get_registry().bind::<wl_seat::WlSeat>(version.min(5), id);
+                listen_to_seat(&seat);
+                seat
+            })
+            .collect();
+        event_queue.sync_roundtrip(&mut (), |_, _, _| {}).ok()?;
+
+        Some(Self {
+            display,
+            event_queue: RefCell::new(event_queue),
+            compositor,
+            layer_shell,
+            _seats: seats,
+        })
+    }
+}
+
+/// The events received from the compositor, which are applied to the windows after the event
+/// queue was dispatched, so that the handlers can create new layer surfaces.
+enum PendingEvent {
+    Configure(Weak<corelib::window::Window>, u32, u32),
+    Closed(Weak<corelib::window::Window>),
+    Mouse(Weak<corelib::window::Window>, MouseEvent),
+    Key(Weak<corelib::window::Window>, KeyEvent),
+    Focus(Weak<corelib::window::Window>, bool),
+}
+
+thread_local! {
+    /// The connection, created for the first layer surface. None if winit doesn't use Wayland or
+    /// the compositor doesn't implement the protocol.
+    static CONNECTION: once_cell::unsync::OnceCell<Option<Connection>> = Default::default();
+    /// The surfaces of the layer-shell windows, which receive the input events
+    static SURFACES: RefCell<Vec<(wl_surface::WlSurface, Weak<corelib::window::Window>)>> =
+        Default::default();
+    static PENDING_EVENTS: RefCell<Vec<PendingEvent>> = Default::default();
+}
+
+fn push_event(event: PendingEvent) {
+    PENDING_EVENTS.with(|events| events.borrow_mut().push(event))
+}
+
+fn window_of_surface(surface: &wl_surface::WlSurface) -> Option<Weak<corelib::window::Window>> {
+    SURFACES.with(|surfaces| {
+        surfaces
+            .borrow()
+            .iter()
+            .find(|(s, _)| s.as_ref().equals(surface.as_ref()))
+            .map(|(_, window)| window.clone())
+    })
+}
+
+/// The surface of a layer-shell window, and the winit window that stands for it in the event loop
+pub struct LayerShellSurface {
+    window: winit::window::Window,
+    surface: Main<wl_surface::WlSurface>,
+    layer_surface: Main<zwlr_layer_surface_v1::ZwlrLayerSurfaceV1>,
+    /// The size of the surface in physical pixels, as configured by the compositor
+    size: Rc<Cell<PhysicalSize<u32>>>,
+    scale_factor: i32,
+}
+
+impl LayerShellSurface {
+    /// Creates the surface and its OpenGL context. Returns None when winit doesn't use Wayland or
+    /// the compositor doesn't implement the protocol, to show a regular window instead.
+    ///
+    /// `size` is the preferred size of the window in logical pixels, which is used in the
+    /// directions in which the surface isn't stretched between two anchors.
+    pub fn new(
+        window_builder: winit::window::WindowBuilder,
+        context_builder: glutin::ContextBuilder<glutin::NotCurrent>,
+        config: &LayerShell,
+        size: winit::dpi::LogicalSize<f32>,
+        scale_factor_override: Option<f64>,
+        runtime_window: Weak<corelib::window::Window>,
+    ) -> Option<(Self, glutin::RawContext<glutin::NotCurrent>)> {
+        use glutin::platform::unix::RawContextExt;
+        use winit::platform::unix::EventLoopWindowTargetExtUnix;
+
+        let (display_ptr, window) = crate::event_loop::with_window_target(|event_loop| {
+            let target = event_loop.event_loop_target();
+            let display_ptr = target.wayland_display()?;
+            let window = window_builder.with_decorations(false).build(target).ok()?;
+            Some((display_ptr, window))
+        })?;
+
+        CONNECTION.with(|connection| {
+            let connection = connection.get_or_init(|| Connection::new(display_ptr)).as_ref()?;
+
+            let scale_factor =
+                scale_factor_override.unwrap_or_else(|| window.scale_factor()).round().max(1.);
+            let surface = connection.compositor.create_surface();
+            surface.set_buffer_scale(scale_factor as i32);
+
+            let layer = match config.layer {
+                LayerShellLayer::Background => zwlr_layer_shell_v1::Layer::Background,
+                LayerShellLayer::Bottom => zwlr_layer_shell_v1::Layer::Bottom,
+                LayerShellLayer::Top => zwlr_layer_shell_v1::Layer::Top,
+                LayerShellLayer::Overlay => zwlr_layer_shell_v1::Layer::Overlay,
+            };
+            let layer_surface = connection.layer_shell.get_layer_surface(
+                &surface,
+                None,
+                layer,
+                config.namespace.to_string(),
+            );
+
+            let mut anchor = zwlr_layer_surface_v1::Anchor::empty();
+            anchor.set(zwlr_layer_surface_v1::Anchor::Top, config.anchor_top);
+            anchor.set(zwlr_layer_surface_v1::Anchor::Bottom, config.anchor_bottom);
+            anchor.set(zwlr_layer_surface_v1::Anchor::Left, config.anchor_left);
+            anchor.set(zwlr_layer_surface_v1::Anchor::Right, config.anchor_right);
+            layer_surface.set_anchor(anchor);
+            // A size of 0 lets the compositor stretch the surface between the two anchors
+            let requested_width = if config.anchor_left && config.anchor_right {
+                0
+            } else {
+                size.width.ceil() as u32
+            };
+            let requested_height = if config.anchor_top && config.anchor_bottom {
+                0
+            } else {
+                size.height.ceil() as u32
+            };
+            layer_surface.set_size(requested_width, requested_height);
+            layer_surface.set_exclusive_zone(config.exclusive_zone);
+            let [top, right, bottom, left] = config.margin;
+            layer_surface.set_margin(top, right, bottom, left);
+            let keyboard_interactivity = match config.keyboard_interactivity {
+                LayerShellKeyboardInteractivity::None => {
+                    zwlr_layer_surface_v1::KeyboardInteractivity::None
+                }
+                LayerShellKeyboardInteractivity::Exclusive => {
+                    zwlr_layer_surface_v1::KeyboardInteractivity::Exclusive
+                }
+                // The on-demand mode only exists since the version 4 of the protocol
+                LayerShellKeyboardInteractivity::OnDemand
+                    if connection.layer_shell.as_ref().version() >= 4 =>
+                {
+                    zwlr_layer_surface_v1::KeyboardInteractivity::OnDemand
+                }
+                LayerShellKeyboardInteractivity::OnDemand => {
+                    zwlr_layer_surface_v1::KeyboardInteractivity::Exclusive
+                }
+            };
+            layer_surface.set_keyboard_interactivity(keyboard_interactivity);
+
+            let physical_size = |width: u32, height: u32| {
+                PhysicalSize::new(
+                    (width as f64 * scale_factor) as u32,
+                    (height as f64 * scale_factor) as u32,
+                )
+            };
+            let size = Rc::new(Cell::new(physical_size(requested_width, requested_height)));
+            layer_surface.quick_assign({
+                let size = size.clone();
+                let runtime_window = runtime_window.clone();
+                move |layer_surface, event, _| match event {
+                    zwlr_layer_surface_v1::Event::Configure { serial, width, height } => {
+                        layer_surface.ack_configure(serial);
+                        // 0 means that the client chooses the size in that direction
+                        let current = size.get();
+                        let configured = physical_size(width, height);
+                        size.set(PhysicalSize::new(
+                            if width > 0 { configured.width } else { current.width },
+                            if height > 0 { configured.height } else { current.height },
+                        ));
+                        push_event(PendingEvent::Configure(runtime_window.clone(), width, height));
+                    }
+                    zwlr_layer_surface_v1::Event::Closed => {
+                        push_event(PendingEvent::Closed(runtime_window.clone()))
+                    }
+                    _ => {}
+                }
+            });
+
+            // The compositor sends the first configure event after a commit without buffer. No
+            // buffer must be attached before it is acknowledged.
+            surface.commit();
+            connection.event_queue.borrow_mut().sync_roundtrip(&mut (), |_, _, _| {}).ok()?;
+
+            let PhysicalSize { width, height } = size.get();
+            // Safety: the display and the surface outlive the context, which is dropped first
+            let context = unsafe {
+                context_builder.build_raw_wayland_context(
+                    connection.display.get_display_ptr() as *mut _,
+                    surface.as_ref().c_ptr() as *mut _,
+                    width.max(1),
+                    height.max(1),
+                )
+            }
+            .map_err(|creation_error| {
+                corelib::debug_log!(
+                    "could not create the OpenGL context of the layer surface: {}",
+                    creation_error
+                )
+            })
+            .ok()?;
+
+            SURFACES.with(|surfaces| {
+                surfaces.borrow_mut().push(((*surface).clone().detach(), runtime_window))
+            });
+
+            Some((
+                Self { window, surface, layer_surface, size, scale_factor: scale_factor as i32 },
+                context,
+            ))
+        })
+    }
+
+    /// The winit window that stands for the surface in the event loop. It is never shown.
+    pub fn window(&self) -> &winit::window::Window {
+        &self.window
+    }
+
+    /// The size of the surface in physical pixels
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.size.get()
+    }
+
+    /// The scale factor of the surface, which doesn't follow the one of the winit window
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor as f64
+    }
+}
+
+impl Drop for LayerShellSurface {
+    fn drop(&mut self) {
+        SURFACES.with(|surfaces| {
+            surfaces
+                .borrow_mut()
+                .retain(|(surface, _)| !surface.as_ref().equals(self.surface.as_ref()))
+        });
+        self.layer_surface.destroy();
+        self.surface.destroy();
+    }
+}
+
+/// Dispatches the events that winit read from the Wayland connection for the layer surfaces, and
+/// applies them to their windows. Called from the event loop for every iteration.
+pub fn dispatch_pending() {
+    CONNECTION.with(|connection| {
+        if let Some(Some(connection)) = connection.get() {
+            if let Err(err) =
+                connection.event_queue.borrow_mut().dispatch_pending(&mut (), |_, _, _| {})
+            {
+                corelib::debug_log!("Error dispatching the events of the layer surfaces: {}", err);
+            }
+        }
+    });
+
+    let events = PENDING_EVENTS.with(|events| events.take());
+    if !events.is_empty() {
+        corelib::animations::update_animations();
+    }
+    for event in events {
+        match event {
+            PendingEvent::Configure(window, width, height) => {
+                if let Some(window) = window.upgrade() {
+                    if width > 0 && height > 0 {
+                        window.set_window_item_geometry(width as f32, height as f32);
+                    }
+                    window.request_redraw();
+                }
+            }
+            PendingEvent::Closed(window) => {
+                if let Some(window) = window.upgrade() {
+                    window.hide();
+                }
+            }
+            PendingEvent::Mouse(window, event) => {
+                if let Some(window) = window.upgrade() {
+                    window.process_mouse_input(event);
+                }
+            }
+            PendingEvent::Key(window, event) => {
+                if let Some(window) = window.upgrade() {
+                    window.process_key_input(&event);
+                }
+            }
+            PendingEvent::Focus(window, have_focus) => {
+                if let Some(window) = window.upgrade() {
+                    window.set_active(have_focus);
+                    window.set_focus(have_focus);
+                }
+            }
+        }
+    }
+}
+
+/// Listens to the pointer and the keyboard of the seat, and sends their events to the
+/// layer surfaces
+fn listen_to_seat(seat: &Main<wl_seat::WlSeat>) {
+    let mut pointer = None;
+    let mut keyboard = None;
+    seat.quick_assign(move |seat, event, _| {
+        if let wl_seat::Event::Capabilities { capabilities } = event {
+            if capabilities.contains(wl_seat::Capability::Pointer) && pointer.is_none() {
+                let new_pointer = seat.get_pointer();
+                new_pointer.quick_assign(pointer_handler());
+                pointer = Some(new_pointer);
+            }
+            if capabilities.contains(wl_seat::Capability::Keyboard) && keyboard.is_none() {
+                keyboard = keyboard::map_keyboard(&seat, None, keyboard_handler()).ok();
+            }
+        }
+    });
+}
+
+fn pointer_handler(
+) -> impl FnMut(Main<wl_pointer::WlPointer>, wl_pointer::Event, wayland_client::DispatchData) {
+    // The linux input event codes of the buttons
+    const BTN_LEFT: u32 = 0x110;
+    const BTN_RIGHT: u32 = 0x111;
+    const BTN_MIDDLE: u32 = 0x112;
+
+    let mut focus: Option<Weak<corelib::window::Window>> = None;
+    let mut position = Point::default();
+    move |_, event, _| {
+        let mouse_event = match event {
+            wl_pointer::Event::Enter { surface, surface_x, surface_y, .. } => {
+                focus = window_of_surface(&surface);
+                position = euclid::point2(surface_x as f32, surface_y as f32);
+                MouseEvent::MouseMoved { pos: position }
+            }
+            wl_pointer::Event::Leave { .. } => MouseEvent::MouseExit,
+            wl_pointer::Event::Motion { surface_x, surface_y, .. } => {
+                position = euclid::point2(surface_x as f32, surface_y as f32);
+                MouseEvent::MouseMoved { pos: position }
+            }
+            wl_pointer::Event::Button { button, state, .. } => {
+                let button = match button {
+                    BTN_LEFT => PointerEventButton::left,
+                    BTN_RIGHT => PointerEventButton::right,
+                    BTN_MIDDLE => PointerEventButton::middle,
+                    _ => PointerEventButton::none,
+                };
+                match state {
+                    wl_pointer::ButtonState::Pressed => {
+                        MouseEvent::MousePressed { pos: position, button }
+                    }
+                    _ => MouseEvent::MouseReleased { pos: position, button },
+                }
+            }
+            wl_pointer::Event::Axis { axis, value, .. } => {
+                // Wayland scrolls down for positive values, the opposite of the wheel delta
+                let delta = match axis {
+                    wl_pointer::Axis::HorizontalScroll => euclid::point2(-value as f32, 0.),
+                    _ => euclid::point2(0., -value as f32),
+                };
+                MouseEvent::MouseWheel { pos: position, delta }
+            }
+            _ => return,
+        };
+        let is_exit = matches!(mouse_event, MouseEvent::MouseExit);
+        if let Some(window) = &focus {
+            push_event(PendingEvent::Mouse(window.clone(), mouse_event));
+        }
+        if is_exit {
+            focus = None;
+        }
+    }
+}
+
+fn keyboard_handler() -> impl FnMut(
+    keyboard::Event,
+    wayland_client::protocol::wl_keyboard::WlKeyboard,
+    wayland_client::DispatchData,
+) {
+    // Wayland leaves the key repeat to the clients, and map_keyboard only implements it with a
+    // calloop event loop, so the last pressed key is repeated with a timer, with the delay and the
+    // rate that the compositors use by default
+    const KEY_REPEAT_DELAY: Duration = Duration::from_millis(600);
+    const KEY_REPEAT_INTERVAL: Duration = Duration::from_millis(40);
+
+    let mut focus: Option<Weak<corelib::window::Window>> = None;
+    let mut modifiers = KeyboardModifiers::default();
+    // The text of the pressed keys, sent again when they are released
+    let mut pressed_keys: HashMap<u32, SharedString> = HashMap::new();
+    // The key that is repeated, and the timer that repeats it
+    let mut repeated_key = None;
+    let repeat_timer = corelib::timers::Timer::default();
+    move |event, _, _| match event {
+        keyboard::Event::Enter { surface, .. } => {
+            focus = window_of_surface(&surface);
+            if let Some(window) = &focus {
+                push_event(PendingEvent::Focus(window.clone(), true));
+            }
+        }
+        keyboard::Event::Leave { .. } => {
+            if let Some(window) = focus.take() {
+                push_event(PendingEvent::Focus(window, false));
+            }
+            pressed_keys.clear();
+            repeated_key = None;
+            repeat_timer.stop();
+        }
+        keyboard::Event::Modifiers { modifiers: state } => {
+            modifiers = KeyboardModifiers {
+                alt: state.alt,
+                control: state.ctrl,
+                shift: state.shift,
+                meta: state.logo,
+            };
+        }
+        keyboard::Event::Key { rawkey, keysym, state, utf8, .. } => {
+            let (event_type, text) = match state {
+                keyboard::KeyState::Pressed => {
+                    let text = match keysym_to_string(keysym).or_else(|| utf8.map(Into::into)) {
+                        Some(text) => text,
+                        None => return,
+                    };
+                    pressed_keys.insert(rawkey, text.clone());
+                    (KeyEventType::KeyPressed, text)
+                }
+                _ => match pressed_keys.remove(&rawkey) {
+                    Some(text) => (KeyEventType::KeyReleased, text),
+                    None => return,
+                },
+            };
+            if let Some(window) = &focus {
+                // The compositor only sends the presses of the keys, the repeats come from the timer
+                let event = KeyEvent {
+                    event_type,
+                    key: i_slint_core::input::LogicalKey::from_text(&text),
+                    text,
+                    modifiers,
+                    scancode: rawkey as i32,
+                    repeat: false,
+                };
+                if event.event_type == KeyEventType::KeyPressed {
+                    let repeat_event = KeyEvent { repeat: true, ..event.clone() };
+                    let window = window.clone();
+                    let first_repeat = Instant::now() + KEY_REPEAT_DELAY;
+                    repeated_key = Some(rawkey);
+                    repeat_timer.start(
+                        corelib::timers::TimerMode::Repeated,
+                        KEY_REPEAT_INTERVAL,
+                        move || {
+                            if Instant::now() < first_repeat {
+                                return;
+                            }
+                            if let Some(window) = window.upgrade() {
+                                corelib::animations::update_animations();
+                                window.process_key_input(&repeat_event);
+                            }
+                        },
+                    );
+                } else if repeated_key == Some(rawkey) {
+                    repeated_key = None;
+                    repeat_timer.stop();
+                }
+                push_event(PendingEvent::Key(window.clone(), event));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the text of the special keys, which xkb doesn't translate to text
+fn keysym_to_string(keysym: u32) -> Option<SharedString> {
+    use keyboard::keysyms::*;
+    let key = match keysym {
+        XKB_KEY_BackSpace => key_codes::Backspace,
+        XKB_KEY_Tab => key_codes::Tab,
+        XKB_KEY_ISO_Left_Tab => key_codes::Backtab,
+        XKB_KEY_Return | XKB_KEY_KP_Enter => key_codes::Return,
+        XKB_KEY_Escape => key_codes::Escape,
+        XKB_KEY_Delete => key_codes::Delete,
+        XKB_KEY_Up => key_codes::UpArrow,
+        XKB_KEY_Down => key_codes::DownArrow,
+        XKB_KEY_Left => key_codes::LeftArrow,
+        XKB_KEY_Right => key_codes::RightArrow,
+        XKB_KEY_Insert => key_codes::Insert,
+        XKB_KEY_Home => key_codes::Home,
+        XKB_KEY_End => key_codes::End,
+        XKB_KEY_Page_Up => key_codes::PageUp,
+        XKB_KEY_Page_Down => key_codes::PageDown,
+        XKB_KEY_F1 => key_codes::F1,
+        XKB_KEY_F2 => key_codes::F2,
+        XKB_KEY_F3 => key_codes::F3,
+        XKB_KEY_F4 => key_codes::F4,
+        XKB_KEY_F5 => key_codes::F5,
+        XKB_KEY_F6 => key_codes::F6,
+        XKB_KEY_F7 => key_codes::F7,
+        XKB_KEY_F8 => key_codes::F8,
+        XKB_KEY_F9 => key_codes::F9,
+        XKB_KEY_F10 => key_codes::F10,
+        XKB_KEY_F11 => key_codes::F11,
+        XKB_KEY_F12 => key_codes::F12,
+        _ => return None,
+    };
+    Some(String::from(key).into())
+}
@@ -12,6 +12,7 @@
 
 use euclid::approxeq::ApproxEq;
 use event_loop::WinitWindow;
+use i_slint_core::api::RenderingQuality;
 use i_slint_core::graphics::{
     Brush, Color, Image, ImageInner, IntRect, IntSize, Point, Rect, RenderingCache, Size,
 };
@@ -32,6 +33,23 @@
 
 mod fonts;
 
+mod accessibility;
+
+#[cfg(all(
+    feature = "wayland",
+    not(any(
+        target_family = "windows",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "android",
+        target_arch = "wasm32"
+    ))
+))]
+mod layer_shell;
+
+#[cfg(target_os = "macos")]
+mod macos;
+
 mod stylemetrics;
 
 type Canvas = femtovg::Canvas<femtovg::renderer::OpenGl>;
@@ -76,6 +94,9 @@ struct State {
     scissor: Rect,
     global_alpha: f32,
     layer: Option<Rc<Layer>>,
+    /// The translation of the canvas in physical pixels, or None when the canvas is rotated, as
+    /// the geometry cannot be aligned to the pixels then
+    offset: Option<euclid::default::Vector2D<f32>>,
 }
 
 pub struct GLItemRenderer {
@@ -86,10 +107,26 @@ pub struct GLItemRenderer {
     layer_images_to_delete_after_flush: Vec<CachedImage>,
     graphics_window: Rc<GLWindow>,
     scale_factor: f32,
+    rendering_quality: RenderingQuality,
     /// track the state manually since femtovg don't have accessor for its state
     state: Vec<State>,
 }
 
+/// Returns the wavy line that underlines a misspelled word, between `x_start` and `x_end` around
+/// `y`, with waves of `width` above and below it
+fn squiggle_path(x_start: f32, x_end: f32, y: f32, width: f32) -> femtovg::Path {
+    let mut path = femtovg::Path::new();
+    path.move_to(x_start, y);
+    let mut x = x_start;
+    let mut above = true;
+    while x < x_end {
+        x = (x + width * 2.).min(x_end);
+        path.line_to(x, if above { y - width } else { y + width });
+        above = !above;
+    }
+    path
+}
+
 fn rect_with_radius_to_path(rect: Rect, border_radius: f32) -> femtovg::Path {
     let mut path = femtovg::Path::new();
     let x = rect.origin.x;
@@ -128,7 +165,7 @@ fn item_rect<Item: i_slint_core::items::Item>(item: Pin<&Item>, scale_factor: f3
 
 impl ItemRenderer for GLItemRenderer {
     fn draw_rectangle(&mut self, rect: std::pin::Pin<&i_slint_core::items::Rectangle>) {
-        let geometry = item_rect(rect, self.scale_factor);
+        let geometry = self.snap_rect(item_rect(rect, self.scale_factor));
         if geometry.is_empty() {
             return;
         }
@@ -145,12 +182,15 @@ fn draw_border_rectangle(
         &mut self,
         rect: std::pin::Pin<&i_slint_core::items::BorderRectangle>,
     ) {
-        let mut geometry = item_rect(rect, self.scale_factor);
+        let mut geometry = self.snap_rect(item_rect(rect, self.scale_factor));
         if geometry.is_empty() {
             return;
         }
 
-        let mut border_width = rect.border_width() * self.scale_factor;
+        let mut border_width = RenderingQuality::snap_length(
+            self.rendering_quality.snap_borders,
+            rect.border_width() * self.scale_factor,
+        );
         // In CSS the border is entirely towards the inside of the boundary
         // geometry, while in femtovg the line with for a stroke is 50% in-
         // and 50% outwards. We choose the CSS model, so the inner rectangle
@@ -238,6 +278,7 @@ fn draw_text(&mut self, text: std::pin::Pin<&i_slint_core::items::Text>) {
         };
 
         let mut canvas = self.canvas.borrow_mut();
+        let snap_line = self.text_line_snapper(canvas.measure_font(paint).unwrap().ascender());
         fonts::layout_text_lines(
             string,
             &font,
@@ -248,7 +289,7 @@ fn draw_text(&mut self, text: std::pin::Pin<&i_slint_core::items::Text>) {
             false,
             paint,
             |to_draw, pos, _, _| {
-                canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+                canvas.fill_text(pos.x, snap_line(pos.y), to_draw.trim_end(), paint).unwrap();
             },
         );
     }
@@ -282,8 +323,14 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&i_slint_core::items::Te
         let cursor_pos = text_input.cursor_position();
         let cursor_visible = cursor_pos >= 0 && text_input.cursor_visible() && text_input.enabled();
         let mut cursor_pos = cursor_pos as usize;
+        let misspellings = text_input.misspellings();
+        let squiggle_width = self.scale_factor;
         let mut canvas = self.canvas.borrow_mut();
-        let font_height = canvas.measure_font(paint).unwrap().height();
+        let font_metrics = canvas.measure_font(paint).unwrap();
+        let font_height = font_metrics.height();
+        // The misspelled words are underlined in the middle of the space below the baseline
+        let squiggle_y = font_metrics.ascender() + font_metrics.descender().abs() / 2.;
+        let snap_line = self.text_line_snapper(font_metrics.ascender());
         let mut text = text_input.text();
 
         if let InputType::password = text_input.input_type() {
@@ -305,6 +352,7 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&i_slint_core::items::Te
             text_input.single_line(),
             paint,
             |to_draw, pos, start, metrics| {
+                let pos = Point::new(pos.x, snap_line(pos.y));
                 let range = start..(start + to_draw.len());
                 if min_select != max_select
                     && (range.contains(&min_select)
@@ -378,6 +426,27 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&i_slint_core::items::Te
                     // no selection on this line
                     canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
                 };
+                for misspelling in &misspellings {
+                    let word = misspelling.range.start.max(start)
+                        ..misspelling.range.end.min(start + to_draw.len());
+                    let mut word_glyphs = metrics
+                        .glyphs
+                        .iter()
+                        .filter(|glyph| word.contains(&(start + glyph.byte_index)));
+                    if let Some(first) = word_glyphs.next() {
+                        let last = word_glyphs.last().unwrap_or(first);
+                        let mut squiggle = squiggle_path(
+                            pos.x + first.x - first.bearing_x,
+                            pos.x + last.x + last.advance_x,
+                            pos.y + squiggle_y,
+                            squiggle_width,
+                        );
+                        let mut squiggle_paint =
+                            femtovg::Paint::color(femtovg::Color::rgb(255, 0, 0));
+                        squiggle_paint.set_line_width(squiggle_width);
+                        canvas.stroke_path(&mut squiggle, squiggle_paint);
+                    }
+                }
                 if cursor_visible
                     && (range.contains(&cursor_pos)
                         || (cursor_pos == range.end && cursor_pos == text.len()))
@@ -783,13 +852,17 @@ fn as_any(&mut self) -> &mut dyn std::any::Any {
 
     fn translate(&mut self, x: f32, y: f32) {
         self.canvas.borrow_mut().translate(x * self.scale_factor, y * self.scale_factor);
-        let clip = &mut self.state.last_mut().unwrap().scissor;
-        *clip = clip.translate((-x, -y).into())
+        let state = self.state.last_mut().unwrap();
+        state.scissor = state.scissor.translate((-x, -y).into());
+        if let Some(offset) = &mut state.offset {
+            *offset += euclid::vec2(x, y) * self.scale_factor;
+        }
     }
 
     fn rotate(&mut self, angle_in_degrees: f32) {
         let angle_in_radians = angle_in_degrees.to_radians();
         self.canvas.borrow_mut().rotate(angle_in_radians);
+        self.state.last_mut().unwrap().offset = None;
         let clip = &mut self.state.last_mut().unwrap().scissor;
         // Compute the bounding box of the rotated rectangle
         let (sin, cos) = angle_in_radians.sin_cos();
@@ -821,6 +894,46 @@ fn apply_opacity(&mut self, opacity: f32) {
 }
 
 impl GLItemRenderer {
+    /// Aligns the edges of this rectangle, in physical pixels in the coordinates of the current
+    /// item, to the pixels of the screen if the rendering quality asks for it. A rectangle that
+    /// isn't empty stays at least one pixel wide and high.
+    fn snap_rect(&self, rect: Rect) -> Rect {
+        let offset = match self.state.last().unwrap().offset {
+            Some(offset) if self.rendering_quality.snap_borders => offset,
+            _ => return rect,
+        };
+        let snap = |coordinate: f32, offset: f32| (coordinate + offset).round() - offset;
+        let min = Point::new(snap(rect.min_x(), offset.x), snap(rect.min_y(), offset.y));
+        let max = Point::new(snap(rect.max_x(), offset.x), snap(rect.max_y(), offset.y));
+        let size = |snapped: f32, original: f32| {
+            if original > 0. {
+                snapped.max(1.)
+            } else {
+                snapped
+            }
+        };
+        Rect::new(
+            min,
+            Size::new(size(max.x - min.x, rect.width()), size(max.y - min.y, rect.height())),
+        )
+    }
+
+    /// Returns a function that takes the y coordinate of the top of a line of text, and returns
+    /// it moved so that the baseline of the line is aligned to the pixels of the screen, if the
+    /// rendering quality asks for it.
+    fn text_line_snapper(&self, ascender: f32) -> impl Fn(f32) -> f32 {
+        let offset = self
+            .state
+            .last()
+            .unwrap()
+            .offset
+            .filter(|_| self.rendering_quality.snap_text_baselines);
+        move |y| match offset {
+            Some(offset) => (y + ascender + offset.y).round() - ascender - offset.y,
+            None => y,
+        }
+    }
+
     fn colorize_image(
         &self,
         original_cache_entry: ItemGraphicsCacheEntry,
@@ -1199,6 +1312,7 @@ pub mod native_widgets {
 pub struct Backend;
 impl i_slint_core::backend::Backend for Backend {
     fn create_window(&'static self) -> Rc<Window> {
+        accessibility::update_system_preferences();
         i_slint_core::window::Window::new(|window| {
             GLWindow::new(
                 window,
@@ -1242,6 +1356,12 @@ fn clipboard_text(&'static self) -> Option<String> {
         CLIPBOARD.with(|clipboard| clipboard.borrow_mut().get_contents().ok())
     }
 
+    fn allow_event_loop_in_any_thread(
+        &'static self,
+    ) -> Result<(), i_slint_core::api::EventLoopThreadError> {
+        crate::event_loop::allow_event_loop_in_any_thread()
+    }
+
     fn post_event(&'static self, event: Box<dyn FnOnce() + Send>) {
         let e = crate::event_loop::CustomEvent::UserEvent(event);
         #[cfg(not(target_arch = "wasm32"))]
@@ -1282,4 +1402,23 @@ fn image_size(&'static self, image: &Image) -> IntSize {
                 .unwrap_or_default()
         })
     }
+
+    #[cfg(target_os = "macos")]
+    fn set_menu_bar(
+        &'static self,
+        menus: Vec<i_slint_core::api::MenuBarEntry>,
+        activated: Box<dyn Fn(&i_slint_core::api::MenuBarEntry)>,
+    ) {
+        self::macos::set_menu_bar(menus, activated)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn set_application_badge(&'static self, label: Option<&str>) {
+        self::macos::set_application_badge(label)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn set_application_progress(&'static self, progress: Option<f32>) {
+        self::macos::set_application_progress(progress)
+    }
 }
@@ -0,0 +1,272 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Integration with the macOS desktop: the global menu bar and the dock tile of the application
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use cocoa::appkit::{NSApp, NSApplication, NSEventModifierFlags, NSMenu, NSMenuItem};
+use cocoa::base::{id, nil, NO, YES};
+use cocoa::foundation::{NSAutoreleasePool, NSInteger, NSPoint, NSRect, NSSize, NSString};
+use i_slint_core::api::MenuBarEntry;
+use objc::declare::ClassDecl;
+use objc::runtime::{Class, Object, Sel};
+use objc::{class, msg_send, sel, sel_impl};
+
+struct MenuBar {
+    menus: Vec<MenuBarEntry>,
+    /// The entries that can be activated, indexed by the tag of their NSMenuItem
+    entries: Vec<MenuBarEntry>,
+    activated: Rc<dyn Fn(&MenuBarEntry)>,
+}
+
+thread_local! {
+    static MENU_BAR: RefCell<Option<MenuBar>> = RefCell::new(None);
+    /// The target of the actions of the menu items. NSMenuItem doesn't retain its target, so
+    /// this is never released.
+    static MENU_HANDLER: Cell<id> = Cell::new(nil);
+    /// The content view of the dock tile that shows the progress, and its progress indicator
+    static DOCK_PROGRESS: Cell<(id, id)> = Cell::new((nil, nil));
+}
+
+/// Sets the menus of the menu bar, after the application menu
+pub fn set_menu_bar(menus: Vec<MenuBarEntry>, activated: Box<dyn Fn(&MenuBarEntry)>) {
+    MENU_BAR.with(|menu_bar| {
+        *menu_bar.borrow_mut() =
+            Some(MenuBar { menus, entries: vec![], activated: activated.into() })
+    });
+    install_menu_bar();
+}
+
+/// Replaces the main menu of the application with the menu bar that was set, if any.
+/// This is called again when the event loop starts, because winit installs its own default
+/// menu when the application finishes launching.
+pub fn install_menu_bar() {
+    MENU_BAR.with(|menu_bar| {
+        let mut menu_bar = menu_bar.borrow_mut();
+        let menu_bar = match menu_bar.as_mut() {
+            Some(menu_bar) => menu_bar,
+            None => return,
+        };
+        unsafe {
+            let handler = menu_handler();
+            let main_menu = new_menu("");
+            let app_menu_item = NSMenuItem::new(nil).autorelease();
+            app_menu_item.setSubmenu_(application_menu(handler));
+            main_menu.addItem_(app_menu_item);
+
+            let mut entries = vec![];
+            for menu in &menu_bar.menus {
+                let item = NSMenuItem::new(nil).autorelease();
+                // The menu bar shows the title of the sub-menu
+                item.setSubmenu_(build_menu(&menu.title, &menu.submenu, handler, &mut entries));
+                main_menu.addItem_(item);
+            }
+            menu_bar.entries = entries;
+
+            NSApp().setMainMenu_(main_menu);
+        }
+    })
+}
+
+/// Shows `label` in a badge on the dock tile, or removes the badge
+pub fn set_application_badge(label: Option<&str>) {
+    unsafe {
+        let dock_tile: id = msg_send![NSApp(), dockTile];
+        let label = label.map_or(nil, |label| NSString::alloc(nil).init_str(label).autorelease());
+        let _: () = msg_send![dock_tile, setBadgeLabel: label];
+    }
+}
+
+/// Shows the `progress` in a bar over the icon of the application in the dock tile, or removes it
+pub fn set_application_progress(progress: Option<f32>) {
+    unsafe {
+        let dock_tile: id = msg_send![NSApp(), dockTile];
+        match progress {
+            Some(progress) => {
+                let (content_view, indicator) = dock_progress_views(dock_tile);
+                let value = progress.clamp(0., 1.) as f64 * 100.;
+                let _: () = msg_send![indicator, setDoubleValue: value];
+                let _: () = msg_send![dock_tile, setContentView: content_view];
+            }
+            None => {
+                let _: () = msg_send![dock_tile, setContentView: nil];
+            }
+        }
+        let _: () = msg_send![dock_tile, display];
+    }
+}
+
+/// The dock tile shows the content view instead of the icon of the application, so the content
+/// view is an image of that icon with a progress indicator at the bottom
+unsafe fn dock_progress_views(dock_tile: id) -> (id, id) {
+    DOCK_PROGRESS.with(|views| {
+        if views.get().0 != nil {
+            return views.get();
+        }
+        let size: NSSize = msg_send![dock_tile, size];
+        let content_view: id = msg_send![class!(NSImageView), alloc];
+        let content_view: id = msg_send![content_view,
+            initWithFrame: NSRect::new(NSPoint::new(0., 0.), size)];
+        let icon: id = msg_send![NSApp(), applicationIconImage];
+        let _: () = msg_send![content_view, setImage: icon];
+
+        let indicator: id = msg_send![class!(NSProgressIndicator), alloc];
+        let indicator: id = msg_send![indicator,
+            initWithFrame: NSRect::new(NSPoint::new(0., 0.), NSSize::new(size.width, 20.))];
+        // NSProgressIndicatorStyleBar
+        let _: () = msg_send![indicator, setStyle: 0 as NSInteger];
+        let _: () = msg_send![indicator, setIndeterminate: NO];
+        let _: () = msg_send![indicator, setMinValue: 0. as f64];
+        let _: () = msg_send![indicator, setMaxValue: 100. as f64];
+        let _: () = msg_send![content_view, addSubview: indicator];
+
+        views.set((content_view, indicator));
+        (content_view, indicator)
+    })
+}
+
+/// The application menu, with the standard About, Hide, and Quit entries
+unsafe fn application_menu(handler: id) -> id {
+    let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+    let name: id = msg_send![process_info, processName];
+    let name = std::ffi::CStr::from_ptr(name.UTF8String()).to_string_lossy();
+
+    let menu = new_menu("");
+    menu.addItem_(new_menu_item(
+        &format!("About {}", name),
+        sel!(orderFrontStandardAboutPanel:),
+        "",
+    ));
+    menu.addItem_(NSMenuItem::separatorItem(nil));
+    menu.addItem_(new_menu_item(&format!("Hide {}", name), sel!(hide:), "h"));
+    let hide_others = new_menu_item("Hide Others", sel!(hideOtherApplications:), "h");
+    hide_others.setKeyEquivalentModifierMask_(
+        NSEventModifierFlags::NSCommandKeyMask | NSEventModifierFlags::NSAlternateKeyMask,
+    );
+    menu.addItem_(hide_others);
+    menu.addItem_(new_menu_item("Show All", sel!(unhideAllApplications:), ""));
+    menu.addItem_(NSMenuItem::separatorItem(nil));
+    let quit = new_menu_item(&format!("Quit {}", name), sel!(quit:), "q");
+    let _: () = msg_send![quit, setTarget: handler];
+    menu.addItem_(quit);
+    menu
+}
+
+unsafe fn build_menu(
+    title: &str,
+    entries: &[MenuBarEntry],
+    handler: id,
+    all_entries: &mut Vec<MenuBarEntry>,
+) -> id {
+    let menu = new_menu(title);
+    for entry in entries {
+        if entry.title.is_empty() {
+            menu.addItem_(NSMenuItem::separatorItem(nil));
+            continue;
+        }
+        let (key, modifiers) = parse_shortcut(&entry.shortcut);
+        let item = new_menu_item(&entry.title, sel!(activate:), &key);
+        item.setKeyEquivalentModifierMask_(modifiers);
+        let _: () = msg_send![item, setEnabled: if entry.disabled { NO } else { YES }];
+        // NSControlStateValueOn and NSControlStateValueOff
+        let state: NSInteger = if entry.checked { 1 } else { 0 };
+        let _: () = msg_send![item, setState: state];
+        if entry.submenu.is_empty() {
+            let _: () = msg_send![item, setTarget: handler];
+            let _: () = msg_send![item, setTag: all_entries.len() as NSInteger];
+            all_entries.push(entry.clone());
+        } else {
+            item.setSubmenu_(build_menu(&entry.title, &entry.submenu, handler, all_entries));
+        }
+        menu.addItem_(item);
+    }
+    menu
+}
+
+unsafe fn new_menu(title: &str) -> id {
+    let menu =
+        NSMenu::alloc(nil).initWithTitle_(NSString::alloc(nil).init_str(title).autorelease());
+    // The entries are enabled according to the `disabled` field, and not by the responder chain
+    let _: () = msg_send![menu, setAutoenablesItems: NO];
+    menu.autorelease()
+}
+
+unsafe fn new_menu_item(title: &str, action: Sel, key: &str) -> id {
+    NSMenuItem::alloc(nil)
+        .initWithTitle_action_keyEquivalent_(
+            NSString::alloc(nil).init_str(title).autorelease(),
+            action,
+            NSString::alloc(nil).init_str(key).autorelease(),
+        )
+        .autorelease()
+}
+
+/// Returns the key equivalent and the modifiers of a shortcut such as "Ctrl+Shift+S"
+fn parse_shortcut(shortcut: &str) -> (String, NSEventModifierFlags) {
+    let mut modifiers = NSEventModifierFlags::empty();
+    let mut key = String::new();
+    for part in shortcut.split('+').map(str::trim) {
+        match part.to_lowercase().as_str() {
+            "ctrl" | "control" | "cmd" | "command" | "meta" => {
+                modifiers |= NSEventModifierFlags::NSCommandKeyMask
+            }
+            "shift" => modifiers |= NSEventModifierFlags::NSShiftKeyMask,
+            "alt" | "option" => modifiers |= NSEventModifierFlags::NSAlternateKeyMask,
+            key_part => key = key_part.into(),
+        }
+    }
+    if key.is_empty() {
+        modifiers = NSEventModifierFlags::empty();
+    }
+    (key, modifiers)
+}
+
+unsafe fn menu_handler() -> id {
+    MENU_HANDLER.with(|handler| {
+        if handler.get() == nil {
+            let class = Class::get("SlintMenuHandler").unwrap_or_else(|| {
+                let mut decl = ClassDecl::new("SlintMenuHandler", class!(NSObject)).unwrap();
+                decl.add_method(sel!(activate:), activate as extern "C" fn(&Object, Sel, id));
+                decl.add_method(sel!(quit:), quit as extern "C" fn(&Object, Sel, id));
+                decl.register()
+            });
+            let instance: id = msg_send![class, new];
+            handler.set(instance);
+        }
+        handler.get()
+    })
+}
+
+extern "C" fn activate(_: &Object, _: Sel, item: id) {
+    let tag: NSInteger = unsafe { msg_send![item, tag] };
+    let index = tag as usize;
+    // Call the callback from the event loop, where it can create windows
+    post_event(Box::new(move || {
+        let entry_and_callback = MENU_BAR.with(|menu_bar| {
+            let menu_bar = menu_bar.borrow();
+            let menu_bar = menu_bar.as_ref()?;
+            Some((menu_bar.entries.get(index)?.clone(), menu_bar.activated.clone()))
+        });
+        if let Some((entry, activated)) = entry_and_callback {
+            activated(&entry);
+        }
+    }));
+}
+
+extern "C" fn quit(_: &Object, _: Sel, _: id) {
+    crate::event_loop::GLOBAL_PROXY
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .send_event(crate::event_loop::CustomEvent::Exit);
+}
+
+fn post_event(event: Box<dyn FnOnce() + Send>) {
+    crate::event_loop::GLOBAL_PROXY
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .send_event(crate::event_loop::CustomEvent::UserEvent(event));
+}
@@ -29,8 +29,10 @@
 mod fonts;
 mod lengths;
 mod renderer;
+mod rotation;
 
 use lengths::*;
+pub use rotation::DisplayRotation;
 
 pub trait Devices {
     fn screen_size(&self) -> PhysicalSize;
@@ -363,6 +365,15 @@ pub fn init_with_display<Display: Devices + 'static>(display: Display) {
     });
 }
 
+/// Like [`init_with_display()`], for a panel that is mounted sideways or upside down: the user
+/// interface is rotated by `rotation` on the panel, and the touch events are rotated back.
+pub fn init_with_rotated_display<Display: Devices + 'static>(
+    display: Display,
+    rotation: DisplayRotation,
+) {
+    init_with_display(rotation::RotatedDevices::new(display, rotation));
+}
+
 #[cfg(not(any(feature = "pico-st7789", feature = "simulator")))]
 pub fn init() {
     struct EmptyDisplay;
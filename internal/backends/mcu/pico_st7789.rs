@@ -100,7 +100,12 @@ pub fn init() {
 
     let timer = Timer::new(pac.TIMER, &mut pac.RESETS);
 
-    crate::init_with_display(PicoDevices { display, touch, last_touch: Default::default(), timer });
+    let rotation =
+        option_env!("SLINT_DISPLAY_ROTATION").and_then(|x| x.parse().ok()).unwrap_or_default();
+    crate::init_with_rotated_display(
+        PicoDevices { display, touch, last_touch: Default::default(), timer },
+        rotation,
+    );
 }
 
 struct PicoDevices<Display, Touch> {
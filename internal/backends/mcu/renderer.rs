@@ -14,6 +14,7 @@
 use core::pin::Pin;
 use embedded_graphics::pixelcolor::Rgb888;
 use euclid::num::Zero;
+use i_slint_core::api::RenderingQuality;
 use i_slint_core::graphics::{FontRequest, IntRect, PixelFormat, Rect as RectF};
 use i_slint_core::item_rendering::PartialRenderingCache;
 use i_slint_core::textlayout::TextParagraphLayout;
@@ -287,7 +288,12 @@ fn prepare_scene(
 ) -> Scene {
     let prepare_scene_profiler = profiler::Timer::new(devices);
     let factor = ScaleFactor::new(runtime_window.scale_factor());
-    let prepare_scene = PrepareScene::new(size, factor, runtime_window.default_font_properties());
+    let prepare_scene = PrepareScene::new(
+        size,
+        factor,
+        runtime_window.default_font_properties(),
+        runtime_window.rendering_quality(),
+    );
     let mut renderer = i_slint_core::item_rendering::PartialRenderer::new(cache, prepare_scene);
 
     runtime_window.draw_contents(|components| {
@@ -318,10 +324,16 @@ struct PrepareScene {
     current_state: RenderState,
     scale_factor: ScaleFactor,
     default_font: FontRequest,
+    rendering_quality: RenderingQuality,
 }
 
 impl PrepareScene {
-    fn new(size: PhysicalSize, scale_factor: ScaleFactor, default_font: FontRequest) -> Self {
+    fn new(
+        size: PhysicalSize,
+        scale_factor: ScaleFactor,
+        default_font: FontRequest,
+        rendering_quality: RenderingQuality,
+    ) -> Self {
         Self {
             items: vec![],
             rounded_rectangles: vec![],
@@ -334,6 +346,7 @@ fn new(size: PhysicalSize, scale_factor: ScaleFactor, default_font: FontRequest)
             },
             scale_factor,
             default_font,
+            rendering_quality,
         }
     }
 
@@ -344,7 +357,35 @@ fn should_draw(&self, rect: &LogicalRect) -> bool {
     }
 
     fn new_scene_rectangle(&mut self, geometry: LogicalRect, color: Color) {
-        self.new_scene_item(geometry, SceneCommand::Rectangle { color });
+        if !self.rendering_quality.snap_borders {
+            return self.new_scene_item(geometry, SceneCommand::Rectangle { color });
+        }
+        // Round the edges rather than truncating the origin and the size, so that a thin
+        // rectangle, such as a border, is at least one pixel wide at a fractional scale factor
+        let physical = (geometry.translate(self.current_state.offset.to_vector())
+            * self.scale_factor)
+            .round()
+            .cast::<i16>();
+        let at_least_one = |snapped: i16, original: f32| {
+            if original > 0. {
+                snapped.max(1)
+            } else {
+                snapped
+            }
+        };
+        let size = PhysicalSize::new(
+            at_least_one(physical.width(), geometry.width()),
+            at_least_one(physical.height(), geometry.height()),
+        );
+        if !size.is_empty() {
+            let z = self.items.len() as u16;
+            self.items.push(SceneItem {
+                pos: physical.origin,
+                size,
+                z,
+                command: SceneCommand::Rectangle { color },
+            });
+        }
     }
 
     fn new_scene_texture(&mut self, geometry: LogicalRect, texture: SceneTexture) {
@@ -454,7 +495,10 @@ fn draw_border_rectangle(&mut self, rect: Pin<&i_slint_core::items::BorderRectan
                     let rectangle_index = self.rounded_rectangles.len() as u16;
                     self.rounded_rectangles.push(RoundedRectangle {
                         radius: (LogicalLength::new(radius) * self.scale_factor).cast(),
-                        width: (LogicalLength::new(border) * self.scale_factor).cast(),
+                        width: PhysicalLength::new(RenderingQuality::snap_length(
+                            self.rendering_quality.snap_borders,
+                            border * self.scale_factor.get(),
+                        ) as i16),
                         border_color: rect.border_color().color(),
                         inner_color: color,
                         top_clip: PhysicalLength::new(clipped2.min_y() - geom2.min_y()),
@@ -0,0 +1,132 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Rotation of the output, for the devices whose panel is mounted sideways or upside down.
+
+The renderer draws in the coordinates of the rotated screen, and [`RotatedDevices`] transforms
+the regions that are filled, and the touch events that are read, between these coordinates and
+the ones of the panel.
+*/
+
+use crate::{Devices, PhysicalRect, PhysicalSize};
+use alloc::vec::Vec;
+use embedded_graphics::pixelcolor::Rgb888;
+use i_slint_core::graphics::Point;
+use i_slint_core::input::MouseEvent;
+
+/// The clockwise rotation of the user interface on the panel
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DisplayRotation {
+    /// The user interface is shown as the panel expects it
+    NoRotation,
+    /// The user interface is rotated by 90 degrees clockwise
+    Rotate90,
+    /// The user interface is upside down
+    Rotate180,
+    /// The user interface is rotated by 270 degrees clockwise, or 90 degrees counterclockwise
+    Rotate270,
+}
+
+impl Default for DisplayRotation {
+    fn default() -> Self {
+        Self::NoRotation
+    }
+}
+
+impl core::str::FromStr for DisplayRotation {
+    type Err = ();
+    /// Parses the rotation in degrees: "0", "90", "180", or "270"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "0" => Ok(Self::NoRotation),
+            "90" => Ok(Self::Rotate90),
+            "180" => Ok(Self::Rotate180),
+            "270" => Ok(Self::Rotate270),
+            _ => Err(()),
+        }
+    }
+}
+
+impl DisplayRotation {
+    fn swaps_width_and_height(self) -> bool {
+        matches!(self, Self::Rotate90 | Self::Rotate270)
+    }
+}
+
+/// Wraps the devices of a panel, to show the user interface rotated on it
+pub struct RotatedDevices<D> {
+    devices: D,
+    rotation: DisplayRotation,
+    /// The pixels of the last filled region, in the order of the panel
+    buffer: Vec<Rgb888>,
+}
+
+impl<D: Devices> RotatedDevices<D> {
+    pub fn new(devices: D, rotation: DisplayRotation) -> Self {
+        Self { devices, rotation, buffer: Vec::new() }
+    }
+}
+
+impl<D: Devices> Devices for RotatedDevices<D> {
+    fn screen_size(&self) -> PhysicalSize {
+        let size = self.devices.screen_size();
+        if self.rotation.swaps_width_and_height() {
+            PhysicalSize::new(size.height, size.width)
+        } else {
+            size
+        }
+    }
+
+    fn fill_region(&mut self, region: PhysicalRect, pixels: &[Rgb888]) {
+        let panel = self.devices.screen_size();
+        let (x, y) = (region.origin.x, region.origin.y);
+        let (w, h) = (region.size.width, region.size.height);
+        let panel_region = match self.rotation {
+            DisplayRotation::NoRotation => return self.devices.fill_region(region, pixels),
+            DisplayRotation::Rotate90 => euclid::rect(panel.width - y - h, x, h, w),
+            DisplayRotation::Rotate180 => {
+                euclid::rect(panel.width - x - w, panel.height - y - h, w, h)
+            }
+            DisplayRotation::Rotate270 => euclid::rect(y, panel.height - x - w, h, w),
+        };
+        let (w, h) = (w as usize, h as usize);
+        self.buffer.clear();
+        for row in 0..panel_region.size.height as usize {
+            for col in 0..panel_region.size.width as usize {
+                // The index in `pixels` of the pixel at this row and column of the panel region
+                let index = match self.rotation {
+                    DisplayRotation::NoRotation => row * w + col,
+                    DisplayRotation::Rotate90 => row + (h - 1 - col) * w,
+                    DisplayRotation::Rotate180 => (h - 1 - row) * w + (w - 1 - col),
+                    DisplayRotation::Rotate270 => (w - 1 - row) + col * w,
+                };
+                self.buffer.push(pixels[index]);
+            }
+        }
+        self.devices.fill_region(panel_region, &self.buffer);
+    }
+
+    fn read_touch_event(&mut self) -> Option<MouseEvent> {
+        let mut event = self.devices.read_touch_event()?;
+        if let Some(pos) = event.pos() {
+            let panel = self.devices.screen_size().to_f32();
+            let rotated = match self.rotation {
+                DisplayRotation::NoRotation => pos,
+                DisplayRotation::Rotate90 => Point::new(pos.y, panel.width - pos.x),
+                DisplayRotation::Rotate180 => Point::new(panel.width - pos.x, panel.height - pos.y),
+                DisplayRotation::Rotate270 => Point::new(panel.height - pos.y, pos.x),
+            };
+            event.translate(rotated - pos);
+        }
+        Some(event)
+    }
+
+    fn debug(&mut self, text: &str) {
+        self.devices.debug(text)
+    }
+
+    fn time(&self) -> core::time::Duration {
+        self.devices.time()
+    }
+}
@@ -28,6 +28,8 @@ pub struct SimulatorWindow {
     self_weak: Weak<i_slint_core::window::Window>,
     keyboard_modifiers: std::cell::Cell<KeyboardModifiers>,
     currently_pressed_key_code: std::cell::Cell<Option<winit::event::VirtualKeyCode>>,
+    currently_pressed_scancode: std::cell::Cell<(i32, bool)>,
+    pressed_scancodes: RefCell<std::collections::HashSet<u32>>,
     canvas: CanvasRc,
     opengl_context: OpenGLContext,
     constraints: Cell<(i_slint_core::layout::LayoutInfo, i_slint_core::layout::LayoutInfo)>,
@@ -41,10 +43,14 @@ pub(crate) fn new(window_weak: &Weak<i_slint_core::window::Window>) -> Rc<Self>
         let window_builder = winit::window::WindowBuilder::new().with_visible(false);
 
         #[cfg(target_arch = "wasm32")]
-        let (opengl_context, renderer) =
-            OpenGLContext::new_context_and_renderer(window_builder, &self.canvas_id);
+        let context = OpenGLContext::new_context_and_renderer(window_builder, &self.canvas_id);
         #[cfg(not(target_arch = "wasm32"))]
-        let (opengl_context, renderer) = OpenGLContext::new_context_and_renderer(window_builder);
+        let context = OpenGLContext::new_context_and_renderer(window_builder);
+        // The simulator cannot run without its window
+        let (opengl_context, renderer) = match context {
+            Ok(context) => context,
+            Err(err) => panic!("Cannot create the window of the simulator: {}", err),
+        };
 
         let canvas = femtovg::Canvas::new(renderer).unwrap();
 
@@ -56,6 +62,8 @@ pub(crate) fn new(window_weak: &Weak<i_slint_core::window::Window>) -> Rc<Self>
             self_weak: window_weak.clone(),
             keyboard_modifiers: Default::default(),
             currently_pressed_key_code: Default::default(),
+            currently_pressed_scancode: Default::default(),
+            pressed_scancodes: Default::default(),
             canvas,
             opengl_context,
             constraints: Default::default(),
@@ -222,6 +230,14 @@ fn currently_pressed_key_code(&self) -> &Cell<Option<winit::event::VirtualKeyCod
         &self.currently_pressed_key_code
     }
 
+    fn currently_pressed_scancode(&self) -> &Cell<(i32, bool)> {
+        &self.currently_pressed_scancode
+    }
+
+    fn pressed_scancodes(&self) -> &RefCell<std::collections::HashSet<u32>> {
+        &self.pressed_scancodes
+    }
+
     fn current_keyboard_modifiers(&self) -> &Cell<KeyboardModifiers> {
         &self.keyboard_modifiers
     }
@@ -374,6 +390,12 @@ fn clipboard_text(&'static self) -> Option<String> {
         unimplemented!()
     }
 
+    fn allow_event_loop_in_any_thread(
+        &'static self,
+    ) -> Result<(), i_slint_core::api::EventLoopThreadError> {
+        self::event_loop::allow_event_loop_in_any_thread()
+    }
+
     fn post_event(&'static self, event: Box<dyn FnOnce() + Send>) {
         self::event_loop::GLOBAL_PROXY
             .get_or_init(Default::default)
@@ -37,7 +37,16 @@ fn main() {
     config.flag_if_supported("/std:c++17");
     config.include(std::env::var("DEP_QT_INCLUDE_PATH").unwrap()).build("lib.rs");
 
+    // qttypes links the Core, Gui, and Widgets modules, but printing also needs QtPrintSupport
+    let qt_library_path = std::env::var("DEP_QT_LIBRARY_PATH").unwrap();
+    if std::path::Path::new(&qt_library_path).join("QtPrintSupport.framework").exists() {
+        println!("cargo:rustc-link-lib=framework=QtPrintSupport");
+    } else {
+        println!("cargo:rustc-link-lib=Qt{}PrintSupport", &qt_version[..1]);
+    }
+
     println!("cargo:rerun-if-changed=qt_window.rs");
+    println!("cargo:rerun-if-changed=qt_accessible.rs");
     println!("cargo:rerun-if-changed=qt_widgets.rs");
     println!("cargo:rerun-if-changed=qt_widgets/button.rs");
     println!("cargo:rerun-if-changed=qt_widgets/checkbox.rs");
@@ -18,6 +18,9 @@
 mod qt_widgets;
 #[cfg(not(no_qt))]
 mod qt_window;
+// After qt_window, whose C++ code it uses
+#[cfg(not(no_qt))]
+mod qt_accessible;
 
 mod key_generated;
 
@@ -0,0 +1,361 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Exposes the accessibility tree of the windows to the assistive technologies, such as the screen
+readers, through QAccessible. Qt forwards it to the accessibility API of the platform: AT-SPI on
+Linux, UI Automation on Windows and NSAccessibility on macOS.
+
+The nodes of the tree are identified by the path of the indices of the children from the root,
+and their role, label, value and geometry are read from the tree of the window each time the
+assistive technology asks for them. The press action of a node invokes the
+`accessible-action-default` callback of its element.
+*/
+
+// cspell:ignore qobject
+
+use cpp::*;
+use i_slint_core::accessibility::AccessibilityNode;
+
+use crate::qt_window::QtWindow;
+
+// This block uses the SlintWidget of qt_window.rs, so this module must be declared after it.
+cpp! {{
+    #include <QtWidgets/QtWidgets>
+    #include <QtWidgets/QAccessibleWidget>
+    #include <QtGui/QAccessible>
+    #include <algorithm>
+    #include <map>
+    #include <vector>
+
+    static int slint_accessible_child_count(void *rust_window, const std::vector<int> &path) {
+        auto path_ptr = path.data();
+        auto path_len = path.size();
+        return rust!(Slint_accessibleChildCount [rust_window: &QtWindow as "void*", path_ptr: *const i32 as "const int*", path_len: usize as "size_t"] -> i32 as "int" {
+            child_count(rust_window, path(path_ptr, path_len))
+        });
+    }
+
+    static bool slint_accessible_is_valid(void *rust_window, const std::vector<int> &path) {
+        auto path_ptr = path.data();
+        auto path_len = path.size();
+        return rust!(Slint_accessibleIsValid [rust_window: &QtWindow as "void*", path_ptr: *const i32 as "const int*", path_len: usize as "size_t"] -> bool as "bool" {
+            node_at(rust_window, path(path_ptr, path_len)).is_some()
+        });
+    }
+
+    static QString slint_accessible_text(void *rust_window, const std::vector<int> &path, bool value) {
+        auto path_ptr = path.data();
+        auto path_len = path.size();
+        return rust!(Slint_accessibleText [rust_window: &QtWindow as "void*", path_ptr: *const i32 as "const int*", path_len: usize as "size_t", value: bool as "bool"] -> qttypes::QString as "QString" {
+            match node_at(rust_window, path(path_ptr, path_len)) {
+                Some(node) if value => node.value.as_str().into(),
+                Some(node) => node.label.as_str().into(),
+                None => Default::default(),
+            }
+        });
+    }
+
+    static QRectF slint_accessible_rect(void *rust_window, const std::vector<int> &path) {
+        auto path_ptr = path.data();
+        auto path_len = path.size();
+        return rust!(Slint_accessibleRect [rust_window: &QtWindow as "void*", path_ptr: *const i32 as "const int*", path_len: usize as "size_t"] -> qttypes::QRectF as "QRectF" {
+            node_at(rust_window, path(path_ptr, path_len)).map_or(Default::default(), |node| {
+                let r = node.geometry;
+                qttypes::QRectF {
+                    x: r.origin.x as _,
+                    y: r.origin.y as _,
+                    width: r.size.width as _,
+                    height: r.size.height as _,
+                }
+            })
+        });
+    }
+
+    static QAccessible::Role slint_accessible_role(void *rust_window, const std::vector<int> &path) {
+        auto path_ptr = path.data();
+        auto path_len = path.size();
+        int role = rust!(Slint_accessibleRole [rust_window: &QtWindow as "void*", path_ptr: *const i32 as "const int*", path_len: usize as "size_t"] -> i32 as "int" {
+            node_at(rust_window, path(path_ptr, path_len)).map_or(-1, |node| node.role as i32)
+        });
+        // In the order of the AccessibleRole enum
+        switch (role) {
+            case 0: return QAccessible::Grouping;
+            case 1: return QAccessible::Button;
+            case 2: return QAccessible::CheckBox;
+            case 3: return QAccessible::ComboBox;
+            case 4: return QAccessible::List;
+            case 5: return QAccessible::ListItem;
+            case 6: return QAccessible::ProgressBar;
+            case 7: return QAccessible::Slider;
+            case 8: return QAccessible::SpinBox;
+            case 9: return QAccessible::PageTab;
+            case 10: return QAccessible::StaticText;
+            case 11: return QAccessible::EditableText;
+            default: return QAccessible::NoRole;
+        }
+    }
+
+    static void slint_accessible_activate(void *rust_window, const std::vector<int> &path) {
+        auto path_ptr = path.data();
+        auto path_len = path.size();
+        rust!(Slint_accessibleActivate [rust_window: &QtWindow as "void*", path_ptr: *const i32 as "const int*", path_len: usize as "size_t"] {
+            if let Some(node) = node_at(rust_window, path(path_ptr, path_len)) {
+                if let Some(window) = rust_window.self_weak.upgrade() {
+                    window.activate_accessible_element(&node);
+                }
+            }
+        });
+    }
+
+    /// Returns the child of the interface whose rectangle contains the position, in global coordinates
+    static QAccessibleInterface *slint_accessible_child_at(const QAccessibleInterface *iface, int x, int y) {
+        for (int i = 0; i < iface->childCount(); ++i) {
+            auto child = iface->child(i);
+            if (child && child->rect().contains(x, y)) {
+                return child;
+            }
+        }
+        return nullptr;
+    }
+
+    /// A node of the accessibility tree of a SlintWidget. Its press action invokes the
+    /// `accessible-action-default` callback of the element.
+    struct SlintAccessibleNode : QAccessibleInterface, QAccessibleActionInterface {
+        QPointer<QWidget> widget;
+        std::vector<int> path;
+
+        SlintAccessibleNode(QWidget *widget, std::vector<int> path) : widget(widget), path(std::move(path)) {}
+
+        void *rust_window() const {
+            return static_cast<SlintWidget*>(widget.data())->rust_window;
+        }
+
+        bool isValid() const override {
+            return widget && slint_accessible_is_valid(rust_window(), path);
+        }
+
+        QObject *object() const override { return nullptr; }
+        QWindow *window() const override { return widget ? widget->window()->windowHandle() : nullptr; }
+
+        QAccessibleInterface *parent() const override;
+        QAccessibleInterface *child(int index) const override;
+        QAccessibleInterface *childAt(int x, int y) const override {
+            return slint_accessible_child_at(this, x, y);
+        }
+
+        int childCount() const override {
+            return widget ? slint_accessible_child_count(rust_window(), path) : 0;
+        }
+
+        int indexOfChild(const QAccessibleInterface *child) const override {
+            auto node = dynamic_cast<const SlintAccessibleNode*>(child);
+            if (!node || node->widget != widget || node->path.size() != path.size() + 1
+                    || !std::equal(path.begin(), path.end(), node->path.begin())) {
+                return -1;
+            }
+            return node->path.back();
+        }
+
+        QString text(QAccessible::Text t) const override {
+            if (!widget || (t != QAccessible::Name && t != QAccessible::Value)) {
+                return QString();
+            }
+            return slint_accessible_text(rust_window(), path, t == QAccessible::Value);
+        }
+        void setText(QAccessible::Text, const QString &) override {}
+
+        void *interface_cast(QAccessible::InterfaceType type) override {
+            if (type == QAccessible::ActionInterface) {
+                return static_cast<QAccessibleActionInterface*>(this);
+            }
+            return nullptr;
+        }
+
+        QStringList actionNames() const override {
+            return isValid() ? QStringList { pressAction() } : QStringList();
+        }
+
+        void doAction(const QString &name) override {
+            if (name == pressAction() && isValid()) {
+                slint_accessible_activate(rust_window(), path);
+            }
+        }
+
+        QStringList keyBindingsForAction(const QString &) const override {
+            return QStringList();
+        }
+
+        QRect rect() const override {
+            if (!widget) {
+                return QRect();
+            }
+            auto r = slint_accessible_rect(rust_window(), path);
+            return QRect(widget->mapToGlobal(r.topLeft().toPoint()), r.size().toSize());
+        }
+
+        QAccessible::Role role() const override {
+            return widget ? slint_accessible_role(rust_window(), path) : QAccessible::NoRole;
+        }
+
+        QAccessible::State state() const override {
+            QAccessible::State state;
+            if (!isValid()) {
+                state.invalid = true;
+                return state;
+            }
+            switch (role()) {
+                case QAccessible::CheckBox:
+                    state.checkable = true;
+                    state.checked = text(QAccessible::Value) == QLatin1String("checked");
+                    break;
+                case QAccessible::EditableText:
+                    state.editable = true;
+                    break;
+                default:
+                    break;
+            }
+            return state;
+        }
+    };
+
+    /// The accessible interface of a SlintWidget, whose children are the roots of the
+    /// accessibility tree of the window
+    struct SlintAccessibleWidget : QAccessibleWidget {
+        /// The ids of the nodes that were registered in QAccessible, by path
+        mutable std::map<std::vector<int>, QAccessible::Id> nodes;
+
+        SlintAccessibleWidget(QWidget *widget) : QAccessibleWidget(widget, QAccessible::Window) {}
+        ~SlintAccessibleWidget() {
+            for (const auto &node : nodes) {
+                QAccessible::deleteAccessibleInterface(node.second);
+            }
+        }
+
+        void *rust_window() const {
+            return static_cast<SlintWidget*>(widget())->rust_window;
+        }
+
+        /// Returns the interface of the node at the path, which is registered the first time
+        QAccessibleInterface *node(const std::vector<int> &path) const {
+            if (path.empty()) {
+                return const_cast<SlintAccessibleWidget*>(this);
+            }
+            auto it = nodes.find(path);
+            if (it != nodes.end()) {
+                return QAccessible::accessibleInterface(it->second);
+            }
+            auto iface = new SlintAccessibleNode(widget(), path);
+            nodes[path] = QAccessible::registerAccessibleInterface(iface);
+            return iface;
+        }
+
+        int childCount() const override {
+            return slint_accessible_child_count(rust_window(), {});
+        }
+
+        QAccessibleInterface *child(int index) const override {
+            if (index < 0 || index >= childCount()) {
+                return nullptr;
+            }
+            return node({index});
+        }
+
+        int indexOfChild(const QAccessibleInterface *child) const override {
+            auto node = dynamic_cast<const SlintAccessibleNode*>(child);
+            if (!node || node->widget != widget() || node->path.size() != 1) {
+                return -1;
+            }
+            return node->path.front();
+        }
+
+        QAccessibleInterface *childAt(int x, int y) const override {
+            return slint_accessible_child_at(this, x, y);
+        }
+    };
+
+    static SlintAccessibleWidget *slint_accessible_widget(QWidget *widget) {
+        return static_cast<SlintAccessibleWidget*>(QAccessible::queryAccessibleInterface(widget));
+    }
+
+    QAccessibleInterface *SlintAccessibleNode::parent() const {
+        if (!widget) {
+            return nullptr;
+        }
+        return slint_accessible_widget(widget)->node(std::vector<int>(path.begin(), path.end() - 1));
+    }
+
+    QAccessibleInterface *SlintAccessibleNode::child(int index) const {
+        if (!widget || index < 0 || index >= childCount()) {
+            return nullptr;
+        }
+        auto child_path = path;
+        child_path.push_back(index);
+        return slint_accessible_widget(widget)->node(child_path);
+    }
+
+    static QAccessibleInterface *slint_accessible_factory(const QString &, QObject *object) {
+        if (auto widget = dynamic_cast<SlintWidget*>(object)) {
+            return new SlintAccessibleWidget(widget);
+        }
+        return nullptr;
+    }
+}}
+
+/// Makes QAccessible use the accessibility tree of the windows for the widgets of the windows
+pub(crate) fn install_factory() {
+    cpp! {unsafe [] {
+        static bool installed = false;
+        if (!installed) {
+            QAccessible::installFactory(slint_accessible_factory);
+            installed = true;
+        }
+    }}
+}
+
+/// Tells the assistive technologies that the accessibility tree of the window changed, if it did
+/// since the last call. Does nothing when no assistive technology is listening.
+pub(crate) fn notify_changes(window: &QtWindow) {
+    if !cpp!(unsafe [] -> bool as "bool" { return QAccessible::isActive(); }) {
+        return;
+    }
+    let tree = window.accessibility_tree();
+    if *window.last_accessibility_tree.borrow() == tree {
+        return;
+    }
+    *window.last_accessibility_tree.borrow_mut() = tree;
+    let widget_ptr = window.widget_ptr();
+    cpp! {unsafe [widget_ptr as "QWidget*"] {
+        QAccessibleEvent event(widget_ptr, QAccessible::ObjectReorder);
+        QAccessible::updateAccessibility(&event);
+    }}
+}
+
+fn path<'a>(ptr: *const i32, len: usize) -> &'a [i32] {
+    if len == 0 {
+        &[]
+    } else {
+        // Safety: the pointer and length are the ones of a std::vector<int> that outlives the call
+        unsafe { std::slice::from_raw_parts(ptr, len) }
+    }
+}
+
+/// Returns the node at the path of the indices of the children from the roots of the tree, or
+/// None if there is no such node anymore
+fn node_at(window: &QtWindow, path: &[i32]) -> Option<AccessibilityNode> {
+    let (first, rest) = path.split_first()?;
+    let mut node = window.accessibility_tree().into_iter().nth(usize::try_from(*first).ok()?)?;
+    for index in rest {
+        node = node.children.into_iter().nth(usize::try_from(*index).ok()?)?;
+    }
+    Some(node)
+}
+
+/// Returns the number of children of the node at the path, the empty path being the window
+fn child_count(window: &QtWindow, path: &[i32]) -> i32 {
+    if path.is_empty() {
+        window.accessibility_tree().len() as i32
+    } else {
+        node_at(window, path).map_or(0, |node| node.children.len() as i32)
+    }
+}
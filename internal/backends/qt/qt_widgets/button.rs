@@ -110,6 +110,7 @@ pub struct NativeButton {
     pub clicked: Callback<VoidArg>,
     pub standard_button_kind: Property<StandardButtonKind>,
     pub is_standard_button: Property<bool>,
+    pub has_focus: Property<bool>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -269,6 +270,7 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
         let text: qttypes::QString = this.actual_text(standard_button_kind);
         let icon: qttypes::QPixmap = this.actual_icon(standard_button_kind);
         let enabled = this.enabled();
+        let has_focus = this.has_focus();
 
         cpp!(unsafe [
             painter as "QPainter*",
@@ -276,6 +278,7 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
             text as "QString",
             icon as "QPixmap",
             enabled as "bool",
+            has_focus as "bool",
             size as "QSize",
             down as "bool",
             dpr as "float",
@@ -294,6 +297,9 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
                 option.state |= QStyle::State_Raised;
             if (enabled) {
                 option.state |= QStyle::State_Enabled;
+                if (has_focus) {
+                    option.state |= QStyle::State_HasFocus;
+                }
             } else {
                 option.palette.setCurrentColorGroup(QPalette::Disabled);
             }
@@ -17,6 +17,7 @@ pub struct NativeCheckBox {
     pub toggled: Callback<VoidArg>,
     pub text: Property<SharedString>,
     pub checked: Property<bool>,
+    pub has_focus: Property<bool>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -88,12 +89,14 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
     fn_render! { this dpr size painter widget initial_state =>
         let checked: bool = this.checked();
         let enabled = this.enabled();
+        let has_focus = this.has_focus();
         let text: qttypes::QString = this.text().as_str().into();
 
         cpp!(unsafe [
             painter as "QPainter*",
             widget as "QWidget*",
             enabled as "bool",
+            has_focus as "bool",
             text as "QString",
             size as "QSize",
             checked as "bool",
@@ -107,6 +110,9 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
             option.state |= checked ? QStyle::State_On : QStyle::State_Off;
             if (enabled) {
                 option.state |= QStyle::State_Enabled;
+                if (has_focus) {
+                    option.state |= QStyle::State_HasFocus;
+                }
             } else {
                 option.palette.setCurrentColorGroup(QPalette::Disabled);
             }
@@ -16,6 +16,7 @@ pub struct NativeComboBox {
     pub enabled: Property<bool>,
     pub pressed: Property<bool>,
     pub is_open: Property<bool>,
+    pub editable: Property<bool>,
     pub current_value: Property<SharedString>,
     pub cached_rendering_data: CachedRenderingData,
     pub open_popup: Callback<VoidArg>,
@@ -99,8 +100,10 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
     fn_render! { this dpr size painter widget initial_state =>
         let down: bool = this.pressed();
         let is_open: bool = this.is_open();
+        let editable: bool = this.editable();
+        // In editable mode, the text is rendered by the line edit placed on top of the combobox
         let text: qttypes::QString =
-            this.current_value().as_str().into();
+            if editable { Default::default() } else { this.current_value().as_str().into() };
         let enabled = this.enabled();
         cpp!(unsafe [
             painter as "QPainter*",
@@ -110,6 +113,7 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
             size as "QSize",
             down as "bool",
             is_open as "bool",
+            editable as "bool",
             dpr as "float",
             initial_state as "int"
         ] {
@@ -117,6 +121,7 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
             QStyleOptionComboBox option;
             option.state |= QStyle::State(initial_state);
             option.currentText = std::move(text);
+            option.editable = editable;
             option.rect = QRect(QPoint(), size / dpr);
             if (down)
                 option.state |= QStyle::State_Sunken;
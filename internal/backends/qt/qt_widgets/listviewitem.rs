@@ -30,11 +30,13 @@ fn geometry(self: Pin<&Self>) -> Rect {
     fn layout_info(self: Pin<&Self>, orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
         let index: i32 = self.index();
         let item = self.item();
-        let text: qttypes::QString = item.text.as_str().into();
+        let text: qttypes::QString = item_text(&item);
+        let icon = item_icon(&item);
 
         let s = cpp!(unsafe [
             index as "int",
-            text as "QString"
+            text as "QString",
+            icon as "QPixmap"
         ] -> qttypes::QSize as "QSize" {
             ensure_initialized();
 
@@ -48,6 +50,12 @@ fn layout_info(self: Pin<&Self>, orientation: Orientation, _window: &WindowRc) -
             }
             option.features |= QStyleOptionViewItem::HasDisplay;
             option.text = text;
+            if (!icon.isNull()) {
+                option.features |= QStyleOptionViewItem::HasDecoration;
+                option.icon = icon;
+                auto iconSize = qApp->style()->pixelMetric(QStyle::PM_SmallIconSize, 0, nullptr);
+                option.decorationSize = QSize(iconSize, iconSize);
+            }
             return qApp->style()->sizeFromContents(QStyle::CT_ItemViewItem, &option, QSize{}, nullptr);
         });
         let min = match orientation {
@@ -88,7 +96,9 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
         let is_selected: bool = this.is_selected();
         let has_hover: bool = this.has_hover();
         let item = this.item();
-        let text: qttypes::QString = item.text.as_str().into();
+        let text: qttypes::QString = item_text(&item);
+        let icon = item_icon(&item);
+        let enabled = !item.disabled;
         cpp!(unsafe [
             painter as "QPainter*",
             widget as "QWidget*",
@@ -98,12 +108,15 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
             is_selected as "bool",
             has_hover as "bool",
             text as "QString",
+            icon as "QPixmap",
+            enabled as "bool",
             initial_state as "int"
         ] {
             QStyleOptionViewItem option;
             option.state |= QStyle::State(initial_state);
             option.rect = QRect(QPoint(), size / dpr);
-            option.state = QStyle::State_Enabled;
+            option.state = enabled ? QStyle::State_Enabled : QStyle::State_None;
+            option.palette.setCurrentColorGroup(enabled ? QPalette::Active : QPalette::Disabled);
             if (is_selected) {
                 option.state |= QStyle::State_Selected;
             }
@@ -119,6 +132,12 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
             }
             option.features |= QStyleOptionViewItem::HasDisplay;
             option.text = text;
+            if (!icon.isNull()) {
+                option.features |= QStyleOptionViewItem::HasDecoration;
+                option.icon = icon;
+                auto iconSize = qApp->style()->pixelMetric(QStyle::PM_SmallIconSize, 0, nullptr);
+                option.decorationSize = QSize(iconSize, iconSize);
+            }
             // CE_ItemViewItem in QCommonStyle calls setClipRect on the painter and replace the clips. So we need to cheat.
             auto engine = painter->paintEngine();
             auto old_clip = engine->systemClip();
@@ -133,6 +152,20 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEve
     }
 }
 
+/// The text to display for the item: the secondary text goes on a second line
+fn item_text(item: &i_slint_core::model::StandardListViewItem) -> qttypes::QString {
+    if item.secondary_text.is_empty() {
+        item.text.as_str().into()
+    } else {
+        format!("{}\n{}", item.text, item.secondary_text).as_str().into()
+    }
+}
+
+fn item_icon(item: &i_slint_core::model::StandardListViewItem) -> qttypes::QPixmap {
+    crate::qt_window::load_image_from_resource((&item.icon).into(), None, Default::default())
+        .unwrap_or_default()
+}
+
 impl ItemConsts for NativeStandardListViewItem {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
         Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
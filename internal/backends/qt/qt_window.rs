@@ -48,6 +48,8 @@
     #include <QtCore/QBuffer>
     #include <QtCore/QEvent>
     #include <QtCore/QFileInfo>
+    #include <QtPrintSupport/QPrinter>
+    #include <QtPrintSupport/QPrintDialog>
     #include <memory>
     void ensure_initialized(bool from_qt_backend);
 
@@ -148,16 +150,20 @@ struct SlintWidget : QWidget {
             uint modifiers = uint(event->modifiers());
             QString text =  event->text();
             int key = event->key();
-            rust!(Slint_keyPress [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString", modifiers: u32 as "uint"] {
-                rust_window.key_event(key, text.clone(), modifiers, false);
+            int scancode = int(event->nativeScanCode());
+            bool repeat = event->isAutoRepeat();
+            rust!(Slint_keyPress [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString", modifiers: u32 as "uint", scancode: i32 as "int", repeat: bool as "bool"] {
+                rust_window.key_event(key, text.clone(), modifiers, scancode, repeat, false);
             });
         }
         void keyReleaseEvent(QKeyEvent *event) override {
             uint modifiers = uint(event->modifiers());
             QString text =  event->text();
             int key = event->key();
-            rust!(Slint_keyRelease [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString", modifiers: u32 as "uint"] {
-                rust_window.key_event(key, text.clone(), modifiers, true);
+            int scancode = int(event->nativeScanCode());
+            bool repeat = event->isAutoRepeat();
+            rust!(Slint_keyRelease [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString", modifiers: u32 as "uint", scancode: i32 as "int", repeat: bool as "bool"] {
+                rust_window.key_event(key, text.clone(), modifiers, scancode, repeat, true);
             });
         }
 
@@ -567,6 +573,19 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
 
         let single_line: bool = text_input.single_line();
 
+        // The start and the length of the misspelled words, in UTF-16 units
+        let misspellings: Vec<i32> = text_input
+            .misspellings()
+            .iter()
+            .flat_map(|misspelling| {
+                let start = utf8_byte_offset_to_utf16_units(text.as_str(), misspelling.range.start);
+                let end = utf8_byte_offset_to_utf16_units(text.as_str(), misspelling.range.end);
+                [start as i32, (end - start) as i32]
+            })
+            .collect();
+        let misspellings_ptr = misspellings.as_ptr();
+        let misspellings_len = misspellings.len();
+
         let painter: &mut QPainter = &mut *self.painter;
         cpp! { unsafe [
                 painter as "QPainter*",
@@ -580,7 +599,9 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
                 font as "QFont",
                 cursor_position as "int",
                 anchor_position as "int",
-                text_cursor_width as "float"] {
+                text_cursor_width as "float",
+                misspellings_ptr as "const int*",
+                misspellings_len as "size_t"] {
             if (!single_line) {
                 string.replace(QChar('\n'), QChar::LineSeparator);
             }
@@ -588,6 +609,12 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
             do_text_layout(layout, flags, rect);
             painter->setPen(QPen(fill_brush, 0));
             QVector<QTextLayout::FormatRange> selections;
+            for (size_t i = 0; i + 1 < misspellings_len; i += 2) {
+                QTextCharFormat fmt;
+                fmt.setUnderlineStyle(QTextCharFormat::SpellCheckUnderline);
+                fmt.setUnderlineColor(Qt::red);
+                selections << QTextLayout::FormatRange{ misspellings_ptr[i], misspellings_ptr[i + 1], fmt };
+            }
             if (anchor_position != cursor_position) {
                 QTextCharFormat fmt;
                 fmt.setBackground(QColor::fromRgba(selection_background_color));
@@ -1103,6 +1130,7 @@ fn draw_rectangle_impl(
 }
 
 cpp_class!(unsafe struct QWidgetPtr as "std::unique_ptr<QWidget>");
+cpp_class!(unsafe struct QPrinterPtr as "std::unique_ptr<QPrinter>");
 
 pub struct QtWindow {
     widget_ptr: QWidgetPtr,
@@ -1111,6 +1139,10 @@ pub struct QtWindow {
     fps_counter: Option<Rc<FPSCounter>>,
 
     cache: QtRenderingCache,
+
+    /// The accessibility tree when the assistive technologies were last told about a change
+    pub(crate) last_accessibility_tree:
+        RefCell<Vec<i_slint_core::accessibility::AccessibilityNode>>,
 }
 
 impl QtWindow {
@@ -1124,6 +1156,7 @@ pub fn new(window_weak: &Weak<i_slint_core::window::Window>) -> Rc<Self> {
             self_weak: window_weak.clone(),
             fps_counter: FPSCounter::new(),
             cache: Default::default(),
+            last_accessibility_tree: Default::default(),
         });
         let self_weak = Rc::downgrade(&rc);
         let widget_ptr = rc.widget_ptr();
@@ -1132,14 +1165,20 @@ pub fn new(window_weak: &Weak<i_slint_core::window::Window>) -> Rc<Self> {
             widget_ptr->rust_window = rust_window;
         }};
         ALL_WINDOWS.with(|aw| aw.borrow_mut().push(self_weak));
+        crate::qt_accessible::install_factory();
         rc
     }
 
     /// Return the QWidget*
-    fn widget_ptr(&self) -> NonNull<()> {
+    pub(crate) fn widget_ptr(&self) -> NonNull<()> {
         unsafe { std::mem::transmute_copy::<QWidgetPtr, NonNull<_>>(&self.widget_ptr) }
     }
 
+    /// Returns the accessibility tree of the window, which is empty once the window is gone
+    pub(crate) fn accessibility_tree(&self) -> Vec<i_slint_core::accessibility::AccessibilityNode> {
+        self.self_weak.upgrade().map_or_else(Vec::new, |window| window.accessibility_tree())
+    }
+
     fn paint_event(&self, painter: &mut QPainter) {
         let runtime_window = self.self_weak.upgrade().unwrap();
         runtime_window.clone().draw_contents(|components| {
@@ -1179,6 +1218,7 @@ fn paint_event(&self, painter: &mut QPainter) {
                 }}
             });
         });
+        crate::qt_accessible::notify_changes(self);
     }
 
     fn resize_event(&self, size: qttypes::QSize) {
@@ -1193,7 +1233,15 @@ fn mouse_event(&self, event: MouseEvent) {
         timer_event();
     }
 
-    fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, released: bool) {
+    fn key_event(
+        &self,
+        key: i32,
+        text: qttypes::QString,
+        qt_modifiers: u32,
+        scancode: i32,
+        repeat: bool,
+        released: bool,
+    ) {
         i_slint_core::animations::update_animations();
         let text: String = text.into();
         let modifiers = i_slint_core::input::KeyboardModifiers {
@@ -1207,8 +1255,11 @@ fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, release
 
         let event = KeyEvent {
             event_type: if released { KeyEventType::KeyReleased } else { KeyEventType::KeyPressed },
+            key: i_slint_core::input::LogicalKey::from_text(&text),
             text,
             modifiers,
+            scancode,
+            repeat,
         };
         self.self_weak.upgrade().unwrap().process_key_input(&event);
 
@@ -1234,8 +1285,25 @@ fn show(self: Rc<Self>) {
             self.apply_window_properties(window_item);
         }
 
+        // The window types that don't take the focus when they are shown are the ones that have
+        // Qt::ToolTip, the others have the Qt::WindowType with the same name
+        use i_slint_core::api::WindowType;
+        let window_type: u32 = match self.self_weak.upgrade().unwrap().window_type() {
+            WindowType::Splash => 1,
+            WindowType::Tooltip | WindowType::Notification => 2,
+            WindowType::Utility => 3,
+            _ => 0,
+        };
         let widget_ptr = self.widget_ptr();
-        cpp! {unsafe [widget_ptr as "QWidget*"] {
+        cpp! {unsafe [widget_ptr as "QWidget*", window_type as "uint32_t"] {
+            Qt::WindowType type = Qt::Window;
+            switch (window_type) {
+                case 1: type = Qt::SplashScreen; break;
+                case 2: type = Qt::ToolTip; break;
+                case 3: type = Qt::Tool; break;
+            }
+            widget_ptr->setWindowFlags((widget_ptr->windowFlags() & ~Qt::WindowType_Mask) | type);
+            widget_ptr->setAttribute(Qt::WA_ShowWithoutActivating, type == Qt::ToolTip);
             widget_ptr->show();
         }};
         if let Some(fps_counter) = &self.fps_counter {
@@ -1385,6 +1453,119 @@ fn show_popup(&self, popup: &i_slint_core::component::ComponentRc, position: Poi
         }};
     }
 
+    fn renderer_statistics(&self) -> i_slint_core::api::RendererStatistics {
+        // The pixmaps of the cache are in the main memory, so there are no textures to report
+        let mut statistics = i_slint_core::api::RendererStatistics::default();
+        statistics.cached_item_count = self
+            .cache
+            .borrow()
+            .iter()
+            .filter(|item| matches!(item, QtRenderingCacheItem::Pixmap(_)))
+            .count();
+        statistics
+    }
+
+    fn print(
+        &self,
+        pages: &[WindowRc],
+        target: &i_slint_core::api::PrintTarget,
+    ) -> Result<(), i_slint_core::api::PrintError> {
+        use i_slint_core::api::{PrintError, PrintTarget};
+        let (file_name, show_dialog): (qttypes::QString, bool) = match target {
+            PrintTarget::Pdf(path) => (path.as_str().into(), false),
+            PrintTarget::Dialog => (Default::default(), true),
+            _ => return Err(PrintError::Unsupported),
+        };
+        let page_count = pages.len() as i32;
+        let widget_ptr = self.widget_ptr();
+        let printer = cpp! {unsafe [widget_ptr as "QWidget*", file_name as "QString", show_dialog as "bool", page_count as "int"] -> QPrinterPtr as "std::unique_ptr<QPrinter>" {
+            auto printer = std::make_unique<QPrinter>(QPrinter::HighResolution);
+            if (show_dialog) {
+                QPrintDialog dialog(printer.get(), widget_ptr->isVisible() ? widget_ptr : nullptr);
+                dialog.setMinMax(1, page_count);
+                if (dialog.exec() != QDialog::Accepted)
+                    return {};
+            } else {
+                printer->setOutputFormat(QPrinter::PdfFormat);
+                printer->setOutputFileName(file_name);
+            }
+            return printer;
+        }};
+        let printer = &printer;
+
+        // The range of pages chosen in the dialog, or -1 if the dialog was cancelled
+        let first_page = cpp! {unsafe [printer as "const std::unique_ptr<QPrinter>*"] -> i32 as "int" {
+            if (!*printer)
+                return -1;
+            return (*printer)->printRange() == QPrinter::PageRange ? (*printer)->fromPage() - 1 : 0;
+        }};
+        if first_page < 0 {
+            return Err(PrintError::Cancelled);
+        }
+        let last_page = cpp! {unsafe [printer as "const std::unique_ptr<QPrinter>*"] -> i32 as "int" {
+            return (*printer)->printRange() == QPrinter::PageRange ? (*printer)->toPage() : 0;
+        }};
+        let last_page = if last_page > 0 { last_page as usize } else { pages.len() };
+
+        let mut painter = cpp! {unsafe [printer as "const std::unique_ptr<QPrinter>*"] -> QPainter as "QPainter" {
+            return QPainter(printer->get());
+        }};
+        let painter = &mut painter;
+        // The painter isn't active if the file can't be written
+        let is_active = cpp! {unsafe [painter as "QPainter*"] -> bool as "bool" { return painter->isActive(); }};
+        if !is_active {
+            return Err(PrintError::Failed);
+        }
+        let resolution = cpp! {unsafe [printer as "const std::unique_ptr<QPrinter>*"] -> f64 as "double" {
+            return (*printer)->resolution();
+        }};
+        let page_size = cpp! {unsafe [printer as "const std::unique_ptr<QPrinter>*"] -> qttypes::QSizeF as "QSizeF" {
+            return QSizeF((*printer)->pageLayout().paintRectPixels((*printer)->resolution()).size());
+        }};
+
+        for (index, page) in pages.iter().enumerate().take(last_page).skip(first_page as usize) {
+            if index > first_page as usize
+                && !cpp! {unsafe [printer as "const std::unique_ptr<QPrinter>*"] -> bool as "bool" {
+                    return (*printer)->newPage();
+                }}
+            {
+                return Err(PrintError::Failed);
+            }
+
+            // The scene keeps the size it has on a screen of 96 DPI, unless it doesn't fit
+            let (width, height) = page.window_item_geometry().unwrap_or_default();
+            let mut scale = resolution / 96.;
+            if width > 0. && height > 0. {
+                scale =
+                    scale.min(page_size.width / width as f64).min(page_size.height / height as f64);
+            }
+            cpp! {unsafe [painter as "QPainter*", scale as "double"] {
+                painter->save();
+                painter->scale(scale, scale);
+                painter->setRenderHints(QPainter::Antialiasing | QPainter::SmoothPixmapTransform);
+            }};
+
+            // The items of the page have their rendering data in the cache of their own window
+            let cache = <dyn std::any::Any>::downcast_ref::<QtWindow>(page.as_ref().as_any())
+                .map_or_else(Default::default, |window| window.cache.clone());
+            let mut renderer = QtItemRenderer {
+                painter: &mut *painter,
+                cache,
+                default_font_properties: page.default_font_properties(),
+                window: page.clone(),
+            };
+            i_slint_core::item_rendering::render_component_items(
+                &page.component(),
+                &mut renderer,
+                Point::default(),
+            );
+            drop(renderer);
+
+            cpp! {unsafe [painter as "QPainter*"] { painter->restore(); }};
+        }
+        Ok(())
+    }
+
     fn set_mouse_cursor(&self, cursor: MouseCursor) {
         let widget_ptr = self.widget_ptr();
         //unidirectional resize cursors are replaced with bidirectional ones
@@ -1526,6 +1707,88 @@ fn text_input_position_for_byte_offset(
         Point::new(r.x as _, r.y as _)
     }
 
+    fn text_byte_offset_for_position(&self, text: Pin<&items::Text>, pos: Point) -> Option<usize> {
+        let rect: qttypes::QRectF = get_geometry!(items::Text, text);
+        let pos = qttypes::QPointF { x: pos.x as _, y: pos.y as _ };
+        let font: QFont =
+            get_font(text.unresolved_font_request().merge(&self.default_font_properties()));
+        let string = qttypes::QString::from(text.text().as_str());
+        let flags = match text.horizontal_alignment() {
+            TextHorizontalAlignment::left => key_generated::Qt_AlignmentFlag_AlignLeft,
+            TextHorizontalAlignment::center => key_generated::Qt_AlignmentFlag_AlignHCenter,
+            TextHorizontalAlignment::right => key_generated::Qt_AlignmentFlag_AlignRight,
+        } | match text.vertical_alignment() {
+            TextVerticalAlignment::top => key_generated::Qt_AlignmentFlag_AlignTop,
+            TextVerticalAlignment::center => key_generated::Qt_AlignmentFlag_AlignVCenter,
+            TextVerticalAlignment::bottom => key_generated::Qt_AlignmentFlag_AlignBottom,
+        } | match text.wrap() {
+            TextWrap::no_wrap => 0,
+            TextWrap::word_wrap => key_generated::Qt_TextFlag_TextWordWrap,
+        };
+        // The text is laid out as if it was not elided
+        Some(cpp! { unsafe [font as "QFont", string as "QString", pos as "QPointF", flags as "int",
+                rect as "QRectF"] -> usize as "size_t" {
+            // we need to do the \n replacement in a copy because the original need to be kept to know the utf8 offset
+            auto copy = string;
+            copy.replace(QChar('\n'), QChar::LineSeparator);
+            QTextLayout layout(copy, font);
+            auto line = do_text_layout(layout, flags, rect, pos.y());
+            if (line < 0 || layout.lineCount() <= line)
+                return 0;
+            QTextLine textLine = layout.lineAt(line);
+            int cur;
+            if (pos.x() > textLine.naturalTextWidth()) {
+                cur = textLine.textStart() + textLine.textLength();
+                if (cur > 0 && string[cur - 1] == '\n')
+                    cur--;
+            } else {
+                cur = textLine.xToCursor(pos.x());
+            }
+            if (cur < string.size() && string[cur].isLowSurrogate())
+                cur++;
+            // convert to an utf8 pos;
+            return QStringView(string).left(cur).toUtf8().size();
+        }})
+    }
+
+    fn text_position_for_byte_offset(
+        &self,
+        text: Pin<&items::Text>,
+        byte_offset: usize,
+    ) -> Option<Point> {
+        let rect: qttypes::QRectF = get_geometry!(items::Text, text);
+        let font: QFont =
+            get_font(text.unresolved_font_request().merge(&self.default_font_properties()));
+        let content = text.text();
+        let mut string = qttypes::QString::from(content.as_str());
+        let offset: u32 = utf8_byte_offset_to_utf16_units(content.as_str(), byte_offset) as _;
+        let flags = match text.horizontal_alignment() {
+            TextHorizontalAlignment::left => key_generated::Qt_AlignmentFlag_AlignLeft,
+            TextHorizontalAlignment::center => key_generated::Qt_AlignmentFlag_AlignHCenter,
+            TextHorizontalAlignment::right => key_generated::Qt_AlignmentFlag_AlignRight,
+        } | match text.vertical_alignment() {
+            TextVerticalAlignment::top => key_generated::Qt_AlignmentFlag_AlignTop,
+            TextVerticalAlignment::center => key_generated::Qt_AlignmentFlag_AlignVCenter,
+            TextVerticalAlignment::bottom => key_generated::Qt_AlignmentFlag_AlignBottom,
+        } | match text.wrap() {
+            TextWrap::no_wrap => 0,
+            TextWrap::word_wrap => key_generated::Qt_TextFlag_TextWordWrap,
+        };
+        // The text is laid out as if it was not elided
+        let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF"]
+                -> qttypes::QPointF as "QPointF" {
+            string.replace(QChar('\n'), QChar::LineSeparator);
+            QTextLayout layout(string, font);
+            do_text_layout(layout, flags, rect);
+
+            QTextLine textLine = layout.lineForTextPosition(offset);
+            if (!textLine.isValid())
+                return QPointF();
+            return QPointF(textLine.x() + textLine.cursorToX(offset), textLine.y());
+        }};
+        Some(Point::new(r.x as _, r.y as _))
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
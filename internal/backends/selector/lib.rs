@@ -3,7 +3,14 @@
 
 #![doc = include_str!("README.md")]
 #![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
-#![cfg_attr(not(any(feature = "i-slint-backend-qt", feature = "i-slint-backend-gl")), no_std)]
+#![cfg_attr(
+    not(any(
+        feature = "i-slint-backend-qt",
+        feature = "i-slint-backend-gl",
+        feature = "headless"
+    )),
+    no_std
+)]
 
 use core::pin::Pin;
 
@@ -38,6 +45,10 @@ pub fn backend() -> &'static dyn i_slint_core::backend::Backend {
                 if backend_config == "GL" {
                     return Box::new(i_slint_backend_gl::Backend);
                 }
+                #[cfg(feature = "headless")]
+                if backend_config == "headless" {
+                    return Box::new(i_slint_backend_testing::TestingBackend::new_headless());
+                }
 
                 #[cfg(any(
                     feature = "i-slint-backend-qt",
@@ -54,6 +65,11 @@ pub fn backend() -> &'static dyn i_slint_core::backend::Backend {
             native_widgets, Backend, NativeGlobals, NativeWidgets, HAS_NATIVE_STYLE,
         };
     } else {
+        #[cfg(feature = "headless")]
+        pub fn backend() -> &'static dyn i_slint_core::backend::Backend {
+            i_slint_core::backend::instance_or_init(|| Box::new(i_slint_backend_testing::TestingBackend::new_headless()))
+        }
+        #[cfg(not(feature = "headless"))]
         pub fn backend() -> &'static dyn i_slint_core::backend::Backend {
             i_slint_core::backend::instance().expect("no default backend configured, the backend must be initialized manually")
         }
@@ -6,29 +6,99 @@
 
 use i_slint_core::component::ComponentRc;
 use i_slint_core::graphics::{Image, IntSize, Point, Size};
-use i_slint_core::window::{PlatformWindow, Window};
+use i_slint_core::window::{PlatformWindow, PopupWindow, PopupWindowLocation, Window};
 use i_slint_core::{ImageInner, StaticTextures};
 use image::GenericImageView;
+use std::cell::Cell;
+use std::collections::VecDeque;
 use std::path::Path;
 use std::pin::Pin;
-use std::rc::Rc;
-use std::sync::Mutex;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+
+/// The number of windows that are shown, to quit the event loop when the last one is hidden
+static SHOWN_WINDOWS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct EventQueue {
+    events: VecDeque<Box<dyn FnOnce() + Send>>,
+    quit: bool,
+}
 
 #[derive(Default)]
 pub struct TestingBackend {
     clipboard: Mutex<Option<String>>,
+    queue: Mutex<EventQueue>,
+    queue_changed: Condvar,
+    /// When true, the time is the one of the system clock instead of the one mocked by
+    /// `slint::testing::mock_elapsed_time`, and the event loop activates the timers when they expire
+    system_clock: bool,
+}
+
+impl TestingBackend {
+    /// A backend without windowing system and without rendering engine, whose time is the one
+    /// of the system clock. This is the headless backend of the backend selector.
+    pub fn new_headless() -> Self {
+        Self { system_clock: true, ..Default::default() }
+    }
 }
 
 impl i_slint_core::backend::Backend for TestingBackend {
     fn create_window(&'static self) -> Rc<Window> {
-        Window::new(|_| Rc::new(TestingWindow::default()))
+        Window::new(|window| {
+            Rc::new(TestingWindow { self_weak: window.clone(), shown: Default::default() })
+        })
     }
 
-    fn run_event_loop(&'static self, _behavior: i_slint_core::backend::EventLoopQuitBehavior) {
-        unimplemented!("running an event loop with the testing backend");
+    fn run_event_loop(&'static self, behavior: i_slint_core::backend::EventLoopQuitBehavior) {
+        use i_slint_core::timers::TimerList;
+        loop {
+            let event = {
+                let mut queue = self.queue.lock().unwrap();
+                loop {
+                    if queue.quit {
+                        queue.quit = false;
+                        return;
+                    }
+                    if let Some(event) = queue.events.pop_front() {
+                        break Some(event);
+                    }
+                    if matches!(
+                        behavior,
+                        i_slint_core::backend::EventLoopQuitBehavior::QuitOnLastWindowClosed
+                    ) && SHOWN_WINDOWS.load(Ordering::SeqCst) == 0
+                    {
+                        return;
+                    }
+                    // With the mocked time, the timers are only activated by mock_elapsed_time
+                    match TimerList::next_timeout().filter(|_| self.system_clock) {
+                        Some(timeout) => {
+                            let now = i_slint_core::animations::Instant::now();
+                            if timeout <= now {
+                                break None;
+                            }
+                            queue =
+                                self.queue_changed.wait_timeout(queue, timeout - now).unwrap().0;
+                        }
+                        None => queue = self.queue_changed.wait(queue).unwrap(),
+                    }
+                }
+            };
+            if let Some(event) = event {
+                event();
+            }
+            if self.system_clock {
+                i_slint_core::animations::update_animations();
+                TimerList::maybe_activate_timers();
+            }
+        }
     }
 
-    fn quit_event_loop(&'static self) {}
+    fn quit_event_loop(&'static self) {
+        self.queue.lock().unwrap().quit = true;
+        self.queue_changed.notify_all();
+    }
 
     fn register_font_from_memory(
         &'static self,
@@ -52,8 +122,16 @@ fn clipboard_text(&'static self) -> Option<String> {
         self.clipboard.lock().unwrap().clone()
     }
 
-    fn post_event(&'static self, _event: Box<dyn FnOnce() + Send>) {
-        // The event will never be invoked
+    fn allow_event_loop_in_any_thread(
+        &'static self,
+    ) -> Result<(), i_slint_core::api::EventLoopThreadError> {
+        // There is no windowing system to require a thread
+        Ok(())
+    }
+
+    fn post_event(&'static self, event: Box<dyn FnOnce() + Send>) {
+        self.queue.lock().unwrap().events.push_back(event);
+        self.queue_changed.notify_all();
     }
 
     fn image_size(&'static self, image: &Image) -> IntSize {
@@ -76,20 +154,37 @@ fn image_size(&'static self, image: &Image) -> IntSize {
     }
 
     fn duration_since_start(&'static self) -> core::time::Duration {
+        if self.system_clock {
+            let the_beginning = *INITIAL_INSTANT.get_or_init(std::time::Instant::now);
+            return std::time::Instant::now() - the_beginning;
+        }
         // The slint::testing::mock_elapsed_time updates the animation tick directly
         core::time::Duration::from_millis(i_slint_core::animations::current_tick().0)
     }
 }
 
-#[derive(Default)]
-pub struct TestingWindow {}
+static INITIAL_INSTANT: once_cell::sync::OnceCell<std::time::Instant> =
+    once_cell::sync::OnceCell::new();
+
+pub struct TestingWindow {
+    self_weak: Weak<Window>,
+    shown: Cell<bool>,
+}
 
 impl PlatformWindow for TestingWindow {
     fn show(self: Rc<Self>) {
-        unimplemented!("showing a testing window")
+        // The window is not shown anywhere, it only keeps the event loop running
+        if !self.shown.replace(true) {
+            SHOWN_WINDOWS.fetch_add(1, Ordering::SeqCst);
+        }
     }
 
-    fn hide(self: Rc<Self>) {}
+    fn hide(self: Rc<Self>) {
+        if self.shown.replace(false) && SHOWN_WINDOWS.fetch_sub(1, Ordering::SeqCst) == 1 {
+            // Wake up the event loop so that it quits when the last window is closed
+            i_slint_core::backend::instance().unwrap().post_event(Box::new(|| {}));
+        }
+    }
 
     fn request_redraw(&self) {}
 
@@ -99,15 +194,17 @@ fn free_graphics_resources<'a>(
     ) {
     }
 
-    fn show_popup(&self, _popup: &ComponentRc, _position: i_slint_core::graphics::Point) {
-        todo!()
+    fn show_popup(&self, popup: &ComponentRc, position: i_slint_core::graphics::Point) {
+        // Register the popup so that it receives the input events until it is closed
+        self.self_weak.upgrade().unwrap().set_active_popup(PopupWindow {
+            location: PopupWindowLocation::ChildWindow(position),
+            component: popup.clone(),
+        });
     }
 
     fn request_window_properties_update(&self) {}
 
-    fn apply_window_properties(&self, _window_item: Pin<&i_slint_core::items::WindowItem>) {
-        todo!()
-    }
+    fn apply_window_properties(&self, _window_item: Pin<&i_slint_core::items::WindowItem>) {}
 
     fn apply_geometry_constraint(
         &self,
@@ -129,18 +226,34 @@ fn text_size(
 
     fn text_input_byte_offset_for_position(
         &self,
-        _text_input: Pin<&i_slint_core::items::TextInput>,
-        _pos: Point,
+        text_input: Pin<&i_slint_core::items::TextInput>,
+        pos: Point,
     ) -> usize {
-        0
+        byte_offset_for_position(&text_input.text(), pos)
     }
 
     fn text_input_position_for_byte_offset(
         &self,
-        _text_input: Pin<&i_slint_core::items::TextInput>,
-        _byte_offset: usize,
+        text_input: Pin<&i_slint_core::items::TextInput>,
+        byte_offset: usize,
     ) -> Point {
-        Default::default()
+        position_for_byte_offset(&text_input.text(), byte_offset)
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&i_slint_core::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        Some(byte_offset_for_position(&text.text(), pos))
+    }
+
+    fn text_position_for_byte_offset(
+        &self,
+        text: Pin<&i_slint_core::items::Text>,
+        byte_offset: usize,
+    ) -> Option<Point> {
+        Some(position_for_byte_offset(&text.text(), byte_offset))
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -148,6 +261,20 @@ fn as_any(&self) -> &dyn std::any::Any {
     }
 }
 
+/// Same layout as `text_size`: the text is on a single line, at the top left of the element,
+/// and each byte is 10 pixels wide.
+fn byte_offset_for_position(text: &str, pos: Point) -> usize {
+    let mut offset = ((pos.x / 10.).round().max(0.) as usize).min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+fn position_for_byte_offset(text: &str, byte_offset: usize) -> Point {
+    Point::new(byte_offset.min(text.len()) as f32 * 10., 0.)
+}
+
 /// Initialize the testing backend.
 /// Must be called before any call that would otherwise initialize the rendering backend.
 /// Calling it when the rendering backend is already initialized will have no effects
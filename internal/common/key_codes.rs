@@ -6,7 +6,8 @@
 // The key code comes from https://www.unicode.org/Public/MAPPINGS/VENDORS/APPLE/CORPCHAR.TXT
 // the names comes should match with https://www.w3.org/TR/uievents-key/#named-key-attribute-values,
 
-// NOTE: Update builtin_elements.md when changing/adding/removing keys, to keep the docs in sync!
+// NOTE: Update builtin_elements.md and the LogicalKey enum in internal/core/input.rs when
+// changing/adding/removing keys, to keep them in sync!
 #[macro_export]
 macro_rules! for_each_special_keys {
     ($macro:ident) => {
@@ -24,6 +24,7 @@ pub fn lower_macro(
     match mac {
         BuiltinMacroFunction::Min => min_max_macro(n, '<', sub_expr.collect(), diag),
         BuiltinMacroFunction::Max => min_max_macro(n, '>', sub_expr.collect(), diag),
+        BuiltinMacroFunction::Clamp => clamp_macro(n, sub_expr.collect(), diag),
         BuiltinMacroFunction::Debug => debug_macro(n, sub_expr.collect(), diag),
         BuiltinMacroFunction::CubicBezier => {
             let mut has_error = None;
@@ -54,6 +55,7 @@ pub fn lower_macro(
             expr
         }
         BuiltinMacroFunction::Rgb => rgb_macro(n, sub_expr.collect(), diag),
+        BuiltinMacroFunction::Hsv => hsv_macro(n, sub_expr.collect(), diag),
     }
 }
 
@@ -69,16 +71,9 @@ fn min_max_macro(
     }
     let mut args = args.into_iter();
     let (mut base, arg_node) = args.next().unwrap();
-    let ty = match base.ty() {
-        Type::Float32 => Type::Float32,
-        // In case there are other floats, we don't want to convert the result to int
-        Type::Int32 => Type::Float32,
-        Type::PhysicalLength => Type::PhysicalLength,
-        Type::LogicalLength => Type::LogicalLength,
-        Type::Duration => Type::Duration,
-        Type::Angle => Type::Angle,
-        Type::Percent => Type::Float32,
-        _ => {
+    let ty = match min_max_type(&base.ty()) {
+        Some(ty) => ty,
+        None => {
             diag.push_error("Invalid argument type".into(), &arg_node);
             return Expression::Invalid;
         }
@@ -90,6 +85,47 @@ fn min_max_macro(
     base
 }
 
+/// The type in which the arguments of min, max, or clamp are compared
+fn min_max_type(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Float32 => Some(Type::Float32),
+        // In case there are other floats, we don't want to convert the result to int
+        Type::Int32 => Some(Type::Float32),
+        Type::PhysicalLength => Some(Type::PhysicalLength),
+        Type::LogicalLength => Some(Type::LogicalLength),
+        Type::Duration => Some(Type::Duration),
+        Type::DateTime => Some(Type::DateTime),
+        Type::Angle => Some(Type::Angle),
+        Type::Percent => Some(Type::Float32),
+        _ => None,
+    }
+}
+
+/// `clamp(value, min, max)` is lowered to `max(min(value, max), min)`
+fn clamp_macro(
+    node: Option<NodeOrToken>,
+    args: Vec<(Expression, Option<NodeOrToken>)>,
+    diag: &mut BuildDiagnostics,
+) -> Expression {
+    if args.len() != 3 {
+        diag.push_error("Needs 3 arguments".into(), &node);
+        return Expression::Invalid;
+    }
+    let mut args = args.into_iter();
+    let (value, value_node) = args.next().unwrap();
+    let ty = match min_max_type(&value.ty()) {
+        Some(ty) => ty,
+        None => {
+            diag.push_error("Invalid argument type".into(), &value_node);
+            return Expression::Invalid;
+        }
+    };
+    let value = value.maybe_convert_to(ty.clone(), &value_node, diag);
+    let mut args = args.map(|(e, n)| e.maybe_convert_to(ty.clone(), &n, diag));
+    let (min, max) = (args.next().unwrap(), args.next().unwrap());
+    min_max_expression(min_max_expression(value, max, '<'), min, '>')
+}
+
 fn rgb_macro(
     node: Option<NodeOrToken>,
     args: Vec<(Expression, Option<NodeOrToken>)>,
@@ -131,6 +167,44 @@ fn rgb_macro(
     }
 }
 
+fn hsv_macro(
+    node: Option<NodeOrToken>,
+    args: Vec<(Expression, Option<NodeOrToken>)>,
+    diag: &mut BuildDiagnostics,
+) -> Expression {
+    if args.len() < 3 || args.len() > 4 {
+        diag.push_error("Needs 3 or 4 argument".into(), &node);
+        return Expression::Invalid;
+    }
+    let mut arguments: Vec<_> = args
+        .into_iter()
+        .enumerate()
+        .map(|(i, (expr, n))| {
+            // The hue is an angle or a number of degrees
+            if i == 0 && expr.ty() == Type::Angle {
+                Expression::BinaryExpression {
+                    lhs: Box::new(expr),
+                    rhs: Box::new(Expression::NumberLiteral(1., Unit::Deg)),
+                    op: '/',
+                }
+            } else {
+                expr.maybe_convert_to(Type::Float32, &n, diag)
+            }
+        })
+        .collect();
+    if arguments.len() < 4 {
+        arguments.push(Expression::NumberLiteral(1., Unit::None))
+    }
+    Expression::FunctionCall {
+        function: Box::new(Expression::BuiltinFunctionReference(
+            BuiltinFunction::Hsv,
+            node.as_ref().map(|t| t.to_source_location()),
+        )),
+        arguments,
+        source_location: Some(node.to_source_location()),
+    }
+}
+
 fn debug_macro(
     node: Option<NodeOrToken>,
     args: Vec<(Expression, Option<NodeOrToken>)>,
@@ -181,6 +255,7 @@ fn to_debug_string(
         | Type::Function { .. }
         | Type::ElementReference
         | Type::LayoutCache
+        | Type::ComponentFactory
         | Type::Model
         | Type::PathData => {
             diag.push_error("Cannot debug this expression".into(), &node);
@@ -189,7 +264,13 @@ fn to_debug_string(
         Type::Float32 | Type::Int32 => expr.maybe_convert_to(Type::String, &node, diag),
         Type::String => expr,
         // TODO
-        Type::Color | Type::Brush | Type::Image | Type::Easing | Type::Array(_) => {
+        Type::Color
+        | Type::Brush
+        | Type::Image
+        | Type::Easing
+        | Type::Array(_)
+        | Type::Map(_)
+        | Type::DateTime => {
             Expression::StringLiteral("<debug-of-this-type-not-yet-implemented>".into())
         }
         Type::Duration
@@ -0,0 +1,282 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Bundles: tar archives with the `.slint` files of a component and the images and fonts they use,
+    that the interpreter loads as a single file.
+
+    The component of a bundle is in the `main.slint` file at the root of the archive. This file can
+    also be a symbolic link to the main file in a sub-directory, so that the relative paths used in
+    that file still resolve within the archive.
+*/
+
+use crate::object_tree::Document;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The file of the bundle that contains the component to load
+pub const BUNDLE_MAIN_FILE: &str = "main.slint";
+
+const BLOCK_SIZE: usize = 512;
+
+/// The content of a bundle
+pub struct Bundle {
+    /// The regular files, with their path relative to the root of the archive
+    pub files: Vec<(String, Vec<u8>)>,
+    /// The path of the file that contains the component, relative to the root of the archive
+    pub main_file: String,
+}
+
+/// Read the content of a bundle
+pub fn read_bundle(data: &[u8]) -> Result<Bundle, String> {
+    let mut files = vec![];
+    let mut main_file = BUNDLE_MAIN_FILE.to_owned();
+    let mut offset = 0;
+    while offset + BLOCK_SIZE <= data.len() {
+        let header = &data[offset..offset + BLOCK_SIZE];
+        // The archive ends with blocks of zeros
+        if header.iter().all(|b| *b == 0) {
+            break;
+        }
+        let field = |range: std::ops::Range<usize>| {
+            let field = &header[range];
+            &field[..field.iter().position(|b| *b == 0).unwrap_or(field.len())]
+        };
+        let text = |range| {
+            std::str::from_utf8(field(range)).map_err(|_| "Invalid file name in the bundle")
+        };
+
+        let name = text(0..100)?;
+        let prefix = if field(257..262) == b"ustar" { text(345..500)? } else { "" };
+        let name = if prefix.is_empty() { name.to_owned() } else { format!("{}/{}", prefix, name) };
+        let name = name.trim_start_matches("./").to_owned();
+        let size = text(124..136)?.trim_matches(|c: char| c == ' ' || c == '\0');
+        let size = usize::from_str_radix(if size.is_empty() { "0" } else { size }, 8)
+            .map_err(|_| format!("Invalid size of {} in the bundle", name))?;
+
+        let start = offset + BLOCK_SIZE;
+        let end = start + size;
+        if end > data.len() {
+            return Err(format!("The bundle is truncated in {}", name));
+        }
+        match header[156] {
+            b'0' | 0 => files.push((name, data[start..end].to_vec())),
+            b'2' if name == BUNDLE_MAIN_FILE => {
+                main_file = text(157..257)?.trim_start_matches("./").to_owned();
+            }
+            // Only keep the regular files
+            _ => {}
+        }
+        offset = start + (size + BLOCK_SIZE - 1) / BLOCK_SIZE * BLOCK_SIZE;
+    }
+    if !files.iter().any(|(name, _)| *name == main_file) {
+        return Err(format!("The bundle does not contain a {} file", BUNDLE_MAIN_FILE));
+    }
+    Ok(Bundle { files, main_file })
+}
+
+/// Write a bundle with the `.slint` files of a compilation, and the images and fonts they use.
+///
+/// `loaded_files` are the files imported by the main file, as in
+/// [`crate::diagnostics::BuildDiagnostics::all_loaded_files`]. The images and fonts are the
+/// ones of the `embedded_file_resources` of the root component, so the document must be compiled
+/// with [`crate::CompilerConfiguration::embed_resources`].
+///
+/// The files are stored relative to their common directory, so that the imports and the
+/// `@image-url` with a relative path still resolve in the bundle. The builtin files and the
+/// remote urls are not part of the bundle.
+pub fn write_bundle(
+    doc: &Document,
+    main_file: &Path,
+    loaded_files: &[PathBuf],
+    destination: &mut impl Write,
+) -> std::io::Result<()> {
+    let mut files = loaded_files.to_vec();
+    files.extend(doc.root_component.embedded_file_resources.borrow().keys().map(PathBuf::from));
+    write_bundle_files(main_file, files, &mut |path| std::fs::read(path), destination)?;
+    Ok(())
+}
+
+/// Write a bundle with the main file and the other files of a component, such as the
+/// dependencies of a component of the interpreter. `read` returns the content of a file, which
+/// allows bundling files that are being edited and not saved yet.
+///
+/// The builtin files and the remote urls are skipped. Returns the common directory of the
+/// files, which is the root of the bundle.
+pub fn write_bundle_files(
+    main_file: &Path,
+    files: impl IntoIterator<Item = PathBuf>,
+    read: &mut dyn FnMut(&Path) -> std::io::Result<Vec<u8>>,
+    destination: &mut impl Write,
+) -> std::io::Result<PathBuf> {
+    let is_local =
+        |path: &Path| !path.starts_with("builtin:/") && !path.to_string_lossy().contains("://");
+    let canonicalize = |path: &Path| dunce::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+    let main_file = canonicalize(main_file);
+
+    let mut files: BTreeSet<PathBuf> =
+        files.into_iter().filter(|path| is_local(path)).map(|path| canonicalize(&path)).collect();
+    files.insert(main_file.clone());
+
+    let mut root = main_file.parent().map(Path::to_owned).unwrap_or_default();
+    for file in &files {
+        while !file.starts_with(&root) {
+            root = root.parent().map(Path::to_owned).unwrap_or_default();
+        }
+    }
+    let relative_name = |path: &Path| -> String {
+        path.strip_prefix(&root)
+            .unwrap()
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+
+    let main_name = relative_name(&main_file);
+    for file in &files {
+        let name = relative_name(file);
+        if name == BUNDLE_MAIN_FILE && main_name != BUNDLE_MAIN_FILE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "{} is not the main file, but it would be the main file of the bundle",
+                    file.display()
+                ),
+            ));
+        }
+        write_tar_entry(destination, &name, b'0', "", &read(file)?)?;
+    }
+    if main_name != BUNDLE_MAIN_FILE {
+        write_tar_entry(destination, BUNDLE_MAIN_FILE, b'2', &main_name, &[])?;
+    }
+    destination.write_all(&[0; 2 * BLOCK_SIZE])?;
+    Ok(root)
+}
+
+/// Write the header and the content of a file in the ustar format
+fn write_tar_entry(
+    destination: &mut impl Write,
+    name: &str,
+    type_flag: u8,
+    link_name: &str,
+    content: &[u8],
+) -> std::io::Result<()> {
+    let too_long = || {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("The path {} is too long to be stored in a bundle", name),
+        )
+    };
+    // Names longer than 100 bytes are split in a prefix and a name at a '/'
+    let (prefix, name) = if name.len() <= 100 {
+        ("", name)
+    } else {
+        name.match_indices('/')
+            .map(|(pos, _)| (&name[..pos], &name[pos + 1..]))
+            .find(|(prefix, name)| prefix.len() <= 155 && name.len() <= 100)
+            .ok_or_else(too_long)?
+    };
+    if link_name.len() > 100 {
+        return Err(too_long());
+    }
+
+    let mut header = [0u8; BLOCK_SIZE];
+    let mut set = |offset: usize, value: &[u8]| {
+        header[offset..offset + value.len()].copy_from_slice(value);
+    };
+    set(0, name.as_bytes());
+    set(100, b"0000644");
+    set(108, b"0000000");
+    set(116, b"0000000");
+    set(124, format!("{:011o}", content.len()).as_bytes());
+    set(136, b"00000000000");
+    set(156, &[type_flag]);
+    set(157, link_name.as_bytes());
+    set(257, b"ustar\x0000");
+    set(345, prefix.as_bytes());
+    // The checksum is computed with the checksum field filled with spaces
+    set(148, &[b' '; 8]);
+    let checksum: u32 = header.iter().map(|b| *b as u32).sum();
+    header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+
+    destination.write_all(&header)?;
+    destination.write_all(content)?;
+    let padding = (BLOCK_SIZE - content.len() % BLOCK_SIZE) % BLOCK_SIZE;
+    destination.write_all(&vec![0; padding])
+}
+
+#[test]
+fn test_read_written_entries() {
+    let mut data = vec![];
+    write_tar_entry(&mut data, "ui/app.slint", b'0', "", b"export App := Window {}").unwrap();
+    let long_name = format!("{}/{}", "d".repeat(120), "logo.png");
+    write_tar_entry(&mut data, &long_name, b'0', "", &[1, 2, 3]).unwrap();
+    write_tar_entry(&mut data, BUNDLE_MAIN_FILE, b'2', "ui/app.slint", &[]).unwrap();
+    data.extend([0; 2 * BLOCK_SIZE]);
+
+    let bundle = read_bundle(&data).unwrap();
+    assert_eq!(bundle.main_file, "ui/app.slint");
+    assert_eq!(
+        bundle.files,
+        vec![
+            ("ui/app.slint".to_owned(), b"export App := Window {}".to_vec()),
+            (long_name, vec![1, 2, 3]),
+        ]
+    );
+
+    assert!(read_bundle(&data[..BLOCK_SIZE]).is_err());
+}
+
+#[test]
+fn test_write_bundle() {
+    let test_source_path: PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+    let mut data = vec![];
+    write_bundle(
+        &Document::default(),
+        &test_source_path.join("incpath").join("dependency_from_incpath.slint"),
+        &[
+            test_source_path.join("dependency_local.slint"),
+            "builtin:/fluent/std-widgets.slint".into(),
+        ],
+        &mut data,
+    )
+    .unwrap();
+
+    let bundle = read_bundle(&data).unwrap();
+    assert_eq!(bundle.main_file, "incpath/dependency_from_incpath.slint");
+    let names: Vec<_> = bundle.files.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["dependency_local.slint", "incpath/dependency_from_incpath.slint"]);
+    assert_eq!(
+        bundle.files[0].1,
+        std::fs::read(test_source_path.join("dependency_local.slint")).unwrap()
+    );
+}
+
+#[test]
+fn test_write_bundle_files() {
+    let test_source_path: PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+    let main_file = test_source_path.join("dependency_local.slint");
+    let mut data = vec![];
+    let root = write_bundle_files(
+        &main_file,
+        vec!["https://example.com/logo.png".into()],
+        &mut |path| {
+            assert_eq!(path, dunce::canonicalize(&main_file).unwrap());
+            Ok(b"export Edited := Rectangle {}".to_vec())
+        },
+        &mut data,
+    )
+    .unwrap();
+    assert_eq!(root, dunce::canonicalize(&test_source_path).unwrap());
+
+    let bundle = read_bundle(&data).unwrap();
+    assert_eq!(bundle.main_file, "dependency_local.slint");
+    assert_eq!(
+        bundle.files,
+        vec![("dependency_local.slint".to_owned(), b"export Edited := Rectangle {}".to_vec())]
+    );
+}
@@ -0,0 +1,273 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Resolve the `@if (condition) { ... } @else { ... }` blocks at compile time.
+
+    The conditions can only refer to the target platform and to the flags set in the
+    [`CompilerConfiguration`]. The tokens of the inactive branches, and the tokens of the
+    `@if`/`@else` syntax itself, are replaced by whitespace and the document is parsed again.
+    This keeps the offsets of all the remaining tokens, so the diagnostics still point to the
+    right location in the original source.
+*/
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::parser::{normalize_identifier, syntax_nodes, SyntaxKind, SyntaxNode, Token};
+use crate::CompilerConfiguration;
+use rowan::TextRange;
+
+/// Returns the platform name for the target we compile for.
+///
+/// In a build script, this is derived from the cargo target, otherwise from the host.
+pub fn default_target_platform() -> String {
+    let os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_else(|_| std::env::consts::OS.into());
+    let arch =
+        std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_else(|_| std::env::consts::ARCH.into());
+    if arch.starts_with("wasm") {
+        "wasm".into()
+    } else if os == "none" {
+        "embedded".into()
+    } else {
+        os
+    }
+}
+
+/// Return a document where the conditional blocks are replaced by the content of their active branch.
+pub fn apply_conditional_blocks(
+    doc: SyntaxNode,
+    config: &CompilerConfiguration,
+    diag: &mut BuildDiagnostics,
+) -> SyntaxNode {
+    if !doc.node.descendants().any(|n| n.kind() == SyntaxKind::ConditionalBlock) {
+        return doc;
+    }
+
+    let mut ctx = ConditionContext { config, diag, removed: vec![] };
+    visit_children(&doc, &mut ctx);
+    let removed = ctx.removed;
+
+    let tokens = doc
+        .node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .map(|token| {
+            let range = token.text_range();
+            let offset = usize::from(range.start());
+            if removed.iter().any(|r| r.contains_range(range)) {
+                let text: String = token
+                    .text()
+                    .chars()
+                    .flat_map(|c| {
+                        let blank = if c == '\n' { '\n' } else { ' ' };
+                        std::iter::repeat(blank).take(c.len_utf8())
+                    })
+                    .collect();
+                Token {
+                    kind: SyntaxKind::Whitespace,
+                    text: text.into(),
+                    offset,
+                    ..Default::default()
+                }
+            } else {
+                Token {
+                    kind: token.kind(),
+                    text: token.text().into(),
+                    offset,
+                    ..Default::default()
+                }
+            }
+        })
+        .collect();
+
+    // The errors were already reported when parsing the original document
+    let mut ignore_diag = BuildDiagnostics::default();
+    crate::parser::parse_tokens(tokens, doc.source_file, &mut ignore_diag)
+}
+
+struct ConditionContext<'a> {
+    config: &'a CompilerConfiguration,
+    diag: &'a mut BuildDiagnostics,
+    /// The text ranges that must be removed from the document
+    removed: Vec<TextRange>,
+}
+
+fn visit_children(node: &SyntaxNode, ctx: &mut ConditionContext) {
+    for child in node.children() {
+        if child.kind() == SyntaxKind::ConditionalBlock {
+            apply_block(child.into(), ctx);
+        } else {
+            visit_children(&child, ctx);
+        }
+    }
+}
+
+fn apply_block(block: syntax_nodes::ConditionalBlock, ctx: &mut ConditionContext) {
+    let block_range = block.text_range();
+    let (condition, content) = match (
+        block.child_node(SyntaxKind::Expression),
+        block.child_node(SyntaxKind::ConditionalBlockContent),
+    ) {
+        (Some(condition), Some(content)) => (
+            syntax_nodes::Expression::from(condition),
+            syntax_nodes::ConditionalBlockContent::from(content),
+        ),
+        // There was a parse error already
+        _ => {
+            ctx.removed.push(block_range);
+            return;
+        }
+    };
+    let active = match evaluate(&condition, ctx) {
+        Some(ConditionValue::Bool(b)) => b,
+        Some(ConditionValue::String(_)) => {
+            ctx.diag.push_error("The condition of @if must be a boolean".into(), &condition);
+            ctx.removed.push(block_range);
+            return;
+        }
+        None => {
+            ctx.removed.push(block_range);
+            return;
+        }
+    };
+
+    if active {
+        keep_content(&content, block_range.start(), block_range.end(), ctx);
+        return;
+    }
+    let else_branch = match block.ConditionalBlockElse() {
+        Some(else_branch) => else_branch,
+        None => {
+            ctx.removed.push(block_range);
+            return;
+        }
+    };
+    let else_range = else_branch.text_range();
+    if let Some(nested) = else_branch.ConditionalBlock() {
+        ctx.removed.push(TextRange::new(block_range.start(), nested.text_range().start()));
+        apply_block(nested, ctx);
+    } else if let Some(content) = else_branch.ConditionalBlockContent() {
+        keep_content(&content, block_range.start(), else_range.end(), ctx);
+    } else {
+        ctx.removed.push(block_range);
+    }
+}
+
+/// Remove everything between `start` and `end`, except what is within the braces of `content`
+fn keep_content(
+    content: &syntax_nodes::ConditionalBlockContent,
+    start: rowan::TextSize,
+    end: rowan::TextSize,
+    ctx: &mut ConditionContext,
+) {
+    let l_brace = content.child_token(SyntaxKind::LBrace);
+    let r_brace = content.child_token(SyntaxKind::RBrace);
+    match (l_brace, r_brace) {
+        (Some(l_brace), Some(r_brace)) => {
+            ctx.removed.push(TextRange::new(start, l_brace.text_range().end()));
+            ctx.removed.push(TextRange::new(r_brace.text_range().start(), end));
+            visit_children(content, ctx);
+        }
+        // There was a parse error already
+        _ => ctx.removed.push(TextRange::new(start, end)),
+    }
+}
+
+enum ConditionValue {
+    Bool(bool),
+    String(String),
+}
+
+/// Evaluate the condition. Returns None if there was an error.
+fn evaluate(node: &syntax_nodes::Expression, ctx: &mut ConditionContext) -> Option<ConditionValue> {
+    if let Some(sub) = node.Expression() {
+        return evaluate(&sub, ctx);
+    }
+    if let Some(qn) = node.QualifiedName() {
+        let mut identifiers = qn
+            .children_with_tokens()
+            .filter_map(|t| t.into_token())
+            .filter(|t| t.kind() == SyntaxKind::Identifier);
+        let ident = identifiers.next().map(|t| normalize_identifier(t.text()));
+        if identifiers.next().is_some() {
+            ctx.diag.push_error("Flags used in @if conditions cannot contain a '.'".into(), &qn);
+            return None;
+        }
+        return Some(match ident?.as_str() {
+            "true" => ConditionValue::Bool(true),
+            "false" => ConditionValue::Bool(false),
+            "platform" => ConditionValue::String(ctx.config.target_platform.clone()),
+            flag => ConditionValue::Bool(
+                ctx.config.conditional_flags.iter().any(|f| normalize_identifier(f) == flag),
+            ),
+        });
+    }
+    if let Some(s) = node.child_text(SyntaxKind::StringLiteral) {
+        return match crate::literals::unescape_string(&s) {
+            Some(s) => Some(ConditionValue::String(s)),
+            None => {
+                ctx.diag.push_error("Cannot parse string literal".into(), node);
+                None
+            }
+        };
+    }
+    if let Some(unary) = node.UnaryOpExpression() {
+        if unary.child_token(SyntaxKind::Bang).is_some() {
+            let sub = unary.Expression();
+            return match evaluate(&sub, ctx)? {
+                ConditionValue::Bool(b) => Some(ConditionValue::Bool(!b)),
+                ConditionValue::String(_) => {
+                    ctx.diag.push_error("The operand of '!' must be a boolean".into(), &sub);
+                    None
+                }
+            };
+        }
+    }
+    if let Some(binary) = node.BinaryExpression() {
+        let op = None
+            .or_else(|| binary.child_token(SyntaxKind::EqualEqual).and(Some('=')))
+            .or_else(|| binary.child_token(SyntaxKind::NotEqual).and(Some('!')))
+            .or_else(|| binary.child_token(SyntaxKind::AndAnd).and(Some('&')))
+            .or_else(|| binary.child_token(SyntaxKind::OrOr).and(Some('|')));
+        if let Some(op) = op {
+            let (lhs_n, rhs_n) = binary.Expression();
+            let lhs = evaluate(&lhs_n, ctx);
+            let rhs = evaluate(&rhs_n, ctx);
+            return match (op, lhs?, rhs?) {
+                ('=', ConditionValue::Bool(a), ConditionValue::Bool(b)) => {
+                    Some(ConditionValue::Bool(a == b))
+                }
+                ('=', ConditionValue::String(a), ConditionValue::String(b)) => {
+                    Some(ConditionValue::Bool(a == b))
+                }
+                ('!', ConditionValue::Bool(a), ConditionValue::Bool(b)) => {
+                    Some(ConditionValue::Bool(a != b))
+                }
+                ('!', ConditionValue::String(a), ConditionValue::String(b)) => {
+                    Some(ConditionValue::Bool(a != b))
+                }
+                ('&', ConditionValue::Bool(a), ConditionValue::Bool(b)) => {
+                    Some(ConditionValue::Bool(a && b))
+                }
+                ('|', ConditionValue::Bool(a), ConditionValue::Bool(b)) => {
+                    Some(ConditionValue::Bool(a || b))
+                }
+                ('=' | '!', _, _) => {
+                    ctx.diag.push_error("Cannot compare a boolean with a string".into(), &binary);
+                    None
+                }
+                _ => {
+                    ctx.diag.push_error(
+                        "The operands of '&&' and '||' must be booleans".into(),
+                        &binary,
+                    );
+                    None
+                }
+            };
+        }
+    }
+    ctx.diag.push_error(
+        "Unsupported expression in @if condition. Only flags, 'platform', string literals, and the operators '!', '&&', '||', '==', '!=' are allowed".into(),
+        node,
+    );
+    None
+}
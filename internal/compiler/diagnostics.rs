@@ -126,6 +126,7 @@ pub fn load_from_path(path: &Path) -> Result<String, Diagnostic> {
             span: Default::default(),
         },
         level: DiagnosticLevel::Error,
+        edits: Vec::new(),
     })
 }
 
@@ -181,6 +182,26 @@ fn from(l: DiagnosticLevel) -> Self {
     }
 }
 
+/// A replacement of some text of the file of a [`Diagnostic`], that fixes what the diagnostic
+/// reports, and that tools can apply without asking the user (eg: the new name of a deprecated
+/// property)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceEdit {
+    /// The offset in bytes of the replaced text within the file
+    pub offset: usize,
+    /// The length in bytes of the replaced text
+    pub length: usize,
+    /// The text that replaces it
+    pub replacement: String,
+}
+
+impl SourceEdit {
+    /// Replace the `length` bytes at the position of `source` with `replacement`
+    pub fn new(source: &dyn Spanned, length: usize, replacement: impl Into<String>) -> Self {
+        Self { offset: source.span().offset, length, replacement: replacement.into() }
+    }
+}
+
 /// This structure represent a diagnostic emitted while compiling .slint code.
 ///
 /// It is basically a message, a level (warning or error), attached to a
@@ -190,6 +211,7 @@ pub struct Diagnostic {
     message: String,
     span: SourceLocation,
     level: DiagnosticLevel,
+    edits: Vec<SourceEdit>,
 }
 
 impl Diagnostic {
@@ -226,6 +248,11 @@ pub fn line_column(&self) -> (usize, usize) {
     pub fn source_file(&self) -> Option<&Path> {
         self.span.source_file().map(|sf| sf.path())
     }
+
+    /// The edits of the source file that fix this diagnostic, if it can be fixed automatically
+    pub fn edits(&self) -> &[SourceEdit] {
+        &self.edits
+    }
 }
 
 impl std::fmt::Display for Diagnostic {
@@ -270,7 +297,7 @@ pub fn push_diagnostic_with_span(
             "Error message should not end with a period: ({:?})",
             message
         );
-        self.inner.push(Diagnostic { message, span, level });
+        self.inner.push(Diagnostic { message, span, level, edits: Vec::new() });
     }
     pub fn push_error_with_span(&mut self, message: String, span: SourceLocation) {
         self.push_diagnostic_with_span(message, span, DiagnosticLevel::Error)
@@ -288,19 +315,34 @@ pub fn push_compiler_error(&mut self, error: Diagnostic) {
         self.inner.push(error);
     }
 
+    /// Push a diagnostic with the edits that fix it
+    pub fn push_diagnostic_with_edits(
+        &mut self,
+        message: String,
+        source: &dyn Spanned,
+        level: DiagnosticLevel,
+        edits: Vec<SourceEdit>,
+    ) {
+        self.push_diagnostic(message, source, level);
+        self.inner.last_mut().unwrap().edits = edits;
+    }
+
+    /// Report the use of a deprecated property. `source` must be at the start of the name of the
+    /// property, which is replaced by the new name in the edit of the diagnostic.
     pub fn push_property_deprecation_warning(
         &mut self,
         old_property: &str,
         new_property: &str,
         source: &dyn Spanned,
     ) {
-        self.push_diagnostic_with_span(
+        self.push_diagnostic_with_edits(
             format!(
                 "The property '{}' has been deprecated. Please use '{}' instead",
                 old_property, new_property
             ),
-            source.to_source_location(),
+            source,
             crate::diagnostics::DiagnosticLevel::Warning,
+            vec![SourceEdit::new(source, old_property.len(), new_property)],
         )
     }
 
@@ -490,3 +532,36 @@ pub fn print_warnings_and_exit_on_error(self) {
         }
     }
 }
+
+#[test]
+fn test_deprecation_edits() {
+    let source = r#"import { Button } from "sixtyfps_widgets.60";
+export Test := Window {
+    Rectangle { color: red; }
+    Button { }
+}"#;
+    let mut diag = BuildDiagnostics::default();
+    let node = crate::parser::parse(source.into(), None, &mut diag);
+    let mut config = crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    config.style = Some("fluent".into());
+    let (_, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+    assert!(!diag.has_error());
+
+    let mut edits = diag.iter().flat_map(|d| d.edits().iter().cloned()).collect::<Vec<_>>();
+    edits.sort_by_key(|e| e.offset);
+    assert_eq!(
+        edits,
+        vec![
+            SourceEdit {
+                offset: source.find("\"sixtyfps").unwrap(),
+                length: "\"sixtyfps_widgets.60\"".len(),
+                replacement: "\"std-widgets.slint\"".into(),
+            },
+            SourceEdit {
+                offset: source.find("color").unwrap(),
+                length: "color".len(),
+                replacement: "background".into(),
+            },
+        ]
+    );
+}
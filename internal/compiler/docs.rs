@@ -0,0 +1,262 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Extraction of the documentation of the exported components of a .slint file.
+
+    The documentation of a component or of one of its properties, callbacks, and functions is the
+    block of `///` comments just before its declaration. The extraction only uses the syntax tree,
+    so the types and the default values are as written in the source, and the imports are not loaded.
+*/
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::parser::{identifier_text, syntax_nodes, SyntaxKind, SyntaxNode};
+
+/// The documentation of an exported component
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentDoc {
+    /// The name under which the component is exported
+    pub name: String,
+    /// The base of the component, such as `Rectangle`
+    pub base: Option<String>,
+    /// The content of the doc comment, without the `///`
+    pub doc: String,
+    pub properties: Vec<MemberDoc>,
+    pub callbacks: Vec<MemberDoc>,
+    pub functions: Vec<MemberDoc>,
+}
+
+/// The documentation of a property, a callback, or a function of a component
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemberDoc {
+    pub name: String,
+    /// The type of a property, or the signature of a callback or a function, such as `(int) -> string`
+    pub ty: String,
+    /// The default value of a property, or the `<=> other.property` of an alias
+    pub default: Option<String>,
+    /// The content of the doc comment, without the `///`
+    pub doc: String,
+}
+
+/// Parse the source code and return the documentation of its exported components.
+///
+/// The syntax errors are reported in the diagnostics.
+pub fn extract_docs_from_source(
+    source: String,
+    path: Option<&std::path::Path>,
+    diag: &mut BuildDiagnostics,
+) -> Vec<ComponentDoc> {
+    let node = crate::parser::parse(source, path, diag);
+    syntax_nodes::Document::new(node).map(|doc| extract_docs(&doc)).unwrap_or_default()
+}
+
+/// Return the documentation of the exported components of a document, in the order of the exports
+pub fn extract_docs(doc: &syntax_nodes::Document) -> Vec<ComponentDoc> {
+    let components: Vec<syntax_nodes::Component> =
+        doc.Component().chain(doc.ExportsList().filter_map(|e| e.Component())).collect();
+    let find_component = |name: &str| {
+        components
+            .iter()
+            .find(|c| identifier_text(&c.DeclaredIdentifier()).as_deref() == Some(name))
+    };
+
+    let mut result = vec![];
+    for exports in doc.ExportsList() {
+        if let Some(component) = exports.Component() {
+            let name = identifier_text(&component.DeclaredIdentifier()).unwrap_or_default();
+            result.push(component_doc(&component, name));
+        }
+        for specifier in exports.ExportSpecifier() {
+            let internal_name = identifier_text(&specifier.ExportIdentifier()).unwrap_or_default();
+            // The components imported from other files are documented with these files
+            if let Some(component) = find_component(&internal_name) {
+                let name = specifier
+                    .ExportName()
+                    .and_then(|n| identifier_text(&n))
+                    .unwrap_or(internal_name);
+                result.push(component_doc(component, name));
+            }
+        }
+    }
+    // Without export, the last component is exported
+    if doc.ExportsList().next().is_none() {
+        if let Some(component) = components.last() {
+            let name = identifier_text(&component.DeclaredIdentifier()).unwrap_or_default();
+            result.push(component_doc(component, name));
+        }
+    }
+    result
+}
+
+fn component_doc(component: &syntax_nodes::Component, name: String) -> ComponentDoc {
+    let element = component.Element();
+    // The comment of `export Foo := ...` is before the `export`
+    let doc = match component.parent() {
+        Some(parent) if parent.kind() == SyntaxKind::ExportsList => doc_comment(&parent),
+        _ => doc_comment(component),
+    };
+    let properties = element
+        .PropertyDeclaration()
+        .map(|p| MemberDoc {
+            name: identifier_text(&p.DeclaredIdentifier()).unwrap_or_default(),
+            ty: p.Type().map(|t| source_text(&t)).unwrap_or_default(),
+            default: p
+                .BindingExpression()
+                .map(|b| source_text(&b).trim_end_matches(';').trim_end().to_owned())
+                .or_else(|| {
+                    p.TwoWayBinding().map(|b| format!("<=> {}", source_text(&b.Expression())))
+                }),
+            doc: doc_comment(&p),
+        })
+        .collect();
+    let callbacks = element
+        .CallbackDeclaration()
+        .map(|c| MemberDoc {
+            name: identifier_text(&c.DeclaredIdentifier()).unwrap_or_default(),
+            ty: signature(
+                c.Type().map(|t| source_text(&t)),
+                c.ReturnType().map(|r| source_text(&r.Type())),
+            ),
+            default: c.TwoWayBinding().map(|b| format!("<=> {}", source_text(&b.Expression()))),
+            doc: doc_comment(&c),
+        })
+        .collect();
+    let functions = element
+        .Function()
+        .map(|f| MemberDoc {
+            name: identifier_text(&f.DeclaredIdentifier()).unwrap_or_default(),
+            ty: signature(
+                f.ArgumentDeclaration().map(|a| {
+                    format!(
+                        "{}: {}",
+                        identifier_text(&a.DeclaredIdentifier()).unwrap_or_default(),
+                        source_text(&a.Type())
+                    )
+                }),
+                f.ReturnType().map(|r| source_text(&r.Type())),
+            ),
+            default: None,
+            doc: doc_comment(&f),
+        })
+        .collect();
+    ComponentDoc {
+        name,
+        base: element.QualifiedName().map(|q| source_text(&q)),
+        doc,
+        properties,
+        callbacks,
+        functions,
+    }
+}
+
+fn signature(args: impl Iterator<Item = String>, return_type: Option<String>) -> String {
+    let args = args.collect::<Vec<_>>().join(", ");
+    match return_type {
+        Some(return_type) => format!("({}) -> {}", args, return_type),
+        None => format!("({})", args),
+    }
+}
+
+/// The text of the node, on one line and without the comments
+fn source_text(node: &SyntaxNode) -> String {
+    let mut text = String::new();
+    for token in node.descendants_with_tokens().filter_map(|n| n.into_token()) {
+        match token.kind() {
+            SyntaxKind::Comment => {}
+            SyntaxKind::Whitespace => text.push(' '),
+            _ => text += token.text(),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The `///` comments just before the node, separated from it by at most one line break
+fn doc_comment(node: &SyntaxNode) -> String {
+    let mut lines = vec![];
+    let mut previous = node.prev_sibling_or_token();
+    while let Some(rowan::NodeOrToken::Token(token)) = previous {
+        match token.kind() {
+            SyntaxKind::Comment if token.text().starts_with("///") => {
+                let line = &token.text()[3..];
+                lines.push(line.strip_prefix(' ').unwrap_or(line).trim_end().to_owned());
+            }
+            SyntaxKind::Whitespace if token.text().matches('\n').count() <= 1 => {}
+            _ => break,
+        }
+        previous = token.prev_sibling_or_token();
+    }
+    lines.reverse();
+    lines.join("\n")
+}
+
+#[test]
+fn test_extract_docs() {
+    let source = r#"
+// Copyright notice
+
+/// A button with a label
+/// and an icon
+export MyButton := Rectangle {
+    /// The label
+    property <string> text: "OK";
+    property <int> count;
+
+    /// Invoked when clicked
+    callback clicked(int,
+                     string) -> bool;
+    /// Returns twice the value
+    function double(value: int) -> int { return value * 2; }
+}
+
+Helper := Text {
+    property <length> size <=> root.font-size;
+}
+
+export { Helper as PublicHelper }
+"#;
+    let mut diag = BuildDiagnostics::default();
+    let docs = extract_docs_from_source(source.into(), None, &mut diag);
+    assert!(!diag.has_error());
+    assert_eq!(
+        docs,
+        vec![
+            ComponentDoc {
+                name: "MyButton".into(),
+                base: Some("Rectangle".into()),
+                doc: "A button with a label\nand an icon".into(),
+                properties: vec![
+                    MemberDoc {
+                        name: "text".into(),
+                        ty: "string".into(),
+                        default: Some("\"OK\"".into()),
+                        doc: "The label".into()
+                    },
+                    MemberDoc { name: "count".into(), ty: "int".into(), ..Default::default() },
+                ],
+                callbacks: vec![MemberDoc {
+                    name: "clicked".into(),
+                    ty: "(int, string) -> bool".into(),
+                    default: None,
+                    doc: "Invoked when clicked".into()
+                }],
+                functions: vec![MemberDoc {
+                    name: "double".into(),
+                    ty: "(value: int) -> int".into(),
+                    default: None,
+                    doc: "Returns twice the value".into()
+                }],
+            },
+            ComponentDoc {
+                name: "PublicHelper".into(),
+                base: Some("Text".into()),
+                properties: vec![MemberDoc {
+                    name: "size".into(),
+                    ty: "length".into(),
+                    default: Some("<=> root.font-size".into()),
+                    doc: String::new()
+                }],
+                ..Default::default()
+            },
+        ]
+    );
+}
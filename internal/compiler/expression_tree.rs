@@ -18,8 +18,15 @@
 /// A function built into the run-time
 pub enum BuiltinFunction {
     GetWindowScaleFactor,
+    GetWindowBaseFontSize,
+    GetWindowViewportWidth,
+    GetWindowViewportHeight,
+    AnimationTick,
+    HighContrast,
+    ReducedMotion,
     Debug,
     Mod,
+    ModFloat,
     Round,
     Ceil,
     Floor,
@@ -31,19 +38,43 @@ pub enum BuiltinFunction {
     ACos,
     ASin,
     ATan,
+    ATan2,
     Log,
     Pow,
+    Exp,
     SetFocusItem,
     ShowPopupWindow,
+    ClosePopupWindow,
     /// the "42".to_float()
     StringToFloat,
     /// the "42".is_float()
     StringIsFloat,
+    /// the "abc".to_uppercase()
+    StringToUppercase,
+    /// the "ABC".to_lowercase()
+    StringToLowercase,
+    /// the " abc ".trim()
+    StringTrim,
+    /// the "a,b".split(",")
+    StringSplit,
+    /// the "abc".replace("b", "x")
+    StringReplace,
+    /// the "abc".starts_with("a")
+    StringStartsWith,
+    /// the "abc".slice(1, 2)
+    StringSlice,
     ColorBrighter,
     ColorDarker,
+    /// the color.mix(other, factor)
+    ColorMix,
+    /// the color.with-alpha(alpha)
+    ColorWithAlpha,
     ImageSize,
     ArrayLength,
+    /// the model.find-starting-with("a", 0)
+    ArrayFindStartingWith,
     Rgb,
+    Hsv,
     ImplicitLayoutInfo(Orientation),
     RegisterCustomFontByPath,
     RegisterCustomFontByMemory,
@@ -55,8 +86,10 @@ pub enum BuiltinFunction {
 pub enum BuiltinMacroFunction {
     Min,
     Max,
+    Clamp,
     CubicBezier,
     Rgb,
+    Hsv,
     Debug,
 }
 
@@ -67,6 +100,17 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::UnitProduct(vec![(Unit::Phx, 1), (Unit::Px, -1)])),
                 args: vec![],
             },
+            BuiltinFunction::GetWindowBaseFontSize
+            | BuiltinFunction::GetWindowViewportWidth
+            | BuiltinFunction::GetWindowViewportHeight => {
+                Type::Function { return_type: Box::new(Type::LogicalLength), args: vec![] }
+            }
+            BuiltinFunction::AnimationTick => {
+                Type::Function { return_type: Box::new(Type::Duration), args: vec![] }
+            }
+            BuiltinFunction::HighContrast | BuiltinFunction::ReducedMotion => {
+                Type::Function { return_type: Box::new(Type::Bool), args: vec![] }
+            }
             BuiltinFunction::Debug => {
                 Type::Function { return_type: Box::new(Type::Void), args: vec![Type::String] }
             }
@@ -74,10 +118,14 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::Int32),
                 args: vec![Type::Int32, Type::Int32],
             },
+            BuiltinFunction::ModFloat => Type::Function {
+                return_type: Box::new(Type::Float32),
+                args: vec![Type::Float32, Type::Float32],
+            },
             BuiltinFunction::Round | BuiltinFunction::Ceil | BuiltinFunction::Floor => {
                 Type::Function { return_type: Box::new(Type::Int32), args: vec![Type::Float32] }
             }
-            BuiltinFunction::Sqrt | BuiltinFunction::Abs => {
+            BuiltinFunction::Sqrt | BuiltinFunction::Abs | BuiltinFunction::Exp => {
                 Type::Function { return_type: Box::new(Type::Float32), args: vec![Type::Float32] }
             }
             BuiltinFunction::Cos | BuiltinFunction::Sin | BuiltinFunction::Tan => {
@@ -86,6 +134,10 @@ pub fn ty(&self) -> Type {
             BuiltinFunction::ACos | BuiltinFunction::ASin | BuiltinFunction::ATan => {
                 Type::Function { return_type: Box::new(Type::Angle), args: vec![Type::Float32] }
             }
+            BuiltinFunction::ATan2 => Type::Function {
+                return_type: Box::new(Type::Angle),
+                args: vec![Type::Float32, Type::Float32],
+            },
             BuiltinFunction::Log | BuiltinFunction::Pow => Type::Function {
                 return_type: Box::new(Type::Float32),
                 args: vec![Type::Float32, Type::Float32],
@@ -94,16 +146,39 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::Void),
                 args: vec![Type::ElementReference],
             },
-            BuiltinFunction::ShowPopupWindow => Type::Function {
-                return_type: Box::new(Type::Void),
-                args: vec![Type::ElementReference],
-            },
+            BuiltinFunction::ShowPopupWindow | BuiltinFunction::ClosePopupWindow => {
+                Type::Function {
+                    return_type: Box::new(Type::Void),
+                    args: vec![Type::ElementReference],
+                }
+            }
             BuiltinFunction::StringToFloat => {
                 Type::Function { return_type: Box::new(Type::Float32), args: vec![Type::String] }
             }
             BuiltinFunction::StringIsFloat => {
                 Type::Function { return_type: Box::new(Type::Bool), args: vec![Type::String] }
             }
+            BuiltinFunction::StringToUppercase
+            | BuiltinFunction::StringToLowercase
+            | BuiltinFunction::StringTrim => {
+                Type::Function { return_type: Box::new(Type::String), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringSplit => Type::Function {
+                return_type: Box::new(Type::Array(Box::new(Type::String))),
+                args: vec![Type::String, Type::String],
+            },
+            BuiltinFunction::StringReplace => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::String, Type::String],
+            },
+            BuiltinFunction::StringStartsWith => Type::Function {
+                return_type: Box::new(Type::Bool),
+                args: vec![Type::String, Type::String],
+            },
+            BuiltinFunction::StringSlice => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::Int32, Type::Int32],
+            },
             BuiltinFunction::ImplicitLayoutInfo(_) => Type::Function {
                 return_type: Box::new(crate::layout::layout_info_type()),
                 args: vec![Type::ElementReference],
@@ -116,6 +191,14 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::Color),
                 args: vec![Type::Color, Type::Float32],
             },
+            BuiltinFunction::ColorMix => Type::Function {
+                return_type: Box::new(Type::Color),
+                args: vec![Type::Color, Type::Color, Type::Float32],
+            },
+            BuiltinFunction::ColorWithAlpha => Type::Function {
+                return_type: Box::new(Type::Color),
+                args: vec![Type::Color, Type::Float32],
+            },
             BuiltinFunction::ImageSize => Type::Function {
                 return_type: Box::new(Type::Struct {
                     fields: IntoIterator::into_iter([
@@ -131,10 +214,18 @@ pub fn ty(&self) -> Type {
             BuiltinFunction::ArrayLength => {
                 Type::Function { return_type: Box::new(Type::Int32), args: vec![Type::Model] }
             }
+            BuiltinFunction::ArrayFindStartingWith => Type::Function {
+                return_type: Box::new(Type::Int32),
+                args: vec![Type::Model, Type::String, Type::Int32],
+            },
             BuiltinFunction::Rgb => Type::Function {
                 return_type: Box::new(Type::Color),
                 args: vec![Type::Int32, Type::Int32, Type::Int32, Type::Float32],
             },
+            BuiltinFunction::Hsv => Type::Function {
+                return_type: Box::new(Type::Color),
+                args: vec![Type::Float32, Type::Float32, Type::Float32, Type::Float32],
+            },
             BuiltinFunction::RegisterCustomFontByPath => {
                 Type::Function { return_type: Box::new(Type::Void), args: vec![Type::String] }
             }
@@ -151,9 +242,15 @@ pub fn ty(&self) -> Type {
     fn is_pure(&self) -> bool {
         match self {
             BuiltinFunction::GetWindowScaleFactor => false,
+            BuiltinFunction::GetWindowBaseFontSize
+            | BuiltinFunction::GetWindowViewportWidth
+            | BuiltinFunction::GetWindowViewportHeight => false,
+            BuiltinFunction::AnimationTick => false,
+            BuiltinFunction::HighContrast | BuiltinFunction::ReducedMotion => false,
             // Even if it is not pure, we optimize it away anyway
             BuiltinFunction::Debug => true,
             BuiltinFunction::Mod
+            | BuiltinFunction::ModFloat
             | BuiltinFunction::Round
             | BuiltinFunction::Ceil
             | BuiltinFunction::Floor
@@ -166,11 +263,23 @@ fn is_pure(&self) -> bool {
             | BuiltinFunction::ASin
             | BuiltinFunction::Log
             | BuiltinFunction::Pow
-            | BuiltinFunction::ATan => true,
+            | BuiltinFunction::Exp
+            | BuiltinFunction::ATan
+            | BuiltinFunction::ATan2 => true,
             BuiltinFunction::SetFocusItem => false,
-            BuiltinFunction::ShowPopupWindow => false,
+            BuiltinFunction::ShowPopupWindow | BuiltinFunction::ClosePopupWindow => false,
             BuiltinFunction::StringToFloat | BuiltinFunction::StringIsFloat => true,
-            BuiltinFunction::ColorBrighter | BuiltinFunction::ColorDarker => true,
+            BuiltinFunction::StringToUppercase
+            | BuiltinFunction::StringToLowercase
+            | BuiltinFunction::StringTrim
+            | BuiltinFunction::StringSplit
+            | BuiltinFunction::StringReplace
+            | BuiltinFunction::StringStartsWith
+            | BuiltinFunction::StringSlice => true,
+            BuiltinFunction::ColorBrighter
+            | BuiltinFunction::ColorDarker
+            | BuiltinFunction::ColorMix
+            | BuiltinFunction::ColorWithAlpha => true,
             // ImageSize is pure, except when loading images via the network. Then the initial size will be 0/0 and
             // we need to make sure that calls to this function stay within a binding, so that the property
             // notification when updating kicks in. Only the online editor (wasm-interpreter) loads images via the network,
@@ -179,8 +288,8 @@ fn is_pure(&self) -> bool {
             BuiltinFunction::ImageSize => true,
             #[cfg(target_arch = "wasm32")]
             BuiltinFunction::ImageSize => false,
-            BuiltinFunction::ArrayLength => true,
-            BuiltinFunction::Rgb => true,
+            BuiltinFunction::ArrayLength | BuiltinFunction::ArrayFindStartingWith => true,
+            BuiltinFunction::Rgb | BuiltinFunction::Hsv => true,
             BuiltinFunction::ImplicitLayoutInfo(_) => false,
             BuiltinFunction::RegisterCustomFontByPath
             | BuiltinFunction::RegisterCustomFontByMemory
@@ -269,6 +378,12 @@ pub fn normalize(self, x: f64) -> f64 {
     In = "in" -> LogicalLength * 96,
     /// Points
     Pt = "pt" -> LogicalLength * 96./72.,
+    /// Relative to the base font size of the window (lowered when resolving the expression)
+    Rem = "rem" -> LogicalLength,
+    /// Percent of the width of the window (lowered when resolving the expression)
+    Vw = "vw" -> LogicalLength,
+    /// Percent of the height of the window (lowered when resolving the expression)
+    Vh = "vh" -> LogicalLength,
 
     // durations
 
@@ -379,9 +494,10 @@ pub enum Expression {
         name: String,
     },
 
-    /// Access to a index within an array.
+    /// Access to a index within an array, or to the value for a key within a map.
     ArrayIndex {
-        /// This expression should have [`Type::Array`] type
+        /// This expression should have [`Type::Array`] or [`Type::Map`] type.
+        /// For a map, the index is the key, which is a string
         array: Box<Expression>,
         index: Box<Expression>,
     },
@@ -438,6 +554,12 @@ pub enum Expression {
         element_ty: Type,
         values: Vec<Expression>,
     },
+    /// A map literal such as `[ "foo": 42 ]`
+    Map {
+        value_ty: Type,
+        /// First expression in the tuple is the key, which is a string, the second is the value
+        entries: Vec<(Expression, Expression)>,
+    },
     Struct {
         ty: Type,
         values: HashMap<String, Expression>,
@@ -504,6 +626,7 @@ pub fn ty(&self) -> Type {
                     match from.ty() {
                         Type::Float32 | Type::Int32 => Type::Int32,
                         Type::Array(elem) => *elem,
+                        ty @ Type::Map(_) => ty.map_entry_type().unwrap(),
                         _ => Type::Invalid,
                     }
                 } else {
@@ -519,7 +642,7 @@ pub fn ty(&self) -> Type {
                 _ => Type::Invalid,
             },
             Expression::ArrayIndex { array, .. } => match array.ty() {
-                Type::Array(ty) => (*ty).clone(),
+                Type::Array(ty) | Type::Map(ty) => (*ty).clone(),
                 _ => Type::Invalid,
             },
             Expression::Cast { to, .. } => to.clone(),
@@ -549,7 +672,13 @@ pub fn ty(&self) -> Type {
                     Type::Bool
                 } else if *op == '+' || *op == '-' {
                     let (rhs_ty, lhs_ty) = (rhs.ty(), lhs.ty());
-                    if rhs_ty == lhs_ty {
+                    if lhs_ty == Type::DateTime {
+                        match rhs_ty {
+                            Type::DateTime if *op == '-' => Type::Duration,
+                            Type::Duration => Type::DateTime,
+                            _ => Type::Invalid,
+                        }
+                    } else if rhs_ty == lhs_ty {
                         rhs_ty
                     } else {
                         Type::Invalid
@@ -598,6 +727,7 @@ pub fn ty(&self) -> Type {
             }
             Expression::UnaryOp { sub, .. } => sub.ty(),
             Expression::Array { element_ty, .. } => Type::Array(Box::new(element_ty.clone())),
+            Expression::Map { value_ty, .. } => Type::Map(Box::new(value_ty.clone())),
             Expression::Struct { ty, .. } => ty.clone(),
             Expression::PathData { .. } => Type::PathData,
             Expression::StoreLocalVariable { .. } => Type::Void,
@@ -666,6 +796,12 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
                     visitor(x);
                 }
             }
+            Expression::Map { entries, .. } => {
+                for (k, v) in entries {
+                    visitor(k);
+                    visitor(v);
+                }
+            }
             Expression::Struct { values, .. } => {
                 for x in values.values() {
                     visitor(x);
@@ -756,6 +892,12 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
                     visitor(x);
                 }
             }
+            Expression::Map { entries, .. } => {
+                for (k, v) in entries {
+                    visitor(k);
+                    visitor(v);
+                }
+            }
             Expression::Struct { values, .. } => {
                 for x in values.values_mut() {
                     visitor(x);
@@ -841,6 +983,9 @@ pub fn is_constant(&self) -> bool {
             Expression::BinaryExpression { lhs, rhs, .. } => lhs.is_constant() && rhs.is_constant(),
             Expression::UnaryOp { sub, .. } => sub.is_constant(),
             Expression::Array { values, .. } => values.iter().all(Expression::is_constant),
+            Expression::Map { entries, .. } => {
+                entries.iter().all(|(k, v)| k.is_constant() && v.is_constant())
+            }
             Expression::Struct { values, .. } => values.iter().all(|(_, v)| v.is_constant()),
             Expression::PathData(data) => match data {
                 Path::Elements(elements) => elements
@@ -993,6 +1138,20 @@ pub fn maybe_convert_to(
                 },
                 _ => unreachable!(),
             }
+        } else if matches!((&ty, &target_type, &self), (Type::Map(left), Type::Map(right), Expression::Map{..})
+            if left.can_convert(right) || **left == Type::Invalid)
+        {
+            // Special case for converting map literals
+            match (self, target_type) {
+                (Expression::Map { entries, .. }, Type::Map(target_type)) => Expression::Map {
+                    entries: entries
+                        .into_iter()
+                        .map(|(k, v)| (k, v.maybe_convert_to((*target_type).clone(), node, diag)))
+                        .collect(),
+                    value_ty: *target_type,
+                },
+                _ => unreachable!(),
+            }
         } else {
             let mut message = format!("Cannot convert {} to {}", ty, target_type);
             // Explicit error message for unit conversion
@@ -1035,6 +1194,7 @@ pub fn default_value_for_type(ty: &Type) -> Expression {
             | Type::InferredProperty
             | Type::InferredCallback
             | Type::ElementReference
+            | Type::ComponentFactory
             | Type::LayoutCache => Expression::Invalid,
             Type::Float32 => Expression::NumberLiteral(0., Unit::None),
             Type::String => Expression::StringLiteral(String::new()),
@@ -1043,6 +1203,10 @@ pub fn default_value_for_type(ty: &Type) -> Expression {
                 to: ty.clone(),
             },
             Type::Duration => Expression::NumberLiteral(0., Unit::Ms),
+            Type::DateTime => Expression::Cast {
+                from: Box::new(Expression::NumberLiteral(0., Unit::None)),
+                to: Type::DateTime,
+            },
             Type::Angle => Expression::NumberLiteral(0., Unit::Deg),
             Type::PhysicalLength => Expression::NumberLiteral(0., Unit::Phx),
             Type::LogicalLength => Expression::NumberLiteral(0., Unit::Px),
@@ -1057,6 +1221,9 @@ pub fn default_value_for_type(ty: &Type) -> Expression {
             Type::Array(element_ty) => {
                 Expression::Array { element_ty: (**element_ty).clone(), values: vec![] }
             }
+            Type::Map(value_ty) => {
+                Expression::Map { value_ty: (**value_ty).clone(), entries: vec![] }
+            }
             Type::Struct { fields, .. } => Expression::Struct {
                 ty: ty.clone(),
                 values: fields
@@ -1086,7 +1253,8 @@ pub fn try_set_rw(&mut self) -> bool {
             }
             Expression::StructFieldAccess { base, .. } => base.try_set_rw(),
             Expression::RepeaterModelReference { .. } => true,
-            Expression::ArrayIndex { .. } => true,
+            // The values of a map cannot be assigned one by one
+            Expression::ArrayIndex { array, .. } => !matches!(array.ty(), Type::Map(_)),
             _ => false,
         }
     }
@@ -1348,6 +1516,19 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
             }
             write!(f, "]")
         }
+        Expression::Map { value_ty: _, entries } => {
+            write!(f, "[")?;
+            if entries.is_empty() {
+                write!(f, ":")?;
+            }
+            for (k, v) in entries {
+                pretty_print(f, k)?;
+                write!(f, ": ")?;
+                pretty_print(f, v)?;
+                write!(f, ", ")?;
+            }
+            write!(f, "]")
+        }
         Expression::Struct { ty: _, values } => {
             write!(f, "{{ ")?;
             for (name, e) in values {
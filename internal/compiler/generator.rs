@@ -448,3 +448,17 @@ pub fn for_each_const_properties(component: &Rc<Component>, mut f: impl FnMut(&E
         }
     });
 }
+
+/// Returns true if `model`, the array argument of `find-starting-with()`, has struct rows whose
+/// `text` field is compared, and false if its rows are the strings themselves.
+pub fn array_rows_are_structs(
+    model: &crate::llr::Expression,
+    ctx: &dyn crate::llr::TypeResolutionContext,
+) -> bool {
+    // The array was converted to a model when resolving the call
+    let ty = match model {
+        crate::llr::Expression::Cast { from, .. } => from.ty(ctx),
+        model => model.ty(ctx),
+    };
+    matches!(ty, Type::Array(row) if matches!(*row, Type::Struct { .. }))
+}
@@ -104,6 +104,7 @@ pub enum Declaration {
         Function(Function),
         Var(Var),
         TypeAlias(TypeAlias),
+        Enum(Enum),
     }
 
     #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -262,6 +263,20 @@ fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         }
     }
 
+    /// An `enum class` declaration
+    #[derive(Default, Debug)]
+    pub struct Enum {
+        pub name: String,
+        pub values: Vec<String>,
+    }
+
+    impl Display for Enum {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            indent(f)?;
+            writeln!(f, "enum class {} {{ {} }};", self.name, self.values.join(", "))
+        }
+    }
+
     pub trait CppType {
         fn cpp_type(&self) -> Option<String>;
     }
@@ -311,6 +326,7 @@ fn cpp_type(&self) -> Option<String> {
             Type::String => Some("slint::SharedString".to_owned()),
             Type::Color => Some("slint::Color".to_owned()),
             Type::Duration => Some("std::int64_t".to_owned()),
+            Type::DateTime => Some("slint::DateTime".to_owned()),
             Type::Angle => Some("float".to_owned()),
             Type::PhysicalLength => Some("float".to_owned()),
             Type::LogicalLength => Some("float".to_owned()),
@@ -331,11 +347,10 @@ fn cpp_type(&self) -> Option<String> {
             }
 
             Type::Array(i) => Some(format!("std::shared_ptr<slint::Model<{}>>", i.cpp_type()?)),
+            Type::Map(v) => Some(format!("std::map<slint::SharedString, {}>", v.cpp_type()?)),
             Type::Image => Some("slint::Image".to_owned()),
             Type::Builtin(elem) => elem.native_class.cpp_type.clone(),
-            Type::Enumeration(enumeration) => {
-                Some(format!("slint::cbindgen_private::{}", ident(&enumeration.name)))
-            }
+            Type::Enumeration(enumeration) => Some(enum_name(enumeration)),
             Type::Brush => Some("slint::Brush".to_owned()),
             Type::LayoutCache => Some("slint::SharedVector<float>".into()),
             _ => None,
@@ -343,6 +358,15 @@ fn cpp_type(&self) -> Option<String> {
     }
 }
 
+/// The C++ name of the enum, which is in the cbindgen_private namespace for builtin enums
+fn enum_name(enumeration: &crate::langtype::Enumeration) -> String {
+    if enumeration.node.is_some() {
+        ident(&enumeration.name)
+    } else {
+        format!("slint::cbindgen_private::{}", ident(&enumeration.name))
+    }
+}
+
 fn to_cpp_orientation(o: Orientation) -> &'static str {
     match o {
         Orientation::Horizontal => "slint::cbindgen_private::Orientation::Horizontal",
@@ -391,11 +415,35 @@ fn property_set_value_code(
     let prop = access_member(property, ctx);
     if let Some(animation) = ctx.current_sub_component.and_then(|c| c.animations.get(property)) {
         let animation_code = compile_expression(animation, ctx);
+        if let Some(finished) =
+            ctx.current_sub_component.and_then(|c| c.animation_finished_handlers.get(property))
+        {
+            return format!(
+                "{}.set_animated_value({}, {}, {})",
+                prop,
+                value_expr,
+                animation_code,
+                animation_finished_code(finished, ctx)
+            );
+        }
         return format!("{}.set_animated_value({}, {})", prop, value_expr, animation_code);
     }
     format!("{}.set({})", prop, value_expr)
 }
 
+/// Returns the code of the AnimationFinishedCallback that runs the given handler.
+/// The handler is not run if the component was destroyed in the meantime
+fn animation_finished_code(finished: &llr::Expression, ctx: &EvaluationContext) -> String {
+    format!(
+        "slint::private_api::AnimationFinishedCallback::from([self, self_weak = self->self_weak]() {{
+            if (auto self_rc = self_weak.lock()) {{
+                {code};
+            }}
+        }})",
+        code = compile_expression(finished, ctx)
+    )
+}
+
 fn handle_property_init(
     prop: &llr::PropertyReference,
     binding_expression: &llr::BindingExpression,
@@ -440,10 +488,18 @@ fn handle_property_init(
             if binding_expression.is_state_info {
                 format!("slint::private_api::set_state_binding({}, {});", prop_access, binding_code)
             } else {
+                let finished_code = binding_expression
+                    .animation_finished
+                    .as_ref()
+                    .map(|finished| format!(", {}", animation_finished_code(finished, ctx)))
+                    .unwrap_or_default();
                 match &binding_expression.animation {
                     Some(llr::Animation::Static(anim)) => {
                         let anim = compile_expression(anim, ctx);
-                        format!("{}.set_animated_binding({}, {});", prop_access, binding_code, anim)
+                        format!(
+                            "{}.set_animated_binding({}, {}{});",
+                            prop_access, binding_code, anim, finished_code
+                        )
                     }
                     Some(llr::Animation::Transition (
                         anim
@@ -456,10 +512,11 @@ fn handle_property_init(
                                 auto [anim, time] = {};
                                 *start_time = time;
                                 return anim;
-                            }});",
+                            }}{});",
                             prop_access,
                             binding_code,
                             anim,
+                            finished_code,
                         )
                     }
                     None => format!("{}.set_binding({});", prop_access, binding_code),
@@ -513,6 +570,13 @@ pub fn generate(doc: &Document) -> impl std::fmt::Display {
         },
     ));
 
+    for en in doc.root_component.used_types.borrow().enums.iter() {
+        file.declarations.push(Declaration::Enum(Enum {
+            name: ident(&en.name),
+            values: en.values.iter().map(|value| ident(value)).collect(),
+        }));
+    }
+
     for ty in doc.root_component.used_types.borrow().structs.iter() {
         if let Type::Struct { fields, name: Some(name), node: Some(_) } = ty {
             generate_struct(&mut file, name, fields);
@@ -1391,6 +1455,20 @@ fn generate_repeated_component(
 
     let mut update_statements = vec!["[[maybe_unused]] auto self = this;".into()];
     update_statements.extend(index_prop.map(|prop| format!("{}.set(i);", prop)));
+    // Only the bindings that use the fields that changed are notified
+    for (name, property_index) in &repeated.data_field_props {
+        if repeated.sub_tree.root.properties[*property_index].use_count.get() == 0 {
+            continue;
+        }
+        let field_access = match model_data_type {
+            Type::Struct { fields, name: None, .. } => {
+                let index = fields.keys().position(|k| k == name).unwrap();
+                format!("std::get<{}>(data)", index)
+            }
+            _ => format!("data.{}", ident(name)),
+        };
+        update_statements.push(format!("{}.set({});", access_prop(property_index), field_access));
+    }
     update_statements.extend(data_prop.map(|prop| format!("{}.set(data);", prop)));
 
     repeater_struct.members.push((
@@ -1525,9 +1603,15 @@ fn generate_public_api_for_properties(
 
         let access = access_member(r, ctx);
 
-        if let Type::Callback { args, return_type } = ty {
+        if let Type::Callback { args, .. } | Type::Function { args, .. } = ty {
             let param_types = args.iter().map(|t| t.cpp_type().unwrap()).collect::<Vec<_>>();
-            let return_type = return_type.as_ref().map_or("void".into(), |t| t.cpp_type().unwrap());
+            let return_type = match ty {
+                Type::Callback { return_type, .. } => {
+                    return_type.as_ref().map_or("void".into(), |t| t.cpp_type().unwrap())
+                }
+                Type::Function { return_type, .. } => return_type.cpp_type().unwrap(),
+                _ => unreachable!(),
+            };
             let callback_emitter = vec![
                 "[[maybe_unused]] auto self = this;".into(),
                 format!(
@@ -1550,6 +1634,10 @@ fn generate_public_api_for_properties(
                 statements: Some(callback_emitter),
                 ..Default::default()
             }));
+            if matches!(ty, Type::Function { .. }) {
+                // Functions can only be invoked, their implementation is fixed in the .slint file
+                continue;
+            }
             declarations.push(Declaration::Function(Function {
                 name: format!("on_{}", ident(p)),
                 template_parameters: Some("typename Functor".into()),
@@ -1767,9 +1855,10 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
             _ => panic!("Expression::ObjectAccess's base expression is not an Object type"),
         },
         Expression::ArrayIndex { array, index } => {
+            let function = if matches!(array.ty(ctx), Type::Map(_)) { "access_map_value" } else { "access_array_index" };
             format!(
-                "slint::private_api::access_array_index({}, {})",
-                compile_expression(array, ctx), compile_expression(index, ctx)
+                "slint::private_api::{}({}, {})",
+                function, compile_expression(array, ctx), compile_expression(index, ctx)
             )
         },
         Expression::Cast { from, to } => {
@@ -1782,9 +1871,15 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                     format!("std::make_shared<slint::private_api::IntModel>({})", f)
                 }
                 (Type::Array(_), Type::Model) => f,
+                (Type::Map(_), Type::Model) => {
+                    format!("slint::private_api::map_to_model({})", f)
+                }
                 (Type::Float32, Type::Color) => {
                     format!("slint::Color::from_argb_encoded({})", f)
                 }
+                (Type::Float32, Type::DateTime) => {
+                    format!("slint::DateTime::from_millis_since_epoch({})", f)
+                }
                 (Type::Color, Type::Brush) => {
                     format!("slint::Brush({})", f)
                 }
@@ -1984,6 +2079,11 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 )
             }
         }
+        Expression::Map { value_ty, entries } => {
+            let ty = value_ty.cpp_type().unwrap();
+            let entries = entries.iter().map(|(k, v)| format!("{{ {}, {ty} ( {} ) }}", compile_expression(k, ctx), compile_expression(v, ctx), ty = ty));
+            format!("std::map<slint::SharedString, {ty}>{{ {} }}", entries.collect::<Vec<_>>().join(", "), ty = ty)
+        }
         Expression::Struct { ty, values } => {
             if let Type::Struct{fields, name: None, ..} = ty {
                 let mut elem = fields.keys().map(|k| {
@@ -2023,7 +2123,7 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
             )
         }
         Expression::EnumerationValue(value) => {
-            format!("slint::cbindgen_private::{}::{}", value.enumeration.name, ident(&value.to_string()))
+            format!("{}::{}", enum_name(&value.enumeration), ident(&value.to_string()))
         }
         Expression::ReturnStatement(Some(expr)) => format!(
             "throw slint::private_api::ReturnWrapper<{}>({})",
@@ -2086,6 +2186,23 @@ fn compile_builtin_function_call(
             let window = access_window_field(ctx);
             format!("{}.scale_factor()", window)
         }
+        BuiltinFunction::GetWindowBaseFontSize => {
+            let window = access_window_field(ctx);
+            format!("{}.base_font_size()", window)
+        }
+        BuiltinFunction::GetWindowViewportWidth => {
+            let window = access_window_field(ctx);
+            format!("{}.viewport_width()", window)
+        }
+        BuiltinFunction::GetWindowViewportHeight => {
+            let window = access_window_field(ctx);
+            format!("{}.viewport_height()", window)
+        }
+        BuiltinFunction::AnimationTick => {
+            "static_cast<std::int64_t>(slint::cbindgen_private::slint_animation_tick())".into()
+        }
+        BuiltinFunction::HighContrast => "slint::cbindgen_private::slint_high_contrast()".into(),
+        BuiltinFunction::ReducedMotion => "slint::cbindgen_private::slint_reduced_motion()".into(),
         BuiltinFunction::Debug => {
             format!("std::cout << {} << std::endl;", a.join("<<"))
         }
@@ -2094,6 +2211,9 @@ fn compile_builtin_function_call(
             a.next().unwrap(),
             a.next().unwrap()
         ),
+        BuiltinFunction::ModFloat => {
+            format!("std::fmod({}, {})", a.next().unwrap(), a.next().unwrap())
+        }
         BuiltinFunction::Round => format!("std::round({})", a.next().unwrap()),
         BuiltinFunction::Ceil => format!("std::ceil({})", a.next().unwrap()),
         BuiltinFunction::Floor => format!("std::floor({})", a.next().unwrap()),
@@ -2105,12 +2225,16 @@ fn compile_builtin_function_call(
         BuiltinFunction::Pow => {
             format!("std::pow(({}), ({}))", a.next().unwrap(), a.next().unwrap())
         }
+        BuiltinFunction::Exp => format!("std::exp({})", a.next().unwrap()),
         BuiltinFunction::Sin => format!("std::sin(({}) * {})", a.next().unwrap(), pi_180),
         BuiltinFunction::Cos => format!("std::cos(({}) * {})", a.next().unwrap(), pi_180),
         BuiltinFunction::Tan => format!("std::tan(({}) * {})", a.next().unwrap(), pi_180),
         BuiltinFunction::ASin => format!("std::asin({}) / {}", a.next().unwrap(), pi_180),
         BuiltinFunction::ACos => format!("std::acos({}) / {}", a.next().unwrap(), pi_180),
         BuiltinFunction::ATan => format!("std::atan({}) / {}", a.next().unwrap(), pi_180),
+        BuiltinFunction::ATan2 => {
+            format!("std::atan2({}, {}) / {}", a.next().unwrap(), a.next().unwrap(), pi_180)
+        }
         BuiltinFunction::SetFocusItem => {
             if let [llr::Expression::PropertyReference(pr)] = arguments {
                 let window = access_window_field(ctx);
@@ -2135,18 +2259,63 @@ fn compile_builtin_function_call(
         BuiltinFunction::StringToFloat => {
             format!("[](const auto &a){{ auto e1 = std::end(a); auto e2 = const_cast<char*>(e1); auto r = std::strtod(std::begin(a), &e2); return e1 == e2 ? r : 0; }}({})", a.next().unwrap())
         }
+        BuiltinFunction::StringToUppercase => {
+            format!("{}.to_uppercase()", a.next().unwrap())
+        }
+        BuiltinFunction::StringToLowercase => {
+            format!("{}.to_lowercase()", a.next().unwrap())
+        }
+        BuiltinFunction::StringTrim => {
+            format!("{}.trim()", a.next().unwrap())
+        }
+        BuiltinFunction::StringSplit => {
+            format!(
+                "slint::private_api::string_split({}, {})",
+                a.next().unwrap(),
+                a.next().unwrap()
+            )
+        }
+        BuiltinFunction::StringReplace => {
+            format!("{}.replace({}, {})", a.next().unwrap(), a.next().unwrap(), a.next().unwrap())
+        }
+        BuiltinFunction::StringStartsWith => {
+            format!("{}.starts_with({})", a.next().unwrap(), a.next().unwrap())
+        }
+        BuiltinFunction::StringSlice => {
+            format!("{}.slice({}, {})", a.next().unwrap(), a.next().unwrap(), a.next().unwrap())
+        }
         BuiltinFunction::ColorBrighter => {
             format!("{}.brighter({})", a.next().unwrap(), a.next().unwrap())
         }
         BuiltinFunction::ColorDarker => {
             format!("{}.darker({})", a.next().unwrap(), a.next().unwrap())
         }
+        BuiltinFunction::ColorMix => {
+            format!("{}.mix({}, {})", a.next().unwrap(), a.next().unwrap(), a.next().unwrap())
+        }
+        BuiltinFunction::ColorWithAlpha => {
+            format!("{}.with_alpha({})", a.next().unwrap(), a.next().unwrap())
+        }
         BuiltinFunction::ImageSize => {
             format!("{}.size()", a.next().unwrap())
         }
         BuiltinFunction::ArrayLength => {
             format!("[](const auto &model){{ (*model).track_row_count_changes(); return (*model).row_count(); }}({})", a.next().unwrap())
         }
+        BuiltinFunction::ArrayFindStartingWith => {
+            let text = if crate::generator::array_rows_are_structs(&arguments[0], ctx) {
+                "[](const auto &row) { return row.text; }"
+            } else {
+                "[](const auto &row) { return row; }"
+            };
+            format!(
+                "slint::private_api::find_row_starting_with({}, {}, {}, {})",
+                a.next().unwrap(),
+                a.next().unwrap(),
+                a.next().unwrap(),
+                text
+            )
+        }
         BuiltinFunction::Rgb => {
             format!("slint::Color::from_argb_uint8(std::clamp(static_cast<float>({a}) * 255., 0., 255.), std::clamp(static_cast<int>({r}), 0, 255), std::clamp(static_cast<int>({g}), 0, 255), std::clamp(static_cast<int>({b}), 0, 255))",
                 r = a.next().unwrap(),
@@ -2155,6 +2324,15 @@ fn compile_builtin_function_call(
                 a = a.next().unwrap(),
             )
         }
+        BuiltinFunction::Hsv => {
+            format!(
+                "slint::Color::from_hsva({}, {}, {}, {})",
+                a.next().unwrap(),
+                a.next().unwrap(),
+                a.next().unwrap(),
+                a.next().unwrap(),
+            )
+        }
         BuiltinFunction::ShowPopupWindow => {
             if let [llr::Expression::NumberLiteral(popup_index), x, y, llr::Expression::PropertyReference(parent_ref)] =
                 arguments
@@ -2174,6 +2352,10 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
+        BuiltinFunction::ClosePopupWindow => {
+            let window = access_window_field(ctx);
+            format!("{}.close_popup()", window)
+        }
         BuiltinFunction::RegisterCustomFontByPath => {
             if let [llr::Expression::StringLiteral(path)] = arguments {
                 format!("slint::private_api::register_font_from_path(\"{}\");", escape_string(path))
@@ -11,7 +11,7 @@
 */
 
 use crate::expression_tree::{BuiltinFunction, EasingCurve, OperatorClass};
-use crate::langtype::Type;
+use crate::langtype::{Enumeration, Type};
 use crate::layout::Orientation;
 use crate::llr::{
     self, EvaluationContext as llr_EvaluationContext, Expression, ParentCtx as llr_ParentCtx,
@@ -64,6 +64,7 @@ fn rust_type(ty: &Type) -> Option<proc_macro2::TokenStream> {
         Type::String => Some(quote!(slint::re_exports::SharedString)),
         Type::Color => Some(quote!(slint::re_exports::Color)),
         Type::Duration => Some(quote!(i64)),
+        Type::DateTime => Some(quote!(slint::DateTime)),
         Type::Angle => Some(quote!(f32)),
         Type::PhysicalLength => Some(quote!(f32)),
         Type::LogicalLength => Some(quote!(f32)),
@@ -80,10 +81,11 @@ fn rust_type(ty: &Type) -> Option<proc_macro2::TokenStream> {
             let inner = rust_type(o)?;
             Some(quote!(slint::re_exports::ModelRc<#inner>))
         }
-        Type::Enumeration(e) => {
-            let e = ident(&e.name);
-            Some(quote!(slint::re_exports::#e))
+        Type::Map(v) => {
+            let inner = rust_type(v)?;
+            Some(quote!(slint::re_exports::BTreeMap<slint::re_exports::SharedString, #inner>))
         }
+        Type::Enumeration(e) => Some(enum_name_to_tokens(e)),
         Type::Brush => Some(quote!(slint::Brush)),
         Type::LayoutCache => Some(quote!(SharedVector<f32>)),
         _ => None,
@@ -112,6 +114,15 @@ pub fn generate(doc: &Document) -> TokenStream {
         })
         .unzip();
 
+    let (enums_ids, enums): (Vec<_>, Vec<_>) = doc
+        .root_component
+        .used_types
+        .borrow()
+        .enums
+        .iter()
+        .map(|en| (ident(&en.name), generate_enum(en)))
+        .unzip();
+
     let llr = crate::llr::lower_to_item_tree::lower_to_item_tree(&doc.root_component);
 
     let sub_compos = llr
@@ -226,13 +237,14 @@ pub fn generate(doc: &Document) -> TokenStream {
         mod #compo_module {
             use slint::re_exports::*;
             #(#structs)*
+            #(#enums)*
             #(#globals)*
             #(#sub_compos)*
             #compo
             #(#resource_symbols)*
             const _THE_SAME_VERSION_MUST_BE_USED_FOR_THE_COMPILER_AND_THE_RUNTIME : slint::#version_check = slint::#version_check;
         }
-        pub use #compo_module::{#compo_id #(,#structs_ids)* #(,#globals_ids)* };
+        pub use #compo_module::{#compo_id #(,#structs_ids)* #(,#enums_ids)* #(,#globals_ids)* };
         pub use slint::{ComponentHandle, Global};
     }
 }
@@ -345,6 +357,25 @@ pub struct #component_id {
     }
 }
 
+fn generate_enum(en: &Enumeration) -> TokenStream {
+    let enum_id = ident(&en.name);
+    let values = en.values.iter().map(|value| ident(value)).collect::<Vec<_>>();
+    let default_value = &values[en.default_value];
+
+    quote! {
+        #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+        pub enum #enum_id {
+            #(#values),*
+        }
+
+        impl Default for #enum_id {
+            fn default() -> Self {
+                Self::#default_value
+            }
+        }
+    }
+}
+
 fn handle_property_init(
     prop: &llr::PropertyReference,
     binding_expression: &llr::BindingExpression,
@@ -411,12 +442,22 @@ fn handle_property_init(
                     slint::internal::set_property_state_binding(#rust_property, &self_rc, #binding_tokens);
                 } }
             } else {
+                let finished_tokens = match &binding_expression.animation_finished {
+                    Some(finished) => {
+                        let finished = compile_expression(finished, ctx);
+                        quote!(Some(move |self_rc| {
+                            #init_self_pin_ref
+                            #finished;
+                        }))
+                    }
+                    None => quote!(None),
+                };
                 match &binding_expression.animation {
                     Some(llr::Animation::Static(anim)) => {
                         let anim = compile_expression(anim, ctx);
                         quote! { {
                             #init_self_pin_ref
-                            slint::internal::set_animated_property_binding(#rust_property, &self_rc, #binding_tokens, #anim);
+                            slint::internal::set_animated_property_binding(#rust_property, &self_rc, #binding_tokens, #anim, #finished_tokens);
                         } }
                     }
                     Some(llr::Animation::Transition(anim)) => {
@@ -426,7 +467,8 @@ fn handle_property_init(
                                 #rust_property, &self_rc, #binding_tokens, move |self_rc| {
                                     #init_self_pin_ref
                                     #anim
-                                }
+                                },
+                                #finished_tokens
                             );
                         }
                     }
@@ -452,7 +494,22 @@ fn public_api(
         let prop_ident = ident(p);
         let prop = access_member(r, ctx);
 
-        if let Type::Callback { args, return_type } = ty {
+        if let Type::Function { args, return_type } = ty {
+            let callback_args = args.iter().map(|a| rust_type(a).unwrap()).collect::<Vec<_>>();
+            let return_type = match &**return_type {
+                Type::Void => quote!(()),
+                ty => rust_type(ty).unwrap(),
+            };
+            let args_name = (0..args.len()).map(|i| format_ident!("arg_{}", i)).collect::<Vec<_>>();
+            let caller_ident = format_ident!("invoke_{}", prop_ident);
+            property_and_callback_accessors.push(quote!(
+                #[allow(dead_code)]
+                pub fn #caller_ident(&self, #(#args_name : #callback_args,)*) -> #return_type {
+                    let _self = #self_init;
+                    #prop.call(&(#(#args_name,)*))
+                }
+            ));
+        } else if let Type::Callback { args, return_type } = ty {
             let callback_args = args.iter().map(|a| rust_type(a).unwrap()).collect::<Vec<_>>();
             let return_type = return_type.as_ref().map_or(quote!(()), |a| rust_type(a).unwrap());
             let args_name = (0..args.len()).map(|i| format_ident!("arg_{}", i)).collect::<Vec<_>>();
@@ -1150,8 +1207,44 @@ fn box_layout_data(self: ::core::pin::Pin<&Self>, o: slint::re_exports::Orientat
             &ctx,
         )
     };
-    let index_prop = repeated.index_prop.iter().map(access_prop);
-    let data_prop = repeated.data_prop.iter().map(access_prop);
+    let index_prop = repeated.index_prop.iter().map(access_prop).collect::<Vec<_>>();
+    let data_prop = repeated.data_prop.iter().map(access_prop).collect::<Vec<_>>();
+
+    // The properties of the fields of the model data that are used by a binding
+    let data_fields = repeated
+        .data_field_props
+        .iter()
+        .filter(|(_, p)| repeated.sub_tree.root.properties[*p].use_count.get() > 0)
+        .collect::<Vec<_>>();
+    let field_props = data_fields.iter().map(|(_, p)| access_prop(p)).collect::<Vec<_>>();
+    let field_names = data_fields.iter().map(|(name, _)| name.as_str());
+    let field_access = data_fields
+        .iter()
+        .map(|(name, _)| match &repeated.sub_tree.root.properties[repeated.data_prop.unwrap()].ty {
+            Type::Struct { fields, name: None, .. } => {
+                let index = fields.keys().position(|k| k == name).unwrap();
+                let index = proc_macro2::Literal::usize_unsuffixed(index);
+                quote!(_data.#index)
+            }
+            _ => {
+                let name = ident(name);
+                quote!(_data.#name)
+            }
+        })
+        .collect::<Vec<_>>();
+    let update_fields_fn = (!data_fields.is_empty()).then(|| {
+        quote! {
+            fn update_fields(&self, _index: usize, _data: Self::Data, _fields: &[slint::SharedString]) {
+                let self_rc = self.self_weak.get().unwrap().upgrade().unwrap();
+                let _self = self_rc.as_pin_ref();
+                #(#index_prop.set(_index as _);)*
+                #(if _fields.iter().any(|f| f.as_str() == #field_names) {
+                    #field_props.set(::core::clone::Clone::clone(&#field_access));
+                })*
+                #(#data_prop.set(_data);)*
+            }
+        }
+    });
 
     quote!(
         #component
@@ -1162,8 +1255,10 @@ fn update(&self, _index: usize, _data: Self::Data) {
                 let self_rc = self.self_weak.get().unwrap().upgrade().unwrap();
                 let _self = self_rc.as_pin_ref();
                 #(#index_prop.set(_index as _);)*
+                #(#field_props.set(::core::clone::Clone::clone(&#field_access));)*
                 #(#data_prop.set(_data);)*
             }
+            #update_fields_fn
             #extra_fn
         }
     )
@@ -1195,6 +1290,22 @@ fn property_set_value_tokens(
     let prop = access_member(property, ctx);
     if let Some(animation) = ctx.current_sub_component.and_then(|c| c.animations.get(property)) {
         let animation_tokens = compile_expression(animation, ctx);
+        if let Some(finished) =
+            ctx.current_sub_component.and_then(|c| c.animation_finished_handlers.get(property))
+        {
+            let finished_tokens = compile_expression(finished, ctx);
+            return quote!(#prop.set_animated_value_with_finished_callback(
+                #value_tokens as _,
+                #animation_tokens,
+                slint::internal::animation_finished_callback(
+                    &_self.self_weak.get().unwrap().upgrade().unwrap(),
+                    Some(move |self_rc| {
+                        let _self = self_rc.as_pin_ref();
+                        #finished_tokens;
+                    }),
+                ),
+            ));
+        }
         return quote!(#prop.set_animated_value(#value_tokens as _, #animation_tokens));
     }
     quote!(#prop.set(#value_tokens as _))
@@ -1368,9 +1479,17 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 (Type::Float32, Type::Model) | (Type::Int32, Type::Model) => {
                     quote!(slint::re_exports::ModelRc::new(#f as usize))
                 }
+                (Type::Map(_), Type::Model) => {
+                    quote!(slint::re_exports::ModelRc::new(slint::re_exports::VecModel::from(
+                        (#f).iter().map(|(k, v)| (k.clone(), v.clone())).collect::<slint::re_exports::Vec<_>>()
+                    )))
+                }
                 (Type::Float32, Type::Color) => {
                     quote!(slint::re_exports::Color::from_argb_encoded(#f as u32))
                 }
+                (Type::Float32, Type::DateTime) => {
+                    quote!(slint::DateTime::from_millis_since_epoch(#f as i64))
+                }
                 (Type::Color, Type::Brush) => {
                     quote!(slint::Brush::SolidColor(#f))
                 }
@@ -1488,9 +1607,12 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
             _ => panic!("Expression::StructFieldAccess's base expression is not an Object type"),
         },
         Expression::ArrayIndex { array, index } => {
-            debug_assert!(matches!(array.ty(ctx), Type::Array(_)));
+            debug_assert!(matches!(array.ty(ctx), Type::Array(_) | Type::Map(_)));
             let base_e = compile_expression(array, ctx);
             let index_e = compile_expression(index, ctx);
+            if matches!(array.ty(ctx), Type::Map(_)) {
+                return quote!((#base_e).get(&(#index_e)).cloned().unwrap_or_default());
+            }
             quote!(match &#base_e { x => {
                 let index = (#index_e) as usize;
                 x.model_tracker().track_row_data_changes(index);
@@ -1541,6 +1663,22 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
             let value_e = compile_expression(value, ctx);
             quote!((#base_e).set_row_data(#index_e as usize, #value_e as _))
         }
+        Expression::BinaryExpression { lhs, rhs, op }
+            if lhs.ty(ctx) == Type::DateTime
+                && crate::expression_tree::operator_class(*op) == OperatorClass::ArithmeticOp =>
+        {
+            let lhs_code = compile_expression(&*lhs, ctx);
+            let rhs_code = compile_expression(&*rhs, ctx);
+            if rhs.ty(ctx) == Type::DateTime {
+                // The difference of two date-times is a duration
+                quote!(((#lhs_code).millis_since_epoch() - (#rhs_code).millis_since_epoch()))
+            } else {
+                let op = proc_macro2::Punct::new(*op, proc_macro2::Spacing::Alone);
+                quote!(slint::DateTime::from_millis_since_epoch(
+                    (#lhs_code).millis_since_epoch() #op ((#rhs_code) as i64)
+                ))
+            }
+        }
         Expression::BinaryExpression { lhs, rhs, op } => {
             let (conv1, conv2) = match crate::expression_tree::operator_class(*op) {
                 OperatorClass::ArithmeticOp => match lhs.ty(ctx) {
@@ -1638,6 +1776,15 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 quote!(Slice::from_slice(&[#(#val),*]))
             }
         }
+        Expression::Map { value_ty, entries } => {
+            let rust_value_ty = rust_type(value_ty).unwrap();
+            let keys = entries.iter().map(|(k, _)| compile_expression(k, ctx));
+            let values = entries.iter().map(|(_, v)| compile_expression(v, ctx));
+            quote!(<slint::re_exports::BTreeMap<slint::re_exports::SharedString, #rust_value_ty>
+            as slint::re_exports::FromIterator<_>>::from_iter(
+                slint::re_exports::vec![#((#keys, #values as _)),*]
+            ))
+        }
         Expression::Struct { ty, values } => {
             if let Type::Struct { fields, name, .. } = ty {
                 let elem = fields.keys().map(|k| values.get(k).map(|e| compile_expression(e, ctx)));
@@ -1695,9 +1842,9 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
             ))
         }
         Expression::EnumerationValue(value) => {
-            let base_ident = ident(&value.enumeration.name);
+            let base = enum_name_to_tokens(&value.enumeration);
             let value_ident = ident(&value.to_string());
-            quote!(slint::re_exports::#base_ident::#value_ident)
+            quote!(#base::#value_ident)
         }
         Expression::ReturnStatement(expr) => {
             let return_expr = expr.as_ref().map(|expr| compile_expression(expr, ctx));
@@ -1788,6 +1935,10 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
+        BuiltinFunction::ClosePopupWindow => {
+            let window_tokens = access_window_field(ctx);
+            quote!(#window_tokens.close_popup();)
+        }
         BuiltinFunction::ImplicitLayoutInfo(orient) => {
             if let [Expression::PropertyReference(pr)] = arguments {
                 let item = access_member(pr, ctx);
@@ -1828,8 +1979,24 @@ fn compile_builtin_function_call(
             let window_tokens = access_window_field(ctx);
             quote!(#window_tokens.scale_factor())
         }
+        BuiltinFunction::GetWindowBaseFontSize => {
+            let window_tokens = access_window_field(ctx);
+            quote!(#window_tokens.base_font_size())
+        }
+        BuiltinFunction::GetWindowViewportWidth => {
+            let window_tokens = access_window_field(ctx);
+            quote!(#window_tokens.viewport_size().width)
+        }
+        BuiltinFunction::GetWindowViewportHeight => {
+            let window_tokens = access_window_field(ctx);
+            quote!(#window_tokens.viewport_size().height)
+        }
+        BuiltinFunction::AnimationTick => quote!((slint::re_exports::animation_tick() as i64)),
+        BuiltinFunction::HighContrast => quote!(slint::re_exports::high_contrast()),
+        BuiltinFunction::ReducedMotion => quote!(slint::re_exports::reduced_motion()),
         BuiltinFunction::Debug => quote!(println!("{:?}", #(#a)*)),
         BuiltinFunction::Mod => quote!((#(#a as i32)%*)),
+        BuiltinFunction::ModFloat => quote!((#(#a as f64)%*)),
         BuiltinFunction::Round => quote!((#(#a)* as f64).round()),
         BuiltinFunction::Ceil => quote!((#(#a)* as f64).ceil()),
         BuiltinFunction::Floor => quote!((#(#a)* as f64).floor()),
@@ -1841,6 +2008,10 @@ fn compile_builtin_function_call(
         BuiltinFunction::ASin => quote!((#(#a)* as f64).asin().to_degrees()),
         BuiltinFunction::ACos => quote!((#(#a)* as f64).acos().to_degrees()),
         BuiltinFunction::ATan => quote!((#(#a)* as f64).atan().to_degrees()),
+        BuiltinFunction::ATan2 => {
+            let (y, x) = (a.next().unwrap(), a.next().unwrap());
+            quote!((#y as f64).atan2(#x as f64).to_degrees())
+        }
         BuiltinFunction::Log => {
             let (a1, a2) = (a.next().unwrap(), a.next().unwrap());
             quote!((#a1 as f64).log(#a2 as f64))
@@ -1849,10 +2020,38 @@ fn compile_builtin_function_call(
             let (a1, a2) = (a.next().unwrap(), a.next().unwrap());
             quote!((#a1 as f64).powf(#a2 as f64))
         }
+        BuiltinFunction::Exp => quote!((#(#a)* as f64).exp()),
         BuiltinFunction::StringToFloat => {
             quote!(#(#a)*.as_str().parse::<f64>().unwrap_or_default())
         }
         BuiltinFunction::StringIsFloat => quote!(#(#a)*.as_str().parse::<f64>().is_ok()),
+        BuiltinFunction::StringToUppercase => {
+            quote!(slint::re_exports::SharedString::from((#(#a)*).to_uppercase()))
+        }
+        BuiltinFunction::StringToLowercase => {
+            quote!(slint::re_exports::SharedString::from((#(#a)*).to_lowercase()))
+        }
+        BuiltinFunction::StringTrim => {
+            quote!(slint::re_exports::SharedString::from((#(#a)*).trim()))
+        }
+        BuiltinFunction::StringSplit => {
+            let (s, separator) = (a.next().unwrap(), a.next().unwrap());
+            quote!(slint::re_exports::ModelRc::new(slint::re_exports::VecModel::from(
+                slint::re_exports::split_string(&(#s), &(#separator))
+            )))
+        }
+        BuiltinFunction::StringReplace => {
+            let (s, from, to) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            quote!(slint::re_exports::SharedString::from((#s).replace((#from).as_str(), (#to).as_str())))
+        }
+        BuiltinFunction::StringStartsWith => {
+            let (s, prefix) = (a.next().unwrap(), a.next().unwrap());
+            quote!((#s).starts_with((#prefix).as_str()))
+        }
+        BuiltinFunction::StringSlice => {
+            let (s, start, end) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            quote!(slint::re_exports::slice_string(&(#s), (#start) as i32, (#end) as i32))
+        }
         BuiltinFunction::ColorBrighter => {
             let x = a.next().unwrap();
             let factor = a.next().unwrap();
@@ -1863,6 +2062,15 @@ fn compile_builtin_function_call(
             let factor = a.next().unwrap();
             quote!(#x.darker(#factor as f32))
         }
+        BuiltinFunction::ColorMix => {
+            let (x, other, factor) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            quote!(#x.mix(&#other, #factor as f32))
+        }
+        BuiltinFunction::ColorWithAlpha => {
+            let x = a.next().unwrap();
+            let alpha = a.next().unwrap();
+            quote!(#x.with_alpha(#alpha as f32))
+        }
         BuiltinFunction::ImageSize => quote!( #(#a)*.size()),
         BuiltinFunction::ArrayLength => {
             quote!(match &#(#a)* { x => {
@@ -1870,6 +2078,15 @@ fn compile_builtin_function_call(
                 x.row_count() as i32
             }})
         }
+        BuiltinFunction::ArrayFindStartingWith => {
+            let (model, prefix, from) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            let text = if crate::generator::array_rows_are_structs(&arguments[0], ctx) {
+                quote!(|row| row.text.clone())
+            } else {
+                quote!(|row| row.clone())
+            };
+            quote!(slint::re_exports::find_row_starting_with(&(#model), (#prefix).as_str(), (#from) as i32, #text))
+        }
 
         BuiltinFunction::Rgb => {
             let (r, g, b, a) =
@@ -1882,10 +2099,24 @@ fn compile_builtin_function_call(
                 slint::re_exports::Color::from_argb_u8(a, r, g, b)
             })
         }
+        BuiltinFunction::Hsv => {
+            let (h, s, v, a) =
+                (a.next().unwrap(), a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            quote!(slint::re_exports::Color::from_hsva(#h as f32, #s as f32, #v as f32, #a as f32))
+        }
     }
 }
 
 /// Return a TokenStream for a name (as in [`Type::Struct::name`])
+fn enum_name_to_tokens(en: &Enumeration) -> TokenStream {
+    let e = ident(&en.name);
+    if en.node.is_some() {
+        quote!(#e)
+    } else {
+        quote!(slint::re_exports::#e)
+    }
+}
+
 fn struct_name_to_tokens(name: &str) -> TokenStream {
     // the name match the C++ signature so we need to change that to the rust namespace
     let mut name = name.replace("slint::private_api::", "slint::re_exports::").replace('-', "_");
@@ -0,0 +1,237 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Semantic highlighting of the .slint source code.
+
+    [`highlight_source`] classifies the tokens of a document, for the editors that do not use the
+    language server. The classification only depends on the syntax tree of the document and on the
+    builtin types, so it works for documents with errors and does not load the imported files.
+*/
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::parser::{SyntaxKind, SyntaxNode, SyntaxToken};
+use crate::typeregister::TypeRegister;
+use std::ops::Range;
+
+/// The classification of a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenClass {
+    /// Keywords such as `property`, `callback`, `import`, `if` or `for`
+    Keyword,
+    /// The name of a component, or of an element whose base is not a builtin element
+    Component,
+    /// The name of a builtin element or type, such as `Rectangle` or `int`
+    Builtin,
+    /// The name of a struct or an enum, or of a type that is not known without loading the imports
+    Type,
+    Property,
+    Callback,
+    Function,
+    /// The arguments of a function or of a callback connection
+    Parameter,
+    /// The id of an element or the index of a repeated element
+    Variable,
+    /// `@children`, `@image-url` and the other `@` keywords
+    Macro,
+    Comment,
+    String,
+    /// Number and color literals
+    Number,
+    Operator,
+}
+
+/// A token of the source code with its classification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighlightedToken {
+    /// The range of the token in the source, in bytes
+    pub range: Range<usize>,
+    pub class: TokenClass,
+    /// The token is the name of the item it declares, such as a property declaration
+    pub is_definition: bool,
+    /// The token gives a name to an item defined elsewhere, such as the name of an export
+    /// or the field of an object literal
+    pub is_declaration: bool,
+}
+
+/// Parse the source code and return its classified tokens, in the order of the source.
+///
+/// The tokens that have no classification, such as the whitespace or the braces, are not returned.
+pub fn highlight_source(source: &str) -> Vec<HighlightedToken> {
+    let mut diag = BuildDiagnostics::default();
+    let node = crate::parser::parse(source.to_owned(), None, &mut diag);
+    highlight_node(&node)
+}
+
+/// Return the classified tokens of a syntax node, such as the document node of a file that was
+/// already parsed
+pub fn highlight_node(node: &SyntaxNode) -> Vec<HighlightedToken> {
+    let register = TypeRegister::builtin();
+    let is_builtin = |token: &SyntaxToken| {
+        !matches!(register.borrow().lookup(token.text()), crate::langtype::Type::Invalid)
+    };
+    let mut result = vec![];
+    let mut token = node.first_token();
+    while let Some(t) = token {
+        if !node.text_range().contains_range(t.text_range()) {
+            break;
+        }
+        if let Some((class, is_definition, is_declaration)) = classify(&t, &is_builtin) {
+            result.push(HighlightedToken {
+                range: t.text_range().start().into()..t.text_range().end().into(),
+                class,
+                is_definition,
+                is_declaration,
+            });
+        }
+        token = t.next_token();
+    }
+    result
+}
+
+/// Returns the class of the token, whether it is a definition, and whether it is a declaration
+fn classify(
+    token: &SyntaxToken,
+    is_builtin: &dyn Fn(&SyntaxToken) -> bool,
+) -> Option<(TokenClass, bool, bool)> {
+    use TokenClass::*;
+    let class = match token.kind() {
+        SyntaxKind::Comment => Comment,
+        SyntaxKind::StringLiteral => String,
+        SyntaxKind::NumberLiteral | SyntaxKind::ColorLiteral => Number,
+        SyntaxKind::Identifier => return classify_identifier(token, is_builtin),
+        SyntaxKind::PlusEqual
+        | SyntaxKind::MinusEqual
+        | SyntaxKind::StarEqual
+        | SyntaxKind::DivEqual
+        | SyntaxKind::LessEqual
+        | SyntaxKind::GreaterEqual
+        | SyntaxKind::EqualEqual
+        | SyntaxKind::NotEqual
+        | SyntaxKind::OrOr
+        | SyntaxKind::AndAnd
+        | SyntaxKind::Plus
+        | SyntaxKind::Minus
+        | SyntaxKind::Star
+        | SyntaxKind::Div
+        | SyntaxKind::Equal
+        | SyntaxKind::Question => Operator,
+        SyntaxKind::LAngle | SyntaxKind::RAngle
+            if token.parent().kind() == SyntaxKind::PropertyDeclaration =>
+        {
+            Operator
+        }
+        SyntaxKind::At => Macro,
+        _ => return None,
+    };
+    Some((class, false, false))
+}
+
+fn classify_identifier(
+    token: &SyntaxToken,
+    is_builtin: &dyn Fn(&SyntaxToken) -> bool,
+) -> Option<(TokenClass, bool, bool)> {
+    use TokenClass::*;
+    let parent = token.parent();
+    let has_sibling = |kind: SyntaxKind| {
+        parent.parent().map_or(false, |p| p.children().any(|n| n.kind() == kind))
+    };
+    let result = match parent.kind() {
+        SyntaxKind::Component
+        | SyntaxKind::RepeatedElement
+        | SyntaxKind::ConditionalElement
+        | SyntaxKind::CallbackDeclaration
+        | SyntaxKind::Function
+        | SyntaxKind::PropertyDeclaration
+        | SyntaxKind::PropertyAnimation
+        | SyntaxKind::ReturnStatement
+        | SyntaxKind::ConditionalExpression
+        | SyntaxKind::States
+        | SyntaxKind::State
+        | SyntaxKind::Transitions
+        | SyntaxKind::Transition
+        | SyntaxKind::ExportsList
+        | SyntaxKind::ExportSpecifier
+        | SyntaxKind::ImportSpecifier
+        | SyntaxKind::ImportIdentifier
+        | SyntaxKind::StructDeclaration
+        | SyntaxKind::EnumDeclaration => (Keyword, false, false),
+        // the id of the element
+        SyntaxKind::SubElement | SyntaxKind::RepeatedIndex => (Variable, true, false),
+        SyntaxKind::CallbackConnection => (Callback, false, false),
+        SyntaxKind::QualifiedName => match parent.parent()?.kind() {
+            SyntaxKind::Type if is_builtin(token) => (Builtin, false, false),
+            SyntaxKind::Type => (Type, false, false),
+            // the base type
+            SyntaxKind::Element if is_builtin(token) => (Builtin, false, false),
+            SyntaxKind::Element => (Component, false, false),
+            SyntaxKind::StatePropertyChange | SyntaxKind::PropertyAnimation => {
+                (Property, false, false)
+            }
+            // FIXME: we should do actual lookup
+            _ => return None,
+        },
+        SyntaxKind::DeclaredIdentifier => match parent.parent()?.kind() {
+            SyntaxKind::Component => (Component, true, false),
+            SyntaxKind::RepeatedElement => (Property, true, false),
+            SyntaxKind::CallbackDeclaration => (Callback, true, false),
+            SyntaxKind::Function => (Function, true, false),
+            SyntaxKind::ArgumentDeclaration | SyntaxKind::CallbackConnection => {
+                (Parameter, true, false)
+            }
+            SyntaxKind::PropertyDeclaration => (Property, true, false),
+            SyntaxKind::StructDeclaration | SyntaxKind::EnumDeclaration => (Type, true, false),
+            // This is the state name, but what semantic type is that?
+            _ => return None,
+        },
+        SyntaxKind::ChildrenPlaceholder
+        | SyntaxKind::ChildrenSlot
+        | SyntaxKind::AtImageUrl
+        | SyntaxKind::AtLinearGradient => (Macro, false, false),
+        SyntaxKind::Binding | SyntaxKind::TwoWayBinding => (Property, false, false),
+        SyntaxKind::ObjectMember => (Property, false, true),
+        SyntaxKind::ExportIdentifier => (Type, false, !has_sibling(SyntaxKind::ExportName)),
+        SyntaxKind::ExportName | SyntaxKind::InternalName => (Type, false, true),
+        SyntaxKind::ExternalName => (Type, false, !has_sibling(SyntaxKind::InternalName)),
+        SyntaxKind::ObjectTypeMember | SyntaxKind::EnumValue => (Property, true, false),
+        _ => return None,
+    };
+    Some(result)
+}
+
+#[test]
+fn test_highlight_source() {
+    let source = r#"
+import { Button } from "std-widgets.slint";
+export Demo := Rectangle {
+    property <int> counter: 42;
+    callback clicked(int);
+    clicked(value) => { counter += value; }
+    btn := Button { text: "Click"; }
+}
+"#;
+    let tokens = highlight_source(source);
+    let find = |text: &str| {
+        tokens
+            .iter()
+            .find(|t| &source[t.range.clone()] == text)
+            .map(|t| (t.class, t.is_definition, t.is_declaration))
+    };
+    assert_eq!(find("import"), Some((TokenClass::Keyword, false, false)));
+    assert_eq!(find("Demo"), Some((TokenClass::Component, true, false)));
+    assert_eq!(find("Rectangle"), Some((TokenClass::Builtin, false, false)));
+    assert_eq!(find("int"), Some((TokenClass::Builtin, false, false)));
+    assert_eq!(find("counter"), Some((TokenClass::Property, true, false)));
+    assert_eq!(find("42"), Some((TokenClass::Number, false, false)));
+    assert_eq!(find("clicked"), Some((TokenClass::Callback, true, false)));
+    assert_eq!(find("value"), Some((TokenClass::Parameter, true, false)));
+    assert_eq!(find("+="), Some((TokenClass::Operator, false, false)));
+    assert_eq!(find("btn"), Some((TokenClass::Variable, true, false)));
+    assert_eq!(find("text"), Some((TokenClass::Property, false, false)));
+    assert_eq!(find("\"Click\""), Some((TokenClass::String, false, false)));
+
+    let base = tokens.iter().rev().find(|t| &source[t.range.clone()] == "Button").unwrap();
+    assert_eq!(base.class, TokenClass::Component);
+    assert!(tokens.windows(2).all(|w| w[0].range.end <= w[1].range.start));
+}
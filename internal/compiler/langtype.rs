@@ -42,6 +42,8 @@ pub enum Type {
     String,
     Color,
     Duration,
+    /// A point in time, stored as milliseconds since the Unix epoch
+    DateTime,
     PhysicalLength,
     LogicalLength,
     Angle,
@@ -52,8 +54,12 @@ pub enum Type {
     PathData, // Either a vector of path elements or a two vectors of events and coordinates
     Easing,
     Brush,
+    /// A factory that creates the component to show in a `ComponentContainer`
+    ComponentFactory,
     /// This is usually a model
     Array(Box<Type>),
+    /// A map with string keys and values of the given type, declared as `[string: T]`
+    Map(Box<Type>),
     Struct {
         fields: BTreeMap<String, Type>,
         /// When declared in .slint as  `struct Foo := { }`, then the name is "Foo"
@@ -96,6 +102,7 @@ fn eq(&self, other: &Self) -> bool {
             Type::String => matches!(other, Type::String),
             Type::Color => matches!(other, Type::Color),
             Type::Duration => matches!(other, Type::Duration),
+            Type::DateTime => matches!(other, Type::DateTime),
             Type::Angle => matches!(other, Type::Angle),
             Type::PhysicalLength => matches!(other, Type::PhysicalLength),
             Type::LogicalLength => matches!(other, Type::LogicalLength),
@@ -106,7 +113,9 @@ fn eq(&self, other: &Self) -> bool {
             Type::PathData => matches!(other, Type::PathData),
             Type::Easing => matches!(other, Type::Easing),
             Type::Brush => matches!(other, Type::Brush),
+            Type::ComponentFactory => matches!(other, Type::ComponentFactory),
             Type::Array(a) => matches!(other, Type::Array(b) if a == b),
+            Type::Map(a) => matches!(other, Type::Map(b) if a == b),
             Type::Struct { fields, name, node: _ } => {
                 matches!(other, Type::Struct{fields: f, name: n, node: _} if fields == f && name == n)
             }
@@ -159,6 +168,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Type::Int32 => write!(f, "int"),
             Type::String => write!(f, "string"),
             Type::Duration => write!(f, "duration"),
+            Type::DateTime => write!(f, "date-time"),
             Type::Angle => write!(f, "angle"),
             Type::PhysicalLength => write!(f, "physical-length"),
             Type::LogicalLength => write!(f, "length"),
@@ -168,6 +178,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Type::Bool => write!(f, "bool"),
             Type::Model => write!(f, "model"),
             Type::Array(t) => write!(f, "[{}]", t),
+            Type::Map(t) => write!(f, "[string: {}]", t),
             Type::Struct { name: Some(name), .. } => write!(f, "{}", name),
             Type::Struct { fields, name: None, .. } => {
                 write!(f, "{{ ")?;
@@ -180,6 +191,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Type::PathData => write!(f, "pathdata"),
             Type::Easing => write!(f, "easing"),
             Type::Brush => write!(f, "brush"),
+            Type::ComponentFactory => write!(f, "component-factory"),
             Type::Enumeration(enumeration) => write!(f, "enum {}", enumeration.name),
             Type::UnitProduct(vec) => {
                 const POWERS: &[char] = &['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
@@ -213,6 +225,7 @@ pub fn is_property_type(&self) -> bool {
                 | Self::String
                 | Self::Color
                 | Self::Duration
+                | Self::DateTime
                 | Self::Angle
                 | Self::PhysicalLength
                 | Self::LogicalLength
@@ -225,7 +238,9 @@ pub fn is_property_type(&self) -> bool {
                 | Self::ElementReference
                 | Self::Struct { .. }
                 | Self::Array(_)
+                | Self::Map(_)
                 | Self::Brush
+                | Self::ComponentFactory
                 | Self::InferredProperty
         )
     }
@@ -416,6 +431,7 @@ pub fn can_convert(&self, other: &Self) -> bool {
             | (Type::Int32, Type::Float32)
             | (Type::Int32, Type::String)
             | (Type::Array(_), Type::Model)
+            | (Type::Map(_), Type::Model)
             | (Type::Float32, Type::Model)
             | (Type::Int32, Type::Model)
             | (Type::PhysicalLength, Type::LogicalLength)
@@ -463,6 +479,7 @@ pub fn collect_contextual_types(
     pub fn default_unit(&self) -> Option<Unit> {
         match self {
             Type::Duration => Some(Unit::Ms),
+            Type::DateTime => None,
             Type::PhysicalLength => Some(Unit::Phx),
             Type::LogicalLength => Some(Unit::Px),
             // Unit::Percent is special that it does not combine with other units like
@@ -486,7 +503,9 @@ pub fn default_unit(&self) -> Option<Unit> {
             Type::PathData => None,
             Type::Easing => None,
             Type::Brush => None,
+            Type::ComponentFactory => None,
             Type::Array(_) => None,
+            Type::Map(_) => None,
             Type::Struct { .. } => None,
             Type::Enumeration(_) => None,
             Type::UnitProduct(_) => None,
@@ -503,6 +522,23 @@ pub fn as_unit_product(&self) -> Option<Vec<(Unit, i8)>> {
             _ => self.default_unit().map(|u| vec![(u, 1)]),
         }
     }
+
+    /// For a `Type::Map`, the type of the entries when iterating over the map: `{ key: string, value: T }`
+    pub fn map_entry_type(&self) -> Option<Type> {
+        match self {
+            Type::Map(value_type) => Some(Type::Struct {
+                fields: [
+                    ("key".to_owned(), Type::String),
+                    ("value".to_owned(), (**value_type).clone()),
+                ]
+                .into_iter()
+                .collect(),
+                name: None,
+                node: None,
+            }),
+            _ => None,
+        }
+    }
 }
 
 impl Default for Type {
@@ -620,6 +656,8 @@ pub struct BuiltinElement {
     pub default_size_binding: DefaultSizeBinding,
     /// When true this is an internal type not shown in the auto-completion
     pub is_internal: bool,
+    /// When set, this element is deprecated, and the string tells what to use instead
+    pub deprecated: Option<String>,
 }
 
 impl BuiltinElement {
@@ -645,6 +683,8 @@ pub struct Enumeration {
     pub name: String,
     pub values: Vec<String>,
     pub default_value: usize, // index in values
+    /// When declared in a .slint file, this is the node of the declaration
+    pub node: Option<syntax_nodes::EnumDeclaration>,
 }
 
 impl PartialEq for Enumeration {
@@ -12,17 +12,23 @@
 use core::future::Future;
 use core::pin::Pin;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 pub mod builtin_macros;
+pub mod bundle;
+pub(crate) mod conditional_blocks;
 pub mod diagnostics;
+pub mod docs;
 pub mod embedded_resources;
 pub mod expression_tree;
 pub mod fileaccess;
 pub mod generator;
+pub mod highlighting;
 pub mod langtype;
 pub mod layout;
 pub mod lexer;
+pub mod lint;
 pub mod literals;
 pub mod llr;
 pub(crate) mod load_builtins;
@@ -43,6 +49,10 @@ pub struct CompilerConfiguration {
     pub embed_resources: bool,
     /// The compiler will look in these paths for components used in the file to compile.
     pub include_paths: Vec<std::path::PathBuf>,
+    /// The paths of the libraries, by name. A document imports the files of a library with
+    /// `import { X } from "@name/path/to/file.slint"`, or the file that is the library path
+    /// with `@name`. The path can also be a URL, that is loaded with `open_import_fallback`.
+    pub library_paths: HashMap<String, std::path::PathBuf>,
     /// the name of the style. (eg: "native")
     pub style: Option<String>,
 
@@ -62,6 +72,78 @@ pub struct CompilerConfiguration {
 
     /// Compile time scale factor to apply to embedded resources such as images and glyphs.
     pub scale_factor: f64,
+
+    /// The platform name matched by `platform == "..."` in the conditions of `@if` blocks.
+    /// (eg: "linux", "windows", "macos", "android", "ios", "wasm", or "embedded")
+    pub target_platform: String,
+
+    /// The flags that are considered set in the conditions of `@if` blocks.
+    pub conditional_flags: Vec<String>,
+
+    /// Constants that the expressions in the `.slint` files can access as `Defines.name`.
+    pub defines: HashMap<String, DefineValue>,
+
+    /// Allow the `ComponentContainer` element and the `component-factory` type.
+    ///
+    /// They are only supported by the interpreter, which can instantiate components at runtime.
+    pub enable_component_containers: bool,
+
+    /// Libraries compiled with [`compile_library`]. The documents of these libraries are not
+    /// read and parsed again when they are imported, and their sources don't need to exist.
+    pub libraries: Vec<Rc<typeloader::CompiledLibrary>>,
+
+    /// Also compile the other exported components of the document, and not only the last one.
+    /// They are then in [`object_tree::Document::other_roots`]. This requires
+    /// [`Self::inline_all_elements`], which the interpreter uses.
+    pub all_exported_components: bool,
+
+    /// The levels of the lints, and the elements that are deprecated in the project.
+    pub lints: lint::LintConfiguration,
+
+    /// The ids of the elements of the main component whose `visible`, `enabled`, and `opacity`
+    /// properties the interpreter can override at runtime.
+    pub overridable_elements: Vec<String>,
+}
+
+/// The value of a constant passed to the compiler in [`CompilerConfiguration::defines`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DefineValue {
+    /// A constant of type `string`
+    String(String),
+    /// A constant of type `float`
+    Number(f64),
+    /// A constant of type `bool`
+    Bool(bool),
+}
+
+impl From<&str> for DefineValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for DefineValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<f64> for DefineValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<i32> for DefineValue {
+    fn from(value: i32) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<bool> for DefineValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
 }
 
 impl CompilerConfiguration {
@@ -97,17 +179,68 @@ pub fn new(output_format: crate::generator::OutputFormat) -> Self {
             .filter(|f| *f > 0.)
             .unwrap_or(1.);
 
+        let target_platform = std::env::var("SLINT_TARGET_PLATFORM")
+            .unwrap_or_else(|_| conditional_blocks::default_target_platform());
+
+        let conditional_flags = std::env::var("SLINT_CONDITIONAL_FLAGS")
+            .map(|var| {
+                var.split(',')
+                    .map(str::trim)
+                    .filter(|flag| !flag.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let enable_component_containers =
+            output_format == crate::generator::OutputFormat::Interpreter;
+
         Self {
             embed_resources,
             include_paths: Default::default(),
+            library_paths: Default::default(),
             style: Default::default(),
             open_import_fallback: Default::default(),
             inline_all_elements,
             scale_factor,
+            target_platform,
+            conditional_flags,
+            defines: Default::default(),
+            enable_component_containers,
+            libraries: Default::default(),
+            all_exported_components: false,
+            lints: Default::default(),
+            overridable_elements: Default::default(),
         }
     }
 }
 
+/// Parse the given .slint files and the files they import, and resolve their expressions, so that
+/// other compilations can link them with [`CompilerConfiguration::libraries`].
+///
+/// The builtin files, such as the widgets of the style, are not part of the library.
+pub async fn compile_library(
+    paths: &[std::path::PathBuf],
+    mut diagnostics: diagnostics::BuildDiagnostics,
+    compiler_config: CompilerConfiguration,
+) -> (typeloader::CompiledLibrary, diagnostics::BuildDiagnostics) {
+    let global_type_registry = typeregister::TypeRegister::builtin();
+    let mut loader =
+        typeloader::TypeLoader::new(global_type_registry, &compiler_config, &mut diagnostics);
+
+    if diagnostics.has_error() {
+        return (Default::default(), diagnostics);
+    }
+
+    for path in paths {
+        loader.load_library_file(path, &mut diagnostics).await;
+    }
+
+    diagnostics.all_loaded_files = loader.all_files().cloned().collect();
+
+    (loader.compiled_library(), diagnostics)
+}
+
 pub async fn compile_syntax_node(
     doc_node: parser::SyntaxNode,
     mut diagnostics: diagnostics::BuildDiagnostics,
@@ -117,7 +250,9 @@ pub async fn compile_syntax_node(
     let type_registry =
         Rc::new(RefCell::new(typeregister::TypeRegister::new(&global_type_registry)));
 
-    let doc_node: parser::syntax_nodes::Document = doc_node.into();
+    let doc_node: parser::syntax_nodes::Document =
+        conditional_blocks::apply_conditional_blocks(doc_node, &compiler_config, &mut diagnostics)
+            .into();
 
     let mut loader =
         typeloader::TypeLoader::new(global_type_registry, &compiler_config, &mut diagnostics);
@@ -129,16 +264,42 @@ pub async fn compile_syntax_node(
     let foreign_imports =
         loader.load_dependencies_recursively(&doc_node, &mut diagnostics, &type_registry).await;
 
-    let doc = crate::object_tree::Document::from_node(
+    let mut doc = crate::object_tree::Document::from_node(
         doc_node,
         foreign_imports,
         &mut diagnostics,
         &type_registry,
     );
 
-    if let Some((_, node)) = &*doc.root_component.child_insertion_point.borrow() {
-        diagnostics
-            .push_error("@children placeholder not allowed in the final component".into(), node)
+    let mut final_components = vec![doc.root_component.clone()];
+    if compiler_config.all_exported_components && compiler_config.inline_all_elements {
+        let exported_components = doc
+            .exports()
+            .iter()
+            .filter_map(|(name, ty)| match ty {
+                langtype::Type::Component(c)
+                    if !c.is_global() && !Rc::ptr_eq(c, &doc.root_component) =>
+                {
+                    Some((name.name.clone(), c.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for (name, component) in exported_components {
+            doc.other_roots.push((name, object_tree::Component::new_root(&component)));
+            final_components.push(component);
+        }
+    }
+
+    for component in &final_components {
+        if let Some((_, node)) = &*component.child_insertion_point.borrow() {
+            diagnostics
+                .push_error("@children placeholder not allowed in the final component".into(), node)
+        }
+        for (_, node) in component.named_child_insertion_points.borrow().values() {
+            diagnostics
+                .push_error("@children placeholder not allowed in the final component".into(), node)
+        }
     }
 
     if !diagnostics.has_error() {
@@ -0,0 +1,327 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+ Lints are optional checks of the .slint files that report code which is valid but probably not
+ what was intended, or that goes against the conventions of a project.
+
+ Each [`Lint`] has a [`LintLevel`] that can be changed in the [`LintConfiguration`] of the
+ [`CompilerConfiguration`](crate::CompilerConfiguration). The lints report their results as
+ warnings or errors in the [`BuildDiagnostics`], like the other diagnostics of the compiler.
+*/
+
+use crate::diagnostics::{BuildDiagnostics, DiagnosticLevel, Spanned};
+use crate::langtype::Type;
+use crate::object_tree::*;
+use crate::parser::SyntaxKind;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// The lints built in the compiler
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Lint {
+    /// A property declared in an element other than the root of a component, that is never read
+    UnusedProperty,
+    /// The model data or index of a `for` that has the same name as the one of an enclosing
+    /// `for`, or as an element id
+    ShadowedName,
+    /// An element that is deprecated, either a builtin element or an element listed in
+    /// [`LintConfiguration::deprecated_elements`]
+    DeprecatedElement,
+    /// A color literal such as `#ff0000` outside of a global, instead of a named color of the
+    /// palette of the project
+    MagicColorLiteral,
+}
+
+impl Lint {
+    /// All the lints
+    pub const ALL: [Lint; 4] = [
+        Lint::UnusedProperty,
+        Lint::ShadowedName,
+        Lint::DeprecatedElement,
+        Lint::MagicColorLiteral,
+    ];
+
+    /// The name of the lint in the configuration and in the diagnostics (eg: "unused-property")
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UnusedProperty => "unused-property",
+            Lint::ShadowedName => "shadowed-name",
+            Lint::DeprecatedElement => "deprecated-element",
+            Lint::MagicColorLiteral => "magic-color-literal",
+        }
+    }
+
+    /// The level of the lint when it is not set in the [`LintConfiguration`]
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            Lint::DeprecatedElement => LintLevel::Warn,
+            Lint::UnusedProperty | Lint::ShadowedName | Lint::MagicColorLiteral => LintLevel::Allow,
+        }
+    }
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Lint {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lint::ALL.into_iter().find(|l| l.name() == s).ok_or_else(|| format!("Unknown lint {}", s))
+    }
+}
+
+/// What the compiler does when a lint finds something
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LintLevel {
+    /// The lint is not run
+    Allow,
+    /// The lint reports warnings
+    Warn,
+    /// The lint reports errors, so the compilation fails
+    Deny,
+}
+
+impl std::str::FromStr for LintLevel {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "deny" => Ok(Self::Deny),
+            _ => Err(format!("Unknown lint level {}, expected allow, warn, or deny", s)),
+        }
+    }
+}
+
+/// The configuration of the lints of a project
+#[derive(Clone, Debug, Default)]
+pub struct LintConfiguration {
+    /// The level of the lints, when it is not their default level
+    pub levels: HashMap<Lint, LintLevel>,
+    /// The elements that the [`Lint::DeprecatedElement`] lint reports, by name, with a message
+    /// that tells what to use instead (eg: "use `MyButton` instead"), which can be empty
+    pub deprecated_elements: HashMap<String, String>,
+}
+
+impl LintConfiguration {
+    /// The level of this lint
+    pub fn level(&self, lint: Lint) -> LintLevel {
+        self.levels.get(&lint).copied().unwrap_or_else(|| lint.default_level())
+    }
+}
+
+/// Run the lints that are not allowed on the components of the document
+pub fn run_lints(doc: &Document, config: &LintConfiguration, diag: &mut BuildDiagnostics) {
+    let mut ctx = LintContext { config, diag };
+    for component in &doc.inner_components {
+        if ctx.enabled(Lint::UnusedProperty) {
+            check_unused_properties(component, &mut ctx);
+        }
+        if ctx.enabled(Lint::ShadowedName) {
+            check_shadowed_names(component, &mut ctx);
+        }
+        if ctx.enabled(Lint::DeprecatedElement) {
+            check_deprecated_elements(component, &mut ctx);
+        }
+        if ctx.enabled(Lint::MagicColorLiteral) {
+            check_magic_color_literals(component, &mut ctx);
+        }
+    }
+}
+
+struct LintContext<'a> {
+    config: &'a LintConfiguration,
+    diag: &'a mut BuildDiagnostics,
+}
+
+impl LintContext<'_> {
+    fn enabled(&self, lint: Lint) -> bool {
+        self.config.level(lint) != LintLevel::Allow
+    }
+
+    fn report(&mut self, lint: Lint, message: String, source: &dyn Spanned) {
+        let level = match self.config.level(lint) {
+            LintLevel::Allow => return,
+            LintLevel::Warn => DiagnosticLevel::Warning,
+            LintLevel::Deny => DiagnosticLevel::Error,
+        };
+        self.diag.push_diagnostic(format!("{} [{}]", message, lint), source, level);
+    }
+}
+
+fn check_unused_properties(component: &Rc<Component>, ctx: &mut LintContext) {
+    if component.is_global() {
+        return;
+    }
+    let mut used = HashSet::new();
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        visit_all_named_references_in_element(elem, |nr| {
+            used.insert((Rc::as_ptr(&nr.element()), nr.name().to_owned()));
+        });
+    });
+
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        // The properties of the root element are the interface of the component
+        if Rc::ptr_eq(elem, &component.root_element) {
+            return;
+        }
+        for (name, decl) in &elem.borrow().property_declarations {
+            if matches!(decl.property_type, Type::Callback { .. })
+                || used.contains(&(Rc::as_ptr(elem), name.clone()))
+            {
+                continue;
+            }
+            if let Some(node) = &decl.node {
+                let node =
+                    node.as_ref().either(|n| n.DeclaredIdentifier(), |n| n.DeclaredIdentifier());
+                ctx.report(
+                    Lint::UnusedProperty,
+                    format!("The property '{}' is declared but never used", name),
+                    &node,
+                );
+            }
+        }
+    });
+}
+
+fn check_shadowed_names(component: &Rc<Component>, ctx: &mut LintContext) {
+    let mut ids = HashSet::new();
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        let id = &elem.borrow().id;
+        if !id.is_empty() {
+            ids.insert(id.clone());
+        }
+    });
+
+    // The state is the names of the model data and index of the enclosing `for`
+    recurse_elem(&component.root_element, &Vec::<String>::new(), &mut |elem, enclosing| {
+        let elem = elem.borrow();
+        let mut names = enclosing.clone();
+        if let Some(r) = elem.repeated.as_ref().filter(|r| !r.is_conditional_element) {
+            for name in [&r.model_data_id, &r.index_id] {
+                if name.is_empty() {
+                    continue;
+                }
+                let shadowed = if enclosing.contains(name) {
+                    "a variable of an enclosing 'for'"
+                } else if ids.contains(name) {
+                    "an element id"
+                } else {
+                    names.push(name.clone());
+                    continue;
+                };
+                ctx.report(
+                    Lint::ShadowedName,
+                    format!("'{}' has the same name as {}", name, shadowed),
+                    &*elem,
+                );
+            }
+        }
+        names
+    });
+}
+
+fn check_deprecated_elements(component: &Rc<Component>, ctx: &mut LintContext) {
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        let elem = elem.borrow();
+        let name = match elem.node.as_ref().and_then(|n| n.QualifiedName()) {
+            Some(name) => name.text().to_string().trim().to_owned(),
+            None => return,
+        };
+        let note = match &elem.base_type {
+            Type::Builtin(b) if b.deprecated.is_some() => b.deprecated.clone(),
+            _ => ctx.config.deprecated_elements.get(&name).cloned(),
+        };
+        match note {
+            Some(note) if note.is_empty() => {
+                ctx.report(Lint::DeprecatedElement, format!("'{}' is deprecated", name), &*elem)
+            }
+            Some(note) => ctx.report(
+                Lint::DeprecatedElement,
+                format!("'{}' is deprecated: {}", name, note),
+                &*elem,
+            ),
+            None => (),
+        }
+    });
+}
+
+fn check_magic_color_literals(component: &Rc<Component>, ctx: &mut LintContext) {
+    // The globals are where the named colors are declared
+    if component.is_global() {
+        return;
+    }
+    let node = match &component.root_element.borrow().node {
+        Some(node) => node.clone(),
+        None => return,
+    };
+    for token in node.descendants_with_tokens().filter_map(|t| t.into_token()) {
+        if token.kind() == SyntaxKind::ColorLiteral {
+            ctx.report(
+                Lint::MagicColorLiteral,
+                format!(
+                    "The color literal {} should be a named color declared in a global",
+                    token.text()
+                ),
+                &token,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_lints() {
+    let source = r#"
+OldButton := Rectangle { }
+export Test := Window {
+    property <color> accent: #ff0000;
+    property <[int]> model: [1, 2];
+    area := TouchArea {
+        property <int> unused: 42;
+        property <int> used: 10;
+        width: used * 1px;
+    }
+    for area in model: OldButton { }
+    for item in model: Rectangle {
+        for item in model: Rectangle { }
+    }
+}"#;
+    let lints = |config: LintConfiguration| {
+        let mut diag = crate::diagnostics::BuildDiagnostics::default();
+        let node = crate::parser::parse(source.into(), None, &mut diag);
+        let mut compiler_config =
+            crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+        compiler_config.style = Some("fluent".into());
+        compiler_config.lints = config;
+        let (_, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, compiler_config));
+        diag.iter().map(|d| (d.level(), d.message().to_owned())).collect::<Vec<_>>()
+    };
+
+    assert_eq!(lints(Default::default()), vec![]);
+
+    let mut config = LintConfiguration {
+        levels: Lint::ALL.into_iter().map(|l| (l, LintLevel::Warn)).collect(),
+        deprecated_elements: [("OldButton".into(), "use `Button` instead".into())].into(),
+    };
+    config.levels.insert(Lint::MagicColorLiteral, LintLevel::Deny);
+    let warning = |m: &str| (DiagnosticLevel::Warning, m.to_owned());
+    assert_eq!(
+        lints(config),
+        vec![
+            warning("The property 'unused' is declared but never used [unused-property]"),
+            warning("'area' has the same name as an element id [shadowed-name]"),
+            warning("'item' has the same name as a variable of an enclosing 'for' [shadowed-name]"),
+            warning("'OldButton' is deprecated: use `Button` instead [deprecated-element]"),
+            (
+                DiagnosticLevel::Error,
+                "The color literal #ff0000 should be a named color declared in a global [magic-color-literal]"
+                    .into()
+            ),
+        ]
+    );
+}
@@ -56,6 +56,19 @@ fn test_parse_color_literal() {
     assert_eq!(parse_color_literal("#1234567890"), None);
 }
 
+/// Returns `0xaarrggbb` for the name of a color of the `Colors` namespace, such as `red`
+pub fn parse_named_color(name: &str) -> Option<u32> {
+    let c = css_color_parser2::NAMED_COLORS.get(name)?;
+    Some(((c.a * 255.) as u32) << 24 | (c.r as u32) << 16 | (c.g as u32) << 8 | (c.b as u32))
+}
+
+#[test]
+fn test_parse_named_color() {
+    assert_eq!(parse_named_color("red"), Some(0xffff0000));
+    assert_eq!(parse_named_color("transparent"), Some(0));
+    assert_eq!(parse_named_color("not-a-color"), None);
+}
+
 pub fn unescape_string(string: &str) -> Option<String> {
     if string.contains('\n') {
         // FIXME: new line in string literal not yet supported
@@ -153,6 +166,8 @@ fn doit(s: &str) -> Result<(f64, Unit), String> {
     assert_eq!(doit("10.10"), Ok((10.10, Unit::None)));
     assert_eq!(doit("10000000"), Ok((10000000., Unit::None)));
     assert_eq!(doit("10000001phx"), Ok((10000001., Unit::Phx)));
+    assert_eq!(doit("1.5rem"), Ok((1.5, Unit::Rem)));
+    assert_eq!(doit("50vw"), Ok((50., Unit::Vw)));
 
     let wrong_unit = Err("Invalid unit".to_owned());
     let cannot_parse = Err("Cannot parse number literal".to_owned());
@@ -46,9 +46,9 @@ pub enum Expression {
         name: String,
     },
 
-    /// Access to a index within an array.
+    /// Access to a index within an array, or to the value for a key within a map.
     ArrayIndex {
-        /// This expression should have [`Type::Array`] type
+        /// This expression should have [`Type::Array`] or [`Type::Map`] type
         array: Box<Expression>,
         index: Box<Expression>,
     },
@@ -127,6 +127,11 @@ pub enum Expression {
         /// When true, this should be converted to a model. When false, this should stay as a slice
         as_model: bool,
     },
+    Map {
+        value_ty: Type,
+        /// The first expression of the tuple is the key
+        entries: Vec<(Expression, Expression)>,
+    },
     Struct {
         ty: Type,
         values: HashMap<String, Expression>,
@@ -186,6 +191,7 @@ pub fn default_value_for_type(ty: &Type) -> Option<Self> {
             | Type::InferredProperty
             | Type::InferredCallback
             | Type::ElementReference
+            | Type::ComponentFactory
             | Type::LayoutCache => return None,
             Type::Float32
             | Type::Duration
@@ -196,7 +202,7 @@ pub fn default_value_for_type(ty: &Type) -> Option<Self> {
             | Type::UnitProduct(_) => Expression::NumberLiteral(0.),
             Type::Percent => Expression::NumberLiteral(1.),
             Type::String => Expression::StringLiteral(String::new()),
-            Type::Color => {
+            Type::Color | Type::DateTime => {
                 Expression::Cast { from: Box::new(Expression::NumberLiteral(0.)), to: ty.clone() }
             }
             Type::Image => Expression::ImageReference {
@@ -210,6 +216,9 @@ pub fn default_value_for_type(ty: &Type) -> Option<Self> {
                 values: vec![],
                 as_model: true,
             },
+            Type::Map(value_ty) => {
+                Expression::Map { value_ty: (**value_ty).clone(), entries: vec![] }
+            }
             Type::Struct { fields, .. } => Expression::Struct {
                 ty: ty.clone(),
                 values: fields
@@ -242,7 +251,7 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
                 _ => unreachable!(),
             },
             Self::ArrayIndex { array, .. } => match array.ty(ctx) {
-                Type::Array(ty) => *ty,
+                Type::Array(ty) | Type::Map(ty) => *ty,
                 _ => unreachable!(),
             },
             Self::Cast { to, .. } => to.clone(),
@@ -262,9 +271,11 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
             Self::PropertyAssignment { .. } => Type::Void,
             Self::ModelDataAssignment { .. } => Type::Void,
             Self::ArrayIndexAssignment { .. } => Type::Void,
-            Self::BinaryExpression { lhs, rhs: _, op } => {
+            Self::BinaryExpression { lhs, rhs, op } => {
                 if crate::expression_tree::operator_class(*op) != OperatorClass::ArithmeticOp {
                     Type::Bool
+                } else if *op == '-' && rhs.ty(ctx) == Type::DateTime {
+                    Type::Duration
                 } else {
                     lhs.ty(ctx)
                 }
@@ -273,6 +284,7 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
             Self::ImageReference { .. } => Type::Image,
             Self::Condition { true_expr, .. } => true_expr.ty(ctx),
             Self::Array { element_ty, .. } => Type::Array(element_ty.clone().into()),
+            Self::Map { value_ty, .. } => Type::Map(value_ty.clone().into()),
             Self::Struct { ty, .. } => ty.clone(),
             Self::EasingCurve(_) => Type::Easing,
             Self::LinearGradient { .. } => Type::Brush,
@@ -332,6 +344,12 @@ macro_rules! visit_impl {
                 $visitor(false_expr);
             }
             Expression::Array { values, .. } => values.$iter().for_each($visitor),
+            Expression::Map { entries, .. } => {
+                for (k, v) in entries {
+                    $visitor(k);
+                    $visitor(v);
+                }
+            }
             Expression::Struct { values, .. } => values.$values().for_each($visitor),
             Expression::EasingCurve(_) => {}
             Expression::LinearGradient { angle, stops } => {
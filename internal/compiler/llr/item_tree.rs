@@ -40,6 +40,8 @@ pub enum Animation {
 pub struct BindingExpression {
     pub expression: MutExpression,
     pub animation: Option<Animation>,
+    /// The code to run when the animation is finished
+    pub animation_finished: Option<Expression>,
     /// When true, we can initialize the property with `set` otherwise, `set_binding` must be used
     pub is_constant: bool,
     /// When true, the expression is a "state binding".  Despite the type of the expression being a integer
@@ -120,6 +122,9 @@ pub struct RepeatedElement {
     pub index_prop: Option<PropertyIndex>,
     /// Within the sub_tree's root component
     pub data_prop: Option<PropertyIndex>,
+    /// The properties that hold the fields of the model data, when it is a struct.
+    /// Within the sub_tree's root component
+    pub data_field_props: Vec<(String, PropertyIndex)>,
     pub sub_tree: ItemTree,
     /// The index of the item node in the parent tree
     pub index_in_tree: usize,
@@ -214,6 +219,8 @@ pub struct SubComponent {
     pub property_init: Vec<(PropertyReference, BindingExpression)>,
     /// The animation for properties which are animated
     pub animations: HashMap<PropertyReference, Expression>,
+    /// The code to run when the animation of a property is finished, for the properties in `animations`
+    pub animation_finished_handlers: HashMap<PropertyReference, Expression>,
     pub two_way_bindings: Vec<(PropertyReference, PropertyReference)>,
     pub const_properties: Vec<PropertyReference>,
     // Code that is run in the sub component constructor, after property initializations
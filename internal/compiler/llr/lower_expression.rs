@@ -99,9 +99,14 @@ pub fn lower_expression(
         tree_Expression::ReadLocalVariable { name, ty } => {
             llr_Expression::ReadLocalVariable { name: name.clone(), ty: ty.clone() }
         }
-        tree_Expression::StructFieldAccess { base, name } => llr_Expression::StructFieldAccess {
-            base: Box::new(lower_expression(base, ctx)),
-            name: name.clone(),
+        tree_Expression::StructFieldAccess { base, name } => match &**base {
+            tree_Expression::RepeaterModelReference { element } => {
+                repeater_model_data_field(element, name, ctx)
+            }
+            _ => llr_Expression::StructFieldAccess {
+                base: Box::new(lower_expression(base, ctx)),
+                name: name.clone(),
+            },
         },
         tree_Expression::ArrayIndex { array, index } => llr_Expression::ArrayIndex {
             array: Box::new(lower_expression(array, ctx)),
@@ -117,6 +122,13 @@ pub fn lower_expression(
             tree_Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, _) => {
                 lower_show_popup(arguments, ctx)
             }
+            tree_Expression::BuiltinFunctionReference(BuiltinFunction::ClosePopupWindow, _) => {
+                // There can only be one popup at the time, so the element is not needed
+                llr_Expression::BuiltinFunctionCall {
+                    function: BuiltinFunction::ClosePopupWindow,
+                    arguments: vec![],
+                }
+            }
             tree_Expression::BuiltinFunctionReference(f, _) => {
                 let arguments = arguments.iter().map(|e| lower_expression(e, ctx)).collect::<_>();
                 llr_Expression::BuiltinFunctionCall { function: *f, arguments }
@@ -151,6 +163,13 @@ pub fn lower_expression(
             values: values.iter().map(|e| lower_expression(e, ctx)).collect::<_>(),
             as_model: true,
         },
+        tree_Expression::Map { value_ty, entries } => llr_Expression::Map {
+            value_ty: value_ty.clone(),
+            entries: entries
+                .iter()
+                .map(|(k, v)| (lower_expression(k, ctx), lower_expression(v, ctx)))
+                .collect::<_>(),
+        },
         tree_Expression::Struct { ty, values } => llr_Expression::Struct {
             ty: ty.clone(),
             values: values
@@ -318,6 +337,30 @@ fn repeater_special_property(
     llr_Expression::PropertyReference(r)
 }
 
+/// Reads a field of the model data of a repeater from the property that holds this field, so that
+/// the binding is not notified when only other fields of the row change
+fn repeater_model_data_field(
+    element: &Weak<RefCell<Element>>,
+    name: &str,
+    ctx: &ExpressionContext,
+) -> llr_Expression {
+    // The context of the repeated component, which has the properties of the fields
+    let mut repeated_ctx = Some(ctx);
+    while let Some(c) = repeated_ctx {
+        if Weak::ptr_eq(&c.component.parent_element, element) {
+            break;
+        }
+        repeated_ctx = c.parent;
+    }
+    match repeated_ctx.and_then(|c| c.mapping.model_data_fields.get(name)) {
+        Some(property_index) => repeater_special_property(element, ctx.component, *property_index),
+        None => llr_Expression::StructFieldAccess {
+            base: repeater_special_property(element, ctx.component, 0).into(),
+            name: name.into(),
+        },
+    }
+}
+
 fn lower_show_popup(args: &[tree_Expression], ctx: &ExpressionContext) -> llr_Expression {
     if let [tree_Expression::ElementReference(e)] = args {
         let popup_window = e.upgrade().unwrap();
@@ -440,6 +483,53 @@ fn animation_ty() -> Type {
     }
 }
 
+/// Lower the `finished` handler of the animation. Returns None if the animation has no such handler.
+pub fn lower_animation_finished(
+    a: &PropertyAnimation,
+    ctx: &ExpressionContext<'_>,
+) -> Option<llr_Expression> {
+    fn lower_finished_handler(
+        a: &ElementRc,
+        ctx: &ExpressionContext<'_>,
+    ) -> Option<llr_Expression> {
+        a.borrow().bindings.get("finished").map(|b| lower_expression(&b.borrow().expression, ctx))
+    }
+
+    match a {
+        PropertyAnimation::Static(a) => lower_finished_handler(a, ctx),
+        PropertyAnimation::Transition { state_ref, animations } => {
+            let handlers = animations
+                .iter()
+                .map(|tr| lower_finished_handler(&tr.animation, ctx))
+                .collect::<Vec<_>>();
+            if handlers.iter().all(Option::is_none) {
+                return None;
+            }
+            // Run the handler of the transition that was selected for the current state
+            let set_state = llr_Expression::StoreLocalVariable {
+                name: "state".into(),
+                value: Box::new(lower_expression(state_ref, ctx)),
+            };
+            let mut run_handler = llr_Expression::CodeBlock(vec![]);
+            for (tr, handler) in animations.iter().zip(handlers).rev() {
+                let condition = lower_expression(
+                    &tr.condition(tree_Expression::ReadLocalVariable {
+                        name: "state".into(),
+                        ty: state_ref.ty(),
+                    }),
+                    ctx,
+                );
+                run_handler = llr_Expression::Condition {
+                    condition: Box::new(condition),
+                    true_expr: Box::new(handler.unwrap_or(llr_Expression::CodeBlock(vec![]))),
+                    false_expr: Box::new(run_handler),
+                }
+            }
+            Some(llr_Expression::CodeBlock(vec![set_state, run_handler]))
+        }
+    }
+}
+
 fn compute_layout_info(
     l: &crate::layout::Layout,
     o: Orientation,
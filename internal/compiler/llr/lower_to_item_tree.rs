@@ -8,7 +8,7 @@
 use crate::llr::item_tree::*;
 use crate::namedreference::NamedReference;
 use crate::object_tree::{Component, ElementRc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
 use super::lower_expression::ExpressionContext;
@@ -68,6 +68,8 @@ pub enum LoweredElement {
 pub struct LoweredSubComponentMapping {
     pub element_mapping: HashMap<ByAddress<ElementRc>, LoweredElement>,
     pub property_mapping: HashMap<NamedReference, PropertyReference>,
+    /// The properties of a repeated component that hold the fields of its model data, by name
+    pub model_data_fields: BTreeMap<String, PropertyIndex>,
 }
 
 impl LoweredSubComponentMapping {
@@ -185,6 +187,7 @@ fn lower_sub_component(
         sub_components: Default::default(),
         property_init: Default::default(),
         animations: Default::default(),
+        animation_finished_handlers: Default::default(),
         two_way_bindings: Default::default(),
         const_properties: Default::default(),
         init_code: Default::default(),
@@ -212,6 +215,18 @@ fn lower_sub_component(
                 ty: Type::Int32,
                 ..Property::default()
             });
+            // One property per field of the model data, so that a binding only depends on the
+            // fields it uses. See lower_expression::repeater_model_data_field
+            if let Type::Struct { fields, .. } = sub_component.properties[0].ty.clone() {
+                for (name, ty) in fields {
+                    mapping.model_data_fields.insert(name.clone(), sub_component.properties.len());
+                    sub_component.properties.push(Property {
+                        name: format!("model_data_field_{}", name),
+                        ty,
+                        ..Property::default()
+                    });
+                }
+            }
         }
     };
 
@@ -298,6 +313,11 @@ fn lower_sub_component(
                 .as_ref()
                 .filter(|_| !is_constant)
                 .map(|a| super::lower_expression::lower_animation(a, &ctx));
+            let animation_finished = binding
+                .animation
+                .as_ref()
+                .filter(|_| !is_constant)
+                .and_then(|a| super::lower_expression::lower_animation_finished(a, &ctx));
 
             sub_component.prop_analysis.insert(
                 prop.clone(),
@@ -317,6 +337,7 @@ fn lower_sub_component(
                 BindingExpression {
                     expression,
                     animation,
+                    animation_finished,
                     is_constant,
                     is_state_info,
                     use_count: 0.into(),
@@ -330,9 +351,16 @@ fn lower_sub_component(
             .get(p)
             .map_or(true, |a| a.is_set || a.is_set_externally)
         {
-            if let Some(anim) = binding.animation.as_ref() {
-                match super::lower_expression::lower_animation(anim, &ctx) {
+            if let Some(anim_tree) = binding.animation.as_ref() {
+                match super::lower_expression::lower_animation(anim_tree, &ctx) {
                     Animation::Static(anim) => {
+                        if let Some(finished) =
+                            super::lower_expression::lower_animation_finished(anim_tree, &ctx)
+                        {
+                            sub_component
+                                .animation_finished_handlers
+                                .insert(prop.clone(), finished);
+                        }
                         sub_component.animations.insert(prop, anim);
                     }
                     Animation::Transition(_) => {
@@ -432,6 +460,13 @@ fn lower_repeated_component(elem: &ElementRc, ctx: &ExpressionContext) -> Repeat
         prop_height: map_inner_prop("height"),
     });
 
+    let data_field_props = sc
+        .mapping
+        .model_data_fields
+        .iter()
+        .map(|(name, property_index)| (name.clone(), *property_index))
+        .collect();
+
     RepeatedElement {
         model: super::lower_expression::lower_expression(&repeated.model, ctx).into(),
         sub_tree: ItemTree {
@@ -441,6 +476,7 @@ fn lower_repeated_component(elem: &ElementRc, ctx: &ExpressionContext) -> Repeat
         },
         index_prop: (!repeated.is_conditional_element).then(|| 1),
         data_prop: (!repeated.is_conditional_element).then(|| 0),
+        data_field_props,
         index_in_tree: *e.item_index.get().unwrap(),
         listview,
     }
@@ -527,6 +563,7 @@ fn lower_global(
         init_values[property_index] = Some(BindingExpression {
             expression,
             animation: None,
+            animation_finished: None,
             is_constant,
             is_state_info: false,
             use_count: 0.into(),
@@ -626,7 +663,7 @@ fn public_properties(
         .map(|(p, c)| {
             let property_reference = mapping
                 .map_property_reference(&NamedReference::new(&component.root_element, p), state);
-            (p.clone(), (c.property_type.clone(), property_reference))
+            (p.clone(), (c.public_api_type(), property_reference))
         })
         .collect()
 }
@@ -43,11 +43,17 @@ pub fn count_property_use(root: &PublicComponent) {
             }
             expr.use_count.set(c + 1);
             expr.expression.borrow().visit_recursive(&mut |e| visit_expression(e, ctx));
+            if let Some(finished) = &expr.animation_finished {
+                finished.visit_recursive(&mut |e| visit_expression(e, ctx));
+            }
         }
-        // 3. the init code
+        // 3. the init code and the code run when an animation is finished
         for expr in &sc.init_code {
             expr.borrow().visit_recursive(&mut |e| visit_expression(e, ctx));
         }
+        for expr in sc.animation_finished_handlers.values() {
+            expr.visit_recursive(&mut |e| visit_expression(e, ctx));
+        }
         // 4. the models
         for (idx, r) in sc.repeated.iter().enumerate() {
             r.model.borrow().visit_recursive(&mut |e| visit_expression(e, ctx));
@@ -97,7 +103,10 @@ fn visit_property(pr: &PropertyReference, ctx: &EvaluationContext) {
         binding.use_count.set(c + 1);
         if c == 0 {
             let ctx2 = map.map_context(ctx);
-            binding.expression.borrow().visit_recursive(&mut |e| visit_expression(e, &ctx2))
+            binding.expression.borrow().visit_recursive(&mut |e| visit_expression(e, &ctx2));
+            if let Some(finished) = &binding.animation_finished {
+                finished.visit_recursive(&mut |e| visit_expression(e, &ctx2));
+            }
         }
     }
 }
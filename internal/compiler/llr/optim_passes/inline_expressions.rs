@@ -44,6 +44,7 @@ fn expression_cost(exp: &Expression, ctx: &EvaluationContext) -> isize {
         Expression::ImageReference { .. } => 1,
         Expression::Condition { .. } => 10,
         Expression::Array { .. } => ALLOC_COST,
+        Expression::Map { .. } => ALLOC_COST,
         Expression::Struct { .. } => 1,
         Expression::EasingCurve(_) => 1,
         Expression::LinearGradient { .. } => ALLOC_COST,
@@ -67,8 +68,15 @@ fn callback_cost(_callback: &crate::llr::PropertyReference, _ctx: &EvaluationCon
 fn builtin_function_cost(function: BuiltinFunction) -> isize {
     match function {
         BuiltinFunction::GetWindowScaleFactor => PROPERTY_ACCESS_COST,
+        BuiltinFunction::GetWindowBaseFontSize => PROPERTY_ACCESS_COST,
+        BuiltinFunction::GetWindowViewportWidth => PROPERTY_ACCESS_COST,
+        BuiltinFunction::GetWindowViewportHeight => PROPERTY_ACCESS_COST,
+        BuiltinFunction::AnimationTick => PROPERTY_ACCESS_COST,
+        BuiltinFunction::HighContrast => PROPERTY_ACCESS_COST,
+        BuiltinFunction::ReducedMotion => PROPERTY_ACCESS_COST,
         BuiltinFunction::Debug => isize::MAX,
         BuiltinFunction::Mod => 10,
+        BuiltinFunction::ModFloat => 10,
         BuiltinFunction::Round => 10,
         BuiltinFunction::Ceil => 10,
         BuiltinFunction::Floor => 10,
@@ -80,17 +88,31 @@ fn builtin_function_cost(function: BuiltinFunction) -> isize {
         BuiltinFunction::ACos => 10,
         BuiltinFunction::ASin => 10,
         BuiltinFunction::ATan => 10,
+        BuiltinFunction::ATan2 => 10,
         BuiltinFunction::Log => 10,
         BuiltinFunction::Pow => 10,
+        BuiltinFunction::Exp => 10,
         BuiltinFunction::SetFocusItem => isize::MAX,
         BuiltinFunction::ShowPopupWindow => isize::MAX,
+        BuiltinFunction::ClosePopupWindow => isize::MAX,
         BuiltinFunction::StringToFloat => 50,
         BuiltinFunction::StringIsFloat => 50,
+        BuiltinFunction::StringToUppercase => 50,
+        BuiltinFunction::StringToLowercase => 50,
+        BuiltinFunction::StringTrim => 50,
+        BuiltinFunction::StringSplit => ALLOC_COST,
+        BuiltinFunction::StringReplace => 50,
+        BuiltinFunction::StringStartsWith => 50,
+        BuiltinFunction::StringSlice => 50,
         BuiltinFunction::ColorBrighter => 50,
         BuiltinFunction::ColorDarker => 50,
+        BuiltinFunction::ColorMix => 50,
+        BuiltinFunction::ColorWithAlpha => 50,
         BuiltinFunction::ImageSize => 50,
         BuiltinFunction::ArrayLength => 50,
+        BuiltinFunction::ArrayFindStartingWith => 50,
         BuiltinFunction::Rgb => 50,
+        BuiltinFunction::Hsv => 50,
         BuiltinFunction::ImplicitLayoutInfo(_) => isize::MAX,
         BuiltinFunction::RegisterCustomFontByPath => isize::MAX,
         BuiltinFunction::RegisterCustomFontByMemory => isize::MAX,
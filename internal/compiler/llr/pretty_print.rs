@@ -181,6 +181,12 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
             Expression::Array { values, .. } => {
                 write!(f, "[{}]", values.iter().map(e).join(", "))
             }
+            Expression::Map { entries, .. } if entries.is_empty() => f.write_str("[:]"),
+            Expression::Map { entries, .. } => write!(
+                f,
+                "[{}]",
+                entries.iter().map(|(k, v)| format!("{}: {}", e(k), e(v))).join(", ")
+            ),
             Expression::Struct { values, .. } => write!(
                 f,
                 "{{ {} }}",
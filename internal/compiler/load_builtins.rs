@@ -165,6 +165,7 @@ enum Base {
             parse_annotation("disallow_global_types_as_child_elements", &e).is_some();
         builtin.is_non_item_type = parse_annotation("is_non_item_type", &e).is_some();
         builtin.is_internal = parse_annotation("is_internal", &e).is_some();
+        builtin.deprecated = parse_annotation("deprecated", &e).map(Option::unwrap_or_default);
         builtin.accepts_focus = parse_annotation("accepts_focus", &e).is_some();
         builtin.default_size_binding = parse_annotation("default_size_binding", &e)
             .map(|size_type| match size_type.as_deref() {
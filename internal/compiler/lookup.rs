@@ -82,6 +82,7 @@ pub enum BuiltinNamespace {
     Colors,
     Math,
     Keys,
+    Defines,
 }
 
 impl From<Expression> for LookupResult {
@@ -144,6 +145,9 @@ fn for_each_entry<R>(
             }
             LookupResult::Namespace(BuiltinNamespace::Math) => MathFunctions.for_each_entry(ctx, f),
             LookupResult::Namespace(BuiltinNamespace::Keys) => KeysLookup.for_each_entry(ctx, f),
+            LookupResult::Namespace(BuiltinNamespace::Defines) => {
+                DefinesLookup.for_each_entry(ctx, f)
+            }
         }
     }
 
@@ -156,6 +160,7 @@ fn lookup(&self, ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
             }
             LookupResult::Namespace(BuiltinNamespace::Math) => MathFunctions.lookup(ctx, name),
             LookupResult::Namespace(BuiltinNamespace::Keys) => KeysLookup.lookup(ctx, name),
+            LookupResult::Namespace(BuiltinNamespace::Defines) => DefinesLookup.lookup(ctx, name),
         }
     }
 }
@@ -524,6 +529,7 @@ fn for_each_entry<R>(
         let sl = || t.as_ref().map(|t| t.to_source_location());
         let mut f = |n, e: Expression| f(n, e.into());
         None.or_else(|| f("mod", BuiltinFunctionReference(BuiltinFunction::Mod, sl())))
+            .or_else(|| f("fmod", BuiltinFunctionReference(BuiltinFunction::ModFloat, sl())))
             .or_else(|| f("round", BuiltinFunctionReference(BuiltinFunction::Round, sl())))
             .or_else(|| f("ceil", BuiltinFunctionReference(BuiltinFunction::Ceil, sl())))
             .or_else(|| f("floor", BuiltinFunctionReference(BuiltinFunction::Floor, sl())))
@@ -531,14 +537,17 @@ fn for_each_entry<R>(
             .or_else(|| f("sqrt", BuiltinFunctionReference(BuiltinFunction::Sqrt, sl())))
             .or_else(|| f("max", BuiltinMacroReference(BuiltinMacroFunction::Max, t.clone())))
             .or_else(|| f("min", BuiltinMacroReference(BuiltinMacroFunction::Min, t.clone())))
+            .or_else(|| f("clamp", BuiltinMacroReference(BuiltinMacroFunction::Clamp, t.clone())))
             .or_else(|| f("sin", BuiltinFunctionReference(BuiltinFunction::Sin, sl())))
             .or_else(|| f("cos", BuiltinFunctionReference(BuiltinFunction::Cos, sl())))
             .or_else(|| f("tan", BuiltinFunctionReference(BuiltinFunction::Tan, sl())))
             .or_else(|| f("asin", BuiltinFunctionReference(BuiltinFunction::ASin, sl())))
             .or_else(|| f("acos", BuiltinFunctionReference(BuiltinFunction::ACos, sl())))
             .or_else(|| f("atan", BuiltinFunctionReference(BuiltinFunction::ATan, sl())))
+            .or_else(|| f("atan2", BuiltinFunctionReference(BuiltinFunction::ATan2, sl())))
             .or_else(|| f("log", BuiltinFunctionReference(BuiltinFunction::Log, sl())))
             .or_else(|| f("pow", BuiltinFunctionReference(BuiltinFunction::Pow, sl())))
+            .or_else(|| f("exp", BuiltinFunctionReference(BuiltinFunction::Exp, sl())))
     }
 }
 
@@ -554,6 +563,8 @@ fn for_each_entry<R>(
         let mut f = |n, e: Expression| f(n, e.into());
         None.or_else(|| f("rgb", BuiltinMacroReference(BuiltinMacroFunction::Rgb, t.clone())))
             .or_else(|| f("rgba", BuiltinMacroReference(BuiltinMacroFunction::Rgb, t.clone())))
+            .or_else(|| f("hsv", BuiltinMacroReference(BuiltinMacroFunction::Hsv, t.clone())))
+            .or_else(|| f("hsva", BuiltinMacroReference(BuiltinMacroFunction::Hsv, t.clone())))
     }
 }
 
@@ -564,16 +575,48 @@ fn for_each_entry<R>(
         ctx: &LookupCtx,
         f: &mut impl FnMut(&str, LookupResult) -> Option<R>,
     ) -> Option<R> {
-        (MathFunctions, ColorFunctions).for_each_entry(ctx, f).or_else(|| {
-            f(
-                "debug",
-                Expression::BuiltinMacroReference(
-                    BuiltinMacroFunction::Debug,
-                    ctx.current_token.clone(),
+        (MathFunctions, ColorFunctions)
+            .for_each_entry(ctx, f)
+            .or_else(|| {
+                f(
+                    "debug",
+                    Expression::BuiltinMacroReference(
+                        BuiltinMacroFunction::Debug,
+                        ctx.current_token.clone(),
+                    )
+                    .into(),
                 )
-                .into(),
-            )
-        })
+            })
+            .or_else(|| {
+                f(
+                    "animation-tick",
+                    Expression::BuiltinFunctionReference(
+                        BuiltinFunction::AnimationTick,
+                        ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                    )
+                    .into(),
+                )
+            })
+            .or_else(|| {
+                f(
+                    "high-contrast",
+                    Expression::BuiltinFunctionReference(
+                        BuiltinFunction::HighContrast,
+                        ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                    )
+                    .into(),
+                )
+            })
+            .or_else(|| {
+                f(
+                    "reduced-motion",
+                    Expression::BuiltinFunctionReference(
+                        BuiltinFunction::ReducedMotion,
+                        ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                    )
+                    .into(),
+                )
+            })
     }
 }
 
@@ -587,6 +630,29 @@ fn for_each_entry<R>(
         None.or_else(|| f("Colors", LookupResult::Namespace(BuiltinNamespace::Colors)))
             .or_else(|| f("Math", LookupResult::Namespace(BuiltinNamespace::Math)))
             .or_else(|| f("Keys", LookupResult::Namespace(BuiltinNamespace::Keys)))
+            .or_else(|| f("Defines", LookupResult::Namespace(BuiltinNamespace::Defines)))
+    }
+}
+
+/// The constants from the `defines` of the CompilerConfiguration
+struct DefinesLookup;
+impl LookupObject for DefinesLookup {
+    fn for_each_entry<R>(
+        &self,
+        ctx: &LookupCtx,
+        f: &mut impl FnMut(&str, LookupResult) -> Option<R>,
+    ) -> Option<R> {
+        for (name, value) in &ctx.type_loader?.compiler_config.defines {
+            let expression = match value {
+                crate::DefineValue::String(s) => Expression::StringLiteral(s.clone()),
+                crate::DefineValue::Number(n) => Expression::NumberLiteral(*n, Unit::None),
+                crate::DefineValue::Bool(b) => Expression::BoolLiteral(*b),
+            };
+            if let Some(r) = f(&crate::parser::normalize_identifier(name), expression.into()) {
+                return Some(r);
+            }
+        }
+        None
     }
 }
 
@@ -683,6 +749,13 @@ fn for_each_entry<R>(
         };
         None.or_else(|| f("is-float", member_function(BuiltinFunction::StringIsFloat)))
             .or_else(|| f("to-float", member_function(BuiltinFunction::StringToFloat)))
+            .or_else(|| f("to-uppercase", member_function(BuiltinFunction::StringToUppercase)))
+            .or_else(|| f("to-lowercase", member_function(BuiltinFunction::StringToLowercase)))
+            .or_else(|| f("trim", member_function(BuiltinFunction::StringTrim)))
+            .or_else(|| f("split", member_function(BuiltinFunction::StringSplit)))
+            .or_else(|| f("replace", member_function(BuiltinFunction::StringReplace)))
+            .or_else(|| f("starts-with", member_function(BuiltinFunction::StringStartsWith)))
+            .or_else(|| f("slice", member_function(BuiltinFunction::StringSlice)))
     }
 }
 struct ColorExpression<'a>(&'a Expression);
@@ -704,6 +777,8 @@ fn for_each_entry<R>(
         };
         None.or_else(|| f("brighter", member_function(BuiltinFunction::ColorBrighter)))
             .or_else(|| f("darker", member_function(BuiltinFunction::ColorDarker)))
+            .or_else(|| f("mix", member_function(BuiltinFunction::ColorMix)))
+            .or_else(|| f("with-alpha", member_function(BuiltinFunction::ColorWithAlpha)))
     }
 }
 
@@ -749,6 +824,31 @@ fn for_each_entry<R>(
                 arguments: vec![self.0.clone()],
             })
         };
-        None.or_else(|| f("length", member_function(BuiltinFunction::ArrayLength)))
+        let method = |f: BuiltinFunction| {
+            LookupResult::from(Expression::MemberFunction {
+                base: Box::new(self.0.clone()),
+                base_node: ctx.current_token.clone(), // Note that this is not the base_node, but the function's node
+                member: Box::new(Expression::BuiltinFunctionReference(
+                    f,
+                    ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                )),
+            })
+        };
+        // find-starting-with() needs a text to compare: the rows are strings or have a text field
+        let has_text = match self.0.ty() {
+            Type::Array(ty) => match &*ty {
+                Type::String => true,
+                Type::Struct { fields, .. } => fields.get("text") == Some(&Type::String),
+                _ => false,
+            },
+            _ => false,
+        };
+        None.or_else(|| f("length", member_function(BuiltinFunction::ArrayLength))).or_else(|| {
+            if has_text {
+                f("find-starting-with", method(BuiltinFunction::ArrayFindStartingWith))
+            } else {
+                None
+            }
+        })
     }
 }
@@ -10,7 +10,7 @@
 use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
 use crate::expression_tree::{self, BindingExpression, Expression, Unit};
 use crate::langtype::PropertyLookupResult;
-use crate::langtype::{BuiltinElement, NativeClass, Type};
+use crate::langtype::{BuiltinElement, Enumeration, NativeClass, Type};
 use crate::layout::{LayoutConstraints, Orientation};
 use crate::namedreference::NamedReference;
 use crate::parser;
@@ -41,6 +41,10 @@ pub struct Document {
     pub inner_components: Vec<Rc<Component>>,
     pub inner_structs: Vec<Type>,
     pub root_component: Rc<Component>,
+    /// The other exported components, with their exported name, when they are compiled too
+    /// (see [`crate::CompilerConfiguration::all_exported_components`]). The root element of each
+    /// of these components is an instance of the exported component.
+    pub other_roots: Vec<(String, Rc<Component>)>,
     pub local_registry: TypeRegister,
     /// A list of paths to .ttf/.ttc files that are supposed to be registered on
     /// startup for custom font use.
@@ -83,6 +87,40 @@ pub fn from_node(
                 local_registry.insert_type(ty.clone());
                 inner_structs.push(ty);
             };
+        let process_enum = |n: syntax_nodes::EnumDeclaration,
+                            diag: &mut BuildDiagnostics,
+                            local_registry: &mut TypeRegister| {
+            let name = match parser::identifier_text(&n.DeclaredIdentifier()) {
+                Some(name) => name,
+                None => {
+                    assert!(diag.has_error());
+                    return;
+                }
+            };
+            let mut values = Vec::<String>::new();
+            for value in n.EnumValue() {
+                let value_name = unwrap_or_continue!(parser::identifier_text(&value); diag);
+                if values.contains(&value_name) {
+                    diag.push_error(format!("Duplicated enum value '{}'", value_name), &value);
+                    continue;
+                }
+                values.push(value_name);
+            }
+            if values.is_empty() {
+                diag.push_error(
+                    format!("The enum '{}' must have at least one value", name),
+                    &n.DeclaredIdentifier(),
+                );
+                return;
+            }
+            let ty = Type::Enumeration(Rc::new(Enumeration {
+                name: name.clone(),
+                values,
+                default_value: 0,
+                node: Some(n),
+            }));
+            local_registry.insert_type_with_name(ty, name);
+        };
 
         for n in node.children() {
             match n.kind() {
@@ -90,6 +128,7 @@ pub fn from_node(
                 SyntaxKind::StructDeclaration => {
                     process_struct(n.into(), diag, &mut local_registry)
                 }
+                SyntaxKind::EnumDeclaration => process_enum(n.into(), diag, &mut local_registry),
                 SyntaxKind::ExportsList => {
                     for n in n.children() {
                         match n.kind() {
@@ -99,6 +138,9 @@ pub fn from_node(
                             SyntaxKind::StructDeclaration => {
                                 process_struct(n.into(), diag, &mut local_registry)
                             }
+                            SyntaxKind::EnumDeclaration => {
+                                process_enum(n.into(), diag, &mut local_registry)
+                            }
                             _ => {}
                         }
                     }
@@ -132,10 +174,10 @@ pub fn from_node(
                     || import.file.ends_with(".ttf")
                     || import.file.ends_with(".otf")
                 {
-                    // Assume remote urls are valid, we need to load them at run-time (which we currently don't). For
-                    // local paths we should try to verify the existence and let the developer know ASAP.
-                    if import.file.starts_with("http://")
-                        || import.file.starts_with("https://")
+                    // Assume urls are valid, such as the remote ones or the files of the bundles loaded by the
+                    // interpreter, we need to load them at run-time. For local paths we should try to verify
+                    // the existence and let the developer know ASAP.
+                    if import.file.contains("://")
                         || crate::fileaccess::load_file(std::path::Path::new(&import.file))
                             .is_some()
                     {
@@ -160,6 +202,7 @@ pub fn from_node(
         Document {
             node: Some(node),
             root_component,
+            other_roots: Vec::new(),
             inner_components,
             inner_structs,
             local_registry,
@@ -168,6 +211,11 @@ pub fn from_node(
         }
     }
 
+    /// The root component followed by the other roots
+    pub fn root_components(&self) -> impl Iterator<Item = &Rc<Component>> {
+        std::iter::once(&self.root_component).chain(self.other_roots.iter().map(|(_, c)| c))
+    }
+
     pub fn exports(&self) -> &Vec<(ExportedName, Type)> {
         &self.exports.0
     }
@@ -181,7 +229,7 @@ pub struct PopupWindow {
     pub parent_element: ElementRc,
 }
 
-type ChildrenInsertionPoint = (ElementRc, syntax_nodes::ChildrenPlaceholder);
+pub type ChildrenInsertionPoint = (ElementRc, syntax_nodes::ChildrenPlaceholder);
 
 /// Used sub types for a root component
 #[derive(Debug, Default)]
@@ -190,6 +238,8 @@ pub struct UsedSubTypes {
     pub globals: Vec<Rc<Component>>,
     /// All the structs used by the component and its children.
     pub structs: Vec<Type>,
+    /// All the enums declared in .slint that are used by the component and its children.
+    pub enums: Vec<Rc<Enumeration>>,
     /// All the sub components use by this components and its children,
     /// and the amount of time it is used
     pub sub_components: Vec<Rc<Component>>,
@@ -215,6 +265,11 @@ pub struct Component {
     pub embedded_file_resources:
         RefCell<HashMap<String, crate::embedded_resources::EmbeddedResources>>,
 
+    /// The files used to build this component: the main file, the imported files, and the
+    /// images and fonts, sorted by path. The builtin files are not listed.
+    /// (This only make sense on the root component)
+    pub dependencies: RefCell<Vec<std::path::PathBuf>>,
+
     /// The layout constraints of the root item
     pub root_constraints: RefCell<LayoutConstraints>,
 
@@ -222,6 +277,10 @@ pub struct Component {
     /// the element pointer to by this field.
     pub child_insertion_point: RefCell<Option<ChildrenInsertionPoint>>,
 
+    /// The `@children(name)` placeholders, indexed by name. The children placed in a `@slot(name)`
+    /// are appended to the children of the element of the placeholder with the same name.
+    pub named_child_insertion_points: RefCell<BTreeMap<String, ChildrenInsertionPoint>>,
+
     /// Code to be inserted into the constructor
     pub setup_code: RefCell<Vec<Expression>>,
 
@@ -246,17 +305,21 @@ pub fn from_node(
         tr: &TypeRegister,
     ) -> Rc<Self> {
         let mut child_insertion_point = None;
+        let root_element = Element::from_node(
+            node.Element(),
+            "root".into(),
+            Type::Invalid,
+            &mut child_insertion_point,
+            diag,
+            tr,
+        );
+        let named_child_insertion_points =
+            collect_named_child_insertion_points(&root_element, diag);
         let c = Component {
             id: parser::identifier_text(&node.DeclaredIdentifier()).unwrap_or_default(),
-            root_element: Element::from_node(
-                node.Element(),
-                "root".into(),
-                Type::Invalid,
-                &mut child_insertion_point,
-                diag,
-                tr,
-            ),
+            root_element,
             child_insertion_point: RefCell::new(child_insertion_point),
+            named_child_insertion_points: RefCell::new(named_child_insertion_points),
             ..Default::default()
         };
         let c = Rc::new(c);
@@ -267,6 +330,22 @@ pub fn from_node(
         c
     }
 
+    /// Create a root component whose root element is an instance of `component`, so that
+    /// `component` can be compiled as a root even if other components use it
+    pub fn new_root(component: &Rc<Component>) -> Rc<Self> {
+        let c = Rc::new(Component {
+            id: component.id.clone(),
+            root_element: Rc::new(RefCell::new(Element {
+                base_type: Type::Component(component.clone()),
+                node: component.root_element.borrow().node.clone(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        });
+        c.root_element.borrow_mut().enclosing_component = Rc::downgrade(&c);
+        c
+    }
+
     /// This component is a global component introduced with the "global" keyword
     pub fn is_global(&self) -> bool {
         match &self.root_element.borrow().base_type {
@@ -322,6 +401,12 @@ pub fn repeater_count(&self) -> u32 {
     }
 }
 
+/// The name of the property of the root element that overrides `property` of the element with
+/// the id `id`, when it is one of the [`crate::CompilerConfiguration::overridable_elements`]
+pub fn element_override_property_name(id: &str, property: &str) -> String {
+    format!("{}-override-{}", id, property)
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct PropertyDeclaration {
     pub property_type: Type,
@@ -330,9 +415,24 @@ pub struct PropertyDeclaration {
     pub expose_in_public_api: bool,
     /// Public API property exposed as an alias: it shouldn't be generated but instead forward to the alias.
     pub is_alias: Option<NamedReference>,
+    /// This is a function declared with `function`. It is implemented as a callback whose handler
+    /// is the body of the function, and that handler cannot be replaced.
+    pub is_function: bool,
 }
 
 impl PropertyDeclaration {
+    /// The type as seen from the public API, where functions are a `Type::Function` rather than a
+    /// `Type::Callback`, since they can only be invoked.
+    pub fn public_api_type(&self) -> Type {
+        match &self.property_type {
+            Type::Callback { return_type, args } if self.is_function => Type::Function {
+                return_type: Box::new(return_type.as_ref().map_or(Type::Void, |t| (**t).clone())),
+                args: args.clone(),
+            },
+            ty => ty.clone(),
+        }
+    }
+
     // For diagnostics: return a node pointing to the type
     pub fn type_node(&self) -> Option<SyntaxNode> {
         self.node.as_ref().map(|x| -> crate::parser::SyntaxNode {
@@ -467,6 +567,10 @@ pub struct Element {
     /// How many times the element was inlined
     pub inline_depth: i32,
 
+    /// The name of the `@children(name)` placeholder of the base type of the parent in which
+    /// this element is placed, when it is declared in a `@slot(name)`. Reset when the parent is inlined.
+    pub child_slot: Option<String>,
+
     /// The AST node, if available
     pub node: Option<syntax_nodes::Element>,
 }
@@ -603,6 +707,9 @@ pub struct ListViewInfo {
     pub listview_height: NamedReference,
     /// The ListView's inner visible width (not counting eventual scrollbar)
     pub listview_width: NamedReference,
+    /// The ListView's `row-count` property, if it declares one, which is bound to the number of
+    /// rows of the model unless it has a binding
+    pub row_count: Option<NamedReference>,
 }
 
 #[derive(Debug, Clone)]
@@ -617,6 +724,16 @@ pub struct RepeatedElementInfo {
     pub is_conditional_element: bool,
     /// When the for is the delegate of a ListView
     pub is_listview: Option<ListViewInfo>,
+    /// When this is the placeholder of a `ComponentContainer`
+    ///
+    /// The model is then of type `component-factory`, and the component instantiated
+    /// at runtime is the one created by the factory.
+    pub is_component_container: bool,
+    /// The id of the `ComponentContainer` in the .slint file, by which the interpreter API
+    /// inserts and removes children at runtime
+    ///
+    /// This is only set for the named containers of the main component.
+    pub component_container_id: Option<String>,
 }
 
 pub type ElementRc = Rc<RefCell<Element>>;
@@ -664,9 +781,8 @@ pub fn from_node(
             };
             node.SubElement().for_each(|n| error_on(&n, "sub elements"));
             node.RepeatedElement().for_each(|n| error_on(&n, "sub elements"));
-            if let Some(n) = node.ChildrenPlaceholder() {
-                error_on(&n, "sub elements");
-            }
+            node.ChildrenPlaceholder().for_each(|n| error_on(&n, "sub elements"));
+            node.ChildrenSlot().for_each(|n| error_on(&n, "sub elements"));
             node.PropertyAnimation().for_each(|n| error_on(&n, "animations"));
             node.States().for_each(|n| error_on(&n, "states"));
             node.Transitions().for_each(|n| error_on(&n, "transitions"));
@@ -776,46 +892,35 @@ pub fn from_node(
             );
         }
 
-        for con_node in node.CallbackConnection() {
-            let unresolved_name = unwrap_or_continue!(parser::identifier_text(&con_node); diag);
-            let PropertyLookupResult { resolved_name, property_type } =
-                r.lookup_property(&unresolved_name);
-            if let Type::Callback { args, .. } = &property_type {
-                let num_arg = con_node.DeclaredIdentifier().count();
-                if num_arg > args.len() {
-                    diag.push_error(
-                        format!(
-                            "'{}' only has {} arguments, but {} were provided",
-                            unresolved_name,
-                            args.len(),
-                            num_arg
-                        ),
-                        &con_node.child_token(SyntaxKind::Identifier).unwrap(),
-                    );
-                }
-            } else if property_type == Type::InferredCallback {
-                // argument matching will happen later
-            } else {
+        for func in node.Function() {
+            let name =
+                unwrap_or_continue!(parser::identifier_text(&func.DeclaredIdentifier()); diag);
+            let args = func
+                .ArgumentDeclaration()
+                .map(|arg| type_from_node(arg.Type(), diag, tr))
+                .collect();
+            let return_type =
+                func.ReturnType().map(|ret_ty| Box::new(type_from_node(ret_ty.Type(), diag, tr)));
+            if r.lookup_property(&name).property_type != Type::Invalid {
                 diag.push_error(
-                    format!("'{}' is not a callback in {}", unresolved_name, r.base_type),
-                    &con_node.child_token(SyntaxKind::Identifier).unwrap(),
+                    format!("Cannot override '{}' with a function", name),
+                    &func.DeclaredIdentifier(),
                 );
                 continue;
             }
-            if r.bindings
-                .insert(
-                    resolved_name.into_owned(),
-                    BindingExpression::new_uncompiled(con_node.clone().into()).into(),
-                )
-                .is_some()
-            {
-                diag.push_error(
-                    "Duplicated callback".into(),
-                    &con_node.child_token(SyntaxKind::Identifier).unwrap(),
-                );
-            }
+            r.property_declarations.insert(
+                name.clone(),
+                PropertyDeclaration {
+                    property_type: Type::Callback { return_type, args },
+                    is_function: true,
+                    ..Default::default()
+                },
+            );
+            r.bindings.insert(name, BindingExpression::new_uncompiled(func.into()).into());
         }
 
+        r.parse_callback_connections(node.CallbackConnection(), diag);
+
         for anim in node.PropertyAnimation() {
             if let Some(star) = anim.child_token(SyntaxKind::Star) {
                 diag.push_error(
@@ -872,34 +977,41 @@ pub fn from_node(
         let r = ElementRc::new(RefCell::new(r));
 
         for se in node.children() {
-            if se.kind() == SyntaxKind::SubElement {
-                let parent_type = r.borrow().base_type.clone();
-                r.borrow_mut().children.push(Element::from_sub_element_node(
-                    se.into(),
-                    parent_type,
-                    component_child_insertion_point,
-                    diag,
-                    tr,
-                ));
-            } else if se.kind() == SyntaxKind::RepeatedElement {
-                let rep = Element::from_repeated_node(
-                    se.into(),
-                    &r,
-                    component_child_insertion_point,
-                    diag,
-                    tr,
-                );
-                r.borrow_mut().children.push(rep);
-            } else if se.kind() == SyntaxKind::ConditionalElement {
-                let rep = Element::from_conditional_node(
-                    se.into(),
-                    r.borrow().base_type.clone(),
-                    component_child_insertion_point,
-                    diag,
-                    tr,
-                );
-                r.borrow_mut().children.push(rep);
+            if se.kind() == SyntaxKind::ChildrenSlot {
+                let slot = syntax_nodes::ChildrenSlot::from(se);
+                let name =
+                    unwrap_or_continue!(parser::identifier_text(&slot.DeclaredIdentifier()); diag);
+                let base_type = r.borrow().base_type.clone();
+                let has_slot = matches!(&base_type, Type::Component(c)
+                    if c.named_child_insertion_points.borrow().contains_key(&name));
+                if !has_slot && base_type != Type::Invalid {
+                    diag.push_error(
+                        format!("'{}' does not have a @children({}) placeholder", base_type, name),
+                        &slot.DeclaredIdentifier(),
+                    );
+                }
+                for child in slot.children() {
+                    if let Some(child) = Element::from_child_node(
+                        child,
+                        &r,
+                        component_child_insertion_point,
+                        diag,
+                        tr,
+                    ) {
+                        if has_slot {
+                            child.borrow_mut().child_slot = Some(name.clone());
+                        }
+                        r.borrow_mut().children.push(child);
+                    }
+                }
             } else if se.kind() == SyntaxKind::ChildrenPlaceholder {
+                if syntax_nodes::ChildrenPlaceholder::from(se.clone())
+                    .DeclaredIdentifier()
+                    .is_some()
+                {
+                    // Named placeholders are collected by collect_named_child_insertion_points
+                    continue;
+                }
                 if children_placeholder.is_some() {
                     diag.push_error(
                         "The @children placeholder can only appear once in an element".into(),
@@ -908,6 +1020,10 @@ pub fn from_node(
                 } else {
                     children_placeholder = Some(se.clone().into());
                 }
+            } else if let Some(child) =
+                Element::from_child_node(se, &r, component_child_insertion_point, diag, tr)
+            {
+                r.borrow_mut().children.push(child);
             }
         }
 
@@ -965,6 +1081,42 @@ pub fn from_node(
         r
     }
 
+    /// Create the element for a SubElement, RepeatedElement or ConditionalElement node.
+    /// Returns None for other nodes.
+    fn from_child_node(
+        node: SyntaxNode,
+        parent: &ElementRc,
+        component_child_insertion_point: &mut Option<ChildrenInsertionPoint>,
+        diag: &mut BuildDiagnostics,
+        tr: &TypeRegister,
+    ) -> Option<ElementRc> {
+        let parent_type = parent.borrow().base_type.clone();
+        match node.kind() {
+            SyntaxKind::SubElement => Some(Element::from_sub_element_node(
+                node.into(),
+                parent_type,
+                component_child_insertion_point,
+                diag,
+                tr,
+            )),
+            SyntaxKind::RepeatedElement => Some(Element::from_repeated_node(
+                node.into(),
+                parent,
+                component_child_insertion_point,
+                diag,
+                tr,
+            )),
+            SyntaxKind::ConditionalElement => Some(Element::from_conditional_node(
+                node.into(),
+                parent_type,
+                component_child_insertion_point,
+                diag,
+                tr,
+            )),
+            _ => None,
+        }
+    }
+
     fn from_sub_element_node(
         node: syntax_nodes::SubElement,
         parent_type: Type,
@@ -1003,6 +1155,9 @@ fn from_repeated_node(
                 viewport_width: NamedReference::new(parent, "viewport-width"),
                 listview_height: NamedReference::new(parent, "visible-height"),
                 listview_width: NamedReference::new(parent, "visible-width"),
+                row_count: (parent.borrow().lookup_property("row-count").property_type
+                    == Type::Int32)
+                    .then(|| NamedReference::new(parent, "row-count")),
             })
         } else {
             None
@@ -1019,6 +1174,8 @@ fn from_repeated_node(
                 .unwrap_or_default(),
             is_conditional_element: false,
             is_listview,
+            is_component_container: false,
+            component_container_id: None,
         };
         let e = Element::from_sub_element_node(
             node.SubElement(),
@@ -1044,6 +1201,8 @@ fn from_conditional_node(
             index_id: String::new(),
             is_conditional_element: true,
             is_listview: None,
+            is_component_container: false,
+            component_container_id: None,
         };
         let e = Element::from_sub_element_node(
             node.SubElement(),
@@ -1071,6 +1230,60 @@ pub fn span(&self) -> crate::diagnostics::Span {
         self.node.as_ref().map(|n| n.span()).unwrap_or_default()
     }
 
+    fn parse_callback_connections(
+        &mut self,
+        nodes: impl Iterator<Item = syntax_nodes::CallbackConnection>,
+        diag: &mut BuildDiagnostics,
+    ) {
+        for con_node in nodes {
+            let unresolved_name = unwrap_or_continue!(parser::identifier_text(&con_node); diag);
+            let PropertyLookupResult { resolved_name, property_type } =
+                self.lookup_property(&unresolved_name);
+            if is_function(self, &resolved_name) {
+                diag.push_error(
+                    format!("Cannot set a handler for the function '{}'", unresolved_name),
+                    &con_node.child_token(SyntaxKind::Identifier).unwrap(),
+                );
+                continue;
+            }
+            if let Type::Callback { args, .. } = &property_type {
+                let num_arg = con_node.DeclaredIdentifier().count();
+                if num_arg > args.len() {
+                    diag.push_error(
+                        format!(
+                            "'{}' only has {} arguments, but {} were provided",
+                            unresolved_name,
+                            args.len(),
+                            num_arg
+                        ),
+                        &con_node.child_token(SyntaxKind::Identifier).unwrap(),
+                    );
+                }
+            } else if property_type == Type::InferredCallback {
+                // argument matching will happen later
+            } else {
+                diag.push_error(
+                    format!("'{}' is not a callback in {}", unresolved_name, self.base_type),
+                    &con_node.child_token(SyntaxKind::Identifier).unwrap(),
+                );
+                continue;
+            }
+            if self
+                .bindings
+                .insert(
+                    resolved_name.into_owned(),
+                    BindingExpression::new_uncompiled(con_node.clone().into()).into(),
+                )
+                .is_some()
+            {
+                diag.push_error(
+                    "Duplicated callback".into(),
+                    &con_node.child_token(SyntaxKind::Identifier).unwrap(),
+                );
+            }
+        }
+    }
+
     fn parse_bindings(
         &mut self,
         bindings: impl Iterator<Item = (crate::parser::SyntaxToken, SyntaxNode)>,
@@ -1218,6 +1431,17 @@ pub fn sub_component(&self) -> Option<&Rc<Component>> {
     }
 }
 
+/// Returns true if `name` is a function declared in the element or in the component it is based on
+fn is_function(element: &Element, name: &str) -> bool {
+    match element.property_declarations.get(name) {
+        Some(decl) => decl.is_function,
+        None => match &element.base_type {
+            Type::Component(c) => is_function(&c.root_element.borrow(), name),
+            _ => false,
+        },
+    }
+}
+
 /// Apply default property values defined in `builtins.60` to the element.
 fn apply_default_type_properties(element: &mut Element) {
     // Apply default property values on top:
@@ -1254,6 +1478,16 @@ pub fn type_from_node(
         type_struct_from_node(object_node, diag, tr)
     } else if let Some(array_node) = node.ArrayType() {
         Type::Array(Box::new(type_from_node(array_node.Type(), diag, tr)))
+    } else if let Some(map_node) = node.MapType() {
+        let (key_node, value_node) = map_node.Type();
+        let key_type = type_from_node(key_node.clone(), diag, tr);
+        if !matches!(key_type, Type::String | Type::Invalid) {
+            diag.push_error(
+                format!("The keys of a map must be of type string, not {}", key_type),
+                &key_node,
+            );
+        }
+        Type::Map(Box::new(type_from_node(value_node, diag, tr)))
     } else {
         assert!(diag.has_error());
         Type::Invalid
@@ -1304,6 +1538,7 @@ fn animation_element_from_node(
             }),
             diag,
         );
+        anim_element.parse_callback_connections(anim.CallbackConnection(), diag);
 
         apply_default_type_properties(&mut anim_element);
 
@@ -1415,6 +1650,37 @@ fn recurse(base: &ElementRc, e: &ElementRc) -> Option<ElementRc> {
 /// Call the visitor for each children of the element recursively, starting with the element itself
 ///
 /// The state returned by the visitor is passed to the children
+/// Find the `@children(name)` placeholders in the elements of a component
+fn collect_named_child_insertion_points(
+    root_element: &ElementRc,
+    diag: &mut BuildDiagnostics,
+) -> BTreeMap<String, ChildrenInsertionPoint> {
+    let mut result = BTreeMap::new();
+    recurse_elem(root_element, &(), &mut |elem, _| {
+        let node = elem.borrow().node.clone();
+        for placeholder in node.iter().flat_map(|n| n.ChildrenPlaceholder()) {
+            let name =
+                match placeholder.DeclaredIdentifier().and_then(|n| parser::identifier_text(&n)) {
+                    Some(name) => name,
+                    None => continue,
+                };
+            match result.entry(name) {
+                Entry::Occupied(e) => diag.push_error(
+                    format!(
+                        "The @children({}) placeholder can only appear once in an element hierarchy",
+                        e.key()
+                    ),
+                    &placeholder,
+                ),
+                Entry::Vacant(e) => {
+                    e.insert((elem.clone(), placeholder));
+                }
+            }
+        }
+    });
+    result
+}
+
 pub fn recurse_elem<State>(
     elem: &ElementRc,
     state: &State,
@@ -1609,6 +1875,9 @@ fn recurse_expression(expr: &mut Expression, vis: &mut impl FnMut(&mut NamedRefe
             vis(&mut lv.viewport_width);
             vis(&mut lv.listview_height);
             vis(&mut lv.listview_width);
+            if let Some(row_count) = &mut lv.row_count {
+                vis(row_count);
+            }
         }
     }
     elem.borrow_mut().repeated = repeated;
@@ -1760,19 +2029,26 @@ struct NamedExport {
             }),
         );
         let exports_it = exports_it.chain(
-            doc.ExportsList().flat_map(|exports| exports.StructDeclaration()).map(|st| {
-                let name_location: SyntaxNode = st.DeclaredIdentifier().into();
-                let name = parser::identifier_text(&st.DeclaredIdentifier()).unwrap_or_else(|| {
-                    debug_assert!(diag.has_error());
-                    String::new()
-                });
-                NamedExport {
-                    internal_name_ident: name_location.clone(),
-                    internal_name: name.clone(),
-                    external_name_ident: name_location,
-                    exported_name: name,
-                }
-            }),
+            doc.ExportsList()
+                .flat_map(|exports| {
+                    exports
+                        .StructDeclaration()
+                        .map(|st| st.DeclaredIdentifier())
+                        .chain(exports.EnumDeclaration().map(|en| en.DeclaredIdentifier()))
+                })
+                .map(|declared_identifier| {
+                    let name_location: SyntaxNode = declared_identifier.clone().into();
+                    let name = parser::identifier_text(&declared_identifier).unwrap_or_else(|| {
+                        debug_assert!(diag.has_error());
+                        String::new()
+                    });
+                    NamedExport {
+                        internal_name_ident: name_location.clone(),
+                        internal_name: name.clone(),
+                        external_name_ident: name_location,
+                        exported_name: name,
+                    }
+                }),
         );
 
         struct SeenExport {
@@ -1823,7 +2099,9 @@ struct SeenExport {
 
         let mut resolve_export_to_inner_component_or_import =
             |export: &NamedExport| match type_registry.lookup(export.internal_name.as_str()) {
-                ty @ Type::Component(_) | ty @ Type::Struct { .. } => Some(ty),
+                ty @ Type::Component(_) | ty @ Type::Struct { .. } | ty @ Type::Enumeration(_) => {
+                    Some(ty)
+                }
                 Type::Invalid => {
                     diag.push_error(
                         format!("'{}' not found", export.internal_name),
@@ -10,6 +10,36 @@
 
 This module has different sub modules with the actual parser functions
 
+## Usage from other tools
+
+The syntax tree is also the entry point for the tools that work on the source code, such as
+linters, code generators, or refactoring tools. [`parse()`] returns the untyped [`SyntaxNode`]
+of the document, which keeps all the tokens, including the whitespace and the comments, so that
+the original source can be reconstructed from it. The [`syntax_nodes`] module has a typed
+wrapper for each kind of node, with accessors for their children.
+
+```
+use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::parser::{identifier_text, parse, syntax_nodes, SyntaxKind};
+
+let mut diagnostics = BuildDiagnostics::default();
+let source = "export Hello := Text { text: \"hello\"; }".to_owned();
+let doc = syntax_nodes::Document::new(parse(source, None, &mut diagnostics)).unwrap();
+assert!(!diagnostics.has_error());
+
+let component = doc.ExportsList().next().unwrap().Component().unwrap();
+assert_eq!(identifier_text(&component.DeclaredIdentifier()).as_deref(), Some("Hello"));
+let bindings = component.descendants().filter(|n| n.kind() == SyntaxKind::Binding).count();
+assert_eq!(bindings, 1);
+```
+
+The parser recovers from the syntax errors: the tree is always complete and the errors are
+reported in the diagnostics.
+
+The names of the [`SyntaxKind`] variants and the children of the nodes are documented in the
+`declare_syntax!` invocation of this module. New kinds may be added in any release, so the
+matches on a [`SyntaxKind`] need a wildcard arm, and the numeric value of a kind is not stable.
+
 */
 
 use crate::diagnostics::{BuildDiagnostics, SourceFile, Spanned};
@@ -185,10 +215,14 @@ macro_rules! declare_syntax {
         $( $(#[$attr:meta])*  $nodekind:ident -> $children:tt ,)*
     })
     => {
+        /// The kind of the tokens and of the nodes of the syntax tree
         #[repr(u16)]
         #[derive(Debug, Copy, Clone, Eq, PartialEq, num_enum::IntoPrimitive, num_enum::TryFromPrimitive, Hash, Ord, PartialOrd)]
+        #[non_exhaustive]
         pub enum SyntaxKind {
+            /// Invalid token
             Error,
+            /// End of the file
             Eof,
 
             // Tokens:
@@ -204,6 +238,14 @@ pub enum SyntaxKind {
             )*
         }
 
+        impl SyntaxKind {
+            /// Returns true if this is the kind of a token (a leaf of the tree), and false if it
+            /// is the kind of a node
+            pub fn is_token(self) -> bool {
+                matches!(self, Self::Error | Self::Eof $(| Self::$token)*)
+            }
+        }
+
         impl Display for SyntaxKind {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
@@ -231,6 +273,10 @@ pub fn lex_next_token(text : &str, state: &mut crate::lexer::LexState) -> Option
             None
         }
 
+        /// Typed wrappers around the [`SyntaxNode`], one for each kind of node.
+        ///
+        /// The accessors are named after the kind of the children, and return an `Option` or an
+        /// `Iterator` depending on how many children of this kind the node can have.
         pub mod syntax_nodes {
             use super::*;
             use derive_more::*;
@@ -324,30 +370,45 @@ fn source_file(&self) -> Option<&SourceFile> {
     }
     // syntax kind
     {
-        Document -> [ *Component, *ExportsList, *ImportSpecifier, *StructDeclaration ],
+        Document -> [ *Component, *ExportsList, *ImportSpecifier, *StructDeclaration, *EnumDeclaration,
+                      *ConditionalBlock ],
         /// `DeclaredIdentifier := Element { ... }`
         Component -> [ DeclaredIdentifier, Element ],
         /// `id := Element { ... }`
         SubElement -> [ Element ],
         Element -> [ ?QualifiedName, *PropertyDeclaration, *Binding, *CallbackConnection,
-                     *CallbackDeclaration, *SubElement, *RepeatedElement, *PropertyAnimation,
-                     *TwoWayBinding, *States, *Transitions, ?ChildrenPlaceholder ],
+                     *CallbackDeclaration, *Function, *SubElement, *RepeatedElement, *PropertyAnimation,
+                     *TwoWayBinding, *States, *Transitions, *ChildrenPlaceholder, *ConditionalBlock,
+                     *ChildrenSlot ],
         RepeatedElement -> [ ?DeclaredIdentifier, ?RepeatedIndex, Expression , SubElement],
         RepeatedIndex -> [],
         ConditionalElement -> [ Expression , SubElement],
+        /// `@if (condition) { ... } @else { ... }`, resolved at compile time
+        ConditionalBlock -> [ Expression, ConditionalBlockContent, ?ConditionalBlockElse ],
+        /// The `{ ... }` of a ConditionalBlock. Contains element or document items depending on where it is.
+        ConditionalBlockContent -> _,
+        /// `@else { ... }` or `@else @if (...) { ... }`
+        ConditionalBlockElse -> [ ?ConditionalBlock, ?ConditionalBlockContent ],
         CallbackDeclaration -> [ DeclaredIdentifier, *Type, ?ReturnType, ?TwoWayBinding ],
         /// `-> type`  (but without the ->)
         ReturnType -> [Type],
+        /// `function foo(a: int) -> int { ... }`
+        Function -> [DeclaredIdentifier, *ArgumentDeclaration, ?ReturnType, CodeBlock ],
+        /// `a: int` in the arguments of a function
+        ArgumentDeclaration -> [DeclaredIdentifier, Type],
         CallbackConnection -> [ *DeclaredIdentifier,  CodeBlock ],
         /// Declaration of a property.
         PropertyDeclaration-> [ ?Type , DeclaredIdentifier, ?BindingExpression, ?TwoWayBinding ],
         /// QualifiedName are the properties name
-        PropertyAnimation-> [ *QualifiedName, *Binding ],
+        PropertyAnimation-> [ *QualifiedName, *Binding, *CallbackConnection ],
         /// wraps Identifiers, like `Rectangle` or `SomeModule.SomeType`
         QualifiedName-> [],
         /// Wraps single identifier (to disambiguate when there are other identifier in the production)
         DeclaredIdentifier -> [],
-        ChildrenPlaceholder -> [],
+        /// `@children` or `@children(name)`
+        ChildrenPlaceholder -> [ ?DeclaredIdentifier ],
+        /// `@slot(name) { ... }`: elements placed in the named `@children(name)` placeholder of the base component
+        ChildrenSlot -> [ DeclaredIdentifier, *SubElement, *RepeatedElement, *ConditionalElement ],
         Binding-> [ BindingExpression ],
         /// `xxx <=> something`
         TwoWayBinding -> [ Expression ],
@@ -358,7 +419,7 @@ fn source_file(&self) -> Option<&SourceFile> {
         ReturnStatement -> [ ?Expression ],
         // FIXME: the test should test that as alternative rather than several of them (but it can also be a literal)
         Expression-> [ ?Expression, ?FunctionCallExpression, ?IndexExpression, ?SelfAssignment,
-                       ?ConditionalExpression, ?QualifiedName, ?BinaryExpression, ?Array, ?ObjectLiteral,
+                       ?ConditionalExpression, ?QualifiedName, ?BinaryExpression, ?Array, ?MapLiteral, ?ObjectLiteral,
                        ?UnaryOpExpression, ?CodeBlock, ?StringTemplate, ?AtImageUrl, ?AtLinearGradient,
                        ?MemberAccess ],
         /// Concatenate the Expressions to make a string (usually expended from a template string)
@@ -383,6 +444,10 @@ fn source_file(&self) -> Option<&SourceFile> {
         MemberAccess -> [Expression],
         /// `[ ... ]`
         Array -> [ *Expression ],
+        /// `[ "foo": bar, ... ]` or `[:]`
+        MapLiteral -> [ *MapEntry ],
+        /// `"foo": bar` inside a MapLiteral: the first expression is the key
+        MapEntry -> [ 2 Expression ],
         /// `{ foo: bar }`
         ObjectLiteral -> [ *ObjectMember ],
         /// `foo: bar` inside an ObjectLiteral
@@ -398,7 +463,7 @@ fn source_file(&self) -> Option<&SourceFile> {
         /// There is an identifier "in" or "out", the DeclaredIdentifier is the state name
         Transition -> [DeclaredIdentifier, *PropertyAnimation],
         /// Export a set of declared components by name
-        ExportsList -> [ *ExportSpecifier, ?Component, *StructDeclaration ],
+        ExportsList -> [ *ExportSpecifier, ?Component, *StructDeclaration, *EnumDeclaration ],
         /// Declare the first identifier to be exported, either under its name or instead
         /// under the name of the second identifier.
         ExportSpecifier -> [ ExportIdentifier, ?ExportName ],
@@ -412,15 +477,21 @@ fn source_file(&self) -> Option<&SourceFile> {
         ExternalName -> [],
         InternalName -> [],
         /// The representation of a type
-        Type -> [ ?QualifiedName, ?ObjectType, ?ArrayType ],
+        Type -> [ ?QualifiedName, ?ObjectType, ?ArrayType, ?MapType ],
         /// `{foo: string, bar: string} `
         ObjectType ->[ *ObjectTypeMember ],
         /// `foo: type` inside an ObjectType
         ObjectTypeMember -> [ Type ],
         /// `[ type ]`
         ArrayType -> [ Type ],
+        /// `[ string: type ]`: the first type is the key, the second is the value
+        MapType -> [ 2 Type ],
         /// `struct Foo := { ... }
         StructDeclaration -> [DeclaredIdentifier, ObjectType],
+        /// `enum Foo { bar, baz }`
+        EnumDeclaration -> [DeclaredIdentifier, *EnumValue],
+        /// `bar` inside an EnumDeclaration
+        EnumValue -> [],
 
     }
 }
@@ -431,6 +502,7 @@ fn from(v: SyntaxKind) -> Self {
     }
 }
 
+/// A token produced by the lexer
 #[derive(Clone, Debug)]
 pub struct Token {
     pub kind: SyntaxKind,
@@ -562,6 +634,7 @@ fn deref(&self) -> &Self::Target {
 #[doc(inline)]
 pub use parser_trait::*;
 
+/// The parser that builds the syntax tree from the tokens
 pub struct DefaultParser<'a> {
     builder: rowan::GreenNodeBuilder<'static>,
     tokens: Vec<Token>,
@@ -663,6 +736,7 @@ fn checkpoint(&mut self) -> Self::Checkpoint {
     }
 }
 
+/// The [`rowan::Language`] of the syntax tree
 #[derive(Clone, Copy, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 pub enum Language {}
 impl rowan::Language for Language {
@@ -675,6 +749,9 @@ fn kind_to_raw(kind: Self::Kind) -> rowan::SyntaxKind {
     }
 }
 
+/// A node of the syntax tree, with the file it comes from.
+///
+/// It derefs to the [`rowan::SyntaxNode`], for example for its `text_range()`.
 #[derive(Debug, Clone, derive_more::Deref)]
 pub struct SyntaxNode {
     #[deref]
@@ -682,6 +759,7 @@ pub struct SyntaxNode {
     pub source_file: SourceFile,
 }
 
+/// A token of the syntax tree, with the file it comes from
 #[derive(Debug, Clone, derive_more::Deref)]
 pub struct SyntaxToken {
     #[deref]
@@ -690,9 +768,11 @@ pub struct SyntaxToken {
 }
 
 impl SyntaxToken {
+    /// The node that contains this token
     pub fn parent(&self) -> SyntaxNode {
         SyntaxNode { node: self.token.parent().unwrap(), source_file: self.source_file.clone() }
     }
+    /// The next token in the file, which can be in another node
     pub fn next_token(&self) -> Option<SyntaxToken> {
         // Due to a bug (as of rowan 0.15.3), rowan::SyntaxToken::next_token doesn't work if a
         // sibling don't have tokens.
@@ -727,12 +807,14 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 }
 
 impl SyntaxNode {
+    /// The first child node of this kind
     pub fn child_node(&self, kind: SyntaxKind) -> Option<SyntaxNode> {
         self.node
             .children()
             .find(|n| n.kind() == kind)
             .map(|node| SyntaxNode { node, source_file: self.source_file.clone() })
     }
+    /// The first child token of this kind
     pub fn child_token(&self, kind: SyntaxKind) -> Option<SyntaxToken> {
         self.node
             .children_with_tokens()
@@ -740,6 +822,7 @@ pub fn child_token(&self, kind: SyntaxKind) -> Option<SyntaxToken> {
             .and_then(|x| x.into_token())
             .map(|token| SyntaxToken { token, source_file: self.source_file.clone() })
     }
+    /// The text of the first child token of this kind
     pub fn child_text(&self, kind: SyntaxKind) -> Option<String> {
         self.node
             .children_with_tokens()
@@ -749,10 +832,12 @@ pub fn child_text(&self, kind: SyntaxKind) -> Option<String> {
     pub fn kind(&self) -> SyntaxKind {
         self.node.kind()
     }
+    /// The child nodes, without the tokens
     pub fn children(&self) -> impl Iterator<Item = SyntaxNode> {
         let source_file = self.source_file.clone();
         self.node.children().map(move |node| SyntaxNode { node, source_file: source_file.clone() })
     }
+    /// The child nodes and tokens, in the order of the source
     pub fn children_with_tokens(&self) -> impl Iterator<Item = NodeOrToken> {
         let source_file = self.source_file.clone();
         self.node.children_with_tokens().map(move |token| match token {
@@ -775,8 +860,33 @@ pub fn first_token(&self) -> Option<SyntaxToken> {
             .first_token()
             .map(|token| SyntaxToken { token, source_file: self.source_file.clone() })
     }
+    pub fn last_token(&self) -> Option<SyntaxToken> {
+        self.node
+            .last_token()
+            .map(|token| SyntaxToken { token, source_file: self.source_file.clone() })
+    }
+    /// This node and all the nodes it contains, in the order of the source
+    pub fn descendants(&self) -> impl Iterator<Item = SyntaxNode> {
+        let source_file = self.source_file.clone();
+        self.node
+            .descendants()
+            .map(move |node| SyntaxNode { node, source_file: source_file.clone() })
+    }
+    /// This node and all the nodes and tokens it contains, in the order of the source
+    pub fn descendants_with_tokens(&self) -> impl Iterator<Item = NodeOrToken> {
+        let source_file = self.source_file.clone();
+        self.node.descendants_with_tokens().map(move |token| match token {
+            rowan::NodeOrToken::Node(node) => {
+                SyntaxNode { node, source_file: source_file.clone() }.into()
+            }
+            rowan::NodeOrToken::Token(token) => {
+                SyntaxToken { token, source_file: source_file.clone() }.into()
+            }
+        })
+    }
 }
 
+/// Either a node or a token of the syntax tree
 #[derive(Debug, Clone, derive_more::From)]
 pub enum NodeOrToken {
     Node(SyntaxNode),
@@ -889,11 +999,16 @@ pub fn identifier_text(node: &SyntaxNode) -> Option<String> {
     node.child_text(SyntaxKind::Identifier).map(|x| normalize_identifier(&x))
 }
 
+/// Return the identifier with the `_` replaced by `-`, as the two are equivalent in identifiers
 pub fn normalize_identifier(ident: &str) -> String {
     ident.replace('_', "-")
 }
 
-// Actual parser
+/// Parse the source code of a document.
+///
+/// The path is used for the diagnostics and the resolution of the relative imports. The syntax
+/// errors are reported in the diagnostics, and the returned node is a
+/// [`SyntaxKind::Document`] even if there are errors.
 pub fn parse(
     source: String,
     path: Option<&std::path::Path>,
@@ -911,6 +1026,7 @@ pub fn parse(
     SyntaxNode { node: rowan::SyntaxNode::new_root(p.builder.finish()), source_file }
 }
 
+/// Load and parse the file, or report an error in the diagnostics if it cannot be read
 pub fn parse_file<P: AsRef<std::path::Path>>(
     path: P,
     build_diagnostics: &mut BuildDiagnostics,
@@ -921,6 +1037,7 @@ pub fn parse_file<P: AsRef<std::path::Path>>(
     Some(parse(source, Some(path.as_ref()), build_diagnostics))
 }
 
+/// Parse a document from the tokens of a lexer, such as the ones of the `slint!` macro
 pub fn parse_tokens(
     tokens: Vec<Token>,
     source_file: SourceFile,
@@ -930,3 +1047,30 @@ pub fn parse_tokens(
     document::parse_document(&mut p);
     SyntaxNode { node: rowan::SyntaxNode::new_root(p.builder.finish()), source_file }
 }
+
+#[test]
+fn test_traverse_syntax_tree() {
+    let mut diag = BuildDiagnostics::default();
+    let source = "Foo := Rectangle { property <int> x: 42; Text {} }".to_owned();
+    let node = parse(source.clone(), None, &mut diag);
+    assert!(!diag.has_error());
+    assert_eq!(node.text().to_string(), source);
+
+    let kinds: Vec<_> = node.descendants().map(|n| n.kind()).collect();
+    assert_eq!(
+        kinds[..3],
+        [SyntaxKind::Document, SyntaxKind::Component, SyntaxKind::DeclaredIdentifier]
+    );
+    assert_eq!(kinds.iter().filter(|k| **k == SyntaxKind::Element).count(), 2);
+    assert!(kinds.iter().all(|k| !k.is_token()));
+
+    let tokens: Vec<_> = node
+        .descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|t| t.kind() == SyntaxKind::Identifier)
+        .map(|t| t.text().to_string())
+        .collect();
+    assert_eq!(tokens, ["Foo", "Rectangle", "property", "int", "x", "Text"]);
+    assert!(SyntaxKind::Identifier.is_token());
+    assert_eq!(node.last_token().map(|t| t.kind()), Some(SyntaxKind::RBrace));
+}
@@ -1,9 +1,9 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use super::element::{parse_element, parse_element_content};
+use super::element::{parse_conditional_block, parse_element, parse_element_content};
 use super::prelude::*;
-use super::r#type::parse_struct_declaration;
+use super::r#type::{parse_enum_declaration, parse_struct_declaration};
 
 #[cfg_attr(test, parser_test)]
 /// ```test,Document
@@ -13,6 +13,8 @@
 /// Type := Base {} export { Type }
 /// import { Base } from "somewhere"; Type := Base {}
 /// struct Foo := { foo: foo }
+/// enum Foo { bar, baz }
+/// @if (embedded) { Type := Base {} } @else { Type := Base { SubElement { } } }
 /// /* empty */
 /// ```
 pub fn parse_document(p: &mut impl Parser) -> bool {
@@ -23,27 +25,42 @@ pub fn parse_document(p: &mut impl Parser) -> bool {
             return true;
         }
 
-        match p.peek().as_str() {
-            "export" => {
-                if !parse_export(&mut *p) {
-                    return false;
-                }
-            }
-            "import" => {
-                if !parse_import_specifier(&mut *p) {
-                    return false;
-                }
-            }
-            "struct" => {
-                if !parse_struct_declaration(&mut *p) {
-                    return false;
-                }
-            }
-            _ => {
-                if !parse_component(&mut *p) {
-                    return false;
-                }
-            }
+        if !parse_document_item(&mut *p) {
+            return false;
+        }
+    }
+}
+
+/// Parse one of the top level items of a document: component, import, export, ...
+fn parse_document_item(p: &mut impl Parser) -> bool {
+    match p.peek().as_str() {
+        "export" => parse_export(&mut *p),
+        "import" => parse_import_specifier(&mut *p),
+        "struct" => parse_struct_declaration(&mut *p),
+        "enum" => parse_enum_declaration(&mut *p),
+        "@" if p.nth(1).as_str() == "if" => {
+            parse_document_conditional_block(&mut *p);
+            true
+        }
+        _ => parse_component(&mut *p),
+    }
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,ConditionalBlock
+/// @if (embedded) { Type := Base { } }
+/// @if (platform == "android") { import { Foo } from "foo.slint"; } @else { struct Foo := { x: int } }
+/// @if (a) { } @else @if (b) { export Type := Base { } }
+/// ```
+fn parse_document_conditional_block(p: &mut impl Parser) {
+    parse_conditional_block(p, parse_document_block_content)
+}
+
+/// The content of a conditional block at the document level
+fn parse_document_block_content(p: &mut impl Parser) {
+    while !matches!(p.nth(0).kind(), SyntaxKind::RBrace | SyntaxKind::Eof) {
+        if !parse_document_item(&mut *p) {
+            return;
         }
     }
 }
@@ -110,6 +127,7 @@ pub fn parse_qualified_name(p: &mut impl Parser) -> bool {
 /// export { Type as Foo, AnotherType }
 /// export Foo := Item { }
 /// export struct Foo := { foo: bar }
+/// export enum Foo { bar, baz }
 /// ```
 fn parse_export(p: &mut impl Parser) -> bool {
     debug_assert_eq!(p.peek().as_str(), "export");
@@ -138,6 +156,8 @@ fn parse_export(p: &mut impl Parser) -> bool {
         }
     } else if p.peek().as_str() == "struct" {
         parse_struct_declaration(&mut *p)
+    } else if p.peek().as_str() == "enum" {
+        parse_enum_declaration(&mut *p)
     } else {
         parse_component(&mut *p)
     }
@@ -33,11 +33,15 @@ pub fn parse_element(p: &mut impl Parser) -> bool {
 /// if condition : Sub {}
 /// clicked => {}
 /// callback foobar;
+/// function foo(a: int) -> int { a }
 /// property<int> width;
 /// animate someProp { }
 /// animate * { }
 /// @children
+/// @children(header)
+/// @slot(header) { Sub {} }
 /// double_binding <=> element.property;
+/// @if (embedded) { width: 100px; } @else { Sub {} }
 /// ```
 pub fn parse_element_content(p: &mut impl Parser) {
     let mut had_parse_error = false;
@@ -58,6 +62,9 @@ pub fn parse_element_content(p: &mut impl Parser) {
                 SyntaxKind::Identifier if p.peek().as_str() == "callback" => {
                     parse_callback_declaration(&mut *p);
                 }
+                SyntaxKind::Identifier if p.peek().as_str() == "function" => {
+                    parse_function(&mut *p);
+                }
                 SyntaxKind::Identifier | SyntaxKind::Star if p.peek().as_str() == "animate" => {
                     parse_property_animation(&mut *p);
                 }
@@ -84,13 +91,26 @@ pub fn parse_element_content(p: &mut impl Parser) {
                     }
                 }
             },
+            SyntaxKind::At if p.nth(1).as_str() == "if" => {
+                parse_element_conditional_block(&mut *p);
+            }
+            SyntaxKind::At if p.nth(1).as_str() == "slot" => {
+                parse_children_slot(&mut *p);
+            }
             SyntaxKind::At => {
                 let checkpoint = p.checkpoint();
                 p.consume();
                 if p.peek().as_str() == "children" {
                     let mut p =
                         p.start_node_at(checkpoint.clone(), SyntaxKind::ChildrenPlaceholder);
-                    p.consume()
+                    p.consume();
+                    if p.test(SyntaxKind::LParent) {
+                        {
+                            let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+                            p.expect(SyntaxKind::Identifier);
+                        }
+                        p.expect(SyntaxKind::RParent);
+                    }
                 } else {
                     p.test(SyntaxKind::Identifier);
                     p.error("Parse error: Expected @children")
@@ -177,6 +197,92 @@ fn parse_if_element(p: &mut impl Parser) {
     parse_sub_element(&mut *p);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,ConditionalBlock
+/// @if (flag) { }
+/// @if (platform == "embedded") { width: 100px; Sub {} }
+/// @if (!a && (b || c)) { foo: bar; } @else { Elem {} }
+/// @if (a) { Elem {} } @else @if (b) { Elem {} } @else { }
+/// ```
+fn parse_element_conditional_block(p: &mut impl Parser) {
+    parse_conditional_block(p, parse_element_content)
+}
+
+/// Parses a `@if (condition) { ... } @else { ... }` block.
+/// The content of the blocks is parsed with `parse_content`
+pub fn parse_conditional_block<P: Parser>(p: &mut P, parse_content: fn(&mut P)) {
+    debug_assert_eq!(p.peek().kind(), SyntaxKind::At);
+    debug_assert_eq!(p.nth(1).as_str(), "if");
+    let mut p = p.start_node(SyntaxKind::ConditionalBlock);
+    p.consume(); // "@"
+    p.consume(); // "if"
+    parse_expression(&mut *p);
+    parse_conditional_block_content(&mut *p, parse_content);
+    if p.nth(0).kind() == SyntaxKind::At && p.nth(1).as_str() == "else" {
+        let mut p = p.start_node(SyntaxKind::ConditionalBlockElse);
+        p.consume(); // "@"
+        p.consume(); // "else"
+        if p.nth(0).kind() == SyntaxKind::At && p.nth(1).as_str() == "if" {
+            parse_conditional_block(&mut *p, parse_content);
+        } else {
+            parse_conditional_block_content(&mut *p, parse_content);
+        }
+    }
+}
+
+fn parse_conditional_block_content<P: Parser>(p: &mut P, parse_content: fn(&mut P)) {
+    let mut p = p.start_node(SyntaxKind::ConditionalBlockContent);
+    if !p.expect(SyntaxKind::LBrace) {
+        return;
+    }
+    parse_content(&mut *p);
+    p.expect(SyntaxKind::RBrace);
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,ChildrenSlot
+/// @slot(header) { }
+/// @slot(footer) { Elem {} foo := Elem { x: y; } }
+/// @slot(body) { for xx in mm: Elem {} if (cond) : Elem {} }
+/// ```
+fn parse_children_slot(p: &mut impl Parser) {
+    debug_assert_eq!(p.peek().kind(), SyntaxKind::At);
+    debug_assert_eq!(p.nth(1).as_str(), "slot");
+    let mut p = p.start_node(SyntaxKind::ChildrenSlot);
+    p.consume(); // "@"
+    p.consume(); // "slot"
+    p.expect(SyntaxKind::LParent);
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    p.expect(SyntaxKind::RParent);
+    if !p.expect(SyntaxKind::LBrace) {
+        return;
+    }
+    let mut had_parse_error = false;
+    loop {
+        match p.nth(0).kind() {
+            SyntaxKind::RBrace | SyntaxKind::Eof => break,
+            SyntaxKind::Identifier if p.peek().as_str() == "for" => parse_repeated_element(&mut *p),
+            SyntaxKind::Identifier if p.peek().as_str() == "if" => parse_if_element(&mut *p),
+            SyntaxKind::Identifier
+                if matches!(p.nth(1).kind(), SyntaxKind::ColonEqual | SyntaxKind::LBrace) =>
+            {
+                parse_sub_element(&mut *p)
+            }
+            _ => {
+                if !had_parse_error {
+                    p.error("Parse error: Only elements can be placed in a @slot");
+                    had_parse_error = true;
+                }
+                p.consume();
+            }
+        }
+    }
+    p.expect(SyntaxKind::RBrace);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,Binding
 /// foo: bar;
@@ -320,6 +426,43 @@ fn parse_callback_declaration(p: &mut impl Parser) {
     p.expect(SyntaxKind::Semicolon);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,Function
+/// function foobar() {}
+/// function add(a: int, b: int) -> int { return a + b; }
+/// function end_coma(a: string, b: {c: string},) -> string { a + b.c }
+/// ```
+fn parse_function(p: &mut impl Parser) {
+    debug_assert_eq!(p.peek().as_str(), "function");
+    let mut p = p.start_node(SyntaxKind::Function);
+    p.consume(); // "function"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    p.expect(SyntaxKind::LParent);
+    while p.peek().kind() != SyntaxKind::RParent {
+        {
+            let mut p = p.start_node(SyntaxKind::ArgumentDeclaration);
+            {
+                let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+                p.expect(SyntaxKind::Identifier);
+            }
+            p.expect(SyntaxKind::Colon);
+            parse_type(&mut *p);
+        }
+        if !p.test(SyntaxKind::Comma) {
+            break;
+        }
+    }
+    p.expect(SyntaxKind::RParent);
+    if p.test(SyntaxKind::Arrow) {
+        let mut p = p.start_node(SyntaxKind::ReturnType);
+        parse_type(&mut *p);
+    }
+    parse_code_block(&mut *p);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,PropertyDeclaration
 /// property<int> foobar;
@@ -368,6 +511,7 @@ fn parse_property_declaration(p: &mut impl Parser) {
 /// animate x { duration: 1000; }
 /// animate x, foo.y {  }
 /// animate * {  }
+/// animate x { duration: 100ms; finished => { foo = 2; } }
 /// ```
 fn parse_property_animation(p: &mut impl Parser) {
     debug_assert_eq!(p.peek().as_str(), "animate");
@@ -393,14 +537,15 @@ fn parse_property_animation(p: &mut impl Parser) {
             SyntaxKind::Eof => return,
             SyntaxKind::Identifier => match p.nth(1).kind() {
                 SyntaxKind::Colon => parse_property_binding(&mut *p),
+                SyntaxKind::FatArrow | SyntaxKind::LParent => parse_callback_connection(&mut *p),
                 _ => {
                     p.consume();
-                    p.error("Only bindings are allowed in animations");
+                    p.error("Only bindings and callback handlers are allowed in animations");
                 }
             },
             _ => {
                 p.consume();
-                p.error("Only bindings are allowed in animations");
+                p.error("Only bindings and callback handlers are allowed in animations");
             }
         }
     }
@@ -241,6 +241,9 @@ fn parse_at_keyword(p: &mut impl Parser) {
 /// [ [], [] ]
 /// ```
 fn parse_array(p: &mut impl Parser) {
+    if is_map_literal(p) {
+        return parse_map_literal(p);
+    }
     let mut p = p.start_node(SyntaxKind::Array);
     p.expect(SyntaxKind::LBracket);
 
@@ -253,6 +256,56 @@ fn parse_array(p: &mut impl Parser) {
     p.expect(SyntaxKind::RBracket);
 }
 
+/// Returns true if the `[` is the start of a map literal, that is, if the first element
+/// is followed by a colon (or for the empty map `[:]`)
+fn is_map_literal(p: &mut impl Parser) -> bool {
+    let mut depth = 0;
+    for n in 1.. {
+        match p.nth(n).kind() {
+            SyntaxKind::LParent | SyntaxKind::LBracket | SyntaxKind::LBrace => depth += 1,
+            SyntaxKind::RParent | SyntaxKind::RBrace => depth -= 1,
+            SyntaxKind::RBracket if depth == 0 => return false,
+            SyntaxKind::RBracket => depth -= 1,
+            SyntaxKind::Comma if depth == 0 => return false,
+            // The colon of a `cond ? a : b` comes after a question mark
+            SyntaxKind::Question if depth == 0 => return false,
+            SyntaxKind::Colon if depth == 0 => return true,
+            SyntaxKind::Eof => return false,
+            _ => {}
+        }
+    }
+    unreachable!()
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,MapLiteral
+/// [:]
+/// [ "a": 1, "b": 2 ]
+/// [ "a": [1, 2], ]
+/// [ foo.bar: baz, "x" + y: (a ? b : c) ]
+/// ```
+fn parse_map_literal(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::MapLiteral);
+    p.expect(SyntaxKind::LBracket);
+    if p.test(SyntaxKind::Colon) {
+        p.expect(SyntaxKind::RBracket);
+        return;
+    }
+
+    while p.nth(0).kind() != SyntaxKind::RBracket {
+        {
+            let mut p = p.start_node(SyntaxKind::MapEntry);
+            parse_expression(&mut *p);
+            p.expect(SyntaxKind::Colon);
+            parse_expression(&mut *p);
+        }
+        if !p.test(SyntaxKind::Comma) {
+            break;
+        }
+    }
+    p.expect(SyntaxKind::RBracket);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,ObjectLiteral
 /// {}
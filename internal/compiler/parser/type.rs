@@ -10,12 +10,14 @@
 /// ```test,Type
 /// string
 /// [ int ]
+/// [ string: int ]
 /// {a: string, b: int}
 /// ```
 pub fn parse_type(p: &mut impl Parser) {
     let mut p = p.start_node(SyntaxKind::Type);
     match p.nth(0).kind() {
         SyntaxKind::LBrace => parse_type_object(&mut *p),
+        SyntaxKind::LBracket if p.nth(2).kind() == SyntaxKind::Colon => parse_type_map(&mut *p),
         SyntaxKind::LBracket => parse_type_array(&mut *p),
         _ => {
             parse_qualified_name(&mut *p);
@@ -61,6 +63,21 @@ pub fn parse_type_array(p: &mut impl Parser) {
     p.expect(SyntaxKind::RBracket);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,MapType
+/// [string: int]
+/// [string: [int]]
+/// [string: {a: string, b: [string: bool]}]
+/// ```
+pub fn parse_type_map(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::MapType);
+    p.expect(SyntaxKind::LBracket);
+    parse_type(&mut *p);
+    p.expect(SyntaxKind::Colon);
+    parse_type(&mut *p);
+    p.expect(SyntaxKind::RBracket);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,StructDeclaration
 /// struct Foo := { foo: bar, xxx: { aaa: bbb, } }
@@ -78,3 +95,34 @@ pub fn parse_struct_declaration(p: &mut impl Parser) -> bool {
     parse_type_object(&mut *p);
     true
 }
+
+#[cfg_attr(test, parser_test)]
+/// ```test,EnumDeclaration
+/// enum Foo { bar, baz }
+/// enum Foo { bar, baz, }
+/// enum Bar {}
+/// ```
+pub fn parse_enum_declaration(p: &mut impl Parser) -> bool {
+    debug_assert_eq!(p.peek().as_str(), "enum");
+    let mut p = p.start_node(SyntaxKind::EnumDeclaration);
+    p.consume(); // "enum"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    if !p.expect(SyntaxKind::LBrace) {
+        return false;
+    }
+    while p.nth(0).kind() != SyntaxKind::RBrace {
+        {
+            let mut p = p.start_node(SyntaxKind::EnumValue);
+            if !p.expect(SyntaxKind::Identifier) {
+                return false;
+            }
+        }
+        if !p.test(SyntaxKind::Comma) {
+            break;
+        }
+    }
+    p.expect(SyntaxKind::RBrace)
+}
@@ -7,6 +7,7 @@
 mod check_public_api;
 mod clip;
 mod collect_custom_fonts;
+mod collect_dependencies;
 mod collect_globals;
 mod collect_structs;
 mod collect_subcomponents;
@@ -14,6 +15,7 @@
 mod const_propagation;
 mod deduplicate_property_read;
 mod default_geometry;
+mod element_overrides;
 mod embed_glyphs;
 mod embed_images;
 mod ensure_window;
@@ -22,6 +24,8 @@
 mod generate_item_indices;
 mod infer_aliases_types;
 mod inlining;
+mod lower_accessibility;
+mod lower_component_container;
 mod lower_layout;
 mod lower_popups;
 mod lower_shadows;
@@ -32,6 +36,7 @@
 mod optimize_useless_rectangles;
 mod remove_aliases;
 mod remove_unused_properties;
+mod remove_unused_resources;
 mod repeater_component;
 mod resolve_native_classes;
 mod resolving;
@@ -40,9 +45,13 @@
 mod visible;
 mod z_order;
 
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+use by_address::ByAddress;
+
 use crate::langtype::Type;
+use crate::object_tree::Component;
 
 pub async fn run_passes(
     doc: &crate::object_tree::Document,
@@ -69,126 +78,215 @@ pub async fn run_passes(
     };
 
     let global_type_registry = type_loader.global_type_registry.clone();
-    let root_component = &doc.root_component;
+    let root_components = doc.root_components().cloned().collect::<Vec<_>>();
+    let is_root =
+        |component: &Rc<Component>| root_components.iter().any(|c| Rc::ptr_eq(c, component));
+    // The components that contain the elements of the root components: the root element of the
+    // other roots is an instance of the exported component
+    let main_components = std::iter::once(doc.root_component.clone())
+        .chain(doc.other_roots.iter().filter_map(|(_, root_component)| {
+            match &root_component.root_element.borrow().base_type {
+                Type::Component(c) => Some(c.clone()),
+                _ => None,
+            }
+        }))
+        .collect::<Vec<_>>();
+    let is_main =
+        |component: &Rc<Component>| main_components.iter().any(|c| Rc::ptr_eq(c, component));
+    // The root components with the exported globals, which the other roots get a copy of
+    let mut roots_and_exported_globals = {
+        let exported_globals = doc
+            .exports()
+            .iter()
+            .filter_map(|(_, ty)| match ty {
+                Type::Component(c) if c.is_global() => Some(c.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        root_components.iter().map(|c| (c.clone(), exported_globals.clone())).collect::<Vec<_>>()
+    };
+
     infer_aliases_types::resolve_aliases(doc, diag);
     resolving::resolve_expressions(doc, type_loader, diag);
     check_expressions::check_expressions(doc, diag);
     unique_id::check_unique_id(doc, diag);
-    check_public_api::check_public_api(doc, diag);
-
-    collect_subcomponents::collect_subcomponents(root_component);
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
-        compile_paths::compile_paths(component, &doc.local_registry, diag);
-        lower_tabwidget::lower_tabwidget(component, type_loader, diag).await;
+    check_public_api::check_public_api(doc, &main_components, diag);
+    if !diag.has_error() {
+        crate::lint::run_lints(doc, &compiler_config.lints, diag);
+    }
+
+    for root_component in &root_components {
+        collect_subcomponents::collect_subcomponents(root_component);
+    }
+    for component in all_components(&root_components) {
+        compile_paths::compile_paths(&component, &doc.local_registry, diag);
+        lower_tabwidget::lower_tabwidget(&component, type_loader, diag).await;
+        lower_component_container::lower_component_container(
+            &component,
+            &global_type_registry.borrow(),
+            compiler_config.enable_component_containers,
+            is_main(&component),
+            diag,
+        );
         apply_default_properties_from_style::apply_default_properties_from_style(
-            component,
+            &component,
             &style_metrics,
             diag,
         );
-        lower_states::lower_states(component, &doc.local_registry, diag);
+        lower_states::lower_states(&component, &doc.local_registry, diag);
+        if is_main(&component) {
+            element_overrides::element_overrides(
+                &component,
+                &compiler_config.overridable_elements,
+                diag,
+            );
+        }
     }
 
-    inlining::inline(doc, inlining::InlineSelection::InlineOnlyRequiredComponents);
-    collect_subcomponents::collect_subcomponents(root_component);
+    for root_component in &root_components {
+        inlining::inline(root_component, inlining::InlineSelection::InlineOnlyRequiredComponents);
+        collect_subcomponents::collect_subcomponents(root_component);
+    }
 
-    embed_images::embed_images(
-        root_component,
-        compiler_config.embed_resources,
-        compiler_config.scale_factor,
-        diag,
-    );
+    // The images of the sub-components that several roots use are only embedded in the resources
+    // of the first of these roots, so each root starts with the resources of the previous ones
+    let mut previous_resources = HashMap::new();
+    for root_component in &root_components {
+        root_component.embedded_file_resources.borrow_mut().extend(previous_resources);
+        embed_images::embed_images(
+            root_component,
+            compiler_config.embed_resources,
+            compiler_config.scale_factor,
+            diag,
+        );
+        previous_resources = root_component.embedded_file_resources.borrow().clone();
+    }
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
-        focus_item::resolve_element_reference_in_set_focus_calls(component, diag);
-        if Rc::ptr_eq(component, root_component) {
-            focus_item::determine_initial_focus_item(component, diag);
+    for component in all_components(&root_components) {
+        focus_item::resolve_element_reference_in_set_focus_calls(&component, diag);
+        if is_root(&component) {
+            focus_item::determine_initial_focus_item(&component, diag);
         }
-        focus_item::erase_forward_focus_properties(component);
+        focus_item::erase_forward_focus_properties(&component);
     }
 
-    ensure_window::ensure_window(root_component, &doc.local_registry, &style_metrics);
+    for root_component in &root_components {
+        ensure_window::ensure_window(root_component, &doc.local_registry, &style_metrics);
+    }
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
-        flickable::handle_flickable(component, &global_type_registry.borrow());
-        repeater_component::process_repeater_components(component);
-        lower_popups::lower_popups(component, &doc.local_registry, diag);
-        lower_layout::lower_layouts(component, type_loader, diag).await;
-        default_geometry::default_geometry(component, diag);
-        z_order::reorder_by_z_order(component, diag);
+    for component in all_components(&root_components) {
+        flickable::handle_flickable(&component, &global_type_registry.borrow());
+        repeater_component::process_repeater_components(&component);
+        lower_popups::lower_popups(&component, &doc.local_registry, diag);
+        lower_layout::lower_layouts(&component, type_loader, diag).await;
+        default_geometry::default_geometry(&component, diag);
+        z_order::reorder_by_z_order(&component, diag);
         transform_and_opacity::handle_transform_and_opacity(
-            component,
+            &component,
             &global_type_registry.borrow(),
             diag,
         );
-        lower_shadows::lower_shadow_properties(component, &doc.local_registry, diag);
-        clip::handle_clip(component, &global_type_registry.borrow(), diag);
-        visible::handle_visible(component, &global_type_registry.borrow());
-        materialize_fake_properties::materialize_fake_properties(component);
+        lower_shadows::lower_shadow_properties(&component, &doc.local_registry, diag);
+        clip::handle_clip(&component, &global_type_registry.borrow(), diag);
+        visible::handle_visible(&component, &global_type_registry.borrow());
+        lower_accessibility::lower_accessibility_properties(
+            &component,
+            &global_type_registry.borrow(),
+            diag,
+        );
+        materialize_fake_properties::materialize_fake_properties(&component);
+    }
+    for (root_component, exported_globals) in &roots_and_exported_globals {
+        collect_globals::collect_globals(root_component, exported_globals, diag);
     }
-    collect_globals::collect_globals(doc, diag);
 
     if compiler_config.inline_all_elements {
-        inlining::inline(doc, inlining::InlineSelection::InlineAllComponents);
-        root_component.used_types.borrow_mut().sub_components.clear();
+        for (root_component, exported_globals) in &mut roots_and_exported_globals {
+            inlining::inline(root_component, inlining::InlineSelection::InlineAllComponents);
+            root_component.used_types.borrow_mut().sub_components.clear();
+            // The roots don't share any element anymore, they must not share the globals either
+            if !Rc::ptr_eq(root_component, &doc.root_component) {
+                let copies = inlining::duplicate_globals(root_component);
+                for global in exported_globals.iter_mut() {
+                    *global = copies[&ByAddress(global.clone())].clone();
+                }
+            }
+        }
     }
 
-    binding_analysis::binding_analysis(doc, diag);
-    unique_id::assign_unique_id(doc);
+    for root_component in &root_components {
+        binding_analysis::binding_analysis(root_component, diag);
+        unique_id::assign_unique_id(root_component);
+    }
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
-        deduplicate_property_read::deduplicate_property_read(component);
-        optimize_useless_rectangles::optimize_useless_rectangles(component);
-        move_declarations::move_declarations(component);
-        remove_aliases::remove_aliases(component, diag);
+    for component in all_components(&root_components) {
+        deduplicate_property_read::deduplicate_property_read(&component);
+        optimize_useless_rectangles::optimize_useless_rectangles(&component);
+        move_declarations::move_declarations(&component);
+        remove_aliases::remove_aliases(&component, diag);
         if !diag.has_error() {
             // binding loop causes panics in const_propagation
-            const_propagation::const_propagation(component);
+            const_propagation::const_propagation(&component);
         }
     }
 
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
-        resolve_native_classes::resolve_native_classes(component);
-        remove_unused_properties::remove_unused_properties(component);
+    for component in all_components(&root_components) {
+        resolve_native_classes::resolve_native_classes(&component);
+        remove_unused_properties::remove_unused_properties(&component);
     }
-
-    collect_structs::collect_structs(doc);
-
-    for component in (root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(root_component))
-    {
-        generate_item_indices::generate_item_indices(component);
+    for root_component in &root_components {
+        remove_unused_resources::remove_unused_resources(root_component);
+        collect_structs::collect_structs(root_component);
     }
 
-    // collect globals once more: After optimizations we might have less globals
-    collect_globals::collect_globals(doc, diag);
+    for component in all_components(&root_components) {
+        generate_item_indices::generate_item_indices(&component);
+    }
 
-    let embedded_fonts = embed_glyphs::embed_glyphs(
-        root_component,
-        compiler_config.scale_factor,
-        std::iter::once(&*doc).chain(type_loader.all_documents()),
-        diag,
-    );
+    for (root_component, exported_globals) in &roots_and_exported_globals {
+        // collect globals once more: After optimizations we might have less globals
+        collect_globals::collect_globals(root_component, exported_globals, diag);
 
-    // Create font registration calls for custom fonts, unless we're embedding pre-rendered glyphs
-    if !embedded_fonts {
-        collect_custom_fonts::collect_custom_fonts(
+        let embedded_fonts = embed_glyphs::embed_glyphs(
             root_component,
+            compiler_config.scale_factor,
             std::iter::once(&*doc).chain(type_loader.all_documents()),
-            compiler_config.embed_resources,
+            diag,
         );
+
+        // Create font registration calls for custom fonts, unless we're embedding pre-rendered glyphs
+        if !embedded_fonts {
+            collect_custom_fonts::collect_custom_fonts(
+                root_component,
+                std::iter::once(&*doc).chain(type_loader.all_documents()),
+                compiler_config.embed_resources,
+            );
+        }
+
+        collect_dependencies::collect_dependencies(
+            doc,
+            root_component,
+            type_loader.all_files(),
+            type_loader.all_documents(),
+        );
+
+        root_component.is_root_component.set(true);
     }
+}
 
-    root_component.is_root_component.set(true);
+/// The components used by the root components, followed by the root components, each of them once
+fn all_components(root_components: &[Rc<Component>]) -> Vec<Rc<Component>> {
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+    for root_component in root_components {
+        let used_types = root_component.used_types.borrow();
+        for component in used_types.sub_components.iter().chain(std::iter::once(root_component)) {
+            if seen.insert(ByAddress(component.clone())) {
+                components.push(component.clone());
+            }
+        }
+    }
+    components
 }
 
 /// Run the passes on imported documents
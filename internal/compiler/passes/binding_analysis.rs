@@ -18,7 +18,6 @@
 use crate::layout::LayoutItem;
 use crate::layout::Orientation;
 use crate::namedreference::NamedReference;
-use crate::object_tree::Document;
 use crate::object_tree::PropertyAnimation;
 use crate::object_tree::{Component, ElementRc};
 use derive_more as dm;
@@ -28,8 +27,7 @@
 /// ReverseAliases maps B to A.
 type ReverseAliases = HashMap<NamedReference, Vec<NamedReference>>;
 
-pub fn binding_analysis(doc: &Document, diag: &mut BuildDiagnostics) {
-    let component = &doc.root_component;
+pub fn binding_analysis(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
     let mut reverse_aliases = Default::default();
     mark_used_base_properties(component);
     propagate_is_set_on_aliases(component, &mut reverse_aliases);
@@ -236,11 +234,13 @@ fn analyse_binding(
     binding.borrow_mut().analysis.as_mut().unwrap().is_const = is_const;
 
     match &binding.borrow().animation {
-        Some(PropertyAnimation::Static(e)) => analyze_element(e, context, reverse_aliases, diag),
+        Some(PropertyAnimation::Static(e)) => {
+            analyze_animation_element(e, context, reverse_aliases, diag)
+        }
         Some(PropertyAnimation::Transition { animations, state_ref }) => {
             recurse_expression(state_ref, &mut process_prop);
             for a in animations {
-                analyze_element(&a.animation, context, reverse_aliases, diag);
+                analyze_animation_element(&a.animation, context, reverse_aliases, diag);
             }
         }
         None => (),
@@ -249,9 +249,43 @@ fn analyse_binding(
     let o = context.currently_analyzing.pop_back();
     assert_eq!(&o.unwrap(), current);
 
+    // The callback handlers of the animation (such as `finished`) are not evaluated as part of
+    // the binding, so they are analyzed once the binding is no longer being analyzed.
+    match &binding.borrow().animation {
+        Some(PropertyAnimation::Static(e)) => analyze_element(e, context, reverse_aliases, diag),
+        Some(PropertyAnimation::Transition { animations, .. }) => {
+            for a in animations {
+                analyze_element(&a.animation, context, reverse_aliases, diag);
+            }
+        }
+        None => (),
+    }
+
     depends_on_external
 }
 
+/// Analyze the bindings of an animation element, except the callback handlers
+fn analyze_animation_element(
+    elem: &ElementRc,
+    context: &mut AnalysisContext,
+    reverse_aliases: &ReverseAliases,
+    diag: &mut BuildDiagnostics,
+) {
+    for (name, binding) in &elem.borrow().bindings {
+        if binding.borrow().analysis.is_some()
+            || matches!(elem.borrow().lookup_property(name).property_type, Type::Callback { .. })
+        {
+            continue;
+        }
+        analyse_binding(
+            &PropertyPath::from(NamedReference::new(elem, name)),
+            context,
+            reverse_aliases,
+            diag,
+        );
+    }
+}
+
 /// Process the property `prop`
 ///
 /// This will visit all the bindings from that property
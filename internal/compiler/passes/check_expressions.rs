@@ -30,6 +30,16 @@ fn check_expression(component: &Rc<Component>, e: &Expression, diag: &mut BuildD
                 diag.push_error("Cannot convert between logical and physical length in a global component, because the scale factor is not known".into(), loc);
             }
         }
+        Expression::BuiltinFunctionReference(
+            BuiltinFunction::GetWindowBaseFontSize
+            | BuiltinFunction::GetWindowViewportWidth
+            | BuiltinFunction::GetWindowViewportHeight,
+            loc,
+        ) => {
+            if component.is_global() {
+                diag.push_error("Cannot use the rem, vw, or vh units in a global component, because the window is not known".into(), loc);
+            }
+        }
         _ => e.visit(|e| check_expression(component, e, diag)),
     }
 }
@@ -9,8 +9,15 @@
 use crate::langtype::Type;
 use crate::object_tree::{Component, Document};
 
-pub fn check_public_api(doc: &Document, diag: &mut BuildDiagnostics) {
-    check_public_api_component(&doc.root_component, diag);
+/// `main_components` are the components that contain the elements of the root components
+pub fn check_public_api(
+    doc: &Document,
+    main_components: &[Rc<Component>],
+    diag: &mut BuildDiagnostics,
+) {
+    for component in main_components {
+        check_public_api_component(component, diag);
+    }
     for (export_name, ty) in doc.exports() {
         if let Type::Component(c) = ty {
             if c.is_global() {
@@ -0,0 +1,54 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that fills the root component's dependencies
+
+use crate::expression_tree::{Expression, ImageReference};
+use crate::object_tree::*;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Fill the root_component's dependencies with the main file, the imported files, and the
+/// images and fonts, whether or not they are embedded.
+pub fn collect_dependencies<'a>(
+    doc: &'a Document,
+    root_component: &Rc<Component>,
+    loaded_files: impl Iterator<Item = &'a PathBuf>,
+    all_docs: impl Iterator<Item = &'a Document>,
+) {
+    let mut dependencies = BTreeSet::new();
+
+    if let Some(node) = &doc.node {
+        dependencies.insert(node.source_file.path().to_owned());
+    }
+    dependencies.extend(loaded_files.cloned());
+
+    for doc in std::iter::once(doc).chain(all_docs) {
+        dependencies.extend(doc.custom_fonts.iter().map(|(path, _)| PathBuf::from(path)));
+    }
+
+    // The embedded images are replaced in the expressions, but they are in the embedded resources
+    dependencies.extend(root_component.embedded_file_resources.borrow().keys().map(PathBuf::from));
+    let used_types = root_component.used_types.borrow();
+    for component in used_types
+        .sub_components
+        .iter()
+        .chain(used_types.globals.iter())
+        .chain(std::iter::once(root_component))
+    {
+        visit_all_expressions(component, |e, _| collect_images(e, &mut dependencies));
+    }
+
+    *root_component.dependencies.borrow_mut() = dependencies
+        .into_iter()
+        .filter(|path| !path.as_os_str().is_empty() && !path.starts_with("builtin:/"))
+        .collect();
+}
+
+fn collect_images(e: &Expression, dependencies: &mut BTreeSet<PathBuf>) {
+    if let Expression::ImageReference { resource_ref: ImageReference::AbsolutePath(path), .. } = e {
+        dependencies.insert(path.into());
+    }
+    e.visit(|e| collect_images(e, dependencies));
+}
@@ -7,28 +7,27 @@
 
 use crate::diagnostics::BuildDiagnostics;
 use crate::expression_tree::NamedReference;
-use crate::langtype::Type;
 use crate::object_tree::*;
 use std::collections::HashSet;
 use std::rc::Rc;
 
 /// Fill the root_component's used_types.globals
-pub fn collect_globals(doc: &Document, _diag: &mut BuildDiagnostics) {
-    doc.root_component.used_types.borrow_mut().globals.clear();
+pub fn collect_globals(
+    root_component: &Rc<Component>,
+    exported_globals: &[Rc<Component>],
+    _diag: &mut BuildDiagnostics,
+) {
+    root_component.used_types.borrow_mut().globals.clear();
     let mut set = HashSet::new();
     let mut sorted_globals = vec![];
-    for (_, ty) in doc.exports() {
-        if let Type::Component(c) = ty {
-            if c.is_global() {
-                if set.insert(ByAddress(c.clone())) {
-                    collect_in_component(c, &mut set, &mut sorted_globals);
-                    sorted_globals.push(c.clone());
-                }
-            }
+    for c in exported_globals {
+        if set.insert(ByAddress(c.clone())) {
+            collect_in_component(c, &mut set, &mut sorted_globals);
+            sorted_globals.push(c.clone());
         }
     }
-    collect_in_component(&doc.root_component, &mut set, &mut sorted_globals);
-    doc.root_component.used_types.borrow_mut().globals = sorted_globals;
+    collect_in_component(root_component, &mut set, &mut sorted_globals);
+    root_component.used_types.borrow_mut().globals = sorted_globals;
 }
 
 fn collect_in_component(
@@ -1,25 +1,27 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-//! Passes that fills the root component used_types.structs
+//! Passes that fills the root component used_types.structs and used_types.enums
 
 use crate::expression_tree::Expression;
-use crate::langtype::Type;
+use crate::langtype::{Enumeration, Type};
 use crate::object_tree::*;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
-/// Fill the root_component's used_types.structs
-pub fn collect_structs(doc: &Document) {
+/// Fill the root_component's used_types.structs and used_types.enums
+pub fn collect_structs(root_component: &Rc<Component>) {
     let mut hash = BTreeMap::new();
+    let mut enums = BTreeMap::new();
 
-    for component in (doc.root_component.used_types.borrow().sub_components.iter())
-        .chain(std::iter::once(&doc.root_component))
+    for component in (root_component.used_types.borrow().sub_components.iter())
+        .chain(std::iter::once(root_component))
     {
-        collect_structs_in_component(component, &mut hash)
+        collect_structs_in_component(component, &mut hash, &mut enums)
     }
 
-    let mut used_types = doc.root_component.used_types.borrow_mut();
+    let mut used_types = root_component.used_types.borrow_mut();
+    used_types.enums = enums.into_values().collect();
     let used_struct = &mut used_types.structs;
     *used_struct = Vec::with_capacity(hash.len());
     while let Some(next) = hash.iter().next() {
@@ -29,10 +31,21 @@ pub fn collect_structs(doc: &Document) {
     }
 }
 
-fn collect_structs_in_component(root_component: &Rc<Component>, hash: &mut BTreeMap<String, Type>) {
+fn collect_structs_in_component(
+    root_component: &Rc<Component>,
+    hash: &mut BTreeMap<String, Type>,
+    enums: &mut BTreeMap<String, Rc<Enumeration>>,
+) {
     let mut maybe_collect_object = |ty: &Type| {
-        visit_named_object(ty, &mut |name, sub_ty| {
-            hash.entry(name.clone()).or_insert_with(|| sub_ty.clone());
+        visit_named_object(ty, &mut |name, sub_ty| match sub_ty {
+            Type::Enumeration(en) => {
+                if en.node.is_some() {
+                    enums.entry(name.clone()).or_insert_with(|| en.clone());
+                }
+            }
+            _ => {
+                hash.entry(name.clone()).or_insert_with(|| sub_ty.clone());
+            }
         });
     };
 
@@ -43,10 +56,12 @@ fn collect_structs_in_component(root_component: &Rc<Component>, hash: &mut BTree
     });
 
     visit_all_expressions(root_component, |expr, _| {
-        expr.visit_recursive(&mut |expr| {
-            if let Expression::Struct { ty, .. } = expr {
-                maybe_collect_object(ty)
+        expr.visit_recursive(&mut |expr| match expr {
+            Expression::Struct { ty, .. } => maybe_collect_object(ty),
+            Expression::EnumerationValue(value) => {
+                maybe_collect_object(&Type::Enumeration(value.enumeration.clone()))
             }
+            _ => {}
         })
     });
 }
@@ -79,7 +94,8 @@ fn visit_named_object(ty: &Type, visitor: &mut impl FnMut(&String, &Type)) {
                 visit_named_object(sub_ty, visitor);
             }
         }
-        Type::Array(x) => visit_named_object(x, visitor),
+        Type::Enumeration(en) => visitor(&en.name, ty),
+        Type::Array(x) | Type::Map(x) => visit_named_object(x, visitor),
         Type::Callback { return_type, args } => {
             if let Some(rt) = return_type {
                 visit_named_object(rt, visitor);
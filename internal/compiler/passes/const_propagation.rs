@@ -2,10 +2,16 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 //! Try to simplify property bindings by propagating constant expressions
+//!
+//! The references to constant properties are replaced by their value, and the references to
+//! properties that are only bound to another property are replaced by a reference to that other
+//! property. The properties that are no longer read after that are removed with their binding.
 
 use crate::expression_tree::*;
 use crate::langtype::Type;
+use crate::namedreference::NamedReference;
 use crate::object_tree::*;
+use std::rc::Weak;
 
 pub fn const_propagation(component: &Component) {
     visit_all_expressions(component, |expr, ty| {
@@ -14,6 +20,7 @@ pub fn const_propagation(component: &Component) {
         }
         simplify_expression(expr);
     });
+    remove_inlined_properties(component);
 }
 
 /// Returns false if the expression still contains a reference to an element
@@ -27,6 +34,10 @@ fn simplify_expression(expr: &mut Expression) -> bool {
                     return true;
                 }
             }
+            if let Some(alias) = extract_alias(nr) {
+                *expr = Expression::PropertyReference(alias);
+                return simplify_expression(expr);
+            }
             false
         }
         Expression::BinaryExpression { lhs, op, rhs } => {
@@ -47,10 +58,48 @@ fn simplify_expression(expr: &mut Expression) -> bool {
                 {
                     Some(Expression::NumberLiteral(*a - *b, *un1))
                 }
-                // TODO: * and / are more complicated because they need to take care about units
+                // The result has the unit of the operand that has one
+                (
+                    '*',
+                    Expression::NumberLiteral(a, un1),
+                    Expression::NumberLiteral(b, Unit::None),
+                ) => Some(Expression::NumberLiteral(*a * *b, *un1)),
+                (
+                    '*',
+                    Expression::NumberLiteral(a, Unit::None),
+                    Expression::NumberLiteral(b, un2),
+                ) => Some(Expression::NumberLiteral(*a * *b, *un2)),
+                (
+                    '/',
+                    Expression::NumberLiteral(a, un1),
+                    Expression::NumberLiteral(b, Unit::None),
+                ) if *b != 0. => Some(Expression::NumberLiteral(*a / *b, *un1)),
                 ('=' | '!', Expression::NumberLiteral(a, _), Expression::NumberLiteral(b, _)) => {
                     Some(Expression::BoolLiteral((a == b) == (*op == '=')))
                 }
+                ('<', Expression::NumberLiteral(a, un1), Expression::NumberLiteral(b, un2))
+                    if un1 == un2 =>
+                {
+                    Some(Expression::BoolLiteral(a < b))
+                }
+                ('>', Expression::NumberLiteral(a, un1), Expression::NumberLiteral(b, un2))
+                    if un1 == un2 =>
+                {
+                    Some(Expression::BoolLiteral(a > b))
+                }
+                ('≤', Expression::NumberLiteral(a, un1), Expression::NumberLiteral(b, un2))
+                    if un1 == un2 =>
+                {
+                    Some(Expression::BoolLiteral(a <= b))
+                }
+                ('≥', Expression::NumberLiteral(a, un1), Expression::NumberLiteral(b, un2))
+                    if un1 == un2 =>
+                {
+                    Some(Expression::BoolLiteral(a >= b))
+                }
+                ('=' | '!', Expression::BoolLiteral(a), Expression::BoolLiteral(b)) => {
+                    Some(Expression::BoolLiteral((a == b) == (*op == '=')))
+                }
                 ('=' | '!', Expression::StringLiteral(a), Expression::StringLiteral(b)) => {
                     Some(Expression::BoolLiteral((a == b) == (*op == '=')))
                 }
@@ -85,6 +134,43 @@ fn simplify_expression(expr: &mut Expression) -> bool {
             }
             can_inline
         }
+        Expression::UnaryOp { sub, op } => {
+            let can_inline = simplify_expression(sub);
+            let new = match (*op, &mut **sub) {
+                ('!', Expression::BoolLiteral(b)) => Some(Expression::BoolLiteral(!*b)),
+                ('-', Expression::NumberLiteral(n, unit)) => {
+                    Some(Expression::NumberLiteral(-*n, *unit))
+                }
+                ('+', Expression::NumberLiteral(n, unit)) => {
+                    Some(Expression::NumberLiteral(*n, *unit))
+                }
+                _ => None,
+            };
+            if let Some(new) = new {
+                *expr = new;
+            }
+            can_inline
+        }
+        Expression::Condition { condition, true_expr, false_expr } => {
+            let mut can_inline = simplify_expression(condition);
+            match **condition {
+                // Only the branch that is taken remains
+                Expression::BoolLiteral(condition) => {
+                    let mut branch = std::mem::take(if condition {
+                        &mut **true_expr
+                    } else {
+                        &mut **false_expr
+                    });
+                    can_inline = simplify_expression(&mut branch);
+                    *expr = branch;
+                }
+                _ => {
+                    can_inline &= simplify_expression(true_expr);
+                    can_inline &= simplify_expression(false_expr);
+                }
+            }
+            can_inline
+        }
         Expression::Cast { from, to } => {
             let can_inline = simplify_expression(from);
             let new = if from.ty() == *to {
@@ -152,3 +238,146 @@ fn extract_constant_property_reference(nr: &NamedReference) -> Option<Expression
     }
     Some(expression)
 }
+
+/// If the property is a private property whose binding is only a reference to another property
+/// of the same component, and that is never set, return that other property, which always
+/// has the same value.
+fn extract_alias(nr: &NamedReference) -> Option<NamedReference> {
+    if nr.is_externally_modified() {
+        return None;
+    }
+    let element = nr.element();
+    let element = element.borrow();
+    let decl = element.property_declarations.get(nr.name())?;
+    if decl.expose_in_public_api || decl.is_alias.is_some() {
+        return None;
+    }
+    let binding = element.bindings.get(nr.name())?.borrow();
+    if binding.animation.is_some() || !binding.two_way_bindings.is_empty() {
+        return None;
+    }
+    let mut expression = &binding.expression;
+    while let Expression::CodeBlock(statements) = expression {
+        match statements.as_slice() {
+            [statement] => expression = statement,
+            _ => return None,
+        }
+    }
+    match expression {
+        Expression::PropertyReference(alias)
+            if alias.ty() == nr.ty()
+                && Weak::ptr_eq(
+                    &alias.element().borrow().enclosing_component,
+                    &element.enclosing_component,
+                ) =>
+        {
+            Some(alias.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Remove the private properties that are not read anymore since their references were inlined,
+/// with their binding.
+fn remove_inlined_properties(component: &Component) {
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        let mut elem = elem.borrow_mut();
+        let to_remove = elem
+            .property_declarations
+            .iter()
+            .filter(|(name, decl)| {
+                !decl.expose_in_public_api
+                    && !elem.named_references.is_referenced(name)
+                    && elem.property_analysis.borrow().get(*name).map_or(false, |analysis| {
+                        analysis.is_read
+                            && !analysis.is_set
+                            && !analysis.is_set_externally
+                            && !analysis.is_read_externally
+                    })
+            })
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        for name in to_remove {
+            elem.property_declarations.remove(&name);
+            elem.property_analysis.borrow_mut().remove(&name);
+            elem.bindings.remove(&name);
+        }
+    });
+}
+
+#[test]
+fn test_fold_constant_expressions() {
+    let number = |n: f64, unit: Unit| Box::new(Expression::NumberLiteral(n, unit));
+    let binary = |lhs, op, rhs| Box::new(Expression::BinaryExpression { lhs, op, rhs });
+
+    // -(2px * 3) < 10px / 2
+    let mut expr = *binary(
+        Box::new(Expression::UnaryOp {
+            sub: binary(number(2., Unit::Px), '*', number(3., Unit::None)),
+            op: '-',
+        }),
+        '<',
+        binary(number(10., Unit::Px), '/', number(2., Unit::None)),
+    );
+    assert!(simplify_expression(&mut expr));
+    assert!(matches!(expr, Expression::BoolLiteral(true)));
+
+    let mut expr = Expression::Condition {
+        condition: Box::new(Expression::UnaryOp {
+            sub: Box::new(Expression::BoolLiteral(true)),
+            op: '!',
+        }),
+        true_expr: number(1., Unit::None),
+        false_expr: binary(number(1., Unit::None), '+', number(2., Unit::None)),
+    };
+    assert!(simplify_expression(&mut expr));
+    assert!(matches!(expr, Expression::NumberLiteral(n, Unit::None) if n == 3.));
+
+    // Division by zero and different units are not folded
+    let mut expr = *binary(number(1., Unit::None), '/', number(0., Unit::None));
+    simplify_expression(&mut expr);
+    assert!(matches!(expr, Expression::BinaryExpression { .. }));
+    let mut expr = *binary(number(1., Unit::Px), '<', number(1., Unit::Phx));
+    simplify_expression(&mut expr);
+    assert!(matches!(expr, Expression::BinaryExpression { .. }));
+}
+
+#[test]
+fn test_remove_inlined_properties() {
+    let source = r#"
+export Test := Rectangle {
+    property <length> input: 5px;
+    inner := TouchArea {
+        property <length> folded-base: 10px;
+        property <length> folded-double: folded-base * 2;
+        property <length> aliased-input: root.input;
+        property <length> set-later: 1px;
+        x: folded-double;
+        y: aliased-input;
+        width: aliased-input + folded-base + set-later;
+        clicked => { set-later += 1px; }
+    }
+}"#;
+    let mut diag = crate::diagnostics::BuildDiagnostics::default();
+    let node = crate::parser::parse(source.into(), None, &mut diag);
+    let mut config = crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    config.style = Some("fluent".into());
+    let (doc, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+    assert!(!diag.has_error());
+
+    let inner = doc.root_component.root_element.borrow().children[0].clone();
+    let inner = inner.borrow();
+    assert_eq!(inner.property_declarations.keys().collect::<Vec<_>>(), vec!["set-later"]);
+    assert!(matches!(inner.bindings["x"].borrow().expression, Expression::NumberLiteral(..)));
+    assert!(matches!(
+        &inner.bindings["y"].borrow().expression,
+        Expression::PropertyReference(nr) if nr.name() == "input"
+    ));
+
+    let llr = crate::llr::lower_to_item_tree::lower_to_item_tree(&doc.root_component);
+    let mut code = String::new();
+    crate::llr::pretty_print::pretty_print(&llr, &mut code).unwrap();
+    assert!(!code.contains("folded"), "{}", code);
+    assert!(!code.contains("aliased"), "{}", code);
+    assert!(code.contains("set-later") || code.contains("set_later"), "{}", code);
+}
@@ -0,0 +1,143 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that allows overriding the `visible`, `enabled`, and `opacity` properties of the
+//! elements listed in [`CompilerConfiguration::overridable_elements`](crate::CompilerConfiguration::overridable_elements)
+//!
+//! For each of these properties that the element has, the root element declares a property
+//! named `<id>-override-<property>` that the interpreter sets at runtime, and that is combined
+//! with the binding of the element.
+//!
+//! Must be done before inlining, so that the ids are the ones written in the main component,
+//! and before the passes that lower `visible` and `opacity`.
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{Expression, NamedReference, Unit};
+use crate::langtype::Type;
+use crate::object_tree::*;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+pub fn element_overrides(
+    component: &Rc<Component>,
+    element_ids: &[String],
+    diag: &mut BuildDiagnostics,
+) {
+    if element_ids.is_empty() {
+        return;
+    }
+    let root = component.root_element.clone();
+    let mut remaining = element_ids.iter().map(String::as_str).collect::<HashSet<_>>();
+
+    recurse_elem_no_borrow(&root, &(), &mut |elem, _| {
+        let id = elem.borrow().id.clone();
+        if id.is_empty() || !remaining.remove(id.as_str()) {
+            return;
+        }
+
+        // The override is combined with the value of the element with this operator
+        let overrides = [
+            ("visible", Type::Bool, Expression::BoolLiteral(true), '&'),
+            ("enabled", Type::Bool, Expression::BoolLiteral(true), '&'),
+            ("opacity", Type::Float32, Expression::NumberLiteral(1., Unit::None), '*'),
+        ];
+        for (property, ty, default_value, op) in overrides {
+            if elem.borrow().lookup_property(property).property_type != ty {
+                continue;
+            }
+            let name = element_override_property_name(&id, property);
+            {
+                let mut root = root.borrow_mut();
+                root.property_declarations.insert(
+                    name.clone(),
+                    PropertyDeclaration { property_type: ty, ..Default::default() },
+                );
+                root.bindings.insert(name.clone(), RefCell::new(default_value.into()));
+                // It is set at runtime, so it must not be optimized as a constant
+                root.property_analysis.borrow_mut().entry(name.clone()).or_default().is_set = true;
+            }
+
+            let override_ref = Expression::PropertyReference(NamedReference::new(&root, &name));
+            let mut elem = elem.borrow_mut();
+            match elem.bindings.get(property) {
+                Some(binding) if !binding.borrow().two_way_bindings.is_empty() => {
+                    diag.push_error(
+                        format!(
+                            "The {} of '{}' is a two-way binding, which cannot be overridden",
+                            property, id
+                        ),
+                        &*binding.borrow(),
+                    );
+                }
+                Some(binding) => {
+                    let mut binding = binding.borrow_mut();
+                    binding.expression = match std::mem::take(&mut binding.expression) {
+                        Expression::Invalid => override_ref,
+                        value => Expression::BinaryExpression {
+                            lhs: Box::new(value),
+                            rhs: Box::new(override_ref),
+                            op,
+                        },
+                    };
+                }
+                None => {
+                    elem.bindings.insert(property.into(), RefCell::new(override_ref.into()));
+                }
+            }
+        }
+    });
+
+    let mut remaining = remaining.into_iter().collect::<Vec<_>>();
+    remaining.sort_unstable();
+    for id in remaining {
+        diag.push_error_with_span(
+            format!("There is no element with the id '{}' in the main component to override", id),
+            Default::default(),
+        );
+    }
+}
+
+#[test]
+fn test_element_overrides() {
+    let source = r#"
+export Test := Window {
+    property <bool> show-panel: true;
+    panel := Rectangle {
+        visible: show-panel;
+        area := TouchArea { }
+    }
+    linked := Rectangle {
+        visible <=> show-panel;
+    }
+}"#;
+    let compile = |ids: &[&str]| {
+        let mut diag = crate::diagnostics::BuildDiagnostics::default();
+        let node = crate::parser::parse(source.into(), None, &mut diag);
+        let mut config =
+            crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+        config.style = Some("fluent".into());
+        config.overridable_elements = ids.iter().map(|id| id.to_string()).collect();
+        let (doc, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+        let messages = diag.iter().map(|d| d.message().to_owned()).collect::<Vec<_>>();
+        (doc, messages)
+    };
+
+    let (doc, messages) = compile(&["panel", "area"]);
+    assert_eq!(messages, Vec::<String>::new());
+    let root = doc.root_component.root_element.borrow();
+    for property in ["panel-override-visible", "panel-override-opacity", "area-override-enabled"] {
+        assert!(root.property_declarations.contains_key(property), "{}", property);
+    }
+    assert!(!root.property_declarations.contains_key("panel-override-enabled"));
+
+    let (_, messages) = compile(&["test", "linked", "missing"]);
+    assert_eq!(
+        messages,
+        vec![
+            "The visible of 'linked' is a two-way binding, which cannot be overridden".to_owned(),
+            "There is no element with the id 'missing' in the main component to override".into(),
+            "There is no element with the id 'test' in the main component to override".into(),
+        ]
+    );
+}
@@ -47,6 +47,7 @@ pub fn ensure_window(
         item_index_of_first_children: Default::default(),
         node: win_elem_mut.node.clone(),
         inline_depth: 0,
+        child_slot: None,
     };
     let new_root = Rc::new(RefCell::new(new_root));
     win_elem_mut.children.push(new_root.clone());
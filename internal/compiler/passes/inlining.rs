@@ -18,7 +18,7 @@ pub enum InlineSelection {
     InlineOnlyRequiredComponents,
 }
 
-pub fn inline(doc: &Document, inline_selection: InlineSelection) {
+pub fn inline(root_component: &Rc<Component>, inline_selection: InlineSelection) {
     fn inline_components_recursively(component: &Rc<Component>, inline_selection: InlineSelection) {
         recurse_elem(&component.root_element, &(), &mut |elem, _| {
             let base = elem.borrow().base_type.clone();
@@ -48,7 +48,45 @@ fn inline_components_recursively(component: &Rc<Component>, inline_selection: In
             .iter()
             .for_each(|p| inline_components_recursively(&p.component, inline_selection))
     }
-    inline_components_recursively(&doc.root_component, inline_selection);
+    inline_components_recursively(root_component, inline_selection);
+}
+
+/// Replace the globals that the root component uses with copies of them, once it doesn't share
+/// its elements with other components anymore. Returns the copy of each of these globals.
+pub fn duplicate_globals(
+    root_component: &Rc<Component>,
+) -> HashMap<ByAddress<Rc<Component>>, Rc<Component>> {
+    let mut mapping = HashMap::new();
+    let mut copies = HashMap::new();
+    let globals = root_component
+        .used_types
+        .borrow()
+        .globals
+        .iter()
+        .map(|global| {
+            let copy = Rc::new(Component {
+                id: global.id.clone(),
+                root_element: duplicate_element_with_mapping(
+                    &global.root_element,
+                    &mut mapping,
+                    global, // that's the wrong one, but we fixup further
+                    0,
+                ),
+                exported_global_names: global.exported_global_names.clone(),
+                ..Default::default()
+            });
+            let weak = Rc::downgrade(&copy);
+            recurse_elem(&copy.root_element, &(), &mut |e, _| {
+                e.borrow_mut().enclosing_component = weak.clone()
+            });
+            copies.insert(ByAddress(global.clone()), copy.clone());
+            copy
+        })
+        .collect();
+    root_component.used_types.borrow_mut().globals = globals;
+    // This also visits the bindings of the copies
+    visit_all_named_references(root_component, &mut |nr| fixup_reference(nr, &mapping));
+    copies
 }
 
 fn clone_tuple<U: Clone, V: Clone>((u, v): (&U, &V)) -> (U, V) {
@@ -103,24 +141,53 @@ fn inline_element(
         }),
     );
 
+    // Move the children declared in a `@slot(name)` to the element of the matching placeholder
+    let mut children_in_root_slot = vec![];
+    for child in std::mem::take(&mut elem_mut.children) {
+        let slot = child.borrow_mut().child_slot.take();
+        let insertion_element = slot.and_then(|slot| {
+            let named = inlined_component.named_child_insertion_points.borrow();
+            mapping.get(&element_key(named.get(&slot)?.0.clone())).cloned()
+        });
+        match insertion_element {
+            Some(insertion_element) if !Rc::ptr_eq(elem, &insertion_element) => {
+                insertion_element.borrow_mut().children.push(child);
+            }
+            Some(_) => children_in_root_slot.push(child),
+            None => elem_mut.children.push(child),
+        }
+    }
+
     match inlined_component
         .child_insertion_point
         .borrow()
         .as_ref()
         .and_then(|(elem, node)| Some((mapping.get(&element_key(elem.clone()))?, node)))
     {
-        Some((insertion_element, cip_node)) if !Rc::ptr_eq(elem, insertion_element) => {
+        Some((insertion_element, _)) if !Rc::ptr_eq(elem, insertion_element) => {
             insertion_element.borrow_mut().children.append(&mut elem_mut.children);
-            if let Some(cip) = root_component.child_insertion_point.borrow_mut().as_mut() {
+            // The placeholders within the children of this element were moved with them
+            let moved_placeholder = |cip: &mut ChildrenInsertionPoint| {
                 if Rc::ptr_eq(&cip.0, elem) {
-                    *cip = (insertion_element.clone(), cip_node.clone());
+                    cip.0 = insertion_element.clone();
                 }
             };
+            root_component
+                .child_insertion_point
+                .borrow_mut()
+                .iter_mut()
+                .for_each(moved_placeholder);
+            root_component
+                .named_child_insertion_points
+                .borrow_mut()
+                .values_mut()
+                .for_each(moved_placeholder);
         }
         _ => {
             new_children.append(&mut elem_mut.children);
         }
     }
+    new_children.append(&mut children_in_root_slot);
 
     elem_mut.children = new_children;
 
@@ -219,6 +286,7 @@ fn duplicate_element_with_mapping(
         item_index_of_first_children: Default::default(),
         is_flickable_viewport: elem.is_flickable_viewport,
         inline_depth: elem.inline_depth + 1,
+        child_slot: elem.child_slot.clone(),
     }));
     mapping.insert(element_key(element.clone()), new.clone());
     if let Type::Component(c) = &mut new.borrow_mut().base_type {
@@ -266,6 +334,7 @@ fn duplicate_sub_component(
         embedded_file_resources: component_to_duplicate.embedded_file_resources.clone(),
         root_constraints: component_to_duplicate.root_constraints.clone(),
         child_insertion_point: component_to_duplicate.child_insertion_point.clone(),
+        named_child_insertion_points: component_to_duplicate.named_child_insertion_points.clone(),
         setup_code: component_to_duplicate.setup_code.clone(),
         used_types: Default::default(),
         popup_windows: Default::default(),
@@ -442,7 +511,9 @@ fn duplicate_transition(
 // Some components need to be inlined to avoid increased complexity in handling them
 // in the code generators and subsequent passes.
 fn component_requires_inlining(component: &Rc<Component>) -> bool {
-    if component.child_insertion_point.borrow().is_some() {
+    if component.child_insertion_point.borrow().is_some()
+        || !component.named_child_insertion_points.borrow().is_empty()
+    {
         return true;
     }
 
@@ -456,9 +527,10 @@ fn component_requires_inlining(component: &Rc<Component>) -> bool {
 
     for (prop, binding) in &root_element.borrow().bindings {
         let binding = binding.borrow();
-        // The passes that dp the drop shadow or the opacity currently won't allow this property
-        // on the top level of a component. This could be changed in the future.
-        if prop.starts_with("drop-shadow-") || prop == "opacity" {
+        // The passes that dp the drop shadow, the opacity, or the accessibility properties currently
+        // won't allow this property on the top level of a component. This could be changed in the future.
+        if prop.starts_with("drop-shadow-") || prop.starts_with("accessible-") || prop == "opacity"
+        {
             return true;
         }
         if (prop == "height" || prop == "width") && binding.expression.ty() == Type::Percent {
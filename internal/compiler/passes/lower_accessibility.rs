@@ -0,0 +1,96 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that lowers synthetic `accessible-*` properties to an Accessible element wrapping the element
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{BindingExpression, NamedReference};
+use crate::langtype::Type;
+use crate::object_tree::{self, Component, Element, ElementRc};
+use crate::typeregister::TypeRegister;
+
+const ACCESSIBLE_PROPERTIES: &[(&str, &str)] = &[
+    ("accessible-role", "role"),
+    ("accessible-label", "label"),
+    ("accessible-value", "value"),
+    ("accessible-action-default", "action-default"),
+];
+
+pub fn lower_accessibility_properties(
+    component: &Rc<Component>,
+    type_register: &TypeRegister,
+    diag: &mut BuildDiagnostics,
+) {
+    for (prop, _) in ACCESSIBLE_PROPERTIES {
+        if let Some(b) = component.root_element.borrow().bindings.get(*prop) {
+            diag.push_warning(
+                format!(
+                    "The {} property cannot be used on the root element, it will not be applied",
+                    prop
+                ),
+                &*b.borrow(),
+            );
+        }
+    }
+
+    object_tree::recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        if elem.borrow().base_type.to_string() == "Accessible" {
+            return;
+        }
+
+        let old_children = {
+            let mut elem = elem.borrow_mut();
+            let new_children = Vec::with_capacity(elem.children.len());
+            std::mem::replace(&mut elem.children, new_children)
+        };
+
+        for mut child in old_children {
+            if child.borrow().repeated.is_some() {
+                let root_elem = child.borrow().base_type.as_component().root_element.clone();
+                if has_accessible_binding(&root_elem) {
+                    object_tree::inject_element_as_repeated_element(
+                        &child,
+                        create_accessible_element(&root_elem, type_register),
+                    )
+                }
+            } else if has_accessible_binding(&child) {
+                let new_child = create_accessible_element(&child, type_register);
+                crate::object_tree::adjust_geometry_for_injected_parent(&new_child, &child);
+                new_child.borrow_mut().children.push(child);
+                child = new_child;
+            }
+
+            elem.borrow_mut().children.push(child);
+        }
+    });
+}
+
+fn has_accessible_binding(e: &ElementRc) -> bool {
+    ACCESSIBLE_PROPERTIES.iter().any(|(prop, _)| {
+        e.borrow().base_type.lookup_property(prop).property_type != Type::Invalid
+            && (e.borrow().bindings.contains_key(*prop)
+                || e.borrow().property_analysis.borrow().get(*prop).map_or(false, |a| a.is_set))
+    })
+}
+
+fn create_accessible_element(child: &ElementRc, type_register: &TypeRegister) -> ElementRc {
+    let element = Element {
+        id: format!("{}-accessible", child.borrow().id),
+        base_type: type_register.lookup_element("Accessible").unwrap(),
+        enclosing_component: child.borrow().enclosing_component.clone(),
+        bindings: ACCESSIBLE_PROPERTIES
+            .iter()
+            .map(|(prop, accessible_prop)| {
+                (
+                    accessible_prop.to_string(),
+                    RefCell::new(BindingExpression::new_two_way(NamedReference::new(child, prop))),
+                )
+            })
+            .collect(),
+        ..Default::default()
+    };
+    Rc::new(RefCell::new(element))
+}
@@ -0,0 +1,89 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Passe that lowers the ComponentContainer
+//!
+//! The ComponentContainer becomes a Rectangle with a single repeated placeholder child whose
+//! model is the `component-factory` property. At runtime, the component created by the
+//! factory replaces the placeholder.
+//!
+//! Must be done before inlining because it adds elements to the tree.
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{Expression, NamedReference};
+use crate::langtype::Type;
+use crate::object_tree::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn lower_component_container(
+    component: &Rc<Component>,
+    type_register: &crate::typeregister::TypeRegister,
+    enable_component_containers: bool,
+    is_main_component: bool,
+    diag: &mut BuildDiagnostics,
+) {
+    let rectangle_type = type_register.lookup_element("Rectangle").unwrap();
+
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        if !enable_component_containers {
+            for (name, decl) in &elem.borrow().property_declarations {
+                if decl.property_type == Type::ComponentFactory {
+                    diag.push_error(
+                        format!(
+                            "The property '{}' is of type component-factory, which is only supported by the interpreter",
+                            name
+                        ),
+                        &decl.type_node(),
+                    );
+                }
+            }
+        }
+        if elem.borrow().base_type.to_string() == "ComponentContainer" {
+            if !enable_component_containers {
+                diag.push_error(
+                    "ComponentContainer is only supported by the interpreter".into(),
+                    &*elem.borrow(),
+                );
+            }
+            process_component_container(elem, &rectangle_type, is_main_component, diag);
+        }
+    })
+}
+
+fn process_component_container(
+    elem: &ElementRc,
+    rectangle_type: &Type,
+    is_main_component: bool,
+    diag: &mut BuildDiagnostics,
+) {
+    for child in &elem.borrow().children {
+        diag.push_error("ComponentContainer cannot have children".into(), &*child.borrow());
+    }
+
+    // The ids are made unique in a later pass, so keep the id by which the API refers to the container
+    let id = elem.borrow().id.clone();
+    let component_container_id = (is_main_component && !id.is_empty()).then(|| id);
+
+    let placeholder = Element {
+        id: format!("{}-placeholder", elem.borrow().id),
+        base_type: rectangle_type.clone(),
+        enclosing_component: elem.borrow().enclosing_component.clone(),
+        repeated: Some(RepeatedElementInfo {
+            model: Expression::PropertyReference(NamedReference::new(elem, "component-factory")),
+            model_data_id: String::new(),
+            index_id: String::new(),
+            is_conditional_element: true,
+            is_listview: None,
+            is_component_container: true,
+            component_container_id,
+        }),
+        ..Default::default()
+    };
+
+    let mut elem = elem.borrow_mut();
+    elem.base_type = rectangle_type.clone();
+    elem.property_declarations
+        .insert("component-factory".to_owned(), Type::ComponentFactory.into());
+    elem.children = vec![Rc::new(RefCell::new(placeholder))];
+}
@@ -0,0 +1,183 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Remove the code and the embedded resources that are not used anymore after the optimizations:
+//!  - the `if` elements whose condition is always false,
+//!  - the sub-components that are no longer instantiated, for example because they were only used
+//!    in such an `if`, with their properties,
+//!  - the images that are no longer referenced, for example because the property that referenced
+//!    them was removed.
+//!
+//! The unused properties of the remaining elements are removed by `remove_unused_properties`.
+
+use crate::expression_tree::{Expression, ImageReference};
+use crate::langtype::Type;
+use crate::object_tree::*;
+use by_address::ByAddress;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+pub fn remove_unused_resources(root_component: &Rc<Component>) {
+    remove_false_conditional_elements(root_component);
+    remove_unused_sub_components(root_component);
+
+    let mut used = HashSet::new();
+    visit_resource_ids(root_component, &mut |id| {
+        used.insert(*id);
+    });
+
+    let mut resources = root_component.embedded_file_resources.borrow_mut();
+    resources.retain(|_, r| used.contains(&r.id));
+    // The ids must stay contiguous, because the passes that embed more resources use the
+    // number of resources as the next id
+    let mut ids = resources.values().map(|r| r.id).collect::<Vec<_>>();
+    ids.sort_unstable();
+    let new_ids =
+        ids.into_iter().enumerate().map(|(new, old)| (old, new)).collect::<HashMap<_, _>>();
+    for r in resources.values_mut() {
+        r.id = new_ids[&r.id];
+    }
+    drop(resources);
+
+    visit_resource_ids(root_component, &mut |id| *id = new_ids[id]);
+}
+
+/// Remove the `if` elements whose condition is the constant `false`, unless something, such as
+/// a layout, still refers to them
+fn remove_false_conditional_elements(root_component: &Rc<Component>) {
+    let is_false_condition = |elem: &ElementRc| {
+        Rc::strong_count(elem) == 1
+            && elem.borrow().repeated.as_ref().map_or(false, |r| {
+                r.is_conditional_element && matches!(r.model, Expression::BoolLiteral(false))
+            })
+    };
+    let used_types = root_component.used_types.borrow();
+    for component in used_types.sub_components.iter().chain(std::iter::once(root_component)) {
+        recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+            let children = std::mem::take(&mut elem.borrow_mut().children);
+            elem.borrow_mut().children =
+                children.into_iter().filter(|child| !is_false_condition(child)).collect();
+        });
+    }
+}
+
+/// Remove the sub-components that are not instantiated by the root component anymore, directly
+/// or through other sub-components
+fn remove_unused_sub_components(root_component: &Rc<Component>) {
+    fn collect_used(component: &Rc<Component>, used: &mut HashSet<ByAddress<Rc<Component>>>) {
+        recurse_elem_including_sub_components(component, &(), &mut |elem, _| {
+            if elem.borrow().repeated.is_some() {
+                // The repeated component itself is visited by recurse_elem_including_sub_components
+                return;
+            }
+            let base = match &elem.borrow().base_type {
+                Type::Component(base) => base.clone(),
+                _ => return,
+            };
+            if used.insert(ByAddress(base.clone())) {
+                collect_used(&base, used);
+            }
+        });
+    }
+    let mut used = HashSet::new();
+    collect_used(root_component, &mut used);
+    root_component
+        .used_types
+        .borrow_mut()
+        .sub_components
+        .retain(|c| used.contains(&ByAddress(c.clone())));
+}
+
+fn visit_resource_ids(root_component: &Rc<Component>, vis: &mut impl FnMut(&mut usize)) {
+    let mut visit_expression = |e: &mut Expression| {
+        e.visit_recursive_mut(&mut |e| {
+            if let Expression::ImageReference {
+                resource_ref:
+                    ImageReference::EmbeddedData { resource_id, .. }
+                    | ImageReference::EmbeddedTexture { resource_id },
+                ..
+            } = e
+            {
+                vis(resource_id)
+            }
+        })
+    };
+
+    let used_types = root_component.used_types.borrow();
+    for component in used_types
+        .sub_components
+        .iter()
+        .chain(used_types.globals.iter())
+        .chain(std::iter::once(root_component))
+    {
+        visit_all_expressions(component, |e, _| visit_expression(e));
+        for e in component.setup_code.borrow_mut().iter_mut() {
+            visit_expression(e);
+        }
+        // Their properties may still be in use
+        for elem in component.optimized_elements.borrow().iter() {
+            visit_element_expressions(elem, |e, _, _| visit_expression(e));
+        }
+    }
+}
+
+#[test]
+fn test_remove_unused_resources() {
+    let logo = |name: &str| {
+        format!("{}/widgets/common/slint-logo-{}.svg", env!("CARGO_MANIFEST_DIR"), name)
+    };
+    let source = format!(
+        r#"
+export Test := Window {{
+    property <image> used: @image-url("{}");
+    Rectangle {{
+        property <image> unused: @image-url("{}");
+    }}
+}}"#,
+        logo("dark"),
+        logo("light")
+    );
+
+    let mut diag = crate::diagnostics::BuildDiagnostics::default();
+    let node = crate::parser::parse(source, None, &mut diag);
+    let mut config = crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    config.embed_resources = true;
+    config.style = Some("fluent".into());
+    let (doc, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+    assert!(!diag.has_error());
+
+    let resources = doc.root_component.embedded_file_resources.borrow();
+    assert_eq!(resources.keys().collect::<Vec<_>>(), vec![&logo("dark")]);
+    assert_eq!(resources[&logo("dark")].id, 0);
+}
+
+#[test]
+fn test_remove_unused_components() {
+    let logo = format!("{}/widgets/common/slint-logo-light.svg", env!("CARGO_MANIFEST_DIR"));
+    let source = format!(
+        r#"
+Logo := Image {{ source: @image-url("{}"); }}
+Debug := Rectangle {{ Logo {{}} }}
+Used := Rectangle {{ }}
+export Test := Window {{
+    Used {{ }}
+    if 1 > 2 : Debug {{ }}
+}}"#,
+        logo
+    );
+
+    let mut diag = crate::diagnostics::BuildDiagnostics::default();
+    let node = crate::parser::parse(source, None, &mut diag);
+    let mut config = crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    config.embed_resources = true;
+    config.inline_all_elements = false;
+    config.style = Some("fluent".into());
+    let (doc, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+    assert!(!diag.has_error());
+
+    let root = &doc.root_component;
+    let sub_components = root.used_types.borrow().sub_components.clone();
+    assert_eq!(sub_components.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["Used"]);
+    assert_eq!(root.root_element.borrow().children.len(), 1);
+    assert!(root.embedded_file_resources.borrow().is_empty());
+}
@@ -45,6 +45,7 @@ fn create_repeater_components(component: &Rc<Component>) {
                 item_index: Default::default(), // Not determined yet
                 item_index_of_first_children: Default::default(),
                 inline_depth: 0,
+                child_slot: None,
             })),
             parent_element,
             ..Component::default()
@@ -50,6 +50,7 @@ fn resolve_expression(
                 //FIXME: proper callback support (node is a codeblock)
                 Expression::from_callback_connection(node.clone().into(), &mut lookup_ctx)
             }
+            SyntaxKind::Function => Expression::from_function(node.clone().into(), &mut lookup_ctx),
             SyntaxKind::Expression => {
                 //FIXME again: this happen for non-binding expression (i.e: model)
                 Expression::from_expression_node(node.clone().into(), &mut lookup_ctx)
@@ -129,12 +130,49 @@ pub fn resolve_expressions(
             for (prop, nr) in two_ways {
                 elem.borrow().bindings.get(&prop).unwrap().borrow_mut().two_way_bindings.push(nr);
             }
+            set_listview_row_count(elem);
             new_scope.0.pop();
             new_scope
         })
     }
 }
 
+/// When `elem` is the delegate of a ListView that has a `row-count` property, bind it to the
+/// number of rows in the model, unless it was set explicitly.
+fn set_listview_row_count(elem: &ElementRc) {
+    let (model, row_count_ref) = match &elem.borrow().repeated {
+        Some(RepeatedElementInfo {
+            is_listview: Some(ListViewInfo { row_count: Some(row_count_ref), .. }),
+            model,
+            ..
+        }) => (model.clone(), row_count_ref.clone()),
+        _ => return,
+    };
+    let listview = row_count_ref.element();
+    if listview.borrow().bindings.contains_key(row_count_ref.name()) {
+        return;
+    }
+    let row_count = match model.ty() {
+        Type::Array(_) | Type::Model => Expression::FunctionCall {
+            function: Box::new(Expression::BuiltinFunctionReference(
+                BuiltinFunction::ArrayLength,
+                None,
+            )),
+            arguments: vec![model],
+            source_location: None,
+        },
+        Type::Int32 => model,
+        Type::Float32 => Expression::Cast { from: Box::new(model), to: Type::Int32 },
+        _ => return,
+    };
+    let span = elem.borrow().to_source_location();
+    let name = row_count_ref.name().to_string();
+    listview
+        .borrow_mut()
+        .bindings
+        .insert(name, BindingExpression::new_with_span(row_count, span).into());
+}
+
 impl Expression {
     pub fn from_binding_expression_node(node: SyntaxNode, ctx: &mut LookupCtx) -> Self {
         debug_assert_eq!(node.kind(), SyntaxKind::BindingExpression);
@@ -225,7 +263,28 @@ fn from_callback_connection(
     ) -> Expression {
         ctx.arguments =
             node.DeclaredIdentifier().map(|x| identifier_text(&x).unwrap_or_default()).collect();
-        Self::from_codeblock_node(node.CodeBlock(), ctx).maybe_convert_to(
+        Self::from_codeblock_node(code_block, ctx).maybe_convert_to(
+            ctx.return_type().clone(),
+            &node,
+            ctx.diag,
+        )
+    }
+
+    fn from_function(node: syntax_nodes::Function, ctx: &mut LookupCtx) -> Expression {
+        // Functions are pure: they compute their result without changing any property
+        let code_block = node.CodeBlock();
+        for assignment in
+            code_block.descendants().filter(|n| n.kind() == SyntaxKind::SelfAssignment)
+        {
+            let assignment =
+                SyntaxNode { node: assignment, source_file: code_block.source_file.clone() };
+            ctx.diag.push_error("Functions cannot assign to properties".into(), &assignment);
+        }
+        ctx.arguments = node
+            .ArgumentDeclaration()
+            .map(|x| identifier_text(&x.DeclaredIdentifier()).unwrap_or_default())
+            .collect();
+        Self::from_codeblock_node(code_block, ctx).maybe_convert_to(
             ctx.return_type().clone(),
             &node,
             ctx.diag,
@@ -270,6 +329,7 @@ fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) ->
                         ctx.diag.push_error(e, &node);
                         Some(Self::Invalid)
                     })
+                    .map(|e| lower_relative_length(e, &node))
             })
             .or_else(|| {
                 node.child_text(SyntaxKind::ColorLiteral).map(|s| {
@@ -299,6 +359,7 @@ fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) ->
             })
             .or_else(|| node.ObjectLiteral().map(|n| Self::from_object_literal_node(n, ctx)))
             .or_else(|| node.Array().map(|n| Self::from_array_node(n, ctx)))
+            .or_else(|| node.MapLiteral().map(|n| Self::from_map_literal_node(n, ctx)))
             .or_else(|| node.CodeBlock().map(|n| Self::from_codeblock_node(n, ctx)))
             .or_else(|| node.StringTemplate().map(|n| Self::from_string_template_node(n, ctx)))
             .unwrap_or(Self::Invalid)
@@ -685,6 +746,7 @@ fn from_self_assignment_node(
             '=' => ty,
             '+' if ty == Type::String || ty.as_unit_product().is_some() => ty,
             '-' if ty.as_unit_product().is_some() => ty,
+            '+' | '-' if ty == Type::DateTime => Type::Duration,
             '/' | '*' if ty.as_unit_product().is_some() => Type::Float32,
             _ => {
                 if ty != Type::Invalid {
@@ -734,6 +796,20 @@ fn from_binary_expression_node(
             OperatorClass::LogicalOp => Type::Bool,
             OperatorClass::ArithmeticOp => {
                 let (lhs_ty, rhs_ty) = (lhs.ty(), rhs.ty());
+                if lhs_ty == Type::DateTime && (op == '+' || op == '-') {
+                    // A duration can be added to or subtracted from a date-time, and the difference
+                    // of two date-times is a duration
+                    let rhs_ty = if op == '-' && rhs_ty == Type::DateTime {
+                        Type::DateTime
+                    } else {
+                        Type::Duration
+                    };
+                    return Expression::BinaryExpression {
+                        lhs: Box::new(lhs),
+                        rhs: Box::new(rhs.maybe_convert_to(rhs_ty, &rhs_n, ctx.diag)),
+                        op,
+                    };
+                }
                 if op == '+' && (lhs_ty == Type::String || rhs_ty == Type::String) {
                     Type::String
                 } else if op == '+' || op == '-' {
@@ -844,14 +920,16 @@ fn from_index_expression_node(
     ) -> Expression {
         let (array_expr_n, index_expr_n) = node.Expression();
         let array_expr = Self::from_expression_node(array_expr_n, ctx);
+        let ty = array_expr.ty();
+        // Maps are indexed by their key
+        let index_ty = if matches!(ty, Type::Map(_)) { Type::String } else { Type::Int32 };
         let index_expr = Self::from_expression_node(index_expr_n.clone(), ctx).maybe_convert_to(
-            Type::Int32,
+            index_ty,
             &index_expr_n,
             &mut ctx.diag,
         );
 
-        let ty = array_expr.ty();
-        if !matches!(ty, Type::Array(_) | Type::Invalid) {
+        if !matches!(ty, Type::Array(_) | Type::Map(_) | Type::Invalid) {
             ctx.diag.push_error(format!("{} is not an indexable type", ty), &node);
         }
         Expression::ArrayIndex { array: Box::new(array_expr), index: Box::new(index_expr) }
@@ -897,6 +975,31 @@ fn from_array_node(node: syntax_nodes::Array, ctx: &mut LookupCtx) -> Expression
         Expression::Array { element_ty, values }
     }
 
+    fn from_map_literal_node(node: syntax_nodes::MapLiteral, ctx: &mut LookupCtx) -> Expression {
+        let mut entries: Vec<(Expression, Expression)> =
+            node.MapEntry()
+                .map(|entry| {
+                    let (key_n, value_n) = entry.Expression();
+                    let key = Expression::from_expression_node(key_n.clone(), ctx)
+                        .maybe_convert_to(Type::String, &key_n, ctx.diag);
+                    (key, Expression::from_expression_node(value_n, ctx))
+                })
+                .collect();
+
+        let value_ty =
+            Self::common_target_type_for_type_list(entries.iter().map(|(_, expr)| expr.ty()));
+
+        for (_, v) in entries.iter_mut() {
+            *v = core::mem::replace(v, Expression::Invalid).maybe_convert_to(
+                value_ty.clone(),
+                &node,
+                ctx.diag,
+            );
+        }
+
+        Expression::Map { value_ty, entries }
+    }
+
     fn from_string_template_node(
         node: syntax_nodes::StringTemplate,
         ctx: &mut LookupCtx,
@@ -979,6 +1082,36 @@ fn common_target_type_for_type_list(types: impl Iterator<Item = Type>) -> Type {
     }
 }
 
+/// The `rem`, `vw` and `vh` units depend on the window at run-time, so replace them by
+/// a multiplication with the value queried from the window
+fn lower_relative_length(expr: Expression, node: &syntax_nodes::Expression) -> Expression {
+    let (value, function) = match expr {
+        Expression::NumberLiteral(value, Unit::Rem) => {
+            (value, BuiltinFunction::GetWindowBaseFontSize)
+        }
+        Expression::NumberLiteral(value, Unit::Vw) => {
+            (value / 100., BuiltinFunction::GetWindowViewportWidth)
+        }
+        Expression::NumberLiteral(value, Unit::Vh) => {
+            (value / 100., BuiltinFunction::GetWindowViewportHeight)
+        }
+        _ => return expr,
+    };
+    let source_location = Some(node.to_source_location());
+    Expression::BinaryExpression {
+        lhs: Box::new(Expression::NumberLiteral(value, Unit::None)),
+        rhs: Box::new(Expression::FunctionCall {
+            function: Box::new(Expression::BuiltinFunctionReference(
+                function,
+                source_location.clone(),
+            )),
+            arguments: vec![],
+            source_location,
+        }),
+        op: '*',
+    }
+}
+
 fn continue_lookup_within_element(
     elem: &ElementRc,
     it: &mut impl Iterator<Item = crate::parser::SyntaxToken>,
@@ -10,14 +10,14 @@
 /// This pass make sure that the id of the elements are unique
 ///
 /// It currently does so by adding a number to the existing id
-pub fn assign_unique_id(doc: &Document) {
+pub fn assign_unique_id(root_component: &Rc<Component>) {
     let mut count = 0;
-    assign_unique_id_in_component(&doc.root_component, &mut count);
-    for c in &doc.root_component.used_types.borrow().sub_components {
+    assign_unique_id_in_component(root_component, &mut count);
+    for c in &root_component.used_types.borrow().sub_components {
         assign_unique_id_in_component(c, &mut count);
     }
 
-    rename_globals(&doc.root_component, count);
+    rename_globals(root_component, count);
 }
 
 fn assign_unique_id_in_component(component: &Rc<Component>, count: &mut u32) {
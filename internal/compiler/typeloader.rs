@@ -7,7 +7,7 @@
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use crate::diagnostics::{BuildDiagnostics, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticLevel, SourceEdit, Spanned};
 use crate::object_tree::{self, Document};
 use crate::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxToken};
 use crate::typeregister::TypeRegister;
@@ -20,8 +20,113 @@ pub struct LoadedDocuments {
     /// maps from the canonical file name to the object_tree::Document
     docs: HashMap<PathBuf, Document>,
     currently_loading: HashSet<PathBuf>,
+    /// maps from the canonical file name to the canonical paths of its .slint imports, except the
+    /// builtin ones, by the file name written in the import
+    imports: HashMap<PathBuf, HashMap<String, PathBuf>>,
 }
 
+/// A set of .slint documents that were parsed once, and that can be linked in other compilations
+/// with [`CompilerConfiguration::libraries`] so that the documents importing them don't parse
+/// their sources again.
+///
+/// Create it with [`crate::compile_library`]. The `@if` blocks of the documents and the paths of
+/// their imports were resolved with the configuration of that compilation, so the include and
+/// library paths of the compilations that link the library don't change them. Only the imports of
+/// the builtin files, such as `std-widgets.slint`, follow the style of each compilation.
+///
+/// The types and the expressions of the documents are resolved again in each compilation, as
+/// the passes of a compilation modify the components it uses.
+///
+/// A library can be saved with [`Self::to_bytes`] and loaded again with [`Self::from_bytes`],
+/// for example to keep it in a cache between two runs of a build.
+#[derive(Default)]
+pub struct CompiledLibrary {
+    /// maps from the canonical file name to the document
+    docs: HashMap<PathBuf, LibraryDocument>,
+}
+
+struct LibraryDocument {
+    node: syntax_nodes::Document,
+    /// maps from the file name written in an import to its canonical path
+    imports: HashMap<String, PathBuf>,
+}
+
+impl CompiledLibrary {
+    /// The canonical paths of the documents in this library
+    pub fn files(&self) -> impl Iterator<Item = &PathBuf> + '_ {
+        self.docs.keys()
+    }
+
+    /// Serializes the library, to be loaded again with [`Self::from_bytes`].
+    ///
+    /// The data contains the source code of the documents, with the inactive `@if` branches
+    /// replaced by whitespace, and the resolved paths of their imports.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        fn write_str(out: &mut Vec<u8>, s: &str) {
+            out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            out.extend_from_slice(s.as_bytes());
+        }
+        let mut out = LIBRARY_MAGIC.to_vec();
+        let mut docs = self.docs.iter().collect::<Vec<_>>();
+        docs.sort_by(|a, b| a.0.cmp(b.0));
+        out.extend_from_slice(&(docs.len() as u32).to_le_bytes());
+        for (path, doc) in docs {
+            write_str(&mut out, &path.to_string_lossy());
+            write_str(&mut out, &doc.node.text().to_string());
+            let mut imports = doc.imports.iter().collect::<Vec<_>>();
+            imports.sort();
+            out.extend_from_slice(&(imports.len() as u32).to_le_bytes());
+            for (name, path) in imports {
+                write_str(&mut out, name);
+                write_str(&mut out, &path.to_string_lossy());
+            }
+        }
+        out
+    }
+
+    /// Loads a library saved with [`Self::to_bytes`]. The documents are parsed again.
+    ///
+    /// Returns None if the data is not a library saved by this version of the compiler, or if one of
+    /// its documents has syntax errors.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        struct Reader<'a>(&'a [u8]);
+        impl<'a> Reader<'a> {
+            fn u32(&mut self) -> Option<usize> {
+                let (value, rest) = (self.0.get(..4)?, self.0.get(4..)?);
+                self.0 = rest;
+                Some(u32::from_le_bytes(value.try_into().ok()?) as usize)
+            }
+            fn str(&mut self) -> Option<&'a str> {
+                let len = self.u32()?;
+                let (value, rest) = (self.0.get(..len)?, self.0.get(len..)?);
+                self.0 = rest;
+                std::str::from_utf8(value).ok()
+            }
+        }
+
+        let mut reader = Reader(data.strip_prefix(LIBRARY_MAGIC)?);
+        let mut docs = HashMap::new();
+        for _ in 0..reader.u32()? {
+            let path = PathBuf::from(reader.str()?);
+            let source = reader.str()?;
+            let mut imports = HashMap::new();
+            for _ in 0..reader.u32()? {
+                imports.insert(reader.str()?.to_owned(), PathBuf::from(reader.str()?));
+            }
+            let mut diag = BuildDiagnostics::default();
+            let node = crate::parser::parse(source.into(), Some(&path), &mut diag);
+            if diag.has_error() {
+                return None;
+            }
+            docs.insert(path, LibraryDocument { node: node.into(), imports });
+        }
+        reader.0.is_empty().then(|| Self { docs })
+    }
+}
+
+/// The start of the data of [`CompiledLibrary::to_bytes`], followed by the version of the format
+const LIBRARY_MAGIC: &[u8] = b"slint-library\0\x01";
+
 pub struct ImportedTypes {
     pub import_token: SyntaxToken,
     pub imported_types: syntax_nodes::ImportSpecifier,
@@ -193,6 +298,16 @@ pub fn resolve_import_path(
         let referencing_file_or_url =
             import_token.and_then(|tok| tok.source_file().map(|s| s.path()));
 
+        if let Some(library_import) = referencing_file_or_url
+            .and_then(|file| self.library_import(file, maybe_relative_path_or_url))
+        {
+            return (library_import, None);
+        }
+
+        if let Some(library_file) = self.resolve_library_path(maybe_relative_path_or_url) {
+            return (library_file, None);
+        }
+
         self.find_file_in_include_path(referencing_file_or_url, maybe_relative_path_or_url)
             .unwrap_or_else(|| {
                 (
@@ -219,6 +334,26 @@ pub fn resolve_import_path(
             })
     }
 
+    /// Resolve a path such as `@mylib/widgets.slint` with the library paths of the configuration.
+    /// Returns None if the path does not start with the name of a known library.
+    fn resolve_library_path(&self, path: &str) -> Option<PathBuf> {
+        let path = path.strip_prefix('@')?;
+        let (library, file) = path.split_once('/').map_or((path, None), |(l, f)| (l, Some(f)));
+        let library_path = self.compiler_config.library_paths.get(library)?;
+        let resolved = match file {
+            None => library_path.clone(),
+            Some(file) => {
+                let library_path_str = library_path.to_string_lossy();
+                if library_path_str.contains("://") {
+                    format!("{}/{}", library_path_str.trim_end_matches('/'), file).into()
+                } else {
+                    library_path.join(file)
+                }
+            }
+        };
+        Some(dunce::canonicalize(&resolved).unwrap_or(resolved))
+    }
+
     async fn ensure_document_loaded<'b>(
         &'b mut self,
         file_to_import: &'b str,
@@ -229,6 +364,19 @@ async fn ensure_document_loaded<'b>(
 
         let path_canon = dunce::canonicalize(&path).unwrap_or_else(|_| path.to_owned());
 
+        // Remember the import, so that a library keeps the path it resolves to
+        if let (None, Some(source_file)) =
+            (is_builtin, import_token.as_ref().and_then(|t| t.source_file()))
+        {
+            let importing_file = dunce::canonicalize(source_file.path())
+                .unwrap_or_else(|_| source_file.path().to_owned());
+            self.all_documents
+                .imports
+                .entry(importing_file)
+                .or_default()
+                .insert(file_to_import.to_owned(), path_canon.clone());
+        }
+
         if self.all_documents.docs.get(path_canon.as_path()).is_some() {
             return Some(path_canon);
         }
@@ -243,6 +391,13 @@ async fn ensure_document_loaded<'b>(
             return None;
         }
 
+        if let Some(doc_node) = self.library_document(&path_canon) {
+            self.load_parsed_file(&path_canon, doc_node, false, diagnostics).await;
+            let _ok = self.all_documents.currently_loading.remove(path_canon.as_path());
+            assert!(_ok);
+            return Some(path_canon);
+        }
+
         let source_code_result = if let Some(builtin) = builtin {
             Ok(String::from_utf8(builtin)
                 .expect("internal error: embedded file is not UTF-8 source code"))
@@ -256,13 +411,21 @@ async fn ensure_document_loaded<'b>(
         let source_code = match source_code_result {
             Ok(source) => source,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                diagnostics.push_error(
-                    format!(
+                let unknown_library = file_to_import
+                    .strip_prefix('@')
+                    .map(|path| path.split('/').next().unwrap_or(path))
+                    .filter(|library| !self.compiler_config.library_paths.contains_key(*library));
+                let message = match unknown_library {
+                    Some(library) => format!(
+                        "Cannot find requested import \"{}\": there is no library named \"{}\" in the library paths",
+                        file_to_import, library
+                    ),
+                    None => format!(
                         "Cannot find requested import \"{}\" in the include search path",
                         file_to_import
                     ),
-                    &import_token,
-                );
+                };
+                diagnostics.push_error(message, &import_token);
                 return None;
             }
             Err(err) => {
@@ -280,7 +443,8 @@ async fn ensure_document_loaded<'b>(
         Some(path_canon)
     }
 
-    /// Load a file, and its dependency not run the passes.
+    /// Load a file and its dependencies, and run the import passes on them, but not the other
+    /// passes of the compilation.
     ///
     /// the path must be the canonical path
     pub async fn load_file(
@@ -291,9 +455,25 @@ pub async fn load_file(
         is_builtin: bool,
         diagnostics: &mut BuildDiagnostics,
     ) {
+        let dependency_doc = crate::parser::parse(source_code, Some(source_path), diagnostics);
         let dependency_doc: syntax_nodes::Document =
-            crate::parser::parse(source_code, Some(source_path), diagnostics).into();
+            crate::conditional_blocks::apply_conditional_blocks(
+                dependency_doc,
+                self.compiler_config,
+                diagnostics,
+            )
+            .into();
+        self.load_parsed_file(path, dependency_doc, is_builtin, diagnostics).await;
+    }
 
+    /// Like [`Self::load_file`], for a file that was already parsed.
+    async fn load_parsed_file(
+        &mut self,
+        path: &Path,
+        dependency_doc: syntax_nodes::Document,
+        is_builtin: bool,
+        diagnostics: &mut BuildDiagnostics,
+    ) {
         let dependency_registry =
             Rc::new(RefCell::new(TypeRegister::new(&self.global_type_registry)));
         dependency_registry.borrow_mut().expose_internal_types = is_builtin;
@@ -341,9 +521,15 @@ fn load_dependency<'b>(
             let mut file = import.file.as_str();
             if file == "sixtyfps_widgets.60" {
                 file = "std-widgets.slint";
-                build_diagnostics.push_warning(
+                build_diagnostics.push_diagnostic_with_edits(
                     "\"sixtyfps_widgets.60\" was renamed \"std-widgets.slint\". Use of the old file name is deprecated".into(),
                     &import.import_token,
+                    DiagnosticLevel::Warning,
+                    vec![SourceEdit::new(
+                        &import.import_token,
+                        import.import_token.text().len(),
+                        "\"std-widgets.slint\"",
+                    )],
                 );
             }
 
@@ -392,6 +578,52 @@ fn load_dependency<'b>(
         })
     }
 
+    /// Load a file and its dependencies, so that they are part of [`Self::compiled_library`]
+    pub async fn load_library_file(
+        &mut self,
+        path: &Path,
+        diagnostics: &mut BuildDiagnostics,
+    ) -> Option<PathBuf> {
+        self.ensure_document_loaded(&path.to_string_lossy(), None, diagnostics).await
+    }
+
+    /// Return a library with the documents loaded so far, except the builtin ones
+    pub fn compiled_library(&self) -> CompiledLibrary {
+        CompiledLibrary {
+            docs: self
+                .all_documents
+                .docs
+                .iter()
+                .filter(|(path, _)| !path.starts_with("builtin:/"))
+                .filter_map(|(path, doc)| {
+                    let imports = self.all_documents.imports.get(path).cloned().unwrap_or_default();
+                    Some((path.clone(), LibraryDocument { node: doc.node.clone()?, imports }))
+                })
+                .collect(),
+        }
+    }
+
+    /// Return the syntax tree of the document if it is part of one of the linked libraries
+    fn library_document(&self, path: &Path) -> Option<syntax_nodes::Document> {
+        self.compiler_config
+            .libraries
+            .iter()
+            .find_map(|library| library.docs.get(path))
+            .map(|doc| doc.node.clone())
+    }
+
+    /// Return the path that an import of a document of one of the linked libraries resolved to
+    /// when the library was compiled
+    fn library_import(&self, importing_file: &Path, import: &str) -> Option<PathBuf> {
+        let importing_file =
+            dunce::canonicalize(importing_file).unwrap_or_else(|_| importing_file.to_owned());
+        self.compiler_config
+            .libraries
+            .iter()
+            .find_map(|library| library.docs.get(&importing_file)?.imports.get(import))
+            .cloned()
+    }
+
     /// Lookup a filename and try to find the absolute filename based on the include path or
     /// the current file directory
     pub fn find_file_in_include_path(
@@ -418,7 +650,9 @@ pub fn find_file_in_include_path(
             .find_map(|include_dir| {
                 let candidate = include_dir.join(file_to_import);
                 crate::fileaccess::load_file(&candidate)
-                    .map(|virtual_file| (candidate, virtual_file.builtin_contents))
+                    .map(|virtual_file| (candidate.clone(), virtual_file.builtin_contents))
+                    // The sources of the linked libraries are not needed
+                    .or_else(|| self.library_document(&candidate).map(|_| (candidate, None)))
             })
     }
 
@@ -558,6 +792,118 @@ fn test_load_from_callback_ok() {
     assert!(!build_diagnostics.has_error());
 }
 
+#[test]
+fn test_library_paths() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let loaded = Rc::new(RefCell::new(Vec::new()));
+    let loaded_ = loaded.clone();
+    let mut compiler_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.style = Some("fluent".into());
+    compiler_config.library_paths.insert("helpers".into(), test_source_path.join("incpath"));
+    compiler_config.library_paths.insert(
+        "helper-file".into(),
+        test_source_path.join("incpath").join("local_helper_type.slint"),
+    );
+    compiler_config.library_paths.insert("remote".into(), "https://example.com/lib/".into());
+    compiler_config.open_import_fallback = Some(Rc::new(move |path| {
+        // The local files are read from the file system
+        let is_remote = path.starts_with("https://");
+        if is_remote {
+            loaded_.borrow_mut().push(path);
+        }
+        Box::pin(async move { is_remote.then(|| Ok("export Remote := Rectangle {} ".to_owned())) })
+    }));
+
+    let mut test_diags = crate::diagnostics::BuildDiagnostics::default();
+    let doc_node = crate::parser::parse(
+        r#"
+import { SomeRect } from "@helpers/local_helper_type.slint";
+import { SomeRect as SameRect } from "@helper-file";
+import { Remote } from "@remote/widgets.slint";
+X := SomeRect {}
+"#
+        .into(),
+        Some(std::path::Path::new("HELLO")),
+        &mut test_diags,
+    );
+
+    let doc_node: syntax_nodes::Document = doc_node.into();
+    let global_registry = TypeRegister::builtin();
+    let registry = Rc::new(RefCell::new(TypeRegister::new(&global_registry)));
+    let mut build_diagnostics = BuildDiagnostics::default();
+    let mut loader = TypeLoader::new(global_registry, &compiler_config, &mut build_diagnostics);
+    spin_on::spin_on(loader.load_dependencies_recursively(
+        &doc_node,
+        &mut build_diagnostics,
+        &registry,
+    ));
+    assert!(!test_diags.has_error());
+    assert!(!build_diagnostics.has_error());
+    // Both imports of the local library load the same file
+    assert_eq!(loader.all_files().count(), 2);
+    assert_eq!(*loaded.borrow(), vec!["https://example.com/lib/widgets.slint".to_string()]);
+}
+
+#[test]
+fn test_library_keeps_resolved_imports() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let mut library_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    library_config.style = Some("fluent".into());
+    library_config.library_paths.insert("helpers".into(), test_source_path.join("incpath"));
+    library_config.open_import_fallback = Some(Rc::new(move |path| {
+        let source = (path == "/lib/uses_helper.slint").then(|| {
+            Ok(r#"import { SomeRect } from "@helpers/local_helper_type.slint";
+                  export Lib := SomeRect {}"#
+                .to_owned())
+        });
+        Box::pin(async move { source })
+    }));
+    let (library, diag) = spin_on::spin_on(crate::compile_library(
+        &["/lib/uses_helper.slint".into()],
+        Default::default(),
+        library_config,
+    ));
+    assert!(!diag.has_error());
+    assert_eq!(library.files().count(), 2);
+    let library = CompiledLibrary::from_bytes(&library.to_bytes()).unwrap();
+
+    // The compilation that links the library does not know the "helpers" library path
+    let mut compiler_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.style = Some("fluent".into());
+    compiler_config.libraries = vec![Rc::new(library)];
+    let mut test_diags = crate::diagnostics::BuildDiagnostics::default();
+    let doc_node = crate::parser::parse(
+        r#"
+import { Lib } from "/lib/uses_helper.slint";
+X := Lib {}
+"#
+        .into(),
+        Some(std::path::Path::new("HELLO")),
+        &mut test_diags,
+    );
+
+    let doc_node: syntax_nodes::Document = doc_node.into();
+    let global_registry = TypeRegister::builtin();
+    let registry = Rc::new(RefCell::new(TypeRegister::new(&global_registry)));
+    let mut build_diagnostics = BuildDiagnostics::default();
+    let mut loader = TypeLoader::new(global_registry, &compiler_config, &mut build_diagnostics);
+    spin_on::spin_on(loader.load_dependencies_recursively(
+        &doc_node,
+        &mut build_diagnostics,
+        &registry,
+    ));
+    assert!(!test_diags.has_error());
+    assert!(!build_diagnostics.has_error());
+    assert_eq!(loader.all_files().count(), 2);
+}
+
 #[test]
 fn test_manual_import() {
     let mut compiler_config =
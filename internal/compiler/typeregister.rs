@@ -52,6 +52,7 @@
             ])
             .collect(),
             default_value: 0,
+            node: None,
         });
 
     pub static LAYOUT_ALIGNMENT_ENUM: Rc<Enumeration> =
@@ -61,6 +62,30 @@
                 ["stretch", "center", "start", "end", "space-between", "space-around"]
             ).map(String::from).collect(),
             default_value: 0,
+            node: None,
+        });
+
+    pub static ACCESSIBLE_ROLE_ENUM: Rc<Enumeration> =
+        Rc::new(Enumeration {
+            name: "AccessibleRole".into(),
+            values: IntoIterator::into_iter([
+                "none",
+                "button",
+                "checkbox",
+                "combobox",
+                "list",
+                "list-item",
+                "progress-indicator",
+                "slider",
+                "spinbox",
+                "tab",
+                "text",
+                "text-input",
+            ])
+            .map(String::from)
+            .collect(),
+            default_value: 0,
+            node: None,
         });
 
     pub static PATH_EVENT_ENUM: Rc<Enumeration> =
@@ -70,6 +95,7 @@
             ["begin", "line", "quadratic", "cubic", "end_open", "end_closed"]
         ).map(String::from).collect(),
         default_value: 0,
+        node: None,
     });
 }
 
@@ -79,6 +105,9 @@
     ("visible", Type::Bool), // ("enabled", Type::Bool),
 ];
 
+pub(crate) const RESERVED_ACCESSIBILITY_PROPERTIES: &[(&str, Type)] =
+    &[("accessible-label", Type::String), ("accessible-value", Type::String)];
+
 pub(crate) const RESERVED_DROP_SHADOW_PROPERTIES: &[(&str, Type)] = &[
     ("drop-shadow-offset-x", Type::LogicalLength),
     ("drop-shadow-offset-y", Type::LogicalLength),
@@ -93,11 +122,14 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
         .chain(RESERVED_LAYOUT_PROPERTIES.iter())
         .chain(RESERVED_OTHER_PROPERTIES.iter())
         .chain(RESERVED_DROP_SHADOW_PROPERTIES.iter())
+        .chain(RESERVED_ACCESSIBILITY_PROPERTIES.iter())
         .map(|(k, v)| (*k, v.clone()))
         .chain(IntoIterator::into_iter([
             ("forward-focus", Type::ElementReference),
             ("focus", BuiltinFunction::SetFocusItem.ty()),
             ("dialog-button-role", Type::Enumeration(DIALOG_BUTTON_ROLE_ENUM.with(|e| e.clone()))),
+            ("accessible-role", Type::Enumeration(ACCESSIBLE_ROLE_ENUM.with(|e| e.clone()))),
+            ("accessible-action-default", Type::Callback { return_type: None, args: vec![] }),
         ]))
 }
 
@@ -174,6 +206,7 @@ pub fn builtin() -> Rc<RefCell<Self>> {
         register.insert_type(Type::LogicalLength);
         register.insert_type(Type::Color);
         register.insert_type(Type::Duration);
+        register.insert_type(Type::DateTime);
         register.insert_type(Type::Image);
         register.insert_type(Type::Bool);
         register.insert_type(Type::Model);
@@ -181,6 +214,7 @@ pub fn builtin() -> Rc<RefCell<Self>> {
         register.insert_type(Type::Easing);
         register.insert_type(Type::Angle);
         register.insert_type(Type::Brush);
+        register.insert_type(Type::ComponentFactory);
 
         let mut declare_enum = |name: &str, values: &[&str]| {
             register.insert_type_with_name(
@@ -188,6 +222,7 @@ pub fn builtin() -> Rc<RefCell<Self>> {
                     name: name.to_owned(),
                     values: values.iter().cloned().map(String::from).collect(),
                     default_value: 0,
+                    node: None,
                 })),
                 name.to_owned(),
             );
@@ -201,7 +236,7 @@ pub fn builtin() -> Rc<RefCell<Self>> {
         declare_enum("ImageRendering", &["smooth", "pixelated"]);
         declare_enum("EventResult", &["reject", "accept"]);
         declare_enum("FillRule", &["nonzero", "evenodd"]);
-        declare_enum("InputType", &["text", "password"]);
+        declare_enum("InputType", &["text", "password", "number"]);
         declare_enum(
             "MouseCursor",
             &[
@@ -244,10 +279,18 @@ pub fn builtin() -> Rc<RefCell<Self>> {
         );
         declare_enum("PointerEventKind", &["cancel", "down", "up"]);
         declare_enum("PointerEventButton", &["none", "left", "right", "middle"]);
+        macro_rules! declare_logical_key_enum {
+            ($($char:literal # $name:ident # $($_qt:ident)|* # $($_winit:ident)|* ;)*) => {
+                declare_enum("LogicalKey", &["Other", $(stringify!($name)),*]);
+            };
+        }
+        i_slint_common::for_each_special_keys!(declare_logical_key_enum);
         DIALOG_BUTTON_ROLE_ENUM
             .with(|e| register.insert_type_with_name(Type::Enumeration(e.clone()), e.name.clone()));
         LAYOUT_ALIGNMENT_ENUM
             .with(|e| register.insert_type_with_name(Type::Enumeration(e.clone()), e.name.clone()));
+        ACCESSIBLE_ROLE_ENUM
+            .with(|e| register.insert_type_with_name(Type::Enumeration(e.clone()), e.name.clone()));
 
         register.supported_property_animation_types.insert(Type::Float32.to_string());
         register.supported_property_animation_types.insert(Type::Int32.to_string());
@@ -276,6 +319,14 @@ pub fn builtin() -> Rc<RefCell<Self>> {
                     "show".into(),
                     Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, None),
                 );
+                Rc::get_mut(b).unwrap().properties.insert(
+                    "close".into(),
+                    BuiltinPropertyInfo::new(BuiltinFunction::ClosePopupWindow.ty()),
+                );
+                Rc::get_mut(b).unwrap().member_functions.insert(
+                    "close".into(),
+                    Expression::BuiltinFunctionReference(BuiltinFunction::ClosePopupWindow, None),
+                );
             }
             _ => unreachable!(),
         };
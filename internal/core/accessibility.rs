@@ -0,0 +1,206 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module builds the tree of accessible elements of a component, which is the information
+that assistive technologies such as screen readers need about the user interface. It also
+holds the accessibility preferences of the user, which the styles follow.
+*/
+
+use crate::component::ComponentRc;
+use crate::graphics::{Point, Rect};
+use crate::item_tree::{ItemVisitorResult, TraversalOrder};
+use crate::items::{Accessible, AccessibleRole, Clip, ItemRc, ItemRef, ItemWeak};
+use crate::properties::Property;
+use crate::SharedString;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::pin::Pin;
+
+#[cfg(all(not(feature = "std"), feature = "unsafe_single_core"))]
+use crate::unsafe_single_core::thread_local;
+
+/// An element of the accessibility tree, created for each element that has one of the
+/// `accessible-role`, `accessible-label` or `accessible-value` properties set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    /// The value of the `accessible-role` property.
+    pub role: AccessibleRole,
+    /// The value of the `accessible-label` property.
+    pub label: SharedString,
+    /// The value of the `accessible-value` property.
+    pub value: SharedString,
+    /// The geometry of the element, in logical coordinates relative to the window.
+    pub geometry: Rect,
+    /// The accessible elements that are contained in this element.
+    pub children: Vec<AccessibilityNode>,
+    /// The Accessible item of the element, whose `action-default` callback activates it.
+    item: ItemWeak,
+}
+
+impl AccessibilityNode {
+    /// Invokes the `accessible-action-default` callback of the element, if it still exists.
+    pub(crate) fn activate(&self) {
+        if let Some(item) = self.item.upgrade() {
+            if let Some(accessible) = ItemRef::downcast_pin::<Accessible>(item.borrow()) {
+                accessible.action_default.call(&());
+            }
+        }
+    }
+}
+
+/// Returns the accessible elements of the component that have no accessible ancestor,
+/// in the order in which they are declared.
+pub(crate) fn build_accessibility_tree(component: &ComponentRc) -> Vec<AccessibilityNode> {
+    #[derive(Clone, Copy)]
+    struct State {
+        /// Index of the nearest accessible ancestor in `nodes`
+        parent: Option<usize>,
+        offset: Point,
+        hidden: bool,
+    }
+
+    // Nodes are collected with the index of their parent, and assembled into a tree afterwards
+    let mut nodes: Vec<(Option<usize>, AccessibilityNode)> = Vec::new();
+    crate::item_tree::visit_items(
+        component,
+        TraversalOrder::BackToFront,
+        |component, item, index, state: &State| {
+            let geometry = item.as_ref().geometry().translate(state.offset.to_vector());
+            let mut state = State { offset: geometry.origin, ..*state };
+            if let Some(clip) = ItemRef::downcast_pin::<Clip>(item) {
+                // The elements with `visible: false` are placed in an empty clipping item
+                if clip.clip() && (geometry.width() <= 0. || geometry.height() <= 0.) {
+                    state.hidden = true;
+                }
+            }
+            if !state.hidden {
+                if let Some(accessible) = ItemRef::downcast_pin::<Accessible>(item) {
+                    nodes.push((
+                        state.parent,
+                        AccessibilityNode {
+                            role: accessible.role(),
+                            label: accessible.label(),
+                            value: accessible.value(),
+                            geometry,
+                            children: Vec::new(),
+                            item: ItemRc::new(component.clone(), index).downgrade(),
+                        },
+                    ));
+                    state.parent = Some(nodes.len() - 1);
+                }
+            }
+            ItemVisitorResult::Continue(state)
+        },
+        State { parent: None, offset: Point::default(), hidden: false },
+    );
+
+    // Children always come after their parent, so going backwards moves complete nodes
+    let mut roots = Vec::new();
+    while let Some((parent, node)) = nodes.pop() {
+        match parent {
+            Some(parent) => nodes[parent].1.children.insert(0, node),
+            None => roots.insert(0, node),
+        }
+    }
+    roots
+}
+
+/// Returns the first node of `nodes`, searched depth-first, that has the given role and label.
+pub(crate) fn find_accessible_element<'a>(
+    nodes: &'a [AccessibilityNode],
+    role: AccessibleRole,
+    label: &str,
+) -> Option<&'a AccessibilityNode> {
+    nodes.iter().find_map(|node| {
+        if node.role == role && node.label.as_str() == label {
+            Some(node)
+        } else {
+            find_accessible_element(&node.children, role, label)
+        }
+    })
+}
+
+/// The preferences of the user about how the user interface is presented, as set in the
+/// accessibility settings of the system, or by the application with
+/// [`set_accessibility_preferences()`](crate::api::set_accessibility_preferences).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AccessibilityPreferences {
+    /// The user needs a high contrast between the colors, such as the ones of a text and of
+    /// its background.
+    pub high_contrast: bool,
+    /// The user prefers to avoid the animations that are not essential.
+    pub reduced_motion: bool,
+}
+
+thread_local!(
+    /// The preferences detected by the backend
+    static SYSTEM_PREFERENCES: Pin<Box<Property<AccessibilityPreferences>>> = Box::pin(Default::default())
+);
+thread_local!(
+    /// The preferences set by the application, which replace the ones of the system
+    static OVERRIDDEN_PREFERENCES: Pin<Box<Property<Option<AccessibilityPreferences>>>> =
+        Box::pin(Default::default())
+);
+
+/// Sets the preferences that the backend detected from the settings of the system.
+pub fn set_system_accessibility_preferences(preferences: AccessibilityPreferences) {
+    SYSTEM_PREFERENCES.with(|p| p.as_ref().set(preferences))
+}
+
+/// Replaces the preferences of the system with these, or follows the system again with `None`.
+pub fn set_accessibility_preferences_override(preferences: Option<AccessibilityPreferences>) {
+    OVERRIDDEN_PREFERENCES.with(|p| p.as_ref().set(preferences))
+}
+
+/// Returns the preferences in effect. The binding that calls this function is evaluated again
+/// when they change.
+pub fn accessibility_preferences() -> AccessibilityPreferences {
+    OVERRIDDEN_PREFERENCES
+        .with(|p| p.as_ref().get())
+        .unwrap_or_else(|| SYSTEM_PREFERENCES.with(|p| p.as_ref().get()))
+}
+
+/// The implementation of the `high-contrast()` function
+pub fn high_contrast() -> bool {
+    accessibility_preferences().high_contrast
+}
+
+/// The implementation of the `reduced-motion()` function
+pub fn reduced_motion() -> bool {
+    accessibility_preferences().reduced_motion
+}
+
+#[cfg(feature = "ffi")]
+pub(crate) mod ffi {
+    /// Implementation of the `high-contrast()` function for the C++ generated code
+    #[no_mangle]
+    pub extern "C" fn slint_high_contrast() -> bool {
+        super::high_contrast()
+    }
+
+    /// Implementation of the `reduced-motion()` function for the C++ generated code
+    #[no_mangle]
+    pub extern "C" fn slint_reduced_motion() -> bool {
+        super::reduced_motion()
+    }
+}
+
+#[test]
+fn test_accessibility_preferences() {
+    use crate::properties::PropertyTracker;
+    let tracker = Box::pin(PropertyTracker::default());
+    assert!(!tracker.as_ref().evaluate(high_contrast));
+    let system = AccessibilityPreferences { high_contrast: true, ..Default::default() };
+    set_system_accessibility_preferences(system);
+    assert!(tracker.is_dirty());
+    assert_eq!(accessibility_preferences(), system);
+
+    let overridden = AccessibilityPreferences { reduced_motion: true, ..Default::default() };
+    set_accessibility_preferences_override(Some(overridden));
+    assert!(!high_contrast());
+    assert!(reduced_motion());
+    set_accessibility_preferences_override(None);
+    assert!(high_contrast());
+}
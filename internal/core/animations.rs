@@ -5,7 +5,9 @@
 //! The animation system
 
 use alloc::boxed::Box;
-use core::cell::Cell;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
 
 #[cfg(feature = "std")]
 use lyon_algorithms::geom::cubic_bezier;
@@ -143,8 +145,23 @@ pub fn solve_t_for_x(&self, x: S, t_range: core::ops::Range<S>, tolerance: S) ->
 pub enum EasingCurve {
     /// The linear curve
     Linear,
-    /// A Cubic bezier curve, with its 4 parameter
+    /// A Cubic bezier curve, with its 4 parameter: the coordinates `[x1, y1, x2, y2]` of the two
+    /// control points, as in CSS. `x1` and `x2` must be between 0 and 1, otherwise the curve is linear.
     CubicBezier([f32; 4]),
+    /// The motion of a damped spring, from its initial position until it settles on the target.
+    ///
+    /// The whole motion of the spring is stretched over the duration of the animation.
+    Spring {
+        /// The mass of the object attached to the spring. Must be positive.
+        mass: f32,
+        /// The stiffness of the spring. Must be positive.
+        stiffness: f32,
+        /// The damping of the spring. Lower values give more oscillations.
+        damping: f32,
+        /// The initial velocity of the object, in distance per second relative to the
+        /// distance between the start and the end values
+        initial_velocity: f32,
+    },
     //Custom(Box<dyn Fn(f32) -> f32>),
 }
 
@@ -211,11 +228,20 @@ fn duration_since_start() -> core::time::Duration {
     }
 }
 
+/// A callback that is invoked once a property animation has run to completion.
+pub type AnimationFinishedCallback = Rc<dyn Fn()>;
+
 /// The AnimationDriver
 pub struct AnimationDriver {
     /// Indicate whether there are any active animations that require a future call to update_animations.
     active_animations: Cell<bool>,
     global_instant: core::pin::Pin<Box<crate::Property<Instant>>>,
+    /// The animations that have a finished callback, polled on every call to update_animations.
+    watched_animations: RefCell<Vec<Box<dyn FnMut() -> bool>>>,
+    /// The durations and delays of all animations are divided by this factor
+    speed_factor: Cell<f32>,
+    /// When false, all the animations complete immediately
+    animations_enabled: Cell<bool>,
 }
 
 impl Default for AnimationDriver {
@@ -226,6 +252,9 @@ fn default() -> Self {
                 Instant::default(),
                 "i_slint_core::AnimationDriver::global_instant",
             )),
+            watched_animations: Default::default(),
+            speed_factor: Cell::new(1.),
+            animations_enabled: Cell::new(true),
         }
     }
 }
@@ -233,11 +262,23 @@ fn default() -> Self {
 impl AnimationDriver {
     /// Iterates through all animations based on the new time tick and updates their state. This should be called by
     /// the windowing system driver for every frame.
+    ///
+    /// This also invokes the finished callbacks of the animations that completed by the new time tick, whether
+    /// or not their property is read.
     pub fn update_animations(&self, new_tick: Instant) {
         if self.global_instant.as_ref().get_untracked() != new_tick {
             self.active_animations.set(false);
             self.global_instant.as_ref().set(new_tick);
         }
+        let mut watched = core::mem::take(&mut *self.watched_animations.borrow_mut());
+        watched.retain_mut(|poll| !poll());
+        let mut watched_animations = self.watched_animations.borrow_mut();
+        // Keep the animations that were started from a finished callback
+        watched.append(&mut watched_animations);
+        *watched_animations = watched;
+        if !watched_animations.is_empty() {
+            self.set_has_active_animations();
+        }
     }
 
     /// Returns true if there are any active or ready animations. This is used by the windowing system to determine
@@ -250,6 +291,41 @@ pub fn has_active_animations(&self) -> bool {
     pub fn set_has_active_animations(&self) {
         self.active_animations.set(true);
     }
+
+    /// Register an animation to be polled on every call to [`Self::update_animations`], until `poll`
+    /// returns true.
+    ///
+    /// This is used to invoke the finished callback of an animation when the time tick reaches its end,
+    /// even if the animated property is not read anymore.
+    pub fn watch_animation(&self, poll: Box<dyn FnMut() -> bool>) {
+        self.watched_animations.borrow_mut().push(poll);
+        self.set_has_active_animations();
+    }
+
+    /// Returns the factor by which the speed of all the animations is multiplied.
+    pub fn speed_factor(&self) -> f32 {
+        self.speed_factor.get()
+    }
+
+    /// Change the speed of all the animations. The durations and delays of the animations are divided
+    /// by this factor: 2 makes the animations twice as fast, and 0.5 twice as slow.
+    /// A factor that is not greater than 0 completes the animations immediately.
+    ///
+    /// Animations that are already running continue from their current value at the new speed.
+    pub fn set_speed_factor(&self, factor: f32) {
+        self.speed_factor.set(factor);
+    }
+
+    /// Returns false if the animations were disabled with [`Self::set_animations_enabled`]
+    pub fn animations_enabled(&self) -> bool {
+        self.animations_enabled.get()
+    }
+
+    /// When disabled, animated properties are directly set to their target value.
+    pub fn set_animations_enabled(&self, enabled: bool) {
+        self.animations_enabled.set(enabled);
+    }
+
     /// The current instant that is to be used for animation
     /// using this function register the current binding as a dependency
     pub fn current_tick(&self) -> Instant {
@@ -272,12 +348,22 @@ pub fn current_tick() -> Instant {
     CURRENT_ANIMATION_DRIVER.with(|driver| driver.current_tick())
 }
 
+/// Same as [`current_tick`], but also tell the animation driver that the bindings depending on it
+/// need to be re-evaluated for the next frame.
+/// This is the implementation of the `animation-tick()` function
+pub fn animation_tick() -> u64 {
+    CURRENT_ANIMATION_DRIVER.with(|driver| {
+        driver.set_has_active_animations();
+        driver.current_tick().0
+    })
+}
+
 /// map a value between 0 and 1 to another value between 0 and 1 according to the curve
 pub fn easing_curve(curve: &EasingCurve, value: f32) -> f32 {
     match curve {
         EasingCurve::Linear => value,
         EasingCurve::CubicBezier([a, b, c, d]) => {
-            if !(0.0..=1.0).contains(a) && !(0.0..=1.0).contains(c) {
+            if !(0.0..=1.0).contains(a) || !(0.0..=1.0).contains(c) {
                 return value;
             };
             let curve = cubic_bezier::CubicBezierSegment {
@@ -290,7 +376,35 @@ pub fn easing_curve(curve: &EasingCurve, value: f32) -> f32 {
             let curve = curve.assume_monotonic();
             curve.y(curve.solve_t_for_x(value, 0.0..1.0, 0.01))
         }
+        EasingCurve::Spring { mass, stiffness, damping, initial_velocity } => {
+            spring_curve(*mass, *stiffness, *damping, *initial_velocity, value)
+        }
+    }
+}
+
+/// Position of a damped spring at `value` (between 0 and 1), where 1 is the time at which the
+/// spring has settled. Over-damped springs are approximated as critically damped.
+fn spring_curve(mass: f32, stiffness: f32, damping: f32, initial_velocity: f32, value: f32) -> f32 {
+    #[cfg(not(feature = "std"))]
+    use num_traits::Float;
+
+    if mass <= 0. || stiffness <= 0. || damping <= 0. {
+        return value;
     }
+    let w0 = (stiffness / mass).sqrt();
+    let zeta = damping / (2. * (stiffness * mass).sqrt());
+    // Settle when the envelope of the motion is below 0.01% of the distance
+    let decay = if zeta < 1. { zeta * w0 } else { w0 };
+    let t = value * (10000_f32).ln() / decay;
+    let displacement = if zeta < 1. {
+        let wd = w0 * (1. - zeta * zeta).sqrt();
+        let b = (zeta * w0 - initial_velocity) / wd;
+        (-t * zeta * w0).exp() * ((wd * t).cos() + b * (wd * t).sin())
+    } else {
+        let b = w0 - initial_velocity;
+        (1. + b * t) * (-t * w0).exp()
+    };
+    1. - displacement
 }
 
 /*
@@ -321,9 +435,37 @@ fn test_curve(name: &str, curve: &EasingCurve) {
     test_curve("ease_in", &EasingCurve::CubicBezier([0.42, 0.0, 1.0, 1.0]));
     test_curve("ease_in_out", &EasingCurve::CubicBezier([0.42, 0.0, 0.58, 1.0]));
     test_curve("ease_out", &EasingCurve::CubicBezier([0.0, 0.0, 0.58, 1.0]));
+    test_curve(
+        "spring",
+        &EasingCurve::Spring { mass: 1., stiffness: 100., damping: 10., initial_velocity: 0. },
+    );
 }
 */
 
+#[test]
+fn spring_easing() {
+    let bouncy =
+        EasingCurve::Spring { mass: 1., stiffness: 100., damping: 10., initial_velocity: 0. };
+    assert_eq!(easing_curve(&bouncy, 0.), 0.);
+    assert!((easing_curve(&bouncy, 1.) - 1.).abs() < 0.001);
+    // overshoot
+    assert!((0..100).any(|i| easing_curve(&bouncy, i as f32 / 100.) > 1.1));
+
+    let critical =
+        EasingCurve::Spring { mass: 1., stiffness: 100., damping: 20., initial_velocity: 0. };
+    assert!((easing_curve(&critical, 1.) - 1.).abs() < 0.002);
+    let mut previous = 0.;
+    for i in 1..=100 {
+        let v = easing_curve(&critical, i as f32 / 100.);
+        assert!(v >= previous && v <= 1.);
+        previous = v;
+    }
+
+    let invalid =
+        EasingCurve::Spring { mass: 0., stiffness: 100., damping: 10., initial_velocity: 0. };
+    assert_eq!(easing_curve(&invalid, 0.3), 0.3);
+}
+
 /// Update the global animation time to the current time
 pub fn update_animations() {
     CURRENT_ANIMATION_DRIVER.with(|driver| {
@@ -337,3 +479,12 @@ pub fn update_animations() {
         driver.update_animations(Instant(duration))
     });
 }
+
+#[cfg(feature = "ffi")]
+pub(crate) mod ffi {
+    /// Implementation of the `animation-tick()` function for the C++ generated code
+    #[no_mangle]
+    pub extern "C" fn slint_animation_tick() -> u64 {
+        super::animation_tick()
+    }
+}
@@ -7,9 +7,16 @@
 
 use alloc::boxed::Box;
 use alloc::rc::Rc;
+use alloc::vec::Vec;
 
 use crate::component::ComponentVTable;
 use crate::window::WindowRc;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
+
+pub use crate::accessibility::{AccessibilityNode, AccessibilityPreferences};
+pub use crate::items::{AccessibleRole, InputType};
+pub use crate::spell_check::{Misspelling, SpellChecker, SuggestionRequest};
 
 /// This enum describes a low-level access to specific graphics APIs used
 /// by the renderer.
@@ -88,6 +95,344 @@ pub enum SetRenderingNotifierError {
     AlreadySet,
 }
 
+/// The error returned when the event loop cannot run in a thread other than the main thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EventLoopThreadError {
+    /// The windowing system requires the event loop to run in the main thread, for example on
+    /// macOS, iOS, and in the web browser.
+    PlatformRequiresMainThread,
+    /// The backend does not support running its event loop in another thread.
+    Unsupported,
+}
+
+impl core::fmt::Display for EventLoopThreadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::PlatformRequiresMainThread => {
+                write!(f, "the platform requires the event loop to run in the main thread")
+            }
+            Self::Unsupported => {
+                write!(f, "the backend cannot run the event loop in another thread")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EventLoopThreadError {}
+
+/// The destination of the pages printed with [`Window::print()`] or [`print_pages()`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PrintTarget {
+    /// Write the pages into a PDF file at the given path.
+    Pdf(crate::SharedString),
+    /// Show the print dialog of the system, to let the user choose the printer and the pages.
+    Dialog,
+}
+
+/// This enum describes the different error scenarios that may occur when printing with
+/// [`Window::print()`] or [`print_pages()`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum PrintError {
+    /// The rendering backend does not support printing.
+    Unsupported,
+    /// The user cancelled the print dialog.
+    Cancelled,
+    /// The pages could not be printed, for example because the file could not be written.
+    Failed,
+}
+
+/// The resources that a window and its renderer use, returned by [`Window::renderer_statistics()`].
+///
+/// Sampling these periodically in a long-running application reveals leaks, such as items that
+/// are never destroyed or textures that are never released. The fields about the GPU are zero
+/// when the rendering backend doesn't use one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RendererStatistics {
+    /// The number of items in the component of the window, including the items that are
+    /// instantiated by `for` and `if`.
+    pub item_count: usize,
+    /// The number of items for which the renderer caches graphics data, such as a texture.
+    pub cached_item_count: usize,
+    /// The number of textures of the images, shadows, and layers of the window.
+    pub texture_count: usize,
+    /// The memory of these textures, in bytes.
+    pub texture_memory: usize,
+    /// The number of textures of the atlases in which the glyphs of the texts are rendered.
+    pub glyph_atlas_texture_count: usize,
+    /// The memory of these glyph atlas textures, in bytes.
+    pub glyph_atlas_memory: usize,
+}
+
+/// The type of a window, which tells the windowing system how to present it.
+///
+/// The windows that are not [`WindowType::Normal`] are not shown in the task bar, and the tooltips
+/// and notifications don't take the keyboard focus when they are shown. How much of this is
+/// honored depends on the backend and the windowing system.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WindowType {
+    /// A regular top-level window.
+    Normal,
+    /// A splash screen shown while the application starts, without a frame.
+    Splash,
+    /// A small window with information about an element, without a frame.
+    Tooltip,
+    /// A tool palette or a tool bar of the main window of the application.
+    Utility,
+    /// A popup that notifies the user of an event, without a frame.
+    Notification,
+}
+
+impl Default for WindowType {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// The material that Windows 11 draws behind a window, which shows through the transparent parts
+/// of its background.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WindowBackdrop {
+    /// No backdrop, the background of the window is drawn as usual.
+    None,
+    /// The Mica material, tinted with the desktop wallpaper, for long-lived windows.
+    Mica,
+    /// The acrylic material, which blurs what is behind the window, for transient windows.
+    Acrylic,
+}
+
+impl Default for WindowBackdrop {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// The layer of the stack of the Wayland compositor in which a [`LayerShell`] surface is placed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LayerShellLayer {
+    /// Below the other layers, for a wallpaper.
+    Background,
+    /// Below the regular windows.
+    Bottom,
+    /// Above the regular windows, for a panel or a bar.
+    Top,
+    /// Above the fullscreen windows, for a notification or a lock screen.
+    Overlay,
+}
+
+/// Whether a [`LayerShell`] surface takes the keyboard focus.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LayerShellKeyboardInteractivity {
+    /// The surface never gets the keyboard focus.
+    None,
+    /// The surface takes the keyboard focus as long as it is shown, in the top and overlay layers.
+    Exclusive,
+    /// The surface gets the keyboard focus when the user clicks on it, like a regular window.
+    OnDemand,
+}
+
+/// Shows a window as a surface of the wlr-layer-shell protocol of the Wayland compositors, instead
+/// of a regular window: a panel, a bar, a dock, a wallpaper or a notification that is attached to
+/// edges of the screen. Set it with [`Window::set_layer_shell()`].
+///
+/// A surface that is anchored to two opposite edges is stretched between them, otherwise it has
+/// the preferred size of the window in that direction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerShell {
+    /// The layer in which the surface is placed.
+    pub layer: LayerShellLayer,
+    /// The surface is attached to the top edge of the screen.
+    pub anchor_top: bool,
+    /// The surface is attached to the bottom edge of the screen.
+    pub anchor_bottom: bool,
+    /// The surface is attached to the left edge of the screen.
+    pub anchor_left: bool,
+    /// The surface is attached to the right edge of the screen.
+    pub anchor_right: bool,
+    /// The distance, in logical pixels, from the edge to which the surface is anchored that the
+    /// compositor keeps free of the other windows, for example the height of a bar. 0 lets the
+    /// other windows go below the surface, and -1 also ignores the exclusive zones of the other
+    /// surfaces.
+    pub exclusive_zone: i32,
+    /// The distances from the top, right, bottom and left edges to which the surface is anchored,
+    /// in logical pixels.
+    pub margin: [i32; 4],
+    /// Whether the surface takes the keyboard focus.
+    pub keyboard_interactivity: LayerShellKeyboardInteractivity,
+    /// The name that tells the compositor what the surface is for, such as "panel" or
+    /// "notifications".
+    pub namespace: crate::SharedString,
+}
+
+impl Default for LayerShell {
+    fn default() -> Self {
+        Self {
+            layer: LayerShellLayer::Top,
+            anchor_top: false,
+            anchor_bottom: false,
+            anchor_left: false,
+            anchor_right: false,
+            exclusive_zone: 0,
+            margin: [0; 4],
+            keyboard_interactivity: LayerShellKeyboardInteractivity::None,
+            namespace: "slint".into(),
+        }
+    }
+}
+
+/// An affine transformation of the pointer positions, applied before the items under the pointer
+/// are looked up. This is how the calibration of a touch screen is applied, for example the one
+/// of a resistive touch screen of an embedded device.
+///
+/// A position `(x, y)` in logical pixels is transformed into
+/// `(m11 * x + m21 * y + m31, m12 * x + m22 * y + m32)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputTransform {
+    /// The factor of `x` in the transformed `x`.
+    pub m11: f32,
+    /// The factor of `x` in the transformed `y`.
+    pub m12: f32,
+    /// The factor of `y` in the transformed `x`.
+    pub m21: f32,
+    /// The factor of `y` in the transformed `y`.
+    pub m22: f32,
+    /// The translation of `x`.
+    pub m31: f32,
+    /// The translation of `y`.
+    pub m32: f32,
+}
+
+impl Default for InputTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl InputTransform {
+    /// The transformation that doesn't change the positions.
+    pub const IDENTITY: Self = Self { m11: 1., m12: 0., m21: 0., m22: 1., m31: 0., m32: 0. };
+
+    /// Computes the transformation from three calibration points: `measured` are the positions
+    /// that the touch screen reports when the user touches the `expected` positions, which must
+    /// not be aligned. Returns `None` if the measured positions are aligned.
+    pub fn from_calibration_points(
+        measured: [(f32, f32); 3],
+        expected: [(f32, f32); 3],
+    ) -> Option<Self> {
+        let [(x1, y1), (x2, y2), (x3, y3)] = measured;
+        let det = |a: [f32; 3], b: [f32; 3], c: [f32; 3]| {
+            a[0] * (b[1] * c[2] - b[2] * c[1]) - b[0] * (a[1] * c[2] - a[2] * c[1])
+                + c[0] * (a[1] * b[2] - a[2] * b[1])
+        };
+        let (xs, ys, ones) = ([x1, x2, x3], [y1, y2, y3], [1.; 3]);
+        let d = det(xs, ys, ones);
+        // f32::abs() needs std
+        if d > -f32::EPSILON && d < f32::EPSILON {
+            return None;
+        }
+        // Cramer's rule, for each coordinate of the expected positions
+        let solve = |target: [f32; 3]| {
+            (det(target, ys, ones) / d, det(xs, target, ones) / d, det(xs, ys, target) / d)
+        };
+        let (m11, m21, m31) = solve(expected.map(|p| p.0));
+        let (m12, m22, m32) = solve(expected.map(|p| p.1));
+        Some(Self { m11, m12, m21, m22, m31, m32 })
+    }
+
+    /// Returns the transformed position.
+    pub fn transform_point(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.m11 * x + self.m21 * y + self.m31, self.m12 * x + self.m22 * y + self.m32)
+    }
+}
+
+/// How the renderer of a window aligns the geometry to the pixels of the screen. With a
+/// fractional scale factor, such as 1.25 or 1.5, a line of one logical pixel covers a fraction
+/// of two physical pixels, so it is blurred, or not drawn at all by the renderers that don't
+/// anti-alias. Aligning it to the pixels keeps it sharp, at the cost of moving it by less than
+/// one physical pixel.
+///
+/// The default aligns both the borders and the text baselines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RenderingQuality {
+    /// The edges of the borders of the rectangles are aligned to the pixels, and the borders
+    /// are at least one physical pixel wide.
+    pub snap_borders: bool,
+    /// The baselines of the lines of text are aligned to the pixels, so that the glyphs are not
+    /// blurred vertically.
+    pub snap_text_baselines: bool,
+}
+
+impl Default for RenderingQuality {
+    fn default() -> Self {
+        Self { snap_borders: true, snap_text_baselines: true }
+    }
+}
+
+impl RenderingQuality {
+    /// Renders the geometry at its exact position, even between two pixels.
+    pub const EXACT: Self = Self { snap_borders: false, snap_text_baselines: false };
+
+    /// Returns the physical length `length` aligned to the pixels if `snap` is true. A length
+    /// that is not zero is at least one pixel long.
+    pub fn snap_length(snap: bool, length: f32) -> f32 {
+        if !snap || length <= 0. {
+            length
+        } else {
+            length.round().max(1.)
+        }
+    }
+}
+
+/// An entry of the global menu bar of the application, as set with `set_menu_bar()`.
+///
+/// The entries of the menu bar are the menus, and their `submenu` holds the entries of the menu.
+/// An entry without a title is a separator.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MenuBarEntry {
+    /// The text of the entry.
+    pub title: crate::SharedString,
+    /// The keyboard shortcut of the entry, such as `"Ctrl+Shift+S"`. On macOS, `Ctrl` is the
+    /// Command key.
+    pub shortcut: crate::SharedString,
+    /// The entry is shown but cannot be activated.
+    pub disabled: bool,
+    /// The entry is shown with a check mark.
+    pub checked: bool,
+    /// The entries of the sub-menu that this entry opens.
+    pub submenu: Vec<MenuBarEntry>,
+}
+
+/// The `TextInput` that has the keyboard focus, as passed to the callback of
+/// [`Window::on_text_input_focus_changed()`], for example to show an on-screen keyboard.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TextInputFocus {
+    /// The geometry of the `TextInput` when it got the focus, in logical coordinates relative
+    /// to the window, to place the keyboard so that it doesn't cover it.
+    pub geometry: crate::graphics::Rect,
+    /// The type of the input, for example to show a numeric keypad for a number.
+    pub input_type: InputType,
+}
+
+/// The font of a text measured with [`Window::measure_text()`]. The fields that are not set use
+/// the default font of the window, like the `Text` elements do.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextFont {
+    /// The name of the font family, such as "Helvetica".
+    pub family: Option<crate::SharedString>,
+    /// The size of the font, in logical pixels.
+    pub size: Option<f32>,
+    /// The weight of the font, such as 400 for a normal text and 700 for a bold text.
+    pub weight: Option<i32>,
+}
+
 /// This type represents a window towards the windowing system, that's used to render the
 /// scene of a component. It provides API to control windowing system specific aspects such
 /// as the position on the screen.
@@ -133,6 +478,217 @@ pub fn request_redraw(&self) {
         crate::animations::CURRENT_ANIMATION_DRIVER
             .with(|driver| driver.set_has_active_animations());
     }
+
+    /// Renders the scene of the window, with vector graphics, on one page of a PDF file or of a
+    /// printer. The window doesn't need to be shown. The scene keeps its size on a screen of 96 DPI,
+    /// unless it needs to be scaled down to fit the page. Use [`print_pages()`] to print several
+    /// pages.
+    pub fn print(&self, target: PrintTarget) -> Result<(), PrintError> {
+        print_pages(&[self], target)
+    }
+
+    /// Returns an SVG document with the scene of the window, for example to hand over a design or
+    /// to document a screen. The texts are `<text>` elements, and the images are embedded. The
+    /// window doesn't need to be shown.
+    #[cfg(feature = "std")]
+    pub fn export_svg(&self) -> alloc::string::String {
+        self.0.export_svg()
+    }
+
+    /// Returns the resources that the window and its renderer currently use, for example to
+    /// monitor a long-running application for leaks.
+    pub fn renderer_statistics(&self) -> RendererStatistics {
+        self.0.renderer_statistics()
+    }
+
+    /// Returns the tree of the elements that have one of the `accessible-role`, `accessible-label` or
+    /// `accessible-value` properties set. This is the information that an integration with an
+    /// assistive technology, such as a screen reader, exposes to the platform.
+    pub fn accessibility_tree(&self) -> Vec<AccessibilityNode> {
+        self.0.accessibility_tree()
+    }
+
+    /// Searches the [accessibility tree](Self::accessibility_tree) depth-first and returns the
+    /// first element with the given role and label, or None if there is no such element.
+    ///
+    /// This allows automated tests and other external tools to locate a control the same way
+    /// a user of a screen reader would, without knowing its position in the window.
+    pub fn find_accessible_element(
+        &self,
+        role: AccessibleRole,
+        label: &str,
+    ) -> Option<AccessibilityNode> {
+        crate::accessibility::find_accessible_element(&self.0.accessibility_tree(), role, label)
+            .cloned()
+    }
+
+    /// Activates an element of the accessibility tree, for example one returned by
+    /// [`Self::find_accessible_element`], by invoking its `accessible-action-default` callback.
+    /// The builtin widgets implement it, so a button is clicked and a checkbox is toggled.
+    pub fn activate_accessible_element(&self, element: &AccessibilityNode) {
+        self.0.activate_accessible_element(element);
+    }
+
+    /// Returns the size of the `rem` unit of the `.slint` language, in logical pixels.
+    pub fn base_font_size(&self) -> f32 {
+        self.0.base_font_size()
+    }
+
+    /// Sets the size of the `rem` unit of the `.slint` language, in logical pixels. The default is 16.
+    ///
+    /// The lengths expressed in `rem` are updated accordingly, which allows to honor the font
+    /// scaling preference of the user.
+    pub fn set_base_font_size(&self, size: f32) {
+        self.0.set_base_font_size(size)
+    }
+
+    /// Returns the width and height in logical pixels of the text rendered with the font, as
+    /// measured by the renderer of the window, so that it matches what a `Text` element draws.
+    ///
+    /// When `max_width` is set, the text is wrapped at the word boundaries to not be wider than
+    /// that, like a `Text` with `wrap: word-wrap`.
+    pub fn measure_text(&self, text: &str, font: &TextFont, max_width: Option<f32>) -> (f32, f32) {
+        let font_request = crate::graphics::FontRequest {
+            family: font.family.clone(),
+            weight: font.weight,
+            pixel_size: font.size,
+            letter_spacing: None,
+        };
+        let size = self.0.text_size(font_request, text, max_width);
+        (size.width, size.height)
+    }
+
+    /// Sets the function that is called when a `TextInput` of the window gets the keyboard focus,
+    /// and with None when the focus leaves it. This allows a device without a hardware keyboard
+    /// to show its own on-screen keyboard, which enters the text with [`Self::commit_text()`].
+    pub fn on_text_input_focus_changed(&self, callback: impl Fn(Option<TextInputFocus>) + 'static) {
+        self.0.add_text_input_focus_listener(Box::new(callback))
+    }
+
+    /// Enters the text in the `TextInput` that has the keyboard focus, in place of its selection.
+    /// This is how an on-screen keyboard enters the text that the user composed: unlike a typed
+    /// text, it doesn't go through the key event handlers, so it is never taken as a shortcut.
+    pub fn commit_text(&self, text: &str) {
+        self.0.clone().commit_text(text);
+    }
+
+    /// Moves the keyboard focus to the next item of the focus chain, like pressing the Tab key.
+    ///
+    /// The focus chain contains the `FocusScope` and `TextInput` elements whose `tab-index` is not
+    /// negative. Elements with a positive `tab-index` come first, the others follow in the order
+    /// in which they are declared.
+    pub fn focus_next_item(&self) {
+        self.0.clone().focus_next_item();
+    }
+
+    /// Moves the keyboard focus to the previous item of the focus chain, like pressing Shift+Tab.
+    pub fn focus_previous_item(&self) {
+        self.0.clone().focus_previous_item();
+    }
+
+    /// Returns the type of the window.
+    pub fn window_type(&self) -> WindowType {
+        self.0.window_type()
+    }
+
+    /// Sets the type of the window. It must be set before the window is shown for the first time.
+    pub fn set_window_type(&self, window_type: WindowType) {
+        self.0.set_window_type(window_type)
+    }
+
+    /// Returns true if the title bar of the window is drawn in the dark mode of the system.
+    pub fn dark_title_bar(&self) -> bool {
+        self.0.dark_title_bar()
+    }
+
+    /// Requests the title bar of the window to be drawn in the dark mode of the system. This is
+    /// only supported on Windows 10 and 11, and must be set before the window is shown for the
+    /// first time.
+    pub fn set_dark_title_bar(&self, dark: bool) {
+        self.0.set_dark_title_bar(dark)
+    }
+
+    /// Returns the transformation of the pointer positions of the window.
+    pub fn input_transform(&self) -> Option<InputTransform> {
+        self.0.input_transform()
+    }
+
+    /// Sets the transformation applied to the positions of the mouse and touch events that the
+    /// window receives, before the items under the pointer are looked up, or removes it with
+    /// `None`. This applies the calibration of a touch screen without changing the backend.
+    pub fn set_input_transform(&self, transform: Option<InputTransform>) {
+        self.0.set_input_transform(transform)
+    }
+
+    /// Returns how the renderer aligns the geometry of the window to the pixels.
+    pub fn rendering_quality(&self) -> RenderingQuality {
+        self.0.rendering_quality()
+    }
+
+    /// Sets how the renderer aligns the borders and the text of the window to the pixels of the
+    /// screen, to keep them sharp with a fractional scale factor. The window is redrawn. The Qt
+    /// backend ignores it, as Qt aligns the geometry itself.
+    pub fn set_rendering_quality(&self, quality: RenderingQuality) {
+        self.0.set_rendering_quality(quality)
+    }
+
+    /// Returns the backdrop of the window.
+    pub fn backdrop(&self) -> WindowBackdrop {
+        self.0.backdrop()
+    }
+
+    /// Sets the material drawn behind the window, which shows through the transparent parts of
+    /// its `background`. This is only supported on Windows 11, and must be set before the window
+    /// is shown for the first time. Where it's not supported, the background is drawn opaque,
+    /// over black if the title bar is dark and over white otherwise.
+    pub fn set_backdrop(&self, backdrop: WindowBackdrop) {
+        self.0.set_backdrop(backdrop)
+    }
+
+    /// Returns the layer-shell configuration of the window, if it is shown as a layer surface.
+    pub fn layer_shell(&self) -> Option<LayerShell> {
+        self.0.layer_shell()
+    }
+
+    /// Shows the window as a wlr-layer-shell surface with the given configuration, or as a regular
+    /// window with `None`. This is only supported by the GL backend on the Wayland compositors that
+    /// implement the protocol, and must be set before the window is shown for the first time.
+    /// Elsewhere, the window is shown as a regular window.
+    pub fn set_layer_shell(&self, layer_shell: Option<LayerShell>) {
+        self.0.set_layer_shell(layer_shell)
+    }
+}
+
+/// Returns the accessibility preferences in effect: the ones of the system, unless they were
+/// replaced with [`set_accessibility_preferences()`].
+pub fn accessibility_preferences() -> AccessibilityPreferences {
+    crate::accessibility::accessibility_preferences()
+}
+
+/// Replaces the accessibility preferences of the system, which the styles and the `high-contrast()`
+/// and `reduced-motion()` functions of the `.slint` language follow, or follows the system again
+/// with `None`. This is useful for an application that has its own accessibility settings.
+pub fn set_accessibility_preferences(preferences: Option<AccessibilityPreferences>) {
+    crate::accessibility::set_accessibility_preferences_override(preferences)
+}
+
+/// Sets the spell checker that checks the words of the `TextInput` elements with
+/// `spell-check: true`, or disables spell checking with `None`.
+pub fn set_spell_checker(checker: Option<Rc<dyn SpellChecker>>) {
+    crate::spell_check::set_spell_checker(checker)
+}
+
+/// Renders the scenes of the `pages` windows, one per page, into a PDF file or to a printer.
+/// This is how a report with several pages is printed: each page is a component, for example
+/// the same component with different properties, and the windows of the components don't need
+/// to be shown. See [`Window::print()`].
+pub fn print_pages(pages: &[&Window], target: PrintTarget) -> Result<(), PrintError> {
+    let first_page = match pages.first() {
+        Some(first_page) => first_page,
+        None => return Ok(()),
+    };
+    let pages: Vec<WindowRc> = pages.iter().map(|page| page.0.clone()).collect();
+    first_page.0.print(&pages, &target)
 }
 
 impl crate::window::WindowHandleAccess for Window {
@@ -377,3 +933,261 @@ pub fn invoke_from_event_loop(func: impl FnOnce() + Send + 'static) {
         panic!("slint::invoke_from_event_loop() must be called after the Slint backend is initialized.")
     }
 }
+
+/// A point in time with the precision of a millisecond, such as the value of a `date-time`
+/// property. It is stored as the number of milliseconds since the Unix epoch, in UTC, and the
+/// default value is the epoch itself.
+///
+/// It converts from and to [`std::time::SystemTime`], and to and from the `DateTime` type of the
+/// `chrono` crate with the `chrono` feature. The `duration` values, which are milliseconds, can
+/// be added to it and subtracted from it.
+///
+/// ```
+/// # use i_slint_core::api::DateTime;
+/// let start = DateTime::from_millis_since_epoch(1_650_000_000_000);
+/// let end = start + core::time::Duration::from_secs(3600);
+/// assert!(end > start);
+/// assert_eq!(end.millis_since_epoch() - start.millis_since_epoch(), 3_600_000);
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    millis_since_epoch: i64,
+}
+
+impl DateTime {
+    /// The Unix epoch, January 1st 1970 at midnight in UTC.
+    pub const UNIX_EPOCH: DateTime = DateTime { millis_since_epoch: 0 };
+
+    /// Creates a date-time from a number of milliseconds since the Unix epoch, which is negative
+    /// for the date-times before the epoch.
+    pub const fn from_millis_since_epoch(millis_since_epoch: i64) -> Self {
+        Self { millis_since_epoch }
+    }
+
+    /// Returns the number of milliseconds since the Unix epoch.
+    pub const fn millis_since_epoch(&self) -> i64 {
+        self.millis_since_epoch
+    }
+
+    /// Returns the current date-time of the system clock.
+    #[cfg(feature = "std")]
+    pub fn now() -> Self {
+        std::time::SystemTime::now().into()
+    }
+}
+
+impl core::ops::Add<core::time::Duration> for DateTime {
+    type Output = DateTime;
+    fn add(self, duration: core::time::Duration) -> DateTime {
+        let millis = i64::try_from(duration.as_millis()).unwrap_or(i64::MAX);
+        DateTime::from_millis_since_epoch(self.millis_since_epoch.saturating_add(millis))
+    }
+}
+
+impl core::ops::Sub<core::time::Duration> for DateTime {
+    type Output = DateTime;
+    fn sub(self, duration: core::time::Duration) -> DateTime {
+        let millis = i64::try_from(duration.as_millis()).unwrap_or(i64::MAX);
+        DateTime::from_millis_since_epoch(self.millis_since_epoch.saturating_sub(millis))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::time::SystemTime> for DateTime {
+    fn from(time: std::time::SystemTime) -> Self {
+        let millis = |duration: core::time::Duration| {
+            i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+        };
+        match time.duration_since(std::time::UNIX_EPOCH) {
+            Ok(after) => DateTime::from_millis_since_epoch(millis(after)),
+            Err(before) => DateTime::from_millis_since_epoch(-millis(before.duration())),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<DateTime> for std::time::SystemTime {
+    type Error = DateTimeRangeError;
+    fn try_from(date_time: DateTime) -> Result<Self, DateTimeRangeError> {
+        let offset = core::time::Duration::from_millis(date_time.millis_since_epoch.unsigned_abs());
+        if date_time.millis_since_epoch >= 0 {
+            std::time::UNIX_EPOCH.checked_add(offset)
+        } else {
+            std::time::UNIX_EPOCH.checked_sub(offset)
+        }
+        .ok_or(DateTimeRangeError)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for DateTime {
+    fn from(date_time: chrono::DateTime<Tz>) -> Self {
+        DateTime::from_millis_since_epoch(date_time.timestamp_millis())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<DateTime> for chrono::DateTime<chrono::Utc> {
+    type Error = DateTimeRangeError;
+    fn try_from(date_time: DateTime) -> Result<Self, DateTimeRangeError> {
+        use chrono::TimeZone;
+        chrono::Utc
+            .timestamp_millis_opt(date_time.millis_since_epoch)
+            .single()
+            .ok_or(DateTimeRangeError)
+    }
+}
+
+/// The error returned when a [`DateTime`] is converted to a type that cannot represent it,
+/// such as a [`std::time::SystemTime`] too far in the past or in the future.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DateTimeRangeError;
+
+impl core::fmt::Display for DateTimeRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the date-time is out of the range of the target type")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DateTimeRangeError {}
+
+#[cfg(feature = "std")]
+#[test]
+fn date_time_system_time_conversion() {
+    let time = std::time::UNIX_EPOCH + core::time::Duration::from_millis(1_650_000_000_123);
+    let date_time = DateTime::from(time);
+    assert_eq!(date_time.millis_since_epoch(), 1_650_000_000_123);
+    assert_eq!(std::time::SystemTime::try_from(date_time), Ok(time));
+
+    let before = std::time::UNIX_EPOCH - core::time::Duration::from_millis(2500);
+    assert_eq!(DateTime::from(before).millis_since_epoch(), -2500);
+    assert_eq!(std::time::SystemTime::try_from(DateTime::from(before)), Ok(before));
+
+    let later = date_time + core::time::Duration::from_secs(2);
+    assert_eq!(later.millis_since_epoch(), 1_650_000_002_123);
+    assert_eq!(later - core::time::Duration::from_secs(2), date_time);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn input_transform_from_calibration_points() {
+    let measured = [(10., 20.), (300., 30.), (20., 220.)];
+    let expected = [(0., 0.), (320., 0.), (0., 240.)];
+    let transform = InputTransform::from_calibration_points(measured, expected).unwrap();
+    for ((x, y), (expected_x, expected_y)) in measured.into_iter().zip(expected) {
+        let (x, y) = transform.transform_point(x, y);
+        assert!((x - expected_x).abs() < 0.01 && (y - expected_y).abs() < 0.01);
+    }
+    assert_eq!(InputTransform::IDENTITY.transform_point(4., 2.), (4., 2.));
+    let aligned = [(0., 0.), (1., 1.), (2., 2.)];
+    assert_eq!(InputTransform::from_calibration_points(aligned, expected), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn rendering_quality_snapping() {
+    assert_eq!(RenderingQuality::snap_length(true, 1.5), 2.);
+    assert_eq!(RenderingQuality::snap_length(true, 0.25), 1.);
+    assert_eq!(RenderingQuality::snap_length(true, 0.), 0.);
+    assert_eq!(RenderingQuality::snap_length(false, 0.25), 0.25);
+}
+
+/// Calls a function with the value that another function computes from properties, each time
+/// these properties change. For example, this can update a data structure of the application when
+/// the user changes the properties of a component.
+///
+/// The `read` function is called to find the properties it depends on, and again after they
+/// change, from the event loop. The changes that happen together result in one call of
+/// `changed`. The functions are not called anymore once the `PropertyWatcher` is dropped.
+///
+/// ```rust,no_run
+/// # i_slint_backend_testing::init();
+/// # use i_slint_core::api::PropertyWatcher;
+/// # use i_slint_core::properties::Property;
+/// # use std::rc::Rc;
+/// let volume = Rc::pin(Property::new(5));
+/// let watcher = PropertyWatcher::new(
+///     { let volume = volume.clone(); move || volume.as_ref().get() },
+///     |volume| println!("The volume is now {}", volume),
+/// );
+/// volume.set(8);
+/// ```
+pub struct PropertyWatcher {
+    _inner: Rc<dyn core::any::Any>,
+}
+
+struct PropertyWatcherInner<T> {
+    tracker: core::pin::Pin<Box<crate::properties::PropertyTracker<Box<dyn Fn()>>>>,
+    read: Box<dyn Fn() -> T>,
+    changed: Box<dyn Fn(T)>,
+}
+
+impl PropertyWatcher {
+    /// Calls `changed` with the result of `read` each time the properties that `read` accesses
+    /// change.
+    pub fn new<T: 'static>(read: impl Fn() -> T + 'static, changed: impl Fn(T) + 'static) -> Self {
+        let inner = Rc::new_cyclic(|weak_inner: &alloc::rc::Weak<PropertyWatcherInner<T>>| {
+            let weak_inner = weak_inner.clone();
+            let on_change: Box<dyn Fn()> = Box::new(move || {
+                // This is called while a property is being set, so the properties are read later
+                let weak_inner = weak_inner.clone();
+                crate::timers::Timer::single_shot(Default::default(), move || {
+                    if let Some(inner) = weak_inner.upgrade() {
+                        let value = inner.tracker.as_ref().evaluate_as_dependency_root(&inner.read);
+                        (inner.changed)(value);
+                    }
+                });
+            });
+            PropertyWatcherInner {
+                tracker: Box::pin(crate::properties::PropertyTracker::new_with_change_handler(
+                    on_change,
+                )),
+                read: Box::new(read),
+                changed: Box::new(changed),
+            }
+        });
+        inner.tracker.as_ref().evaluate_as_dependency_root(&inner.read);
+        Self { _inner: inner }
+    }
+}
+
+#[test]
+fn property_watcher() {
+    use crate::properties::Property;
+    use core::cell::RefCell;
+
+    let a = Rc::pin(Property::new(1));
+    let b = Rc::pin(Property::new(2));
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let watcher = PropertyWatcher::new(
+        {
+            let (a, b) = (a.clone(), b.clone());
+            move || a.as_ref().get() + b.as_ref().get()
+        },
+        {
+            let values = values.clone();
+            move |sum| values.borrow_mut().push(sum)
+        },
+    );
+    let run_timers = || {
+        crate::tests::slint_mock_elapsed_time(1);
+        crate::timers::TimerList::maybe_activate_timers();
+    };
+
+    run_timers();
+    assert!(values.borrow().is_empty());
+    a.as_ref().set(10);
+    b.as_ref().set(20);
+    run_timers();
+    assert_eq!(*values.borrow(), vec![30]);
+    b.as_ref().set(5);
+    run_timers();
+    assert_eq!(*values.borrow(), vec![30, 15]);
+
+    drop(watcher);
+    a.as_ref().set(0);
+    run_timers();
+    assert_eq!(*values.borrow(), vec![30, 15]);
+}
@@ -8,6 +8,7 @@
 use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 use crate::graphics::{Image, IntSize};
 use crate::window::Window;
@@ -64,11 +65,36 @@ fn register_bitmap_font(&'static self, _font_data: &'static crate::graphics::Bit
     fn set_clipboard_text(&'static self, text: String);
     fn clipboard_text(&'static self) -> Option<String>;
 
+    /// Allows the windows and the event loop to be created in a thread other than the main
+    /// thread. This must be called before the first window is created.
+    fn allow_event_loop_in_any_thread(
+        &'static self,
+    ) -> Result<(), crate::api::EventLoopThreadError> {
+        Err(crate::api::EventLoopThreadError::Unsupported)
+    }
+
     /// Send an user event to from another thread that should be run in the GUI event loop
     fn post_event(&'static self, event: Box<dyn FnOnce() + Send>);
 
     fn image_size(&'static self, image: &Image) -> IntSize;
 
+    /// Sets the menus of the global menu bar of the application, on the platforms that have one.
+    /// `activated` is called with the entry that the user activated.
+    fn set_menu_bar(
+        &'static self,
+        _menus: Vec<crate::api::MenuBarEntry>,
+        _activated: Box<dyn Fn(&crate::api::MenuBarEntry)>,
+    ) {
+    }
+
+    /// Shows `label` in a badge on the icon of the application in the dock or task bar, or
+    /// removes the badge if `label` is `None`.
+    fn set_application_badge(&'static self, _label: Option<&str>) {}
+
+    /// Shows a progress bar on the icon of the application in the dock or task bar, with a
+    /// `progress` between 0 and 1, or removes it if `progress` is `None`.
+    fn set_application_progress(&'static self, _progress: Option<f32>) {}
+
     fn duration_since_start(&'static self) -> core::time::Duration {
         #[cfg(feature = "std")]
         {
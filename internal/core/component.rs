@@ -83,6 +83,7 @@ pub fn free_component_item_graphics_resources<Base>(
         crate::item_tree::ItemTreeNode::Item { item, .. } => Some(item.apply_pin(base)),
         crate::item_tree::ItemTreeNode::DynamicTree { .. } => None,
     }));
+    window.check_destroyed_focus_item();
 }
 
 #[cfg(feature = "ffi")]
@@ -118,6 +118,11 @@ pub fn remove(&mut self, index: usize) -> CachedGraphicsData<T> {
         self.slab.remove(index)
     }
 
+    /// Returns an iterator over the data of all the entries of the cache.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slab.iter().map(|(_, entry)| &entry.data)
+    }
+
     /// Removes all entries from the cache and increases the cache's generation count, so
     /// that stale index access can be avoided.
     pub fn clear(&mut self) {
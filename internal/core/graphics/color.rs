@@ -134,6 +134,20 @@ pub fn from_rgb_f32(red: f32, green: f32, blue: f32) -> Self {
         Self::from_argb_f32(1.0, red, green, blue)
     }
 
+    /// Construct a color from the hue in degrees, and the saturation, value (brightness) and
+    /// alpha channel in the range 0..1, in the HSV color space. The hue wraps around, so
+    /// -60 degrees is the same as 300 degrees, and the other parameters are clamped.
+    pub fn from_hsva(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        let hue = hue % 360.;
+        let hsva = HsvaColor {
+            h: if hue < 0. { hue + 360. } else { hue },
+            s: saturation.max(0.).min(1.),
+            v: value.max(0.).min(1.),
+            alpha: alpha.max(0.).min(1.),
+        };
+        RgbaColor::<f32>::from(hsva).into()
+    }
+
     /// Converts this color to an RgbaColor struct for easy destructuring.
     pub fn to_argb_u8(&self) -> RgbaColor<u8> {
         RgbaColor::from(*self)
@@ -192,6 +206,30 @@ pub fn darker(&self, factor: f32) -> Self {
         let rgba: RgbaColor<f32> = hsva.into();
         rgba.into()
     }
+
+    /// Returns a mix of this color and `other`. The factor is clamped to the range 0..1 and is
+    /// the proportion of this color, while `1 - factor` is the proportion of `other`, for each
+    /// channel including the alpha channel.
+    /// So for example `mix(&other, 0.75)` is closer to this color than to `other`.
+    #[must_use]
+    pub fn mix(&self, other: &Self, factor: f32) -> Self {
+        let factor = factor.max(0.).min(1.);
+        let (a, b) = (self.to_argb_f32(), other.to_argb_f32());
+        let mix = |a: f32, b: f32| a * factor + b * (1. - factor);
+        Self::from_argb_f32(
+            mix(a.alpha, b.alpha),
+            mix(a.red, b.red),
+            mix(a.green, b.green),
+            mix(a.blue, b.blue),
+        )
+    }
+
+    /// Returns this color with the alpha channel replaced by `alpha`, which is clamped to the
+    /// range 0..1. So for example `with_alpha(0.5)` is this color half transparent.
+    #[must_use]
+    pub fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha: (alpha.max(0.).min(1.) * 255.) as u8, ..*self }
+    }
 }
 
 impl InterpolatedPropertyValue for Color {
@@ -303,6 +341,20 @@ fn test_brighter_darker() {
     assert_eq!(blue.darker(0.5), Color::from_rgb_u8(0, 0, 85));
 }
 
+#[test]
+fn test_mix_with_alpha_hsva() {
+    let red = Color::from_rgb_u8(255, 0, 0);
+    let blue = Color::from_rgb_u8(0, 0, 255);
+    assert_eq!(red.mix(&blue, 1.), red);
+    assert_eq!(red.mix(&blue, 0.), blue);
+    assert_eq!(red.mix(&blue, 0.75), Color::from_rgb_u8(191, 0, 63));
+    assert_eq!(red.mix(&blue, 2.), red);
+    assert_eq!(red.with_alpha(0.5), Color::from_argb_u8(127, 255, 0, 0));
+    assert_eq!(Color::from_hsva(0., 1., 1., 1.), red);
+    assert_eq!(Color::from_hsva(240., 1., 1., 1.), blue);
+    assert_eq!(Color::from_hsva(-120., 1., 1., 0.5), blue.with_alpha(0.5));
+}
+
 #[cfg(feature = "ffi")]
 pub(crate) mod ffi {
     #![allow(unsafe_code)]
@@ -317,4 +369,30 @@ pub(crate) mod ffi {
     pub unsafe extern "C" fn slint_color_darker(col: &Color, factor: f32, out: *mut Color) {
         core::ptr::write(out, col.darker(factor))
     }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_color_mix(
+        col: &Color,
+        other: &Color,
+        factor: f32,
+        out: *mut Color,
+    ) {
+        core::ptr::write(out, col.mix(other, factor))
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_color_with_alpha(col: &Color, alpha: f32, out: *mut Color) {
+        core::ptr::write(out, col.with_alpha(alpha))
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_color_from_hsva(
+        hue: f32,
+        saturation: f32,
+        value: f32,
+        alpha: f32,
+        out: *mut Color,
+    ) {
+        core::ptr::write(out, Color::from_hsva(hue, saturation, value, alpha))
+    }
 }
@@ -123,6 +123,84 @@ macro_rules! declare_consts_for_special_keys {
     i_slint_common::for_each_special_keys!(declare_consts_for_special_keys);
 }
 
+/// The key of a [`KeyEvent`], for the special keys whose text is a private character
+/// code of [`key_codes`]. The keys that produce some text are `Other`.
+///
+/// The values are the names of the special keys in `for_each_special_keys`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(missing_docs)]
+pub enum LogicalKey {
+    Other,
+    Backspace,
+    Tab,
+    Return,
+    Escape,
+    Backtab,
+    Delete,
+    UpArrow,
+    DownArrow,
+    LeftArrow,
+    RightArrow,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    ScrollLock,
+    Pause,
+    SysReq,
+    Stop,
+    Menu,
+}
+
+macro_rules! logical_key_from_text {
+    ($($char:literal # $name:ident # $($_qt:ident)|* # $($_winit:ident)|* ;)*) => {
+        impl LogicalKey {
+            /// Returns the key whose character code is the text of a key event
+            pub fn from_text(text: &str) -> Self {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    $((Some($char), None) => Self::$name,)*
+                    _ => Self::Other,
+                }
+            }
+        }
+    };
+}
+
+i_slint_common::for_each_special_keys!(logical_key_from_text);
+
+impl Default for LogicalKey {
+    fn default() -> Self {
+        Self::Other
+    }
+}
+
 /// KeyboardModifier provides booleans to indicate possible modifier keys
 /// on a keyboard, such as Shift, Control, etc.
 ///
@@ -166,6 +244,13 @@ pub struct KeyEvent {
     pub modifiers: KeyboardModifiers,
     /// The unicode representation of the key pressed.
     pub text: SharedString,
+    /// The code of the physical key, which doesn't depend on the keyboard layout. It is specific
+    /// to the platform, and 0 when it is not known.
+    pub scancode: i32,
+    /// True if the event was generated by the key repeat of the system, while the key is held.
+    pub repeat: bool,
+    /// The special key that was pressed, which matches the text.
+    pub key: LogicalKey,
 
     // note: this field is not exported in the .slint in the KeyEvent builtin struct
     /// Indicates whether the key was pressed or released
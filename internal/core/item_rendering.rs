@@ -8,7 +8,7 @@
 use super::items::*;
 use crate::component::ComponentRc;
 use crate::graphics::{CachedGraphicsData, Point, Rect};
-use crate::item_tree::ItemVisitorResult;
+use crate::item_tree::{ItemVisitorResult, VisitChildrenResult};
 use alloc::boxed::Box;
 use core::cell::{Cell, RefCell};
 use core::pin::Pin;
@@ -104,42 +104,69 @@ pub fn render_component_items(
 ) {
     renderer.save_state();
     renderer.translate(origin.x, origin.y);
+    render_items(component, None, renderer);
+    renderer.restore_state();
+}
 
+/// Renders the item and its children, with the origin of the item at `origin` instead of its
+/// position in the window, for example to render a part of the window in an image.
+pub fn render_item_tree(item: &ItemRc, renderer: &mut dyn ItemRenderer, origin: Point) {
+    renderer.save_state();
+    renderer.translate(origin.x, origin.y);
+    item.borrow().as_ref().render(&mut &mut *renderer);
+    render_items(&item.component(), Some(item.index()), renderer);
+    renderer.restore_state();
+}
+
+/// Renders the descendants of the item at `index` in the component, or all the items of the
+/// component when `index` is None
+fn render_items(component: &ComponentRc, index: Option<usize>, renderer: &mut dyn ItemRenderer) {
     let renderer = RefCell::new(renderer);
 
-    crate::item_tree::visit_items_with_post_visit(
-        component,
-        crate::item_tree::TraversalOrder::BackToFront,
-        |_, item, _, _| {
-            renderer.borrow_mut().save_state();
-
-            let (do_draw, item_geometry) = renderer.borrow_mut().filter_item(item);
-
-            let item_origin = item_geometry.origin;
-            renderer.borrow_mut().translate(item_origin.x, item_origin.y);
-
-            // Don't render items that are clipped, with the exception of the Clip or Flickable since
-            // they themselves clip their content.
-            if !do_draw
-                && !is_clipping_item(item)
-                // HACK, the geometry of the box shadow does not include the shadow, because when the shadow is the root for repeated elements it would translate the children
-                && ItemRef::downcast_pin::<BoxShadow>(item).is_none()
-            {
-                return (ItemVisitorResult::Continue(()), ());
-            }
-            item.as_ref().render(&mut (*renderer.borrow_mut() as &mut dyn ItemRenderer));
-
-            (ItemVisitorResult::Continue(()), ())
-        },
-        |_, _, _, r| {
-            renderer.borrow_mut().restore_state();
-            r
-        },
-        (),
-    );
+    let visitor = |_: &ComponentRc, item: Pin<ItemRef>, _: usize, _: &()| {
+        renderer.borrow_mut().save_state();
 
-    let renderer = renderer.into_inner();
-    renderer.restore_state();
+        let (do_draw, item_geometry) = renderer.borrow_mut().filter_item(item);
+
+        let item_origin = item_geometry.origin;
+        renderer.borrow_mut().translate(item_origin.x, item_origin.y);
+
+        // Don't render items that are clipped, with the exception of the Clip or Flickable since
+        // they themselves clip their content.
+        if !do_draw
+            && !is_clipping_item(item)
+            // HACK, the geometry of the box shadow does not include the shadow, because when the shadow is the root for repeated elements it would translate the children
+            && ItemRef::downcast_pin::<BoxShadow>(item).is_none()
+        {
+            return (ItemVisitorResult::Continue(()), ());
+        }
+        item.as_ref().render(&mut (*renderer.borrow_mut() as &mut dyn ItemRenderer));
+
+        (ItemVisitorResult::Continue(()), ())
+    };
+    let post_visitor = |_: &ComponentRc, _: Pin<ItemRef>, _: (), r: VisitChildrenResult| {
+        renderer.borrow_mut().restore_state();
+        r
+    };
+
+    let order = crate::item_tree::TraversalOrder::BackToFront;
+    match index {
+        Some(index) => crate::item_tree::visit_item_descendants_with_post_visit(
+            component,
+            index,
+            order,
+            visitor,
+            post_visitor,
+            (),
+        ),
+        None => crate::item_tree::visit_items_with_post_visit(
+            component,
+            order,
+            visitor,
+            post_visitor,
+            (),
+        ),
+    };
 }
 
 /// Trait used to render each items.
@@ -186,6 +186,29 @@ pub fn visit_items_with_post_visit<State, PostVisitState>(
     visit_internal(component, order, &mut visitor, &mut post_visitor, -1, &state)
 }
 
+/// Same as [`visit_items_with_post_visit`], but only visits the descendants of the item at
+/// `index` in the component
+pub fn visit_item_descendants_with_post_visit<State, PostVisitState>(
+    component: &ComponentRc,
+    index: usize,
+    order: TraversalOrder,
+    mut visitor: impl FnMut(
+        &ComponentRc,
+        Pin<ItemRef>,
+        usize,
+        &State,
+    ) -> (ItemVisitorResult<State>, PostVisitState),
+    mut post_visitor: impl FnMut(
+        &ComponentRc,
+        Pin<ItemRef>,
+        PostVisitState,
+        VisitChildrenResult,
+    ) -> VisitChildrenResult,
+    state: State,
+) -> VisitChildrenResult {
+    visit_internal(component, order, &mut visitor, &mut post_visitor, index as isize, &state)
+}
+
 fn visit_internal<State, PostVisitState>(
     component: &ComponentRc,
     order: TraversalOrder,
@@ -210,6 +210,12 @@ fn eq(&self, other: &Self) -> bool {
 
 impl Eq for ItemWeak {}
 
+impl core::fmt::Debug for ItemWeak {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ItemWeak").field("index", &self.index).finish()
+    }
+}
+
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement)]
 #[pin]
@@ -577,6 +583,9 @@ pub struct FocusScope {
     pub width: Property<f32>,
     pub height: Property<f32>,
     pub has_focus: Property<bool>,
+    /// Position in the Tab focus chain: negative values exclude the item, zero follows the
+    /// item tree order, and positive values come first in ascending order.
+    pub tab_index: Property<i32>,
     pub key_pressed: Callback<KeyEventArg, EventResult>,
     pub key_released: Callback<KeyEventArg, EventResult>,
     /// FIXME: remove this
@@ -806,6 +815,75 @@ impl ItemConsts for Opacity {
     fn slint_get_OpacityVTable() -> OpacityVTable for Opacity
 }
 
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The Accessible Item is not meant to be used directly by the .slint code, instead, the
+/// `accessible-role`, `accessible-label`, `accessible-value` and `accessible-action-default`
+/// properties should be used
+pub struct Accessible {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub role: Property<AccessibleRole>,
+    pub label: Property<SharedString>,
+    pub value: Property<SharedString>,
+    pub action_default: Callback<VoidArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for Accessible {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(self: Pin<&Self>, _backend: &mut ItemRendererRef) {}
+}
+
+impl ItemConsts for Accessible {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        Accessible,
+        CachedRenderingData,
+    > = Accessible::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_AccessibleVTable() -> AccessibleVTable for Accessible
+}
+
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement)]
 #[pin]
@@ -1041,6 +1119,10 @@ pub struct WindowItem {
     pub default_font_family: Property<SharedString>,
     pub default_font_size: Property<f32>,
     pub default_font_weight: Property<i32>,
+    pub safe_area_inset_top: Property<f32>,
+    pub safe_area_inset_bottom: Property<f32>,
+    pub safe_area_inset_left: Property<f32>,
+    pub safe_area_inset_right: Property<f32>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -1259,6 +1341,31 @@ fn default() -> Self {
     }
 }
 
+/// The role of an element, as reported to assistive technologies such as screen readers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum AccessibleRole {
+    none,
+    button,
+    checkbox,
+    combobox,
+    list,
+    list_item,
+    progress_indicator,
+    slider,
+    spinbox,
+    tab,
+    text,
+    text_input,
+}
+
+impl Default for AccessibleRole {
+    fn default() -> Self {
+        Self::none
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
 #[repr(C)]
 #[allow(non_camel_case_types)]
@@ -8,8 +8,8 @@
 Lookup the [`crate::items`] module documentation.
 */
 
-use super::{Item, ItemConsts, ItemRc, PointArg, PointerEventButton, VoidArg};
-use crate::graphics::{Brush, Color, FontRequest, Rect};
+use super::{Item, ItemConsts, ItemRc, ItemRef, PointArg, PointerEventButton, VoidArg};
+use crate::graphics::{Brush, Color, FontRequest, Point, Rect};
 use crate::input::{
     key_codes, FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent,
     KeyEventResult, KeyEventType, KeyboardModifiers, MouseEvent,
@@ -20,7 +20,10 @@
 use crate::rtti::*;
 use crate::window::WindowRc;
 use crate::{Callback, Property, SharedString};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
 use alloc::string::String;
+use alloc::vec::Vec;
 use const_field_offset::FieldOffsets;
 use core::pin::Pin;
 use i_slint_core_macros::*;
@@ -38,6 +41,9 @@ pub enum InputType {
     text,
     /// This type is used for password inputs where the characters are represented as *'s
     password,
+    /// This type is used for numbers: only the digits, the signs, and the decimal separators
+    /// can be entered
+    number,
 }
 impl Default for InputType {
     fn default() -> Self {
@@ -263,12 +269,14 @@ pub struct TextInput {
     pub text_cursor_width: Property<f32>,
     pub cursor_visible: Property<bool>,
     pub has_focus: Property<bool>,
+    pub tab_index: Property<i32>,
     pub enabled: Property<bool>,
     pub accepted: Callback<VoidArg>,
     pub cursor_position_changed: Callback<PointArg>,
     pub edited: Callback<VoidArg>,
     pub pressed: core::cell::Cell<bool>,
     pub single_line: Property<bool>,
+    pub spell_check: Property<bool>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -351,6 +359,17 @@ fn input_event(
                     window.clone().set_focus_item(self_rc);
                 }
             }
+            MouseEvent::MousePressed { pos, button: PointerEventButton::right }
+                if self.spell_check() =>
+            {
+                let offset = window.text_input_byte_offset_for_position(self, pos);
+                let misspelling =
+                    self.misspellings().into_iter().find(|m| m.range.contains(&offset));
+                match misspelling {
+                    Some(misspelling) => self.show_suggestions(misspelling, pos, window, self_rc),
+                    None => return InputEventResult::EventIgnored,
+                }
+            }
             MouseEvent::MouseReleased { button: PointerEventButton::left, .. }
             | MouseEvent::MouseExit => self.as_ref().pressed.set(false),
             MouseEvent::MouseMoved { pos } => {
@@ -419,6 +438,9 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &WindowRc) -> KeyEventR
                     }
                     return KeyEventResult::EventIgnored;
                 }
+                if !self.accepts_text(&event.text) {
+                    return KeyEventResult::EventIgnored;
+                }
                 self.delete_selection(window);
 
                 let mut text: String = self.text().into();
@@ -641,7 +663,21 @@ fn selected_text(self: Pin<&Self>) -> String {
         text.split_at(anchor).1.split_at(cursor - anchor).0.into()
     }
 
+    /// Returns false if the text cannot be entered in this input, because it is not a number
+    /// in a `number` input
+    fn accepts_text(self: Pin<&Self>, text: &str) -> bool {
+        match self.input_type() {
+            InputType::number => {
+                text.chars().all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | ','))
+            }
+            InputType::text | InputType::password => true,
+        }
+    }
+
     fn insert(self: Pin<&Self>, text_to_insert: &str, window: &WindowRc) {
+        if !self.accepts_text(text_to_insert) {
+            return;
+        }
         self.delete_selection(window);
         let mut text: String = self.text().into();
         let cursor_pos = self.selection_anchor_and_cursor().1;
@@ -657,6 +693,62 @@ fn insert(self: Pin<&Self>, text_to_insert: &str, window: &WindowRc) {
         Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
     }
 
+    /// Inserts the text that an input method, such as an on-screen keyboard, committed, in
+    /// place of the selection. Unlike a key event, the text is never a shortcut.
+    pub fn commit_text(self: Pin<&Self>, text: &str, window: &WindowRc) {
+        if !self.enabled() || text.is_empty() {
+            return;
+        }
+        self.insert(text, window);
+        self.as_ref().show_cursor(window);
+    }
+
+    /// Returns the misspelled words of the text, or none if spell checking is disabled
+    pub fn misspellings(self: Pin<&Self>) -> Vec<crate::spell_check::Misspelling> {
+        if !self.spell_check() || matches!(self.input_type(), InputType::password) {
+            return Vec::new();
+        }
+        crate::spell_check::misspellings(&self.text())
+    }
+
+    fn show_suggestions(
+        self: Pin<&Self>,
+        misspelling: crate::spell_check::Misspelling,
+        pos: Point,
+        window: &WindowRc,
+        self_rc: &ItemRc,
+    ) {
+        let mut position = pos;
+        let mut item = self_rc.clone();
+        loop {
+            position += item.borrow().as_ref().geometry().origin.to_vector();
+            item = match item.parent_item().upgrade() {
+                None => break,
+                Some(parent) => parent,
+            }
+        }
+
+        // The word is only replaced if the text is still the one that was checked
+        let text = self.text();
+        let range = misspelling.range.clone();
+        let item = self_rc.downgrade();
+        let window = Rc::downgrade(window);
+        let replace = move |replacement: &str| {
+            let (item, window) = match (item.upgrade(), window.upgrade()) {
+                (Some(item), Some(window)) => (item, window),
+                _ => return,
+            };
+            if let Some(text_input) = ItemRef::downcast_pin::<TextInput>(item.borrow()) {
+                if text_input.text() == text {
+                    text_input.anchor_position.set(range.start as i32);
+                    text_input.set_cursor_position(range.end as i32, &window);
+                    text_input.insert(replacement, &window);
+                }
+            }
+        };
+        crate::spell_check::show_suggestions(misspelling, position, Box::new(replace));
+    }
+
     fn select_all(self: Pin<&Self>, window: &WindowRc) {
         self.move_cursor(TextCursorDirection::StartOfText, AnchorMode::MoveAnchor, window);
         self.move_cursor(TextCursorDirection::EndOfText, AnchorMode::KeepAnchor, window);
@@ -60,6 +60,7 @@ unsafe impl<T> Send for OnceCell<T> {}
     unsafe impl<T> Sync for OnceCell<T> {}
 }
 
+pub mod accessibility;
 pub mod animations;
 pub mod api;
 pub mod backend;
@@ -76,7 +77,10 @@ unsafe impl<T> Sync for OnceCell<T> {}
 pub mod properties;
 pub mod sharedvector;
 pub mod slice;
+pub mod spell_check;
 pub mod string;
+#[cfg(feature = "std")]
+pub mod svg_export;
 pub mod tests;
 pub mod timers;
 pub mod window;
@@ -137,6 +141,8 @@ pub fn use_modules() -> usize {
             + timers::ffi::slint_timer_start as usize
             + graphics::color::ffi::slint_color_brighter as usize
             + graphics::image::ffi::slint_image_size as usize
+            + animations::ffi::slint_animation_tick as usize
+            + accessibility::ffi::slint_high_contrast as usize
     }
     #[cfg(not(feature = "ffi"))]
     {
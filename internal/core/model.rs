@@ -11,7 +11,7 @@
 use crate::items::ItemRef;
 use crate::layout::Orientation;
 use crate::properties::dependency_tracker::DependencyNode;
-use crate::{Property, SharedVector};
+use crate::{Property, SharedString, SharedVector};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::cell::{Cell, RefCell};
@@ -90,6 +90,23 @@ pub fn row_changed(&self, row: usize) {
             inner.as_ref().project_ref().peers.for_each(|p| unsafe { &**p }.row_changed(row))
         }
     }
+    /// Notify the peers that only some fields of the struct in a specific row were changed.
+    ///
+    /// The fields are named as in the `.slint` file, where `-` and `_` are equivalent.
+    /// The repeaters then only update the bindings that depend on these fields, instead of
+    /// all the bindings of the row. The other peers treat this like [`Self::row_changed`].
+    pub fn row_fields_changed(&self, row: usize, fields: &[&str]) {
+        if let Some(inner) = self.inner.get() {
+            if inner.tracked_rows.borrow().binary_search(&row).is_ok() {
+                inner.model_row_data_dirty_property.mark_dirty();
+            }
+            inner
+                .as_ref()
+                .project_ref()
+                .peers
+                .for_each(|p| unsafe { &**p }.row_fields_changed(row, fields))
+        }
+    }
     /// Notify the peers that rows were added
     pub fn row_added(&self, index: usize, count: usize) {
         if let Some(inner) = self.inner.get() {
@@ -559,6 +576,217 @@ fn as_any(&self) -> &dyn core::any::Any {
     }
 }
 
+/// A model that wraps another model to find out why a repeater doesn't update.
+///
+/// It logs the accesses to the rows and the notifications of the wrapped model on stderr, and
+/// checks that they are consistent:
+///  * The rows that are accessed or notified must be within the bounds of the model.
+///  * The row count must only change together with a [`ModelNotify::row_added`] or
+///    [`ModelNotify::row_removed`] notification, and the model must be changed before it sends
+///    the notification.
+///
+/// The problems are logged as errors, and counted by [`Self::error_count`].
+///
+/// ```
+/// # use i_slint_core::model::*;
+/// # use std::rc::Rc;
+/// let names = Rc::new(VecModel::from(vec![1, 2]));
+/// // Give this model to the component instead of `names`
+/// let debug_model = ModelRc::new(DebugModel::new("names", names.clone()));
+/// names.push(3);
+/// assert_eq!(debug_model.row_count(), 3);
+/// ```
+pub struct DebugModel<M: Model + 'static> {
+    inner: Rc<DebugModelInner<M>>,
+    /// Attached to the wrapped model, and points to `inner`
+    peer: Pin<Rc<DependencyNode<*const dyn ErasedRepeater>>>,
+}
+
+struct DebugModelInner<M: Model> {
+    model: M,
+    name: SharedString,
+    notify: ModelNotify,
+    /// The row count after the last notification
+    row_count: Cell<usize>,
+    error_count: Cell<usize>,
+}
+
+impl<M: Model> DebugModelInner<M> {
+    fn log(&self, _message: core::fmt::Arguments) {
+        #[cfg(feature = "std")]
+        eprintln!("DebugModel {}: {}", self.name, _message);
+    }
+
+    fn error(&self, _message: core::fmt::Arguments) {
+        self.error_count.set(self.error_count.get() + 1);
+        #[cfg(feature = "std")]
+        eprintln!("DebugModel {}: error: {}", self.name, _message);
+    }
+
+    /// Checks that the row count didn't change since the last notification
+    fn check_row_count(&self) -> usize {
+        let row_count = self.model.row_count();
+        if row_count != self.row_count.get() {
+            self.error(format_args!(
+                "the row count changed from {} to {} without a notification",
+                self.row_count.get(),
+                row_count
+            ));
+            self.row_count.set(row_count);
+        }
+        row_count
+    }
+
+    /// Checks that the row count changed as the notification says
+    fn check_row_count_change(&self, notification: core::fmt::Arguments, expected: usize) {
+        let row_count = self.model.row_count();
+        if row_count != expected {
+            self.error(format_args!(
+                "{} while the row count went from {} to {} instead of {}. The model must be \
+                 changed before sending the notification",
+                notification,
+                self.row_count.get(),
+                row_count,
+                expected
+            ));
+        }
+        self.row_count.set(row_count);
+    }
+}
+
+impl<M: Model> ErasedRepeater for DebugModelInner<M> {
+    fn row_changed(&self, row: usize) {
+        self.log(format_args!("row_changed({})", row));
+        if row >= self.check_row_count() {
+            self.error(format_args!("row_changed({}) is out of bounds", row));
+        }
+        self.notify.row_changed(row)
+    }
+    fn row_fields_changed(&self, row: usize, fields: &[&str]) {
+        self.log(format_args!("row_fields_changed({}, {:?})", row, fields));
+        if row >= self.check_row_count() {
+            self.error(format_args!("row_fields_changed({}) is out of bounds", row));
+        }
+        self.notify.row_fields_changed(row, fields)
+    }
+    fn row_added(&self, index: usize, count: usize) {
+        self.log(format_args!("row_added({}, {})", index, count));
+        if index > self.row_count.get() {
+            self.error(format_args!("row_added({}, {}) is out of bounds", index, count));
+        }
+        self.check_row_count_change(
+            format_args!("row_added({}, {})", index, count),
+            self.row_count.get() + count,
+        );
+        self.notify.row_added(index, count)
+    }
+    fn row_removed(&self, index: usize, count: usize) {
+        self.log(format_args!("row_removed({}, {})", index, count));
+        if index + count > self.row_count.get() {
+            self.error(format_args!("row_removed({}, {}) is out of bounds", index, count));
+        }
+        self.check_row_count_change(
+            format_args!("row_removed({}, {})", index, count),
+            self.row_count.get().saturating_sub(count),
+        );
+        self.notify.row_removed(index, count)
+    }
+}
+
+impl<M: Model + 'static> DebugModel<M> {
+    /// Wraps the model. The name identifies this model in the log.
+    pub fn new(name: &str, model: M) -> Self {
+        let inner = Rc::new(DebugModelInner {
+            row_count: Cell::new(model.row_count()),
+            model,
+            name: name.into(),
+            notify: Default::default(),
+            error_count: Cell::new(0),
+        });
+        let peer = Rc::pin(DependencyNode::new(
+            &*inner as &dyn ErasedRepeater as *const dyn ErasedRepeater,
+        ));
+        inner
+            .model
+            .model_tracker()
+            .attach_peer(ModelPeer { inner: PinWeak::downgrade(peer.clone()) });
+        Self { inner, peer }
+    }
+
+    /// The wrapped model
+    pub fn wrapped_model(&self) -> &M {
+        &self.inner.model
+    }
+
+    /// The amount of problems that were found so far
+    pub fn error_count(&self) -> usize {
+        self.inner.error_count.get()
+    }
+
+    /// Returns a copy of the rows of the model, without logging the accesses. With the
+    /// interpreter, `Value::Model(debug_model.dump())` is the array of the rows.
+    pub fn dump(&self) -> ModelRc<M::Data>
+    where
+        M::Data: 'static,
+    {
+        ModelRc::new(VecModel::from(
+            (0..self.inner.model.row_count())
+                .filter_map(|row| self.inner.model.row_data(row))
+                .collect::<Vec<_>>(),
+        ))
+    }
+}
+
+impl<M: Model + 'static> Drop for DebugModel<M> {
+    fn drop(&mut self) {
+        // The peer points to `inner`, so it must not be accessed anymore
+        self.peer.remove();
+    }
+}
+
+impl<M: Model + 'static> Model for DebugModel<M> {
+    type Data = M::Data;
+
+    fn row_count(&self) -> usize {
+        self.inner.check_row_count()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        self.inner.log(format_args!("row_data({})", row));
+        let row_count = self.inner.check_row_count();
+        let data = self.inner.model.row_data(row);
+        if row >= row_count {
+            self.inner.error(format_args!(
+                "row_data({}) is out of bounds, the row count is {}",
+                row, row_count
+            ));
+        } else if data.is_none() {
+            self.inner.error(format_args!("row_data({}) returned None", row));
+        }
+        data
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        self.inner.log(format_args!("set_row_data({})", row));
+        let row_count = self.inner.check_row_count();
+        if row >= row_count {
+            self.inner.error(format_args!(
+                "set_row_data({}) is out of bounds, the row count is {}",
+                row, row_count
+            ));
+        }
+        self.inner.model.set_row_data(row, data)
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.inner.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
 /// Component that can be instantiated by a repeater.
 pub trait RepeatedComponent: crate::component::Component {
     /// The data corresponding to the model
@@ -567,6 +795,14 @@ pub trait RepeatedComponent: crate::component::Component {
     /// Update this component at the given index and the given data
     fn update(&self, index: usize, data: Self::Data);
 
+    /// Update this component at the given index and the given data, when only the given fields
+    /// of the data changed since the last update. The field names use `-` and not `_`.
+    ///
+    /// The default implementation calls [`Self::update`].
+    fn update_fields(&self, index: usize, data: Self::Data, _fields: &[SharedString]) {
+        self.update(index, data)
+    }
+
     /// Layout this item in the listview
     ///
     /// offset_y is the `y` position where this item should be placed.
@@ -587,12 +823,32 @@ fn box_layout_data(
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 enum RepeatedComponentState {
     /// The item is in a clean state
     Clean,
     /// The model data is stale and needs to be refreshed
     Dirty,
+    /// Only these fields of the model data are stale
+    FieldsDirty(Vec<SharedString>),
+}
+
+impl RepeatedComponentState {
+    /// Updates the component with the data of the row if it is not clean, and marks it clean
+    fn update<C: RepeatedComponent>(
+        &mut self,
+        component: &ComponentRc<C>,
+        row: usize,
+        model: &ModelRc<C::Data>,
+    ) {
+        match core::mem::replace(self, RepeatedComponentState::Clean) {
+            RepeatedComponentState::Clean => {}
+            RepeatedComponentState::Dirty => component.update(row, model.row_data(row).unwrap()),
+            RepeatedComponentState::FieldsDirty(fields) => {
+                component.update_fields(row, model.row_data(row).unwrap(), &fields)
+            }
+        }
+    }
 }
 struct RepeaterInner<C: RepeatedComponent> {
     components: Vec<(RepeatedComponentState, Option<ComponentRc<C>>)>,
@@ -601,15 +857,24 @@ struct RepeaterInner<C: RepeatedComponent> {
     offset: usize,
     /// The average visible item_height. Only used for ListView
     cached_item_height: f32,
+    /// Set when the repeater is the one of a ListView, whose instances are re-used
+    /// for the rows of a new model.
+    is_listview: bool,
 }
 
 impl<C: RepeatedComponent> Default for RepeaterInner<C> {
     fn default() -> Self {
-        RepeaterInner { components: Default::default(), offset: 0, cached_item_height: 0. }
+        RepeaterInner {
+            components: Default::default(),
+            offset: 0,
+            cached_item_height: 0.,
+            is_listview: false,
+        }
     }
 }
 trait ErasedRepeater {
     fn row_changed(&self, row: usize);
+    fn row_fields_changed(&self, row: usize, fields: &[&str]);
     fn row_added(&self, index: usize, count: usize);
     fn row_removed(&self, index: usize, count: usize);
 }
@@ -624,6 +889,22 @@ fn row_changed(&self, row: usize) {
             c.0 = RepeatedComponentState::Dirty;
         }
     }
+    /// Notify the peers that some fields of a specific row were changed
+    fn row_fields_changed(&self, row: usize, fields: &[&str]) {
+        self.is_dirty.set(true);
+        let mut inner = self.inner.borrow_mut();
+        let inner = &mut *inner;
+        if let Some(c) = inner.components.get_mut(row.wrapping_sub(inner.offset)) {
+            let fields = fields.iter().map(|f| SharedString::from(f.replace('_', "-")));
+            match &mut c.0 {
+                state @ RepeatedComponentState::Clean => {
+                    *state = RepeatedComponentState::FieldsDirty(fields.collect())
+                }
+                RepeatedComponentState::Dirty => {}
+                RepeatedComponentState::FieldsDirty(dirty_fields) => dirty_fields.extend(fields),
+            }
+        }
+    }
     /// Notify the peers that rows were added
     fn row_added(&self, mut index: usize, mut count: usize) {
         let mut inner = self.inner.borrow_mut();
@@ -721,7 +1002,18 @@ fn model(self: Pin<&Self>) -> ModelRc<C::Data> {
         let model = self.project_ref().model;
 
         if model.is_dirty() {
-            *self.inner.borrow_mut() = RepeaterInner::default();
+            let mut inner = self.inner.borrow_mut();
+            if inner.is_listview {
+                // Keep the existing instances so they can be re-used for the rows of the new model
+                // instead of instantiating new components.
+                inner.offset = 0;
+                for c in inner.components.iter_mut() {
+                    c.0 = RepeatedComponentState::Dirty;
+                }
+            } else {
+                *inner = RepeaterInner::default();
+            }
+            drop(inner);
             self.is_dirty.set(true);
             let m = model.get();
             let peer = self.peer.get_or_init(|| {
@@ -759,13 +1051,13 @@ fn ensure_updated_impl(
         let offset = inner.offset;
         let mut created = false;
         for (i, c) in inner.components.iter_mut().enumerate() {
-            if c.0 == RepeatedComponentState::Dirty {
+            if c.0 != RepeatedComponentState::Clean {
                 if c.1.is_none() {
                     created = true;
                     c.1 = Some(init());
+                    c.0 = RepeatedComponentState::Dirty;
                 }
-                c.1.as_ref().unwrap().update(i + offset, model.row_data(i + offset).unwrap());
-                c.0 = RepeatedComponentState::Clean;
+                c.0.update(c.1.as_ref().unwrap(), i + offset, model);
             }
         }
         self.is_dirty.set(false);
@@ -782,6 +1074,7 @@ pub fn ensure_updated_listview(
         listview_width: f32,
         listview_height: Pin<&Property<f32>>,
     ) {
+        self.inner.borrow_mut().is_listview = true;
         let model = self.model();
         let row_count = model.row_count();
         if row_count == 0 {
@@ -895,21 +1188,28 @@ pub fn ensure_updated_listview(
         }
     }
 
+    /// Set the model row of the first component and the amount of components.
+    ///
+    /// The components for rows that stay in the range are kept as is, and the components of
+    /// the rows that go out of the range are recycled for the rows that come in range.
     fn set_offset(&self, offset: usize, count: usize) {
         let mut inner = self.inner.borrow_mut();
         let old_offset = inner.offset;
-        // Remove the items before the offset, or add items until the old offset
-        let to_remove = offset.saturating_sub(old_offset);
-        if to_remove < inner.components.len() {
-            inner.components.splice(
-                0..to_remove,
-                core::iter::repeat((RepeatedComponentState::Dirty, None))
-                    .take(old_offset.saturating_sub(offset)),
-            );
-        } else {
-            inner.components.truncate(0);
+        let mut recycled = Vec::new();
+        let mut kept = Vec::new();
+        for (i, c) in core::mem::take(&mut inner.components).into_iter().enumerate() {
+            let row = old_offset + i;
+            if row >= offset && row < offset + count {
+                kept.push(c);
+            } else if let Some(component) = c.1 {
+                recycled.push(component);
+            }
         }
-        inner.components.resize_with(count, || (RepeatedComponentState::Dirty, None));
+        // The rows between the new offset and the old one are new
+        let added_before = old_offset.saturating_sub(offset).min(count);
+        let mut new_row = || (RepeatedComponentState::Dirty, recycled.pop());
+        inner.components = (0..added_before).map(|_| new_row()).chain(kept).collect();
+        inner.components.resize_with(count, new_row);
         inner.offset = offset;
         self.is_dirty.set(true);
     }
@@ -919,11 +1219,8 @@ pub fn model_set_row_data(self: Pin<&Self>, row: usize, data: C::Data) {
         let model = self.model();
         model.set_row_data(row, data);
         if let Some(c) = self.inner.borrow_mut().components.get_mut(row) {
-            if c.0 == RepeatedComponentState::Dirty {
-                if let Some(comp) = c.1.as_ref() {
-                    comp.update(row, model.row_data(row).unwrap());
-                    c.0 = RepeatedComponentState::Clean;
-                }
+            if let Some(comp) = c.1.as_ref() {
+                c.0.update(comp, row, &model);
             }
         }
     }
@@ -935,6 +1232,17 @@ pub fn set_model_binding(&self, binding: impl Fn() -> ModelRc<C::Data> + 'static
         self.model.set_binding(binding);
     }
 
+    /// Drop all the component instances.
+    ///
+    /// They will be instantiated again the next time the repeater is updated, instead of being
+    /// re-used for the rows of the model.
+    pub fn clear(self: Pin<&Self>) {
+        // Take the components out first, so that they are not dropped while `inner` is borrowed
+        let components = core::mem::take(&mut self.inner.borrow_mut().components);
+        self.is_dirty.set(true);
+        drop(components);
+    }
+
     /// Call the visitor for each component
     pub fn visit(
         &self,
@@ -997,6 +1305,78 @@ pub fn compute_layout_listview(
 pub struct StandardListViewItem {
     /// The text content of the item
     pub text: crate::SharedString,
+    /// An icon shown before the text
+    pub icon: crate::graphics::Image,
+    /// A text shown below the main text, in a less prominent way
+    pub secondary_text: crate::SharedString,
+    /// When true, the item is greyed out and cannot be selected
+    pub disabled: bool,
+    /// Data that is not shown, but that the application can use to identify the item
+    pub user_data: crate::SharedString,
+}
+
+impl From<&str> for StandardListViewItem {
+    fn from(text: &str) -> Self {
+        Self { text: text.into(), ..Default::default() }
+    }
+}
+
+impl From<crate::SharedString> for StandardListViewItem {
+    fn from(text: crate::SharedString) -> Self {
+        Self { text, ..Default::default() }
+    }
+}
+
+/// Represent a calendar date, as used by the DatePicker widget
+#[repr(C)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year, for example 2022
+    pub year: i32,
+    /// The month, from 1 (January) to 12 (December)
+    pub month: i32,
+    /// The day of the month, from 1 to 31
+    pub day: i32,
+}
+
+/// Represent a time of the day, as used by the TimePicker widget
+#[repr(C)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// The hour, from 0 to 23
+    pub hour: i32,
+    /// The minute, from 0 to 59
+    pub minute: i32,
+    /// The second, from 0 to 59
+    pub second: i32,
+}
+
+/// Returns the index of the first row of `model`, starting at the row `from` and wrapping around
+/// to the first row, whose text starts with `prefix`, ignoring the case. `text` returns the text of
+/// a row. Returns -1 if no row matches or if the prefix is empty.
+///
+/// This implements `find-starting-with()` on arrays in .slint
+pub fn find_row_starting_with<T>(
+    model: &ModelRc<T>,
+    prefix: &str,
+    from: i32,
+    text: impl Fn(&T) -> SharedString,
+) -> i32 {
+    model.model_tracker().track_row_count_changes();
+    let row_count = model.row_count();
+    let prefix = prefix.to_lowercase();
+    if prefix.is_empty() || row_count == 0 {
+        return -1;
+    }
+    let from = from.max(0) as usize % row_count;
+    (from..row_count)
+        .chain(0..from)
+        .find(|row| {
+            model
+                .row_data(*row)
+                .map_or(false, |data| text(&data).to_lowercase().starts_with(&prefix))
+        })
+        .map_or(-1, |row| row as i32)
 }
 
 #[test]
@@ -1081,3 +1461,68 @@ fn test_data_tracking() {
     model.insert(0, 255);
     assert!(tracker.is_dirty());
 }
+
+#[test]
+fn test_debug_model() {
+    #[derive(Default)]
+    struct NotifyTooEarly {
+        rows: RefCell<Vec<u8>>,
+        notify: ModelNotify,
+    }
+    impl Model for NotifyTooEarly {
+        type Data = u8;
+        fn row_count(&self) -> usize {
+            self.rows.borrow().len()
+        }
+        fn row_data(&self, row: usize) -> Option<u8> {
+            self.rows.borrow().get(row).cloned()
+        }
+        fn model_tracker(&self) -> &dyn ModelTracker {
+            &self.notify
+        }
+    }
+
+    let model = Rc::new(VecModel::from(vec![1u8, 2]));
+    let debug_model = DebugModel::new("vec", model.clone());
+    let tracker = Box::pin(crate::properties::PropertyTracker::default());
+    let row_count = || {
+        tracker.as_ref().evaluate(|| {
+            debug_model.model_tracker().track_row_count_changes();
+            debug_model.row_count()
+        })
+    };
+    assert_eq!(row_count(), 2);
+    model.push(3);
+    assert!(tracker.is_dirty());
+    assert_eq!(row_count(), 3);
+    model.remove(0);
+    model.set_row_data(0, 4);
+    assert_eq!(debug_model.error_count(), 0);
+    assert_eq!(debug_model.dump().iter().collect::<Vec<_>>(), vec![4, 3]);
+
+    assert_eq!(debug_model.row_data(2), None);
+    assert_eq!(debug_model.error_count(), 1);
+
+    let model = Rc::new(NotifyTooEarly::default());
+    let debug_model = DebugModel::new("too early", model.clone());
+    model.notify.row_added(0, 1);
+    model.rows.borrow_mut().push(1);
+    assert_eq!(debug_model.error_count(), 1);
+    model.rows.borrow_mut().push(2);
+    assert_eq!(debug_model.row_count(), 2);
+    assert_eq!(debug_model.error_count(), 2);
+}
+
+#[test]
+fn test_find_row_starting_with() {
+    let model: ModelRc<SharedString> =
+        Rc::new(VecModel::from(vec!["Apple".into(), "banana".into(), "Avocado".into()])).into();
+    let find = |prefix, from| find_row_starting_with(&model, prefix, from, |row| row.clone());
+    assert_eq!(find("a", 0), 0);
+    assert_eq!(find("a", 1), 2);
+    assert_eq!(find("A", 3), 0);
+    assert_eq!(find("B", 2), 1);
+    assert_eq!(find("av", 0), 2);
+    assert_eq!(find("c", 0), -1);
+    assert_eq!(find("", 0), -1);
+}
@@ -228,6 +228,7 @@ fn drop(&mut self) {
 use core::marker::PhantomPinned;
 use core::pin::Pin;
 
+use crate::animations::AnimationFinishedCallback;
 use crate::items::PropertyAnimation;
 
 /// if a DependencyListHead points to that value, it is because the property is actually
@@ -887,12 +888,23 @@ impl<T: Clone + InterpolatedPropertyValue + 'static> Property<T> {
     /// If other properties have binding depending of this property, these properties will
     /// be marked as dirty.
     pub fn set_animated_value(&self, value: T, animation_data: PropertyAnimation) {
+        self.set_animated_value_with_finished_callback(value, animation_data, None)
+    }
+
+    /// Same as [`Self::set_animated_value`], but `finished_callback` is invoked once the animation is over.
+    pub fn set_animated_value_with_finished_callback(
+        &self,
+        value: T,
+        animation_data: PropertyAnimation,
+        finished_callback: Option<AnimationFinishedCallback>,
+    ) {
         // FIXME if the current value is a dirty binding, we must run it, but we do not have the context
-        let d = RefCell::new(PropertyValueAnimationData::new(
+        let d = AnimationDataOwner::new(PropertyValueAnimationData::new(
             self.get_internal(),
             value,
             animation_data,
         ));
+        watch_animation(&d, &finished_callback);
         // Safety: the BindingCallable will cast its argument to T
         unsafe {
             self.handle.set_binding(
@@ -923,6 +935,16 @@ pub fn set_animated_binding(
         &self,
         binding: impl Binding<T> + 'static,
         animation_data: PropertyAnimation,
+    ) {
+        self.set_animated_binding_with_finished_callback(binding, animation_data, None)
+    }
+
+    /// Same as [`Self::set_animated_binding`], but `finished_callback` is invoked each time an animation is over.
+    pub fn set_animated_binding_with_finished_callback(
+        &self,
+        binding: impl Binding<T> + 'static,
+        animation_data: PropertyAnimation,
+        finished_callback: Option<AnimationFinishedCallback>,
     ) {
         let binding_callable = AnimatedBindingCallable::<T, _> {
             original_binding: PropertyHandle {
@@ -936,12 +958,13 @@ pub fn set_animated_binding(
                 ),
             },
             state: Cell::new(AnimatedBindingState::NotAnimating),
-            animation_data: RefCell::new(PropertyValueAnimationData::new(
+            animation_data: AnimationDataOwner::new(PropertyValueAnimationData::new(
                 T::default(),
                 T::default(),
                 animation_data,
             )),
             compute_animation_details: || -> AnimationDetail { None },
+            finished_callback,
         };
 
         // Safety: the `AnimatedBindingCallable`'s type match the property type
@@ -965,6 +988,22 @@ pub fn set_animated_binding_for_transition(
         binding: impl Binding<T> + 'static,
         compute_animation_details: impl Fn() -> (PropertyAnimation, crate::animations::Instant)
             + 'static,
+    ) {
+        self.set_animated_binding_for_transition_with_finished_callback(
+            binding,
+            compute_animation_details,
+            None,
+        )
+    }
+
+    /// Same as [`Self::set_animated_binding_for_transition`], but `finished_callback` is invoked
+    /// each time a transition is over.
+    pub fn set_animated_binding_for_transition_with_finished_callback(
+        &self,
+        binding: impl Binding<T> + 'static,
+        compute_animation_details: impl Fn() -> (PropertyAnimation, crate::animations::Instant)
+            + 'static,
+        finished_callback: Option<AnimationFinishedCallback>,
     ) {
         let binding_callable = AnimatedBindingCallable::<T, _> {
             original_binding: PropertyHandle {
@@ -978,12 +1017,13 @@ pub fn set_animated_binding_for_transition(
                 ),
             },
             state: Cell::new(AnimatedBindingState::NotAnimating),
-            animation_data: RefCell::new(PropertyValueAnimationData::new(
+            animation_data: AnimationDataOwner::new(PropertyValueAnimationData::new(
                 T::default(),
                 T::default(),
                 PropertyAnimation::default(),
             )),
             compute_animation_details: move || Some(compute_animation_details()),
+            finished_callback,
         };
 
         // Safety: the `AnimatedBindingCallable`'s type match the property type
@@ -1165,35 +1205,57 @@ struct PropertyValueAnimationData<T> {
     from_value: T,
     to_value: T,
     details: PropertyAnimation,
-    start_time: crate::animations::Instant,
+    /// The tick until which the time of the animation was counted
+    last_tick: crate::animations::Instant,
+    /// The time of the current state of the animation, in milliseconds. It advances by the
+    /// elapsed time multiplied by the speed factor, so that changing the factor while the
+    /// animation runs doesn't make the value jump.
+    time_progress: f64,
     state: AnimationState,
+    /// True while the animation driver polls this animation to invoke its finished callback
+    watched: bool,
 }
 
 impl<T: InterpolatedPropertyValue + Clone> PropertyValueAnimationData<T> {
     fn new(from_value: T, to_value: T, details: PropertyAnimation) -> Self {
         let start_time = crate::animations::current_tick();
 
-        Self { from_value, to_value, details, start_time, state: AnimationState::Delaying }
+        Self {
+            from_value,
+            to_value,
+            details,
+            last_tick: start_time,
+            time_progress: 0.,
+            state: AnimationState::Delaying,
+            watched: false,
+        }
     }
 
     fn compute_interpolated_value(&mut self) -> (T, bool) {
+        let (speed_factor, animations_enabled) = crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| (driver.speed_factor(), driver.animations_enabled()));
+        if !animations_enabled || speed_factor <= 0. {
+            self.state = AnimationState::Done;
+        }
         let new_tick = crate::animations::current_tick();
-        let mut time_progress = new_tick.duration_since(self.start_time).as_millis() as u64;
+        if new_tick > self.last_tick {
+            self.time_progress +=
+                new_tick.duration_since(self.last_tick).as_millis() as f64 * speed_factor as f64;
+            self.last_tick = new_tick;
+        }
 
         match self.state {
             AnimationState::Delaying => {
-                if self.details.delay <= 0 {
+                let delay = self.details.delay as f64;
+                if delay <= 0. {
                     self.state = AnimationState::Animating { current_iteration: 0 };
                     return self.compute_interpolated_value();
                 }
 
-                let delay = self.details.delay as u64;
-
-                if time_progress < delay {
+                if self.time_progress < delay {
                     (self.from_value.clone(), false)
                 } else {
-                    self.start_time =
-                        new_tick - core::time::Duration::from_millis(time_progress - delay);
+                    self.time_progress -= delay;
 
                     // Decide on next state:
                     self.state = AnimationState::Animating { current_iteration: 0 };
@@ -1201,28 +1263,25 @@ fn compute_interpolated_value(&mut self) -> (T, bool) {
                 }
             }
             AnimationState::Animating { mut current_iteration } => {
-                if self.details.duration <= 0 || self.details.iteration_count == 0. {
+                let duration = self.details.duration as f64;
+                if duration <= 0. || self.details.iteration_count == 0. {
                     self.state = AnimationState::Done;
                     return self.compute_interpolated_value();
                 }
 
-                let duration = self.details.duration as u64;
-                if time_progress >= duration {
+                if self.time_progress >= duration {
                     // wrap around
-                    current_iteration += time_progress / duration;
-                    time_progress %= duration;
-                    self.start_time =
-                        new_tick - core::time::Duration::from_millis(time_progress as u64);
+                    current_iteration += (self.time_progress / duration) as u64;
+                    self.time_progress %= duration;
                 }
 
                 if (self.details.iteration_count < 0.)
-                    || (((current_iteration * duration) + time_progress) as f64)
-                        < ((self.details.iteration_count as f64) * (duration as f64))
+                    || ((current_iteration as f64 * duration) + self.time_progress)
+                        < ((self.details.iteration_count as f64) * duration)
                 {
                     self.state = AnimationState::Animating { current_iteration };
 
-                    let progress =
-                        (time_progress as f32 / self.details.duration as f32).clamp(0., 1.);
+                    let progress = (self.time_progress / duration).clamp(0., 1.) as f32;
                     let t = crate::animations::easing_curve(&self.details.easing, progress);
                     let val = self.from_value.interpolate(&self.to_value, t);
 
@@ -1238,10 +1297,81 @@ fn compute_interpolated_value(&mut self) -> (T, bool) {
 
     fn reset(&mut self) {
         self.state = AnimationState::Delaying;
-        self.start_time = crate::animations::current_tick();
+        self.set_start_time(crate::animations::current_tick());
+    }
+
+    /// Count the time of the animation from `start_time`
+    fn set_start_time(&mut self, start_time: crate::animations::Instant) {
+        self.last_tick = start_time;
+        self.time_progress = 0.;
+    }
+}
+
+/// Owns the data of the animation of a binding.
+///
+/// Dropping it before the animation is done (because the binding was replaced) cancels the
+/// finished callback.
+struct AnimationDataOwner<T>(Rc<RefCell<PropertyValueAnimationData<T>>>);
+
+impl<T> AnimationDataOwner<T> {
+    fn new(data: PropertyValueAnimationData<T>) -> Self {
+        Self(Rc::new(RefCell::new(data)))
+    }
+}
+
+impl<T> core::ops::Deref for AnimationDataOwner<T> {
+    type Target = RefCell<PropertyValueAnimationData<T>>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
+impl<T> Drop for AnimationDataOwner<T> {
+    fn drop(&mut self) {
+        if let Ok(mut data) = self.0.try_borrow_mut() {
+            if !matches!(data.state, AnimationState::Done) {
+                data.watched = false;
+            }
+        }
+    }
+}
+
+/// Have the animation driver invoke the finished callback when its time tick reaches the end of
+/// the animation, even if the property is not evaluated anymore.
+fn watch_animation<T: InterpolatedPropertyValue + Clone>(
+    animation_data: &AnimationDataOwner<T>,
+    finished_callback: &Option<AnimationFinishedCallback>,
+) {
+    let callback = match finished_callback {
+        Some(callback) => callback.clone(),
+        None => return,
+    };
+    {
+        let mut data = animation_data.borrow_mut();
+        if data.watched {
+            // The animation was restarted before the driver saw it finish
+            return;
+        }
+        data.watched = true;
+    }
+    let data = animation_data.0.clone();
+    crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| {
+        driver.watch_animation(Box::new(move || {
+            let mut d = data.borrow_mut();
+            if !d.watched {
+                return true;
+            }
+            if !d.compute_interpolated_value().1 {
+                return false;
+            }
+            d.watched = false;
+            drop(d);
+            callback();
+            true
+        }))
+    });
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 enum AnimatedBindingState {
     Animating,
@@ -1252,8 +1382,9 @@ enum AnimatedBindingState {
 struct AnimatedBindingCallable<T, A> {
     original_binding: PropertyHandle,
     state: Cell<AnimatedBindingState>,
-    animation_data: RefCell<PropertyValueAnimationData<T>>,
+    animation_data: AnimationDataOwner<T>,
     compute_animation_details: A,
+    finished_callback: Option<AnimationFinishedCallback>,
 }
 
 type AnimationDetail = Option<(PropertyAnimation, crate::animations::Instant)>;
@@ -1272,7 +1403,7 @@ unsafe fn evaluate(self: Pin<&Self>, value: *mut ()) -> BindingResult {
                 let (val, finished) = self.animation_data.borrow_mut().compute_interpolated_value();
                 *(value as *mut T) = val;
                 if finished {
-                    self.state.set(AnimatedBindingState::NotAnimating)
+                    self.state.set(AnimatedBindingState::NotAnimating);
                 } else {
                     crate::animations::CURRENT_ANIMATION_DRIVER
                         .with(|driver| driver.set_has_active_animations());
@@ -1289,17 +1420,19 @@ unsafe fn evaluate(self: Pin<&Self>, value: *mut ()) -> BindingResult {
                 animation_data.from_value = value.clone();
                 self.original_binding.update((&mut animation_data.to_value) as *mut T as *mut ());
                 if let Some((details, start_time)) = (self.compute_animation_details)() {
-                    animation_data.start_time = start_time;
+                    animation_data.set_start_time(start_time);
                     animation_data.details = details;
                 }
                 let (val, finished) = animation_data.compute_interpolated_value();
+                drop(animation_data);
                 *value = val;
                 if finished {
-                    self.state.set(AnimatedBindingState::NotAnimating)
+                    self.state.set(AnimatedBindingState::NotAnimating);
                 } else {
                     crate::animations::CURRENT_ANIMATION_DRIVER
                         .with(|driver| driver.set_has_active_animations());
                 }
+                watch_animation(&self.animation_data, &self.finished_callback);
             }
         };
         BindingResult::KeepBinding
@@ -1988,6 +2121,211 @@ fn test_loop_via_binding() {
 
         assert_eq!(get_prop_value(&compo.width), 300);
     }
+
+    #[test]
+    fn finished_callback_triggered_by_set() {
+        let compo = Component::new_test_component();
+        let finished = Rc::new(Cell::new(0));
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+
+        let start_time = crate::animations::current_tick();
+
+        let f = finished.clone();
+        compo.width.set_animated_value_with_finished_callback(
+            200,
+            animation_details,
+            Some(Rc::new(move || f.set(f.get() + 1))),
+        );
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 2));
+        assert_eq!(get_prop_value(&compo.width), 150);
+        assert_eq!(finished.get(), 0);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION));
+        assert_eq!(finished.get(), 1);
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + 2 * DURATION));
+        assert_eq!(get_prop_value(&compo.width), 200);
+        assert_eq!(finished.get(), 1);
+    }
+
+    #[test]
+    fn finished_callback_without_evaluation() {
+        let compo = Component::new_test_component();
+        let finished = Rc::new(Cell::new(0));
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+
+        let start_time = crate::animations::current_tick();
+
+        let f = finished.clone();
+        compo.width.set_animated_value_with_finished_callback(
+            200,
+            animation_details.clone(),
+            Some(Rc::new(move || f.set(f.get() + 1))),
+        );
+
+        // The property is never read, the driver alone completes the animation
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 2));
+        assert_eq!(finished.get(), 0);
+        assert!(crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.has_active_animations()));
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION));
+        assert_eq!(finished.get(), 1);
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        // Replacing the animated value before the end cancels the callback
+        let start_time = crate::animations::current_tick();
+        let f = finished.clone();
+        compo.width.set_animated_value_with_finished_callback(
+            300,
+            animation_details,
+            Some(Rc::new(move || f.set(f.get() + 1))),
+        );
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 2));
+        compo.width.set(400);
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION));
+        assert_eq!(finished.get(), 1);
+        assert_eq!(get_prop_value(&compo.width), 400);
+    }
+
+    #[test]
+    fn finished_callback_via_binding() {
+        let compo = Component::new_test_component();
+        let finished = Rc::new(Cell::new(0));
+
+        let start_time = crate::animations::current_tick();
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            ..PropertyAnimation::default()
+        };
+
+        let w = Rc::downgrade(&compo);
+        let f = finished.clone();
+        compo.width.set_animated_binding_with_finished_callback(
+            move || {
+                let compo = w.upgrade().unwrap();
+                get_prop_value(&compo.feed_property)
+            },
+            animation_details,
+            Some(Rc::new(move || f.set(f.get() + 1))),
+        );
+
+        compo.feed_property.set(100);
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        compo.feed_property.set(200);
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 2));
+        assert_eq!(finished.get(), 0);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION));
+        assert_eq!(finished.get(), 1);
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        // Restart the animation by setting a new value.
+        let start_time = crate::animations::current_tick();
+        compo.feed_property.set(300);
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION));
+        assert_eq!(finished.get(), 2);
+        assert_eq!(get_prop_value(&compo.width), 300);
+    }
+
+    #[test]
+    fn animation_speed_factor() {
+        let compo = Component::new_test_component();
+
+        let animation_details = PropertyAnimation {
+            delay: DELAY.as_millis() as _,
+            duration: DURATION.as_millis() as _,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+        crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| driver.set_speed_factor(2.));
+
+        let start_time = crate::animations::current_tick();
+
+        compo.width.set_animated_value(200, animation_details);
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DELAY / 2 + DURATION / 4));
+        assert_eq!(get_prop_value(&compo.width), 150);
+
+        // Slow down while running: the animation continues from where it was, at a quarter of
+        // the speed
+        crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| driver.set_speed_factor(0.5));
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DELAY / 2 + DURATION / 2));
+        assert_eq!(get_prop_value(&compo.width), 162);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DELAY / 2 + DURATION * 3 / 4));
+        assert_eq!(get_prop_value(&compo.width), 175);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DELAY / 2 + DURATION * 2));
+        assert_eq!(get_prop_value(&compo.width), 200);
+    }
+
+    #[test]
+    fn animations_disabled() {
+        let compo = Component::new_test_component();
+        let finished = Rc::new(Cell::new(0));
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.set_animations_enabled(false));
+
+        let start_time = crate::animations::current_tick();
+
+        let f = finished.clone();
+        compo.width.set_animated_value_with_finished_callback(
+            200,
+            animation_details,
+            Some(Rc::new(move || f.set(f.get() + 1))),
+        );
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 2));
+        assert_eq!(finished.get(), 1);
+        assert_eq!(get_prop_value(&compo.width), 200);
+    }
 }
 
 /// Value of the state property
@@ -2504,13 +2842,44 @@ pub extern "C" fn slint_property_mark_dirty(handle: &PropertyHandleOpaque) {
         core::ptr::drop_in_place(handle);
     }
 
+    /// Wrap the function called when an animation is finished, and its user data, in an [`AnimationFinishedCallback`]
+    unsafe fn make_c_finished_callback(
+        finished: Option<extern "C" fn(user_data: *mut c_void)>,
+        user_data: *mut c_void,
+        drop_user_data: Option<extern "C" fn(*mut c_void)>,
+    ) -> Option<AnimationFinishedCallback> {
+        struct CFinishedCallback {
+            finished: Option<extern "C" fn(user_data: *mut c_void)>,
+            user_data: *mut c_void,
+            drop_user_data: Option<extern "C" fn(*mut c_void)>,
+        }
+
+        impl Drop for CFinishedCallback {
+            fn drop(&mut self) {
+                if let Some(x) = self.drop_user_data {
+                    x(self.user_data)
+                }
+            }
+        }
+
+        let callback = CFinishedCallback { finished, user_data, drop_user_data };
+        let finished = callback.finished?;
+        Some(Rc::new(move || finished(callback.user_data)))
+    }
+
     fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
         handle: &PropertyHandleOpaque,
         from: T,
         to: T,
         animation_data: &PropertyAnimation,
+        finished_callback: Option<AnimationFinishedCallback>,
     ) {
-        let d = RefCell::new(PropertyValueAnimationData::new(from, to, animation_data.clone()));
+        let d = AnimationDataOwner::new(PropertyValueAnimationData::new(
+            from,
+            to,
+            animation_data.clone(),
+        ));
+        watch_animation(&d, &finished_callback);
         // Safety: The BindingCallable is for type T
         unsafe {
             handle.0.set_binding(move |val: *mut ()| {
@@ -2535,8 +2904,13 @@ fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
         from: i32,
         to: i32,
         animation_data: &PropertyAnimation,
+        finished: Option<extern "C" fn(user_data: *mut c_void)>,
+        finished_user_data: *mut c_void,
+        drop_finished_user_data: Option<extern "C" fn(*mut c_void)>,
     ) {
-        c_set_animated_value(handle, from, to, animation_data)
+        let finished_callback =
+            make_c_finished_callback(finished, finished_user_data, drop_finished_user_data);
+        c_set_animated_value(handle, from, to, animation_data, finished_callback)
     }
 
     /// Internal function to set up a property animation to the specified target value for a float property.
@@ -2546,8 +2920,13 @@ fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
         from: f32,
         to: f32,
         animation_data: &PropertyAnimation,
+        finished: Option<extern "C" fn(user_data: *mut c_void)>,
+        finished_user_data: *mut c_void,
+        drop_finished_user_data: Option<extern "C" fn(*mut c_void)>,
     ) {
-        c_set_animated_value(handle, from, to, animation_data)
+        let finished_callback =
+            make_c_finished_callback(finished, finished_user_data, drop_finished_user_data);
+        c_set_animated_value(handle, from, to, animation_data, finished_callback)
     }
 
     /// Internal function to set up a property animation to the specified target value for a color property.
@@ -2557,8 +2936,13 @@ fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
         from: Color,
         to: Color,
         animation_data: &PropertyAnimation,
+        finished: Option<extern "C" fn(user_data: *mut c_void)>,
+        finished_user_data: *mut c_void,
+        drop_finished_user_data: Option<extern "C" fn(*mut c_void)>,
     ) {
-        c_set_animated_value(handle, from, to, animation_data);
+        let finished_callback =
+            make_c_finished_callback(finished, finished_user_data, drop_finished_user_data);
+        c_set_animated_value(handle, from, to, animation_data, finished_callback);
     }
 
     unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
@@ -2570,6 +2954,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         transition_data: Option<
             extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
         >,
+        finished_callback: Option<AnimationFinishedCallback>,
     ) {
         let binding = core::mem::transmute::<
             extern "C" fn(*mut c_void, *mut T),
@@ -2587,7 +2972,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
                     | 0b10,
             ),
         };
-        let animation_data = RefCell::new(PropertyValueAnimationData::new(
+        let animation_data = AnimationDataOwner::new(PropertyValueAnimationData::new(
             T::default(),
             T::default(),
             animation_data.cloned().unwrap_or_default(),
@@ -2602,6 +2987,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
                     let anim = transition_data(user_data, &mut start_instant);
                     Some((anim, crate::animations::Instant(start_instant)))
                 },
+                finished_callback,
             });
         } else {
             handle.0.set_binding(AnimatedBindingCallable::<T, _> {
@@ -2609,6 +2995,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
                 state: Cell::new(AnimatedBindingState::NotAnimating),
                 animation_data,
                 compute_animation_details: || -> AnimationDetail { None },
+                finished_callback,
             });
         }
         handle.0.mark_dirty();
@@ -2625,6 +3012,9 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         transition_data: Option<
             extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
         >,
+        finished: Option<extern "C" fn(user_data: *mut c_void)>,
+        finished_user_data: *mut c_void,
+        drop_finished_user_data: Option<extern "C" fn(*mut c_void)>,
     ) {
         c_set_animated_binding(
             handle,
@@ -2633,6 +3023,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
             drop_user_data,
             animation_data,
             transition_data,
+            make_c_finished_callback(finished, finished_user_data, drop_finished_user_data),
         );
     }
 
@@ -2647,6 +3038,9 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         transition_data: Option<
             extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
         >,
+        finished: Option<extern "C" fn(user_data: *mut c_void)>,
+        finished_user_data: *mut c_void,
+        drop_finished_user_data: Option<extern "C" fn(*mut c_void)>,
     ) {
         c_set_animated_binding(
             handle,
@@ -2655,6 +3049,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
             drop_user_data,
             animation_data,
             transition_data,
+            make_c_finished_callback(finished, finished_user_data, drop_finished_user_data),
         );
     }
 
@@ -2669,6 +3064,9 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         transition_data: Option<
             extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
         >,
+        finished: Option<extern "C" fn(user_data: *mut c_void)>,
+        finished_user_data: *mut c_void,
+        drop_finished_user_data: Option<extern "C" fn(*mut c_void)>,
     ) {
         c_set_animated_binding(
             handle,
@@ -2677,6 +3075,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
             drop_user_data,
             animation_data,
             transition_data,
+            make_c_finished_callback(finished, finished_user_data, drop_finished_user_data),
         );
     }
 
@@ -2691,6 +3090,9 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         transition_data: Option<
             extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
         >,
+        finished: Option<extern "C" fn(user_data: *mut c_void)>,
+        finished_user_data: *mut c_void,
+        drop_finished_user_data: Option<extern "C" fn(*mut c_void)>,
     ) {
         c_set_animated_binding(
             handle,
@@ -2699,6 +3101,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
             drop_user_data,
             animation_data,
             transition_data,
+            make_c_finished_callback(finished, finished_user_data, drop_finished_user_data),
         );
     }
 
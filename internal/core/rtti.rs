@@ -7,6 +7,7 @@
 */
 
 pub type FieldOffset<T, U> = const_field_offset::FieldOffset<T, U, const_field_offset::AllowPin>;
+use crate::animations::AnimationFinishedCallback;
 use crate::items::PropertyAnimation;
 use alloc::rc::Rc;
 use core::convert::{TryFrom, TryInto};
@@ -39,6 +40,7 @@ pub trait ValueType: 'static + Default + Clone $(+ TryInto<$ty> + TryFrom<$ty>)*
     crate::items::ImageFit,
     crate::items::ImageRendering,
     crate::input::KeyEvent,
+    crate::input::LogicalKey,
     crate::items::EventResult,
     crate::Brush,
     crate::items::FillRule,
@@ -56,19 +58,22 @@ pub trait ValueType: 'static + Default + Clone $(+ TryInto<$ty> + TryFrom<$ty>)*
 pub enum AnimatedBindingKind {
     /// No animation is on the binding
     NotAnimated,
-    /// Single animation
-    Animation(PropertyAnimation),
-    /// Transition
-    Transition(Box<dyn Fn() -> (PropertyAnimation, crate::animations::Instant)>),
+    /// Single animation, and the callback invoked when it is finished
+    Animation(PropertyAnimation, Option<AnimationFinishedCallback>),
+    /// Transition, and the callback invoked when it is finished
+    Transition(
+        Box<dyn Fn() -> (PropertyAnimation, crate::animations::Instant)>,
+        Option<AnimationFinishedCallback>,
+    ),
 }
 
 impl AnimatedBindingKind {
-    /// return a PropertyAnimation if self contains AnimatedBindingKind::Animation
-    pub fn as_animation(self) -> Option<PropertyAnimation> {
+    /// return a PropertyAnimation and its finished callback if self contains AnimatedBindingKind::Animation
+    pub fn as_animation(self) -> Option<(PropertyAnimation, Option<AnimationFinishedCallback>)> {
         match self {
             AnimatedBindingKind::NotAnimated => None,
-            AnimatedBindingKind::Animation(a) => Some(a),
-            AnimatedBindingKind::Transition(_) => None,
+            AnimatedBindingKind::Animation(a, finished) => Some((a, finished)),
+            AnimatedBindingKind::Transition(..) => None,
         }
     }
 }
@@ -79,7 +84,7 @@ fn set(
         &self,
         item: Pin<&Item>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Option<AnimationFinishedCallback>)>,
     ) -> Result<(), ()>;
     fn set_binding(
         &self,
@@ -122,7 +127,7 @@ fn set(
         &self,
         item: Pin<&Item>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Option<AnimationFinishedCallback>)>,
     ) -> Result<(), ()> {
         if animation.is_some() {
             Err(())
@@ -178,10 +183,14 @@ fn set(
         &self,
         item: Pin<&Item>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Option<AnimationFinishedCallback>)>,
     ) -> Result<(), ()> {
-        if let Some(animation) = animation {
-            self.apply_pin(item).set_animated_value(value.try_into().map_err(|_| ())?, animation);
+        if let Some((animation, finished)) = animation {
+            self.apply_pin(item).set_animated_value_with_finished_callback(
+                value.try_into().map_err(|_| ())?,
+                animation,
+                finished,
+            );
             Ok(())
         } else {
             self.0.set(item, value, None)
@@ -211,8 +220,8 @@ fn set_binding_impl<T: Clone + 'static, Value: 'static>(
                     });
                     Ok(())
                 }
-                AnimatedBindingKind::Animation(animation) => {
-                    p.set_animated_binding(
+                AnimatedBindingKind::Animation(animation, finished) => {
+                    p.set_animated_binding_with_finished_callback(
                         move || {
                             binding()
                                 .try_into()
@@ -220,11 +229,12 @@ fn set_binding_impl<T: Clone + 'static, Value: 'static>(
                                 .expect("binding was of the wrong type")
                         },
                         animation,
+                        finished,
                     );
                     Ok(())
                 }
-                AnimatedBindingKind::Transition(tr) => {
-                    p.set_animated_binding_for_transition(
+                AnimatedBindingKind::Transition(tr, finished) => {
+                    p.set_animated_binding_for_transition_with_finished_callback(
                         move || {
                             binding()
                                 .try_into()
@@ -232,6 +242,7 @@ fn set_binding_impl<T: Clone + 'static, Value: 'static>(
                                 .expect("binding was of the wrong type")
                         },
                         tr,
+                        finished,
                     );
                     Ok(())
                 }
@@ -0,0 +1,206 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Spell checking of the text of the `TextInput` elements that have `spell-check: true`.
+
+The application provides the [`SpellChecker`], which is asked about each word of the text.
+The renderer underlines the misspelled words with a wavy line, and a right click on one of
+them asks the spell checker to show its suggestions.
+*/
+
+use crate::graphics::Point;
+use crate::{Property, SharedString};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::ops::Range;
+use core::pin::Pin;
+
+#[cfg(all(not(feature = "std"), feature = "unsafe_single_core"))]
+use crate::unsafe_single_core::thread_local;
+
+/// A misspelled word of the text of a `TextInput`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Misspelling {
+    /// The range of the word in the text, in bytes.
+    pub range: Range<usize>,
+    /// The corrections that the spell checker proposes, the most likely first.
+    pub suggestions: Vec<SharedString>,
+}
+
+/// The request to show the suggestions for a misspelled word that the user right-clicked on,
+/// which [`SpellChecker::show_suggestions()`] receives.
+pub struct SuggestionRequest {
+    /// The misspelled word.
+    pub misspelling: Misspelling,
+    /// The position of the click, in logical coordinates relative to the window.
+    pub position: Point,
+    replace: Box<dyn FnOnce(&str)>,
+}
+
+impl SuggestionRequest {
+    /// Replaces the misspelled word with the text, such as the suggestion that the user chose.
+    /// Does nothing if the text of the `TextInput` changed since the request.
+    pub fn replace(self, text: &str) {
+        (self.replace)(text)
+    }
+}
+
+/// Checks the spelling of the words of the `TextInput` elements with `spell-check: true`.
+///
+/// The misspelled words of a text are remembered until the spell checker is set again with
+/// [`set_spell_checker()`], which is how a spell checker whose dictionary changed has the texts
+/// checked again.
+pub trait SpellChecker {
+    /// Returns None if the word is spelled correctly, or the corrections to propose for it,
+    /// which might be empty.
+    fn check_word(&self, word: &str) -> Option<Vec<SharedString>>;
+
+    /// Shows the suggestions for a misspelled word that the user right-clicked on, for example
+    /// in a popup menu, and calls [`SuggestionRequest::replace()`] with the chosen one.
+    ///
+    /// The default implementation does nothing.
+    fn show_suggestions(&self, _request: SuggestionRequest) {}
+}
+
+/// The number of texts whose misspelled words are remembered
+const CACHE_SIZE: usize = 16;
+
+thread_local!(
+    /// The spell checker set by the application
+    static SPELL_CHECKER: RefCell<Option<Rc<dyn SpellChecker>>> = RefCell::new(None)
+);
+thread_local!(
+    /// Incremented each time the spell checker is set, so that the bindings and the renderings
+    /// that called `misspellings()` are evaluated again
+    static SPELL_CHECKER_GENERATION: Pin<Box<Property<u32>>> = Box::pin(Default::default())
+);
+thread_local!(
+    /// The misspelled words of the texts that were checked the most recently, the most recent last
+    static CACHE: RefCell<Vec<(SharedString, Vec<Misspelling>)>> = RefCell::new(Vec::new())
+);
+
+/// Sets the spell checker of the `TextInput` elements, or disables spell checking with `None`.
+pub fn set_spell_checker(checker: Option<Rc<dyn SpellChecker>>) {
+    SPELL_CHECKER.with(|c| *c.borrow_mut() = checker);
+    CACHE.with(|cache| cache.borrow_mut().clear());
+    SPELL_CHECKER_GENERATION.with(|generation| {
+        let generation = generation.as_ref();
+        generation.set(generation.get_untracked().wrapping_add(1))
+    });
+}
+
+fn spell_checker() -> Option<Rc<dyn SpellChecker>> {
+    SPELL_CHECKER.with(|c| c.borrow().clone())
+}
+
+/// Returns the byte ranges of the words of the text. The words are made of letters, and of the
+/// apostrophes between them. The ones that contain digits are not words.
+pub fn words(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = None;
+    text.char_indices().chain(core::iter::once((text.len(), ' '))).filter_map(move |(i, c)| {
+        if c.is_alphanumeric() || c == '\'' {
+            start.get_or_insert(i);
+            return None;
+        }
+        let word = start.take()?..i;
+        let word_text = &text[word.clone()];
+        let trimmed = word_text.trim_matches('\'');
+        if trimmed.is_empty() || trimmed.chars().any(|c| c.is_numeric()) {
+            return None;
+        }
+        let leading = word_text.len() - word_text.trim_start_matches('\'').len();
+        Some((word.start + leading)..(word.start + leading + trimmed.len()))
+    })
+}
+
+/// Returns the misspelled words of the text, which is empty when there is no spell checker.
+/// The words of a text are only checked again after the spell checker was set again, which also
+/// evaluates again the binding or the rendering that calls this function.
+pub fn misspellings(text: &SharedString) -> Vec<Misspelling> {
+    SPELL_CHECKER_GENERATION.with(|generation| generation.as_ref().get());
+    let checker = match spell_checker() {
+        Some(checker) => checker,
+        None => return Vec::new(),
+    };
+    let cached = CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let index = cache.iter().position(|(checked, _)| checked == text)?;
+        let entry = cache.remove(index);
+        let misspellings = entry.1.clone();
+        cache.push(entry);
+        Some(misspellings)
+    });
+    if let Some(misspellings) = cached {
+        return misspellings;
+    }
+    let misspellings: Vec<_> = words(text)
+        .filter_map(|range| {
+            let suggestions = checker.check_word(&text[range.clone()])?;
+            Some(Misspelling { range, suggestions })
+        })
+        .collect();
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() == CACHE_SIZE {
+            cache.remove(0);
+        }
+        cache.push((text.clone(), misspellings.clone()));
+    });
+    misspellings
+}
+
+/// Asks the spell checker to show the suggestions for the misspelled word. `replace` replaces
+/// the word in the text.
+pub(crate) fn show_suggestions(
+    misspelling: Misspelling,
+    position: Point,
+    replace: Box<dyn FnOnce(&str)>,
+) {
+    if let Some(checker) = spell_checker() {
+        checker.show_suggestions(SuggestionRequest { misspelling, position, replace });
+    }
+}
+
+#[test]
+fn test_misspellings() {
+    let text = "Helo 'quoted' world, it's 42 x2 dont";
+    let words = words(text).map(|r| &text[r]).collect::<Vec<_>>();
+    assert_eq!(words, ["Helo", "quoted", "world", "it's", "dont"]);
+
+    struct Checker(core::cell::Cell<usize>);
+    impl SpellChecker for Checker {
+        fn check_word(&self, word: &str) -> Option<Vec<SharedString>> {
+            self.0.set(self.0.get() + 1);
+            match word {
+                "Helo" => Some(alloc::vec!["Hello".into(), "Help".into()]),
+                "dont" => Some(Vec::new()),
+                _ => None,
+            }
+        }
+    }
+    let text = SharedString::from(text);
+    let tracker = Box::pin(crate::properties::PropertyTracker::default());
+    assert_eq!(tracker.as_ref().evaluate(|| misspellings(&text)), []);
+    let checker = Rc::new(Checker(Default::default()));
+    set_spell_checker(Some(checker.clone()));
+    assert!(tracker.is_dirty());
+    let expected = [
+        Misspelling { range: 0..4, suggestions: alloc::vec!["Hello".into(), "Help".into()] },
+        Misspelling { range: 32..36, suggestions: Vec::new() },
+    ];
+    assert_eq!(misspellings(&text), expected);
+    assert_eq!(checker.0.get(), 5);
+    // The misspellings of the text are remembered, and the other texts are checked
+    assert_eq!(misspellings(&text), expected);
+    assert_eq!(checker.0.get(), 5);
+    assert_eq!(misspellings(&"dont".into()).len(), 1);
+    assert_eq!(checker.0.get(), 6);
+    // Setting the spell checker again checks the texts again
+    set_spell_checker(Some(checker.clone()));
+    assert_eq!(misspellings(&text), expected);
+    assert_eq!(checker.0.get(), 11);
+    set_spell_checker(None);
+}
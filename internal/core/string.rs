@@ -201,6 +201,37 @@ fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
     }
 }
 
+/// Returns the part of `s` from the character at index `start` up to, but excluding, the character
+/// at index `end`. The indexes count characters, not bytes, and are clamped to the length of the string.
+///
+/// This implements `slice()` on strings in .slint
+pub fn slice_string(s: &str, start: i32, end: i32) -> SharedString {
+    let byte_index = |i: i32| s.char_indices().nth(i.max(0) as usize).map_or(s.len(), |(b, _)| b);
+    let (start, end) = (byte_index(start), byte_index(end));
+    SharedString::from(if start < end { &s[start..end] } else { "" })
+}
+
+/// Splits `s` at each occurrence of `separator`. An empty separator does not split the string.
+///
+/// This implements `split()` on strings in .slint
+pub fn split_string(s: &str, separator: &str) -> alloc::vec::Vec<SharedString> {
+    if separator.is_empty() {
+        return alloc::vec![s.into()];
+    }
+    s.split(separator).map(SharedString::from).collect()
+}
+
+#[test]
+fn slice_and_split() {
+    assert_eq!(slice_string("hello", 1, 3), "el");
+    assert_eq!(slice_string("héllo", 1, 100), "éllo");
+    assert_eq!(slice_string("hello", -2, 2), "he");
+    assert_eq!(slice_string("hello", 3, 1), "");
+    assert_eq!(split_string("a,b,,c", ","), ["a", "b", "", "c"]);
+    assert_eq!(split_string("", ","), [""]);
+    assert_eq!(split_string("abc", ""), ["abc"]);
+}
+
 #[test]
 fn simple_test() {
     let x = SharedString::from("hello world!");
@@ -339,6 +370,57 @@ fn test_slint_shared_string_from_number() {
         let str = core::str::from_utf8(core::slice::from_raw_parts(bytes, len)).unwrap();
         self_.push_str(str);
     }
+    /// Write in `out` the string `ss` converted to upper case.
+    /// The resulting structure must be passed to slint_shared_string_drop
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_shared_string_to_uppercase(
+        out: *mut SharedString,
+        ss: &SharedString,
+    ) {
+        core::ptr::write(out, SharedString::from(ss.to_uppercase()));
+    }
+
+    /// Write in `out` the string `ss` converted to lower case.
+    /// The resulting structure must be passed to slint_shared_string_drop
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_shared_string_to_lowercase(
+        out: *mut SharedString,
+        ss: &SharedString,
+    ) {
+        core::ptr::write(out, SharedString::from(ss.to_lowercase()));
+    }
+
+    /// Write in `out` the string `ss` without its leading and trailing whitespace.
+    /// The resulting structure must be passed to slint_shared_string_drop
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_shared_string_trim(out: *mut SharedString, ss: &SharedString) {
+        core::ptr::write(out, SharedString::from(ss.trim()));
+    }
+
+    /// Write in `out` the string `ss` where all the occurrences of `from` are replaced by `to`.
+    /// The resulting structure must be passed to slint_shared_string_drop
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_shared_string_replace(
+        out: *mut SharedString,
+        ss: &SharedString,
+        from: &SharedString,
+        to: &SharedString,
+    ) {
+        core::ptr::write(out, SharedString::from(ss.replace(from.as_str(), to.as_str())));
+    }
+
+    /// Write in `out` the characters of `ss` from index `start` to `end`, see [`slice_string`].
+    /// The resulting structure must be passed to slint_shared_string_drop
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_shared_string_slice(
+        out: *mut SharedString,
+        ss: &SharedString,
+        start: i32,
+        end: i32,
+    ) {
+        core::ptr::write(out, slice_string(ss, start, end));
+    }
+
     #[test]
     fn test_slint_shared_string_append() {
         let mut s = SharedString::default();
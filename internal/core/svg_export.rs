@@ -0,0 +1,744 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+The renderer that exports the scene of a window as an SVG document.
+
+The items are written as SVG shapes, the texts as `<text>` elements laid out with the metrics of the
+backend, and the images are embedded as data URLs.
+*/
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::pin::Pin;
+
+use crate::graphics::{
+    Brush, Color, FontRequest, Image, ImageInner, IntRect, Rect, SharedImageBuffer,
+};
+use crate::item_rendering::{CachedRenderingData, ItemRenderer};
+use crate::items::{
+    self, ImageFit, ImageRendering, InputType, TextHorizontalAlignment, TextVerticalAlignment,
+    TextWrap,
+};
+use crate::window::WindowRc;
+
+/// Renders the component of the window into an SVG document with the size of the window item.
+pub fn render_window(window: &WindowRc) -> String {
+    let (width, height) = window.window_item_geometry().unwrap_or_default();
+    let mut renderer = SvgRenderer {
+        window: window.clone(),
+        output: String::new(),
+        next_id: 0,
+        state: State { open_groups: 0, clip: euclid::rect(0., 0., width, height) },
+        saved_states: Vec::new(),
+    };
+    write!(
+        renderer.output,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    )
+    .unwrap();
+    renderer.output.push('\n');
+    crate::item_rendering::render_component_items(
+        &window.component(),
+        &mut renderer,
+        Default::default(),
+    );
+    renderer.output.push_str("</svg>\n");
+    renderer.output
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    /// The number of `<g>` elements opened since the state was saved
+    open_groups: usize,
+    clip: Rect,
+}
+
+struct SvgRenderer {
+    window: WindowRc,
+    output: String,
+    /// The number of the next id of a definition, such as a gradient or a clip path
+    next_id: usize,
+    state: State,
+    saved_states: Vec<State>,
+}
+
+impl SvgRenderer {
+    fn new_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        alloc::format!("{}{}", prefix, self.next_id)
+    }
+
+    fn open_group(&mut self, attributes: &str) {
+        writeln!(self.output, "<g {}>", attributes).unwrap();
+        self.state.open_groups += 1;
+    }
+
+    /// Returns the attribute that paints with the brush, such as `fill="#ff0000"`, after writing
+    /// the definition of the gradient if it is one.
+    fn paint(&mut self, attribute: &str, brush: &Brush) -> String {
+        if brush.is_transparent() {
+            return alloc::format!(r#"{}="none""#, attribute);
+        }
+        match brush {
+            Brush::SolidColor(color) => color_attributes(attribute, *color),
+            Brush::LinearGradient(gradient) => {
+                let id = self.new_id("gradient");
+                let (start, end) = crate::graphics::line_for_angle(gradient.angle());
+                write!(
+                    self.output,
+                    r#"<defs><linearGradient id="{}" x1="{}" y1="{}" x2="{}" y2="{}">"#,
+                    id, start.x, start.y, end.x, end.y
+                )
+                .unwrap();
+                for stop in gradient.stops() {
+                    write!(
+                        self.output,
+                        r#"<stop offset="{}" {}/>"#,
+                        stop.position,
+                        color_attributes("stop-color", stop.color)
+                    )
+                    .unwrap();
+                }
+                self.output.push_str("</linearGradient></defs>\n");
+                alloc::format!(r#"{}="url(#{})""#, attribute, id)
+            }
+            _ => alloc::format!(r#"{}="none""#, attribute),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text_impl(
+        &mut self,
+        text: &str,
+        font_request: FontRequest,
+        color: Brush,
+        width: f32,
+        height: f32,
+        horizontal_alignment: TextHorizontalAlignment,
+        vertical_alignment: TextVerticalAlignment,
+        wrap: TextWrap,
+    ) {
+        if text.is_empty() {
+            return;
+        }
+        let font_request = font_request.merge(&self.window.default_font_properties());
+        let text_width = |text: &str| self.window.text_size(font_request.clone(), text, None).width;
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            if wrap != TextWrap::word_wrap {
+                lines.push(String::from(paragraph));
+                continue;
+            }
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                if !line.is_empty() && text_width(&alloc::format!("{} {}", line, word)) > width {
+                    lines.push(core::mem::take(&mut line));
+                } else if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
+            lines.push(line);
+        }
+
+        let line_height = self.window.text_size(font_request.clone(), "X", None).height;
+        let text_height = line_height * lines.len() as f32;
+        let top = match vertical_alignment {
+            TextVerticalAlignment::top => 0.,
+            TextVerticalAlignment::center => (height - text_height) / 2.,
+            TextVerticalAlignment::bottom => height - text_height,
+        };
+        let (x, anchor) = match horizontal_alignment {
+            TextHorizontalAlignment::left => (0., "start"),
+            TextHorizontalAlignment::center => (width / 2., "middle"),
+            TextHorizontalAlignment::right => (width, "end"),
+        };
+
+        let fill = self.paint("fill", &color);
+        write!(
+            self.output,
+            r#"<text {} text-anchor="{}" dominant-baseline="text-before-edge" xml:space="preserve""#,
+            fill, anchor
+        ).unwrap();
+        if let Some(family) = &font_request.family {
+            write!(self.output, r#" font-family="{}""#, escape(family)).unwrap();
+        }
+        if let Some(pixel_size) = font_request.pixel_size {
+            write!(self.output, r#" font-size="{}""#, pixel_size).unwrap();
+        }
+        if let Some(weight) = font_request.weight {
+            write!(self.output, r#" font-weight="{}""#, weight).unwrap();
+        }
+        if let Some(letter_spacing) = font_request.letter_spacing.filter(|s| *s != 0.) {
+            write!(self.output, r#" letter-spacing="{}""#, letter_spacing).unwrap();
+        }
+        self.output.push('>');
+        for (index, line) in lines.iter().enumerate() {
+            write!(
+                self.output,
+                r#"<tspan x="{}" y="{}">{}</tspan>"#,
+                x,
+                top + line_height * index as f32,
+                escape(line)
+            )
+            .unwrap();
+        }
+        self.output.push_str("</text>\n");
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_image_impl(
+        &mut self,
+        source: &Image,
+        width: f32,
+        height: f32,
+        image_fit: ImageFit,
+        image_rendering: ImageRendering,
+        source_clip: Option<IntRect>,
+        colorize: Brush,
+    ) {
+        let href = match image_data_url(source) {
+            Some(href) => href,
+            None => return,
+        };
+        let size = source.size();
+        let view_box =
+            source_clip.filter(|clip| clip.width() > 0 && clip.height() > 0).unwrap_or_else(|| {
+                IntRect::new(
+                    Default::default(),
+                    euclid::size2(size.width as i32, size.height as i32),
+                )
+            });
+        let preserve_aspect_ratio = match image_fit {
+            ImageFit::fill => "none",
+            ImageFit::contain => "xMidYMid meet",
+            ImageFit::cover => "xMidYMid slice",
+        };
+        let rendering = match image_rendering {
+            ImageRendering::smooth => "",
+            ImageRendering::pixelated => r#" style="image-rendering:pixelated""#,
+        };
+        // The nested svg element clips the image to the source clip and fits it in the item
+        let image = alloc::format!(
+            r#"<svg width="{}" height="{}" viewBox="{} {} {} {}" preserveAspectRatio="{}"><image width="{}" height="{}" href="{}"{}/></svg>"#,
+            width,
+            height,
+            view_box.origin.x,
+            view_box.origin.y,
+            view_box.width(),
+            view_box.height(),
+            preserve_aspect_ratio,
+            size.width,
+            size.height,
+            href,
+            rendering
+        );
+        if colorize.is_transparent() {
+            self.output.push_str(&image);
+            self.output.push('\n');
+        } else {
+            // The image is used as a mask for a rectangle filled with the colorize brush
+            let id = self.new_id("mask");
+            writeln!(
+                self.output,
+                r#"<defs><mask id="{}" style="mask-type:alpha">{}</mask></defs>"#,
+                id, image
+            )
+            .unwrap();
+            let fill = self.paint("fill", &colorize);
+            writeln!(
+                self.output,
+                r#"<rect width="{}" height="{}" {} mask="url(#{})"/>"#,
+                width, height, fill, id
+            )
+            .unwrap();
+        }
+    }
+}
+
+impl ItemRenderer for SvgRenderer {
+    fn draw_rectangle(&mut self, rect: Pin<&items::Rectangle>) {
+        if rect.background().is_transparent() {
+            return;
+        }
+        let fill = self.paint("fill", &rect.background());
+        writeln!(
+            self.output,
+            r#"<rect width="{}" height="{}" {}/>"#,
+            rect.width(),
+            rect.height(),
+            fill
+        )
+        .unwrap();
+    }
+
+    fn draw_border_rectangle(&mut self, rect: Pin<&items::BorderRectangle>) {
+        // The border is entirely inside of the geometry, like in CSS
+        let border_width = rect.border_width().min(rect.width() / 2.).max(0.);
+        let fill = self.paint("fill", &rect.background());
+        let stroke = if border_width > 0. {
+            self.paint("stroke", &rect.border_color())
+        } else {
+            String::from(r#"stroke="none""#)
+        };
+        writeln!(
+            self.output,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" {} {} stroke-width="{}"/>"#,
+            border_width / 2.,
+            border_width / 2.,
+            (rect.width() - border_width).max(0.),
+            (rect.height() - border_width).max(0.),
+            rect.border_radius(),
+            fill,
+            stroke,
+            border_width
+        )
+        .unwrap();
+    }
+
+    fn draw_image(&mut self, image: Pin<&items::ImageItem>) {
+        self.draw_image_impl(
+            &image.source(),
+            image.width(),
+            image.height(),
+            image.image_fit(),
+            image.image_rendering(),
+            None,
+            Brush::default(),
+        );
+    }
+
+    fn draw_clipped_image(&mut self, image: Pin<&items::ClippedImage>) {
+        let source_clip = IntRect::new(
+            [image.source_clip_x() as _, image.source_clip_y() as _].into(),
+            [image.source_clip_width() as _, image.source_clip_height() as _].into(),
+        );
+        self.draw_image_impl(
+            &image.source(),
+            image.width(),
+            image.height(),
+            image.image_fit(),
+            image.image_rendering(),
+            Some(source_clip),
+            image.colorize(),
+        );
+    }
+
+    fn draw_text(&mut self, text: Pin<&items::Text>) {
+        self.draw_text_impl(
+            &text.text(),
+            text.unresolved_font_request(),
+            text.color(),
+            text.width(),
+            text.height(),
+            text.horizontal_alignment(),
+            text.vertical_alignment(),
+            text.wrap(),
+        );
+    }
+
+    fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>) {
+        let text = text_input.text();
+        let text = match text_input.input_type() {
+            InputType::password => {
+                core::iter::repeat('●').take(text.chars().count()).collect::<String>()
+            }
+            _ => String::from(text.as_str()),
+        };
+        self.draw_text_impl(
+            &text,
+            text_input.unresolved_font_request(),
+            text_input.color(),
+            text_input.width(),
+            text_input.height(),
+            text_input.horizontal_alignment(),
+            text_input.vertical_alignment(),
+            text_input.wrap(),
+        );
+    }
+
+    fn draw_path(&mut self, path: Pin<&items::Path>) {
+        use lyon_path::Event;
+        let (offset, events) = path.fitted_path_events();
+        let mut data = String::new();
+        for event in events.iter() {
+            match event {
+                Event::Begin { at } => write!(data, "M{} {} ", at.x, at.y).unwrap(),
+                Event::Line { to, .. } => write!(data, "L{} {} ", to.x, to.y).unwrap(),
+                Event::Quadratic { ctrl, to, .. } => {
+                    write!(data, "Q{} {} {} {} ", ctrl.x, ctrl.y, to.x, to.y).unwrap()
+                }
+                Event::Cubic { ctrl1, ctrl2, to, .. } => write!(
+                    data,
+                    "C{} {} {} {} {} {} ",
+                    ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y
+                )
+                .unwrap(),
+                Event::End { close: true, .. } => data.push_str("Z "),
+                Event::End { close: false, .. } => (),
+            }
+        }
+        let fill_rule = match path.fill_rule() {
+            items::FillRule::nonzero => "nonzero",
+            items::FillRule::evenodd => "evenodd",
+        };
+        let fill = self.paint("fill", &path.fill());
+        let stroke = self.paint("stroke", &path.stroke());
+        writeln!(
+            self.output,
+            r#"<path transform="translate({} {})" d="{}" fill-rule="{}" {} {} stroke-width="{}"/>"#,
+            offset.x,
+            offset.y,
+            data.trim_end(),
+            fill_rule,
+            fill,
+            stroke,
+            path.stroke_width()
+        )
+        .unwrap();
+    }
+
+    fn draw_box_shadow(&mut self, box_shadow: Pin<&items::BoxShadow>) {
+        if box_shadow.color().alpha() == 0 {
+            return;
+        }
+        let (x, y) = (box_shadow.offset_x(), box_shadow.offset_y());
+        let (width, height) = (box_shadow.width(), box_shadow.height());
+        let blur = box_shadow.blur();
+        let filter = if blur > 0. {
+            let id = self.new_id("shadow");
+            writeln!(
+                self.output,
+                r#"<defs><filter id="{}" filterUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}"><feGaussianBlur stdDeviation="{}"/></filter></defs>"#,
+                id,
+                x - 2. * blur,
+                y - 2. * blur,
+                width + 4. * blur,
+                height + 4. * blur,
+                blur / 2.
+            ).unwrap();
+            alloc::format!(r#" filter="url(#{})""#, id)
+        } else {
+            String::new()
+        };
+        writeln!(
+            self.output,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" {}{}/>"#,
+            x,
+            y,
+            width,
+            height,
+            box_shadow.border_radius(),
+            color_attributes("fill", box_shadow.color()),
+            filter
+        )
+        .unwrap();
+    }
+
+    fn combine_clip(&mut self, rect: Rect, radius: f32, border_width: f32) {
+        self.state.clip = self.state.clip.intersection(&rect).unwrap_or_default();
+        // Clip to the inside of the border
+        let rect = rect.inflate(-border_width, -border_width);
+        let id = self.new_id("clip");
+        writeln!(
+            self.output,
+            r#"<defs><clipPath id="{}"><rect x="{}" y="{}" width="{}" height="{}" rx="{}"/></clipPath></defs>"#,
+            id,
+            rect.origin.x,
+            rect.origin.y,
+            rect.width().max(0.),
+            rect.height().max(0.),
+            (radius - border_width).max(0.)
+        ).unwrap();
+        self.open_group(&alloc::format!(r#"clip-path="url(#{})""#, id));
+    }
+
+    fn get_current_clip(&self) -> Rect {
+        self.state.clip
+    }
+
+    fn translate(&mut self, x: f32, y: f32) {
+        if x == 0. && y == 0. {
+            return;
+        }
+        self.state.clip = self.state.clip.translate((-x, -y).into());
+        self.open_group(&alloc::format!(r#"transform="translate({} {})""#, x, y));
+    }
+
+    fn rotate(&mut self, angle_in_degrees: f32) {
+        let rotation = euclid::default::Transform2D::<f32>::rotation(euclid::Angle::degrees(
+            -angle_in_degrees,
+        ));
+        self.state.clip = rotation.outer_transformed_rect(&self.state.clip);
+        self.open_group(&alloc::format!(r#"transform="rotate({})""#, angle_in_degrees));
+    }
+
+    fn apply_opacity(&mut self, opacity: f32) {
+        if opacity < 1. {
+            self.open_group(&alloc::format!(r#"opacity="{}""#, opacity));
+        }
+    }
+
+    fn save_state(&mut self) {
+        self.saved_states.push(self.state);
+        self.state.open_groups = 0;
+    }
+
+    fn restore_state(&mut self) {
+        for _ in 0..self.state.open_groups {
+            self.output.push_str("</g>\n");
+        }
+        if let Some(state) = self.saved_states.pop() {
+            self.state = state;
+        }
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.window.scale_factor()
+    }
+
+    fn draw_cached_pixmap(
+        &mut self,
+        _item_cache: &CachedRenderingData,
+        update_fn: &dyn Fn(&mut dyn FnMut(u32, u32, &[u8])),
+    ) {
+        let scale_factor = self.scale_factor();
+        let output = &mut self.output;
+        update_fn(&mut |width, height, data| {
+            // The pixels are premultiplied
+            let rgba = data
+                .chunks(4)
+                .flat_map(|p| {
+                    let unpremultiply = |c: u8| match p[3] {
+                        0 => 0,
+                        a => ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8,
+                    };
+                    [unpremultiply(p[0]), unpremultiply(p[1]), unpremultiply(p[2]), p[3]]
+                })
+                .collect::<Vec<u8>>();
+            writeln!(
+                output,
+                r#"<image width="{}" height="{}" href="data:image/png;base64,{}"/>"#,
+                width as f32 / scale_factor,
+                height as f32 / scale_factor,
+                base64(&encode_png(width, height, &rgba))
+            )
+            .unwrap();
+        });
+    }
+
+    fn draw_string(&mut self, string: &str, color: Color) {
+        self.draw_text_impl(
+            string,
+            Default::default(),
+            Brush::SolidColor(color),
+            0.,
+            0.,
+            TextHorizontalAlignment::left,
+            TextVerticalAlignment::top,
+            TextWrap::no_wrap,
+        );
+    }
+
+    fn window(&self) -> WindowRc {
+        self.window.clone()
+    }
+
+    fn as_any(&mut self) -> &mut dyn core::any::Any {
+        self
+    }
+}
+
+/// Returns the attributes that paint with an opaque color and its opacity
+fn color_attributes(attribute: &str, color: Color) -> String {
+    let mut result = alloc::format!(
+        r##"{}="#{:02x}{:02x}{:02x}""##,
+        attribute,
+        color.red(),
+        color.green(),
+        color.blue()
+    );
+    if color.alpha() != 255 {
+        write!(
+            result,
+            r#" {}-opacity="{}""#,
+            attribute.trim_end_matches("-color"),
+            color.alpha() as f32 / 255.
+        )
+        .unwrap();
+    }
+    result
+}
+
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Returns the data URL with the content of the image file, or the pixels of the image as a PNG
+fn image_data_url(image: &Image) -> Option<String> {
+    let mime_type = |extension: &str| {
+        Some(match extension.to_ascii_lowercase().as_str() {
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "bmp" => "image/bmp",
+            "webp" => "image/webp",
+            "svg" => "image/svg+xml",
+            _ => return None,
+        })
+    };
+    let (mime_type, data) = match <&ImageInner>::from(image) {
+        ImageInner::AbsoluteFilePath(path) => {
+            let extension = std::path::Path::new(path.as_str()).extension()?.to_str()?;
+            (mime_type(extension)?, std::fs::read(path.as_str()).ok()?)
+        }
+        ImageInner::EmbeddedData { data, format } => {
+            (mime_type(core::str::from_utf8(format.as_slice()).ok()?)?, data.as_slice().to_vec())
+        }
+        ImageInner::EmbeddedImage(buffer) => {
+            let rgba = match buffer {
+                SharedImageBuffer::RGB8(buffer) => {
+                    buffer.as_slice().iter().flat_map(|p| [p.r, p.g, p.b, 255]).collect::<Vec<u8>>()
+                }
+                SharedImageBuffer::RGBA8(buffer) => buffer.as_bytes().to_vec(),
+                SharedImageBuffer::RGBA8Premultiplied(buffer) => buffer
+                    .as_slice()
+                    .iter()
+                    .flat_map(|p| {
+                        let unpremultiply = |c: u8| match p.a {
+                            0 => 0,
+                            a => ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8,
+                        };
+                        [unpremultiply(p.r), unpremultiply(p.g), unpremultiply(p.b), p.a]
+                    })
+                    .collect::<Vec<u8>>(),
+            };
+            ("image/png", encode_png(buffer.width(), buffer.height(), &rgba))
+        }
+        ImageInner::None | ImageInner::StaticTextures(_) => return None,
+    };
+    Some(alloc::format!("data:{};base64,{}", mime_type, base64(&data)))
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let bytes =
+            [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Encodes RGBA pixels in a PNG image. The image data is stored in uncompressed deflate blocks,
+/// which keeps the encoder small at the cost of the size of the images.
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    let mut scanlines = Vec::with_capacity((width as usize * 4 + 1) * height as usize);
+    for row in rgba.chunks(width as usize * 4).take(height as usize) {
+        // Filter type None
+        scanlines.push(0);
+        scanlines.extend_from_slice(row);
+    }
+
+    // zlib stream with the stored deflate blocks
+    let mut image_data = alloc::vec![0x78, 0x01];
+    let mut blocks = scanlines.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        image_data.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_last = blocks.peek().is_none();
+        let length = block.len() as u16;
+        image_data.push(is_last as u8);
+        image_data.extend_from_slice(&length.to_le_bytes());
+        image_data.extend_from_slice(&(!length).to_le_bytes());
+        image_data.extend_from_slice(block);
+    }
+    image_data.extend_from_slice(&adler32(&scanlines).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGBA, default compression, filter, and no interlacing
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_png_chunk(&mut png, b"IHDR", &header);
+    write_png_chunk(&mut png, b"IDAT", &image_data);
+    write_png_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+    png.extend_from_slice(&crc32(chunk_type.iter().chain(data)).to_be_bytes());
+}
+
+fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
+
+#[test]
+fn test_png_checksums() {
+    assert_eq!(crc32(b"IEND".iter()), 0xae42_6082);
+    assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+}
+
+#[test]
+fn test_encode_png() {
+    let png = encode_png(1, 1, &[255, 0, 0, 255]);
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+    // The scanline with its filter byte is in one final stored block
+    assert!(png.windows(10).any(|w| w == [1, 5, 0, 0xfa, 0xff, 0, 255, 0, 0, 255]));
+}
+
+#[test]
+fn test_color_attributes() {
+    assert_eq!(color_attributes("fill", Color::from_rgb_u8(255, 0, 16)), r##"fill="#ff0010""##);
+    assert_eq!(
+        color_attributes("stop-color", Color::from_argb_u8(51, 0, 0, 0)),
+        r##"stop-color="#000000" stop-opacity="0.2""##
+    );
+    assert_eq!(escape(r#"<a & "b">"#), "&lt;a &amp; &quot;b&quot;&gt;");
+}
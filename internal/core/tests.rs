@@ -23,35 +23,47 @@ pub extern "C" fn slint_mock_elapsed_time(time_in_ms: u64) {
 }
 
 /// Simulate a click on a position within the component.
+///
+/// The events go through the window that shows the component, so that a click on a popup
+/// reaches it.
 #[no_mangle]
 pub extern "C" fn slint_send_mouse_click(
-    component: &crate::component::ComponentRc,
+    _component: &crate::component::ComponentRc,
     x: f32,
     y: f32,
     window: &WindowRc,
 ) {
-    let mut state = crate::input::MouseInputState::default();
+    slint_send_mouse_press(x, y, window);
+    slint_mock_elapsed_time(50);
+    slint_send_mouse_release(x, y, window);
+}
+
+/// Simulate pressing the left mouse button on a position within the window. Unlike
+/// [`slint_send_mouse_click`], the button stays pressed until [`slint_send_mouse_release`], and
+/// [`slint_send_mouse_move`] moves the mouse in between, for example to drag an item.
+#[no_mangle]
+pub extern "C" fn slint_send_mouse_press(x: f32, y: f32, window: &WindowRc) {
     let pos = euclid::point2(x, y);
+    window.clone().process_mouse_input(MouseEvent::MouseMoved { pos });
+    window.clone().process_mouse_input(MouseEvent::MousePressed {
+        pos,
+        button: crate::items::PointerEventButton::left,
+    });
+}
 
-    state = crate::input::process_mouse_input(
-        component.clone(),
-        MouseEvent::MouseMoved { pos },
-        window,
-        state,
-    );
-    state = crate::input::process_mouse_input(
-        component.clone(),
-        MouseEvent::MousePressed { pos, button: crate::items::PointerEventButton::left },
-        window,
-        state,
-    );
-    slint_mock_elapsed_time(50);
-    crate::input::process_mouse_input(
-        component.clone(),
-        MouseEvent::MouseReleased { pos, button: crate::items::PointerEventButton::left },
-        window,
-        state,
-    );
+/// Simulate moving the mouse to a position within the window.
+#[no_mangle]
+pub extern "C" fn slint_send_mouse_move(x: f32, y: f32, window: &WindowRc) {
+    window.clone().process_mouse_input(MouseEvent::MouseMoved { pos: euclid::point2(x, y) });
+}
+
+/// Simulate releasing the left mouse button on a position within the window.
+#[no_mangle]
+pub extern "C" fn slint_send_mouse_release(x: f32, y: f32, window: &WindowRc) {
+    window.clone().process_mouse_input(MouseEvent::MouseReleased {
+        pos: euclid::point2(x, y),
+        button: crate::items::PointerEventButton::left,
+    });
 }
 
 /// Simulate a character input event.
@@ -69,15 +81,20 @@ pub extern "C" fn send_keyboard_string_sequence(
         let mut buffer = [0; 6];
         let text = SharedString::from(ch.encode_utf8(&mut buffer) as &str);
 
+        let key = crate::input::LogicalKey::from_text(&text);
         window.clone().process_key_input(&KeyEvent {
             event_type: KeyEventType::KeyPressed,
             text: text.clone(),
             modifiers,
+            key,
+            ..Default::default()
         });
         window.clone().process_key_input(&KeyEvent {
             event_type: KeyEventType::KeyReleased,
             text,
             modifiers,
+            key,
+            ..Default::default()
         });
     }
 }
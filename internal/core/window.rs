@@ -6,11 +6,13 @@
 
 use crate::component::{ComponentRc, ComponentWeak};
 use crate::graphics::{Point, Size};
-use crate::input::{KeyEvent, MouseEvent, MouseInputState, TextCursorBlinker};
+use crate::input::{key_codes, KeyEvent, MouseEvent, MouseInputState, TextCursorBlinker};
+use crate::item_tree::ItemVisitorResult;
 use crate::items::{ItemRc, ItemRef, ItemWeak, MouseCursor};
 use crate::properties::{Property, PropertyTracker};
 use alloc::boxed::Box;
 use alloc::rc::{Rc, Weak};
+use alloc::vec::Vec;
 use core::cell::{Cell, RefCell};
 use core::pin::Pin;
 
@@ -39,6 +41,40 @@ fn set_rendering_notifier(
         Err(crate::api::SetRenderingNotifierError::Unsupported)
     }
 
+    /// Render the content of the window and return its pixels, or None if the backend can't read
+    /// back what it renders. The window must be shown.
+    fn take_snapshot(
+        self: Rc<Self>,
+    ) -> Option<crate::graphics::SharedPixelBuffer<crate::graphics::Rgba8Pixel>> {
+        None
+    }
+
+    /// Render the item and its children on a transparent background, in an image of the size of
+    /// the item, and return its pixels. Returns None if the backend can't read back what it
+    /// renders. The window must be shown.
+    fn render_item_to_image(
+        self: Rc<Self>,
+        _item: &ItemRc,
+    ) -> Option<crate::graphics::SharedPixelBuffer<crate::graphics::Rgba8Pixel>> {
+        None
+    }
+
+    /// Render the components of the `pages` windows, one per page, with vector graphics into a
+    /// PDF file or to a printer. The windows of the pages might not be shown.
+    fn print(
+        &self,
+        _pages: &[WindowRc],
+        _target: &crate::api::PrintTarget,
+    ) -> Result<(), crate::api::PrintError> {
+        Err(crate::api::PrintError::Unsupported)
+    }
+
+    /// Returns the statistics about the graphics resources of the renderer of this window. The
+    /// `item_count` is filled in by the caller.
+    fn renderer_statistics(&self) -> crate::api::RendererStatistics {
+        Default::default()
+    }
+
     /// Show a popup at the given position
     fn show_popup(&self, popup: &ComponentRc, position: Point);
 
@@ -85,6 +121,26 @@ fn text_input_position_for_byte_offset(
         byte_offset: usize,
     ) -> Point;
 
+    /// Same as [`Self::text_input_byte_offset_for_position`], for a `Text` element. Returns None
+    /// if the backend doesn't support it.
+    fn text_byte_offset_for_position(
+        &self,
+        _text: Pin<&crate::items::Text>,
+        _pos: Point,
+    ) -> Option<usize> {
+        None
+    }
+
+    /// Same as [`Self::text_input_position_for_byte_offset`], for a `Text` element. Returns None
+    /// if the backend doesn't support it.
+    fn text_position_for_byte_offset(
+        &self,
+        _text: Pin<&crate::items::Text>,
+        _byte_offset: usize,
+    ) -> Option<Point> {
+        None
+    }
+
     /// Return self as any so the backend can upcast
     fn as_any(&self) -> &dyn core::any::Any;
 }
@@ -150,8 +206,22 @@ pub struct Window {
     cursor_blinker: RefCell<pin_weak::rc::PinWeak<crate::input::TextCursorBlinker>>,
 
     scale_factor: Pin<Box<Property<f32>>>,
+    base_font_size: Pin<Box<Property<f32>>>,
     active: Pin<Box<Property<bool>>>,
     active_popup: RefCell<Option<PopupWindow>>,
+    window_type: Cell<crate::api::WindowType>,
+    dark_title_bar: Cell<bool>,
+    backdrop: Cell<crate::api::WindowBackdrop>,
+    layer_shell: RefCell<Option<crate::api::LayerShell>>,
+    input_transform: Cell<Option<crate::api::InputTransform>>,
+    rendering_quality: Cell<crate::api::RenderingQuality>,
+    visible: Cell<bool>,
+    /// The functions called with the new visibility when the window is shown or hidden
+    visibility_listeners: RefCell<Vec<Box<dyn Fn(bool)>>>,
+    /// The functions called when a `TextInput` gets or loses the keyboard focus
+    text_input_focus_listeners: RefCell<Vec<Box<dyn Fn(Option<crate::api::TextInputFocus>)>>>,
+    /// Whether the text input focus listeners were last told that a `TextInput` has the focus
+    text_input_focused: Cell<bool>,
 }
 
 impl Drop for Window {
@@ -178,8 +248,22 @@ pub fn new(
             focus_item: Default::default(),
             cursor_blinker: Default::default(),
             scale_factor: Box::pin(Property::new_named(1., "i_slint_core::Window::scale_factor")),
+            base_font_size: Box::pin(Property::new_named(
+                16.,
+                "i_slint_core::Window::base_font_size",
+            )),
             active: Box::pin(Property::new_named(false, "i_slint_core::Window::active")),
             active_popup: Default::default(),
+            window_type: Default::default(),
+            dark_title_bar: Default::default(),
+            backdrop: Default::default(),
+            layer_shell: Default::default(),
+            input_transform: Default::default(),
+            rendering_quality: Default::default(),
+            visible: Default::default(),
+            visibility_listeners: Default::default(),
+            text_input_focus_listeners: Default::default(),
+            text_input_focused: Default::default(),
         });
         let window_weak = Rc::downgrade(&window);
         window.platform_window.set(platform_window_fn(&window_weak)).ok().unwrap();
@@ -210,6 +294,7 @@ pub fn new(
     pub fn set_component(&self, component: &ComponentRc) {
         self.close_popup();
         self.focus_item.replace(Default::default());
+        self.notify_text_input_focus(None);
         self.mouse_input_state.replace(Default::default());
         self.component.replace(ComponentRc::downgrade(component));
         self.meta_properties_tracker.set_dirty(); // component changed, layout constraints for sure must be re-calculated
@@ -238,6 +323,11 @@ pub fn try_component(&self) -> Option<ComponentRc> {
     pub fn process_mouse_input(self: Rc<Self>, mut event: MouseEvent) {
         crate::animations::update_animations();
 
+        if let Some((transform, pos)) = self.input_transform.get().zip(event.pos()) {
+            let (x, y) = transform.transform_point(pos.x, pos.y);
+            event.translate(Point::new(x, y) - pos);
+        }
+
         let embedded_popup_component =
             self.active_popup.borrow().as_ref().and_then(|popup| match popup.location {
                 PopupWindowLocation::TopLevel(_) => None,
@@ -280,10 +370,16 @@ pub fn process_mouse_input(self: Rc<Self>, mut event: MouseEvent) {
             self.mouse_input_state.take(),
         ));
 
-        if embedded_popup_component.is_some() {
-            //FIXME: currently the ComboBox is the only thing that uses the popup, and it should close automatically
-            // on release.  But ideally, there would be API to close the popup rather than always closing it on release
-            if matches!(event, MouseEvent::MouseReleased { .. }) {
+        if let Some((popup_component, _)) = embedded_popup_component {
+            //FIXME: currently the popups close automatically on release. But ideally, they should
+            // be closed with the `close()` function instead of always closing on release.
+            // Don't close a popup that was just shown by the handler of this event.
+            let is_same_popup = self
+                .active_popup
+                .borrow()
+                .as_ref()
+                .map_or(false, |popup| vtable::VRc::ptr_eq(&popup.component, &popup_component));
+            if is_same_popup && matches!(event, MouseEvent::MouseReleased { .. }) {
                 self.close_popup();
             }
         }
@@ -304,6 +400,135 @@ pub fn process_key_input(self: Rc<Self>, event: &KeyEvent) {
             }
             item = focus_item.parent_item();
         }
+
+        // Nobody handled Tab, so it moves the focus along the focus chain
+        if event.event_type == crate::input::KeyEventType::KeyPressed {
+            match event.text.chars().next() {
+                Some(key_codes::Tab) if !event.modifiers.shift => self.focus_next_item(),
+                Some(key_codes::Tab) | Some(key_codes::Backtab) => self.focus_previous_item(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Moves the focus to the next item of the focus chain, wrapping around at the end.
+    pub fn focus_next_item(self: Rc<Self>) {
+        self.move_focus(1)
+    }
+
+    /// Moves the focus to the previous item of the focus chain, wrapping around at the start.
+    pub fn focus_previous_item(self: Rc<Self>) {
+        self.move_focus(-1)
+    }
+
+    fn move_focus(self: Rc<Self>, step: isize) {
+        let chain = self.tab_order();
+        if chain.is_empty() {
+            return;
+        }
+        let current = self.focus_item.borrow().clone();
+        let next = match chain.iter().position(|(item, _)| item.downgrade() == current) {
+            Some(pos) => (pos as isize + step).rem_euclid(chain.len() as isize) as usize,
+            None if step > 0 => 0,
+            None => chain.len() - 1,
+        };
+        self.set_focus_item(&chain[next].0);
+    }
+
+    /// Returns the tree of accessible elements of the window. While a popup is shown, its
+    /// elements are appended after the ones of the window.
+    pub fn accessibility_tree(&self) -> Vec<crate::accessibility::AccessibilityNode> {
+        let mut tree = match self.try_component() {
+            Some(component) => crate::accessibility::build_accessibility_tree(&component),
+            None => return Vec::new(),
+        };
+        if let Some(popup) = self.active_popup.borrow().as_ref() {
+            let offset = match popup.location {
+                PopupWindowLocation::ChildWindow(offset) => offset,
+                PopupWindowLocation::TopLevel(_) => Point::default(),
+            };
+            let mut popup_tree = crate::accessibility::build_accessibility_tree(&popup.component);
+            fn translate(nodes: &mut [crate::accessibility::AccessibilityNode], offset: Point) {
+                for node in nodes {
+                    node.geometry = node.geometry.translate(offset.to_vector());
+                    translate(&mut node.children, offset);
+                }
+            }
+            translate(&mut popup_tree, offset);
+            tree.extend(popup_tree);
+        }
+        tree
+    }
+
+    /// Activates an element of the accessibility tree, by invoking its `accessible-action-default`
+    /// callback. Does nothing if the element is gone.
+    pub fn activate_accessible_element(&self, element: &crate::accessibility::AccessibilityNode) {
+        element.activate();
+    }
+
+    /// Returns the items that can receive the focus with the Tab key, in the order in which
+    /// they are traversed, with their geometry in logical coordinates relative to the window.
+    /// Only the active popup is considered while it is shown.
+    ///
+    /// The FocusScopes with a negative `tab-index`, such as the ones that the widgets use
+    /// internally, are not part of it.
+    pub fn tab_order(&self) -> Vec<(ItemRc, crate::graphics::Rect)> {
+        let (component, offset) = match self.active_popup.borrow().as_ref() {
+            Some(popup) => (
+                popup.component.clone(),
+                match popup.location {
+                    PopupWindowLocation::ChildWindow(offset) => offset,
+                    PopupWindowLocation::TopLevel(_) => Point::default(),
+                },
+            ),
+            None => match self.try_component() {
+                Some(component) => (component, Point::default()),
+                None => return Vec::new(),
+            },
+        };
+
+        #[derive(Clone, Copy)]
+        struct State {
+            offset: Point,
+            hidden: bool,
+        }
+
+        let mut items = Vec::new();
+        crate::item_tree::visit_items(
+            &component,
+            crate::item_tree::TraversalOrder::BackToFront,
+            |component, item, index, state: &State| {
+                let geometry = item.as_ref().geometry().translate(state.offset.to_vector());
+                let mut state = State { offset: geometry.origin, ..*state };
+                if let Some(clip) = ItemRef::downcast_pin::<crate::items::Clip>(item) {
+                    // The elements with `visible: false` are placed in an empty clipping item
+                    if clip.clip() && (clip.width() <= 0. || clip.height() <= 0.) {
+                        state.hidden = true;
+                    }
+                }
+                if state.hidden {
+                    return ItemVisitorResult::Continue(state);
+                }
+                let tab_index = if let Some(scope) =
+                    ItemRef::downcast_pin::<crate::items::FocusScope>(item)
+                {
+                    Some(scope.tab_index())
+                } else if let Some(input) = ItemRef::downcast_pin::<crate::items::TextInput>(item) {
+                    input.enabled().then(|| input.tab_index())
+                } else {
+                    None
+                };
+                if let Some(tab_index) = tab_index.filter(|i| *i >= 0) {
+                    items.push((tab_index, ItemRc::new(component.clone(), index), geometry));
+                }
+                ItemVisitorResult::Continue(state)
+            },
+            State { offset, hidden: false },
+        );
+
+        // Items with a positive tab-index come first, the others keep the item tree order
+        items.sort_by_key(|(tab_index, ..)| if *tab_index > 0 { *tab_index } else { i32::MAX });
+        items.into_iter().map(|(_, item, geometry)| (item, geometry)).collect()
     }
 
     /// Installs a binding on the specified property that's toggled whenever the text cursor is supposed to be visible or not.
@@ -333,6 +558,53 @@ pub fn set_focus_item(self: Rc<Self>, focus_item: &ItemRc) {
         *self.as_ref().focus_item.borrow_mut() = focus_item.downgrade();
 
         focus_item.borrow().as_ref().focus_event(&crate::input::FocusEvent::FocusIn, &self);
+
+        self.notify_text_input_focus(text_input_focus(focus_item));
+    }
+
+    /// Enters the text in the `TextInput` that has the keyboard focus, if any, without going
+    /// through the key event handlers.
+    pub fn commit_text(self: Rc<Self>, text: &str) {
+        let focus_item = self.focus_item.borrow().upgrade();
+        if let Some(text_input) = focus_item
+            .as_ref()
+            .and_then(|item| ItemRef::downcast_pin::<crate::items::TextInput>(item.borrow()))
+        {
+            text_input.commit_text(text, &self);
+        }
+    }
+
+    /// Tells the text input focus listeners that the focus left the `TextInput` if the item that
+    /// has the focus was destroyed. This is called when the items of a component are destroyed.
+    pub fn check_destroyed_focus_item(&self) {
+        if self.focus_item.borrow().upgrade().is_none() {
+            self.notify_text_input_focus(None);
+        }
+    }
+
+    /// Registers a function that is called when a `TextInput` gets the keyboard focus, with its
+    /// geometry and input type, and with None when the focus leaves the `TextInput`, for example
+    /// to show and hide an on-screen keyboard.
+    pub fn add_text_input_focus_listener(
+        &self,
+        listener: Box<dyn Fn(Option<crate::api::TextInputFocus>)>,
+    ) {
+        self.text_input_focus_listeners.borrow_mut().push(listener);
+    }
+
+    fn notify_text_input_focus(&self, focus: Option<crate::api::TextInputFocus>) {
+        // The listeners are told once that the focus left the TextInput
+        if focus.is_none() && !self.text_input_focused.get() {
+            return;
+        }
+        self.text_input_focused.set(focus.is_some());
+        // The listeners may register other listeners, which are kept after the current ones
+        let mut listeners = self.text_input_focus_listeners.take();
+        for listener in &listeners {
+            listener(focus.clone());
+        }
+        listeners.append(&mut self.text_input_focus_listeners.borrow_mut());
+        *self.text_input_focus_listeners.borrow_mut() = listeners;
     }
 
     /// Sets the focus on the window to true or false, depending on the have_focus argument.
@@ -344,9 +616,15 @@ pub fn set_focus(self: Rc<Self>, have_focus: bool) {
             crate::input::FocusEvent::WindowLostFocus
         };
 
-        if let Some(focus_item) = self.as_ref().focus_item.borrow().upgrade() {
+        let focus_item = self.as_ref().focus_item.borrow().upgrade();
+        if let Some(focus_item) = &focus_item {
             focus_item.borrow().as_ref().focus_event(&event, &self);
         }
+
+        // The on-screen keyboard is only needed while the window has the focus
+        self.notify_text_input_focus(
+            focus_item.filter(|_| have_focus).and_then(|item| text_input_focus(&item)),
+        );
     }
 
     /// Marks the window to be the active window. This typically coincides with the keyboard
@@ -426,11 +704,115 @@ pub fn draw_contents(self: Rc<Self>, render_components: impl FnOnce(&[(&Componen
     pub fn show(&self) {
         self.platform_window.get().unwrap().clone().show();
         self.update_window_properties();
+        self.set_visible(true);
     }
 
     /// De-registers the window with the windowing system.
     pub fn hide(&self) {
         self.platform_window.get().unwrap().clone().hide();
+        self.set_visible(false);
+    }
+
+    /// Returns true if the window was shown and not hidden since.
+    pub fn is_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    /// Registers a function that is called with the new visibility every time the window is
+    /// shown or hidden.
+    pub fn add_visibility_listener(&self, listener: Box<dyn Fn(bool)>) {
+        self.visibility_listeners.borrow_mut().push(listener);
+    }
+
+    fn set_visible(&self, visible: bool) {
+        if self.visible.replace(visible) == visible {
+            return;
+        }
+        if !visible {
+            self.notify_text_input_focus(None);
+        }
+        // The listeners may register other listeners, which are kept after the current ones
+        let mut listeners = self.visibility_listeners.take();
+        for listener in &listeners {
+            listener(visible);
+        }
+        listeners.append(&mut self.visibility_listeners.borrow_mut());
+        *self.visibility_listeners.borrow_mut() = listeners;
+    }
+
+    /// Render the window and return its pixels, or None if the backend doesn't support it.
+    /// See [`PlatformWindow::take_snapshot`].
+    pub fn take_snapshot(
+        &self,
+    ) -> Option<crate::graphics::SharedPixelBuffer<crate::graphics::Rgba8Pixel>> {
+        self.platform_window.get().unwrap().clone().take_snapshot()
+    }
+
+    /// Render the item and its children in an image of the size of the item, or return None if
+    /// the backend doesn't support it. See [`PlatformWindow::render_item_to_image`].
+    pub fn render_item_to_image(
+        &self,
+        item: &ItemRc,
+    ) -> Option<crate::graphics::SharedPixelBuffer<crate::graphics::Rgba8Pixel>> {
+        self.platform_window.get().unwrap().clone().render_item_to_image(item)
+    }
+
+    /// Print the components of the `pages` windows, after giving their preferred size to the
+    /// windows that were never shown. See [`PlatformWindow::print`].
+    pub fn print(
+        &self,
+        pages: &[WindowRc],
+        target: &crate::api::PrintTarget,
+    ) -> Result<(), crate::api::PrintError> {
+        for page in pages {
+            page.ensure_window_item_geometry();
+        }
+        self.platform_window.get().unwrap().print(pages, target)
+    }
+
+    /// Returns the statistics of the renderer, with the number of items of the component.
+    /// See [`PlatformWindow::renderer_statistics`].
+    pub fn renderer_statistics(&self) -> crate::api::RendererStatistics {
+        let mut statistics = self.platform_window.get().unwrap().renderer_statistics();
+        let mut item_count = 0;
+        crate::item_tree::visit_items(
+            &self.component(),
+            crate::item_tree::TraversalOrder::BackToFront,
+            |_, _, _, _| {
+                item_count += 1;
+                ItemVisitorResult::Continue(())
+            },
+            (),
+        );
+        statistics.item_count = item_count;
+        statistics
+    }
+
+    /// Render the component of the window into an SVG document, after giving its preferred size
+    /// to the window if it was never shown.
+    #[cfg(feature = "std")]
+    pub fn export_svg(self: &Rc<Self>) -> alloc::string::String {
+        self.ensure_window_item_geometry();
+        crate::svg_export::render_window(self)
+    }
+
+    /// The windows that are rendered without being shown have no size yet, so give them the
+    /// preferred size of their component.
+    fn ensure_window_item_geometry(&self) {
+        if self.window_item_geometry().map_or(false, |(w, h)| w <= 0. || h <= 0.) {
+            let component_rc = self.component();
+            let component = ComponentRc::borrow_pin(&component_rc);
+            self.set_window_item_geometry(
+                component
+                    .as_ref()
+                    .layout_info(crate::layout::Orientation::Horizontal)
+                    .preferred_bounded(),
+                component
+                    .as_ref()
+                    .layout_info(crate::layout::Orientation::Vertical)
+                    .preferred_bounded(),
+            );
+        }
     }
 
     /// Registers the specified window and component to be considered the active popup.
@@ -521,6 +903,27 @@ pub fn set_scale_factor(&self, factor: f32) {
         self.scale_factor.as_ref().set(factor)
     }
 
+    /// Returns the size of the `rem` unit, in logical pixels.
+    pub fn base_font_size(&self) -> f32 {
+        self.base_font_size.as_ref().get()
+    }
+
+    /// Sets the size of the `rem` unit, in logical pixels. The default is 16.
+    pub fn set_base_font_size(&self, size: f32) {
+        self.base_font_size.as_ref().set(size)
+    }
+
+    /// Returns the size of the root item, in logical pixels. This is the reference of the `vw` and
+    /// `vh` units.
+    pub fn viewport_size(&self) -> Size {
+        self.try_component()
+            .map(|component_rc| {
+                let component = ComponentRc::borrow_pin(&component_rc);
+                component.as_ref().get_item_ref(0).as_ref().geometry().size
+            })
+            .unwrap_or_default()
+    }
+
     /// Returns the font properties that are set on the root item if it's a Window item.
     pub fn default_font_properties(&self) -> crate::graphics::FontRequest {
         self.try_component()
@@ -536,6 +939,71 @@ pub fn default_font_properties(&self) -> crate::graphics::FontRequest {
             .unwrap_or_default()
     }
 
+    /// Returns the type of the window, which the backend passes to the windowing system when it
+    /// creates the window.
+    pub fn window_type(&self) -> crate::api::WindowType {
+        self.window_type.get()
+    }
+
+    /// Sets the type of the window. This has no effect on a window that was already shown.
+    pub fn set_window_type(&self, window_type: crate::api::WindowType) {
+        self.window_type.set(window_type)
+    }
+
+    /// Returns true if the backend should draw the title bar in dark mode when it creates the
+    /// window.
+    pub fn dark_title_bar(&self) -> bool {
+        self.dark_title_bar.get()
+    }
+
+    /// Sets whether the title bar is dark. This has no effect on a window that was already shown.
+    pub fn set_dark_title_bar(&self, dark: bool) {
+        self.dark_title_bar.set(dark)
+    }
+
+    /// Returns the backdrop that the backend requests when it creates the window.
+    pub fn backdrop(&self) -> crate::api::WindowBackdrop {
+        self.backdrop.get()
+    }
+
+    /// Sets the backdrop of the window. This has no effect on a window that was already shown.
+    pub fn set_backdrop(&self, backdrop: crate::api::WindowBackdrop) {
+        self.backdrop.set(backdrop)
+    }
+
+    /// Returns the layer-shell configuration that the backend uses when it creates the window.
+    pub fn layer_shell(&self) -> Option<crate::api::LayerShell> {
+        self.layer_shell.borrow().clone()
+    }
+
+    /// Sets the layer-shell configuration of the window. This has no effect on a window that was
+    /// already shown.
+    pub fn set_layer_shell(&self, layer_shell: Option<crate::api::LayerShell>) {
+        *self.layer_shell.borrow_mut() = layer_shell
+    }
+
+    /// Returns the transformation applied to the pointer positions.
+    pub fn input_transform(&self) -> Option<crate::api::InputTransform> {
+        self.input_transform.get()
+    }
+
+    /// Sets the transformation applied to the pointer positions in [`Self::process_mouse_input`].
+    pub fn set_input_transform(&self, transform: Option<crate::api::InputTransform>) {
+        self.input_transform.set(transform)
+    }
+
+    /// Returns how the renderer aligns the geometry to the pixels.
+    pub fn rendering_quality(&self) -> crate::api::RenderingQuality {
+        self.rendering_quality.get()
+    }
+
+    /// Sets how the renderer aligns the geometry to the pixels, and redraws the window.
+    pub fn set_rendering_quality(&self, quality: crate::api::RenderingQuality) {
+        if self.rendering_quality.replace(quality) != quality {
+            self.request_redraw();
+        }
+    }
+
     /// Sets the size of the window item. This method is typically called in response to receiving a
     /// window resize event from the windowing system.
     /// Size is in logical pixels.
@@ -550,6 +1018,33 @@ pub fn set_window_item_geometry(&self, width: f32, height: f32) {
             }
         }
     }
+
+    /// Sets the insets of the safe area of the window item, which is the part of the window that is
+    /// not covered by the status bar, the notch, or the rounded corners of the screen of a mobile
+    /// device. The insets are in logical pixels.
+    pub fn set_window_item_safe_area(&self, top: f32, bottom: f32, left: f32, right: f32) {
+        if let Some(component_rc) = self.try_component() {
+            let component = ComponentRc::borrow_pin(&component_rc);
+            let root_item = component.as_ref().get_item_ref(0);
+            if let Some(window_item) = ItemRef::downcast_pin::<crate::items::WindowItem>(root_item)
+            {
+                window_item.safe_area_inset_top.set(top);
+                window_item.safe_area_inset_bottom.set(bottom);
+                window_item.safe_area_inset_left.set(left);
+                window_item.safe_area_inset_right.set(right);
+            }
+        }
+    }
+
+    /// Returns the size of the window item in logical pixels, if the root item of the component
+    /// is a window item.
+    pub fn window_item_geometry(&self) -> Option<(f32, f32)> {
+        let component_rc = self.try_component()?;
+        let component = ComponentRc::borrow_pin(&component_rc);
+        let root_item = component.as_ref().get_item_ref(0);
+        let window_item = ItemRef::downcast_pin::<crate::items::WindowItem>(root_item)?;
+        Some((window_item.width(), window_item.height()))
+    }
 }
 
 impl core::ops::Deref for Window {
@@ -560,6 +1055,19 @@ fn deref(&self) -> &Self::Target {
     }
 }
 
+/// Returns the geometry relative to the window and the input type of the item if it is a
+/// `TextInput`
+fn text_input_focus(item: &ItemRc) -> Option<crate::api::TextInputFocus> {
+    let input_type = ItemRef::downcast_pin::<crate::items::TextInput>(item.borrow())?.input_type();
+    let mut geometry = item.borrow().as_ref().geometry();
+    let mut parent_item = item.parent_item().upgrade();
+    while let Some(parent) = parent_item {
+        geometry.origin += parent.borrow().as_ref().geometry().origin.to_vector();
+        parent_item = parent.parent_item().upgrade();
+    }
+    Some(crate::api::TextInputFocus { geometry, input_type })
+}
+
 /// Internal trait used by generated code to access window internals.
 pub trait WindowHandleAccess {
     /// Returns a reference to the window implementation.
@@ -646,6 +1154,43 @@ pub enum GraphicsAPI {
         window.set_scale_factor(value)
     }
 
+    /// Returns the size of the `rem` unit.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_get_base_font_size(
+        handle: *const WindowRcOpaque,
+    ) -> f32 {
+        let window = &*(handle as *const WindowRc);
+        window.base_font_size()
+    }
+
+    /// Sets the size of the `rem` unit.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_set_base_font_size(
+        handle: *const WindowRcOpaque,
+        value: f32,
+    ) {
+        let window = &*(handle as *const WindowRc);
+        window.set_base_font_size(value)
+    }
+
+    /// Returns the width of the root item, used by the `vw` unit.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_get_viewport_width(
+        handle: *const WindowRcOpaque,
+    ) -> f32 {
+        let window = &*(handle as *const WindowRc);
+        window.viewport_size().width
+    }
+
+    /// Returns the height of the root item, used by the `vh` unit.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_get_viewport_height(
+        handle: *const WindowRcOpaque,
+    ) -> f32 {
+        let window = &*(handle as *const WindowRc);
+        window.viewport_size().height
+    }
+
     /// Sets the window scale factor, merely for testing purposes.
     #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_free_graphics_resources<'a>(
@@ -688,6 +1233,7 @@ pub enum GraphicsAPI {
         window.show_popup(popup, position, parent_item);
     }
     /// Close the current popup
+    #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_close_popup(handle: *const WindowRcOpaque) {
         let window = &*(handle as *const WindowRc);
         window.close_popup();
@@ -752,4 +1298,18 @@ fn notify(&mut self, state: RenderingState, graphics_api: &crate::api::GraphicsA
         let window = &*(handle as *const WindowRc);
         window.request_redraw();
     }
+
+    /// Moves the keyboard focus to the next item of the focus chain.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_focus_next_item(handle: *const WindowRcOpaque) {
+        let window = &*(handle as *const WindowRc);
+        window.clone().focus_next_item();
+    }
+
+    /// Moves the keyboard focus to the previous item of the focus chain.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_focus_previous_item(handle: *const WindowRcOpaque) {
+        let window = &*(handle as *const WindowRc);
+        window.clone().focus_previous_item();
+    }
 }
@@ -3,17 +3,20 @@
 
 use core::convert::TryInto;
 use i_slint_compiler::langtype::Type as LangType;
+use i_slint_core::animations::EasingCurve;
 use i_slint_core::graphics::Image;
 use i_slint_core::model::{Model, ModelRc};
 use i_slint_core::{Brush, PathData, SharedString, SharedVector};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::iter::FromIterator;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 #[doc(inline)]
 pub use i_slint_compiler::diagnostics::{Diagnostic, DiagnosticLevel};
+pub use i_slint_compiler::typeloader::CompiledLibrary;
+pub use i_slint_compiler::DefineValue;
 
 pub use i_slint_core::api::*;
 
@@ -41,6 +44,14 @@ pub enum ValueType {
     Brush,
     /// Correspond to `image` type in .slint.
     Image,
+    /// Correspond to an `enum` type in .slint, such as one declared with `enum Foo { bar, baz }`
+    Enumeration,
+    /// Correspond to a map type in .slint, such as `[string: int]`
+    Map,
+    /// Correspond to the `component-factory` type in .slint
+    ComponentFactory,
+    /// Correspond to the `date-time` type in .slint
+    DateTime,
     /// The type is not a public type but something internal.
     #[doc(hidden)]
     Other = -1,
@@ -64,6 +75,10 @@ fn from(ty: LangType) -> Self {
             LangType::Struct { .. } => Self::Struct,
             LangType::Void => Self::Void,
             LangType::Image => Self::Image,
+            LangType::Enumeration(_) => Self::Enumeration,
+            LangType::Map(_) => Self::Map,
+            LangType::ComponentFactory => Self::ComponentFactory,
+            LangType::DateTime => Self::DateTime,
             _ => Self::Other,
         }
     }
@@ -107,12 +122,18 @@ pub enum Value {
     #[doc(hidden)]
     /// An easing curve
     EasingCurve(i_slint_core::animations::EasingCurve),
-    #[doc(hidden)]
     /// An enumeration, like `TextHorizontalAlignment::align_center`, represented by `("TextHorizontalAlignment", "align_center")`.
-    /// FIXME: consider representing that with a number?
+    /// This is also used for the enums declared in .slint: with `enum Foo { bar, baz }`, `Foo.baz` is
+    /// represented by `("Foo", "baz")`.
     EnumerationValue(String, String),
+    /// Correspond to a map type in .slint, such as `[string: int]`
+    Map(Map),
     #[doc(hidden)]
     LayoutCache(SharedVector<f32>),
+    /// Correspond to the `component-factory` type in .slint
+    ComponentFactory(ComponentFactory),
+    /// Correspond to the `date-time` type in .slint
+    DateTime(DateTime),
 }
 
 impl Value {
@@ -127,9 +148,58 @@ pub fn value_type(&self) -> ValueType {
             Value::Struct(_) => ValueType::Struct,
             Value::Brush(_) => ValueType::Brush,
             Value::Image(_) => ValueType::Image,
+            Value::EnumerationValue(..) => ValueType::Enumeration,
+            Value::Map(_) => ValueType::Map,
+            Value::ComponentFactory(_) => ValueType::ComponentFactory,
+            Value::DateTime(_) => ValueType::DateTime,
             _ => ValueType::Other,
         }
     }
+
+    /// Returns an object that formats the value on several lines, such as to log a deeply nested
+    /// struct. See [`PrettyValue`].
+    pub fn pretty(&self) -> PrettyValue<'_> {
+        PrettyValue { value: self, max_depth: usize::MAX }
+    }
+
+    /// A color from the hue in degrees, and the saturation, value (brightness) and alpha in the
+    /// range 0..1, like the `hsva()` function of the `.slint` language.
+    /// See [`Color::from_hsva`](i_slint_core::Color::from_hsva).
+    pub fn from_hsva(hue: f32, saturation: f32, value: f32, alpha: f32) -> Value {
+        i_slint_core::Color::from_hsva(hue, saturation, value, alpha).into()
+    }
+
+    /// Returns this color made brighter by the factor, like `color.brighter(factor)` in the
+    /// `.slint` language, or None if this value is not a color
+    pub fn brighter(&self, factor: f32) -> Option<Value> {
+        self.as_color().map(|c| c.brighter(factor).into())
+    }
+
+    /// Returns this color made darker by the factor, like `color.darker(factor)` in the
+    /// `.slint` language, or None if this value is not a color
+    pub fn darker(&self, factor: f32) -> Option<Value> {
+        self.as_color().map(|c| c.darker(factor).into())
+    }
+
+    /// Returns a mix of this color and `other`, where `factor` is the proportion of this color,
+    /// like `color.mix(other, factor)` in the `.slint` language, or None if one of the values
+    /// is not a color
+    pub fn mix(&self, other: &Value, factor: f32) -> Option<Value> {
+        Some(self.as_color()?.mix(&other.as_color()?, factor).into())
+    }
+
+    /// Returns this color with this alpha channel, like `color.with-alpha(alpha)` in the
+    /// `.slint` language, or None if this value is not a color
+    pub fn with_alpha(&self, alpha: f32) -> Option<Value> {
+        self.as_color().map(|c| c.with_alpha(alpha).into())
+    }
+
+    fn as_color(&self) -> Option<i_slint_core::Color> {
+        match self {
+            Value::Brush(Brush::SolidColor(c)) => Some(*c),
+            _ => None,
+        }
+    }
 }
 
 impl Default for Value {
@@ -160,7 +230,12 @@ fn eq(&self, other: &Self) -> bool {
             Value::EnumerationValue(lhs_name, lhs_value) => {
                 matches!(other, Value::EnumerationValue(rhs_name, rhs_value) if lhs_name == rhs_name && lhs_value == rhs_value)
             }
+            Value::Map(lhs) => matches!(other, Value::Map(rhs) if lhs == rhs),
             Value::LayoutCache(lhs) => matches!(other, Value::LayoutCache(rhs) if lhs == rhs),
+            Value::ComponentFactory(lhs) => {
+                matches!(other, Value::ComponentFactory(rhs) if lhs == rhs)
+            }
+            Value::DateTime(lhs) => matches!(other, Value::DateTime(rhs) if lhs == rhs),
         }
     }
 }
@@ -183,11 +258,172 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Value::PathData(e) => write!(f, "Value::PathElements({:?})", e),
             Value::EasingCurve(c) => write!(f, "Value::EasingCurve({:?})", c),
             Value::EnumerationValue(n, v) => write!(f, "Value::EnumerationValue({:?}, {:?})", n, v),
+            Value::Map(m) => write!(f, "Value::Map({:?})", m),
             Value::LayoutCache(v) => write!(f, "Value::LayoutCache({:?})", v),
+            Value::ComponentFactory(c) => write!(f, "Value::ComponentFactory({:?})", c),
+            Value::DateTime(d) => write!(f, "Value::DateTime({:?})", d),
+        }
+    }
+}
+
+/// Formats the value with a notation close to the one of .slint, on a single line, such as
+/// `{ name: "Olivier", scores: [12, 14] }`. The fields of the structs are sorted by name.
+///
+/// The alternate flag formats the nested structs, models, and maps on several lines, like
+/// [`Value::pretty()`] without a depth limit.
+///
+/// ```
+/// # use slint_interpreter::*;
+/// let value : Value = [("foo".into(), 45u32.into()), ("bar".into(), true.into())]
+///     .iter().cloned().collect::<Struct>().into();
+/// assert_eq!(value.to_string(), "{ bar: true, foo: 45 }");
+/// assert_eq!(format!("{:#}", value), "{\n    bar: true,\n    foo: 45,\n}");
+/// ```
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let indent = if f.alternate() { Some(0) } else { None };
+        format_value(self, f, indent, usize::MAX)
+    }
+}
+
+/// Formats a [`Value`] on several lines, with one line per field of the structs and per row of
+/// the models, up to a depth limit. Returned by [`Value::pretty()`].
+///
+/// ```
+/// # use slint_interpreter::*;
+/// let inner : Value = [("x".into(), 1u32.into())].iter().cloned().collect::<Struct>().into();
+/// let value : Value = [("inner".into(), inner)].iter().cloned().collect::<Struct>().into();
+/// assert_eq!(value.pretty().to_string(), "{\n    inner: {\n        x: 1,\n    },\n}");
+/// assert_eq!(value.pretty().max_depth(1).to_string(), "{\n    inner: { ... },\n}");
+/// ```
+#[derive(Clone, Copy)]
+pub struct PrettyValue<'a> {
+    value: &'a Value,
+    max_depth: usize,
+}
+
+impl<'a> PrettyValue<'a> {
+    /// Sets the number of levels of structs, models, and maps that are shown. The content of the
+    /// deeper levels is elided as `{ ... }` or `[...]`.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl<'a> std::fmt::Display for PrettyValue<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        format_value(self.value, f, Some(0), self.max_depth)
+    }
+}
+
+/// Formats the value on a single line if `indent` is None, or else on several lines, with the
+/// nested lines indented by one more level than `indent`.
+fn format_value(
+    value: &Value,
+    f: &mut std::fmt::Formatter<'_>,
+    indent: Option<usize>,
+    depth: usize,
+) -> std::fmt::Result {
+    match value {
+        Value::Void => write!(f, "void"),
+        Value::Number(n) => write!(f, "{}", n),
+        Value::String(s) => write!(f, "{:?}", s.as_str()),
+        Value::Bool(b) => write!(f, "{}", b),
+        Value::Image(image) => match image.path() {
+            Some(path) => write!(f, "@image-url({:?})", path),
+            None => write!(f, "image({}x{})", image.size().width, image.size().height),
+        },
+        Value::Model(model) => format_entries(
+            f,
+            ("[", "]", "..."),
+            model.iter().map(|row| (None, row)).collect(),
+            indent,
+            depth,
+        ),
+        Value::Struct(st) => {
+            let mut fields = st.iter().collect::<Vec<_>>();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            format_entries(
+                f,
+                ("{", "}", " ... "),
+                fields.into_iter().map(|(name, v)| (Some(name.to_string()), v.clone())).collect(),
+                indent,
+                depth,
+            )
+        }
+        Value::Map(map) => format_entries(
+            f,
+            ("[", "]", "..."),
+            map.iter().map(|(k, v)| (Some(format!("{:?}", k)), v.clone())).collect(),
+            indent,
+            depth,
+        ),
+        Value::Brush(Brush::SolidColor(color)) => format_color(f, color),
+        Value::Brush(Brush::LinearGradient(gradient)) => {
+            write!(f, "@linear-gradient({}deg", gradient.angle())?;
+            for stop in gradient.stops() {
+                write!(f, ", ")?;
+                format_color(f, &stop.color)?;
+                write!(f, " {}%", stop.position * 100.)?;
+            }
+            write!(f, ")")
+        }
+        Value::EnumerationValue(name, value) => write!(f, "{}.{}", name, value),
+        Value::ComponentFactory(_) => write!(f, "component-factory"),
+        Value::DateTime(d) => write!(f, "@date-time({})", d.millis_since_epoch()),
+        Value::PathData(_) | Value::EasingCurve(_) | Value::LayoutCache(_) => {
+            write!(f, "{:?}", value)
+        }
+    }
+}
+
+/// Formats the entries of a struct, a model, or a map between the delimiters, with their names
+/// for the structs and the maps. If `depth` is 0, only the elision marker is written.
+fn format_entries(
+    f: &mut std::fmt::Formatter<'_>,
+    (open, close, elided): (&str, &str, &str),
+    entries: Vec<(Option<String>, Value)>,
+    indent: Option<usize>,
+    depth: usize,
+) -> std::fmt::Result {
+    if entries.is_empty() {
+        return write!(f, "{}{}", open, close);
+    }
+    if depth == 0 {
+        return write!(f, "{}{}{}", open, elided, close);
+    }
+    // The struct fields are separated from the braces by a space, like in .slint
+    let padding = if open == "{" { " " } else { "" };
+    write!(f, "{}", open)?;
+    for (i, (name, value)) in entries.iter().enumerate() {
+        match indent {
+            Some(indent) => write!(f, "\n{:width$}", "", width = (indent + 1) * 4)?,
+            None if i == 0 => write!(f, "{}", padding)?,
+            None => write!(f, ", ")?,
+        }
+        if let Some(name) = name {
+            write!(f, "{}: ", name)?;
         }
+        format_value(value, f, indent.map(|indent| indent + 1), depth - 1)?;
+        if indent.is_some() {
+            write!(f, ",")?;
+        }
+    }
+    match indent {
+        Some(indent) => write!(f, "\n{:width$}{}", "", close, width = indent * 4),
+        None => write!(f, "{}{}", padding, close),
     }
 }
 
+fn format_color(f: &mut std::fmt::Formatter<'_>, color: &i_slint_core::Color) -> std::fmt::Result {
+    write!(f, "#{:02x}{:02x}{:02x}", color.red(), color.green(), color.blue())?;
+    if color.alpha() != 255 {
+        write!(f, "{:02x}", color.alpha())?;
+    }
+    Ok(())
+}
+
 /// Helper macro to implement the From / TryInto for Value
 ///
 /// For example
@@ -222,10 +458,28 @@ fn try_into(self) -> Result<$ty, Value> {
 declare_value_conversion!(Bool => [bool] );
 declare_value_conversion!(Image => [Image] );
 declare_value_conversion!(Struct => [Struct] );
+declare_value_conversion!(Map => [Map] );
 declare_value_conversion!(Brush => [Brush] );
 declare_value_conversion!(PathData => [PathData]);
 declare_value_conversion!(EasingCurve => [i_slint_core::animations::EasingCurve]);
 declare_value_conversion!(LayoutCache => [SharedVector<f32>] );
+declare_value_conversion!(ComponentFactory => [ComponentFactory] );
+declare_value_conversion!(DateTime => [DateTime] );
+
+impl From<std::time::SystemTime> for Value {
+    fn from(time: std::time::SystemTime) -> Self {
+        Value::DateTime(time.into())
+    }
+}
+impl TryInto<std::time::SystemTime> for Value {
+    type Error = Value;
+    fn try_into(self) -> Result<std::time::SystemTime, Value> {
+        match self {
+            Self::DateTime(d) => d.try_into().map_err(|_| self),
+            _ => Err(self),
+        }
+    }
+}
 
 /// Implement From / TryInto for Value that convert a `struct` to/from `Value::Object`
 macro_rules! declare_value_struct_conversion {
@@ -255,10 +509,12 @@ fn try_into(self) -> Result<$name, ()> {
     };
 }
 
-declare_value_struct_conversion!(struct i_slint_core::model::StandardListViewItem { text });
+declare_value_struct_conversion!(struct i_slint_core::model::StandardListViewItem { text, icon, secondary_text, disabled, user_data });
+declare_value_struct_conversion!(struct i_slint_core::model::Date { year, month, day });
+declare_value_struct_conversion!(struct i_slint_core::model::Time { hour, minute, second });
 declare_value_struct_conversion!(struct i_slint_core::properties::StateInfo { current_state, previous_state, change_time });
 declare_value_struct_conversion!(struct i_slint_core::input::KeyboardModifiers { control, alt, shift, meta });
-declare_value_struct_conversion!(struct i_slint_core::input::KeyEvent { event_type, text, modifiers });
+declare_value_struct_conversion!(struct i_slint_core::input::KeyEvent { event_type, text, modifiers, scancode, repeat, key });
 declare_value_struct_conversion!(struct i_slint_core::layout::LayoutInfo { min, max, min_percent, max_percent, preferred, stretch });
 declare_value_struct_conversion!(struct i_slint_core::graphics::Point { x, y, ..Default::default()});
 declare_value_struct_conversion!(struct i_slint_core::items::PointerEvent { kind, button });
@@ -306,6 +562,7 @@ fn try_into(self) -> Result<$ty, ()> {
 declare_value_enum_conversion!(i_slint_core::items::ImageFit, ImageFit);
 declare_value_enum_conversion!(i_slint_core::items::ImageRendering, ImageRendering);
 declare_value_enum_conversion!(i_slint_core::input::KeyEventType, KeyEventType);
+declare_value_enum_conversion!(i_slint_core::input::LogicalKey, LogicalKey);
 declare_value_enum_conversion!(i_slint_core::items::EventResult, EventResult);
 declare_value_enum_conversion!(i_slint_core::items::FillRule, FillRule);
 declare_value_enum_conversion!(i_slint_core::items::MouseCursor, MouseCursor);
@@ -314,6 +571,7 @@ fn try_into(self) -> Result<$ty, ()> {
 declare_value_enum_conversion!(i_slint_core::items::PointerEventButton, PointerEventButton);
 declare_value_enum_conversion!(i_slint_core::items::DialogButtonRole, DialogButtonRole);
 declare_value_enum_conversion!(i_slint_core::items::InputType, InputType);
+declare_value_enum_conversion!(i_slint_core::items::AccessibleRole, AccessibleRole);
 declare_value_enum_conversion!(i_slint_core::graphics::PathEvent, PathEvent);
 
 impl From<i_slint_core::animations::Instant> for Value {
@@ -424,6 +682,361 @@ fn from_iter<T: IntoIterator<Item = (String, Value)>>(iter: T) -> Self {
     }
 }
 
+/// This type represents the value of a map type in .slint, such as `[string: int]`.
+/// The entries are ordered by their keys.
+///
+/// ```
+/// # use slint_interpreter::*;
+/// use core::convert::TryInto;
+/// // Construct the value of `["foo": 45, "bar": 12]`
+/// let value : Value = [("foo".into(), 45u32.into()), ("bar".into(), 12u32.into())]
+///     .iter().cloned().collect::<Map>().into();
+///
+/// let m : Map = value.try_into().unwrap();
+/// assert_eq!(m.get("foo").cloned().unwrap().try_into(), Ok(45u32));
+/// assert_eq!(m.iter().map(|(k, _)| k).collect::<Vec<_>>(), vec!["bar", "foo"]);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Map(BTreeMap<String, Value>);
+impl Map {
+    /// Get the value for a given key
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+    /// Set the value of a given key
+    pub fn insert(&mut self, key: String, value: Value) {
+        self.0.insert(key, value);
+    }
+    /// Returns the number of entries in this map
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Returns true if this map has no entries
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over all the entries in this map, ordered by key
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(a, b)| (a.as_str(), b))
+    }
+}
+
+impl FromIterator<(String, Value)> for Map {
+    fn from_iter<T: IntoIterator<Item = (String, Value)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl From<HashMap<String, Value>> for Struct {
+    fn from(fields: HashMap<String, Value>) -> Self {
+        fields.into_iter().collect()
+    }
+}
+
+impl From<Struct> for HashMap<String, Value> {
+    fn from(struct_: Struct) -> Self {
+        struct_.0
+    }
+}
+
+/// Converts JSON to a value, like its deserialization: `null` to [`Value::Void`], the booleans,
+/// numbers, and strings to the corresponding values, the arrays to models, and the objects to
+/// structs.
+///
+/// ```
+/// # use slint_interpreter::*;
+/// use core::convert::TryInto;
+/// let json = serde_json::json!({ "title": "Settings", "volume": 8 });
+/// let config: Struct = Value::from(json).try_into().unwrap();
+/// assert_eq!(config.get_field("volume"), Some(&Value::Number(8.)));
+/// ```
+#[cfg(feature = "json")]
+impl From<serde_json::Value> for Value {
+    fn from(json: serde_json::Value) -> Self {
+        // The deserialization of a Value accepts any JSON
+        serde::Deserialize::deserialize(json).unwrap_or_default()
+    }
+}
+
+/// Converts a value to JSON, like its serialization. The values that cannot be serialized, such
+/// as the images that weren't loaded from a file, are returned as error.
+#[cfg(feature = "json")]
+impl TryFrom<Value> for serde_json::Value {
+    type Error = Value;
+    fn try_from(value: Value) -> Result<Self, Value> {
+        serde_json::to_value(&value).map_err(|_| value)
+    }
+}
+
+/// Converts a JSON object to a struct, or returns the JSON value as error if it isn't an object.
+/// This can set the properties of a component from a configuration file:
+///
+/// ```no_run
+/// # use slint_interpreter::*;
+/// # fn set_config(instance: &ComponentInstance, json: serde_json::Value) {
+/// let config = Struct::try_from(json).unwrap();
+/// for (name, value) in config.iter() {
+///     instance.set_property(name, value.clone()).unwrap();
+/// }
+/// # }
+/// ```
+#[cfg(feature = "json")]
+impl TryFrom<serde_json::Value> for Struct {
+    type Error = serde_json::Value;
+    fn try_from(json: serde_json::Value) -> Result<Self, serde_json::Value> {
+        match <Struct as serde::Deserialize>::deserialize(&json) {
+            Ok(st) => Ok(st),
+            Err(_) => Err(json),
+        }
+    }
+}
+
+/// Serializes the value: [`Value::Void`] as a unit, the models as sequences, the structs and the
+/// maps as maps, the colors as strings with the `#rrggbb` or `#rrggbbaa` notation, the images
+/// loaded from a file as their path, and the enumeration values as the string of their name.
+/// The other values, such as the other images, cannot be serialized and return an error.
+///
+/// This is also the conversion of a value to JSON.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Value {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error, SerializeSeq};
+        match self {
+            Value::Void => serializer.serialize_unit(),
+            Value::Number(n) => serializer.serialize_f64(*n),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::Brush(Brush::SolidColor(_)) => serializer.serialize_str(&self.to_string()),
+            Value::EnumerationValue(_, name) => serializer.serialize_str(name),
+            Value::Image(image) if image.path().is_some() => {
+                serializer.serialize_str(&image.path().unwrap().to_string_lossy())
+            }
+            Value::Model(model) => {
+                let mut seq = serializer.serialize_seq(Some(model.row_count()))?;
+                for row in model.iter() {
+                    seq.serialize_element(&row)?;
+                }
+                seq.end()
+            }
+            Value::Struct(st) => serializer.collect_map(st.iter()),
+            Value::Map(map) => serializer.collect_map(map.iter()),
+            _ => Err(S::Error::custom(format!("{:?} cannot be serialized", self.value_type()))),
+        }
+    }
+}
+
+/// Deserializes a value: a unit or none to [`Value::Void`], the booleans, numbers, and strings to
+/// the corresponding values, the sequences to models, and the maps to structs.
+///
+/// This is also the conversion of JSON to a value.
+///
+/// The keys of the maps become the names of the fields, where the underscores are replaced by
+/// dashes like in .slint, so a `first_name` key is serialized back as `first-name`.
+///
+/// ```
+/// # use slint_interpreter::*;
+/// use core::convert::TryInto;
+/// let value: Value = serde_json::from_str(r#"{ "title": "Settings", "volume": 8 }"#).unwrap();
+/// let config: Struct = value.try_into().unwrap();
+/// assert_eq!(config.get_field("volume"), Some(&Value::Number(8.)));
+/// assert_eq!(serde_json::to_string(&config.get_field("title")).unwrap(), r#""Settings""#);
+/// ```
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Value {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValueVisitor;
+        impl<'de> serde::de::Visitor<'de> for ValueVisitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a boolean, a number, a string, a sequence, a map, or a unit")
+            }
+
+            fn visit_unit<E>(self) -> Result<Value, E> {
+                Ok(Value::Void)
+            }
+
+            fn visit_none<E>(self) -> Result<Value, E> {
+                Ok(Value::Void)
+            }
+
+            fn visit_some<D: serde::Deserializer<'de>>(self, d: D) -> Result<Value, D::Error> {
+                serde::Deserialize::deserialize(d)
+            }
+
+            fn visit_bool<E>(self, b: bool) -> Result<Value, E> {
+                Ok(Value::Bool(b))
+            }
+
+            fn visit_i64<E>(self, n: i64) -> Result<Value, E> {
+                Ok(Value::Number(n as f64))
+            }
+
+            fn visit_u64<E>(self, n: u64) -> Result<Value, E> {
+                Ok(Value::Number(n as f64))
+            }
+
+            fn visit_f64<E>(self, n: f64) -> Result<Value, E> {
+                Ok(Value::Number(n))
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Value, E> {
+                Ok(Value::String(s.into()))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Value, A::Error> {
+                let mut rows = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(row) = seq.next_element()? {
+                    rows.push(row);
+                }
+                Ok(Value::Model(ModelRc::new(i_slint_core::model::VecModel::from(rows))))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Value, A::Error> {
+                // set_field() normalizes the names, like the ones of the fields of .slint
+                let mut st = Struct::default();
+                while let Some((name, value)) = map.next_entry::<String, Value>()? {
+                    st.set_field(name, value);
+                }
+                Ok(Value::Struct(st))
+            }
+        }
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Serializes the struct as a map of its fields
+#[cfg(feature = "serde")]
+impl serde::Serialize for Struct {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+/// Deserializes the struct from a map of its fields
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Struct {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::Struct(st) => Ok(st),
+            _ => Err(<D::Error as serde::de::Error>::custom("expected a map")),
+        }
+    }
+}
+
+/// A struct or an enum exported by a .slint file, as returned by
+/// [`ComponentDefinition::exported_types`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ExportedType {
+    /// A struct, with the names and the types of its fields, ordered by name
+    Struct {
+        /// The name under which the struct is exported
+        name: String,
+        /// The names and the types of the fields
+        fields: Vec<(String, ValueType)>,
+    },
+    /// An enum, with its values in the order of their declaration
+    Enum {
+        /// The name under which the enum is exported
+        name: String,
+        /// The values of the enum
+        values: Vec<String>,
+    },
+}
+
+/// This type represents the value of the `component-factory` type in .slint.
+///
+/// It is set on the `component-factory` property of a `ComponentContainer` element to decide at
+/// runtime which component is shown inside of it. The factory is called each time the container
+/// needs to create its content, and should return a component created with
+/// [`ComponentDefinition::create_embedded`] using the given [`FactoryContext`].
+/// If the factory returns `None`, the container stays empty.
+///
+/// Two factories compare equal if they are clones of each other.
+///
+/// ```
+/// # use slint_interpreter::*;
+/// let mut compiler = ComponentCompiler::default();
+/// let panel = spin_on::spin_on(compiler.build_from_source(
+///     "export Panel := Rectangle { background: blue; }".into(),
+///     Default::default(),
+/// ))
+/// .unwrap();
+/// let host = spin_on::spin_on(compiler.build_from_source(
+///     r#"
+///     export Host := Window {
+///         property <component-factory> panel <=> container.component-factory;
+///         container := ComponentContainer {}
+///     }"#
+///     .into(),
+///     Default::default(),
+/// ))
+/// .unwrap();
+///
+/// let instance = host.create();
+/// let factory = ComponentFactory::new(move |ctx| Some(panel.create_embedded(ctx)));
+/// instance.set_property("panel", Value::ComponentFactory(factory)).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct ComponentFactory(Option<Rc<dyn Fn(FactoryContext) -> Option<ComponentInstance>>>);
+
+impl ComponentFactory {
+    /// Create a new factory from a function that creates the component to embed.
+    pub fn new(factory: impl Fn(FactoryContext) -> Option<ComponentInstance> + 'static) -> Self {
+        Self(Some(Rc::new(factory)))
+    }
+
+    /// Returns true if this is the default factory, that does not create any component.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub(crate) fn build(
+        &self,
+        ctx: FactoryContext,
+    ) -> Option<vtable::VRc<i_slint_core::component::ComponentVTable, ErasedComponentBox>> {
+        self.0.as_ref().and_then(|factory| factory(ctx)).map(|instance| instance.inner)
+    }
+}
+
+impl PartialEq for ComponentFactory {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(lhs), Some(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for ComponentFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            write!(f, "ComponentFactory(<empty>)")
+        } else {
+            write!(f, "ComponentFactory(..)")
+        }
+    }
+}
+
+/// The context in which a [`ComponentFactory`] creates its component.
+///
+/// Pass it to [`ComponentDefinition::create_embedded`] to create a component that is shown
+/// within the `ComponentContainer`.
+#[derive(Clone)]
+pub struct FactoryContext {
+    pub(crate) window: i_slint_core::window::WindowRc,
+    pub(crate) parent_item: i_slint_core::items::ItemWeak,
+}
+
 /// ComponentCompiler is the entry point to the Slint interpreter that can be used
 /// to load .slint files or compile them on-the-fly from a string.
 pub struct ComponentCompiler {
@@ -458,6 +1071,16 @@ pub fn include_paths(&self) -> &Vec<std::path::PathBuf> {
         &self.config.include_paths
     }
 
+    /// Sets the paths of the libraries, that the `.slint` files import with `@name/path/to/file.slint`.
+    pub fn set_library_paths(&mut self, library_paths: HashMap<String, PathBuf>) {
+        self.config.library_paths = library_paths;
+    }
+
+    /// Returns the library paths the component compiler is currently configured with.
+    pub fn library_paths(&self) -> &HashMap<String, PathBuf> {
+        &self.config.library_paths
+    }
+
     /// Sets the style to be used for widgets.
     pub fn set_style(&mut self, style: String) {
         self.config.style = Some(style);
@@ -468,6 +1091,26 @@ pub fn style(&self) -> Option<&String> {
         self.config.style.as_ref()
     }
 
+    /// Sets the flags which enable the matching `@if (flag) { ... }` blocks in the `.slint` files.
+    pub fn set_conditional_flags(&mut self, flags: Vec<String>) {
+        self.config.conditional_flags = flags;
+    }
+
+    /// Returns the flags the compiler is currently using to resolve the `@if` blocks.
+    pub fn conditional_flags(&self) -> &Vec<String> {
+        &self.config.conditional_flags
+    }
+
+    /// Sets the constants that the expressions in the `.slint` files can access as `Defines.name`.
+    pub fn set_defines(&mut self, defines: HashMap<String, DefineValue>) {
+        self.config.defines = defines;
+    }
+
+    /// Returns the constants the compiler is currently configured with.
+    pub fn defines(&self) -> &HashMap<String, DefineValue> {
+        &self.config.defines
+    }
+
     /// Sets the callback that will be invoked when loading imported .slint files. The specified
     /// `file_loader_callback` parameter will be called with a canonical file path as argument
     /// and is expected to return a future that, when resolved, provides the source code of the
@@ -487,11 +1130,53 @@ pub fn set_file_loader(
             Some(Rc::new(move |path| file_loader_fallback(Path::new(path.as_str()))));
     }
 
-    /// Returns the diagnostics that were produced in the last call to [`Self::build_from_path`] or [`Self::build_from_source`].
+    /// Sets the libraries that are linked in the compilations. The documents of these libraries
+    /// are not read or parsed again when they are imported.
+    pub fn set_libraries(&mut self, libraries: Vec<Rc<CompiledLibrary>>) {
+        self.config.libraries = libraries;
+    }
+
+    /// Returns the libraries the compiler is currently linking.
+    pub fn libraries(&self) -> &Vec<Rc<CompiledLibrary>> {
+        &self.config.libraries
+    }
+
+    /// Sets the ids of the elements of the main component whose `visible`, `enabled`, and
+    /// `opacity` can be overridden at runtime with [`ComponentInstance::set_element_visible`],
+    /// [`ComponentInstance::set_element_enabled`], and [`ComponentInstance::set_element_opacity`].
+    ///
+    /// The overrides are combined with the bindings of the elements: an element is only visible
+    /// or enabled if both its binding and the override allow it, and the opacities are multiplied.
+    pub fn set_overridable_elements(&mut self, ids: Vec<String>) {
+        self.config.overridable_elements =
+            ids.iter().map(|id| normalize_identifier(id).into_owned()).collect();
+    }
+
+    /// Returns the ids of the elements whose properties can be overridden at runtime.
+    pub fn overridable_elements(&self) -> &Vec<String> {
+        &self.config.overridable_elements
+    }
+
+    /// Returns the diagnostics that were produced in the last call to [`Self::build_from_path`],
+    /// [`Self::build_from_source`], or [`Self::compile_library`].
     pub fn diagnostics(&self) -> &Vec<Diagnostic> {
         &self.diagnostics
     }
 
+    /// Parse the .slint files at the given paths and the files they import into a library that
+    /// can be linked with [`Self::set_libraries`], for example to share the components of a large
+    /// application between several compilations without parsing them each time.
+    ///
+    /// Returns the library if there were no errors. The diagnostics are collected in this
+    /// ComponentCompiler like for [`Self::build_from_path`].
+    pub async fn compile_library(&mut self, paths: &[PathBuf]) -> Option<Rc<CompiledLibrary>> {
+        let (library, diag) =
+            i_slint_compiler::compile_library(paths, Default::default(), self.config.clone()).await;
+        let has_error = diag.has_error();
+        self.diagnostics = diag.into_iter().collect();
+        (!has_error).then(|| Rc::new(library))
+    }
+
     /// Compile a .slint file into a ComponentDefinition
     ///
     /// Returns the compiled `ComponentDefinition` if there were no errors.
@@ -556,24 +1241,162 @@ pub async fn build_from_source(
         self.diagnostics = diag.into_iter().collect();
         c.ok().map(|inner| ComponentDefinition { inner: inner.into() })
     }
-}
 
-/// ComponentDefinition is a representation of a compiled component from .slint markup.
-///
-/// It can be constructed from a .slint file using the [`ComponentCompiler::build_from_path`] or [`ComponentCompiler::build_from_source`] functions.
-/// And then it can be instantiated with the [`Self::create`] function.
-///
-/// The ComponentDefinition acts as a factory to create new instances. When you've finished
-/// creating the instances it is safe to drop the ComponentDefinition.
-#[derive(Clone)]
-pub struct ComponentDefinition {
-    inner: crate::dynamic_component::ErasedComponentDescription,
-}
+    /// Compile all the components exported by a .slint file, such as the main window and the
+    /// dialogs of an application, into ComponentDefinitions indexed by their exported name.
+    ///
+    /// Returns the compiled definitions if there were no errors. The diagnostics are collected in
+    /// this ComponentCompiler like for [`Self::build_from_path`].
+    pub async fn build_all_from_path<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Option<HashMap<String, ComponentDefinition>> {
+        let path = path.as_ref();
+        let source = match i_slint_compiler::diagnostics::load_from_path(path) {
+            Ok(s) => s,
+            Err(d) => {
+                self.diagnostics = vec![d];
+                return None;
+            }
+        };
+        self.build_all_from_source(source, path.into()).await
+    }
 
-impl ComponentDefinition {
-    /// Creates a new instance of the component and returns a shared handle to it.
-    pub fn create(&self) -> ComponentInstance {
-        generativity::make_guard!(guard);
+    /// Compile all the components exported by some .slint code into ComponentDefinitions indexed
+    /// by their exported name.
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, SharedString};
+    /// let code = r#"
+    ///     export Dialog := Window { property <string> message; }
+    ///     export MainWindow := Window { title: "App"; }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definitions = spin_on::spin_on(
+    ///     compiler.build_all_from_source(code.into(), Default::default())).unwrap();
+    /// let dialog = definitions["Dialog"].create();
+    /// dialog.set_property("message", SharedString::from("Saved").into()).unwrap();
+    /// assert_eq!(definitions["MainWindow"].name(), "MainWindow");
+    /// ```
+    ///
+    /// The source code is compiled once for all the components.
+    pub async fn build_all_from_source(
+        &mut self,
+        source_code: String,
+        path: PathBuf,
+    ) -> Option<HashMap<String, ComponentDefinition>> {
+        let (definitions, diag) =
+            crate::dynamic_component::load_all(source_code, path, self.config.clone()).await;
+        self.diagnostics = diag.into_iter().collect();
+        definitions.ok().map(|definitions| {
+            definitions
+                .into_iter()
+                .map(|(name, inner)| (name, ComponentDefinition { inner }))
+                .collect()
+        })
+    }
+
+    /// Compile the component of a bundle into a ComponentDefinition
+    ///
+    /// A bundle is a tar archive that contains the `.slint` files of a component, such as a theme
+    /// or a plugin, together with the images and fonts they use. The component is the last exported
+    /// component of the `main.slint` file at the root of the archive, which can be a symbolic link
+    /// to a file in a sub-directory. The files of the bundle can import each other, and their
+    /// images and fonts, with relative paths. `slint-compiler --bundle` creates such a bundle.
+    ///
+    /// The content of the bundle is kept in memory as long as the `ComponentDefinition` or one of
+    /// its instances is alive. The images of the bundle are written to temporary files for the
+    /// same duration.
+    ///
+    /// Returns the compiled `ComponentDefinition` if there were no errors. The diagnostics are
+    /// collected in this ComponentCompiler like for [`Self::build_from_path`].
+    pub async fn build_from_bundle<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Option<ComponentDefinition> {
+        let path = path.as_ref();
+        match std::fs::read(path) {
+            Ok(data) => self.build_from_bundle_data(&data).await,
+            Err(err) => {
+                self.diagnostics =
+                    error_diagnostics(format!("Could not load {}: {}", path.display(), err));
+                None
+            }
+        }
+    }
+
+    /// Compile the component of a bundle that is already in memory into a ComponentDefinition
+    ///
+    /// See [`Self::build_from_bundle`] for the content of the bundle.
+    pub async fn build_from_bundle_data(&mut self, data: &[u8]) -> Option<ComponentDefinition> {
+        let (bundle, main_url) = match crate::bundle::load_bundle(data) {
+            Ok(loaded) => loaded,
+            Err(message) => {
+                self.diagnostics = error_diagnostics(message);
+                return None;
+            }
+        };
+        let main_source = match crate::bundle::file_data(&main_url) {
+            Some(data) => String::from_utf8_lossy(&data).into_owned(),
+            None => {
+                self.diagnostics =
+                    error_diagnostics(format!("The bundle does not contain {}", main_url));
+                return None;
+            }
+        };
+
+        // The files of the bundle are loaded from memory, the other files as usual
+        let mut config = self.config.clone();
+        let fallback = config.open_import_fallback.take();
+        config.open_import_fallback = Some(Rc::new(
+            move |path: String| -> core::pin::Pin<
+                Box<dyn core::future::Future<Output = Option<std::io::Result<String>>>>,
+            > {
+                if let Some(data) = crate::bundle::file_data(&path) {
+                    let source = String::from_utf8_lossy(&data).into_owned();
+                    Box::pin(async move { Some(Ok(source)) })
+                } else if let Some(fallback) = &fallback {
+                    fallback(path)
+                } else {
+                    Box::pin(async { None })
+                }
+            },
+        ));
+
+        generativity::make_guard!(guard);
+        let (c, diag) =
+            crate::dynamic_component::load(main_source, main_url.into(), config, guard).await;
+        self.diagnostics = diag.into_iter().collect();
+        c.ok().map(|inner| {
+            inner.bundle.replace(Some(bundle));
+            ComponentDefinition { inner: inner.into() }
+        })
+    }
+}
+
+/// The diagnostics for an error that is not within a .slint file
+fn error_diagnostics(message: String) -> Vec<Diagnostic> {
+    let mut diag = i_slint_compiler::diagnostics::BuildDiagnostics::default();
+    diag.push_error_with_span(message, Default::default());
+    diag.into_iter().collect()
+}
+
+/// ComponentDefinition is a representation of a compiled component from .slint markup.
+///
+/// It can be constructed from a .slint file using the [`ComponentCompiler::build_from_path`] or [`ComponentCompiler::build_from_source`] functions.
+/// And then it can be instantiated with the [`Self::create`] function.
+///
+/// The ComponentDefinition acts as a factory to create new instances. When you've finished
+/// creating the instances it is safe to drop the ComponentDefinition.
+#[derive(Clone)]
+pub struct ComponentDefinition {
+    inner: crate::dynamic_component::ErasedComponentDescription,
+}
+
+impl ComponentDefinition {
+    /// Creates a new instance of the component and returns a shared handle to it.
+    pub fn create(&self) -> ComponentInstance {
+        generativity::make_guard!(guard);
         ComponentInstance {
             inner: self.inner.unerase(guard).clone().create(
                 #[cfg(target_arch = "wasm32")]
@@ -582,13 +1405,25 @@ pub fn create(&self) -> ComponentInstance {
         }
     }
 
-    /// Instantiate the component for wasm using the given canvas id
+    /// Instantiate the component for wasm in the canvas with the given id, or otherwise in the
+    /// canvas that matches `canvas_id` as a CSS selector
     #[cfg(target_arch = "wasm32")]
     pub fn create_with_canvas_id(&self, canvas_id: &str) -> ComponentInstance {
         generativity::make_guard!(guard);
         ComponentInstance { inner: self.inner.unerase(guard).clone().create(canvas_id.into()) }
     }
 
+    /// Instantiate the component to be embedded in a `ComponentContainer`.
+    ///
+    /// This must be called from the function of a [`ComponentFactory`], with the context it received.
+    /// The component is shown in the window of the container. It is resized to fill the container,
+    /// unless its root element sets its own `width` or `height`. The background of a `Window` is
+    /// not drawn.
+    pub fn create_embedded(&self, ctx: FactoryContext) -> ComponentInstance {
+        generativity::make_guard!(guard);
+        ComponentInstance { inner: self.inner.unerase(guard).clone().create_embedded(ctx) }
+    }
+
     /// Instantiate the component using an existing window.
     /// This method is internal because the WindowRc is not a public type
     #[doc(hidden)]
@@ -645,6 +1480,22 @@ pub fn callbacks(&self) -> impl Iterator<Item = String> + '_ {
         })
     }
 
+    /// Returns the names of all publicly declared functions.
+    ///
+    /// These can be called with [`ComponentInstance::invoke`].
+    pub fn functions(&self) -> impl Iterator<Item = String> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).properties().filter_map(|(prop_name, prop_type)| {
+            if matches!(prop_type, LangType::Function { .. }) {
+                Some(prop_name)
+            } else {
+                None
+            }
+        })
+    }
+
     /// Returns the names of all exported global singletons
     ///
     /// **Note:** Only globals that are exported or re-exported from the main .slint file will
@@ -656,6 +1507,156 @@ pub fn globals(&self) -> impl Iterator<Item = String> + '_ {
         self.inner.unerase(guard).global_names()
     }
 
+    /// Registers `implementation` as the implementation of the exported global singleton named
+    /// `global`, in the instances of this component that are created afterwards, or removes the
+    /// registration with `None`. The instances that were already created are unchanged.
+    ///
+    /// Returns an error if there is no such exported global, or if `implementation` implements
+    /// a property or a callback that it cannot implement, such as an alias.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, GlobalImplementation, SharedString, Value};
+    /// struct Clock;
+    /// impl GlobalImplementation for Clock {
+    ///     fn implements(&self, name: &str) -> bool {
+    ///         name == "time-zone" || name == "format"
+    ///     }
+    ///     fn property(&self, _name: &str) -> Value {
+    ///         Value::from(SharedString::from("UTC"))
+    ///     }
+    ///     fn invoke_callback(&self, _name: &str, args: &[Value]) -> Value {
+    ///         args[0].clone()
+    ///     }
+    /// }
+    ///
+    /// let code = r#"
+    ///     export global Clock := {
+    ///         property <string> time-zone: "CET";
+    ///         callback format(string) -> string;
+    ///     }
+    ///     export MyWin := Window {
+    ///         property <string> zone: Clock.time-zone;
+    ///         property <string> formatted: Clock.format("noon");
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let definition = definition.unwrap();
+    /// definition.set_global_implementation("Clock", Some(std::rc::Rc::new(Clock))).unwrap();
+    /// let instance = definition.create();
+    /// assert_eq!(instance.get_property("zone").unwrap(), Value::from(SharedString::from("UTC")));
+    /// assert_eq!(instance.get_property("formatted").unwrap(), Value::from(SharedString::from("noon")));
+    /// ```
+    pub fn set_global_implementation(
+        &self,
+        global: &str,
+        implementation: Option<Rc<dyn GlobalImplementation>>,
+    ) -> Result<(), SetGlobalImplementationError> {
+        generativity::make_guard!(guard);
+        self.inner.unerase(guard).set_global_implementation(global, implementation)
+    }
+
+    /// Returns the names of the enums declared in the .slint markup that are used by this component.
+    ///
+    /// Together with [`Self::enum_values`], this allows listing the values that a property of type
+    /// [`ValueType::Enumeration`] can hold, for example to fill a combo box.
+    pub fn enums(&self) -> impl Iterator<Item = String> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).enums().into_iter().map(|en| en.name.clone())
+    }
+
+    /// Returns the values of the enum specified by its name, in the order of declaration, or None
+    /// if there is no such enum declared in the .slint markup and used by this component.
+    ///
+    /// These are the values that can be used in a [`Value::EnumerationValue`] for this enum.
+    ///
+    /// ## Examples
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler, Value};
+    /// let code = r#"
+    ///     enum Mode { automatic, manual, off }
+    ///     MyWin := Window {
+    ///         property <Mode> mode: manual;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default())).unwrap();
+    /// assert_eq!(definition.enums().collect::<Vec<_>>(), vec!["Mode".to_string()]);
+    /// assert_eq!(
+    ///     definition.enum_values("Mode").unwrap().collect::<Vec<_>>(),
+    ///     vec!["automatic".to_string(), "manual".to_string(), "off".to_string()]
+    /// );
+    /// let instance = definition.create();
+    /// assert_eq!(
+    ///     instance.get_property("mode").unwrap(),
+    ///     Value::EnumerationValue("Mode".into(), "manual".into())
+    /// );
+    /// ```
+    pub fn enum_values(&self, enum_name: &str) -> Option<impl Iterator<Item = String> + '_> {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        let enum_name = normalize_identifier(enum_name);
+        self.inner
+            .unerase(guard)
+            .enums()
+            .into_iter()
+            .find(|en| en.name == enum_name.as_ref())
+            .map(|en| en.values.clone().into_iter())
+    }
+
+    /// Returns the structs and enums exported by the .slint file of this component, with their
+    /// fields or values. For example, a serializer can use it to convert the values of these
+    /// types.
+    ///
+    /// ## Examples
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, ExportedType, ValueType};
+    /// let code = r#"
+    ///     export struct Point := { x: int, y: int }
+    ///     export enum Mode { automatic, manual }
+    ///     export MyWin := Window {
+    ///         property <Point> origin;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default())).unwrap();
+    /// assert_eq!(
+    ///     definition.exported_types().collect::<Vec<_>>(),
+    ///     vec![
+    ///         ExportedType::Struct {
+    ///             name: "Point".into(),
+    ///             fields: vec![("x".into(), ValueType::Number), ("y".into(), ValueType::Number)],
+    ///         },
+    ///         ExportedType::Enum {
+    ///             name: "Mode".into(),
+    ///             values: vec!["automatic".into(), "manual".into()],
+    ///         },
+    ///     ]
+    /// );
+    /// ```
+    pub fn exported_types(&self) -> impl Iterator<Item = ExportedType> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).exported_types().iter().filter_map(|(name, ty)| match ty {
+            LangType::Struct { fields, .. } => Some(ExportedType::Struct {
+                name: name.clone(),
+                fields: fields.iter().map(|(n, ty)| (n.clone(), ty.clone().into())).collect(),
+            }),
+            LangType::Enumeration(en) => {
+                Some(ExportedType::Enum { name: name.clone(), values: en.values.clone() })
+            }
+            _ => None,
+        })
+    }
+
     /// List of publicly declared properties in the exported global singleton specified by its name.
     pub fn global_properties(
         &self,
@@ -698,6 +1699,16 @@ pub fn name(&self) -> &str {
         let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
         self.inner.unerase(guard).id()
     }
+
+    /// The files used to build this component: the main .slint file, the files it imports, and
+    /// the images and fonts they use, whether or not they are embedded.
+    ///
+    /// These are the files that a build system, or a tool that reloads the component when they
+    /// change, needs to watch. The builtin files, such as the widgets of the style, are not listed.
+    pub fn dependencies(&self) -> Vec<PathBuf> {
+        generativity::make_guard!(guard);
+        self.inner.unerase(guard).original.dependencies.borrow().clone()
+    }
 }
 
 /// Print the diagnostics to stderr
@@ -714,6 +1725,20 @@ pub fn print_diagnostics(diagnostics: &[Diagnostic]) {
     build_diagnostics.print();
 }
 
+/// The location in the source code and the geometry of an element of a [`ComponentInstance`].
+///
+/// See [`ComponentInstance::elements_at_position`] and [`ComponentInstance::tab_order`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ElementPosition {
+    /// The path of the .slint file that declares the element
+    pub path: PathBuf,
+    /// The offset, in bytes, of the declaration of the element in the file
+    pub offset: usize,
+    /// The geometry of the element, in logical coordinates relative to the window
+    pub geometry: i_slint_core::graphics::Rect,
+}
+
 /// This represent an instance of a dynamic component
 ///
 /// You can create an instance with the [`ComponentDefinition::create`] function.
@@ -731,6 +1756,26 @@ pub struct ComponentInstance {
 
 impl ComponentInstance {
     /// Return the [`ComponentDefinition`] that was used to create this instance.
+    ///
+    /// This lets the code that only has an instance list its properties and callbacks, or
+    /// create other instances of the same component.
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <int> my_property: 42;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    ///
+    /// let sibling = instance.definition().create();
+    /// assert_eq!(sibling.get_property("my_property").unwrap(), Value::from(42));
+    /// assert_eq!(instance.definition().properties().count(), 1);
+    /// ```
     pub fn definition(&self) -> ComponentDefinition {
         generativity::make_guard!(guard);
         ComponentDefinition { inner: self.inner.unerase(guard).description().into() }
@@ -769,6 +1814,200 @@ pub fn set_property(&self, name: &str, value: Value) -> Result<(), SetPropertyEr
         comp.description().set_property(comp.borrow(), &normalize_identifier(name), value)
     }
 
+    /// Set several properties of this component, and of the globals it uses, at once.
+    ///
+    /// The closure adds the changes to the [`PropertyTransaction`], which checks that the
+    /// properties exist and that the values have the right type. If one of the changes is
+    /// invalid, none of them is applied and the batch returns the first error. Otherwise the
+    /// changes are applied when the closure returns, so in the closure [`Self::get_property`]
+    /// still returns the previous values.
+    ///
+    /// The batch only defers the changes: they are then set one after the other, and they cannot
+    /// be undone. The bindings that depend on these properties are evaluated lazily, the next time
+    /// they are read, so they see all the changes at once, but the change handlers that react to
+    /// each property as it is set, such as the one of the `settings` module, can see the
+    /// intermediate states.
+    ///
+    /// Returns the value returned by the closure, or the first error of adding the changes.
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, SharedString, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <string> title;
+    ///         property <int> count;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// instance
+    ///     .batch(|transaction| {
+    ///         transaction.set("title", SharedString::from("Inbox").into()).unwrap();
+    ///         transaction.set("count", Value::from(12)).unwrap();
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(instance.get_property("count").unwrap(), Value::from(12));
+    /// ```
+    pub fn batch<R>(
+        &self,
+        f: impl FnOnce(&mut PropertyTransaction) -> R,
+    ) -> Result<R, SetPropertyError> {
+        let mut transaction = PropertyTransaction::new(self);
+        let result = f(&mut transaction);
+        if let Some(error) = transaction.error {
+            return Err(error);
+        }
+        match transaction.apply().into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => Ok(result),
+        }
+    }
+
+    /// Returns the values of all the public properties of this component, with the name of the
+    /// properties as field names, for example to restore them with [`Self::restore`] to undo
+    /// changes, or in a new instance after reloading the .slint file.
+    ///
+    /// The models are copied: the snapshot contains the rows that they have now, and not the
+    /// models themselves. The callbacks and the functions are not part of the snapshot.
+    pub fn snapshot(&self) -> Struct {
+        self.definition()
+            .properties()
+            .filter_map(|(name, _)| {
+                Some((name.clone(), copy_models(self.get_property(&name).ok()?)))
+            })
+            .collect()
+    }
+
+    /// Sets the public properties of this component to the values of a snapshot taken with
+    /// [`Self::snapshot`]. The changes are applied together like in [`Self::batch`].
+    ///
+    /// The fields of the snapshot for which this component has no property with the same name and
+    /// type, or that cannot be set, are skipped without cancelling the others, so that a snapshot
+    /// taken before the .slint file changed can be restored. Returns the names of these fields.
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <int> count: 1;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let snapshot = instance.snapshot();
+    /// instance.set_property("count", Value::from(5)).unwrap();
+    /// assert!(instance.restore(&snapshot).is_empty());
+    /// assert_eq!(instance.get_property("count").unwrap(), Value::from(1));
+    /// ```
+    #[must_use = "the fields that were not restored are returned"]
+    pub fn restore(&self, snapshot: &Struct) -> Vec<String> {
+        let mut transaction = PropertyTransaction::new(self);
+        let mut skipped: Vec<String> = snapshot
+            .iter()
+            .filter(|(name, value)| transaction.set(name, copy_models((*value).clone())).is_err())
+            .map(|(name, _)| name.to_string())
+            .collect();
+        skipped.extend(transaction.apply().into_iter().map(|(name, _)| name));
+        skipped
+    }
+
+    /// Set the value for a public property of this component, after converting the value to the
+    /// type of the property with the enabled conversions of `coercion`. This lets values from
+    /// untyped sources, such as JSON or the arguments of the command line, be set directly.
+    ///
+    /// ```
+    /// use slint_interpreter::{Coercion, ComponentCompiler, SharedString, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <int> count;
+    ///         property <color> accent;
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let coercion = Coercion::all();
+    /// let value = Value::from(SharedString::from("42"));
+    /// instance.set_property_with_coercion("count", value, coercion).unwrap();
+    /// assert_eq!(instance.get_property("count").unwrap(), Value::from(42));
+    /// let value = Value::from(SharedString::from("#ff0000"));
+    /// instance.set_property_with_coercion("accent", value, coercion).unwrap();
+    /// ```
+    pub fn set_property_with_coercion(
+        &self,
+        name: &str,
+        value: Value,
+        coercion: Coercion,
+    ) -> Result<(), SetPropertyError> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        let name = normalize_identifier(name);
+        let ty = comp
+            .description()
+            .properties()
+            .find(|(prop_name, _)| normalize_identifier(prop_name) == name)
+            .map(|(_, ty)| ty)
+            .ok_or(SetPropertyError::NoSuchProperty)?;
+        comp.description().set_property(comp.borrow(), &name, coercion.apply(value, &ty))
+    }
+
+    /// Animate a public property of this component from its current value to `value`.
+    ///
+    /// The property goes through the same animation system as the `animate` blocks of the
+    /// .slint file, over the given `duration` and with the given `easing` curve. This is
+    /// useful to run animations that are not declared in the .slint file. Like
+    /// [`Self::set_property`], this removes the binding of the property.
+    ///
+    /// Returns [`SetPropertyError::NotAnimatable`] if the type of the property cannot be animated.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler, EasingCurve, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <float> progress: 0;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// instance.animate_property(
+    ///     "progress",
+    ///     Value::from(1.),
+    ///     std::time::Duration::from_millis(300),
+    ///     EasingCurve::Linear,
+    /// ).unwrap();
+    /// // The animation has not started yet
+    /// assert_eq!(instance.get_property("progress").unwrap(), Value::from(0.));
+    /// ```
+    pub fn animate_property(
+        &self,
+        name: &str,
+        value: Value,
+        duration: core::time::Duration,
+        easing: EasingCurve,
+    ) -> Result<(), SetPropertyError> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        let animation = i_slint_core::items::PropertyAnimation {
+            duration: duration.as_millis() as _,
+            easing,
+            ..Default::default()
+        };
+        comp.description().animate_property(
+            comp.borrow(),
+            &normalize_identifier(name),
+            value,
+            animation,
+        )
+    }
+
     /// Set a handler for the callback with the given name. A callback with that
     /// name must be defined in the document otherwise an error will be returned.
     ///
@@ -814,23 +2053,272 @@ pub fn set_callback(
             .map_err(|()| SetCallbackError::NoSuchCallback)
     }
 
-    /// Call the given callback with the arguments
+    /// Set a handler for the callback with the given name, that is called with a strong
+    /// reference to the component of `instance` and the arguments of the callback.
+    ///
+    /// The handler only holds the weak reference, so it can use the component without keeping
+    /// it alive. When the component was dropped, the handler is not called, and the callback
+    /// returns the default value of its return type.
     ///
     /// ## Examples
-    /// See the documentation of [`Self::set_callback`] for an example
-    pub fn invoke_callback(
-        &self,
-        name: &str,
-        args: &[Value],
-    ) -> Result<Value, InvokeCallbackError> {
-        generativity::make_guard!(guard);
-        let comp = self.inner.unerase(guard);
-        comp.description()
-            .invoke_callback(comp.borrow(), &normalize_identifier(name), args)
-            .map_err(|()| InvokeCallbackError::NoSuchCallback)
-    }
-
-    /// Return the value for a property within an exported global singleton used by this component.
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler, Value, ComponentHandle};
+    /// use core::convert::TryInto;
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         callback foo(int) -> int;
+    ///         property <int> my_prop: 12;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    ///
+    /// instance.set_callback_weak("foo", instance.as_weak(), |instance, args| {
+    ///     let arg: u32 = args[0].clone().try_into().unwrap();
+    ///     let my_prop: u32 = instance.get_property("my_prop").unwrap().try_into().unwrap();
+    ///     Value::from(arg + my_prop)
+    /// }).unwrap();
+    ///
+    /// let res = instance.invoke_callback("foo", &[Value::from(500)]).unwrap();
+    /// assert_eq!(res, Value::from(500+12));
+    /// ```
+    pub fn set_callback_weak<T: ComponentHandle + 'static>(
+        &self,
+        name: &str,
+        instance: Weak<T>,
+        callback: impl Fn(T, &[Value]) -> Value + 'static,
+    ) -> Result<(), SetCallbackError> {
+        let name = normalize_identifier(name);
+        let callback_type = self
+            .definition()
+            .properties_and_callbacks()
+            .find_map(|(n, ty)| (normalize_identifier(&n) == name).then(|| ty))
+            .ok_or(SetCallbackError::NoSuchCallback)?;
+        self.set_callback(&name, weak_callback_handler(&callback_type, instance, callback))
+    }
+
+    /// Registers a handler that is called at the stages of the life of this instance: when its
+    /// window is shown for the first time, every time it is shown or hidden, and before the
+    /// instance is destroyed.
+    ///
+    /// If the window is already shown, the handler is called with [`LifecycleEvent::Shown`]
+    /// right away, so that it can start what is tied to the visibility of the component.
+    ///
+    /// Note: Since the [`ComponentInstance`] holds the handler, the handler should only capture
+    /// a weak reference to the instance.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, LifecycleEvent};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <int> my_prop: 12;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    ///
+    /// instance.on_lifecycle_event(|event| match event {
+    ///     LifecycleEvent::Shown => println!("start the timers"),
+    ///     LifecycleEvent::Hidden => println!("stop the timers"),
+    ///     _ => (),
+    /// });
+    /// ```
+    pub fn on_lifecycle_event(&self, handler: impl Fn(LifecycleEvent) + 'static) {
+        use i_slint_core::window::WindowHandleAccess;
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        let instance_ref = comp.borrow_instance();
+        let window_visible = comp.window().window_handle().is_visible();
+        instance_ref
+            .component_type
+            .extra_data_offset
+            .apply(instance_ref.as_ref())
+            .lifecycle_handlers
+            .add(Rc::new(handler), window_visible);
+    }
+
+    /// Call the given callback with the arguments
+    ///
+    /// ## Examples
+    /// See the documentation of [`Self::set_callback`] for an example
+    pub fn invoke_callback(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Value, InvokeCallbackError> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        comp.description()
+            .invoke_callback(comp.borrow(), &normalize_identifier(name), args)
+            .map_err(|()| InvokeCallbackError::NoSuchCallback)
+    }
+
+    /// Call the function declared in the .slint file with the given name, and return its result
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <int> offset: 100;
+    ///         function add(a: int, b: int) -> int { a + b + offset }
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default())).unwrap();
+    /// assert_eq!(definition.functions().collect::<Vec<_>>(), vec!["add".to_string()]);
+    /// let instance = definition.create();
+    /// let res = instance.invoke("add", &[Value::from(1), Value::from(2)]).unwrap();
+    /// assert_eq!(res, Value::from(103));
+    /// ```
+    pub fn invoke(&self, name: &str, args: &[Value]) -> Result<Value, InvokeError> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        comp.description()
+            .invoke_function(comp.borrow(), &normalize_identifier(name), args)
+            .map_err(|()| InvokeError::NoSuchFunction)
+    }
+
+    /// Create an instance of `definition` and insert it at `index` in the children of the
+    /// `ComponentContainer` element with the id `element`, then return the new child.
+    ///
+    /// The element must be declared in the exported component. The children are shown above the
+    /// component created by the container's `component-factory`, and the ones with a higher index
+    /// are shown above the others. Like with [`ComponentDefinition::create_embedded`], a child
+    /// fills the container unless it sets its own `width` or `height`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use slint_interpreter::*;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let badge = spin_on::spin_on(compiler.build_from_source(
+    ///     "export Badge := Rectangle { width: 10px; height: 10px; background: red; }".into(),
+    ///     Default::default(),
+    /// ))
+    /// .unwrap();
+    /// let host = spin_on::spin_on(compiler.build_from_source(
+    ///     "export Host := Window { badges := ComponentContainer {} }".into(),
+    ///     Default::default(),
+    /// ))
+    /// .unwrap();
+    ///
+    /// let instance = host.create();
+    /// let child = instance.insert_child("badges", 0, &badge).unwrap();
+    /// instance.remove_child("badges", &child).unwrap();
+    /// assert_eq!(instance.remove_child("badges", &child), Err(ChildError::NotAChild));
+    /// ```
+    pub fn insert_child(
+        &self,
+        element: &str,
+        index: usize,
+        definition: &ComponentDefinition,
+    ) -> Result<ComponentInstance, ChildError> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        generativity::make_guard!(child_guard);
+        let child = definition.inner.unerase(child_guard).clone();
+        let inner = comp.description().insert_child(
+            comp.borrow(),
+            &normalize_identifier(element),
+            index,
+            child,
+        )?;
+        Ok(ComponentInstance { inner })
+    }
+
+    /// Remove a child that was inserted with [`Self::insert_child`] in the `ComponentContainer`
+    /// element with the id `element`. The child is no longer shown, and is destroyed when the
+    /// last [`ComponentInstance`] referencing it is dropped.
+    pub fn remove_child(&self, element: &str, child: &ComponentInstance) -> Result<(), ChildError> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        comp.description().remove_child(comp.borrow(), &normalize_identifier(element), &child.inner)
+    }
+
+    /// Shows or hides the element with the id `element`, which must be one of the
+    /// [`ComponentCompiler::set_overridable_elements`]. The element is only visible if its
+    /// `visible` property is also true.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentCompiler, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         admin-panel := Rectangle {
+    ///             delete-area := TouchArea { }
+    ///         }
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// compiler.set_overridable_elements(vec!["admin-panel".into(), "delete-area".into()]);
+    /// let definition = spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    ///
+    /// instance.set_element_visible("admin-panel", false).unwrap();
+    /// instance.set_element_opacity("admin-panel", 0.5).unwrap();
+    /// instance.set_element_enabled("delete-area", false).unwrap();
+    /// // A Rectangle has no `enabled` property
+    /// assert!(instance.set_element_enabled("admin-panel", false).is_err());
+    /// ```
+    pub fn set_element_visible(
+        &self,
+        element: &str,
+        visible: bool,
+    ) -> Result<(), SetPropertyError> {
+        self.set_element_override(element, "visible", Value::Bool(visible))
+    }
+
+    /// Enables or disables the element with the id `element`, which must be one of the
+    /// [`ComponentCompiler::set_overridable_elements`] and have an `enabled` property. The
+    /// element is only enabled if its `enabled` property is also true.
+    ///
+    /// See [`Self::set_element_visible`] for an example
+    pub fn set_element_enabled(
+        &self,
+        element: &str,
+        enabled: bool,
+    ) -> Result<(), SetPropertyError> {
+        self.set_element_override(element, "enabled", Value::Bool(enabled))
+    }
+
+    /// Sets the opacity of the element with the id `element`, which must be one of the
+    /// [`ComponentCompiler::set_overridable_elements`]. It is multiplied by the `opacity`
+    /// property of the element.
+    ///
+    /// See [`Self::set_element_visible`] for an example
+    pub fn set_element_opacity(&self, element: &str, opacity: f32) -> Result<(), SetPropertyError> {
+        self.set_element_override(element, "opacity", Value::Number(opacity as f64))
+    }
+
+    fn set_element_override(
+        &self,
+        element: &str,
+        property: &str,
+        value: Value,
+    ) -> Result<(), SetPropertyError> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        let name = i_slint_compiler::object_tree::element_override_property_name(
+            &normalize_identifier(element),
+            property,
+        );
+        let description = comp.description();
+        if !description.custom_properties.contains_key(&name) {
+            return Err(SetPropertyError::NoSuchProperty);
+        }
+        description.set_property(comp.borrow(), &name, value)
+    }
+
+    /// Return the value for a property within an exported global singleton used by this component.
     ///
     /// The `global` parameter is the exported name of the global singleton. The `property` argument
     /// is the name of the property
@@ -934,6 +2422,36 @@ pub fn set_global_callback(
             .map_err(|()| SetCallbackError::NoSuchCallback)
     }
 
+    /// Set a handler for the callback in the exported global singleton, that is called with a
+    /// strong reference to the component of `instance` and the arguments of the callback.
+    ///
+    /// Like with [`Self::set_callback_weak`], the handler is not called when the component was
+    /// dropped, and the callback returns the default value of its return type.
+    pub fn set_global_callback_weak<T: ComponentHandle + 'static>(
+        &self,
+        global: &str,
+        name: &str,
+        instance: Weak<T>,
+        callback: impl Fn(T, &[Value]) -> Value + 'static,
+    ) -> Result<(), SetCallbackError> {
+        let name = normalize_identifier(name);
+        let callback_type = {
+            generativity::make_guard!(guard);
+            let comp = self.inner.unerase(guard);
+            comp.description()
+                .global_properties(global)
+                .and_then(|mut properties| {
+                    properties.find_map(|(n, ty)| (normalize_identifier(&n) == name).then(|| ty))
+                })
+                .ok_or(SetCallbackError::NoSuchCallback)?
+        };
+        self.set_global_callback(
+            global,
+            &name,
+            weak_callback_handler(&callback_type, instance, callback),
+        )
+    }
+
     /// Call the given callback within a global singleton with the arguments
     ///
     /// ## Examples
@@ -953,6 +2471,254 @@ pub fn invoke_global_callback(
             .invoke_callback(&normalize_identifier(callback_name), args)
             .map_err(|()| InvokeCallbackError::NoSuchCallback)
     }
+
+    /// Returns the tree of accessible elements of the window of this component.
+    ///
+    /// See [`Window::accessibility_tree`].
+    pub fn accessibility_tree(&self) -> Vec<AccessibilityNode> {
+        self.window().accessibility_tree()
+    }
+
+    /// Returns the first accessible element with the given role and label.
+    ///
+    /// ## Examples
+    /// ```
+    /// use slint_interpreter::{AccessibleRole, ComponentDefinition, ComponentCompiler, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         width: 100px;
+    ///         height: 100px;
+    ///         property <int> clicks;
+    ///         Rectangle {
+    ///             accessible-role: button;
+    ///             accessible-label: "Increment";
+    ///             accessible-action-default => { clicks += 1; }
+    ///         }
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let button = instance.find_accessible_element(AccessibleRole::button, "Increment").unwrap();
+    /// instance.activate_accessible_element(&button);
+    /// assert_eq!(instance.get_property("clicks").unwrap(), Value::from(1));
+    /// ```
+    pub fn find_accessible_element(
+        &self,
+        role: AccessibleRole,
+        label: &str,
+    ) -> Option<AccessibilityNode> {
+        self.window().find_accessible_element(role, label)
+    }
+
+    /// Activates an element of the accessibility tree, like a click on it would.
+    ///
+    /// See [`Window::activate_accessible_element`].
+    pub fn activate_accessible_element(&self, element: &AccessibilityNode) {
+        self.window().activate_accessible_element(element)
+    }
+
+    /// Returns the elements whose geometry contains the position, in logical coordinates relative
+    /// to the window, from the topmost element to the root element.
+    ///
+    /// This is meant for the design tools, such as the preview of the language server, that show
+    /// the source code of the element under the mouse cursor. The elements that are optimized away
+    /// by the compiler, such as the layouts, are not returned.
+    ///
+    /// ## Examples
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         width: 100px;
+    ///         height: 100px;
+    ///         Rectangle { x: 10px; y: 10px; width: 20px; height: 20px; background: red; }
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), "main.slint".into()));
+    /// let instance = definition.unwrap().create();
+    /// let elements = instance.elements_at_position(15., 15.);
+    /// assert_eq!(elements.len(), 2);
+    /// assert!(code[elements[0].offset..].starts_with("Rectangle"));
+    /// assert!(elements[0].path.ends_with("main.slint"));
+    /// assert_eq!(instance.elements_at_position(50., 50.).len(), 1);
+    /// ```
+    pub fn elements_at_position(&self, x: f32, y: f32) -> Vec<ElementPosition> {
+        crate::dynamic_component::elements_at_position(
+            &vtable::VRc::into_dyn(self.inner.clone()),
+            i_slint_core::graphics::Point::new(x, y),
+        )
+    }
+
+    /// Returns the elements that receive the focus when pressing the Tab key, in the order in
+    /// which the focus moves through them. While a popup is shown, only its elements are returned.
+    ///
+    /// The FocusScopes that the widgets use internally, such as the one of a StandardListView,
+    /// are not part of the tab order, only the widgets themselves.
+    ///
+    /// ## Examples
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         second := TextInput { y: 20px; height: 20px; }
+    ///         first := TextInput { height: 20px; tab-index: 1; }
+    ///         FocusScope { tab-index: -1; }
+    ///     }
+    /// "#;
+    /// let mut compiler = ComponentCompiler::default();
+    /// let definition = spin_on::spin_on(
+    ///     compiler.build_from_source(code.into(), "main.slint".into()));
+    /// let instance = definition.unwrap().create();
+    /// let tab_order = instance.tab_order();
+    /// assert_eq!(tab_order.len(), 2);
+    /// assert!(code[tab_order[0].offset..].starts_with("TextInput { height"));
+    /// assert_eq!(tab_order[1].geometry.origin.y, 20.);
+    /// ```
+    pub fn tab_order(&self) -> Vec<ElementPosition> {
+        use i_slint_core::window::WindowHandleAccess;
+        crate::dynamic_component::tab_order(self.window().window_handle())
+    }
+
+    /// Returns the preferred size of the component, in logical pixels, as computed from the
+    /// layout constraints of its elements. This is the size that fits its content, for example
+    /// to size a dialog before showing it.
+    ///
+    /// ## Examples
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler};
+    /// let code = r#"
+    ///     MyDialog := Window {
+    ///         VerticalLayout {
+    ///             Rectangle { min-width: 50px; preferred-width: 200px; min-height: 20px; }
+    ///         }
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// assert_eq!(instance.preferred_size().width, 200.);
+    /// assert_eq!(instance.min_size().width, 50.);
+    /// assert_eq!(instance.min_size().height, 20.);
+    /// ```
+    pub fn preferred_size(&self) -> i_slint_core::graphics::Size {
+        self.layout_size(|info| info.preferred)
+    }
+
+    /// Returns the minimum size of the component, in logical pixels, as computed from the layout
+    /// constraints of its elements.
+    ///
+    /// See [`Self::preferred_size`] for an example
+    pub fn min_size(&self) -> i_slint_core::graphics::Size {
+        self.layout_size(|info| info.min)
+    }
+
+    /// Returns the maximum size of the component, in logical pixels, as computed from the layout
+    /// constraints of its elements. The dimensions that are not bounded are `f32::MAX`.
+    pub fn max_size(&self) -> i_slint_core::graphics::Size {
+        self.layout_size(|info| info.max)
+    }
+
+    /// Renders the element with this id and its children on a transparent background, in an
+    /// image of the size of the element in physical pixels, for example to export a chart or
+    /// a part of a form.
+    ///
+    /// The element is searched in the main component and in its `for` and `if`, and the first
+    /// one with this id is rendered. The window must be shown, as the image is rendered by the
+    /// renderer of the window.
+    ///
+    /// Returns None if there is no element with this id, or if the backend can't render it in
+    /// an image.
+    pub fn render_element_to_image(
+        &self,
+        element_id: &str,
+    ) -> Option<i_slint_core::graphics::SharedPixelBuffer<i_slint_core::graphics::Rgba8Pixel>> {
+        use i_slint_core::window::WindowHandleAccess;
+        let component = vtable::VRc::into_dyn(self.inner.clone());
+        let item = crate::dynamic_component::find_element_item(&component, element_id)?;
+        self.window().window_handle().render_item_to_image(&item)
+    }
+
+    /// Returns the position, relative to the element, of the cursor before the character at
+    /// the byte offset in the text of the `Text` or `TextInput` element with this id, as laid
+    /// out by the renderer, for example to place a selection handle or an annotation there.
+    ///
+    /// Returns None if there is no `Text` or `TextInput` element with this id, or if the
+    /// backend can't lay out the text of a `Text` element.
+    pub fn text_position_for_byte_offset(
+        &self,
+        element_id: &str,
+        byte_offset: usize,
+    ) -> Option<i_slint_core::graphics::Point> {
+        use i_slint_core::items::{ItemRef, Text, TextInput};
+        use i_slint_core::window::WindowHandleAccess;
+        let component = vtable::VRc::into_dyn(self.inner.clone());
+        let item = crate::dynamic_component::find_element_item(&component, element_id)?;
+        let window = self.window().window_handle();
+        let item = item.borrow();
+        if let Some(text_input) = ItemRef::downcast_pin::<TextInput>(item) {
+            Some(window.text_input_position_for_byte_offset(text_input, byte_offset))
+        } else {
+            window.text_position_for_byte_offset(ItemRef::downcast_pin::<Text>(item)?, byte_offset)
+        }
+    }
+
+    /// Returns the byte offset in the text of the `Text` or `TextInput` element with this id of
+    /// the character nearest to the position, relative to the element. This is the opposite of
+    /// [`Self::text_position_for_byte_offset`].
+    ///
+    /// Returns None if there is no `Text` or `TextInput` element with this id, or if the
+    /// backend can't lay out the text of a `Text` element.
+    pub fn text_byte_offset_for_position(&self, element_id: &str, x: f32, y: f32) -> Option<usize> {
+        use i_slint_core::items::{ItemRef, Text, TextInput};
+        use i_slint_core::window::WindowHandleAccess;
+        let component = vtable::VRc::into_dyn(self.inner.clone());
+        let item = crate::dynamic_component::find_element_item(&component, element_id)?;
+        let window = self.window().window_handle();
+        let item = item.borrow();
+        let position = i_slint_core::graphics::Point::new(x, y);
+        if let Some(text_input) = ItemRef::downcast_pin::<TextInput>(item) {
+            Some(window.text_input_byte_offset_for_position(text_input, position))
+        } else {
+            window.text_byte_offset_for_position(ItemRef::downcast_pin::<Text>(item)?, position)
+        }
+    }
+
+    fn layout_size(
+        &self,
+        dimension: impl Fn(&i_slint_core::layout::LayoutInfo) -> f32,
+    ) -> i_slint_core::graphics::Size {
+        use i_slint_core::layout::Orientation;
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        let component = comp.borrow();
+        i_slint_core::graphics::Size::new(
+            dimension(&component.as_ref().layout_info(Orientation::Horizontal)),
+            dimension(&component.as_ref().layout_info(Orientation::Vertical)),
+        )
+    }
+}
+
+/// Wraps the handler of a callback of this type, so that it is called with the upgraded
+/// `instance`, or returns the default value when the component was dropped
+fn weak_callback_handler<T: ComponentHandle + 'static>(
+    callback_type: &LangType,
+    instance: Weak<T>,
+    callback: impl Fn(T, &[Value]) -> Value + 'static,
+) -> impl Fn(&[Value]) -> Value + 'static {
+    let default_value = match callback_type {
+        LangType::Callback { return_type: Some(return_type), .. } => {
+            crate::eval::default_value_for_type(return_type)
+        }
+        _ => Value::Void,
+    };
+    move |args| match instance.upgrade() {
+        Some(instance) => callback(instance, args),
+        None => default_value.clone(),
+    }
 }
 
 impl ComponentHandle for ComponentInstance {
@@ -1033,34 +2799,423 @@ pub enum SetPropertyError {
     /// The property exist but does not have a type matching the dynamic value
     #[error("wrong type")]
     WrongType,
+    /// The property exist but its type cannot be animated
+    #[error("property cannot be animated")]
+    NotAnimatable,
 }
 
-/// Error returned by [`ComponentInstance::set_callback`]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
-#[non_exhaustive]
-pub enum SetCallbackError {
-    /// There is no callback with the given name
-    #[error("no such callback")]
-    NoSuchCallback,
+/// Replace the models in the value, and in its structs and maps, by models with a copy of their
+/// rows
+fn copy_models(value: Value) -> Value {
+    match value {
+        Value::Model(model) => Value::Model(ModelRc::new(i_slint_core::model::VecModel::from(
+            model.iter().map(copy_models).collect::<Vec<_>>(),
+        ))),
+        Value::Struct(st) => {
+            Value::Struct(st.0.into_iter().map(|(k, v)| (k, copy_models(v))).collect())
+        }
+        Value::Map(map) => {
+            Value::Map(map.0.into_iter().map(|(k, v)| (k, copy_models(v))).collect())
+        }
+        value => value,
+    }
 }
 
-/// Error returned by [`ComponentInstance::invoke_callback`]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
-#[non_exhaustive]
-pub enum InvokeCallbackError {
-    /// There is no callback with the given name
-    #[error("no such callback")]
-    NoSuchCallback,
+/// The property changes of a [`ComponentInstance::batch`], which are applied together at the end
+/// of the batch
+pub struct PropertyTransaction<'a> {
+    instance: &'a ComponentInstance,
+    /// The changes, with the name of the global for the properties of globals
+    changes: Vec<(Option<String>, String, Value)>,
+    /// The first error of the changes that were added, which cancels the batch
+    error: Option<SetPropertyError>,
 }
 
-/// Enters the main event loop. This is necessary in order to receive
-/// events from the windowing system in order to render to the screen
+impl<'a> PropertyTransaction<'a> {
+    fn new(instance: &'a ComponentInstance) -> Self {
+        Self { instance, changes: Vec::new(), error: None }
+    }
+
+    /// Set the value for a public property of the component at the end of the batch. Returns an
+    /// error right away if there is no such property, or if the value doesn't have its type. The
+    /// batch is then cancelled.
+    pub fn set(&mut self, name: &str, value: Value) -> Result<(), SetPropertyError> {
+        let name = normalize_identifier(name);
+        let checked = self.check_property(&name, &value);
+        self.add_change(None, name.into_owned(), value, checked)
+    }
+
+    /// Set the value for a property of an exported global singleton at the end of the batch.
+    /// Returns an error right away if there is no such property, or if the value doesn't have its
+    /// type. The batch is then cancelled.
+    pub fn set_global(
+        &mut self,
+        global: &str,
+        name: &str,
+        value: Value,
+    ) -> Result<(), SetPropertyError> {
+        let name = normalize_identifier(name);
+        let checked = self.check_global_property(global, &name, &value);
+        self.add_change(Some(global.into()), name.into_owned(), value, checked)
+    }
+
+    fn add_change(
+        &mut self,
+        global: Option<String>,
+        name: String,
+        value: Value,
+        checked: Result<(), SetPropertyError>,
+    ) -> Result<(), SetPropertyError> {
+        match checked {
+            Ok(()) => self.changes.push((global, name, value)),
+            Err(error) => {
+                self.error.get_or_insert(error);
+            }
+        }
+        checked
+    }
+
+    fn check_property(&self, name: &str, value: &Value) -> Result<(), SetPropertyError> {
+        generativity::make_guard!(guard);
+        let comp = self.instance.inner.unerase(guard);
+        let ty = comp
+            .description()
+            .properties()
+            .find(|(prop_name, _)| normalize_identifier(prop_name) == name)
+            .map(|(_, ty)| ty)
+            .ok_or(SetPropertyError::NoSuchProperty)?;
+        Self::check_type(value, &ty)
+    }
+
+    fn check_global_property(
+        &self,
+        global: &str,
+        name: &str,
+        value: &Value,
+    ) -> Result<(), SetPropertyError> {
+        generativity::make_guard!(guard);
+        let comp = self.instance.inner.unerase(guard);
+        let ty = comp
+            .description()
+            .global_properties(global)
+            .and_then(|mut properties| {
+                properties.find(|(prop_name, _)| normalize_identifier(prop_name) == name)
+            })
+            .map(|(_, ty)| ty)
+            .ok_or(SetPropertyError::NoSuchProperty)?;
+        Self::check_type(value, &ty)
+    }
+
+    /// Set the properties to the values of the changes. They were checked when they were added,
+    /// so this only fails if the instance doesn't accept a value that has the type of its
+    /// property. Returns the names of the properties that could not be set, with the errors.
+    fn apply(self) -> Vec<(String, SetPropertyError)> {
+        let instance = self.instance;
+        self.changes
+            .into_iter()
+            .filter_map(|(global, name, value)| {
+                let result = match &global {
+                    Some(global) => instance.set_global_property(global, &name, value),
+                    None => instance.set_property(&name, value),
+                };
+                result.err().map(|error| (name, error))
+            })
+            .collect()
+    }
+
+    fn check_type(value: &Value, ty: &LangType) -> Result<(), SetPropertyError> {
+        if !ty.is_property_type() {
+            // Callbacks and functions
+            Err(SetPropertyError::NoSuchProperty)
+        } else if crate::eval::check_value_type(value, ty) {
+            Ok(())
+        } else {
+            Err(SetPropertyError::WrongType)
+        }
+    }
+}
+
+/// The conversions that [`ComponentInstance::set_property_with_coercion`] applies to a value whose
+/// type doesn't match the type of the property. They also apply to the fields of the structs and
+/// to the rows of the models and the maps. By default, no conversion is enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Coercion {
+    /// Parse the strings, such as `"42"` or `"1.5"`, for the properties of a numeric type, such
+    /// as `int`, `float`, or `length`. The numbers are rounded for the `int` properties.
+    pub parse_numbers: bool,
+    /// Parse the strings with the `#rgb`, `#rgba`, `#rrggbb`, or `#rrggbbaa` notation for the
+    /// `color` and `brush` properties
+    pub parse_colors: bool,
+    /// Parse `"true"` and `"false"` for the `bool` properties, and convert the numbers to `true`
+    /// if they are not 0
+    pub parse_bools: bool,
+    /// Format the numbers and the booleans for the `string` properties
+    pub format_strings: bool,
+}
+
+impl Coercion {
+    /// All the conversions are enabled
+    pub fn all() -> Self {
+        Self { parse_numbers: true, parse_colors: true, parse_bools: true, format_strings: true }
+    }
+
+    /// Convert the value to the type, or return it unchanged if no enabled conversion applies
+    fn apply(&self, value: Value, ty: &LangType) -> Value {
+        match (value, ty) {
+            (
+                Value::String(s),
+                LangType::Float32
+                | LangType::Int32
+                | LangType::Duration
+                | LangType::Angle
+                | LangType::PhysicalLength
+                | LangType::LogicalLength
+                | LangType::Percent,
+            ) if self.parse_numbers => match s.trim().parse::<f64>() {
+                Ok(n) if *ty == LangType::Int32 => Value::Number(n.round()),
+                Ok(n) => Value::Number(n),
+                Err(_) => Value::String(s),
+            },
+            (Value::Number(n), LangType::Int32) if self.parse_numbers => Value::Number(n.round()),
+            (Value::String(s), LangType::Color | LangType::Brush) if self.parse_colors => {
+                match parse_color(s.trim()) {
+                    Some(color) => Value::Brush(Brush::SolidColor(color)),
+                    None => Value::String(s),
+                }
+            }
+            (Value::String(s), LangType::Bool) if self.parse_bools => match s.trim() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => Value::String(s),
+            },
+            (Value::Number(n), LangType::Bool) if self.parse_bools => Value::Bool(n != 0.),
+            (Value::Number(n), LangType::String) if self.format_strings => {
+                Value::String(n.to_string().into())
+            }
+            (Value::Bool(b), LangType::String) if self.format_strings => {
+                Value::String(b.to_string().into())
+            }
+            (Value::Struct(st), LangType::Struct { fields, .. }) => Value::Struct(
+                st.0.into_iter()
+                    .map(|(name, v)| match fields.get(&name) {
+                        Some(field_ty) => {
+                            let v = self.apply(v, field_ty);
+                            (name, v)
+                        }
+                        None => (name, v),
+                    })
+                    .collect(),
+            ),
+            (Value::Map(map), LangType::Map(inner)) => {
+                Value::Map(map.0.into_iter().map(|(k, v)| (k, self.apply(v, inner))).collect())
+            }
+            (Value::Model(model), LangType::Array(inner)) => {
+                let rows = model.iter().collect::<Vec<_>>();
+                let converted =
+                    rows.iter().map(|v| self.apply(v.clone(), inner)).collect::<Vec<_>>();
+                // Keep the model, which may be shared with the application, if no row changed
+                if converted == rows {
+                    Value::Model(model)
+                } else {
+                    Value::Model(ModelRc::new(i_slint_core::model::VecModel::from(converted)))
+                }
+            }
+            (value, _) => value,
+        }
+    }
+}
+
+/// Parse a color in the `#rgb`, `#rgba`, `#rrggbb`, or `#rrggbbaa` notation
+pub(crate) fn parse_color(s: &str) -> Option<i_slint_core::Color> {
+    let hex = s.strip_prefix('#')?;
+    let digits =
+        hex.chars().map(|c| c.to_digit(16).map(|d| d as u8)).collect::<Option<Vec<_>>>()?;
+    let components: Vec<u8> = match digits.len() {
+        3 | 4 => digits.iter().map(|d| d * 17).collect(),
+        6 | 8 => digits.chunks(2).map(|c| c[0] * 16 + c[1]).collect(),
+        _ => return None,
+    };
+    let alpha = components.get(3).copied().unwrap_or(0xff);
+    Some(i_slint_core::Color::from_argb_u8(alpha, components[0], components[1], components[2]))
+}
+
+/// The stages of the life of a [`ComponentInstance`], that are notified to the handlers
+/// registered with [`ComponentInstance::on_lifecycle_event`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LifecycleEvent {
+    /// The instance was created and its window is shown for the first time. This is notified
+    /// once, before the first [`LifecycleEvent::Shown`].
+    Initialized,
+    /// The window of the instance was shown
+    Shown,
+    /// The window of the instance was hidden
+    Hidden,
+    /// The last reference to the instance was dropped, and it is about to be destroyed
+    Destroyed,
+}
+
+/// Error returned by [`ComponentInstance::set_callback`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SetCallbackError {
+    /// There is no callback with the given name
+    #[error("no such callback")]
+    NoSuchCallback,
+}
+
+/// Error returned by [`ComponentDefinition::set_global_implementation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SetGlobalImplementationError {
+    /// There is no exported global singleton with the given name
+    #[error("no such global")]
+    NoSuchGlobal,
+    /// The implementation implements a property or a callback that it cannot implement
+    #[error("property or callback cannot be implemented")]
+    CannotImplement,
+}
+
+/// Error returned by [`ComponentInstance::invoke_callback`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum InvokeCallbackError {
+    /// There is no callback with the given name
+    #[error("no such callback")]
+    NoSuchCallback,
+}
+
+/// Error returned by [`ComponentInstance::invoke`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum InvokeError {
+    /// There is no function with the given name
+    #[error("no such function")]
+    NoSuchFunction,
+}
+
+/// Error returned by [`ComponentInstance::insert_child`] and [`ComponentInstance::remove_child`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ChildError {
+    /// There is no `ComponentContainer` with the given id in the exported component
+    #[error("no such element")]
+    NoSuchElement,
+    /// The index is greater than the number of children
+    #[error("index out of bounds")]
+    IndexOutOfBounds,
+    /// The component was not inserted in this element
+    #[error("not a child of this element")]
+    NotAChild,
+}
+
+/// Enters the main event loop. This is necessary in order to receive
+/// events from the windowing system in order to render to the screen
 /// and react to user input.
 pub fn run_event_loop() {
     i_slint_backend_selector::backend()
         .run_event_loop(i_slint_core::backend::EventLoopQuitBehavior::QuitOnLastWindowClosed);
 }
 
+/// Runs the event loop in a new thread, for the applications whose main thread does other work.
+///
+/// `setup` is called in the new thread before entering the event loop. It must create and show
+/// the components there, since they cannot be sent to another thread, and return what must stay
+/// alive while the event loop runs. The other threads then communicate with the components with
+/// [`invoke_from_event_loop()`]. The thread ends when the last window is closed.
+///
+/// This is supported with the GL backend on Windows, and on Linux and the BSDs with X11 or
+/// Wayland. An error is returned on macOS, iOS, and the web, where the event loop must run in
+/// the main thread, and with the backends that do not support it. In that case, the components
+/// must be created and the event loop run in the main thread, which can give work to other threads.
+///
+/// ```no_run
+/// # use slint_interpreter::*;
+/// let event_loop = spawn_event_loop(|| {
+///     let mut compiler = ComponentCompiler::default();
+///     let definition = spin_on::spin_on(
+///         compiler.build_from_source("export Demo := Window {}".into(), Default::default()),
+///     );
+///     let instance = definition.unwrap().create();
+///     instance.show();
+///     instance
+/// })
+/// .unwrap();
+/// // ... do other work in the main thread
+/// event_loop.join().unwrap();
+/// ```
+pub fn spawn_event_loop<T: 'static>(
+    setup: impl FnOnce() -> T + Send + 'static,
+) -> Result<std::thread::JoinHandle<()>, EventLoopThreadError> {
+    let backend = i_slint_backend_selector::backend();
+    backend.allow_event_loop_in_any_thread()?;
+    Ok(std::thread::spawn(move || {
+        let _keep_alive = setup();
+        backend
+            .run_event_loop(i_slint_core::backend::EventLoopQuitBehavior::QuitOnLastWindowClosed);
+    }))
+}
+
+/// The implementation in Rust of a global singleton declared in a `.slint` file, such as a
+/// service of the application that many components use. It is registered once with
+/// [`ComponentDefinition::set_global_implementation()`], instead of setting the properties and the
+/// callback handlers of the global of each component instance.
+pub trait GlobalImplementation {
+    /// Returns true if this object computes the property, or handles the callback, with this
+    /// name. The other properties and callbacks keep the bindings and handlers of the `.slint`
+    /// file.
+    fn implements(&self, name: &str) -> bool;
+
+    /// Computes the value of the property with this name. This is evaluated like a binding: it
+    /// is called when the property is read and is not up to date, and the property is dirty again
+    /// when the properties that this function reads change.
+    fn property(&self, name: &str) -> Value;
+
+    /// Handles the invocation of the callback with this name, and returns its return value.
+    fn invoke_callback(&self, name: &str, args: &[Value]) -> Value;
+}
+
+/// Sets the menus of the global menu bar of the application, replacing the previous ones.
+/// `activated` is called with the [`MenuBarEntry`] that the user activated.
+///
+/// On macOS, the menus are shown after the application menu, which has the standard About,
+/// Hide, and Quit entries. Quitting from that menu quits the event loop. Other platforms don't
+/// have a global menu bar and ignore this call.
+pub fn set_menu_bar(menus: Vec<MenuBarEntry>, activated: impl Fn(&MenuBarEntry) + 'static) {
+    i_slint_backend_selector::backend().set_menu_bar(menus, Box::new(activated));
+}
+
+/// Shows `label` in a badge on the icon of the application in the dock, or removes the badge
+/// if `label` is `None`. This is only implemented on macOS.
+pub fn set_application_badge(label: Option<&str>) {
+    i_slint_backend_selector::backend().set_application_badge(label);
+}
+
+/// Shows a progress bar on the icon of the application in the dock, with a `progress` between
+/// 0 and 1, or removes it if `progress` is `None`. This is only implemented on macOS.
+pub fn set_application_progress(progress: Option<f32>) {
+    i_slint_backend_selector::backend().set_application_progress(progress);
+}
+
+/// Change the speed of all the animations of the current thread, without changing the .slint documents.
+///
+/// The durations and delays of the animations are divided by `factor`: use a factor lower than 1 to
+/// slow down the animations, for example when debugging them. A factor that is not greater than 0
+/// completes the animations immediately. The default is 1.
+pub fn set_animation_speed_factor(factor: f32) {
+    i_slint_core::animations::CURRENT_ANIMATION_DRIVER
+        .with(|driver| driver.set_speed_factor(factor));
+}
+
+/// Enable or disable all the animations of the current thread. When disabled, the animated
+/// properties are set to their target value right away, which is useful in tests or for
+/// users who prefer reduced motion. The animations are enabled by default.
+pub fn set_animations_enabled(enabled: bool) {
+    i_slint_core::animations::CURRENT_ANIMATION_DRIVER
+        .with(|driver| driver.set_animations_enabled(enabled));
+}
+
 /// This module contains a few function use by tests
 pub mod testing {
     use super::ComponentHandle;
@@ -1075,6 +3230,18 @@ pub fn send_mouse_click(comp: &super::ComponentInstance, x: f32, y: f32) {
             comp.window().window_handle(),
         );
     }
+    /// Wrapper around [`i_slint_core::tests::slint_send_mouse_press`]
+    pub fn send_mouse_press(comp: &super::ComponentInstance, x: f32, y: f32) {
+        i_slint_core::tests::slint_send_mouse_press(x, y, comp.window().window_handle());
+    }
+    /// Wrapper around [`i_slint_core::tests::slint_send_mouse_move`]
+    pub fn send_mouse_move(comp: &super::ComponentInstance, x: f32, y: f32) {
+        i_slint_core::tests::slint_send_mouse_move(x, y, comp.window().window_handle());
+    }
+    /// Wrapper around [`i_slint_core::tests::slint_send_mouse_release`]
+    pub fn send_mouse_release(comp: &super::ComponentInstance, x: f32, y: f32) {
+        i_slint_core::tests::slint_send_mouse_release(x, y, comp.window().window_handle());
+    }
     /// Wrapper around [`i_slint_core::tests::send_keyboard_string_sequence`]
     pub fn send_keyboard_string_sequence(
         comp: &super::ComponentInstance,
@@ -1089,65 +3256,476 @@ pub fn send_keyboard_string_sequence(
 }
 
 #[test]
-fn component_definition_properties() {
+fn component_definition_properties() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let comp_def = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Rectangle {
+        property <string> test;
+        property <int> underscores-and-dashes_preserved: 44;
+        callback hello;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let props = comp_def.properties().collect::<Vec<(_, _)>>();
+
+    assert_eq!(props.len(), 2);
+    assert_eq!(props[0].0, "test");
+    assert_eq!(props[0].1, ValueType::String);
+    assert_eq!(props[1].0, "underscores-and-dashes_preserved");
+    assert_eq!(props[1].1, ValueType::Number);
+
+    let instance = comp_def.create();
+    assert_eq!(instance.get_property("underscores_and-dashes-preserved"), Ok(Value::Number(44.)));
+    assert_eq!(
+        instance.get_property("underscoresanddashespreserved"),
+        Err(GetPropertyError::NoSuchProperty)
+    );
+    assert_eq!(
+        instance.set_property("underscores-and_dashes-preserved", Value::Number(88.)),
+        Ok(())
+    );
+    assert_eq!(
+        instance.set_property("underscoresanddashespreserved", Value::Number(99.)),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+    assert_eq!(
+        instance.set_property("underscores-and_dashes-preserved", Value::String("99".into())),
+        Err(SetPropertyError::WrongType)
+    );
+    assert_eq!(instance.get_property("underscores-and-dashes-preserved"), Ok(Value::Number(88.)));
+}
+
+#[test]
+fn component_definition_properties2() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let comp_def = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Rectangle {
+        property <string> sub-text <=> sub.text;
+        sub := Text { property <int> private-not-exported; }
+        callback hello;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let props = comp_def.properties().collect::<Vec<(_, _)>>();
+
+    assert_eq!(props.len(), 1);
+    assert_eq!(props[0].0, "sub-text");
+    assert_eq!(props[0].1, ValueType::String);
+
+    let callbacks = comp_def.callbacks().collect::<Vec<_>>();
+    assert_eq!(callbacks.len(), 1);
+    assert_eq!(callbacks[0], "hello");
+}
+
+#[test]
+fn component_instance_animate_property() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let comp_def = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Rectangle {
+        property <int> value: 10;
+        property <length> rect-width <=> rect.width;
+        property <string> text;
+        rect := Rectangle { width: 10phx; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let instance = comp_def.create();
+    let duration = core::time::Duration::from_millis(100);
+    assert_eq!(
+        instance.animate_property("value", Value::Number(20.), duration, EasingCurve::Linear),
+        Ok(())
+    );
+    assert_eq!(
+        instance.animate_property("rect-width", Value::Number(30.), duration, EasingCurve::Linear),
+        Ok(())
+    );
+    assert_eq!(instance.get_property("value"), Ok(Value::Number(10.)));
+    assert_eq!(instance.get_property("rect-width"), Ok(Value::Number(10.)));
+    i_slint_core::tests::slint_mock_elapsed_time(50);
+    assert_eq!(instance.get_property("value"), Ok(Value::Number(15.)));
+    assert_eq!(instance.get_property("rect-width"), Ok(Value::Number(20.)));
+    i_slint_core::tests::slint_mock_elapsed_time(50);
+    assert_eq!(instance.get_property("value"), Ok(Value::Number(20.)));
+    assert_eq!(instance.get_property("rect-width"), Ok(Value::Number(30.)));
+
+    assert_eq!(
+        instance.animate_property("text", Value::String("x".into()), duration, EasingCurve::Linear),
+        Err(SetPropertyError::NotAnimatable)
+    );
+    assert_eq!(
+        instance.animate_property("nothing", Value::Number(1.), duration, EasingCurve::Linear),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+    assert_eq!(
+        instance.animate_property(
+            "value",
+            Value::String("x".into()),
+            duration,
+            EasingCurve::Linear
+        ),
+        Err(SetPropertyError::WrongType)
+    );
+}
+
+#[test]
+fn animation_speed_factor() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let comp_def = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Rectangle {
+        property <int> value: 10;
+        animate value { duration: 100ms; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let instance = comp_def.create();
+    set_animation_speed_factor(0.5);
+    instance.set_property("value", Value::Number(20.)).unwrap();
+    assert_eq!(instance.get_property("value"), Ok(Value::Number(10.)));
+    i_slint_core::tests::slint_mock_elapsed_time(100);
+    assert_eq!(instance.get_property("value"), Ok(Value::Number(15.)));
+    i_slint_core::tests::slint_mock_elapsed_time(100);
+    assert_eq!(instance.get_property("value"), Ok(Value::Number(20.)));
+
+    set_animations_enabled(false);
+    instance.set_property("value", Value::Number(30.)).unwrap();
+    assert_eq!(instance.get_property("value"), Ok(Value::Number(30.)));
+    set_animations_enabled(true);
+    set_animation_speed_factor(1.);
+}
+
+#[test]
+fn component_factory() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let panel = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Panel := Rectangle {
+        property <length> panel-width: width;
+        property <int> clicks;
+        TouchArea { clicked => { clicks += 1; } }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let host = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Host := Window {
+        width: 100phx;
+        height: 100phx;
+        property <component-factory> panel <=> container.component-factory;
+        container := ComponentContainer { x: 10phx; width: 40phx; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let instance = host.create();
+    let created = Rc::new(std::cell::RefCell::new(Vec::<ComponentInstance>::new()));
+    let make_factory = || {
+        let panel = panel.clone();
+        let created = created.clone();
+        ComponentFactory::new(move |ctx| {
+            let component = panel.create_embedded(ctx);
+            created.borrow_mut().push(component.clone_strong());
+            Some(component)
+        })
+    };
+
+    // No component is created while the factory is empty
+    testing::send_mouse_click(&instance, 20., 20.);
+    assert_eq!(created.borrow().len(), 0);
+
+    let factory = make_factory();
+    instance.set_property("panel", Value::ComponentFactory(factory.clone())).unwrap();
+    assert_eq!(instance.get_property("panel"), Ok(Value::ComponentFactory(factory)));
+    testing::send_mouse_click(&instance, 20., 20.);
+    assert_eq!(created.borrow().len(), 1);
+    assert_eq!(created.borrow()[0].get_property("panel-width"), Ok(Value::Number(40.)));
+    assert_eq!(created.borrow()[0].get_property("clicks"), Ok(Value::Number(1.)));
+    // Outside of the container
+    testing::send_mouse_click(&instance, 5., 5.);
+    assert_eq!(created.borrow()[0].get_property("clicks"), Ok(Value::Number(1.)));
+
+    // A new factory replaces the component
+    instance.set_property("panel", Value::ComponentFactory(make_factory())).unwrap();
+    testing::send_mouse_click(&instance, 20., 20.);
+    assert_eq!(created.borrow().len(), 2);
+    assert_eq!(created.borrow()[0].get_property("clicks"), Ok(Value::Number(1.)));
+    assert_eq!(created.borrow()[1].get_property("clicks"), Ok(Value::Number(1.)));
+
+    // A factory that does not create a component leaves the container empty
+    instance
+        .set_property("panel", Value::ComponentFactory(ComponentFactory::new(|_| None)))
+        .unwrap();
+    testing::send_mouse_click(&instance, 20., 20.);
+    assert_eq!(created.borrow().len(), 2);
+    assert_eq!(created.borrow()[1].get_property("clicks"), Ok(Value::Number(1.)));
+}
+
+#[test]
+fn component_container_children() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let badge = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Badge := Rectangle {
+        x: 20phx;
+        y: 20phx;
+        width: 20phx;
+        height: 20phx;
+        property <int> clicks;
+        TouchArea { clicked => { clicks += 1; } }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let host = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Host := Window {
+        width: 100phx;
+        height: 100phx;
+        badges := ComponentContainer {}
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let instance = host.create();
+    let bottom = instance.insert_child("badges", 0, &badge).unwrap();
+    let top = instance.insert_child("badges", 1, &badge).unwrap();
+
+    // The child with the highest index is on top
+    testing::send_mouse_click(&instance, 30., 30.);
+    assert_eq!(top.get_property("clicks"), Ok(Value::Number(1.)));
+    assert_eq!(bottom.get_property("clicks"), Ok(Value::Number(0.)));
+    // Outside of the children
+    testing::send_mouse_click(&instance, 10., 10.);
+    assert_eq!(top.get_property("clicks"), Ok(Value::Number(1.)));
+    assert_eq!(bottom.get_property("clicks"), Ok(Value::Number(0.)));
+
+    instance.remove_child("badges", &top).unwrap();
+    testing::send_mouse_click(&instance, 30., 30.);
+    assert_eq!(top.get_property("clicks"), Ok(Value::Number(1.)));
+    assert_eq!(bottom.get_property("clicks"), Ok(Value::Number(1.)));
+
+    assert_eq!(instance.remove_child("badges", &top), Err(ChildError::NotAChild));
+    assert_eq!(
+        instance.insert_child("badges", 2, &badge).err(),
+        Some(ChildError::IndexOutOfBounds)
+    );
+    assert_eq!(instance.insert_child("unknown", 0, &badge).err(), Some(ChildError::NoSuchElement));
+}
+
+#[test]
+fn compiled_library() {
+    i_slint_backend_testing::init();
+    let loads = Rc::new(std::cell::Cell::new(0));
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    compiler.set_file_loader({
+        let loads = loads.clone();
+        move |_| {
+            loads.set(loads.get() + 1);
+            Box::pin(async {
+                Some(Ok("export Badge := Rectangle { property <int> value: 42; }".to_string()))
+            })
+        }
+    });
+    let library = spin_on::spin_on(compiler.compile_library(&["/lib/badge.slint".into()])).unwrap();
+    assert_eq!(library.files().collect::<Vec<_>>(), vec![Path::new("/lib/badge.slint")]);
+    assert_eq!(loads.get(), 1);
+
+    // A saved library can be loaded again
+    let bytes = library.to_bytes();
+    assert!(CompiledLibrary::from_bytes(&bytes[1..]).is_none());
+    assert!(CompiledLibrary::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+    let loaded = CompiledLibrary::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.files().collect::<Vec<_>>(), vec![Path::new("/lib/badge.slint")]);
+    assert_eq!(loaded.to_bytes(), bytes);
+
+    for library in [library, Rc::new(loaded)] {
+        compiler.set_libraries(vec![library]);
+        for _ in 0..2 {
+            let definition = spin_on::spin_on(
+                compiler.build_from_source(
+                    r#"
+    import { Badge } from "/lib/badge.slint";
+    export App := Window {
+        property <int> value: badge.value;
+        badge := Badge {}
+    }"#
+                    .into(),
+                    "".into(),
+                ),
+            )
+            .unwrap();
+            assert_eq!(definition.create().get_property("value"), Ok(Value::from(42)));
+        }
+    }
+    // The library is not loaded again
+    assert_eq!(loads.get(), 1);
+}
+
+#[test]
+fn component_dependencies() {
     i_slint_backend_testing::init();
     let mut compiler = ComponentCompiler::default();
     compiler.set_style("fluent".into());
-    let comp_def = spin_on::spin_on(
+    compiler.set_file_loader(|path| {
+        let source = (path == Path::new("/app/widgets/badge.slint")).then(|| {
+            Ok("export Badge := Image { source: @image-url(\"/app/widgets/badge.png\"); }"
+                .to_string())
+        });
+        Box::pin(async move { source })
+    });
+    let definition = spin_on::spin_on(
         compiler.build_from_source(
             r#"
-    export Dummy := Rectangle {
-        property <string> test;
-        property <int> underscores-and-dashes_preserved: 44;
-        callback hello;
+    import { Button } from "std-widgets.slint";
+    import { Badge } from "/app/widgets/badge.slint";
+    export App := Window {
+        Image { source: @image-url("/app/logo.png"); }
+        Badge {}
+        Button {}
     }"#
             .into(),
-            "".into(),
+            "/app/main.slint".into(),
         ),
     )
     .unwrap();
-
-    let props = comp_def.properties().collect::<Vec<(_, _)>>();
-
-    assert_eq!(props.len(), 2);
-    assert_eq!(props[0].0, "test");
-    assert_eq!(props[0].1, ValueType::String);
-    assert_eq!(props[1].0, "underscores-and-dashes_preserved");
-    assert_eq!(props[1].1, ValueType::Number);
-
-    let instance = comp_def.create();
-    assert_eq!(instance.get_property("underscores_and-dashes-preserved"), Ok(Value::Number(44.)));
-    assert_eq!(
-        instance.get_property("underscoresanddashespreserved"),
-        Err(GetPropertyError::NoSuchProperty)
-    );
     assert_eq!(
-        instance.set_property("underscores-and_dashes-preserved", Value::Number(88.)),
-        Ok(())
+        definition.dependencies(),
+        vec![
+            PathBuf::from("/app/logo.png"),
+            PathBuf::from("/app/main.slint"),
+            PathBuf::from("/app/widgets/badge.png"),
+            PathBuf::from("/app/widgets/badge.slint"),
+        ]
     );
-    assert_eq!(
-        instance.set_property("underscoresanddashespreserved", Value::Number(99.)),
-        Err(SetPropertyError::NoSuchProperty)
+}
+
+#[test]
+fn component_bundle() {
+    i_slint_backend_testing::init();
+    // The entry of a tar archive, without the checksum that is not verified
+    fn tar_entry(name: &str, content: &str) -> Vec<u8> {
+        let mut entry = vec![0; 512];
+        entry[..name.len()].copy_from_slice(name.as_bytes());
+        entry[124..135].copy_from_slice(format!("{:011o}", content.len()).as_bytes());
+        entry[156] = b'0';
+        entry.extend_from_slice(content.as_bytes());
+        entry.resize((entry.len() + 511) / 512 * 512, 0);
+        entry
+    }
+    let mut bundle = tar_entry(
+        "./main.slint",
+        r#"
+    import { Label } from "widgets/label.slint";
+    export Theme := Rectangle {
+        property <string> text: label.text;
+        property <image> logo: @image-url("images/logo.svg");
+        label := Label {}
+    }"#,
     );
-    assert_eq!(
-        instance.set_property("underscores-and_dashes-preserved", Value::String("99".into())),
-        Err(SetPropertyError::WrongType)
+    bundle
+        .extend(tar_entry("./widgets/label.slint", r#"export Label := Text { text: "bundled"; }"#));
+    bundle.extend(tar_entry(
+        "./images/logo.svg",
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#,
+    ));
+    bundle.extend([0; 1024]);
+
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(compiler.build_from_bundle_data(&bundle)).unwrap();
+    let instance = definition.create();
+    assert_eq!(instance.get_property("text"), Ok(Value::String("bundled".into())));
+    let logo = match instance.get_property("logo") {
+        Ok(Value::Image(logo)) => logo,
+        logo => panic!("unexpected logo {:?}", logo),
+    };
+    let logo_path = logo.path().unwrap().to_owned();
+    assert!(logo_path.exists());
+    assert!(crate::bundle::file_data("bundle://1/images/logo.svg").is_some());
+
+    // The files of the bundle are released with the component
+    drop(instance);
+    drop(definition);
+    assert!(!logo_path.exists());
+    assert!(crate::bundle::file_data("bundle://1/images/logo.svg").is_none());
+
+    assert!(
+        spin_on::spin_on(compiler.build_from_bundle_data(&tar_entry("theme.slint", ""))).is_none()
     );
-    assert_eq!(instance.get_property("underscores-and-dashes-preserved"), Ok(Value::Number(88.)));
+    assert_eq!(compiler.diagnostics().len(), 1);
 }
 
 #[test]
-fn component_definition_properties2() {
+fn component_compiler_defines() {
     i_slint_backend_testing::init();
     let mut compiler = ComponentCompiler::default();
     compiler.set_style("fluent".into());
+    compiler.set_defines(
+        [
+            ("brand_name".to_string(), DefineValue::from("ACME")),
+            ("max-items".to_string(), DefineValue::from(12)),
+            ("pro".to_string(), DefineValue::from(true)),
+        ]
+        .into_iter()
+        .collect(),
+    );
     let comp_def = spin_on::spin_on(
         compiler.build_from_source(
             r#"
     export Dummy := Rectangle {
-        property <string> sub-text <=> sub.text;
-        sub := Text { property <int> private-not-exported; }
-        callback hello;
+        property <string> title: Defines.brand-name + (Defines.pro ? " Pro" : "");
+        property <int> count: Defines.max_items + 1;
     }"#
             .into(),
             "".into(),
@@ -1155,15 +3733,17 @@ fn component_definition_properties2() {
     )
     .unwrap();
 
-    let props = comp_def.properties().collect::<Vec<(_, _)>>();
-
-    assert_eq!(props.len(), 1);
-    assert_eq!(props[0].0, "sub-text");
-    assert_eq!(props[0].1, ValueType::String);
+    let instance = comp_def.create();
+    assert_eq!(instance.get_property("title"), Ok(Value::String("ACME Pro".into())));
+    assert_eq!(instance.get_property("count"), Ok(Value::Number(13.)));
 
-    let callbacks = comp_def.callbacks().collect::<Vec<_>>();
-    assert_eq!(callbacks.len(), 1);
-    assert_eq!(callbacks[0], "hello");
+    compiler.set_defines(Default::default());
+    let comp_def = spin_on::spin_on(compiler.build_from_source(
+        "export Dummy := Rectangle { property <string> title: Defines.brand-name; }".into(),
+        "".into(),
+    ));
+    assert!(comp_def.is_none());
+    assert_eq!(compiler.diagnostics().len(), 1);
 }
 
 #[test]
@@ -1392,6 +3972,488 @@ fn check_model(val: Value, r: &[f64]) {
     check_model(instance.get_property("prop").unwrap(), &[]);
 }
 
+#[test]
+fn value_display() {
+    let rows = vec![Value::Number(1.5), Value::String("a \"quote\"".into())];
+    let model = Value::Model(ModelRc::new(i_slint_core::model::VecModel::from(rows)));
+    let map: Value = [("k".into(), Value::Bool(false))].iter().cloned().collect::<Map>().into();
+    let value: Value = [
+        ("rows".into(), model),
+        ("map".into(), map),
+        (
+            "color".into(),
+            Value::Brush(Brush::SolidColor(i_slint_core::Color::from_rgb_u8(255, 0, 16))),
+        ),
+        (
+            "align".into(),
+            Value::EnumerationValue("TextHorizontalAlignment".into(), "center".into()),
+        ),
+        ("empty".into(), Value::Struct(Struct::default())),
+    ]
+    .iter()
+    .cloned()
+    .collect::<Struct>()
+    .into();
+
+    assert_eq!(
+        value.to_string(),
+        r#"{ align: TextHorizontalAlignment.center, color: #ff0010, empty: {}, map: ["k": false], rows: [1.5, "a \"quote\""] }"#
+    );
+    assert_eq!(
+        value.pretty().to_string(),
+        r#"{
+    align: TextHorizontalAlignment.center,
+    color: #ff0010,
+    empty: {},
+    map: [
+        "k": false,
+    ],
+    rows: [
+        1.5,
+        "a \"quote\"",
+    ],
+}"#
+    );
+    assert_eq!(value.pretty().to_string(), format!("{:#}", value));
+    assert_eq!(value.pretty().max_depth(0).to_string(), "{ ... }");
+    assert_eq!(
+        value.pretty().max_depth(1).to_string(),
+        "{\n    align: TextHorizontalAlignment.center,\n    color: #ff0010,\n    empty: {},\n    map: [...],\n    rows: [...],\n}"
+    );
+    assert_eq!(Value::Void.to_string(), "void");
+}
+
+#[test]
+fn struct_hash_map_conversion() {
+    let fields: HashMap<String, Value> =
+        [("font_size".to_string(), Value::Number(12.)), ("bold".to_string(), Value::Bool(true))]
+            .into_iter()
+            .collect();
+    let struct_ = Struct::from(fields);
+    assert_eq!(struct_.get_field("font-size"), Some(&Value::Number(12.)));
+    let fields: HashMap<String, Value> = struct_.into();
+    assert_eq!(fields.get("font-size"), Some(&Value::Number(12.)));
+    assert_eq!(fields.get("bold"), Some(&Value::Bool(true)));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_conversion() {
+    let json = serde_json::json!({
+        "name": "Olivier",
+        "scores": [12.5, 14.5],
+        "address": { "city": "Berlin", "zip": null },
+    });
+    let value = Value::from(json.clone());
+    let st: Struct = value.clone().try_into().unwrap();
+    assert_eq!(st.get_field("name"), Some(&Value::String("Olivier".into())));
+    assert_eq!(serde_json::Value::try_from(value).unwrap(), json);
+
+    let color = Value::Brush(Brush::SolidColor(i_slint_core::Color::from_rgb_u8(255, 0, 16)));
+    assert_eq!(serde_json::Value::try_from(color), Ok(serde_json::json!("#ff0010")));
+    let image = Value::Image(Default::default());
+    assert_eq!(serde_json::Value::try_from(image.clone()), Err(image));
+
+    assert!(Struct::try_from(serde_json::json!({ "a": 1 })).is_ok());
+    assert_eq!(Struct::try_from(serde_json::json!([1])), Err(serde_json::json!([1])));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let json = r#"{"address":{"city":"Berlin","zip":null},"name":"Olivier","scores":[12.5,14.5]}"#;
+    let value: Value = serde_json::from_str(json).unwrap();
+    let st: Struct = value.clone().try_into().unwrap();
+    assert_eq!(st.get_field("name"), Some(&Value::String("Olivier".into())));
+    assert_eq!(
+        serde_json::to_value(&value).unwrap(),
+        serde_json::from_str::<serde_json::Value>(json).unwrap()
+    );
+    let st: Struct = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_string(&st).unwrap().len(), json.len());
+
+    let color = Value::Brush(Brush::SolidColor(i_slint_core::Color::from_rgb_u8(255, 0, 16)));
+    assert_eq!(serde_json::to_string(&color).unwrap(), r##""#ff0010""##);
+    assert!(serde_json::to_string(&Value::Image(Default::default())).is_err());
+    assert!(serde_json::from_str::<Struct>("[1]").is_err());
+
+    // The underscores of the keys become dashes, as in the field names of .slint
+    let st: Struct = serde_json::from_str(r#"{"first_name":"Ada"}"#).unwrap();
+    assert_eq!(st.iter().map(|(name, _)| name).collect::<Vec<_>>(), ["first-name"]);
+    let json = serde_json::to_string(&st).unwrap();
+    assert_eq!(json, r#"{"first-name":"Ada"}"#);
+    assert_eq!(serde_json::from_str::<Struct>(&json).unwrap(), st);
+}
+
+#[test]
+fn set_property_with_coercion() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export struct Setting := { label: string, enabled: bool, size: length }
+    export Dummy := Rectangle {
+        property <float> ratio;
+        property <string> title;
+        property <brush> background-color;
+        property <Setting> setting;
+        property <[int]> counts;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    );
+    let instance = definition.unwrap().create();
+    let string = |s: &str| Value::String(s.into());
+
+    assert_eq!(
+        instance.set_property_with_coercion("ratio", string("1.5"), Coercion::default()),
+        Err(SetPropertyError::WrongType)
+    );
+    let coercion = Coercion::all();
+    instance.set_property_with_coercion("ratio", string(" 1.5 "), coercion).unwrap();
+    assert_eq!(instance.get_property("ratio").unwrap(), Value::Number(1.5));
+    instance.set_property_with_coercion("title", Value::Number(3.), coercion).unwrap();
+    assert_eq!(instance.get_property("title").unwrap(), string("3"));
+    instance.set_property_with_coercion("background_color", string("#f00"), coercion).unwrap();
+    assert_eq!(
+        instance.get_property("background-color").unwrap(),
+        Value::Brush(Brush::SolidColor(i_slint_core::Color::from_rgb_u8(255, 0, 0)))
+    );
+    assert_eq!(
+        instance.set_property_with_coercion("ratio", string("one"), coercion),
+        Err(SetPropertyError::WrongType)
+    );
+    assert_eq!(
+        instance.set_property_with_coercion("unknown", string("1"), coercion),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+
+    let setting: Struct =
+        [("label", Value::Number(12.)), ("enabled", string("true")), ("size", string("20"))]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+    instance.set_property_with_coercion("setting", setting.into(), coercion).unwrap();
+    let setting: Struct = instance.get_property("setting").unwrap().try_into().unwrap();
+    assert_eq!(setting.get_field("label"), Some(&string("12")));
+    assert_eq!(setting.get_field("enabled"), Some(&Value::Bool(true)));
+    assert_eq!(setting.get_field("size"), Some(&Value::Number(20.)));
+
+    let counts =
+        ModelRc::new(i_slint_core::model::VecModel::from(vec![string("1"), Value::Number(2.4)]));
+    instance.set_property_with_coercion("counts", Value::Model(counts), coercion).unwrap();
+    match instance.get_property("counts").unwrap() {
+        Value::Model(counts) => assert_eq!(
+            counts.iter().collect::<Vec<_>>(),
+            vec![Value::Number(1.), Value::Number(2.)]
+        ),
+        value => panic!("unexpected value {:?}", value),
+    }
+}
+
+#[test]
+fn property_transaction() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export global Settings := {
+        property <bool> dark-mode;
+    }
+    export Dummy := Rectangle {
+        property <int> count;
+        property <int> double: count * 2;
+        callback clicked;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    );
+    let instance = definition.unwrap().create();
+
+    let result = instance.batch(|transaction| {
+        assert_eq!(
+            transaction.set("count", Value::String("x".into())),
+            Err(SetPropertyError::WrongType)
+        );
+        assert_eq!(transaction.set("clicked", Value::Void), Err(SetPropertyError::NoSuchProperty));
+        assert_eq!(
+            transaction.set_global("Settings", "unknown", Value::Bool(true)),
+            Err(SetPropertyError::NoSuchProperty)
+        );
+        transaction.set("count", Value::Number(21.)).unwrap();
+        transaction.set_global("Settings", "dark_mode", Value::Bool(true)).unwrap();
+    });
+    // The invalid changes cancel the valid ones
+    assert_eq!(result, Err(SetPropertyError::WrongType));
+    assert_eq!(instance.get_property("double").unwrap(), Value::Number(0.));
+    assert_eq!(instance.get_global_property("Settings", "dark-mode").unwrap(), Value::Bool(false));
+
+    let result = instance.batch(|transaction| {
+        transaction.set("count", Value::Number(21.)).unwrap();
+        transaction.set_global("Settings", "dark_mode", Value::Bool(true)).unwrap();
+        // The changes are not applied yet
+        instance.get_property("double").unwrap()
+    });
+    assert_eq!(result, Ok(Value::Number(0.)));
+    assert_eq!(instance.get_property("double").unwrap(), Value::Number(42.));
+    assert_eq!(instance.get_global_property("Settings", "dark-mode").unwrap(), Value::Bool(true));
+}
+
+#[test]
+fn snapshot_and_restore() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Rectangle {
+        property <string> name: "a";
+        property <[int]> values: [1, 2];
+        callback clicked;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    );
+    let instance = definition.unwrap().create();
+    let model = Rc::new(i_slint_core::model::VecModel::from(vec![Value::Number(1.)]));
+    instance.set_property("values", Value::Model(model.clone().into())).unwrap();
+
+    let mut snapshot = instance.snapshot();
+    assert_eq!(snapshot.iter().count(), 2);
+    assert_eq!(snapshot.get_field("name"), Some(&Value::String("a".into())));
+
+    instance.set_property("name", Value::String("b".into())).unwrap();
+    model.push(Value::Number(2.));
+    snapshot.set_field("removed".into(), Value::Bool(true));
+    assert_eq!(instance.restore(&snapshot), vec!["removed".to_string()]);
+    assert_eq!(instance.get_property("name").unwrap(), Value::String("a".into()));
+    match instance.get_property("values").unwrap() {
+        Value::Model(values) => assert_eq!(values.iter().collect::<Vec<_>>(), [Value::Number(1.)]),
+        value => panic!("unexpected value {:?}", value),
+    }
+}
+
+#[test]
+fn weak_callbacks() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export global Logic := {
+        callback compute(int) -> int;
+    }
+    export Dummy := Rectangle {
+        property <int> offset: 10;
+        callback compute(int) -> int;
+        callback clicked;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let instance = definition.create();
+    let other = definition.create();
+    other.set_property("offset", Value::Number(100.)).unwrap();
+
+    let add_offset = |instance: ComponentInstance, args: &[Value]| match (
+        instance.get_property("offset").unwrap(),
+        &args[0],
+    ) {
+        (Value::Number(offset), Value::Number(arg)) => Value::Number(offset + arg),
+        _ => panic!("unexpected arguments {:?}", args),
+    };
+    instance.set_callback_weak("compute", other.as_weak(), add_offset).unwrap();
+    instance.set_global_callback_weak("Logic", "compute", other.as_weak(), add_offset).unwrap();
+    instance.set_callback_weak("clicked", other.as_weak(), |_, _| Value::Void).unwrap();
+    assert_eq!(
+        instance.set_callback_weak("not-there", other.as_weak(), add_offset),
+        Err(SetCallbackError::NoSuchCallback)
+    );
+
+    let args = [Value::Number(1.)];
+    assert_eq!(instance.invoke_callback("compute", &args), Ok(Value::Number(101.)));
+    assert_eq!(instance.invoke_global_callback("Logic", "compute", &args), Ok(Value::Number(101.)));
+
+    // The handlers don't keep the other instance alive, and return the default value without it
+    drop(other);
+    assert_eq!(instance.invoke_callback("compute", &args), Ok(Value::Number(0.)));
+    assert_eq!(instance.invoke_global_callback("Logic", "compute", &args), Ok(Value::Number(0.)));
+    assert_eq!(instance.invoke_callback("clicked", &[]), Ok(Value::Void));
+}
+
+#[test]
+fn lifecycle_events() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source("export Dummy := Rectangle { }".into(), "".into()),
+    )
+    .unwrap();
+    let instance = definition.create();
+    let events = Rc::new(std::cell::RefCell::new(Vec::new()));
+    let events_clone = events.clone();
+    instance.on_lifecycle_event(move |event| events_clone.borrow_mut().push(event));
+
+    // The window was never shown, so hiding it doesn't change its visibility
+    instance.hide();
+    assert!(events.borrow().is_empty());
+    drop(instance);
+    assert_eq!(*events.borrow(), [LifecycleEvent::Destroyed]);
+}
+
+#[test]
+fn text_layout_of_elements() {
+    use i_slint_core::graphics::Point;
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+        export Dummy := Rectangle {
+            label := Text { text: "Héllo"; }
+            input := TextInput { text: "World"; }
+        }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let instance = definition.create();
+
+    // The testing backend lays out the text on one line, with 10 pixels per byte
+    assert_eq!(instance.text_position_for_byte_offset("input", 3), Some(Point::new(30., 0.)));
+    assert_eq!(instance.text_byte_offset_for_position("input", 31., 5.), Some(3));
+    assert_eq!(instance.text_byte_offset_for_position("input", 200., 5.), Some(5));
+    assert_eq!(instance.text_position_for_byte_offset("label", 3), Some(Point::new(30., 0.)));
+    assert_eq!(instance.text_byte_offset_for_position("label", 42., 0.), Some(4));
+    // The offsets in the middle of a character go to its start
+    assert_eq!(instance.text_byte_offset_for_position("label", 20., 0.), Some(1));
+    assert_eq!(instance.text_position_for_byte_offset("missing", 0), None);
+    assert_eq!(instance.text_byte_offset_for_position("missing", 0., 0.), None);
+}
+
+#[test]
+fn text_input_focus_and_commit_text() {
+    use i_slint_core::graphics::{Point, Rect, Size};
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+        export Dummy := Rectangle {
+            width: 100px;
+            height: 100px;
+            property <string> amount <=> input.text;
+            property <bool> show-note: true;
+            property <int> key-count;
+            input := TextInput { x: 10px; y: 20px; width: 50px; height: 30px; input-type: number; }
+            FocusScope {
+                y: 60px;
+                height: 40px;
+                key-pressed(event) => { key-count += 1; accept }
+            }
+            if show-note : TextInput { x: 70px; y: 0px; width: 30px; height: 30px; }
+        }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let instance = definition.create();
+    let notifications = Rc::new(std::cell::RefCell::new(Vec::new()));
+    let notifications_clone = notifications.clone();
+    instance
+        .window()
+        .on_text_input_focus_changed(move |f| notifications_clone.borrow_mut().push(f));
+
+    crate::testing::send_mouse_click(&instance, 15., 25.);
+    let mut focus = notifications.take();
+    assert_eq!(focus.len(), 1);
+    let focus = focus.pop().unwrap().unwrap();
+    assert_eq!(focus.geometry, Rect::new(Point::new(10., 20.), Size::new(50., 30.)));
+    assert_eq!(focus.input_type, InputType::number);
+
+    // Only numbers can be entered in a number input
+    instance.window().commit_text("12a");
+    instance.window().commit_text("4.5");
+    assert_eq!(instance.get_property("amount").unwrap(), Value::String("4.5".into()));
+    instance.window().commit_text("6");
+    assert_eq!(instance.get_property("amount").unwrap(), Value::String("4.56".into()));
+
+    // The focus leaves the TextInput for a FocusScope, which doesn't receive the committed text
+    crate::testing::send_mouse_click(&instance, 50., 80.);
+    assert_eq!(notifications.take(), [None]);
+    instance.window().commit_text("7");
+    assert_eq!(instance.get_property("amount").unwrap(), Value::String("4.56".into()));
+    assert_eq!(instance.get_property("key-count").unwrap(), Value::Number(0.));
+
+    // The focus leaves the TextInput when it is destroyed
+    crate::testing::send_mouse_click(&instance, 85., 15.);
+    assert_eq!(notifications.take().len(), 1);
+    instance.set_property("show-note", Value::Bool(false)).unwrap();
+    crate::testing::send_mouse_click(&instance, 5., 5.);
+    assert_eq!(notifications.take(), [None]);
+}
+
+#[test]
+fn build_all_exported_components() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let definitions = spin_on::spin_on(
+        compiler.build_all_from_source(
+            r#"
+    export global Settings := {
+        property <int> size: 10;
+    }
+    export Label := Rectangle {
+        property <string> text: "label";
+        property <bool> highlighted;
+        property <int> size: Settings.size * 2;
+        states [
+            highlighted when highlighted: { text: "highlighted"; }
+        ]
+    }
+    export { Label as Caption }
+    export MainWindow := Window {
+        property <string> label-text <=> label.text;
+        property <int> size: Settings.size;
+        label := Label { text: "main"; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let mut names = definitions.keys().map(String::as_str).collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, ["Caption", "Label", "MainWindow"]);
+
+    // The component used by another one is also compiled as a root, with its own public API
+    let label = definitions["Label"].create();
+    assert_eq!(label.get_property("text").unwrap(), Value::String("label".into()));
+    label.set_property("highlighted", Value::Bool(true)).unwrap();
+    assert_eq!(label.get_property("text").unwrap(), Value::String("highlighted".into()));
+    assert_eq!(definitions["Caption"].name(), "Label");
+
+    let main = definitions["MainWindow"].create();
+    assert_eq!(main.get_property("label-text").unwrap(), Value::String("main".into()));
+
+    // Each root has its own copy of the globals
+    assert_eq!(label.get_property("size").unwrap(), Value::Number(20.));
+    label.set_global_property("Settings", "size", Value::Number(5.)).unwrap();
+    assert_eq!(label.get_property("size").unwrap(), Value::Number(10.));
+    assert_eq!(main.get_property("size").unwrap(), Value::Number(10.));
+    main.set_global_property("Settings", "size", Value::Number(3.)).unwrap();
+    assert_eq!(main.get_property("size").unwrap(), Value::Number(3.));
+}
+
 #[cfg(feature = "ffi")]
 #[allow(missing_docs)]
 #[path = "ffi.rs"]
@@ -0,0 +1,300 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    A server with which external tools, such as the test frameworks in Python or Robot Framework,
+    read and change the properties of a running component, invoke its callbacks, send it mouse
+    clicks and key presses, and take screenshots of its window.
+
+    The server is started with [`start_tcp_server`] or, on Unix, [`start_unix_socket_server`].
+    Each request is a JSON object on a single line, and the server answers each request with a
+    JSON object on a single line, with the `id` of the request and either a `result` or an `error`:
+
+    ```text
+    → {"id": 1, "command": "set-property", "name": "counter", "value": 41}
+    ← {"id": 1, "result": null}
+    → {"id": 2, "command": "invoke", "name": "increment", "args": []}
+    ← {"id": 2, "result": null}
+    → {"id": 3, "command": "get-property", "name": "counter"}
+    ← {"id": 3, "result": 42.0}
+    → {"id": 4, "command": "get-property", "name": "countr"}
+    ← {"id": 4, "error": "no such property"}
+    ```
+
+    The commands are:
+     - `get-property` with a `name`: the value of a property
+     - `set-property` with a `name` and a `value`
+     - `invoke` with a `name` and `args`: invoke a callback or a function, and return its result
+     - `mouse-click` with `x` and `y`: a click with the left mouse button, in logical pixels
+     - `key-sequence` with a `text`: a key press and release for each character of the text
+     - `screenshot`: the content of the window, as an object with the `width`, the `height`,
+       and the `rgba` pixels encoded in base64
+
+    The values are converted from JSON with [`value_from_json`], and to JSON with the
+    `TryFrom<Value>` implementation of `serde_json::Value`.
+
+    The server doesn't authenticate its clients, so it must only be started in test environments.
+*/
+
+use crate::api::parse_color;
+use crate::{Brush, ComponentHandle, ComponentInstance, Value, Weak};
+use i_slint_core::graphics::Image;
+use i_slint_core::model::{Model, ModelRc, VecModel};
+use i_slint_core::window::WindowHandleAccess;
+use serde_json::Value as JsonValue;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+use std::path::Path;
+
+/// Start a thread that accepts the connections on this TCP address, such as `127.0.0.1:9321`,
+/// and executes their requests on the instance in the event loop. Returns the address of the
+/// server, whose port is chosen by the system if the port of `address` is 0.
+pub fn start_tcp_server(
+    instance: &ComponentInstance,
+    address: impl ToSocketAddrs,
+) -> std::io::Result<SocketAddr> {
+    let listener = TcpListener::bind(address)?;
+    let local_address = listener.local_addr()?;
+    let instance = instance.as_weak();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let instance = instance.clone();
+            if let Ok(writer) = stream.try_clone() {
+                std::thread::spawn(move || serve(&instance, BufReader::new(stream), writer));
+            }
+        }
+    });
+    Ok(local_address)
+}
+
+/// Start a thread that accepts the connections on a Unix domain socket at this path, and
+/// executes their requests on the instance in the event loop
+#[cfg(unix)]
+pub fn start_unix_socket_server(
+    instance: &ComponentInstance,
+    path: impl AsRef<Path>,
+) -> std::io::Result<()> {
+    let listener = std::os::unix::net::UnixListener::bind(path)?;
+    let instance = instance.as_weak();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let instance = instance.clone();
+            if let Ok(writer) = stream.try_clone() {
+                std::thread::spawn(move || serve(&instance, BufReader::new(stream), writer));
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Answer the requests of a connection until it is closed
+fn serve(instance: &Weak<ComponentInstance>, reader: impl BufRead, mut writer: impl Write) {
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) if line.trim().is_empty() => continue,
+            Ok(line) => line,
+            Err(_) => return,
+        };
+        let response = match serde_json::from_str::<JsonValue>(&line) {
+            Ok(request) => handle_request(instance, request),
+            Err(err) => serde_json::json!({ "id": null, "error": err.to_string() }),
+        };
+        if writeln!(writer, "{}", response).and_then(|()| writer.flush()).is_err() {
+            return;
+        }
+    }
+}
+
+/// Execute the request in the event loop, and wait for its result
+fn handle_request(instance: &Weak<ComponentInstance>, request: JsonValue) -> JsonValue {
+    let id = request.get("id").cloned().unwrap_or_default();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    instance.upgrade_in_event_loop(move |instance| {
+        sender.send(execute(&instance, &request)).ok();
+    });
+    // The sender is dropped without a result if the component is destroyed
+    match receiver.recv().unwrap_or_else(|_| Err("The component was destroyed".into())) {
+        Ok(result) => serde_json::json!({ "id": id, "result": result }),
+        Err(error) => serde_json::json!({ "id": id, "error": error }),
+    }
+}
+
+fn execute(instance: &ComponentInstance, request: &JsonValue) -> Result<JsonValue, String> {
+    let string = |field: &str| {
+        request.get(field).and_then(JsonValue::as_str).ok_or_else(|| format!("Missing {}", field))
+    };
+    let number = |field: &str| {
+        request.get(field).and_then(JsonValue::as_f64).ok_or_else(|| format!("Missing {}", field))
+    };
+    match string("command")? {
+        "get-property" => {
+            let value = instance.get_property(string("name")?).map_err(|e| e.to_string())?;
+            Ok(JsonValue::try_from(value).unwrap_or_default())
+        }
+        "set-property" => {
+            let name = string("name")?;
+            let current = instance.get_property(name).map_err(|e| e.to_string())?;
+            let json = request.get("value").ok_or("Missing value")?;
+            let value = value_from_json(json, &current, Path::new(""));
+            instance.set_property(name, value).map_err(|e| e.to_string())?;
+            Ok(JsonValue::Null)
+        }
+        "invoke" => {
+            let name = string("name")?;
+            let args = match request.get("args") {
+                Some(JsonValue::Array(args)) => args
+                    .iter()
+                    .map(|arg| value_from_json(arg, &Value::Void, Path::new("")))
+                    .collect(),
+                _ => vec![],
+            };
+            let result = instance
+                .invoke_callback(name, &args)
+                .or_else(|_| instance.invoke(name, &args))
+                .map_err(|_| format!("There is no callback or function named {}", name))?;
+            Ok(JsonValue::try_from(result).unwrap_or_default())
+        }
+        "mouse-click" => {
+            crate::testing::send_mouse_click(instance, number("x")? as f32, number("y")? as f32);
+            Ok(JsonValue::Null)
+        }
+        "key-sequence" => {
+            crate::testing::send_keyboard_string_sequence(instance, string("text")?.into());
+            Ok(JsonValue::Null)
+        }
+        "screenshot" => {
+            let buffer = instance
+                .window()
+                .window_handle()
+                .take_snapshot()
+                .ok_or("The rendering backend cannot take screenshots")?;
+            Ok(serde_json::json!({
+                "width": buffer.width(),
+                "height": buffer.height(),
+                "rgba": base64(buffer.as_bytes()),
+            }))
+        }
+        command => Err(format!("Unknown command {}", command)),
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let bytes =
+            [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Convert a JSON value to a value of the same type as `current`, such as the current value of
+/// the property that is set:
+///  - The strings are converted to colors for the brushes, with the `#rgb`, `#rgba`, `#rrggbb`, or
+///    `#rrggbbaa` notation, to images for the images, loaded from a path relative to `base_dir`,
+///    and to enumeration values for the enumerations
+///  - The rows of the arrays, the fields of the objects, and the entries of the maps are
+///    converted like the ones of `current`
+///
+/// The other JSON values are converted with the `From<serde_json::Value>` implementation of
+/// [`Value`].
+pub fn value_from_json(json: &JsonValue, current: &Value, base_dir: &Path) -> Value {
+    match (json, current) {
+        (JsonValue::String(s), Value::Brush(_)) => {
+            if let Some(color) = parse_color(s) {
+                return Value::Brush(Brush::SolidColor(color));
+            }
+        }
+        (JsonValue::String(s), Value::Image(_)) => {
+            if let Ok(image) = Image::load_from_path(&base_dir.join(s)) {
+                return Value::Image(image);
+            }
+        }
+        (JsonValue::String(s), Value::EnumerationValue(enumeration, _)) => {
+            return Value::EnumerationValue(enumeration.clone(), s.clone());
+        }
+        (JsonValue::Array(array), Value::Model(model)) => {
+            let row = model.row_data(0).unwrap_or_default();
+            return Value::Model(ModelRc::new(VecModel::from(
+                array.iter().map(|v| value_from_json(v, &row, base_dir)).collect::<Vec<_>>(),
+            )));
+        }
+        (JsonValue::Object(obj), Value::Map(map)) => {
+            let entry = map.iter().next().map(|(_, v)| v.clone()).unwrap_or_default();
+            return obj
+                .iter()
+                .map(|(k, v)| (k.clone(), value_from_json(v, &entry, base_dir)))
+                .collect::<crate::Map>()
+                .into();
+        }
+        (JsonValue::Object(obj), Value::Struct(st)) => {
+            return obj
+                .iter()
+                .map(|(k, v)| {
+                    let field = st.get_field(k).cloned().unwrap_or_default();
+                    (k.clone(), value_from_json(v, &field, base_dir))
+                })
+                .collect::<crate::Struct>()
+                .into();
+        }
+        _ => (),
+    }
+    json.clone().into()
+}
+
+#[test]
+fn test_base64() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"f"), "Zg==");
+    assert_eq!(base64(b"fo"), "Zm8=");
+    assert_eq!(base64(b"foo"), "Zm9v");
+    assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+}
+
+#[test]
+fn test_automation_requests() {
+    i_slint_backend_testing::init();
+    let mut compiler = crate::ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Counter := Rectangle {
+        property <int> counter: 1;
+        property <color> tint: #ff0000;
+        callback increment(int);
+        increment(step) => { counter += step; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let instance = definition.create();
+    let request = |request: &str| execute(&instance, &serde_json::from_str(request).unwrap());
+
+    assert_eq!(request(r#"{"command": "get-property", "name": "counter"}"#), Ok(1.0.into()));
+    assert_eq!(
+        request(r#"{"command": "set-property", "name": "counter", "value": 40}"#),
+        Ok(JsonValue::Null)
+    );
+    assert_eq!(
+        request(r#"{"command": "invoke", "name": "increment", "args": [2]}"#),
+        Ok(JsonValue::Null)
+    );
+    assert_eq!(request(r#"{"command": "get-property", "name": "counter"}"#), Ok(42.0.into()));
+    assert_eq!(
+        request(r#"{"command": "set-property", "name": "tint", "value": "#00ff00"}"#),
+        Ok(JsonValue::Null)
+    );
+    assert_eq!(request(r#"{"command": "get-property", "name": "tint"}"#), Ok("#00ff00".into()));
+    assert!(request(r#"{"command": "get-property", "name": "countr"}"#).is_err());
+    assert!(request(r#"{"command": "launch"}"#).is_err());
+}
@@ -0,0 +1,102 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Benchmarks of a ListView with a large model in the interpreter
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use i_slint_core::item_tree::{visit_items, ItemVisitorResult, TraversalOrder};
+use i_slint_core::model::{ModelRc, VecModel};
+use i_slint_core::window::WindowHandleAccess;
+use slint_interpreter::{ComponentCompiler, ComponentHandle, ComponentInstance, Value};
+
+const ROW_COUNT: usize = 100_000;
+
+fn create_instance() -> ComponentInstance {
+    i_slint_backend_testing::init();
+    let code = r#"
+        import { ListView } from "std-widgets.slint";
+        MainWindow := Window {
+            width: 300px;
+            height: 600px;
+            property <[string]> model;
+            property <length> viewport-y <=> lv.viewport-y;
+            lv := ListView {
+                for data[idx] in model : Rectangle {
+                    height: 20px;
+                    Text { text: idx + ": " + data; }
+                }
+            }
+        }
+    "#;
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+    assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+    definition.unwrap().create()
+}
+
+fn large_model() -> Value {
+    Value::Model(ModelRc::new(VecModel::from(
+        (0..ROW_COUNT).map(|i| Value::String(format!("Row {}", i).into())).collect::<Vec<_>>(),
+    )))
+}
+
+/// Does what rendering a frame does without drawing anything: visiting the item tree
+/// instantiates and lays out the visible rows of the ListView
+fn update_listview(instance: &ComponentInstance) {
+    instance.window().window_handle().clone().draw_contents(|components| {
+        for (component, _) in components {
+            visit_items(
+                component,
+                TraversalOrder::BackToFront,
+                |_, item, _, _| {
+                    black_box(item.as_ref().geometry());
+                    ItemVisitorResult::Continue(())
+                },
+                (),
+            );
+        }
+    });
+}
+
+fn set_model(c: &mut Criterion) {
+    let instance = create_instance();
+    c.bench_function("listview set 100k rows model", |b| {
+        b.iter(|| {
+            instance.set_property("model", large_model()).unwrap();
+            update_listview(black_box(&instance));
+        })
+    });
+}
+
+fn scroll(c: &mut Criterion) {
+    let instance = create_instance();
+    instance.set_property("model", large_model()).unwrap();
+    update_listview(&instance);
+    let mut y = 0.;
+    c.bench_function("listview scroll 100k rows model", |b| {
+        b.iter(|| {
+            y = (y + 137.) % (ROW_COUNT as f64 * 20.);
+            instance.set_property("viewport-y", Value::Number(-y)).unwrap();
+            update_listview(black_box(&instance));
+        })
+    });
+}
+
+fn jump(c: &mut Criterion) {
+    let instance = create_instance();
+    instance.set_property("model", large_model()).unwrap();
+    update_listview(&instance);
+    let mut top = false;
+    c.bench_function("listview jump between start and end of 100k rows model", |b| {
+        b.iter(|| {
+            top = !top;
+            let y = if top { 0. } else { -(ROW_COUNT as f64 * 20.) };
+            instance.set_property("viewport-y", Value::Number(y)).unwrap();
+            update_listview(black_box(&instance));
+        })
+    });
+}
+
+criterion_group!(benches, set_model, scroll, jump);
+criterion_main!(benches);
@@ -0,0 +1,299 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    A harness that measures the performance of a component in a reproducible way, for example to
+    detect performance regressions of the interpreter and of the renderer in a continuous
+    integration.
+
+    A [`Benchmark`] compiles a component and creates an instance of it, without showing its
+    window. Then it runs a number of frames: before each frame, the steps of the script for that
+    frame, such as property changes or mouse clicks, are applied to the instance, and the mocked
+    clock of the animations advances by the duration of a frame. Then the scene of the window is
+    rendered into an SVG document, which evaluates the bindings and the layouts, like a rendering
+    backend does.
+
+    ```rust,no_run
+    use slint_interpreter::benchmark::{Benchmark, Step};
+    use slint_interpreter::Value;
+
+    let mut benchmark = Benchmark::from_path("ui/main.slint".into());
+    benchmark.set_frame_count(120);
+    benchmark.add_step(0, Step::SetProperty("counter".into(), Value::Number(42.)));
+    benchmark.add_step(60, Step::MouseClick(100., 20.));
+    let report = benchmark.run().unwrap();
+    println!("{}", report);
+    ```
+
+    The timers are not run, because they use the real time. Since the clock is mocked, the
+    animations progress by the same amount in each frame, and a run does the same work regardless
+    of the speed of the machine.
+
+    To not depend on a windowing system, initialize the testing backend before running the
+    benchmark.
+*/
+
+use crate::{ComponentCompiler, ComponentHandle, ComponentInstance, Diagnostic, Value};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// A change of the component, applied before a frame of the benchmark
+#[derive(Debug, Clone)]
+pub enum Step {
+    /// Set the property with this name to the value
+    SetProperty(String, Value),
+    /// Invoke the callback or the function with this name, with the arguments
+    Invoke(String, Vec<Value>),
+    /// A click with the left mouse button at this position, in logical pixels.
+    /// The button is released 50ms after it is pressed, on the mocked clock.
+    MouseClick(f32, f32),
+    /// A key press and release for each character of the text
+    KeySequence(String),
+    /// Advance the mocked clock by this duration, in addition to the duration of the frame
+    AdvanceTime(Duration),
+}
+
+/// The error returned by [`Benchmark::run`]
+#[derive(Debug, thiserror::Error)]
+pub enum BenchmarkError {
+    /// The component could not be compiled. Contains the diagnostics.
+    #[error("the component could not be compiled")]
+    Compile(Vec<Diagnostic>),
+    /// The step applied before the frame failed
+    #[error("step {step:?} of frame {frame} failed: {message}")]
+    Step {
+        /// The frame before which the step was applied
+        frame: usize,
+        /// The step that failed
+        step: Step,
+        /// Why the step failed
+        message: String,
+    },
+}
+
+/// Compiles a component, and measures how long the frames of a scripted run take.
+/// See the [module documentation](self) for an example.
+pub struct Benchmark {
+    source: Option<String>,
+    path: PathBuf,
+    style: Option<String>,
+    frame_count: usize,
+    frame_duration: Duration,
+    steps: Vec<(usize, Step)>,
+}
+
+impl Benchmark {
+    /// A benchmark of the last component of the `.slint` file at this path
+    pub fn from_path(path: PathBuf) -> Self {
+        Self::new(None, path)
+    }
+
+    /// A benchmark of the last component of the `source` code. The `path` is used to resolve
+    /// the relative imports and image paths.
+    pub fn from_source(source: String, path: PathBuf) -> Self {
+        Self::new(Some(source), path)
+    }
+
+    fn new(source: Option<String>, path: PathBuf) -> Self {
+        Self {
+            source,
+            path,
+            style: None,
+            frame_count: 60,
+            frame_duration: Duration::from_millis(16),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Sets the style used to compile the component, as with [`ComponentCompiler::set_style`]
+    pub fn set_style(&mut self, style: String) {
+        self.style = Some(style);
+    }
+
+    /// Sets the number of frames that are run. The default is 60.
+    pub fn set_frame_count(&mut self, frame_count: usize) {
+        self.frame_count = frame_count;
+    }
+
+    /// Sets the duration by which the mocked clock advances at each frame. The default is 16ms.
+    pub fn set_frame_duration(&mut self, frame_duration: Duration) {
+        self.frame_duration = frame_duration;
+    }
+
+    /// Adds a step that is applied before the frame with this index. The steps of a frame are
+    /// applied in the order in which they were added.
+    pub fn add_step(&mut self, frame: usize, step: Step) {
+        self.steps.push((frame, step));
+    }
+
+    /// Compiles the component, creates an instance, and runs the frames
+    pub fn run(&self) -> Result<BenchmarkReport, BenchmarkError> {
+        let start = Instant::now();
+        let mut compiler = ComponentCompiler::default();
+        if let Some(style) = &self.style {
+            compiler.set_style(style.clone());
+        }
+        let definition = match &self.source {
+            Some(source) => {
+                spin_on::spin_on(compiler.build_from_source(source.clone(), self.path.clone()))
+            }
+            None => spin_on::spin_on(compiler.build_from_path(&self.path)),
+        };
+        let definition =
+            definition.ok_or_else(|| BenchmarkError::Compile(compiler.diagnostics().clone()))?;
+        let compile = start.elapsed();
+
+        let start = Instant::now();
+        let instance = definition.create();
+        let instantiate = start.elapsed();
+
+        let mut frames = Vec::with_capacity(self.frame_count);
+        for frame in 0..self.frame_count {
+            let start = Instant::now();
+            for (_, step) in self.steps.iter().filter(|(f, _)| *f == frame) {
+                apply_step(&instance, step).map_err(|message| BenchmarkError::Step {
+                    frame,
+                    step: step.clone(),
+                    message,
+                })?;
+            }
+            let script = start.elapsed();
+
+            let start = Instant::now();
+            i_slint_core::tests::slint_mock_elapsed_time(self.frame_duration.as_millis() as u64);
+            let animations = start.elapsed();
+
+            let start = Instant::now();
+            instance.window().export_svg();
+            let rendering = start.elapsed();
+
+            frames.push(FrameTimings { script, animations, rendering });
+        }
+
+        Ok(BenchmarkReport { compile, instantiate, frames })
+    }
+}
+
+fn apply_step(instance: &ComponentInstance, step: &Step) -> Result<(), String> {
+    match step {
+        Step::SetProperty(name, value) => {
+            instance.set_property(name, value.clone()).map_err(|e| e.to_string())
+        }
+        Step::Invoke(name, args) => instance
+            .invoke_callback(name, args)
+            .or_else(|_| instance.invoke(name, args))
+            .map(|_| ())
+            .map_err(|_| format!("There is no callback or function named {}", name)),
+        Step::MouseClick(x, y) => {
+            crate::testing::send_mouse_click(instance, *x, *y);
+            Ok(())
+        }
+        Step::KeySequence(text) => {
+            crate::testing::send_keyboard_string_sequence(instance, text.as_str().into());
+            Ok(())
+        }
+        Step::AdvanceTime(duration) => {
+            i_slint_core::tests::slint_mock_elapsed_time(duration.as_millis() as u64);
+            Ok(())
+        }
+    }
+}
+
+/// How long the phases of a frame took
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameTimings {
+    /// Applying the steps of the script
+    pub script: Duration,
+    /// Advancing the mocked clock, which updates the animations
+    pub animations: Duration,
+    /// Rendering the scene, including the evaluation of the bindings and of the layouts
+    pub rendering: Duration,
+}
+
+impl FrameTimings {
+    /// The duration of the whole frame
+    pub fn total(&self) -> Duration {
+        self.script + self.animations + self.rendering
+    }
+}
+
+/// The timings measured by [`Benchmark::run`]. Its `Display` implementation shows a summary
+/// with the median and the maximum of each phase of the frames.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BenchmarkReport {
+    /// The time it took to compile the component
+    pub compile: Duration,
+    /// The time it took to create the instance of the component
+    pub instantiate: Duration,
+    /// The timings of each frame
+    pub frames: Vec<FrameTimings>,
+}
+
+impl BenchmarkReport {
+    /// The median of the durations that `phase` returns for the frames, such as
+    /// `report.median(FrameTimings::total)`
+    pub fn median(&self, phase: impl Fn(&FrameTimings) -> Duration) -> Duration {
+        let mut durations = self.frames.iter().map(phase).collect::<Vec<_>>();
+        durations.sort();
+        durations.get(durations.len() / 2).copied().unwrap_or_default()
+    }
+
+    /// The longest of the durations that `phase` returns for the frames
+    pub fn max(&self, phase: impl Fn(&FrameTimings) -> Duration) -> Duration {
+        self.frames.iter().map(phase).max().unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "compile: {:?}", self.compile)?;
+        writeln!(f, "instantiate: {:?}", self.instantiate)?;
+        writeln!(f, "{} frames (median / max):", self.frames.len())?;
+        let phases: [(&str, fn(&FrameTimings) -> Duration); 4] = [
+            ("script", |t| t.script),
+            ("animations", |t| t.animations),
+            ("rendering", |t| t.rendering),
+            ("total", FrameTimings::total),
+        ];
+        for (name, phase) in phases {
+            writeln!(f, "  {}: {:?} / {:?}", name, self.median(phase), self.max(phase))?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_benchmark_run() {
+    i_slint_backend_testing::init();
+    let mut benchmark = Benchmark::from_source(
+        r#"
+    export Counter := Window {
+        width: 100px;
+        height: 100px;
+        property <int> counter: 1;
+        callback increment(int);
+        increment(step) => { counter += step; }
+        Text { text: counter; }
+        TouchArea { clicked => { counter = 0; } }
+    }"#
+        .into(),
+        "".into(),
+    );
+    benchmark.set_frame_count(5);
+    benchmark.add_step(1, Step::SetProperty("counter".into(), Value::Number(40.)));
+    benchmark.add_step(2, Step::Invoke("increment".into(), vec![Value::Number(2.)]));
+    benchmark.add_step(3, Step::MouseClick(50., 50.));
+    let report = benchmark.run().unwrap();
+    assert_eq!(report.frames.len(), 5);
+    assert!(report.to_string().contains("5 frames"));
+
+    benchmark.add_step(4, Step::SetProperty("countr".into(), Value::Number(0.)));
+    match benchmark.run() {
+        Err(BenchmarkError::Step { frame: 4, .. }) => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    let benchmark =
+        Benchmark::from_source("export Foo := Rectangle { x: unknown; }".into(), "".into());
+    assert!(matches!(benchmark.run(), Err(BenchmarkError::Compile(_))));
+}
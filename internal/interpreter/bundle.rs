@@ -0,0 +1,128 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Component bundles, loaded with [`crate::ComponentCompiler::build_from_bundle`].
+
+    The format of the bundles is described in [`i_slint_compiler::bundle`]. The files of a bundle
+    get a url such as `bundle://1/images/logo.png`, so that the relative imports and the
+    `@image-url` within the bundle resolve to the other files of the bundle.
+
+    The files stay available as long as the [`Bundle`] returned by [`load_bundle`] is alive, which
+    the component compiled from the bundle keeps. The backends load the images lazily and cache
+    them by path, so the images are written to a temporary directory that is removed with the
+    bundle. The fonts cannot be unregistered: a single copy of each distinct font is kept for
+    the rest of the program, so that loading the same bundle again doesn't grow the memory.
+*/
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+thread_local! {
+    /// The files of the bundles loaded in this thread, by url
+    static BUNDLE_FILES: RefCell<HashMap<String, Rc<[u8]>>> = Default::default();
+    /// The images of the bundles that were written to a temporary file, by url
+    static IMAGE_FILES: RefCell<HashMap<String, PathBuf>> = Default::default();
+    /// The fonts of the bundles that are registered, which are never released
+    static REGISTERED_FONTS: RefCell<HashSet<&'static [u8]>> = Default::default();
+    /// Used to give a different url to each bundle
+    static BUNDLE_COUNT: Cell<usize> = Cell::new(0);
+}
+
+/// The files of a loaded bundle, which are released when it is dropped
+pub(crate) struct Bundle {
+    base_url: String,
+    /// The directory in which the images are written
+    image_dir: PathBuf,
+}
+
+impl Bundle {
+    /// The url of a file of this bundle
+    pub fn url(&self, name: &str) -> String {
+        format!("{}{}", self.base_url, name)
+    }
+}
+
+impl Drop for Bundle {
+    fn drop(&mut self) {
+        BUNDLE_FILES
+            .with(|files| files.borrow_mut().retain(|url, _| !url.starts_with(&self.base_url)));
+        IMAGE_FILES
+            .with(|files| files.borrow_mut().retain(|url, _| !url.starts_with(&self.base_url)));
+        // The directory only exists if an image was used
+        std::fs::remove_dir_all(&self.image_dir).ok();
+    }
+}
+
+/// Register the files of the bundle, and return them with the url of the main file
+pub(crate) fn load_bundle(data: &[u8]) -> Result<(Rc<Bundle>, String), String> {
+    let bundle = i_slint_compiler::bundle::read_bundle(data)?;
+    let count = BUNDLE_COUNT.with(|count| {
+        count.set(count.get() + 1);
+        count.get()
+    });
+    let loaded = Rc::new(Bundle {
+        base_url: format!("bundle://{}/", count),
+        image_dir: image_dir(&count.to_string()),
+    });
+    BUNDLE_FILES.with(|files| {
+        let mut files = files.borrow_mut();
+        for (name, data) in bundle.files {
+            files.insert(loaded.url(&name), data.into());
+        }
+    });
+    let main_url = loaded.url(&bundle.main_file);
+    Ok((loaded, main_url))
+}
+
+/// The temporary directory in which the images of the bundle with this number are written
+fn image_dir(bundle_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("slint-bundle-{}-{}", std::process::id(), bundle_id))
+}
+
+/// Return the content of the file if the url refers to a file of a loaded bundle
+pub(crate) fn file_data(url: &str) -> Option<Rc<[u8]>> {
+    if !url.starts_with("bundle://") {
+        return None;
+    }
+    BUNDLE_FILES.with(|files| files.borrow().get(url).cloned())
+}
+
+/// Register the font if the url refers to a file of a loaded bundle. Returns None otherwise.
+pub(crate) fn register_font(url: &str) -> Option<Result<(), Box<dyn std::error::Error>>> {
+    let data = file_data(url)?;
+    Some(REGISTERED_FONTS.with(|fonts| {
+        if fonts.borrow().contains(&*data) {
+            return Ok(());
+        }
+        // The backends need the font for as long as it is registered, which is until the end of
+        // the program
+        let copy: &'static [u8] = Box::leak(data.to_vec().into_boxed_slice());
+        fonts.borrow_mut().insert(copy);
+        crate::register_font_from_memory(copy)
+    }))
+}
+
+/// Return the image if the url refers to an image of a loaded bundle
+pub(crate) fn image(
+    url: &str,
+) -> Option<Result<i_slint_core::graphics::Image, i_slint_core::graphics::LoadImageError>> {
+    if let Some(path) = IMAGE_FILES.with(|files| files.borrow().get(url).cloned()) {
+        return Some(i_slint_core::graphics::Image::load_from_path(&path));
+    }
+    let data = file_data(url)?;
+    let (bundle_id, name) = url["bundle://".len()..].split_once('/')?;
+    let path = image_dir(bundle_id).join(name);
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, &data));
+    if let Err(err) = written {
+        i_slint_core::debug_log!("Could not write the image {} of the bundle: {}", url, err);
+        return Some(Err(Default::default()));
+    }
+    IMAGE_FILES.with(|files| files.borrow_mut().insert(url.into(), path.clone()));
+    Some(i_slint_core::graphics::Image::load_from_path(&path))
+}
@@ -1,7 +1,8 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use crate::{api::Value, dynamic_type, eval};
+use crate::api::{GlobalImplementation, LifecycleEvent, SetGlobalImplementationError, Value};
+use crate::{dynamic_type, eval};
 
 use core::convert::TryInto;
 use core::ptr::NonNull;
@@ -12,7 +13,9 @@
 use i_slint_compiler::*;
 use i_slint_compiler::{diagnostics::BuildDiagnostics, object_tree::PropertyDeclaration};
 use i_slint_core::api::Window;
-use i_slint_core::component::{Component, ComponentRef, ComponentRefPin, ComponentVTable};
+use i_slint_core::component::{
+    Component, ComponentRc, ComponentRef, ComponentRefPin, ComponentVTable,
+};
 use i_slint_core::item_tree::{
     ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, TraversalOrder, VisitChildrenResult,
 };
@@ -24,6 +27,7 @@
 use i_slint_core::rtti::{self, AnimatedBindingKind, FieldOffset, PropertyInfo};
 use i_slint_core::window::{WindowHandleAccess, WindowRc};
 use i_slint_core::{Brush, Color, Property, SharedString, SharedVector};
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::{pin::Pin, rc::Rc};
@@ -61,12 +65,19 @@ pub fn window(&self) -> &Window {
 impl<'id> Drop for ComponentBox<'id> {
     fn drop(&mut self) {
         let instance_ref = self.borrow_instance();
+        instance_ref
+            .component_type
+            .extra_data_offset
+            .apply(instance_ref.as_ref())
+            .lifecycle_handlers
+            .notify(LifecycleEvent::Destroyed);
         if let Some(window) = eval::window_ref(instance_ref) {
             i_slint_core::component::init_component_items(
                 instance_ref.instance,
                 instance_ref.component_type.item_tree.as_slice(),
                 window,
             );
+            window.check_destroyed_focus_item();
         }
     }
 }
@@ -103,6 +114,8 @@ pub(crate) struct RepeaterWithinComponent<'par_id, 'sub_id> {
     pub(crate) component_to_repeat: Rc<ComponentDescription<'sub_id>>,
     /// The model
     pub(crate) model: Expression,
+    /// True when this is the placeholder of a ComponentContainer, and the model is a component factory
+    pub(crate) is_component_container: bool,
     /// Offset of the `Repeater`
     offset: FieldOffset<Instance<'par_id>, Repeater<ErasedComponentBox>>,
 }
@@ -113,7 +126,38 @@ impl RepeatedComponent for ErasedComponentBox {
     fn update(&self, index: usize, data: Self::Data) {
         generativity::make_guard!(guard);
         let s = self.unerase(guard);
+        if s.component_type.parent_component_offset.is_none() {
+            // This is a component created by the factory of a ComponentContainer
+            return;
+        }
+        s.component_type.set_property(s.borrow(), "index", index.try_into().unwrap()).unwrap();
+        if let Value::Struct(data) = &data {
+            for (name, value) in data.iter() {
+                // Ignore the fields that are not in the type of the model data
+                s.component_type
+                    .set_property(s.borrow(), &format!("model_data.{}", name), value.clone())
+                    .ok();
+            }
+        }
+        s.component_type.set_property(s.borrow(), "model_data", data).unwrap();
+    }
+
+    fn update_fields(&self, index: usize, data: Self::Data, fields: &[SharedString]) {
+        generativity::make_guard!(guard);
+        let s = self.unerase(guard);
+        if s.component_type.parent_component_offset.is_none() {
+            return;
+        }
         s.component_type.set_property(s.borrow(), "index", index.try_into().unwrap()).unwrap();
+        if let Value::Struct(data) = &data {
+            for name in fields {
+                if let Some(value) = data.get_field(name) {
+                    s.component_type
+                        .set_property(s.borrow(), &format!("model_data.{}", name), value.clone())
+                        .ok();
+                }
+            }
+        }
         s.component_type.set_property(s.borrow(), "model_data", data).unwrap();
     }
 
@@ -177,10 +221,55 @@ fn parent_item(self: Pin<&Self>, index: usize, result: &mut ItemWeak) {
 #[derive(Default)]
 pub(crate) struct ComponentExtraData {
     pub(crate) globals: crate::global_component::GlobalStorage,
+    /// Shared so that callbacks that may outlive the instance can check if it is still alive
     pub(crate) self_weak:
-        once_cell::unsync::OnceCell<vtable::VWeak<ComponentVTable, ErasedComponentBox>>,
+        Rc<once_cell::unsync::OnceCell<vtable::VWeak<ComponentVTable, ErasedComponentBox>>>,
     // resource id -> file path
     pub(crate) embedded_file_resources: HashMap<usize, String>,
+    /// The item of the ComponentContainer when this component was created by a component factory
+    pub(crate) embedding_parent: once_cell::unsync::OnceCell<ItemWeak>,
+    /// Children inserted at runtime in the ComponentContainers. The key is the index of the
+    /// placeholder in the `repeater` vec of the ComponentDescription
+    pub(crate) dynamic_children:
+        core::cell::RefCell<HashMap<usize, Vec<vtable::VRc<ComponentVTable, ErasedComponentBox>>>>,
+    /// Shared with the visibility listener of the window, which may outlive the instance
+    pub(crate) lifecycle_handlers: Rc<LifecycleHandlers>,
+}
+
+/// The handlers registered with [`crate::ComponentInstance::on_lifecycle_event`]
+#[derive(Default)]
+pub(crate) struct LifecycleHandlers {
+    handlers: core::cell::RefCell<Vec<Rc<dyn Fn(LifecycleEvent)>>>,
+    /// Whether `Initialized` was notified, which happens the first time the window is shown
+    initialized: core::cell::Cell<bool>,
+}
+
+impl LifecycleHandlers {
+    /// Adds a handler, which is notified right away if the window is already shown
+    pub(crate) fn add(&self, handler: Rc<dyn Fn(LifecycleEvent)>, window_visible: bool) {
+        self.handlers.borrow_mut().push(handler.clone());
+        if window_visible {
+            if !self.initialized.replace(true) {
+                handler(LifecycleEvent::Initialized);
+            }
+            handler(LifecycleEvent::Shown);
+        }
+    }
+
+    pub(crate) fn notify(&self, event: LifecycleEvent) {
+        // Cloned so that the handlers can register other handlers
+        let handlers = self.handlers.borrow().clone();
+        for handler in handlers {
+            handler(event);
+        }
+    }
+
+    fn visibility_changed(&self, visible: bool) {
+        if visible && !self.initialized.replace(true) {
+            self.notify(LifecycleEvent::Initialized);
+        }
+        self.notify(if visible { LifecycleEvent::Shown } else { LifecycleEvent::Hidden });
+    }
 }
 
 struct ErasedRepeaterWithinComponent<'id>(RepeaterWithinComponent<'id, 'static>);
@@ -267,6 +356,8 @@ pub struct ComponentDescription<'id> {
     repeater: Vec<ErasedRepeaterWithinComponent<'id>>,
     /// Map the Element::id of the repeater to the index in the `repeater` vec
     pub repeater_names: HashMap<String, usize>,
+    /// Map the id of the named ComponentContainers to the index of their placeholder in the `repeater` vec
+    pub(crate) component_containers: HashMap<String, usize>,
     /// Offset to a Option<ComponentPinRef>
     pub(crate) parent_component_offset:
         Option<FieldOffset<Instance<'id>, Option<ComponentRefPin<'id>>>>,
@@ -284,6 +375,16 @@ pub struct ComponentDescription<'id> {
     /// Map of all exported global singletons and their index in the compiled_globals vector. The key
     /// is the normalized name of the global.
     exported_globals_by_name: BTreeMap<String, usize>,
+    /// The implementations registered with `ComponentDefinition::set_global_implementation()`, by
+    /// index in the compiled_globals vector
+    global_implementations: RefCell<HashMap<usize, Rc<dyn GlobalImplementation>>>,
+
+    /// The structs and enums exported by the document of the root component, with their exported
+    /// name. Empty for the other components.
+    exported_types: Vec<(String, Type)>,
+
+    /// The bundle from which the component was compiled, whose files are released with it
+    pub(crate) bundle: RefCell<Option<Rc<crate::bundle::Bundle>>>,
 }
 
 fn internal_properties_to_public<'a>(
@@ -300,7 +401,7 @@ fn internal_properties_to_public<'a>(
             })
             .map(|n| n.to_string())
             .unwrap_or_else(|| s.clone());
-        (name, v.property_type.clone())
+        (name, v.public_api_type())
     })
 }
 
@@ -319,6 +420,16 @@ pub fn properties(
         internal_properties_to_public(self.public_properties.iter())
     }
 
+    /// List of the enums declared in .slint that are used by this component
+    pub fn enums(&self) -> Vec<Rc<langtype::Enumeration>> {
+        self.original.used_types.borrow().enums.clone()
+    }
+
+    /// List of the structs and enums exported by the document, with their exported name
+    pub fn exported_types(&self) -> &[(String, Type)] {
+        &self.exported_types
+    }
+
     /// List names of exported global singletons
     pub fn global_names(&self) -> impl Iterator<Item = String> + '_ {
         self.compiled_globals
@@ -337,6 +448,30 @@ pub fn global_properties(
             .map(|global| internal_properties_to_public(global.public_properties()))
     }
 
+    pub fn set_global_implementation(
+        &self,
+        name: &str,
+        implementation: Option<Rc<dyn GlobalImplementation>>,
+    ) -> Result<(), SetGlobalImplementationError> {
+        let global_idx = *self
+            .exported_globals_by_name
+            .get(crate::normalize_identifier(name).as_ref())
+            .ok_or(SetGlobalImplementationError::NoSuchGlobal)?;
+        let mut implementations = self.global_implementations.borrow_mut();
+        match implementation {
+            Some(implementation) => {
+                if !self.compiled_globals[global_idx].can_be_implemented_by(&*implementation) {
+                    return Err(SetGlobalImplementationError::CannotImplement);
+                }
+                implementations.insert(global_idx, implementation);
+            }
+            None => {
+                implementations.remove(&global_idx);
+            }
+        }
+        Ok(())
+    }
+
     /// Instantiate a runtime component from this ComponentDescription
     pub fn create(
         self: Rc<Self>,
@@ -358,16 +493,158 @@ pub fn create_with_existing_window(
         self: Rc<Self>,
         window: &i_slint_core::window::WindowRc,
     ) -> vtable::VRc<ComponentVTable, ErasedComponentBox> {
+        // Keep the size of the window when replacing its component, unless the new component sets it
+        let root_element = self.original.root_element.clone();
+        let previous_geometry = window.window_item_geometry().filter(|_| {
+            let root_element = root_element.borrow();
+            !root_element.bindings.contains_key("width")
+                && !root_element.bindings.contains_key("height")
+        });
         let component_ref = instantiate(self, None, Some(window), Default::default());
+        window.set_component(&vtable::VRc::into_dyn(component_ref.clone()));
+        if let Some((width, height)) = previous_geometry.filter(|(w, h)| *w > 0. && *h > 0.) {
+            window.set_window_item_geometry(width, height);
+        }
+        component_ref.run_setup_code();
         component_ref
-            .as_pin_ref()
-            .window()
-            .window_handle()
-            .set_component(&vtable::VRc::into_dyn(component_ref.clone()));
+    }
+
+    /// Instantiate the component to be shown within a ComponentContainer
+    pub fn create_embedded(
+        self: Rc<Self>,
+        ctx: crate::api::FactoryContext,
+    ) -> vtable::VRc<ComponentVTable, ErasedComponentBox> {
+        let component_ref = instantiate(self, None, Some(&ctx.window), Default::default());
+        {
+            generativity::make_guard!(guard);
+            let comp = component_ref.unerase(guard);
+            let instance_ref = comp.borrow_instance();
+            let extra_data =
+                instance_ref.component_type.extra_data_offset.apply(instance_ref.as_ref());
+            extra_data.embedding_parent.set(ctx.parent_item.clone()).ok();
+
+            let root_element = instance_ref.component_type.original.root_element.clone();
+            let root_item_info =
+                &instance_ref.component_type.items[root_element.borrow().id.as_str()];
+            let root_item = unsafe { root_item_info.item_from_component(instance_ref.as_ptr()) };
+            // The container is not a window: don't draw the window background
+            if let Some(background) = root_item_info.rtti.properties.get("background") {
+                background
+                    .set(
+                        root_item,
+                        Value::Brush(Brush::SolidColor(Color::from_argb_u8(0, 0, 0, 0))),
+                        None,
+                    )
+                    .unwrap();
+            }
+
+            // The root item fills the ComponentContainer, unless it has its own size
+            let parent_geometry = |parent_item: &ItemWeak| {
+                parent_item
+                    .upgrade()
+                    .map_or_else(Default::default, |item| item.borrow().as_ref().geometry())
+            };
+            let has_binding = |prop: &str| root_element.borrow().bindings.contains_key(prop);
+            if let Some(width) =
+                root_item_info.rtti.properties.get("width").filter(|_| !has_binding("width"))
+            {
+                let parent_item = ctx.parent_item.clone();
+                width.set_binding(
+                    root_item,
+                    Box::new(move || parent_geometry(&parent_item).width().into()),
+                    AnimatedBindingKind::NotAnimated,
+                );
+            }
+            if let Some(height) =
+                root_item_info.rtti.properties.get("height").filter(|_| !has_binding("height"))
+            {
+                let parent_item = ctx.parent_item;
+                height.set_binding(
+                    root_item,
+                    Box::new(move || parent_geometry(&parent_item).height().into()),
+                    AnimatedBindingKind::NotAnimated,
+                );
+            }
+        }
         component_ref.run_setup_code();
         component_ref
     }
 
+    /// Instantiate `child` and insert it at `index` in the children of the ComponentContainer
+    /// with the given id.
+    pub fn insert_child(
+        &self,
+        component: ComponentRefPin,
+        container: &str,
+        index: usize,
+        child: Rc<ComponentDescription<'_>>,
+    ) -> Result<vtable::VRc<ComponentVTable, ErasedComponentBox>, crate::api::ChildError> {
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        let repeater_index = *self
+            .component_containers
+            .get(container)
+            .ok_or(crate::api::ChildError::NoSuchElement)?;
+        let extra_data = c.component_type.extra_data_offset.apply(c.as_ref());
+        if index > extra_data.dynamic_children.borrow().get(&repeater_index).map_or(0, Vec::len) {
+            return Err(crate::api::ChildError::IndexOutOfBounds);
+        }
+
+        let window = c.component_type.window_offset.apply(c.as_ref()).as_ref().unwrap();
+        let placeholder = self.repeater[repeater_index]
+            .0
+            .component_to_repeat
+            .original
+            .parent_element
+            .upgrade()
+            .unwrap();
+        let placeholder_index = *placeholder.borrow().item_index.get().unwrap();
+        let self_rc = c.self_weak().get().unwrap().clone().into_dyn().upgrade().unwrap();
+        let instance = child.create_embedded(crate::api::FactoryContext {
+            window: window.window_handle().clone(),
+            parent_item: ItemRc::new(self_rc, placeholder_index).parent_item(),
+        });
+        extra_data
+            .dynamic_children
+            .borrow_mut()
+            .entry(repeater_index)
+            .or_default()
+            .insert(index, instance.clone());
+        window.request_redraw();
+        Ok(instance)
+    }
+
+    /// Remove a child that was inserted with [`Self::insert_child`] from the ComponentContainer
+    /// with the given id.
+    pub fn remove_child(
+        &self,
+        component: ComponentRefPin,
+        container: &str,
+        child: &vtable::VRc<ComponentVTable, ErasedComponentBox>,
+    ) -> Result<(), crate::api::ChildError> {
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        let repeater_index = *self
+            .component_containers
+            .get(container)
+            .ok_or(crate::api::ChildError::NoSuchElement)?;
+        let extra_data = c.component_type.extra_data_offset.apply(c.as_ref());
+        let removed = {
+            let mut dynamic_children = extra_data.dynamic_children.borrow_mut();
+            let children = dynamic_children.entry(repeater_index).or_default();
+            let position = children
+                .iter()
+                .position(|existing| vtable::VRc::ptr_eq(existing, child))
+                .ok_or(crate::api::ChildError::NotAChild)?;
+            children.remove(position)
+        };
+        // Drop the child after the borrow is released, in case it refers to this component
+        drop(removed);
+        let window = c.component_type.window_offset.apply(c.as_ref()).as_ref().unwrap();
+        window.request_redraw();
+        Ok(())
+    }
+
     /// Set a value to property.
     ///
     /// Return an error if the property with this name does not exist in this component,
@@ -398,14 +675,44 @@ pub fn set_property(
         }
     }
 
+    /// Animate a property towards the value, with the given animation.
+    ///
+    /// Return an error if the property with this name does not exist in this component,
+    /// if the value is the wrong type, or if the property cannot be animated.
+    /// Panics if the component is not an instance corresponding to this ComponentDescription,
+    pub fn animate_property(
+        &self,
+        component: ComponentRefPin,
+        name: &str,
+        value: Value,
+        animation: i_slint_core::items::PropertyAnimation,
+    ) -> Result<(), crate::api::SetPropertyError> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            panic!("mismatch instance and vtable");
+        }
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        if let Some(alias) = self
+            .original
+            .root_element
+            .borrow()
+            .property_declarations
+            .get(name)
+            .and_then(|d| d.is_alias.as_ref())
+        {
+            eval::animate_property(c, &alias.element(), alias.name(), value, animation)
+        } else {
+            eval::animate_property(c, &self.original.root_element, name, value, animation)
+        }
+    }
+
     /// Set a binding to a property
     ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
     /// or if the property with this name does not exist in this component
-    #[allow(unused)]
     pub fn set_binding(
         &self,
-        component: ComponentRef,
+        component: ComponentRefPin,
         name: &str,
         binding: Box<dyn Fn() -> Value>,
     ) -> Result<(), ()> {
@@ -413,10 +720,13 @@ pub fn set_binding(
             return Err(());
         }
         let x = self.custom_properties.get(name).ok_or(())?;
+        generativity::make_guard!(guard);
+        // Safety: we just verified that the component has the right vtable
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
         unsafe {
             x.prop
                 .set_binding(
-                    Pin::new_unchecked(&*component.as_ptr().add(x.offset)),
+                    Pin::new_unchecked(&*c.as_ptr().add(x.offset)),
                     binding,
                     i_slint_core::rtti::AnimatedBindingKind::NotAnimated,
                 )
@@ -453,7 +763,7 @@ pub fn get_property(&self, component: ComponentRefPin, name: &str) -> Result<Val
     /// Sets an handler for a callback
     ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
-    /// or if the property with this name does not exist in this component
+    /// or if the property with this name does not exist in this component, or is a function
     pub fn set_callback_handler(
         &self,
         component: Pin<ComponentRef>,
@@ -463,6 +773,9 @@ pub fn set_callback_handler(
         if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
             return Err(());
         }
+        if self.public_properties.get(name).map_or(false, |d| d.is_function) {
+            return Err(());
+        }
         if let Some(alias) = self
             .original
             .root_element
@@ -509,6 +822,23 @@ pub fn set_callback_handler(
         Ok(())
     }
 
+    /// Calls the specified function
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// or if there is no function with this name in this component
+    pub fn invoke_function(
+        &self,
+        component: ComponentRefPin,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Value, ()> {
+        if !self.public_properties.get(name).map_or(false, |d| d.is_function) {
+            return Err(());
+        }
+        // Functions are implemented as callbacks whose handler is the body of the function
+        self.invoke_callback(component, name, args)
+    }
+
     /// Emits the specified callback
     ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
@@ -590,11 +920,58 @@ extern "C" fn visit_children_item(
             let rep_in_comp = unsafe { instance_ref.component_type.repeater[index].get_untagged() };
             ensure_repeater_updated(instance_ref, rep_in_comp);
             let repeater = rep_in_comp.offset.apply_pin(instance_ref.instance);
-            repeater.visit(order, visitor)
+            if !rep_in_comp.is_component_container {
+                return repeater.visit(order, visitor);
+            }
+            visit_component_container(instance_ref, repeater, index, order, visitor)
         },
     )
 }
 
+/// Visit the component created by the factory of a ComponentContainer, and the children
+/// inserted with `insert_child`, which are above it.
+fn visit_component_container(
+    instance_ref: InstanceRef<'_, '_>,
+    repeater: Pin<&Repeater<ErasedComponentBox>>,
+    repeater_index: usize,
+    order: TraversalOrder,
+    mut visitor: ItemVisitorRefMut,
+) -> VisitChildrenResult {
+    let extra_data = instance_ref.component_type.extra_data_offset.apply(instance_ref.as_ref());
+    // Clone the children because the visitor may insert or remove some
+    let children =
+        extra_data.dynamic_children.borrow().get(&repeater_index).cloned().unwrap_or_default();
+    let visit_children = |visitor: &mut ItemVisitorRefMut| {
+        for i in 0..children.len() {
+            let i = if order == TraversalOrder::BackToFront { i } else { children.len() - i - 1 };
+            if children[i]
+                .as_pin_ref()
+                .visit_children_item(-1, order, visitor.borrow_mut())
+                .has_aborted()
+            {
+                return VisitChildrenResult::abort(repeater.len() + i, 0);
+            }
+        }
+        VisitChildrenResult::CONTINUE
+    };
+    match order {
+        TraversalOrder::BackToFront => {
+            let result = repeater.visit(order, visitor.borrow_mut());
+            if result.has_aborted() {
+                return result;
+            }
+            visit_children(&mut visitor)
+        }
+        TraversalOrder::FrontToBack => {
+            let result = visit_children(&mut visitor);
+            if result.has_aborted() {
+                return result;
+            }
+            repeater.visit(order, visitor)
+        }
+    }
+}
+
 /// Make sure that the repeater is updated
 fn ensure_repeater_updated<'id>(
     instance_ref: InstanceRef<'_, 'id>,
@@ -608,6 +985,11 @@ fn ensure_repeater_updated<'id>(
             .apply(instance_ref.as_ref())
             .as_ref()
             .unwrap();
+        if let Some(instance) =
+            instantiate_from_component_factory(instance_ref, rep_in_comp, window.window_handle())
+        {
+            return instance;
+        }
         let instance = instantiate(
             rep_in_comp.component_to_repeat.clone(),
             Some(instance_ref.borrow()),
@@ -647,6 +1029,34 @@ fn ensure_repeater_updated<'id>(
     }
 }
 
+/// When the repeater is the placeholder of a ComponentContainer, create the component with the
+/// component factory.
+///
+/// Returns None if this is not a ComponentContainer, or if the factory did not create a component.
+fn instantiate_from_component_factory<'id>(
+    instance_ref: InstanceRef<'_, 'id>,
+    rep_in_comp: &RepeaterWithinComponent<'id, '_>,
+    window: &WindowRc,
+) -> Option<vtable::VRc<ComponentVTable, ErasedComponentBox>> {
+    if !rep_in_comp.is_component_container {
+        return None;
+    }
+    let factory = match eval::eval_expression(
+        &rep_in_comp.model,
+        &mut eval::EvalLocalContext::from_component_instance(instance_ref),
+    ) {
+        Value::ComponentFactory(factory) => factory,
+        _ => return None,
+    };
+    let placeholder = rep_in_comp.component_to_repeat.original.parent_element.upgrade()?;
+    let placeholder_index = *placeholder.borrow().item_index.get()?;
+    let self_rc = instance_ref.self_weak().get()?.clone().into_dyn().upgrade()?;
+    factory.build(crate::api::FactoryContext {
+        window: window.clone(),
+        parent_item: ItemRc::new(self_rc, placeholder_index).parent_item(),
+    })
+}
+
 /// Information attached to a builtin item
 pub(crate) struct ItemRTTI {
     vtable: &'static ItemVTable,
@@ -678,9 +1088,57 @@ fn rtti_for<T: 'static + Default + rtti::BuiltinItem + vtable::HasStaticVTable<I
 pub async fn load(
     source: String,
     path: std::path::PathBuf,
-    mut compiler_config: CompilerConfiguration,
+    compiler_config: CompilerConfiguration,
     guard: generativity::Guard<'_>,
 ) -> (Result<Rc<ComponentDescription<'_>>, ()>, i_slint_compiler::diagnostics::BuildDiagnostics) {
+    let (doc, diag) = compile(source, path, compiler_config).await;
+    match doc {
+        Some(doc) => (Ok(describe_root_component(&doc, &doc.root_component, guard)), diag),
+        None => (Err(()), diag),
+    }
+}
+
+/// Create the ComponentDescriptions of all the components exported by a source, with their
+/// exported name. The source is compiled once for all of them.
+pub async fn load_all(
+    source: String,
+    path: std::path::PathBuf,
+    mut compiler_config: CompilerConfiguration,
+) -> (
+    Result<Vec<(String, ErasedComponentDescription)>, ()>,
+    i_slint_compiler::diagnostics::BuildDiagnostics,
+) {
+    compiler_config.all_exported_components = true;
+    let (doc, diag) = compile(source, path, compiler_config).await;
+    let doc = match doc {
+        Some(doc) => doc,
+        None => return (Err(()), diag),
+    };
+    let root_description = {
+        generativity::make_guard!(guard);
+        ErasedComponentDescription::from(describe_root_component(&doc, &doc.root_component, guard))
+    };
+    // The last component can be exported under several names
+    let mut descriptions = doc
+        .exports()
+        .iter()
+        .filter(|(_, ty)| matches!(ty, Type::Component(c) if Rc::ptr_eq(c, &doc.root_component)))
+        .map(|(name, _)| (name.name.clone(), root_description.clone()))
+        .collect::<Vec<_>>();
+    for (name, root_component) in &doc.other_roots {
+        generativity::make_guard!(guard);
+        let description = describe_root_component(&doc, root_component, guard);
+        descriptions.push((name.clone(), description.into()));
+    }
+    (Ok(descriptions), diag)
+}
+
+/// Compile the source into a document, or return None if there are errors
+async fn compile(
+    source: String,
+    path: std::path::PathBuf,
+    mut compiler_config: CompilerConfiguration,
+) -> (Option<object_tree::Document>, i_slint_compiler::diagnostics::BuildDiagnostics) {
     if compiler_config.style.is_none()
         && std::env::var("SLINT_STYLE").is_err()
         && std::env::var("SIXTYFPS_STYLE").is_err()
@@ -696,17 +1154,34 @@ pub async fn load(
     let mut diag = BuildDiagnostics::default();
     let syntax_node = parser::parse(source, Some(path.as_path()), &mut diag);
     if diag.has_error() {
-        return (Err(()), diag);
+        return (None, diag);
     }
     let (doc, mut diag) = compile_syntax_node(syntax_node, diag, compiler_config).await;
     if diag.has_error() {
-        return (Err(()), diag);
+        return (None, diag);
     }
     if matches!(doc.root_component.root_element.borrow().base_type, Type::Invalid | Type::Void) {
         diag.push_error_with_span("No component found".into(), Default::default());
-        return (Err(()), diag);
+        return (None, diag);
     }
-    (Ok(generate_component(&doc.root_component, guard)), diag)
+    (Some(doc), diag)
+}
+
+/// Create the ComponentDescription of one of the root components of the document
+fn describe_root_component<'id>(
+    doc: &object_tree::Document,
+    root_component: &Rc<object_tree::Component>,
+    guard: generativity::Guard<'id>,
+) -> Rc<ComponentDescription<'id>> {
+    let mut description = generate_component(root_component, guard);
+    let description_mut = Rc::get_mut(&mut description).expect("the description was just created");
+    description_mut.exported_types = doc
+        .exports()
+        .iter()
+        .filter(|(_, ty)| matches!(ty, Type::Struct { .. } | Type::Enumeration(_)))
+        .map(|(name, ty)| (name.name.clone(), ty.clone()))
+        .collect();
+    description
 }
 
 pub(crate) fn generate_component<'id>(
@@ -734,6 +1209,7 @@ pub(crate) fn generate_component<'id>(
                 rtti_for::<BoxShadow>(),
                 rtti_for::<Rotate>(),
                 rtti_for::<Opacity>(),
+                rtti_for::<Accessible>(),
             ]
             .iter()
             .cloned(),
@@ -765,6 +1241,7 @@ struct TreeBuilder<'id> {
         type_builder: dynamic_type::TypeBuilder<'id>,
         repeater: Vec<ErasedRepeaterWithinComponent<'id>>,
         repeater_names: HashMap<String, usize>,
+        component_containers: HashMap<String, usize>,
         rtti: Rc<HashMap<&'static str, Rc<ItemRTTI>>>,
     }
     impl<'id> generator::ItemTreeBuilder for TreeBuilder<'id> {
@@ -782,12 +1259,16 @@ fn push_repeated_item(
             let item = item_rc.borrow();
             let base_component = item.base_type.as_component();
             self.repeater_names.insert(item.id.clone(), self.repeater.len());
+            if let Some(id) = &item.repeated.as_ref().unwrap().component_container_id {
+                self.component_containers.insert(id.clone(), self.repeater.len());
+            }
             generativity::make_guard!(guard);
             self.repeater.push(
                 RepeaterWithinComponent {
                     component_to_repeat: generate_component(base_component, guard),
                     offset: self.type_builder.add_field_type::<Repeater<ErasedComponentBox>>(),
                     model: item.repeated.as_ref().unwrap().model.clone(),
+                    is_component_container: item.repeated.as_ref().unwrap().is_component_container,
                 }
                 .into(),
             );
@@ -855,6 +1336,7 @@ fn enter_component_children(
         type_builder: dynamic_type::TypeBuilder::new(guard),
         repeater: vec![],
         repeater_names: HashMap::new(),
+        component_containers: HashMap::new(),
         rtti: Rc::new(rtti),
     };
 
@@ -920,7 +1402,7 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
                 property_info::<i_slint_core::properties::StateInfo>()
             }
             Type::Struct { .. } => property_info::<Value>(),
-            Type::Array(_) => property_info::<Value>(),
+            Type::Array(_) | Type::Map(_) => property_info::<Value>(),
             Type::Percent => property_info::<f32>(),
             Type::Enumeration(e) => match e.name.as_ref() {
                 "LayoutAlignment" => property_info::<i_slint_core::layout::LayoutAlignment>(),
@@ -940,9 +1422,15 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
                 "DialogButtonRole" => property_info::<i_slint_core::items::DialogButtonRole>(),
                 "PointerEventButton" => property_info::<i_slint_core::items::PointerEventButton>(),
                 "PointerEventKind" => property_info::<i_slint_core::items::PointerEventKind>(),
+                "LogicalKey" => property_info::<i_slint_core::input::LogicalKey>(),
+                "AccessibleRole" => property_info::<i_slint_core::items::AccessibleRole>(),
+                // Enums declared in .slint don't have a native counterpart
+                _ if e.node.is_some() => property_info::<Value>(),
                 _ => panic!("unknown enum"),
             },
             Type::LayoutCache => property_info::<SharedVector<f32>>(),
+            Type::ComponentFactory => property_info::<Value>(),
+            Type::DateTime => property_info::<i_slint_core::api::DateTime>(),
             _ => panic!("bad type {:?}", &decl.property_type),
         };
         custom_properties.insert(
@@ -962,6 +1450,22 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
             "model_data".into(),
             PropertiesWithinComponent { offset: builder.type_builder.add_field(type_info), prop },
         );
+        // One property per field of the model data, so that a binding only depends on the
+        // fields it uses
+        if let Type::Struct { fields, .. } =
+            (Expression::RepeaterModelReference { element: component.parent_element.clone() }).ty()
+        {
+            for name in fields.keys() {
+                let (prop, type_info) = property_info::<Value>();
+                custom_properties.insert(
+                    format!("model_data.{}", name),
+                    PropertiesWithinComponent {
+                        offset: builder.type_builder.add_field(type_info),
+                        prop,
+                    },
+                );
+            }
+        }
     } else {
         let (prop, type_info) = property_info::<f32>();
         custom_properties.insert(
@@ -1029,12 +1533,16 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
         original: component.clone(),
         repeater: builder.repeater,
         repeater_names: builder.repeater_names,
+        component_containers: builder.component_containers,
         parent_component_offset,
         window_offset,
         extra_data_offset,
         public_properties,
         compiled_globals,
         exported_globals_by_name,
+        global_implementations: Default::default(),
+        exported_types: Vec::new(),
+        bundle: Default::default(),
     };
 
     Rc::new(t)
@@ -1046,21 +1554,56 @@ pub fn animation_for_property(
 ) -> AnimatedBindingKind {
     match animation {
         Some(i_slint_compiler::object_tree::PropertyAnimation::Static(anim_elem)) => {
-            AnimatedBindingKind::Animation(eval::new_struct_with_bindings(
-                &anim_elem.borrow().bindings,
-                &mut eval::EvalLocalContext::from_component_instance(component),
-            ))
+            let finished = anim_elem.borrow().bindings.get("finished").map(|b| {
+                let handler = b.borrow().expression.clone();
+                animation_finished_callback(component, move |context| {
+                    eval::eval_expression(&handler, context);
+                })
+            });
+            AnimatedBindingKind::Animation(
+                eval::new_struct_with_bindings(
+                    &anim_elem.borrow().bindings,
+                    &mut eval::EvalLocalContext::from_component_instance(component),
+                ),
+                finished,
+            )
         }
         Some(i_slint_compiler::object_tree::PropertyAnimation::Transition {
             animations,
             state_ref,
         }) => {
+            let finished = animations
+                .iter()
+                .any(|a| a.animation.borrow().bindings.contains_key("finished"))
+                .then(|| {
+                    let animations = animations.clone();
+                    let state_ref = state_ref.clone();
+                    animation_finished_callback(component, move |context| {
+                        let state = eval::eval_expression(&state_ref, context);
+                        let state_info: i_slint_core::properties::StateInfo =
+                            state.try_into().unwrap();
+                        let handler = find_transition(&animations, &state_info).and_then(|a| {
+                            Some(
+                                a.animation
+                                    .borrow()
+                                    .bindings
+                                    .get("finished")?
+                                    .borrow()
+                                    .expression
+                                    .clone(),
+                            )
+                        });
+                        if let Some(handler) = handler {
+                            eval::eval_expression(&handler, context);
+                        }
+                    })
+                });
             let component_ptr = component.as_ptr();
             let vtable = NonNull::from(&component.component_type.ct).cast();
             let animations = animations.clone();
             let state_ref = state_ref.clone();
-            AnimatedBindingKind::Transition(Box::new(
-                move || -> (PropertyAnimation, i_slint_core::animations::Instant) {
+            AnimatedBindingKind::Transition(
+                Box::new(move || -> (PropertyAnimation, i_slint_core::animations::Instant) {
                     generativity::make_guard!(guard);
                     let component = unsafe {
                         InstanceRef::from_pin_ref(
@@ -1075,27 +1618,54 @@ pub fn animation_for_property(
                     let mut context = eval::EvalLocalContext::from_component_instance(component);
                     let state = eval::eval_expression(&state_ref, &mut context);
                     let state_info: i_slint_core::properties::StateInfo = state.try_into().unwrap();
-                    for a in &animations {
-                        if (a.is_out && a.state_id == state_info.previous_state)
-                            || (!a.is_out && a.state_id == state_info.current_state)
-                        {
-                            return (
-                                eval::new_struct_with_bindings(
-                                    &a.animation.borrow().bindings,
-                                    &mut context,
-                                ),
-                                state_info.change_time,
-                            );
-                        }
+                    match find_transition(&animations, &state_info) {
+                        Some(a) => (
+                            eval::new_struct_with_bindings(
+                                &a.animation.borrow().bindings,
+                                &mut context,
+                            ),
+                            state_info.change_time,
+                        ),
+                        None => Default::default(),
                     }
-                    Default::default()
-                },
-            ))
+                }),
+                finished,
+            )
         }
         None => AnimatedBindingKind::NotAnimated,
     }
 }
 
+/// Returns the transition that applies to the change of state described by `state_info`
+fn find_transition<'a>(
+    animations: &'a [i_slint_compiler::object_tree::TransitionPropertyAnimation],
+    state_info: &i_slint_core::properties::StateInfo,
+) -> Option<&'a i_slint_compiler::object_tree::TransitionPropertyAnimation> {
+    animations.iter().find(|a| {
+        (a.is_out && a.state_id == state_info.previous_state)
+            || (!a.is_out && a.state_id == state_info.current_state)
+    })
+}
+
+/// Wrap the handler of a `finished` callback of an animation so it is evaluated in the
+/// context of the component, unless the component was destroyed in the meantime.
+fn animation_finished_callback(
+    component: InstanceRef,
+    run_handler: impl Fn(&mut eval::EvalLocalContext) + 'static,
+) -> i_slint_core::animations::AnimationFinishedCallback {
+    let self_weak =
+        component.component_type.extra_data_offset.apply(component.as_ref()).self_weak.clone();
+    Rc::new(move || {
+        if let Some(comp_rc) = self_weak.get().and_then(|weak| weak.upgrade()) {
+            generativity::make_guard!(guard);
+            let comp = comp_rc.unerase(guard);
+            let mut context =
+                eval::EvalLocalContext::from_component_instance(comp.borrow_instance());
+            run_handler(&mut context);
+        }
+    })
+}
+
 pub fn instantiate(
     component_type: Rc<ComponentDescription>,
     parent_ctx: Option<ComponentRefPin>,
@@ -1108,9 +1678,11 @@ pub fn instantiate(
         *component_type.parent_component_offset.unwrap().apply_mut(instance.as_mut()) =
             Some(parent);
     } else {
-        for g in &component_type.compiled_globals {
-            crate::global_component::instantiate(g, &mut globals);
+        let implementations = component_type.global_implementations.borrow();
+        for (index, g) in component_type.compiled_globals.iter().enumerate() {
+            crate::global_component::instantiate(g, &mut globals, implementations.get(&index));
         }
+        drop(implementations);
         let extra_data = component_type.extra_data_offset.apply_mut(instance.as_mut());
         extra_data.globals = globals;
 
@@ -1121,6 +1693,15 @@ pub fn instantiate(
             .iter()
             .map(|(path, er)| (er.id, path.clone()))
             .collect();
+
+        if let Some(window) = window {
+            let lifecycle_handlers = Rc::downgrade(&extra_data.lifecycle_handlers);
+            window.add_visibility_listener(Box::new(move |visible| {
+                if let Some(lifecycle_handlers) = lifecycle_handlers.upgrade() {
+                    lifecycle_handlers.visibility_changed(visible);
+                }
+            }));
+        }
     }
     *component_type.window_offset.apply_mut(instance.as_mut()) =
         window.map(|window| window.clone().into());
@@ -1138,8 +1719,10 @@ pub fn instantiate(
 
     // Some properties are generated as Value, but for which the default constructed Value must be initialized
     for (prop_name, decl) in &component_type.original.root_element.borrow().property_declarations {
-        if !matches!(decl.property_type, Type::Struct { .. } | Type::Array(_))
-            || decl.is_alias.is_some()
+        if !matches!(
+            decl.property_type,
+            Type::Struct { .. } | Type::Array(_) | Type::Map(_) | Type::ComponentFactory
+        ) || decl.is_alias.is_some()
         {
             continue;
         }
@@ -1327,6 +1910,8 @@ pub fn instantiate(
         let rep_in_comp = rep_in_comp.unerase(guard);
 
         let repeater = rep_in_comp.offset.apply_pin(instance_ref.instance);
+        let repeater_ptr = repeater.get_ref() as *const Repeater<ErasedComponentBox>;
+        let is_component_container = rep_in_comp.is_component_container;
         let expr = rep_in_comp.model.clone();
         let component_type = component_type.clone();
         let instance = component_box.instance.as_ptr();
@@ -1344,6 +1929,10 @@ pub fn instantiate(
                     InstanceRef::from_pin_ref(c, guard)
                 }),
             );
+            if is_component_container {
+                // The component created by the previous factory must not be re-used
+                unsafe { Pin::new_unchecked(&*repeater_ptr) }.clear();
+            }
             i_slint_core::model::ModelRc::new(crate::value_model::ValueModel::new(m))
         });
     }
@@ -1524,6 +2113,14 @@ extern "C" fn layout_info(component: ComponentRefPin, orientation: Orientation)
                     .unwrap();
                 *result = ItemRc::new(parent_rc, parent_index).parent_item();
             };
+        } else if let Some(embedding_parent) = instance_ref
+            .component_type
+            .extra_data_offset
+            .apply(instance_ref.as_ref())
+            .embedding_parent
+            .get()
+        {
+            *result = embedding_parent.clone();
         }
         return;
     }
@@ -1587,7 +2184,7 @@ pub fn self_weak(
         &self,
     ) -> &once_cell::unsync::OnceCell<vtable::VWeak<ComponentVTable, ErasedComponentBox>> {
         let extra_data = self.component_type.extra_data_offset.apply(self.as_ref());
-        &extra_data.self_weak
+        &*extra_data.self_weak
     }
 
     pub fn window(&self) -> &i_slint_core::api::Window {
@@ -1637,3 +2234,126 @@ pub fn show_popup(
     inst.run_setup_code();
     parent_window.show_popup(&vtable::VRc::into_dyn(inst), pos, parent_item);
 }
+
+/// Returns the element that was compiled to the item at this index of the component
+fn element_of_item(component: &ComponentRc, index: usize) -> Option<ElementRc> {
+    generativity::make_guard!(guard);
+    // Safety: all the components of the item tree of an interpreted component are interpreted
+    let instance_ref =
+        unsafe { InstanceRef::from_pin_ref(vtable::VRc::borrow_pin(component), guard) };
+    instance_ref
+        .component_type
+        .items
+        .values()
+        .find(|item| item.elem.borrow().item_index.get() == Some(&index))
+        .map(|item| item.elem.clone())
+}
+
+/// Returns the item of the element with this id in the main component, or in the components of
+/// its `for` and `if`. The elements of the components that are used in it are not found.
+pub(crate) fn find_element_item(component: &ComponentRc, id: &str) -> Option<ItemRc> {
+    use i_slint_core::item_tree::ItemVisitorResult;
+
+    let root_node = {
+        generativity::make_guard!(guard);
+        // Safety: the component is an interpreted component
+        let instance_ref =
+            unsafe { InstanceRef::from_pin_ref(vtable::VRc::borrow_pin(component), guard) };
+        let root_element = instance_ref.component_type.original.root_element.borrow();
+        root_element.node.as_ref()?.node.clone()
+    };
+    let id = i_slint_compiler::parser::normalize_identifier(id);
+
+    let mut result = None;
+    i_slint_core::item_tree::visit_items(
+        component,
+        TraversalOrder::BackToFront,
+        |component, _, index, _| {
+            let found = element_of_item(component, index).map_or(false, |elem| {
+                let elem = elem.borrow();
+                let node = match &elem.node {
+                    Some(node) => node,
+                    None => return false,
+                };
+                let declared_id = node
+                    .parent()
+                    .filter(|n| n.kind() == i_slint_compiler::parser::SyntaxKind::SubElement)
+                    .and_then(|n| i_slint_compiler::parser::identifier_text(&n));
+                declared_id.as_deref() == Some(id.as_str())
+                    && node.ancestors().any(|n| n == root_node)
+            });
+            if found {
+                result = Some(ItemRc::new(component.clone(), index));
+                ItemVisitorResult::Abort
+            } else {
+                ItemVisitorResult::Continue(())
+            }
+        },
+        (),
+    );
+    result
+}
+
+/// Returns the elements of the item tree whose geometry contains the position, from the topmost
+/// to the root
+pub(crate) fn elements_at_position(
+    component: &ComponentRc,
+    position: i_slint_core::graphics::Point,
+) -> Vec<crate::api::ElementPosition> {
+    use i_slint_core::graphics::Point;
+    use i_slint_core::item_tree::ItemVisitorResult;
+
+    #[derive(Clone, Copy)]
+    struct State {
+        offset: Point,
+        hidden: bool,
+    }
+
+    let mut result = vec![];
+    i_slint_core::item_tree::visit_items(
+        component,
+        TraversalOrder::BackToFront,
+        |component, item, index, state: &State| {
+            let geometry = item.as_ref().geometry().translate(state.offset.to_vector());
+            let mut state = State { offset: geometry.origin, ..*state };
+            if let Some(clip) = ItemRef::downcast_pin::<i_slint_core::items::Clip>(item) {
+                // The elements with `visible: false` are placed in an empty clipping item
+                if clip.clip() && (geometry.width() <= 0. || geometry.height() <= 0.) {
+                    state.hidden = true;
+                }
+            }
+            if !state.hidden && geometry.contains(position) {
+                let node = element_of_item(component, index).and_then(|e| e.borrow().node.clone());
+                if let Some(node) = node {
+                    result.push(crate::api::ElementPosition {
+                        path: node.source_file.path().to_owned(),
+                        offset: node.text_range().start().into(),
+                        geometry,
+                    });
+                }
+            }
+            ItemVisitorResult::Continue(state)
+        },
+        State { offset: Point::default(), hidden: false },
+    );
+    result.reverse();
+    result
+}
+
+/// Returns the elements that the Tab key moves the focus to, in that order
+pub(crate) fn tab_order(
+    window: &i_slint_core::window::WindowRc,
+) -> Vec<crate::api::ElementPosition> {
+    window
+        .tab_order()
+        .into_iter()
+        .filter_map(|(item, geometry)| {
+            let node = element_of_item(&item.component(), item.index())?.borrow().node.clone()?;
+            Some(crate::api::ElementPosition {
+                path: node.source_file.path().to_owned(),
+                offset: node.text_range().start().into(),
+                geometry,
+            })
+        })
+        .collect()
+}
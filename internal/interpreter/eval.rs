@@ -5,6 +5,7 @@
 use crate::dynamic_component::InstanceRef;
 use core::convert::TryInto;
 use core::pin::Pin;
+use corelib::animations::AnimationFinishedCallback;
 use corelib::graphics::{GradientStop, LinearGradientBrush, PathElement};
 use corelib::items::{ItemRef, PropertyAnimation};
 use corelib::model::{Model, ModelRc};
@@ -26,7 +27,7 @@ fn set(
         &self,
         item: Pin<ItemRef>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Option<AnimationFinishedCallback>)>,
     ) -> Result<(), ()>;
     fn set_binding(
         &self,
@@ -51,7 +52,7 @@ fn set(
         &self,
         item: Pin<ItemRef>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Option<AnimationFinishedCallback>)>,
     ) -> Result<(), ()> {
         (*self).set(ItemRef::downcast_pin(item).unwrap(), value, animation)
     }
@@ -165,6 +166,16 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
             local_context.function_arguments[*index].clone()
         }
         Expression::StructFieldAccess { base, name } => {
+            if let Expression::RepeaterModelReference { element } = &**base {
+                // The fields of the model data have their own property, so that the binding
+                // only depends on the field it uses
+                if let Ok(value) = load_property_helper(local_context.component_instance,
+                    &element.upgrade().unwrap().borrow().base_type.as_component().root_element,
+                    &format!("model_data.{}", name),
+                ) {
+                    return value;
+                }
+            }
             if let Value::Struct(o) = eval_expression(base, local_context) {
                 o.get_field(name).cloned().unwrap_or(Value::Void)
             } else {
@@ -183,6 +194,9 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                         default_value_for_type(&expression.ty())
                     }
                 }
+                (Value::Map(map), Value::String(key)) => {
+                    map.get(key.as_str()).cloned().unwrap_or_else(|| default_value_for_type(&expression.ty()))
+                }
                 _ => {
                     Value::Void
                 }
@@ -197,6 +211,20 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 }
                 (Value::Number(n), Type::Color) => Color::from_argb_encoded(n as u32).into(),
                 (Value::Brush(brush), Type::Color) => brush.color().into(),
+                (Value::Number(n), Type::DateTime) => Value::DateTime(corelib::api::DateTime::from_millis_since_epoch(n as i64)),
+                (Value::Map(map), Type::Model) => Value::Model(ModelRc::new(
+                    corelib::model::SharedVectorModel::from(
+                        map.iter()
+                            .map(|(k, v)| {
+                                Value::Struct(
+                                    [("key".into(), Value::String(k.into())), ("value".into(), v.clone())]
+                                        .into_iter()
+                                        .collect(),
+                                )
+                            })
+                            .collect::<SharedVector<_>>(),
+                    ),
+                )),
                 (v, _) => v,
             }
         }
@@ -221,6 +249,33 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     ComponentInstance::GlobalComponent(_) => panic!("Cannot get the window from a global component"),
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::GetWindowBaseFontSize, _) => {
+                match local_context.component_instance {
+                    ComponentInstance::InstanceRef(component) => Value::Number(window_ref(component).unwrap().base_font_size() as _),
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot get the window from a global component"),
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::GetWindowViewportWidth, _) => {
+                match local_context.component_instance {
+                    ComponentInstance::InstanceRef(component) => Value::Number(window_ref(component).unwrap().viewport_size().width as _),
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot get the window from a global component"),
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::GetWindowViewportHeight, _) => {
+                match local_context.component_instance {
+                    ComponentInstance::InstanceRef(component) => Value::Number(window_ref(component).unwrap().viewport_size().height as _),
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot get the window from a global component"),
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::AnimationTick, _) => {
+                Value::Number(corelib::animations::animation_tick() as f64)
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::HighContrast, _) => {
+                Value::Bool(corelib::accessibility::high_contrast())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::ReducedMotion, _) => {
+                Value::Bool(corelib::accessibility::reduced_motion())
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::Debug, _) => {
                 let to_print: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
                 corelib::debug_log!("{}", to_print);
@@ -230,6 +285,11 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 let mut to_int = |e| -> i32 { eval_expression(e, local_context).try_into().unwrap() };
                 Value::Number((to_int(&arguments[0]) % to_int(&arguments[1])) as _)
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::ModFloat, _) => {
+                let x: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let y: f64 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::Number(x % y)
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::Round, _) => {
                 let x: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
                 Value::Number(x.round())
@@ -274,6 +334,11 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 let x: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
                 Value::Number(x.atan().to_degrees())
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::ATan2, _) => {
+                let y: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let x: f64 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::Number(y.atan2(x).to_degrees())
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::Log, _) => {
                 let x: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
                 let y: f64 = eval_expression(&arguments[1], local_context).try_into().unwrap();
@@ -284,6 +349,10 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 let y: f64 = eval_expression(&arguments[1], local_context).try_into().unwrap();
                 Value::Number(x.powf(y))
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::Exp, _) => {
+                let x: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                Value::Number(x.exp())
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::SetFocusItem, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to SetFocusItem")
@@ -345,6 +414,15 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("internal error: argument to SetFocusItem must be an element")
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::ClosePopupWindow, _) => {
+                // A global component has no window, so there is no popup to close
+                if let ComponentInstance::InstanceRef(component) = local_context.component_instance {
+                    if let Some(window) = window_ref(component) {
+                        window.close_popup();
+                    }
+                }
+                Value::Void
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::StringIsFloat, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to StringIsFloat")
@@ -365,6 +443,63 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("Argument not a string");
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringToUppercase, _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to StringToUppercase")
+                }
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                Value::String(s.to_uppercase().into())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringToLowercase, _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to StringToLowercase")
+                }
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                Value::String(s.to_lowercase().into())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringTrim, _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to StringTrim")
+                }
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                Value::String(s.trim().into())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringSplit, _) => {
+                if arguments.len() != 2 {
+                    panic!("internal error: incorrect argument count to StringSplit")
+                }
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let separator: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::Model(ModelRc::new(corelib::model::SharedVectorModel::from(
+                    corelib::string::split_string(&s, &separator).into_iter().map(Value::String).collect::<SharedVector<_>>()
+                )))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringReplace, _) => {
+                if arguments.len() != 3 {
+                    panic!("internal error: incorrect argument count to StringReplace")
+                }
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let from: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                let to: SharedString = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                Value::String(s.replace(from.as_str(), to.as_str()).into())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringStartsWith, _) => {
+                if arguments.len() != 2 {
+                    panic!("internal error: incorrect argument count to StringStartsWith")
+                }
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let prefix: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::Bool(s.starts_with(prefix.as_str()))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringSlice, _) => {
+                if arguments.len() != 3 {
+                    panic!("internal error: incorrect argument count to StringSlice")
+                }
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let start: i32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                let end: i32 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                Value::String(corelib::string::slice_string(&s, start, end))
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ColorBrighter, _) => {
                 if arguments.len() != 2 {
                     panic!("internal error: incorrect argument count to ColorBrighter")
@@ -393,6 +528,23 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("First argument not a color");
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::ColorMix, _) => {
+                if arguments.len() != 3 {
+                    panic!("internal error: incorrect argument count to ColorMix")
+                }
+                let color = eval_expression(&arguments[0], local_context);
+                let other = eval_expression(&arguments[1], local_context);
+                let factor: f32 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                color.mix(&other, factor).expect("Arguments not colors")
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::ColorWithAlpha, _) => {
+                if arguments.len() != 2 {
+                    panic!("internal error: incorrect argument count to ColorWithAlpha")
+                }
+                let color = eval_expression(&arguments[0], local_context);
+                let alpha: f32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                color.with_alpha(alpha).expect("First argument not a color")
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ImageSize, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to ImageSize")
@@ -422,6 +574,26 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     }
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::ArrayFindStartingWith, _) => {
+                if arguments.len() != 3 {
+                    panic!("internal error: incorrect argument count to ArrayFindStartingWith")
+                }
+                let prefix: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                let from: i32 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                match eval_expression(&arguments[0], local_context) {
+                    Value::Model(model) => {
+                        let text = |row: &Value| match row {
+                            Value::String(s) => s.clone(),
+                            Value::Struct(s) => s.get_field("text").cloned().and_then(|t| t.try_into().ok()).unwrap_or_default(),
+                            _ => SharedString::default(),
+                        };
+                        Value::Number(corelib::model::find_row_starting_with(&model, prefix.as_str(), from, text) as f64)
+                    }
+                    _ => {
+                        panic!("First argument not an array");
+                    }
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::Rgb, _) => {
                 let r: i32 = eval_expression(&arguments[0], local_context).try_into().unwrap();
                 let g: i32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
@@ -433,6 +605,13 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 let a: u8 = (255. * a).max(0.).min(255.) as u8;
                 Value::Brush(Brush::SolidColor(Color::from_argb_u8(a, r, g, b)))
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::Hsv, _) => {
+                let h: f32 = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let s: f32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                let v: f32 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                let a: f32 = eval_expression(&arguments[3], local_context).try_into().unwrap();
+                Value::from_hsva(h, s, v, a)
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ImplicitLayoutInfo(orient), _) => {
                 let component = match  local_context.component_instance  {
                     ComponentInstance::InstanceRef(c) => c,
@@ -459,7 +638,11 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("internal error: incorrect argument count to RegisterCustomFontByPath")
                 }
                 if let Value::String(s) = eval_expression(&arguments[0], local_context) {
-                    if let Some(err) = crate::register_font_from_path(&std::path::PathBuf::from(s.as_str())).err() {
+                    let result = match crate::bundle::register_font(s.as_str()) {
+                        Some(result) => result,
+                        None => crate::register_font_from_path(&std::path::PathBuf::from(s.as_str())),
+                    };
+                    if let Some(err) = result.err() {
                         corelib::debug_log!("Error loading custom font {}: {}", s.as_str(), err);
                     }
                     Value::Void
@@ -501,6 +684,13 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 ('>', Value::String(a), Value::String(b)) => Value::Bool(a > b),
                 ('≤', Value::String(a), Value::String(b)) => Value::Bool(a <= b),
                 ('≥', Value::String(a), Value::String(b)) => Value::Bool(a >= b),
+                ('+', Value::DateTime(a), Value::Number(b)) => Value::DateTime(corelib::api::DateTime::from_millis_since_epoch(a.millis_since_epoch() + b as i64)),
+                ('-', Value::DateTime(a), Value::Number(b)) => Value::DateTime(corelib::api::DateTime::from_millis_since_epoch(a.millis_since_epoch() - b as i64)),
+                ('-', Value::DateTime(a), Value::DateTime(b)) => Value::Number((a.millis_since_epoch() - b.millis_since_epoch()) as f64),
+                ('<', Value::DateTime(a), Value::DateTime(b)) => Value::Bool(a < b),
+                ('>', Value::DateTime(a), Value::DateTime(b)) => Value::Bool(a > b),
+                ('≤', Value::DateTime(a), Value::DateTime(b)) => Value::Bool(a <= b),
+                ('≥', Value::DateTime(a), Value::DateTime(b)) => Value::Bool(a >= b),
                 ('=', a, b) => Value::Bool(a == b),
                 ('!', a, b) => Value::Bool(a != b),
                 ('&', Value::Bool(a), Value::Bool(b)) => Value::Bool(a && b),
@@ -523,7 +713,10 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     Ok(Default::default())
                 }
                 i_slint_compiler::expression_tree::ImageReference::AbsolutePath(path) => {
-                    corelib::graphics::Image::load_from_path(std::path::Path::new(path))
+                    match crate::bundle::image(path) {
+                        Some(image) => image,
+                        None => corelib::graphics::Image::load_from_path(std::path::Path::new(path)),
+                    }
                 }
                 i_slint_compiler::expression_tree::ImageReference::EmbeddedData { resource_id, extension } => {
                     let toplevel_instance = match local_context.component_instance {
@@ -572,6 +765,15 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 values.iter().map(|e| eval_expression(e, local_context)).collect::<SharedVector<_>>()
             )
         )),
+        Expression::Map { entries, .. } => Value::Map(
+            entries
+                .iter()
+                .map(|(k, v)| {
+                    let key: SharedString = eval_expression(k, local_context).try_into().unwrap();
+                    (key.as_str().to_owned(), eval_expression(v, local_context))
+                })
+                .collect(),
+        ),
         Expression::Struct { values, .. } => Value::Struct(
             values
                 .iter()
@@ -805,6 +1007,41 @@ pub fn store_property(
     element: &ElementRc,
     name: &str,
     value: Value,
+) -> Result<(), SetPropertyError> {
+    store_property_impl(component_instance, element, name, value, None)
+}
+
+/// Same as [`store_property`], but the property is animated towards the value with the given
+/// animation instead of the one declared in the .slint file.
+pub fn animate_property(
+    component_instance: InstanceRef,
+    element: &ElementRc,
+    name: &str,
+    value: Value,
+    animation: PropertyAnimation,
+) -> Result<(), SetPropertyError> {
+    let property_type = element.borrow().lookup_property(name).property_type;
+    match property_type {
+        Type::Invalid => Err(SetPropertyError::NoSuchProperty),
+        Type::Float32
+        | Type::Int32
+        | Type::Color
+        | Type::Brush
+        | Type::Angle
+        | Type::PhysicalLength
+        | Type::LogicalLength => {
+            store_property_impl(component_instance, element, name, value, Some(animation))
+        }
+        _ => Err(SetPropertyError::NotAnimatable),
+    }
+}
+
+fn store_property_impl(
+    component_instance: InstanceRef,
+    element: &ElementRc,
+    name: &str,
+    value: Value,
+    animation: Option<PropertyAnimation>,
 ) -> Result<(), SetPropertyError> {
     generativity::make_guard!(guard);
     match enclosing_component_instance_for_element(
@@ -813,12 +1050,13 @@ pub fn store_property(
         guard,
     ) {
         ComponentInstance::InstanceRef(enclosing_component) => {
-            let maybe_animation = match element.borrow().bindings.get(name) {
-                Some(b) => crate::dynamic_component::animation_for_property(
+            let maybe_animation = match (animation, element.borrow().bindings.get(name)) {
+                (Some(animation), _) => AnimatedBindingKind::Animation(animation, None),
+                (None, Some(b)) => crate::dynamic_component::animation_for_property(
                     enclosing_component,
                     &b.borrow().animation,
                 ),
-                None => {
+                (None, None) => {
                     crate::dynamic_component::animation_for_property(enclosing_component, &None)
                 }
             };
@@ -864,7 +1102,7 @@ pub fn store_property(
 }
 
 /// Return true if the Value can be used for a property of the given type
-fn check_value_type(value: &Value, ty: &Type) -> bool {
+pub(crate) fn check_value_type(value: &Value, ty: &Type) -> bool {
     match ty {
         Type::Void => true,
         Type::Invalid
@@ -897,13 +1135,18 @@ fn check_value_type(value: &Value, ty: &Type) -> bool {
         Type::Array(inner) => {
             matches!(value, Value::Model(m) if m.iter().all(|v| check_value_type(&v, inner)))
         }
+        Type::Map(inner) => {
+            matches!(value, Value::Map(m) if m.iter().all(|(_, v)| check_value_type(v, inner)))
+        }
         Type::Struct { fields, .. } => {
             matches!(value, Value::Struct(str) if str.iter().all(|(k, v)| fields.get(k).map_or(false, |ty| check_value_type(v, ty))))
         }
         Type::Enumeration(en) => {
-            matches!(value, Value::EnumerationValue(name, _) if name == en.name.as_str())
+            matches!(value, Value::EnumerationValue(name, value) if name == en.name.as_str() && en.values.contains(value))
         }
         Type::LayoutCache => matches!(value, Value::LayoutCache(_)),
+        Type::ComponentFactory => matches!(value, Value::ComponentFactory(_)),
+        Type::DateTime => matches!(value, Value::DateTime(_)),
     }
 }
 
@@ -1160,6 +1403,7 @@ pub fn default_value_for_type(ty: &Type) -> Value {
             fields.iter().map(|(n, t)| (n.clone(), default_value_for_type(t))).collect::<Struct>(),
         ),
         Type::Array(_) | Type::Model => Value::Void,
+        Type::Map(_) => Value::Map(Default::default()),
         Type::Percent => Value::Number(0.),
         Type::Enumeration(e) => {
             Value::EnumerationValue(e.name.clone(), e.values.get(e.default_value).unwrap().clone())
@@ -1169,6 +1413,8 @@ pub fn default_value_for_type(ty: &Type) -> Value {
         Type::UnitProduct(_) => Value::Number(0.),
         Type::PathData => Value::PathData(Default::default()),
         Type::LayoutCache => Value::LayoutCache(Default::default()),
+        Type::ComponentFactory => Value::ComponentFactory(Default::default()),
+        Type::DateTime => Value::DateTime(Default::default()),
         Type::InferredProperty
         | Type::InferredCallback
         | Type::ElementReference
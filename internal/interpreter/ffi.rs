@@ -369,6 +369,32 @@ pub extern "C" fn slint_interpreter_component_instance_set_property(
     }
 }
 
+/// Invoke a function.
+/// The `out` parameter must be uninitialized. If this function returns true, the out will be initialized
+/// to the resulting value. If this function returns false, out is unchanged
+#[no_mangle]
+pub unsafe extern "C" fn slint_interpreter_component_instance_invoke(
+    inst: &ErasedComponentBox,
+    name: Slice<u8>,
+    args: Slice<ValueOpaque>,
+    out: *mut ValueOpaque,
+) -> bool {
+    let args = std::mem::transmute::<Slice<ValueOpaque>, Slice<Value>>(args);
+    generativity::make_guard!(guard);
+    let comp = inst.unerase(guard);
+    match comp.description().invoke_function(
+        comp.borrow(),
+        &normalize_identifier(std::str::from_utf8(&name).unwrap()),
+        args.as_slice(),
+    ) {
+        Ok(val) => {
+            std::ptr::write(out as *mut Value, val);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
 /// Wrap the user_data provided by the native code and call the drop function on Drop.
 ///
 /// Safety: user_data must be a pointer that can be destroyed by the drop_user_data function.
@@ -902,6 +928,15 @@ fn as_component_definition(&self) -> &ComponentDefinition {
     callbacks.extend((&*def).as_component_definition().callbacks().map(|name| name.into()))
 }
 
+/// Returns the list of function names of the component the component definition describes
+#[no_mangle]
+pub unsafe extern "C" fn slint_interpreter_component_definition_functions(
+    def: &ComponentDefinitionOpaque,
+    functions: &mut SharedVector<SharedString>,
+) {
+    functions.extend((&*def).as_component_definition().functions().map(|name| name.into()))
+}
+
 /// Return the name of the component definition
 #[no_mangle]
 pub unsafe extern "C" fn slint_interpreter_component_definition_name(
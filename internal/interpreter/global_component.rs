@@ -5,7 +5,7 @@
 use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
-use crate::api::Value;
+use crate::api::{GlobalImplementation, Value};
 use crate::dynamic_component::{ErasedComponentBox, ErasedComponentDescription};
 use crate::SetPropertyError;
 use i_slint_compiler::namedreference::NamedReference;
@@ -61,6 +61,32 @@ pub fn public_properties(&self) -> impl Iterator<Item = (&String, &PropertyDecla
         }
     }
 
+    /// Returns true if all the properties and callbacks that `implementation` implements can be
+    /// bound to it: they are declared in the global and are neither functions nor aliases
+    pub fn can_be_implemented_by(&self, implementation: &dyn GlobalImplementation) -> bool {
+        match self {
+            CompiledGlobal::Builtin { .. } => false,
+            CompiledGlobal::Component { component, .. } => {
+                generativity::make_guard!(guard);
+                let component = component.unerase(guard);
+                let root_element = component.original.root_element.borrow();
+                root_element
+                    .property_declarations
+                    .iter()
+                    .filter(|(name, _)| implementation.implements(name))
+                    .all(|(name, decl)| {
+                        !decl.is_function
+                            && decl.is_alias.is_none()
+                            && if matches!(decl.property_type, Type::Callback { .. }) {
+                                component.custom_callbacks.contains_key(name)
+                            } else {
+                                component.custom_properties.contains_key(name)
+                            }
+                    })
+            }
+        }
+    }
+
     pub fn extend_public_properties(
         &mut self,
         iter: impl IntoIterator<Item = (String, PropertyDeclaration)>,
@@ -91,8 +117,13 @@ fn set_property(
     fn get_property_ptr(self: Pin<&Self>, prop_name: &str) -> *const ();
 }
 
-/// Instantiate the global singleton and store it in `globals`
-pub fn instantiate(description: &CompiledGlobal, globals: &mut GlobalStorage) {
+/// Instantiate the global singleton and store it in `globals`, with the properties and the
+/// callbacks that `implementation` implements bound to it
+pub fn instantiate(
+    description: &CompiledGlobal,
+    globals: &mut GlobalStorage,
+    implementation: Option<&Rc<dyn GlobalImplementation>>,
+) {
     let instance = match description {
         CompiledGlobal::Builtin { element, .. } => {
             trait Helper {
@@ -116,13 +147,17 @@ fn instantiate(name: &str) -> Pin<Rc<dyn GlobalComponent>> {
         }
         CompiledGlobal::Component { component, .. } => {
             generativity::make_guard!(guard);
-            let description = component.unerase(guard);
-            Rc::pin(GlobalComponentInstance(crate::dynamic_component::instantiate(
-                description.clone(),
+            let component = component.unerase(guard);
+            let instance = crate::dynamic_component::instantiate(
+                component.clone(),
                 None,
                 None,
                 globals.clone(),
-            )))
+            );
+            if let Some(implementation) = implementation {
+                implement(&instance, implementation.clone());
+            }
+            Rc::pin(GlobalComponentInstance(instance))
         }
     };
     globals.extend(
@@ -133,6 +168,37 @@ fn instantiate(name: &str) -> Pin<Rc<dyn GlobalComponent>> {
     );
 }
 
+/// Binds the properties and sets the callback handlers of the global that `implementation`
+/// implements, which `CompiledGlobal::can_be_implemented_by` checked when it was registered
+fn implement(
+    instance: &vtable::VRc<ComponentVTable, ErasedComponentBox>,
+    implementation: Rc<dyn GlobalImplementation>,
+) {
+    generativity::make_guard!(guard);
+    let comp = instance.unerase(guard);
+    let description = comp.description();
+    for (name, decl) in description.original.root_element.borrow().property_declarations.iter() {
+        if decl.is_function || !implementation.implements(name) {
+            continue;
+        }
+        let (implementation, name_) = (implementation.clone(), name.clone());
+        let result = if matches!(decl.property_type, Type::Callback { .. }) {
+            description.set_callback_handler(
+                comp.borrow(),
+                name,
+                Box::new(move |args| implementation.invoke_callback(&name_, args)),
+            )
+        } else {
+            description.set_binding(
+                comp.borrow(),
+                name,
+                Box::new(move || implementation.property(&name_)),
+            )
+        };
+        debug_assert!(result.is_ok(), "cannot implement {} of {}", name, description.id());
+    }
+}
+
 /// For the global components, we don't use the dynamic_type optimization,
 /// and we don't try to optimize the property to their real type
 pub struct GlobalComponentInstance(vtable::VRc<ComponentVTable, ErasedComponentBox>);
@@ -73,6 +73,11 @@
 );
 
 mod api;
+#[cfg(feature = "automation")]
+pub mod automation;
+#[cfg(feature = "benchmark")]
+pub mod benchmark;
+mod bundle;
 mod dynamic_component;
 mod dynamic_type;
 mod eval;
@@ -80,11 +85,18 @@
 mod global_component;
 #[cfg(doc)]
 pub mod migration;
+pub mod remote_preview;
+#[cfg(feature = "settings")]
+pub mod settings;
+pub mod table_model;
 mod value_model;
 
 #[doc(inline)]
 pub use api::*;
 
+#[doc(hidden)]
+pub use eval::default_value_for_type;
+
 /// This function can be used to register a custom TrueType font with Slint,
 /// for use with the `font-family` property. The provided path must refer to a valid TrueType
 /// font.
@@ -94,10 +106,21 @@ pub(crate) fn register_font_from_path<P: AsRef<std::path::Path>>(
     i_slint_backend_selector::backend().register_font_from_path(path.as_ref())
 }
 
+/// Register a TrueType font whose data is in memory, for use with the `font-family` property.
+pub(crate) fn register_font_from_memory(
+    data: &'static [u8],
+) -> Result<(), Box<dyn std::error::Error>> {
+    i_slint_backend_selector::backend().register_font_from_memory(data)
+}
+
 /// (Re-export from corelib.)
 #[doc(inline)]
 pub use i_slint_core::{Brush, Color, SharedString, SharedVector};
 
+/// (Re-export from corelib.)
+#[doc(inline)]
+pub use i_slint_core::animations::EasingCurve;
+
 /// One need to use at least one function in each module in order to get them
 /// exported in the final binary.
 /// This only use functions from modules which are not otherwise used.
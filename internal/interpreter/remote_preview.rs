@@ -0,0 +1,241 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    The protocol of the remote preview, with which an editor on a desktop shows a component on a
+    device, such as an embedded board, whose rendering differs from the one of the desktop.
+
+    The editor connects to the agent that runs on the device, such as `slint-viewer --listen`, and
+    sends it a [`RemoteMessage::Load`] message with a [bundle](crate::ComponentCompiler::build_from_bundle)
+    each time the component changes. The agent compiles the component of the bundle, shows it in
+    place of the previous one, and answers with a [`RemoteMessage::Diagnostics`] message.
+
+    Each message starts with the `SLRP` bytes, followed by the kind of the message in one byte and
+    by the length of the content of the message as a 32 bits big endian integer. The numbers in the
+    content are 32 bits big endian integers too, and the strings are UTF-8 preceded by their length.
+    The content of a message is at most [`MAX_MESSAGE_SIZE`] bytes long.
+*/
+
+use crate::{Diagnostic, DiagnosticLevel};
+use std::io::{Error, ErrorKind, Read, Write};
+
+const MAGIC: &[u8; 4] = b"SLRP";
+const LOAD: u8 = 1;
+const DIAGNOSTICS: u8 = 2;
+
+/// The maximum size of the content of a message, in bytes. [`read_message()`] returns an error
+/// instead of allocating the memory for a longer message, and [`write_message()`] refuses to
+/// send it.
+pub const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// A message of the remote preview protocol
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RemoteMessage {
+    /// From the editor to the agent: compile the component of this bundle and show it instead of
+    /// the current one
+    Load {
+        /// The data of the bundle, as written by `slint-compiler --bundle`
+        bundle: Vec<u8>,
+    },
+    /// From the agent to the editor: the diagnostics of the compilation of the last bundle, and
+    /// the error of loading its data, if any. The list is empty when the compilation succeeded
+    /// without warnings.
+    Diagnostics(Vec<RemoteDiagnostic>),
+}
+
+/// A diagnostic of the compilation of a bundle by the agent
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteDiagnostic {
+    /// Whether this is an error or a warning
+    pub level: DiagnosticLevel,
+    /// The path of the file in the bundle, such as `ui/app.slint`, or an empty string if the
+    /// diagnostic is not attached to a file
+    pub file: String,
+    /// The line, starting at 1
+    pub line: usize,
+    /// The column, starting at 0
+    pub column: usize,
+    /// The message of the diagnostic
+    pub message: String,
+}
+
+impl RemoteDiagnostic {
+    /// Convert a diagnostic of the compilation of a bundle. The path of the file is made relative
+    /// to the root of the bundle.
+    pub fn from_diagnostic(diagnostic: &Diagnostic) -> Self {
+        let file = diagnostic
+            .source_file()
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        // The files of a bundle have an url such as `bundle://1/ui/app.slint`
+        let file = match file.strip_prefix("bundle://") {
+            Some(rest) => rest.split_once('/').map_or(rest, |(_, path)| path).to_owned(),
+            None => file,
+        };
+        let (line, column) = diagnostic.line_column();
+        Self {
+            level: diagnostic.level(),
+            file,
+            line,
+            column,
+            message: diagnostic.message().to_owned(),
+        }
+    }
+}
+
+/// Write a message, such as to a `TcpStream`
+pub fn write_message(destination: &mut impl Write, message: &RemoteMessage) -> std::io::Result<()> {
+    let mut content = vec![];
+    let kind = match message {
+        RemoteMessage::Load { bundle } => {
+            content.extend_from_slice(bundle);
+            LOAD
+        }
+        RemoteMessage::Diagnostics(diagnostics) => {
+            write_number(&mut content, diagnostics.len())?;
+            for d in diagnostics {
+                content.push(if d.level == DiagnosticLevel::Warning { 1 } else { 0 });
+                write_string(&mut content, &d.file)?;
+                write_number(&mut content, d.line)?;
+                write_number(&mut content, d.column)?;
+                write_string(&mut content, &d.message)?;
+            }
+            DIAGNOSTICS
+        }
+    };
+    if content.len() > MAX_MESSAGE_SIZE {
+        return Err(too_big(content.len()));
+    }
+    let mut header = MAGIC.to_vec();
+    header.push(kind);
+    write_number(&mut header, content.len())?;
+    destination.write_all(&header)?;
+    destination.write_all(&content)?;
+    destination.flush()
+}
+
+/// Read a message, such as from a `TcpStream`. Blocks until the whole message is received.
+///
+/// Returns `Ok(None)` if the connection was closed before the start of a message, and an error
+/// if the content of the message is longer than [`MAX_MESSAGE_SIZE`].
+pub fn read_message(source: &mut impl Read) -> std::io::Result<Option<RemoteMessage>> {
+    let mut header = [0u8; 9];
+    match source.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    if &header[..4] != MAGIC {
+        return Err(invalid("This is not a message of the remote preview"));
+    }
+    let len = u32::from_be_bytes([header[5], header[6], header[7], header[8]]) as usize;
+    if len > MAX_MESSAGE_SIZE {
+        return Err(too_big(len));
+    }
+    let mut content = vec![0; len];
+    source.read_exact(&mut content)?;
+    match header[4] {
+        LOAD => Ok(Some(RemoteMessage::Load { bundle: content })),
+        DIAGNOSTICS => {
+            let mut content = content.as_slice();
+            let count = read_number(&mut content)?;
+            let mut diagnostics = Vec::with_capacity(count.min(1024));
+            for _ in 0..count {
+                let mut level = [0u8];
+                content.read_exact(&mut level)?;
+                let level =
+                    if level[0] == 1 { DiagnosticLevel::Warning } else { DiagnosticLevel::Error };
+                diagnostics.push(RemoteDiagnostic {
+                    level,
+                    file: read_string(&mut content)?,
+                    line: read_number(&mut content)?,
+                    column: read_number(&mut content)?,
+                    message: read_string(&mut content)?,
+                });
+            }
+            Ok(Some(RemoteMessage::Diagnostics(diagnostics)))
+        }
+        kind => Err(invalid(&format!("Unknown message of the remote preview: {}", kind))),
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+fn too_big(len: usize) -> Error {
+    invalid(&format!(
+        "The message of {} bytes is bigger than the maximum of {} bytes",
+        len, MAX_MESSAGE_SIZE
+    ))
+}
+
+fn write_number(destination: &mut Vec<u8>, number: usize) -> std::io::Result<()> {
+    let number = u32::try_from(number).map_err(|_| invalid("The message is too big"))?;
+    destination.extend_from_slice(&number.to_be_bytes());
+    Ok(())
+}
+
+fn write_string(destination: &mut Vec<u8>, string: &str) -> std::io::Result<()> {
+    write_number(destination, string.len())?;
+    destination.extend_from_slice(string.as_bytes());
+    Ok(())
+}
+
+fn read_number(source: &mut &[u8]) -> std::io::Result<usize> {
+    let mut number = [0u8; 4];
+    source.read_exact(&mut number)?;
+    Ok(u32::from_be_bytes(number) as usize)
+}
+
+fn read_string(source: &mut &[u8]) -> std::io::Result<String> {
+    let len = read_number(source)?;
+    if len > source.len() {
+        return Err(invalid("The message is truncated"));
+    }
+    let (string, rest) = source.split_at(len);
+    *source = rest;
+    String::from_utf8(string.to_vec()).map_err(|_| invalid("Invalid string in the message"))
+}
+
+#[test]
+fn test_write_read_messages() {
+    let messages = [
+        RemoteMessage::Load { bundle: b"bundle data".to_vec() },
+        RemoteMessage::Diagnostics(vec![
+            RemoteDiagnostic {
+                level: DiagnosticLevel::Error,
+                file: "ui/app.slint".into(),
+                line: 12,
+                column: 4,
+                message: "Unknown property colour".into(),
+            },
+            RemoteDiagnostic {
+                level: DiagnosticLevel::Warning,
+                file: String::new(),
+                line: 0,
+                column: 0,
+                message: "Ünicode".into(),
+            },
+        ]),
+        RemoteMessage::Diagnostics(vec![]),
+    ];
+    let mut data = vec![];
+    for message in &messages {
+        write_message(&mut data, message).unwrap();
+    }
+    let mut source = data.as_slice();
+    for message in &messages {
+        assert_eq!(read_message(&mut source).unwrap().as_ref(), Some(message));
+    }
+    assert_eq!(read_message(&mut source).unwrap(), None);
+    assert!(read_message(&mut &b"HTTP/1.1 200 OK"[..]).is_err());
+
+    let mut too_big = b"SLRP\x01".to_vec();
+    too_big.extend_from_slice(&(MAX_MESSAGE_SIZE as u32 + 1).to_be_bytes());
+    let error = read_message(&mut too_big.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    let bundle = vec![0; MAX_MESSAGE_SIZE + 1];
+    assert!(write_message(&mut vec![], &RemoteMessage::Load { bundle }).is_err());
+}
@@ -0,0 +1,275 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Persist the values of some public properties of a component, such as the preferences of the
+    user, in a store like a JSON file.
+
+    [`PersistentSettings::bind`] sets the properties to the values that were saved in the store,
+    if any, and then saves the values of the properties each time they change, until the
+    `PersistentSettings` is dropped.
+
+    ```rust,no_run
+    use slint_interpreter::settings::{JsonFileStore, PersistentSettings};
+    # fn bind(instance: &slint_interpreter::ComponentInstance) {
+    let store = JsonFileStore::new("preferences.json".into());
+    let settings = PersistentSettings::bind(instance, &["dark-mode", "font-size"], store);
+    // ... run the event loop while `settings` is alive
+    # }
+    ```
+
+    The values are saved from a timer, after a short delay that groups the changes that happen
+    together, so the event loop must be running. The errors of these saves are passed to the
+    handler set with [`PersistentSettings::on_save_error`]. The models are saved with their rows.
+    Other stores, such as a database, can be used by implementing [`SettingsStore`].
+*/
+
+use crate::{ComponentHandle, ComponentInstance, Struct, Value, Weak};
+use i_slint_core::properties::PropertyTracker;
+use i_slint_core::timers::{Timer, TimerMode};
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// The storage of the values of the properties bound by [`PersistentSettings`]
+pub trait SettingsStore {
+    /// Returns the values that were saved, with the name of the properties as field names, or
+    /// None if nothing was saved yet
+    fn load(&self) -> Option<Struct>;
+    /// Saves the values of the properties, with the name of the properties as field names
+    fn save(&self, values: &Struct) -> std::io::Result<()>;
+}
+
+/// A [`SettingsStore`] that saves the values in a JSON file, with an object that has a field for
+/// each property
+pub struct JsonFileStore {
+    path: PathBuf,
+}
+
+impl JsonFileStore {
+    /// A store that saves the values in the file at this path. The file is created by the
+    /// first save.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl SettingsStore for JsonFileStore {
+    fn load(&self) -> Option<Struct> {
+        let json = std::fs::read_to_string(&self.path).ok()?;
+        Struct::try_from(serde_json::from_str::<serde_json::Value>(&json).ok()?).ok()
+    }
+
+    fn save(&self, values: &Struct) -> std::io::Result<()> {
+        let json = serde_json::Value::try_from(Value::Struct(values.clone())).map_err(|value| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} cannot be saved in JSON", value),
+            )
+        })?;
+        std::fs::write(&self.path, serde_json::to_string_pretty(&json)?)
+    }
+}
+
+struct Inner {
+    instance: Weak<ComponentInstance>,
+    properties: Vec<String>,
+    store: Box<dyn SettingsStore>,
+    tracker: Pin<Box<PropertyTracker<Box<dyn Fn()>>>>,
+    timer: Timer,
+    save_delay: Cell<Duration>,
+    save_error_handler: RefCell<Option<Box<dyn Fn(std::io::Error)>>>,
+}
+
+impl Inner {
+    /// Reads the properties in the tracker, so that it is notified of their next change, and
+    /// returns their values
+    fn values(&self) -> Option<Struct> {
+        let instance = self.instance.upgrade()?;
+        Some(self.tracker.as_ref().evaluate_as_dependency_root(|| {
+            self.properties
+                .iter()
+                .filter_map(|name| Some((name.clone(), instance.get_property(name).ok()?)))
+                .collect()
+        }))
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        match self.values() {
+            Some(values) => self.store.save(&values),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Keeps the properties of a component bound to a [`SettingsStore`].
+/// See the [module documentation](self).
+pub struct PersistentSettings {
+    inner: Rc<Inner>,
+}
+
+impl PersistentSettings {
+    /// Sets the `properties` of the instance to the values saved in the store, and saves them
+    /// when they change. The values of the store for which the instance has no property with the
+    /// same name and type are ignored.
+    pub fn bind(
+        instance: &ComponentInstance,
+        properties: &[&str],
+        store: impl SettingsStore + 'static,
+    ) -> Self {
+        if let Some(saved) = store.load() {
+            let ignored = instance.restore(
+                &saved
+                    .iter()
+                    .filter(|(name, _)| properties.contains(name))
+                    .map(|(name, value)| (name.to_string(), value.clone()))
+                    .collect(),
+            );
+            for name in ignored {
+                i_slint_core::debug_log!("The saved value of the setting {} is ignored", name);
+            }
+        }
+
+        let inner = Rc::new_cyclic(|weak_inner: &std::rc::Weak<Inner>| {
+            let weak_inner = weak_inner.clone();
+            let on_change: Box<dyn Fn()> = Box::new(move || {
+                // This is called while a property is being set, so the values are read later
+                if let Some(inner) = weak_inner.upgrade() {
+                    let weak_inner = Rc::downgrade(&inner);
+                    inner.timer.start(TimerMode::SingleShot, inner.save_delay.get(), move || {
+                        if let Some(inner) = weak_inner.upgrade() {
+                            if let Err(err) = inner.save() {
+                                if let Some(handler) = &*inner.save_error_handler.borrow() {
+                                    handler(err);
+                                }
+                            }
+                        }
+                    });
+                }
+            });
+            Inner {
+                instance: instance.as_weak(),
+                properties: properties.iter().map(|name| name.to_string()).collect(),
+                store: Box::new(store),
+                tracker: Box::pin(PropertyTracker::new_with_change_handler(on_change)),
+                timer: Timer::default(),
+                save_delay: Cell::new(Duration::from_millis(500)),
+                save_error_handler: Default::default(),
+            }
+        });
+        inner.values();
+        Self { inner }
+    }
+
+    /// Sets the delay between a change of the properties and the save. The changes that happen
+    /// during this delay are saved together. The default is 500ms.
+    pub fn set_save_delay(&self, delay: Duration) {
+        self.inner.save_delay.set(delay);
+    }
+
+    /// Sets the function that is called with the error when saving the values after a change
+    /// fails, for example to tell the user that the preferences could not be written. The
+    /// errors are ignored when no handler is set.
+    pub fn on_save_error(&self, handler: impl Fn(std::io::Error) + 'static) {
+        *self.inner.save_error_handler.borrow_mut() = Some(Box::new(handler));
+    }
+
+    /// Saves the values of the properties now, for example before the application exits while a
+    /// save is pending
+    pub fn save(&self) -> std::io::Result<()> {
+        self.inner.timer.stop();
+        self.inner.save()
+    }
+}
+
+#[test]
+fn test_persistent_settings() {
+    #[derive(Clone, Default)]
+    struct MemoryStore(Rc<RefCell<Option<Struct>>>);
+    impl SettingsStore for MemoryStore {
+        fn load(&self) -> Option<Struct> {
+            self.0.borrow().clone()
+        }
+        fn save(&self, values: &Struct) -> std::io::Result<()> {
+            *self.0.borrow_mut() = Some(values.clone());
+            Ok(())
+        }
+    }
+
+    i_slint_backend_testing::init();
+    let mut compiler = crate::ComponentCompiler::default();
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Rectangle {
+        property <bool> dark-mode;
+        property <int> font-size: 12;
+        property <int> other;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    );
+    let instance = definition.unwrap().create();
+
+    let store = MemoryStore::default();
+    *store.0.borrow_mut() = Some(
+        [("dark-mode", Value::Bool(true)), ("other", Value::Number(3.))]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    );
+    let settings = PersistentSettings::bind(&instance, &["dark-mode", "font-size"], store.clone());
+    assert_eq!(instance.get_property("dark-mode").unwrap(), Value::Bool(true));
+    // Only the bound properties are loaded
+    assert_eq!(instance.get_property("other").unwrap(), Value::Number(0.));
+
+    settings.set_save_delay(Duration::from_millis(10));
+    instance.set_property("font-size", Value::Number(14.)).unwrap();
+    instance.set_property("font-size", Value::Number(16.)).unwrap();
+    i_slint_core::tests::slint_mock_elapsed_time(20);
+    i_slint_core::timers::TimerList::maybe_activate_timers();
+    let saved = store.0.borrow().clone().unwrap();
+    assert_eq!(saved.get_field("font-size"), Some(&Value::Number(16.)));
+    assert_eq!(saved.get_field("other"), None);
+
+    // The changes are tracked again after a save
+    *store.0.borrow_mut() = None;
+    instance.set_property("dark-mode", Value::Bool(false)).unwrap();
+    i_slint_core::tests::slint_mock_elapsed_time(20);
+    i_slint_core::timers::TimerList::maybe_activate_timers();
+    assert_eq!(
+        store.0.borrow().as_ref().unwrap().get_field("dark-mode"),
+        Some(&Value::Bool(false))
+    );
+
+    // The errors of the saves after a change are passed to the handler
+    struct FailingStore;
+    impl SettingsStore for FailingStore {
+        fn load(&self) -> Option<Struct> {
+            None
+        }
+        fn save(&self, _: &Struct) -> std::io::Result<()> {
+            Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "read-only"))
+        }
+    }
+    let failing = PersistentSettings::bind(&instance, &["other"], FailingStore);
+    failing.set_save_delay(Duration::from_millis(10));
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let errors_clone = errors.clone();
+    failing.on_save_error(move |err| errors_clone.borrow_mut().push(err.kind()));
+    instance.set_property("other", Value::Number(4.)).unwrap();
+    i_slint_core::tests::slint_mock_elapsed_time(20);
+    i_slint_core::timers::TimerList::maybe_activate_timers();
+    assert_eq!(*errors.borrow(), [std::io::ErrorKind::PermissionDenied]);
+    drop(failing);
+
+    drop(settings);
+    *store.0.borrow_mut() = None;
+    instance.set_property("dark-mode", Value::Bool(true)).unwrap();
+    i_slint_core::tests::slint_mock_elapsed_time(20);
+    i_slint_core::timers::TimerList::maybe_activate_timers();
+    assert!(store.0.borrow().is_none());
+}
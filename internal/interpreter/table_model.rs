@@ -0,0 +1,308 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Models of rows with named and typed columns, to show tables and to sort or filter them by
+    column from Rust.
+
+    A [`TableModel`] is a model of [`Value::Struct`] rows, with a field for each of its
+    [`Column`]s, so it can be set as the value of an array property of a component.
+    [`VecTableModel`] stores the rows in a `Vec<Struct>`.
+
+    ```rust
+    use slint_interpreter::table_model::{SortOrder, TableModel, VecTableModel};
+    use slint_interpreter::{Struct, Value};
+    let person = |name: &str| -> Struct {
+        std::iter::once(("name".to_string(), Value::String(name.into()))).collect()
+    };
+    let table = VecTableModel::from(vec![person("Bob"), person("Alice")]);
+    let name = table.column_index("name").unwrap();
+    table.sort_by_column(name, SortOrder::Ascending);
+    assert_eq!(table.cell(0, name), Some(Value::String("Alice".into())));
+    ```
+*/
+
+use crate::{Struct, Value, ValueType};
+use i_slint_core::model::{Model, ModelNotify, ModelTracker};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+
+/// A column of a [`TableModel`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Column {
+    /// The name of the field of the rows that holds the cells of this column
+    pub name: String,
+    /// The type of the cells of this column
+    pub value_type: ValueType,
+}
+
+impl Column {
+    /// A column for the field with this name, which contains values of this type
+    pub fn new(name: impl Into<String>, value_type: ValueType) -> Self {
+        Self { name: name.into(), value_type }
+    }
+}
+
+/// The order of a sort
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SortOrder {
+    /// From the smallest value to the largest
+    Ascending,
+    /// From the largest value to the smallest
+    Descending,
+}
+
+/// A model of rows that are [`Value::Struct`] with a field for each column
+pub trait TableModel: Model<Data = Value> {
+    /// The columns of the table
+    fn columns(&self) -> Vec<Column>;
+
+    /// Returns the index of the column with this name
+    fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns().iter().position(|c| c.name == name)
+    }
+
+    /// Returns the value of the cell at this row and column
+    fn cell(&self, row: usize, column: usize) -> Option<Value> {
+        let name = self.columns().into_iter().nth(column)?.name;
+        match self.row_data(row)? {
+            Value::Struct(row) => row.get_field(&name).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Sets the value of the cell at this row and column
+    fn set_cell(&self, row: usize, column: usize, value: Value) {
+        let name = match self.columns().into_iter().nth(column) {
+            Some(column) => column.name,
+            None => return,
+        };
+        if let Some(Value::Struct(mut data)) = self.row_data(row) {
+            data.set_field(name, value);
+            self.set_row_data(row, Value::Struct(data));
+        }
+    }
+}
+
+/// Compares two cells of the same column, to sort the rows by this column. The values of
+/// different types, or of types that cannot be sorted, are equal.
+pub fn compare_cells(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.as_str().cmp(b.as_str()),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::DateTime(a), Value::DateTime(b)) => {
+            a.millis_since_epoch().cmp(&b.millis_since_epoch())
+        }
+        (Value::EnumerationValue(_, a), Value::EnumerationValue(_, b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+type Filter = (usize, Box<dyn Fn(&Value) -> bool>);
+
+/// A [`TableModel`] that stores its rows in a `Vec<Struct>`, and that can be sorted and filtered
+/// by column
+pub struct VecTableModel {
+    columns: Vec<Column>,
+    rows: RefCell<Vec<Struct>>,
+    /// The index in `rows` of the visible rows, when there is a filter
+    visible_rows: RefCell<Option<Vec<usize>>>,
+    filter: RefCell<Option<Filter>>,
+    notify: ModelNotify,
+}
+
+impl VecTableModel {
+    /// A table with these columns and rows
+    pub fn new(columns: Vec<Column>, rows: Vec<Struct>) -> Self {
+        Self {
+            columns,
+            rows: RefCell::new(rows),
+            visible_rows: Default::default(),
+            filter: Default::default(),
+            notify: Default::default(),
+        }
+    }
+
+    /// Adds a row at the end of the table
+    pub fn push(&self, row: Struct) {
+        let index = self.rows.borrow().len();
+        self.insert(index, row)
+    }
+
+    /// Inserts a row at this index in the rows of the table, ignoring the filter
+    pub fn insert(&self, index: usize, row: Struct) {
+        self.rows.borrow_mut().insert(index, row);
+        if self.filter.borrow().is_some() {
+            self.refresh_filter();
+        } else {
+            self.notify.row_added(index, 1);
+        }
+    }
+
+    /// Removes the row at this index in the rows of the table, ignoring the filter
+    pub fn remove(&self, index: usize) -> Struct {
+        let row = self.rows.borrow_mut().remove(index);
+        if self.filter.borrow().is_some() {
+            self.refresh_filter();
+        } else {
+            self.notify.row_removed(index, 1);
+        }
+        row
+    }
+
+    /// Returns all the rows of the table, including the ones that are hidden by the filter
+    pub fn rows(&self) -> Vec<Struct> {
+        self.rows.borrow().clone()
+    }
+
+    /// Sorts the rows by the values of a column. The sort is stable, so sorting by another
+    /// column first gives a secondary sort order.
+    pub fn sort_by_column(&self, column: usize, order: SortOrder) {
+        let name = match self.columns.get(column) {
+            Some(column) => column.name.clone(),
+            None => return,
+        };
+        self.rows.borrow_mut().sort_by(|a, b| {
+            let ordering = match (a.get_field(&name), b.get_field(&name)) {
+                (Some(a), Some(b)) => compare_cells(a, b),
+                _ => Ordering::Equal,
+            };
+            match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        });
+        if self.filter.borrow().is_some() {
+            self.refresh_filter();
+        } else {
+            for row in 0..self.rows.borrow().len() {
+                self.notify.row_changed(row);
+            }
+        }
+    }
+
+    /// Only shows the rows for which `filter` returns true with the value of the cell of this
+    /// column
+    pub fn set_filter(&self, column: usize, filter: impl Fn(&Value) -> bool + 'static) {
+        *self.filter.borrow_mut() = Some((column, Box::new(filter)));
+        self.refresh_filter();
+    }
+
+    /// Shows all the rows again
+    pub fn clear_filter(&self) {
+        *self.filter.borrow_mut() = None;
+        self.refresh_filter();
+    }
+
+    /// Computes the visible rows again, and notifies that all the rows changed
+    fn refresh_filter(&self) {
+        let old_count = self.row_count();
+        let visible_rows = self.filter.borrow().as_ref().map(|(column, filter)| {
+            let name = self.columns.get(*column).map(|c| c.name.as_str());
+            self.rows
+                .borrow()
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| match name.and_then(|name| row.get_field(name)) {
+                    Some(value) => filter(value),
+                    None => false,
+                })
+                .map(|(index, _)| index)
+                .collect()
+        });
+        *self.visible_rows.borrow_mut() = visible_rows;
+        self.notify.row_removed(0, old_count);
+        self.notify.row_added(0, self.row_count());
+    }
+
+    /// The index in `rows` of the visible row at this index
+    fn source_row(&self, row: usize) -> Option<usize> {
+        match &*self.visible_rows.borrow() {
+            Some(visible_rows) => visible_rows.get(row).cloned(),
+            None if row < self.rows.borrow().len() => Some(row),
+            None => None,
+        }
+    }
+}
+
+impl From<Vec<Struct>> for VecTableModel {
+    /// A table with these rows, and a column for each field of the first row, sorted by name
+    fn from(rows: Vec<Struct>) -> Self {
+        let mut columns = rows.first().map_or(Vec::new(), |row| {
+            row.iter().map(|(name, value)| Column::new(name, value.value_type())).collect()
+        });
+        columns.sort_by(|a, b| a.name.cmp(&b.name));
+        Self::new(columns, rows)
+    }
+}
+
+impl Model for VecTableModel {
+    type Data = Value;
+
+    fn row_count(&self) -> usize {
+        match &*self.visible_rows.borrow() {
+            Some(visible_rows) => visible_rows.len(),
+            None => self.rows.borrow().len(),
+        }
+    }
+
+    fn row_data(&self, row: usize) -> Option<Value> {
+        let row = self.source_row(row)?;
+        self.rows.borrow().get(row).cloned().map(Value::Struct)
+    }
+
+    fn set_row_data(&self, row: usize, data: Value) {
+        if let (Some(source_row), Value::Struct(data)) = (self.source_row(row), data) {
+            self.rows.borrow_mut()[source_row] = data;
+            self.notify.row_changed(row);
+        }
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+impl TableModel for VecTableModel {
+    fn columns(&self) -> Vec<Column> {
+        self.columns.clone()
+    }
+}
+
+#[test]
+fn test_vec_table_model() {
+    let person = |name: &str, age: f64| -> Struct {
+        [("name", Value::String(name.into())), ("age", Value::Number(age))]
+            .into_iter()
+            .map(|(field, value)| (field.to_string(), value))
+            .collect()
+    };
+    let table = VecTableModel::from(vec![person("Olivier", 40.), person("Simon", 30.)]);
+    assert_eq!(
+        table.columns(),
+        vec![Column::new("age", ValueType::Number), Column::new("name", ValueType::String)]
+    );
+    let (age, name) = (table.column_index("age").unwrap(), table.column_index("name").unwrap());
+
+    table.push(person("Tobias", 35.));
+    table.sort_by_column(age, SortOrder::Ascending);
+    let names = || (0..table.row_count()).map(|row| table.cell(row, name)).collect::<Vec<_>>();
+    let string = |s: &str| Some(Value::String(s.into()));
+    assert_eq!(names(), vec![string("Simon"), string("Tobias"), string("Olivier")]);
+
+    table.set_filter(age, |age| matches!(age, Value::Number(age) if *age > 31.));
+    assert_eq!(names(), vec![string("Tobias"), string("Olivier")]);
+    table.set_cell(0, name, Value::String("Aurindam".into()));
+    table.sort_by_column(name, SortOrder::Descending);
+    assert_eq!(names(), vec![string("Olivier"), string("Aurindam")]);
+
+    table.clear_filter();
+    assert_eq!(names(), vec![string("Simon"), string("Olivier"), string("Aurindam")]);
+    assert_eq!(table.rows().len(), 3);
+}
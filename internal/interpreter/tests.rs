@@ -43,3 +43,26 @@ fn reuse_window() {
         instance
     };
 }
+
+#[test]
+fn renderer_statistics_item_count() {
+    i_slint_backend_testing::init();
+    use crate::{ComponentCompiler, ComponentHandle, Value};
+    let code = r#"
+        MainWindow := Window {
+            property <int> count: 3;
+            for i in count: Rectangle {
+                Text { text: i; }
+            }
+        }
+    "#;
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+    assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+    let instance = definition.unwrap().create();
+    let item_count = instance.window().renderer_statistics().item_count;
+    instance.set_property("count", Value::Number(5.)).unwrap();
+    assert_eq!(instance.window().renderer_statistics().item_count, item_count + 4);
+    instance.set_property("count", Value::Number(0.)).unwrap();
+    assert_eq!(instance.window().renderer_statistics().item_count, item_count - 6);
+}
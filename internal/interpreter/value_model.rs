@@ -50,6 +50,13 @@ fn row_count(&self) -> usize {
             Value::Number(x) => *x as usize,
             Value::Void => 0,
             Value::Model(model_ptr) => model_ptr.row_count(),
+            Value::ComponentFactory(factory) => {
+                if factory.is_empty() {
+                    0
+                } else {
+                    1
+                }
+            }
             x => panic!("Invalid model {:?}", x),
         }
     }
@@ -59,7 +66,7 @@ fn row_data(&self, row: usize) -> Option<Self::Data> {
             None
         } else {
             Some(match &*self.value.borrow() {
-                Value::Bool(_) => Value::Void,
+                Value::Bool(_) | Value::ComponentFactory(_) => Value::Void,
                 Value::Number(_) => Value::Number(row as _),
                 Value::Model(model_ptr) => model_ptr.row_data(row)?,
                 x => panic!("Invalid model {:?}", x),
@@ -55,9 +55,12 @@ pub fn test(testcase: &test_driver_lib::TestCase) -> Result<(), Box<dyn Error>>
     )?;
     let source = std::fs::read_to_string(&testcase.absolute_path)?;
     let include_paths = test_driver_lib::extract_include_paths(&source);
+    // The tests run in an async function, so that they can await the Promises
+    writeln!(main_js, "(async function() {{")?;
     for x in test_driver_lib::extract_test_functions(&source).filter(|x| x.language_id == "js") {
         write!(main_js, "{{\n    {}\n}}\n", x.source.replace("\n", "\n    "))?;
     }
+    writeln!(main_js, "}})().catch((error) => {{ console.error(error); process.exit(1); }});")?;
 
     let output = std::process::Command::new("node")
         .arg(dir.path().join("main.js"))
@@ -4,7 +4,9 @@
 use clap::Parser;
 use i_slint_compiler::diagnostics::BuildDiagnostics;
 use i_slint_compiler::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -13,10 +15,24 @@ struct Cli {
     #[clap(short = 'f', long = "format", default_value = "cpp")]
     format: generator::OutputFormat,
 
+    /// Read the include paths, library paths, style, flags, defines, resource embedding, and outputs
+    /// from a json project configuration file
+    #[clap(long = "config", name = "project file", parse(from_os_str))]
+    config: Option<std::path::PathBuf>,
+
+    /// Generate a file in the given format, instead of the file of `-o` (eg: `--emit rust=app.rs`).
+    /// The formats are 'cpp', 'rust', 'llr', and 'bundle'. Can be used several times
+    #[clap(long = "emit", name = "format=path", number_of_values = 1)]
+    emits: Vec<String>,
+
     /// Include path for other .slint files
     #[clap(short = 'I', name = "include path", number_of_values = 1, parse(from_os_str))]
     include_paths: Vec<std::path::PathBuf>,
 
+    /// Path of a library imported with `@name` (eg: `--library mylib=path/to/mylib`)
+    #[clap(long = "library", name = "name=path", number_of_values = 1)]
+    libraries: Vec<String>,
+
     /// Path to .slint file ('-' for stdin)
     #[clap(name = "file", parse(from_os_str))]
     path: std::path::PathBuf,
@@ -25,6 +41,23 @@ struct Cli {
     #[clap(long, name = "style name")]
     style: Option<String>,
 
+    /// Set a flag for the `@if` conditional blocks
+    #[clap(long = "flag", name = "flag", number_of_values = 1)]
+    flags: Vec<String>,
+
+    /// Define a constant accessible as `Defines.name` (eg: `-D name=value`)
+    #[clap(short = 'D', long = "define", name = "name=value", number_of_values = 1)]
+    defines: Vec<String>,
+
+    /// Set the level of a lint to 'allow', 'warn', or 'deny' (eg: `--lint unused-property=warn`)
+    #[clap(long = "lint", name = "lint=level", number_of_values = 1)]
+    lints: Vec<String>,
+
+    /// Instead of generating code, write a bundle with the .slint files and the images and fonts
+    /// they use, that the interpreter can load without the original source tree
+    #[clap(long = "bundle")]
+    bundle: bool,
+
     /// Generate a dependency file
     #[clap(name = "dependency file", long = "depfile", number_of_values = 1, parse(from_os_str))]
     depfile: Option<std::path::PathBuf>,
@@ -34,46 +67,235 @@ struct Cli {
     output: std::path::PathBuf,
 }
 
+/// The project configuration file, read with `--config`. The relative paths are relative to the
+/// directory of the file, and the command line arguments are added to its settings.
+#[derive(Default, serde::Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct ProjectConfig {
+    include_paths: Vec<PathBuf>,
+    library_paths: HashMap<String, PathBuf>,
+    style: Option<String>,
+    flags: Vec<String>,
+    defines: HashMap<String, serde_json::Value>,
+    embed_resources: Option<bool>,
+    /// The level of the lints, by name
+    lints: HashMap<String, String>,
+    /// The elements reported by the `deprecated-element` lint, with what to use instead
+    deprecated_elements: HashMap<String, String>,
+    /// The files to generate, by format
+    outputs: BTreeMap<String, PathBuf>,
+}
+
+/// A file to generate
+enum Output {
+    Code(generator::OutputFormat, PathBuf),
+    Bundle(PathBuf),
+}
+
+impl Output {
+    fn parse(format: &str, path: PathBuf) -> Result<Self, String> {
+        match format {
+            "bundle" => Ok(Self::Bundle(path)),
+            _ => Ok(Self::Code(format.parse()?, path)),
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            Self::Code(_, path) | Self::Bundle(path) => path,
+        }
+    }
+}
+
+fn read_project_config(path: &Path) -> Result<ProjectConfig, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|err| format!("Cannot open {}: {}", path.display(), err))?;
+    let mut config: ProjectConfig = serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|err| format!("Cannot read {}: {}", path.display(), err))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    for path in config
+        .include_paths
+        .iter_mut()
+        .chain(config.library_paths.values_mut())
+        .chain(config.outputs.values_mut())
+    {
+        *path = base_dir.join(&*path);
+    }
+    Ok(config)
+}
+
+/// The settings of the compilation, from the project configuration file and the command line
+struct Settings {
+    project: ProjectConfig,
+    defines: HashMap<String, DefineValue>,
+    lints: lint::LintConfiguration,
+    outputs: Vec<Output>,
+}
+
+/// Merge the project configuration file and the command line arguments
+fn read_settings(args: &Cli) -> Result<Settings, String> {
+    let mut project = match &args.config {
+        Some(config_path) => read_project_config(config_path)?,
+        None => ProjectConfig::default(),
+    };
+    project.include_paths.extend(args.include_paths.iter().cloned());
+    for library in &args.libraries {
+        let (name, path) = library
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid library '{}', expected name=path", library))?;
+        project.library_paths.insert(name.into(), path.into());
+    }
+    if args.style.is_some() {
+        project.style = args.style.clone();
+    }
+    project.flags.extend(args.flags.iter().cloned());
+
+    let mut defines = HashMap::new();
+    for (name, value) in &project.defines {
+        let value = match value {
+            serde_json::Value::Bool(value) => DefineValue::Bool(*value),
+            serde_json::Value::Number(value) => {
+                DefineValue::Number(value.as_f64().unwrap_or_default())
+            }
+            serde_json::Value::String(value) => DefineValue::String(value.clone()),
+            _ => {
+                return Err(format!(
+                    "Invalid value of the define '{}': expected a bool, a number, or a string",
+                    name
+                ))
+            }
+        };
+        defines.insert(name.clone(), value);
+    }
+    for define in &args.defines {
+        let (name, value) = define.split_once('=').unwrap_or((define, "true"));
+        let value = match value {
+            "true" => DefineValue::Bool(true),
+            "false" => DefineValue::Bool(false),
+            _ => value.parse().map_or_else(|_| value.into(), DefineValue::Number),
+        };
+        defines.insert(name.into(), value);
+    }
+
+    let mut lints = lint::LintConfiguration {
+        deprecated_elements: std::mem::take(&mut project.deprecated_elements),
+        ..Default::default()
+    };
+    let lint_levels = project.lints.iter().map(|(name, level)| (name.as_str(), level.as_str()));
+    let lint_args = args.lints.iter().map(|lint| {
+        lint.split_once('=').ok_or_else(|| format!("Invalid lint '{}', expected lint=level", lint))
+    });
+    for lint in lint_levels.map(Ok).chain(lint_args) {
+        let (name, level) = lint?;
+        lints.levels.insert(name.parse()?, level.parse()?);
+    }
+
+    let mut outputs = vec![];
+    for (format, path) in std::mem::take(&mut project.outputs) {
+        outputs.push(Output::parse(&format, path)?);
+    }
+    for emit in &args.emits {
+        let (format, path) = emit
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid output '{}', expected format=path", emit))?;
+        outputs.push(Output::parse(format, path.into())?);
+    }
+    if outputs.is_empty() {
+        outputs.push(if args.bundle {
+            Output::Bundle(args.output.clone())
+        } else {
+            Output::Code(args.format, args.output.clone())
+        });
+    }
+    if outputs.iter().any(|output| matches!(output, Output::Bundle(_)))
+        && args.path == Path::new("-")
+    {
+        return Err("Cannot create a bundle from stdin".into());
+    }
+    if outputs.iter().filter(|output| output.path() == Path::new("-")).count() > 1 {
+        return Err("Only one output can be written to stdout".into());
+    }
+    Ok(Settings { project, defines, lints, outputs })
+}
+
 fn main() -> std::io::Result<()> {
     proc_macro2::fallback::force(); // avoid a abort if panic=abort is set
     let args = Cli::parse();
-    let mut diag = BuildDiagnostics::default();
-    let syntax_node = parser::parse_file(&args.path, &mut diag);
-    //println!("{:#?}", syntax_node);
-    if diag.has_error() {
-        diag.print();
-        std::process::exit(-1);
-    }
-    let mut compiler_config = CompilerConfiguration::new(args.format);
-    compiler_config.include_paths = args.include_paths;
-    if let Some(style) = args.style {
-        compiler_config.style = Some(style);
-    }
-    let syntax_node = syntax_node.expect("diags contained no compilation errors");
-    let (doc, diag) = spin_on::spin_on(compile_syntax_node(syntax_node, diag, compiler_config));
 
-    let diag = diag.check_and_exit_on_error();
+    let Settings { project, defines, lints, outputs } = match read_settings(&args) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(-1);
+        }
+    };
+
+    let mut dependencies = BTreeSet::new();
+    for (index, output) in outputs.iter().enumerate() {
+        // Each format needs its own compilation, because the passes depend on the format
+        let format = match output {
+            Output::Code(format, _) => *format,
+            Output::Bundle(_) => generator::OutputFormat::Interpreter,
+        };
+        let mut compiler_config = CompilerConfiguration::new(format);
+        if let Some(embed_resources) = project.embed_resources {
+            compiler_config.embed_resources = embed_resources;
+        }
+        if let Output::Bundle(_) = output {
+            // Collect the images and fonts in the embedded resources
+            compiler_config.embed_resources = true;
+        }
+        compiler_config.include_paths = project.include_paths.clone();
+        compiler_config.library_paths = project.library_paths.clone();
+        compiler_config.style = project.style.clone();
+        compiler_config.conditional_flags.extend(project.flags.iter().cloned());
+        compiler_config.defines = defines.clone();
+        compiler_config.lints = lints.clone();
+
+        let mut diag = BuildDiagnostics::default();
+        let syntax_node = parser::parse_file(&args.path, &mut diag);
+        //println!("{:#?}", syntax_node);
+        if diag.has_error() {
+            diag.print();
+            std::process::exit(-1);
+        }
+        let syntax_node = syntax_node.expect("diags contained no compilation errors");
+        let (doc, diag) = spin_on::spin_on(compile_syntax_node(syntax_node, diag, compiler_config));
 
-    if args.output == std::path::Path::new("-") {
-        generator::generate(args.format, &mut std::io::stdout(), &doc)?;
-    } else {
-        generator::generate(args.format, &mut std::fs::File::create(&args.output)?, &doc)?;
+        let diag = diag.check_and_exit_on_error();
+
+        let path = output.path();
+        let mut destination: Box<dyn Write> = if path == Path::new("-") {
+            Box::new(std::io::stdout())
+        } else {
+            Box::new(std::fs::File::create(path)?)
+        };
+        match output {
+            Output::Code(format, _) => generator::generate(*format, &mut destination, &doc)?,
+            Output::Bundle(_) => {
+                bundle::write_bundle(&doc, &args.path, &diag.all_loaded_files, &mut destination)?
+            }
+        }
+
+        dependencies.extend(doc.root_component.dependencies.borrow().iter().cloned());
+        // The warnings are the same for all the outputs
+        if index == 0 {
+            diag.print_warnings_and_exit_on_error();
+        }
     }
 
     if let Some(depfile) = args.depfile {
         let mut f = std::fs::File::create(depfile)?;
-        write!(f, "{}:", args.output.display())?;
-        for x in &diag.all_loaded_files {
+        let targets: Vec<String> =
+            outputs.iter().map(|output| output.path().display().to_string()).collect();
+        write!(f, "{}:", targets.join(" "))?;
+        for x in dependencies {
             if x.is_absolute() {
                 write!(f, " {}", x.display())?;
             }
         }
-        for resource in doc.root_component.embedded_file_resources.borrow().keys() {
-            write!(f, " {}", resource)?;
-        }
 
         writeln!(f)?;
     }
-    diag.print_warnings_and_exit_on_error();
     Ok(())
 }
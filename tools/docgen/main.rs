@@ -0,0 +1,155 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    Generate the Markdown or HTML documentation of the exported components of .slint files,
+    with the doc comments of the components and of their properties, callbacks, and functions.
+    ```sh
+        cargo run --bin slint-docgen -- -o components.html ui/button.slint ui/slider.slint
+    ```
+*/
+
+use clap::Parser;
+use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::docs::{ComponentDoc, MemberDoc};
+use std::io::Write;
+
+#[derive(Copy, Clone, Debug, PartialEq, clap::ArgEnum)]
+enum Format {
+    Markdown,
+    Html,
+}
+
+#[derive(clap::Parser)]
+#[clap(author, version, about, long_about = None)]
+struct Cli {
+    #[clap(name = "path to .slint file(s)", parse(from_os_str), required = true)]
+    paths: Vec<std::path::PathBuf>,
+
+    /// Write the documentation to this file instead of stdout
+    #[clap(short = 'o', name = "output file", parse(from_os_str))]
+    output: Option<std::path::PathBuf>,
+
+    /// The format of the documentation. The default is html for the .html and .htm output files,
+    /// and markdown otherwise
+    #[clap(short = 'f', long = "format", arg_enum)]
+    format: Option<Format>,
+}
+
+fn main() -> std::io::Result<()> {
+    let args = Cli::parse();
+
+    let mut diag = BuildDiagnostics::default();
+    let mut components = vec![];
+    for path in &args.paths {
+        let source = std::fs::read_to_string(path)?;
+        components.extend(i_slint_compiler::docs::extract_docs_from_source(
+            source,
+            Some(path.as_path()),
+            &mut diag,
+        ));
+    }
+    if diag.has_error() {
+        diag.print();
+        std::process::exit(-1);
+    }
+
+    let format = args.format.unwrap_or_else(|| {
+        match args.output.as_ref().and_then(|o| o.extension()).and_then(|e| e.to_str()) {
+            Some("html") | Some("htm") => Format::Html,
+            _ => Format::Markdown,
+        }
+    });
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(std::fs::File::create(path)?),
+        None => Box::new(std::io::stdout()),
+    };
+    match format {
+        Format::Markdown => write_markdown(&components, &mut output),
+        Format::Html => write_html(&components, &mut output),
+    }
+}
+
+fn write_markdown(components: &[ComponentDoc], out: &mut impl Write) -> std::io::Result<()> {
+    for component in components {
+        writeln!(out, "## {}\n", component.name)?;
+        if !component.doc.is_empty() {
+            writeln!(out, "{}\n", component.doc)?;
+        }
+        if let Some(base) = &component.base {
+            writeln!(out, "Inherits `{}`\n", base)?;
+        }
+        for (title, members) in [
+            ("Properties", &component.properties),
+            ("Callbacks", &component.callbacks),
+            ("Functions", &component.functions),
+        ] {
+            if members.is_empty() {
+                continue;
+            }
+            writeln!(out, "### {}\n", title)?;
+            for member in members {
+                write!(out, " - **`{}`** (_{}_", member.name, member.ty)?;
+                if let Some(default) = &member.default {
+                    write!(out, ", default: `{}`", default)?;
+                }
+                write!(out, ")")?;
+                if !member.doc.is_empty() {
+                    // Indent the next lines of the comment to keep them in the list item
+                    write!(out, ": {}", member.doc.replace('\n', "\n   "))?;
+                }
+                writeln!(out)?;
+            }
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_html(components: &[ComponentDoc], out: &mut impl Write) -> std::io::Result<()> {
+    writeln!(out, "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Components</title></head>\n<body>")?;
+    for component in components {
+        writeln!(out, "<h2 id=\"{0}\">{0}</h2>", escape(&component.name))?;
+        write_html_doc(&component.doc, out)?;
+        if let Some(base) = &component.base {
+            writeln!(out, "<p>Inherits <code>{}</code></p>", escape(base))?;
+        }
+        for (title, members) in [
+            ("Properties", &component.properties),
+            ("Callbacks", &component.callbacks),
+            ("Functions", &component.functions),
+        ] {
+            if !members.is_empty() {
+                writeln!(out, "<h3>{}</h3>", title)?;
+                write_html_members(members, out)?;
+            }
+        }
+    }
+    writeln!(out, "</body>\n</html>")
+}
+
+fn write_html_members(members: &[MemberDoc], out: &mut impl Write) -> std::io::Result<()> {
+    writeln!(out, "<dl>")?;
+    for member in members {
+        write!(out, "<dt><code>{}</code> <em>{}</em>", escape(&member.name), escape(&member.ty))?;
+        if let Some(default) = &member.default {
+            write!(out, " (default: <code>{}</code>)", escape(default))?;
+        }
+        writeln!(out, "</dt>\n<dd>")?;
+        write_html_doc(&member.doc, out)?;
+        writeln!(out, "</dd>")?;
+    }
+    writeln!(out, "</dl>")
+}
+
+/// Write the doc comment, with a paragraph for each block of lines separated by an empty line
+fn write_html_doc(doc: &str, out: &mut impl Write) -> std::io::Result<()> {
+    for paragraph in doc.split("\n\n").filter(|p| !p.trim().is_empty()) {
+        writeln!(out, "<p>{}</p>", escape(paragraph.trim()))?;
+    }
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
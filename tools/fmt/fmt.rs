@@ -1,19 +1,43 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use crate::writer::TokenWriter;
-use i_slint_compiler::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode};
+use crate::writer::{FileWriter, TokenWriter};
+use i_slint_compiler::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// The options of the formatter
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct FormatOptions {
+    /// Sort the members of the elements: the property and callback declarations first, then
+    /// the property bindings, then the callback handlers, and then the children and the other
+    /// members. The members of a group keep their order. The elements that contain comments
+    /// keep the order of their members, as the comments could refer to the member below or above.
+    ///
+    /// When false, the default, the order of the members is kept.
+    pub sort_members: bool,
+}
+
+/// Format the document, by passing each of its tokens to the writer with the whitespace to change
+pub fn format_document(
+    doc: syntax_nodes::Document,
+    writer: &mut impl TokenWriter,
+) -> Result<(), std::io::Error> {
+    format_document_with_options(doc, writer, FormatOptions::default())
+}
 
-pub(crate) fn format_document(
+/// Like [`format_document`], with options
+pub fn format_document_with_options(
     doc: syntax_nodes::Document,
     writer: &mut impl TokenWriter,
+    options: FormatOptions,
 ) -> Result<(), std::io::Error> {
-    let mut state = FormatState::default();
+    let mut state = FormatState { options, ..Default::default() };
     format_node(&doc, writer, &mut state)
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct FormatState {
+    options: FormatOptions,
+
     /// The whitespace have been written, all further whitespace can be skipped
     skip_all_whitespace: bool,
     /// The whitespace to add before the next token
@@ -99,6 +123,9 @@ fn format_node(
         SyntaxKind::ChildrenPlaceholder => {
             return format_children_placeholder(node, writer, state);
         }
+        SyntaxKind::Array | SyntaxKind::ObjectLiteral | SyntaxKind::FunctionCallExpression => {
+            return format_comma_separated_list(node, writer, state);
+        }
 
         _ => (),
     }
@@ -220,8 +247,33 @@ fn format_element(
     state.new_line();
     let ins_ctn = state.insertion_count;
 
+    let sub = sub.collect::<Vec<_>>();
+    let members = sub.iter().filter_map(|n| n.as_node()).cloned().collect::<Vec<_>>();
+    let mut sorted_members = members.clone();
+    if state.options.sort_members && !sub.iter().any(|n| n.kind() == SyntaxKind::Comment) {
+        sorted_members.sort_by_key(|n| member_rank(n.kind()));
+    }
+    let mut member_index = 0;
+
     for n in sub {
-        if n.kind() == SyntaxKind::RBrace {
+        if let (NodeOrToken::Node(member), true) = (&n, sorted_members != members) {
+            // The tokens of this member are replaced by the ones of the member at this place in
+            // the sorted order
+            let mut formatted = Vec::new();
+            format_node(
+                &sorted_members[member_index],
+                &mut FileWriter { file: &mut formatted },
+                state,
+            )?;
+            member_index += 1;
+            let mut tokens = all_tokens(member).into_iter();
+            if let Some(first) = tokens.next() {
+                writer.with_new_content(first, &String::from_utf8_lossy(&formatted))?;
+            }
+            for token in tokens {
+                writer.with_new_content(token, "")?;
+            }
+        } else if n.kind() == SyntaxKind::RBrace {
             state.indentation_level -= 1;
             state.whitespace_to_add = None;
             if ins_ctn == state.insertion_count {
@@ -238,6 +290,25 @@ fn format_element(
     Ok(())
 }
 
+/// The position of a member of an element when sorting them
+fn member_rank(kind: SyntaxKind) -> u8 {
+    match kind {
+        SyntaxKind::PropertyDeclaration | SyntaxKind::CallbackDeclaration => 0,
+        SyntaxKind::Binding | SyntaxKind::TwoWayBinding => 1,
+        SyntaxKind::CallbackConnection => 2,
+        _ => 3,
+    }
+}
+
+fn all_tokens(node: &SyntaxNode) -> Vec<SyntaxToken> {
+    node.children_with_tokens()
+        .flat_map(|n| match n {
+            NodeOrToken::Node(n) => all_tokens(&n),
+            NodeOrToken::Token(t) => vec![t],
+        })
+        .collect()
+}
+
 fn format_sub_element(
     node: &SyntaxNode,
     writer: &mut impl TokenWriter,
@@ -448,6 +519,75 @@ fn format_expression(
     Ok(())
 }
 
+fn format_comma_separated_list(
+    node: &SyntaxNode,
+    writer: &mut impl TokenWriter,
+    state: &mut FormatState,
+) -> Result<(), std::io::Error> {
+    // The comma of the object literals is part of the member, so the members are flattened
+    let children: Vec<NodeOrToken> = node
+        .children_with_tokens()
+        .flat_map(|n| match n {
+            NodeOrToken::Node(n) if n.kind() == SyntaxKind::ObjectMember => {
+                n.children_with_tokens().collect()
+            }
+            n => vec![n],
+        })
+        .collect();
+    let next_kind = |index: usize| {
+        children[index + 1..]
+            .iter()
+            .map(|n| n.kind())
+            .find(|kind| !matches!(kind, SyntaxKind::Whitespace | SyntaxKind::Comment))
+    };
+    let is_last_comma = |index: usize| {
+        children[index].kind() == SyntaxKind::Comma
+            && matches!(
+                next_kind(index),
+                None | Some(SyntaxKind::RBracket | SyntaxKind::RBrace | SyntaxKind::RParent)
+            )
+    };
+    // The trailing comma is kept when the items are on several lines, where it makes adding an
+    // item at the end a one line change
+    let is_multi_line = node.text().to_string().contains('\n');
+    let is_trailing_comma = |index: usize| !is_multi_line && is_last_comma(index);
+
+    for (index, n) in children.iter().enumerate() {
+        let previous_kind = index.checked_sub(1).map(|i| children[i].kind());
+        match n {
+            // The whitespace before a comma is removed, and the one after a comma or a colon
+            // is a single space, unless the items are on several lines
+            NodeOrToken::Token(t) if t.kind() == SyntaxKind::Whitespace => {
+                if previous_kind != Some(SyntaxKind::Comment)
+                    && children.get(index + 1).map(|n| n.kind()) == Some(SyntaxKind::Comma)
+                {
+                    writer.with_new_content(t.clone(), "")?;
+                } else if matches!(previous_kind, Some(SyntaxKind::Comma | SyntaxKind::Colon))
+                    && !is_last_comma(index - 1)
+                    && !t.text().contains('\n')
+                {
+                    writer.with_new_content(t.clone(), " ")?;
+                } else {
+                    fold(n.clone(), writer, state)?;
+                }
+            }
+            NodeOrToken::Token(t) if is_trailing_comma(index) => {
+                writer.with_new_content(t.clone(), "")?;
+            }
+            _ => {
+                fold(n.clone(), writer, state)?;
+                if matches!(n.kind(), SyntaxKind::Comma | SyntaxKind::Colon)
+                    && !is_last_comma(index)
+                    && children.get(index + 1).map(|n| n.kind()) != Some(SyntaxKind::Whitespace)
+                {
+                    state.insert_whitespace(" ");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn format_children_placeholder(
     node: &SyntaxNode,
     writer: &mut impl TokenWriter,
@@ -465,7 +605,6 @@ fn format_children_placeholder(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::writer::FileWriter;
     use i_slint_compiler::diagnostics::BuildDiagnostics;
     use i_slint_compiler::parser::syntax_nodes;
 
@@ -619,6 +758,79 @@ fn file_with_an_import() {
         );
     }
 
+    #[test]
+    fn trailing_commas() {
+        assert_formatting(
+            r#"
+A := B {
+    model: [1,2 ,3,];
+    point: {x:1,y: 2,};
+    value: max(1,  2,);
+    items: [
+        { name: "a", value: 1 },
+        { name: "b" , value: 2 },
+    ];
+}"#,
+            r#"
+A := B {
+    model: [1, 2, 3];
+    point: {x: 1, y: 2};
+    value: max(1, 2);
+    items: [
+        { name: "a", value: 1 },
+        { name: "b", value: 2 },
+    ];
+}"#,
+        );
+    }
+
+    fn assert_formatting_with_options(unformatted: &str, formatted: &str, options: FormatOptions) {
+        let syntax_node = i_slint_compiler::parser::parse(
+            String::from(unformatted),
+            None,
+            &mut BuildDiagnostics::default(),
+        );
+        let doc = syntax_nodes::Document::new(syntax_node).unwrap();
+        let mut file = Vec::new();
+        format_document_with_options(doc, &mut FileWriter { file: &mut file }, options).unwrap();
+        assert_eq!(String::from_utf8(file).unwrap(), formatted);
+    }
+
+    #[test]
+    fn sort_members() {
+        let options = FormatOptions { sort_members: true };
+        assert_formatting_with_options(
+            r#"
+Main :=Window{clicked=>{a=1;} Text{} width:10px;callback clicked;property<int>a;height:5px;}"#,
+            r#"
+Main := Window {
+    callback clicked;
+    property <int> a;
+    width: 10px;
+    height: 5px;
+    clicked => {a=1;}
+    Text { }
+}"#,
+            options,
+        );
+        // The order is kept when there are comments
+        assert_formatting_with_options(
+            r#"
+A := B {
+    width: 10px;
+    // The value
+    property <int> a;
+}"#,
+            r#"
+A := B {
+    width: 10px;
+    // The value
+    property <int> a;
+}"#,
+            options,
+        );
+    }
+
     #[test]
     fn children() {
         // Regression test - children was causing additional newlines
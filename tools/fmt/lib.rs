@@ -0,0 +1,43 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+    The formatter of the `slint-fmt` binary, for the editors and the tools that format .slint code.
+
+    [`format_source`] returns the formatted code. The [`TokenWriter`] trait gets each token with
+    the whitespace to change instead, for the editors that want the edits rather than the full file.
+
+    By default, the formatter keeps the order of the properties, callbacks, and children of the
+    elements. With [`FormatOptions::sort_members`], the declarations come first, then the property
+    bindings, then the callback handlers, and then the children.
+
+    The trailing comma of the arrays, the object literals, and the function arguments is removed
+    when they fit on one line, and kept when the items are on several lines.
+*/
+
+use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::parser::syntax_nodes;
+
+mod fmt;
+mod writer;
+
+pub use fmt::{format_document, format_document_with_options, FormatOptions};
+pub use writer::{FileWriter, TokenWriter};
+
+/// Format the .slint source code, or return None if it has syntax errors
+pub fn format_source(source: &str) -> Option<String> {
+    format_source_with_options(source, FormatOptions::default())
+}
+
+/// Like [`format_source`], with options
+pub fn format_source_with_options(source: &str, options: FormatOptions) -> Option<String> {
+    let mut diag = BuildDiagnostics::default();
+    let syntax_node = i_slint_compiler::parser::parse(source.to_owned(), None, &mut diag);
+    if diag.has_error() {
+        return None;
+    }
+    let doc = syntax_nodes::Document::new(syntax_node)?;
+    let mut file = Vec::new();
+    format_document_with_options(doc, &mut FileWriter { file: &mut file }, options).ok()?;
+    String::from_utf8(file).ok()
+}
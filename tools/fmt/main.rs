@@ -10,9 +10,6 @@
 
     Some code in this main.rs file is duplicated with the syntax_updater, i guess it could
     be refactored in a separate utility crate or module or something.
-
-    The [`TokenWriter`] trait is meant to be able to support the LSP later as the
-    LSP wants just the edits, not the full file
 */
 
 use i_slint_compiler::diagnostics::BuildDiagnostics;
@@ -22,9 +19,6 @@
 
 use clap::Parser;
 
-mod fmt;
-mod writer;
-
 #[derive(clap::Parser)]
 #[clap(author, version, about, long_about = None)]
 struct Cli {
@@ -34,26 +28,35 @@ struct Cli {
     /// modify the file inline instead of printing to stdout
     #[clap(short, long)]
     inline: bool,
+
+    /// sort the members of the elements: declarations, bindings, callback handlers, then children
+    #[clap(long)]
+    sort_members: bool,
 }
 
 fn main() -> std::io::Result<()> {
     let args = Cli::parse();
+    let options = slint_fmt::FormatOptions { sort_members: args.sort_members };
 
     for path in args.paths {
         let source = std::fs::read_to_string(&path)?;
 
         if args.inline {
             let file = std::fs::File::create(&path)?;
-            process_file(source, path, file)?
+            process_file(source, path, file, options)?
         } else {
-            process_file(source, path, std::io::stdout())?
+            process_file(source, path, std::io::stdout(), options)?
         }
     }
     Ok(())
 }
 
 /// FIXME! this is duplicated with the updater
-fn process_rust_file(source: String, mut file: impl Write) -> std::io::Result<()> {
+fn process_rust_file(
+    source: String,
+    mut file: impl Write,
+    options: slint_fmt::FormatOptions,
+) -> std::io::Result<()> {
     let mut source_slice = &source[..];
     let slint_macro = format!("{}!", "slint"); // in a variable so it does not appear as is
     'l: while let Some(idx) = source_slice.find(&slint_macro) {
@@ -98,7 +101,7 @@ fn process_rust_file(source: String, mut file: impl Write) -> std::io::Result<()
         let mut diag = BuildDiagnostics::default();
         let syntax_node = i_slint_compiler::parser::parse(code.to_owned(), None, &mut diag);
         let len = syntax_node.text_range().end().into();
-        visit_node(syntax_node, &mut file)?;
+        visit_node(syntax_node, &mut file, options)?;
         if diag.has_error() {
             file.write_all(&code.as_bytes()[len..])?;
             diag.print();
@@ -108,7 +111,11 @@ fn process_rust_file(source: String, mut file: impl Write) -> std::io::Result<()
 }
 
 /// FIXME! this is duplicated with the updater
-fn process_markdown_file(source: String, mut file: impl Write) -> std::io::Result<()> {
+fn process_markdown_file(
+    source: String,
+    mut file: impl Write,
+    options: slint_fmt::FormatOptions,
+) -> std::io::Result<()> {
     let mut source_slice = &source[..];
     const CODE_FENCE_START: &str = "```slint\n";
     const CODE_FENCE_END: &str = "```\n";
@@ -128,7 +135,7 @@ fn process_markdown_file(source: String, mut file: impl Write) -> std::io::Resul
         let mut diag = BuildDiagnostics::default();
         let syntax_node = i_slint_compiler::parser::parse(code.to_owned(), None, &mut diag);
         let len = syntax_node.text_range().end().into();
-        visit_node(syntax_node, &mut file)?;
+        visit_node(syntax_node, &mut file, options)?;
         if diag.has_error() {
             file.write_all(&code.as_bytes()[len..])?;
             diag.print();
@@ -141,11 +148,12 @@ fn process_slint_file(
     source: String,
     path: std::path::PathBuf,
     mut file: impl Write,
+    options: slint_fmt::FormatOptions,
 ) -> std::io::Result<()> {
     let mut diag = BuildDiagnostics::default();
     let syntax_node = i_slint_compiler::parser::parse(source.clone(), Some(&path), &mut diag);
     let len = syntax_node.node.text_range().end().into();
-    visit_node(syntax_node, &mut file)?;
+    visit_node(syntax_node, &mut file, options)?;
     if diag.has_error() {
         file.write_all(&source.as_bytes()[len..])?;
         diag.print();
@@ -157,18 +165,19 @@ fn process_file(
     source: String,
     path: std::path::PathBuf,
     mut file: impl Write,
+    options: slint_fmt::FormatOptions,
 ) -> std::io::Result<()> {
     match path.extension() {
-        Some(ext) if ext == "rs" => return process_rust_file(source, file),
-        Some(ext) if ext == "md" => return process_markdown_file(source, file),
+        Some(ext) if ext == "rs" => return process_rust_file(source, file, options),
+        Some(ext) if ext == "md" => return process_markdown_file(source, file, options),
         // Formatting .60 files because of backwards compatibility (project was recently renamed)
         Some(ext) if ext == "slint" || ext == ".60" => {
-            return process_slint_file(source, path, file)
+            return process_slint_file(source, path, file, options)
         }
         _ => {
             // This allows usage like `cat x.slint | slint-fmt /dev/stdin`
             if path.as_path() == Path::new("/dev/stdin") {
-                return process_slint_file(source, path, file);
+                return process_slint_file(source, path, file, options);
             }
             // With other file types, we just output them in their original form.
             return file.write_all(source.as_bytes());
@@ -176,10 +185,14 @@ fn process_file(
     }
 }
 
-fn visit_node(node: SyntaxNode, file: &mut impl Write) -> std::io::Result<()> {
+fn visit_node(
+    node: SyntaxNode,
+    file: &mut impl Write,
+    options: slint_fmt::FormatOptions,
+) -> std::io::Result<()> {
     if let Some(doc) = syntax_nodes::Document::new(node) {
-        let mut writer = writer::FileWriter { file };
-        fmt::format_document(doc, &mut writer)
+        let mut writer = slint_fmt::FileWriter { file };
+        slint_fmt::format_document_with_options(doc, &mut writer, options)
     } else {
         Err(std::io::Error::new(std::io::ErrorKind::Other, "Not a Document"))
     }
@@ -0,0 +1,222 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The colors of a document shown in the editor, and the text that replaces them when they are
+//! changed with the color picker
+
+use crate::DocumentCache;
+use i_slint_compiler::literals::{parse_color_literal, parse_named_color};
+use i_slint_compiler::parser::{SyntaxKind, SyntaxNode, SyntaxToken};
+use lsp_types::{Color, ColorInformation, ColorPresentation, ColorPresentationParams, TextEdit};
+
+/// How a color is written in the source
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ColorSyntax {
+    /// `#rrggbb`
+    Literal,
+    /// `Colors.red`
+    Named,
+    /// `rgb(255, 0, 0)` or `rgba(255, 0, 0, 50%)`
+    Rgb,
+}
+
+struct FoundColor {
+    /// The range in the source, in bytes
+    range: std::ops::Range<u32>,
+    color: Color,
+    syntax: ColorSyntax,
+}
+
+pub fn get_document_color(
+    document_cache: &mut DocumentCache,
+    text_document: &lsp_types::TextDocumentIdentifier,
+) -> Option<Vec<ColorInformation>> {
+    let uri = &text_document.uri;
+    let doc = document_cache.documents.get_document(&uri.to_file_path().ok()?)?;
+    let colors = find_colors(doc.node.as_ref()?);
+    Some(
+        colors
+            .into_iter()
+            .filter_map(|found| {
+                Some(ColorInformation {
+                    range: lsp_types::Range::new(
+                        document_cache.byte_offset_to_position(found.range.start, uri)?,
+                        document_cache.byte_offset_to_position(found.range.end, uri)?,
+                    ),
+                    color: found.color,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// The text of the color picked in the editor. The first presentation keeps the syntax of the
+/// color it replaces.
+pub fn get_color_presentation(
+    document_cache: &mut DocumentCache,
+    params: &ColorPresentationParams,
+) -> Vec<ColorPresentation> {
+    let uri = &params.text_document.uri;
+    let syntax = (|| {
+        let doc = document_cache.documents.get_document(&uri.to_file_path().ok()?)?;
+        let colors = find_colors(doc.node.as_ref()?);
+        colors.into_iter().find_map(|found| {
+            let start = document_cache.byte_offset_to_position(found.range.start, uri)?;
+            let end = document_cache.byte_offset_to_position(found.range.end, uri)?;
+            (lsp_types::Range::new(start, end) == params.range).then(|| found.syntax)
+        })
+    })()
+    .unwrap_or(ColorSyntax::Literal);
+
+    let mut labels = vec![hex_color(&params.color), rgb_color(&params.color)];
+    if syntax == ColorSyntax::Rgb {
+        labels.reverse();
+    }
+    labels
+        .into_iter()
+        .map(|label| ColorPresentation {
+            text_edit: Some(TextEdit::new(params.range, label.clone())),
+            label,
+            additional_text_edits: None,
+        })
+        .collect()
+}
+
+fn to_u8(component: f32) -> u8 {
+    (component.clamp(0., 1.) * 255.).round() as u8
+}
+
+fn hex_color(color: &Color) -> String {
+    let rgb =
+        format!("#{:02x}{:02x}{:02x}", to_u8(color.red), to_u8(color.green), to_u8(color.blue));
+    if to_u8(color.alpha) < 255 {
+        format!("{}{:02x}", rgb, to_u8(color.alpha))
+    } else {
+        rgb
+    }
+}
+
+fn rgb_color(color: &Color) -> String {
+    let (r, g, b) = (to_u8(color.red), to_u8(color.green), to_u8(color.blue));
+    if to_u8(color.alpha) < 255 {
+        format!("rgba({}, {}, {}, {}%)", r, g, b, (color.alpha.clamp(0., 1.) * 100.).round())
+    } else {
+        format!("rgb({}, {}, {})", r, g, b)
+    }
+}
+
+fn argb_to_color(argb: u32) -> Color {
+    let shift = |s: u32| -> f32 { ((argb >> s) & 0xff) as f32 / 255. };
+    Color { alpha: shift(24), red: shift(16), green: shift(8), blue: shift(0) }
+}
+
+/// Find the color literals, the colors of the `Colors` namespace, and the `rgb()` calls with
+/// number literals as arguments
+fn find_colors(doc_node: &SyntaxNode) -> Vec<FoundColor> {
+    let mut result = vec![];
+    let mut token = doc_node.first_token();
+    while let Some(t) = token {
+        match t.kind() {
+            SyntaxKind::ColorLiteral => {
+                if let Some(argb) = parse_color_literal(t.text()) {
+                    result.push(FoundColor {
+                        range: t.text_range().start().into()..t.text_range().end().into(),
+                        color: argb_to_color(argb),
+                        syntax: ColorSyntax::Literal,
+                    });
+                }
+            }
+            SyntaxKind::Identifier => {
+                if let Some(found) = named_color(&t).or_else(|| rgb_call(&t)) {
+                    result.push(found);
+                }
+            }
+            _ => {}
+        }
+        token = t.next_token();
+    }
+    result
+}
+
+/// `Colors.red`, if the token is the `Colors` identifier
+fn named_color(token: &SyntaxToken) -> Option<FoundColor> {
+    let qualified_name = token.parent();
+    if token.text() != "Colors"
+        || qualified_name.kind() != SyntaxKind::QualifiedName
+        || qualified_name.parent()?.kind() != SyntaxKind::Expression
+    {
+        return None;
+    }
+    let mut identifiers = qualified_name
+        .children_with_tokens()
+        .filter(|n| n.kind() == SyntaxKind::Identifier)
+        .filter_map(|n| n.into_token());
+    if identifiers.next()?.text_range() != token.text_range() {
+        return None;
+    }
+    let name = identifiers.next()?;
+    if identifiers.next().is_some() {
+        return None;
+    }
+    let argb = parse_named_color(&name.text().replace('_', "-"))?;
+    Some(FoundColor {
+        range: qualified_name.text_range().start().into()..qualified_name.text_range().end().into(),
+        color: argb_to_color(argb),
+        syntax: ColorSyntax::Named,
+    })
+}
+
+/// `rgb(r, g, b)` or `rgba(r, g, b, a)`, if the token is the name of the function
+fn rgb_call(token: &SyntaxToken) -> Option<FoundColor> {
+    if token.text() != "rgb" && token.text() != "rgba" {
+        return None;
+    }
+    let qualified_name = token.parent();
+    let function = qualified_name.parent()?;
+    let call = function.parent()?;
+    if qualified_name.kind() != SyntaxKind::QualifiedName
+        || call.kind() != SyntaxKind::FunctionCallExpression
+        || call.children().next()?.text_range() != function.text_range()
+    {
+        return None;
+    }
+    let args = call
+        .children()
+        .skip(1)
+        .map(|arg| {
+            // Only the arguments that are a number literal, maybe with a `%`
+            let mut tokens = arg
+                .children_with_tokens()
+                .filter(|n| !matches!(n.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment));
+            let literal = tokens.next()?.into_token()?;
+            if literal.kind() != SyntaxKind::NumberLiteral || tokens.next().is_some() {
+                return None;
+            }
+            let text = literal.text();
+            match text.strip_suffix('%') {
+                Some(percent) => percent.parse::<f32>().ok().map(|p| (p / 100., true)),
+                None => text.parse::<f32>().ok().map(|v| (v, false)),
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if args.len() != 3 && args.len() != 4 {
+        return None;
+    }
+    let component = |(value, is_percent): (f32, bool)| {
+        if is_percent {
+            value
+        } else {
+            value / 255.
+        }
+    };
+    Some(FoundColor {
+        range: call.text_range().start().into()..call.text_range().end().into(),
+        color: Color {
+            red: component(args[0]).clamp(0., 1.),
+            green: component(args[1]).clamp(0., 1.),
+            blue: component(args[2]).clamp(0., 1.),
+            alpha: args.get(3).map_or(1., |(alpha, _)| alpha.clamp(0., 1.)),
+        },
+        syntax: ColorSyntax::Rgb,
+    })
+}
@@ -0,0 +1,308 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The design mode of the preview: a click on an element of the component shows its declaration
+//! in the editor, and the bindings of the element can be edited in a panel next to the component.
+//! The edits are sent to the editor, which then sends the new content of the document, which
+//! reloads the preview.
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::parser::{syntax_nodes, SyntaxKind, SyntaxNode};
+use i_slint_core::model::{ModelRc, VecModel};
+use lsp_server::Message;
+use lsp_types::request::{ApplyWorkspaceEdit, ShowDocument};
+use slint_interpreter::{ComponentHandle, ComponentInstance, Value};
+
+/// The component that shows the previewed component with the overlay that selects the elements,
+/// and the panel with the bindings of the selected element.
+/// `{component}` is replaced by the name of the previewed component.
+const DESIGN_MODE_COMPONENT: &str = r#"
+_SlintDesignMode := Window {
+    property <bool> has-selection;
+    property <length> selection-x;
+    property <length> selection-y;
+    property <length> selection-width;
+    property <length> selection-height;
+    property <[{name: string, value: string}]> bindings;
+    callback element-clicked(length, length);
+    callback binding-edited(string, string);
+
+    HorizontalLayout {
+        preview := {component} { }
+        Rectangle {
+            width: 250px;
+            background: #f4f4f4;
+            VerticalLayout {
+                padding: 8px;
+                spacing: 4px;
+                alignment: start;
+                Text {
+                    text: !has-selection ? "Click on an element to select it"
+                        : bindings.length == 0 ? "The element has no binding" : "Bindings:";
+                }
+                for binding in bindings : HorizontalLayout {
+                    spacing: 4px;
+                    Text { text: binding.name; width: 90px; }
+                    Rectangle {
+                        border-width: 1px;
+                        border-color: #a0a0a0;
+                        background: white;
+                        TextInput {
+                            x: 2px;
+                            width: parent.width - 4px;
+                            text: binding.value;
+                            accepted => { root.binding-edited(binding.name, self.text); }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    TouchArea {
+        x: preview.x;
+        y: preview.y;
+        width: preview.width;
+        height: preview.height;
+        clicked => { root.element-clicked(self.x + self.pressed-x, self.y + self.pressed-y); }
+    }
+    Rectangle {
+        visible: has-selection;
+        x: selection-x;
+        y: selection-y;
+        width: selection-width;
+        height: selection-height;
+        border-width: 1px;
+        border-color: #2060e0;
+    }
+}
+"#;
+
+/// Append the design mode component to the source of the file that contains the previewed
+/// component. Returns the new source and the offset of the use of the previewed component.
+pub fn wrap_source(source: String, component: &str) -> (String, usize) {
+    let usage = format!("preview := {} {{ }}", component);
+    let wrapper = DESIGN_MODE_COMPONENT.replace("preview := {component} { }", &usage);
+    let usage_offset = source.len() + wrapper.find(&usage).unwrap_or_default();
+    (source + &wrapper, usage_offset)
+}
+
+/// The file of the previewed component
+#[derive(Clone)]
+pub struct DesignModeSource {
+    pub path: PathBuf,
+    pub component: String,
+    /// The source of the file, without the design mode component
+    pub source: String,
+    /// The offset of the use of the previewed component in the design mode component
+    pub usage_offset: usize,
+}
+
+/// The element selected by a click, and whose bindings are shown
+struct Selection {
+    path: PathBuf,
+    /// The offset of the element in the file
+    offset: usize,
+}
+
+thread_local! {static SELECTION: RefCell<Option<Selection>> = Default::default();}
+
+/// Set the callbacks of the design mode component of the preview
+pub fn setup(
+    instance: &ComponentInstance,
+    source: DesignModeSource,
+    sender: crossbeam_channel::Sender<Message>,
+) {
+    SELECTION.with(|selection| *selection.borrow_mut() = None);
+
+    let weak = instance.as_weak();
+    let click_sender = sender.clone();
+    let file = source.clone();
+    instance
+        .set_callback("element-clicked", move |args| {
+            if let (Some(instance), [Value::Number(x), Value::Number(y)]) = (weak.upgrade(), args) {
+                select_element_at(&instance, &file, *x as f32, *y as f32, &click_sender);
+            }
+            Value::Void
+        })
+        .unwrap_or_else(|e| eprintln!("Error setting up the design mode: {}", e));
+
+    let file = source;
+    instance
+        .set_callback("binding-edited", move |args| {
+            if let [Value::String(name), Value::String(value)] = args {
+                edit_binding(&file, name, value, &sender);
+            }
+            Value::Void
+        })
+        .unwrap_or_else(|e| eprintln!("Error setting up the design mode: {}", e));
+}
+
+fn select_element_at(
+    instance: &ComponentInstance,
+    file: &DesignModeSource,
+    x: f32,
+    y: f32,
+    sender: &crossbeam_channel::Sender<Message>,
+) -> Option<()> {
+    // The topmost element that is declared in the files of the user. The use of the previewed
+    // component in the design mode component selects the root element of the component.
+    let (path, offset, geometry) =
+        instance.elements_at_position(x, y).into_iter().find_map(|e| {
+            if e.path.starts_with("builtin:/") {
+                None
+            } else if e.path != file.path || e.offset < file.source.len() {
+                Some((e.path, e.offset, e.geometry))
+            } else if e.offset == file.usage_offset {
+                Some((file.path.clone(), component_offset(file)?, e.geometry))
+            } else {
+                None
+            }
+        })?;
+
+    let source =
+        if path == file.path { file.source.clone() } else { crate::preview::get_source(&path)? };
+    let element = find_element(&source, &path, offset)?;
+
+    let bindings = element
+        .Binding()
+        .filter_map(|b| {
+            let name = b.child_text(SyntaxKind::Identifier)?;
+            let value = expression_of(&b.BindingExpression())?.text().to_string().trim().to_owned();
+            Some(Value::Struct(
+                [
+                    ("name".to_owned(), Value::String(name.into())),
+                    ("value".to_owned(), Value::String(value.into())),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    let set = |name: &str, value: Value| {
+        instance
+            .set_property(name, value)
+            .unwrap_or_else(|e| eprintln!("Error setting {} in the design mode: {}", name, e));
+    };
+    set("bindings", Value::Model(ModelRc::new(VecModel::from(bindings))));
+    set("selection-x", Value::Number(geometry.origin.x as f64));
+    set("selection-y", Value::Number(geometry.origin.y as f64));
+    set("selection-width", Value::Number(geometry.size.width as f64));
+    set("selection-height", Value::Number(geometry.size.height as f64));
+    set("has-selection", Value::Bool(true));
+
+    // Show the element in the editor
+    let uri = lsp_types::Url::from_file_path(&path).ok()?;
+    let end = offset + element.QualifiedName().map_or(0, |q| usize::from(q.text_range().len()));
+    let range = lsp_types::Range::new(position(&source, offset), position(&source, end));
+    send_request::<ShowDocument>(
+        sender,
+        lsp_types::ShowDocumentParams {
+            uri,
+            external: None,
+            take_focus: Some(true),
+            selection: Some(range),
+        },
+    );
+
+    SELECTION.with(|selection| *selection.borrow_mut() = Some(Selection { path, offset }));
+    Some(())
+}
+
+/// Replace the expression of the binding of the selected element. The editor then sends the new
+/// content, which reloads the preview.
+fn edit_binding(
+    file: &DesignModeSource,
+    name: &str,
+    value: &str,
+    sender: &crossbeam_channel::Sender<Message>,
+) -> Option<()> {
+    let (path, offset) = SELECTION
+        .with(|selection| selection.borrow().as_ref().map(|s| (s.path.clone(), s.offset)))?;
+    let source =
+        if path == file.path { file.source.clone() } else { crate::preview::get_source(&path)? };
+    let element = find_element(&source, &path, offset)?;
+    let binding = element
+        .Binding()
+        .find(|b| b.child_text(SyntaxKind::Identifier).map_or(false, |n| n == name))?;
+    let expression = expression_of(&binding.BindingExpression())?;
+
+    // Only the expression is replaced, to keep the comments and the formatting around it
+    let range = expression.text_range();
+    let edit = lsp_types::TextEdit::new(
+        lsp_types::Range::new(
+            position(&source, range.start().into()),
+            position(&source, range.end().into()),
+        ),
+        value.to_owned(),
+    );
+    let uri = lsp_types::Url::from_file_path(&path).ok()?;
+    send_request::<ApplyWorkspaceEdit>(
+        sender,
+        lsp_types::ApplyWorkspaceEditParams {
+            label: Some(format!("Edit the binding of {}", name)),
+            edit: lsp_types::WorkspaceEdit {
+                changes: Some(std::iter::once((uri, vec![edit])).collect()),
+                ..Default::default()
+            },
+        },
+    );
+    Some(())
+}
+
+/// The offset of the root element of the previewed component
+fn component_offset(file: &DesignModeSource) -> Option<usize> {
+    let mut diag = BuildDiagnostics::default();
+    let doc = syntax_nodes::Document::new(i_slint_compiler::parser::parse(
+        file.source.clone(),
+        Some(&file.path),
+        &mut diag,
+    ))?;
+    doc.Component()
+        .chain(doc.ExportsList().filter_map(|e| e.Component()))
+        .find(|c| {
+            i_slint_compiler::parser::identifier_text(&c.DeclaredIdentifier()).as_deref()
+                == Some(file.component.as_str())
+        })
+        .map(|c| c.Element().text_range().start().into())
+}
+
+/// Parse the source and return the element that starts at the offset
+fn find_element(source: &str, path: &Path, offset: usize) -> Option<syntax_nodes::Element> {
+    let mut diag = BuildDiagnostics::default();
+    let node = i_slint_compiler::parser::parse(source.to_owned(), Some(path), &mut diag);
+    node.descendants()
+        .filter(|n| n.kind() == SyntaxKind::Element)
+        .find(|n| usize::from(n.text_range().start()) == offset)
+        .map(Into::into)
+}
+
+/// The expression or the code block of a binding, without the `;`
+fn expression_of(binding_expression: &SyntaxNode) -> Option<SyntaxNode> {
+    binding_expression
+        .children()
+        .find(|n| matches!(n.kind(), SyntaxKind::Expression | SyntaxKind::CodeBlock))
+}
+
+/// The position of a byte offset of the source, with the same convention as
+/// `DocumentCache::byte_offset_to_position`
+fn position(source: &str, offset: usize) -> lsp_types::Position {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count();
+    let character = before.len() - before.rfind('\n').map_or(0, |n| n + 1);
+    lsp_types::Position::new(line as u32, character as u32)
+}
+
+fn send_request<R: lsp_types::request::Request>(
+    sender: &crossbeam_channel::Sender<Message>,
+    params: R::Params,
+) {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+    let id = format!("slint-design-mode-{}", NEXT_ID.fetch_add(1, Ordering::SeqCst));
+    sender
+        .send(Message::Request(lsp_server::Request::new(id.into(), R::METHOD.into(), params)))
+        .unwrap_or_else(|e| eprintln!("Error sending request: {:?}", e));
+}
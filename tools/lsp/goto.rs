@@ -15,6 +15,17 @@ pub fn goto_definition(
     document_cache: &mut DocumentCache,
     token: SyntaxToken,
 ) -> Option<GotoDefinitionResponse> {
+    let node = find_definition_node(document_cache, token)?;
+    goto_node(document_cache, &node)
+}
+
+/// Return the node that declares the item referenced by the token: the element of a component,
+/// the declaration of a property or a callback, the declaration of a struct, or the document of an
+/// import
+pub fn find_definition_node(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+) -> Option<SyntaxNode> {
     let mut node = token.parent();
     loop {
         if let Some(n) = syntax_nodes::QualifiedName::new(node.clone()) {
@@ -25,10 +36,10 @@ pub fn goto_definition(
                     let doc = document_cache.documents.get_document(node.source_file.path())?;
                     match doc.local_registry.lookup_qualified(&qual.members) {
                         i_slint_compiler::langtype::Type::Component(c) => {
-                            goto_node(document_cache, &*c.root_element.borrow().node.as_ref()?)
+                            c.root_element.borrow().node.clone().map(Into::into)
                         }
                         i_slint_compiler::langtype::Type::Struct { node: Some(node), .. } => {
-                            goto_node(document_cache, node.parent().as_ref()?)
+                            node.parent()
                         }
                         _ => None,
                     }
@@ -80,7 +91,7 @@ pub fn goto_definition(
                         }
                         _ => return None,
                     };
-                    goto_node(document_cache, &gn)
+                    Some(gn)
                 }
                 _ => None,
             };
@@ -89,8 +100,20 @@ pub fn goto_definition(
             let imp_name = i_slint_compiler::typeloader::ImportedName::from_node(n);
             return match doc.local_registry.lookup(&imp_name.internal_name) {
                 i_slint_compiler::langtype::Type::Component(c) => {
-                    goto_node(document_cache, &*c.root_element.borrow().node.as_ref()?)
+                    c.root_element.borrow().node.clone().map(Into::into)
+                }
+                _ => None,
+            };
+        } else if syntax_nodes::ExportIdentifier::new(node.clone()).is_some() {
+            let doc = document_cache.documents.get_document(node.source_file.path())?;
+            return match doc
+                .local_registry
+                .lookup(&i_slint_compiler::parser::identifier_text(&node)?)
+            {
+                i_slint_compiler::langtype::Type::Component(c) => {
+                    c.root_element.borrow().node.clone().map(Into::into)
                 }
+                i_slint_compiler::langtype::Type::Struct { node: Some(node), .. } => node.parent(),
                 _ => None,
             };
         } else if let Some(n) = syntax_nodes::ImportSpecifier::new(node.clone()) {
@@ -103,7 +126,7 @@ pub fn goto_definition(
             let import_file = dunce::canonicalize(&import_file).unwrap_or(import_file);
             let doc = document_cache.documents.get_document(&import_file)?;
             let doc_node = doc.node.clone()?;
-            return goto_node(document_cache, &*doc_node);
+            return Some(doc_node.into());
         } else if syntax_nodes::BindingExpression::new(node.clone()).is_some() {
             // don't fallback to the Binding
             return None;
@@ -117,10 +140,10 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then(|| p)
             }) {
-                return goto_node(document_cache, &p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(document_cache, &n);
+            return Some(n);
         } else if let Some(n) = syntax_nodes::TwoWayBinding::new(node.clone()) {
             if token.kind() != SyntaxKind::Identifier {
                 return None;
@@ -134,10 +157,10 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then(|| p)
             }) {
-                return goto_node(document_cache, &p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(document_cache, &n);
+            return Some(n);
         } else if let Some(n) = syntax_nodes::CallbackConnection::new(node.clone()) {
             if token.kind() != SyntaxKind::Identifier {
                 return None;
@@ -151,17 +174,17 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then(|| p)
             }) {
-                return goto_node(document_cache, &p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(document_cache, &n);
+            return Some(n);
         }
         node = node.parent()?;
     }
 }
 
 /// Try to lookup the property `prop_name` in the base of the given Element
-fn find_property_declaration_in_base(
+pub fn find_property_declaration_in_base(
     document_cache: &DocumentCache,
     element: syntax_nodes::Element,
     prop_name: &str,
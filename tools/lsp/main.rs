@@ -1,10 +1,14 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
+mod colors;
 mod completion;
+mod design_mode;
 mod goto;
 mod lsp_ext;
 mod preview;
+mod remote_preview;
+mod rename;
 mod semantic_tokens;
 mod util;
 
@@ -20,15 +24,16 @@
 use lsp_types::notification::{DidChangeTextDocument, DidOpenTextDocument, Notification};
 use lsp_types::request::{
     CodeActionRequest, CodeLensRequest, ColorPresentationRequest, Completion, DocumentColor,
-    DocumentSymbolRequest, ExecuteCommand, GotoDefinition, HoverRequest, SemanticTokensFullRequest,
+    DocumentSymbolRequest, ExecuteCommand, GotoDefinition, HoverRequest, Rename,
+    SemanticTokensFullRequest,
 };
 use lsp_types::{
-    CodeActionOrCommand, CodeActionProviderCapability, CodeLens, CodeLensOptions, Color,
-    ColorInformation, ColorPresentation, Command, CompletionOptions, DidChangeTextDocumentParams,
-    DidOpenTextDocumentParams, DocumentSymbolResponse, ExecuteCommandOptions, Hover,
-    InitializeParams, Location, OneOf, Position, PublishDiagnosticsParams, Range,
-    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, ServerCapabilities,
-    SymbolInformation, TextDocumentSyncCapability, Url, WorkDoneProgressOptions,
+    CodeActionOrCommand, CodeActionProviderCapability, CodeLens, CodeLensOptions, Command,
+    CompletionOptions, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentSymbolResponse, ExecuteCommandOptions, Hover, InitializeParams, Location, OneOf,
+    Position, PublishDiagnosticsParams, Range, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, ServerCapabilities, SymbolInformation, TextDocumentSyncCapability, Url,
+    WorkDoneProgressOptions,
 };
 
 use clap::Parser;
@@ -36,6 +41,7 @@
 type Error = Box<dyn std::error::Error>;
 
 const SHOW_PREVIEW_COMMAND: &str = "showPreview";
+const DESIGN_PREVIEW_COMMAND: &str = "designPreview";
 
 #[derive(Clone, clap::Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -55,6 +61,11 @@ struct Cli {
     /// The backend used for the preview ('GL' or 'Qt')
     #[clap(long, name = "backend", default_value_t)]
     backend: String,
+
+    /// Also send the preview to a `slint-viewer --listen` that runs on a device, at this
+    /// address, such as '192.168.1.20:9320'
+    #[clap(long, name = "device address")]
+    remote_preview: Option<String>,
 }
 
 pub struct DocumentCache<'a> {
@@ -154,12 +165,13 @@ fn run_lsp_server() -> Result<(), Error> {
         )),
         code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
         execute_command_provider: Some(ExecuteCommandOptions {
-            commands: vec![SHOW_PREVIEW_COMMAND.into()],
+            commands: vec![SHOW_PREVIEW_COMMAND.into(), DESIGN_PREVIEW_COMMAND.into()],
             ..Default::default()
         }),
         document_symbol_provider: Some(OneOf::Left(true)),
         color_provider: Some(true.into()),
         code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(true) }),
+        rename_provider: Some(OneOf::Left(true)),
         semantic_tokens_provider: Some(
             SemanticTokensOptions {
                 legend: SemanticTokensLegend {
@@ -264,36 +276,18 @@ fn handle_request(
     } else if let Some((id, params)) = cast::<ExecuteCommand>(&mut req) {
         if params.command.as_str() == SHOW_PREVIEW_COMMAND {
             show_preview_command(&params.arguments, connection, document_cache)?
+        } else if params.command.as_str() == DESIGN_PREVIEW_COMMAND {
+            design_preview_command(&params.arguments, connection, document_cache)?
         }
         connection
             .sender
             .send(Message::Response(Response::new_ok(id, None::<serde_json::Value>)))?;
     } else if let Some((id, params)) = cast::<DocumentColor>(&mut req) {
-        let result = get_document_color(document_cache, &params.text_document).unwrap_or_default();
+        let result =
+            colors::get_document_color(document_cache, &params.text_document).unwrap_or_default();
         connection.sender.send(Message::Response(Response::new_ok(id, result)))?;
     } else if let Some((id, params)) = cast::<ColorPresentationRequest>(&mut req) {
-        // Convert the color from the color picker to a string representation. This could try to produce a minimal
-        // representation.
-        let requested_color = params.color;
-
-        let color_literal = if requested_color.alpha < 1. {
-            format!(
-                "#{:0>2x}{:0>2x}{:0>2x}{:0>2x}",
-                (requested_color.red * 255.) as u8,
-                (requested_color.green * 255.) as u8,
-                (requested_color.blue * 255.) as u8,
-                (requested_color.alpha * 255.) as u8
-            )
-        } else {
-            format!(
-                "#{:0>2x}{:0>2x}{:0>2x}",
-                (requested_color.red * 255.) as u8,
-                (requested_color.green * 255.) as u8,
-                (requested_color.blue * 255.) as u8,
-            )
-        };
-
-        let result = vec![ColorPresentation { label: color_literal, ..Default::default() }];
+        let result = colors::get_color_presentation(document_cache, &params);
         connection.sender.send(Message::Response(Response::new_ok(id, result)))?;
     } else if let Some((id, params)) = cast::<DocumentSymbolRequest>(&mut req) {
         let result = get_document_symbols(document_cache, &params.text_document);
@@ -301,6 +295,21 @@ fn handle_request(
     } else if let Some((id, params)) = cast::<CodeLensRequest>(&mut req) {
         let result = get_code_lenses(document_cache, &params.text_document);
         connection.sender.send(Message::Response(Response::new_ok(id, result)))?;
+    } else if let Some((id, params)) = cast::<Rename>(&mut req) {
+        let result = token_descr(
+            document_cache,
+            params.text_document_position.text_document,
+            params.text_document_position.position,
+        )
+        .map(|token| rename::rename(document_cache, token.0, &params.new_name));
+        let resp = match result {
+            Some(Err(message)) => {
+                Response::new_err(id, lsp_server::ErrorCode::InvalidParams as i32, message)
+            }
+            Some(Ok(edit)) => Response::new_ok(id, Some(edit)),
+            None => Response::new_ok(id, None::<lsp_types::WorkspaceEdit>),
+        };
+        connection.sender.send(Message::Response(resp))?;
     } else if let Some((id, params)) = cast::<SemanticTokensFullRequest>(&mut req) {
         let result = semantic_tokens::get_semantic_tokens(document_cache, &params.text_document);
         connection.sender.send(Message::Response(Response::new_ok(id, result)))?;
@@ -351,6 +360,13 @@ fn handle_notification(
                 document_cache,
             )?;
         }
+        "slint/showDesignPreview" => {
+            design_preview_command(
+                req.params.as_array().map_or(&[], |x| x.as_slice()),
+                connection,
+                document_cache,
+            )?;
+        }
         _ => (),
     }
     Ok(())
@@ -371,7 +387,46 @@ fn show_preview_command(
     let component = params.get(1).and_then(|v| v.as_str()).map(|v| v.to_string());
     preview::load_preview(
         connection.sender.clone(),
-        preview::PreviewComponent { path: path_canon, component },
+        preview::PreviewComponent { path: path_canon, component, design_mode: false },
+        preview::PostLoadBehavior::ShowAfterLoad,
+    );
+    Ok(())
+}
+
+/// Show the component at the position in the design mode of the preview.
+/// The parameters are the path of the file, and the line and the character of the position.
+fn design_preview_command(
+    params: &[serde_json::Value],
+    connection: &Connection,
+    document_cache: &mut DocumentCache,
+) -> Result<(), Error> {
+    let e = || -> Error { "InvalidParameter".into() };
+    let path = std::path::PathBuf::from(params.get(0).and_then(|v| v.as_str()).ok_or_else(e)?);
+    let line = params.get(1).and_then(|v| v.as_u64()).ok_or_else(e)?;
+    let character = params.get(2).and_then(|v| v.as_u64()).ok_or_else(e)?;
+    let path_canon = dunce::canonicalize(&path).unwrap_or_else(|_| path.to_owned());
+    let uri = Url::from_file_path(&path).map_err(|_| e())?;
+    let (token, _) = token_descr(
+        document_cache,
+        lsp_types::TextDocumentIdentifier { uri },
+        Position::new(line as u32, character as u32),
+    )
+    .ok_or_else(e)?;
+    let mut node = token.parent();
+    let component = loop {
+        if let Some(component) = syntax_nodes::Component::new(node.clone()) {
+            break i_slint_compiler::parser::identifier_text(&component.DeclaredIdentifier())
+                .ok_or_else(e)?;
+        }
+        node = node.parent().ok_or("There is no component at this position")?;
+    };
+    preview::load_preview(
+        connection.sender.clone(),
+        preview::PreviewComponent {
+            path: path_canon,
+            component: Some(component),
+            design_mode: true,
+        },
         preview::PostLoadBehavior::ShowAfterLoad,
     );
     Ok(())
@@ -407,6 +462,7 @@ fn maybe_goto_preview(
                 preview::PreviewComponent {
                     path: token.source_file.path().into(),
                     component: Some(component_name),
+                    design_mode: false,
                 },
                 preview::PostLoadBehavior::ShowAfterLoad,
             );
@@ -528,43 +584,6 @@ fn get_code_actions(
     ))])
 }
 
-fn get_document_color(
-    document_cache: &mut DocumentCache,
-    text_document: &lsp_types::TextDocumentIdentifier,
-) -> Option<Vec<ColorInformation>> {
-    let mut result = Vec::new();
-    let uri = &text_document.uri;
-    let doc = document_cache.documents.get_document(&uri.to_file_path().ok()?)?;
-    let root_node = &doc.node.as_ref()?.node;
-    let mut token = root_node.first_token()?;
-    loop {
-        if token.kind() == SyntaxKind::ColorLiteral {
-            (|| -> Option<()> {
-                let range = token.text_range();
-                let col = i_slint_compiler::literals::parse_color_literal(token.text())?;
-                let shift = |s: u32| -> f32 { ((col >> s) & 0xff) as f32 / 255. };
-                result.push(ColorInformation {
-                    range: Range::new(
-                        document_cache.byte_offset_to_position(range.start().into(), uri)?,
-                        document_cache.byte_offset_to_position(range.end().into(), uri)?,
-                    ),
-                    color: Color {
-                        alpha: shift(24),
-                        red: shift(16),
-                        green: shift(8),
-                        blue: shift(0),
-                    },
-                });
-                Some(())
-            })();
-        }
-        token = match token.next_token() {
-            Some(token) => token,
-            None => break Some(result),
-        }
-    }
-}
-
 fn get_document_symbols(
     document_cache: &mut DocumentCache,
     text_document: &lsp_types::TextDocumentIdentifier,
@@ -639,16 +658,33 @@ fn get_code_lenses(
         .iter()
         .filter(|c| !c.is_global())
         .filter_map(|c| {
-            Some(CodeLens {
-                range: make_range(c.root_element.borrow().node.as_ref()?)?,
-                command: Some(Command::new(
-                    "▶ Show preview".into(),
-                    SHOW_PREVIEW_COMMAND.into(),
-                    Some(vec![filepath.to_str()?.into(), c.id.as_str().into()]),
-                )),
-                data: None,
-            })
+            let range = make_range(c.root_element.borrow().node.as_ref()?)?;
+            Some([
+                CodeLens {
+                    range,
+                    command: Some(Command::new(
+                        "▶ Show preview".into(),
+                        SHOW_PREVIEW_COMMAND.into(),
+                        Some(vec![filepath.to_str()?.into(), c.id.as_str().into()]),
+                    )),
+                    data: None,
+                },
+                CodeLens {
+                    range,
+                    command: Some(Command::new(
+                        "✎ Design".into(),
+                        DESIGN_PREVIEW_COMMAND.into(),
+                        Some(vec![
+                            filepath.to_str()?.into(),
+                            range.start.line.into(),
+                            range.start.character.into(),
+                        ]),
+                    )),
+                    data: None,
+                },
+            ])
         })
+        .flatten()
         .collect::<Vec<_>>();
     Some(r)
 }
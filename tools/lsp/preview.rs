@@ -154,6 +154,9 @@ pub struct PreviewComponent {
     /// The name of the component within that file.
     /// If None, then the last component is going to be shown.
     pub component: Option<String>,
+    /// Show the component in the design mode, where the elements can be selected and their
+    /// bindings edited. Requires the name of the component.
+    pub design_mode: bool,
 }
 
 #[derive(Default)]
@@ -180,6 +183,13 @@ pub fn set_contents(path: &Path, content: String) {
     }
 }
 
+/// Returns the content of the file from the cache, or from the disk if it is not opened in the
+/// editor, without registering it as a dependency
+pub fn get_source(path: &Path) -> Option<String> {
+    let cache = CONTENT_CACHE.get_or_init(Default::default).lock().unwrap();
+    cache.source_code.get(path).cloned().or_else(|| std::fs::read_to_string(path).ok())
+}
+
 /// If the file is in the cache, returns it.
 /// In any was, register it as a dependency
 fn get_file_from_cache(path: PathBuf) -> Option<String> {
@@ -214,14 +224,36 @@ async fn reload_preview(
         Box::pin(async move { get_file_from_cache(path).map(Result::Ok) })
     });
 
-    let compiled = if let Some(mut from_cache) = get_file_from_cache(preview_component.path.clone())
-    {
-        if let Some(component) = &preview_component.component {
-            from_cache = format!("{}\n_Preview := {} {{ }}\n", from_cache, component);
+    let mut from_cache = get_file_from_cache(preview_component.path.clone());
+    if from_cache.is_none() && preview_component.design_mode {
+        from_cache = std::fs::read_to_string(&preview_component.path).ok();
+    }
+    let mut design_mode_source = None;
+    // The source of the main file for the remote preview, which doesn't have the design mode
+    let mut remote_source = from_cache.clone();
+    let compiled = if let Some(mut from_cache) = from_cache {
+        match &preview_component.component {
+            Some(component) if preview_component.design_mode => {
+                remote_source = Some(format!("{}\n_Preview := {} {{ }}\n", from_cache, component));
+                let (wrapped, usage_offset) =
+                    crate::design_mode::wrap_source(from_cache.clone(), component);
+                design_mode_source = Some(crate::design_mode::DesignModeSource {
+                    path: preview_component.path.clone(),
+                    component: component.clone(),
+                    source: from_cache,
+                    usage_offset,
+                });
+                from_cache = wrapped;
+            }
+            Some(component) => {
+                from_cache = format!("{}\n_Preview := {} {{ }}\n", from_cache, component);
+                remote_source = Some(from_cache.clone());
+            }
+            None => {}
         }
-        builder.build_from_source(from_cache, preview_component.path).await
+        builder.build_from_source(from_cache, preview_component.path.clone()).await
     } else {
-        builder.build_from_path(preview_component.path).await
+        builder.build_from_path(&preview_component.path).await
     };
 
     notify_diagnostics(builder.diagnostics(), &sender);
@@ -247,8 +279,23 @@ struct PreviewState {
                 handle.show();
                 preview_state.handle = Some(handle);
             }
+            if let (Some(handle), Some(source)) = (&preview_state.handle, design_mode_source) {
+                crate::design_mode::setup(handle, source, sender.clone());
+            }
         });
         send_notification(&sender, "Preview Loaded", Health::Ok);
+        if let Some(address) = cli_args.remote_preview {
+            let path = &preview_component.path;
+            if let Some(source) = remote_source.or_else(|| std::fs::read_to_string(path).ok()) {
+                crate::remote_preview::send(
+                    address,
+                    path,
+                    source,
+                    compiled.dependencies(),
+                    sender.clone(),
+                );
+            }
+        }
     } else {
         send_notification(&sender, "Preview not updated", Health::Error);
     }
@@ -0,0 +1,202 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The remote preview: the previewed component is also sent to a `slint-viewer --listen` that
+//! runs on a device, and the diagnostics of its compilation on the device are shown in the editor
+
+use std::collections::{HashMap, HashSet};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use lsp_server::Message;
+use lsp_types::notification::Notification;
+use once_cell::sync::Lazy;
+use slint_interpreter::remote_preview::{read_message, write_message, RemoteMessage};
+
+use crate::lsp_ext::{Health, ServerStatusNotification, ServerStatusParams};
+
+struct Load {
+    address: String,
+    bundle: Vec<u8>,
+    /// The directory of the files of the bundle
+    root: PathBuf,
+    sender: crossbeam_channel::Sender<Message>,
+}
+
+/// The bundles are sent by a thread, so that a slow network doesn't block the preview
+static QUEUE: Lazy<Mutex<crossbeam_channel::Sender<Load>>> = Lazy::new(|| {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    std::thread::spawn(move || send_bundles(receiver));
+    Mutex::new(sender)
+});
+
+/// Send a bundle with the main file and the dependencies of the previewed component to the
+/// device. `main_source` is the source of the main file that was compiled for the preview.
+pub fn send(
+    address: String,
+    main_file: &Path,
+    main_source: String,
+    dependencies: Vec<PathBuf>,
+    sender: crossbeam_channel::Sender<Message>,
+) {
+    let canonical_main_file = dunce::canonicalize(main_file).unwrap_or_else(|_| main_file.into());
+    let mut bundle = vec![];
+    let root = i_slint_compiler::bundle::write_bundle_files(
+        main_file,
+        dependencies,
+        &mut |path| {
+            if path == canonical_main_file {
+                Ok(main_source.clone().into_bytes())
+            } else if path.extension().map_or(false, |e| e == "slint" || e == "60") {
+                crate::preview::get_source(path).map(String::into_bytes).ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::NotFound, path.display().to_string())
+                })
+            } else {
+                std::fs::read(path)
+            }
+        },
+        &mut bundle,
+    );
+    match root {
+        Ok(root) => {
+            let load = Load { address, bundle, root, sender };
+            QUEUE.lock().unwrap().send(load).unwrap_or_else(|e| eprintln!("{}", e));
+        }
+        Err(err) => send_notification(
+            &sender,
+            &format!("Cannot send the preview to the device: {}", err),
+            Health::Warning,
+        ),
+    }
+}
+
+struct Connection {
+    address: String,
+    stream: TcpStream,
+    /// The directory of the files of the last bundle, to which the diagnostics are relative
+    root: Arc<Mutex<PathBuf>>,
+}
+
+fn send_bundles(receiver: crossbeam_channel::Receiver<Load>) {
+    let mut connection: Option<Connection> = None;
+    for load in receiver {
+        if connection.as_ref().map_or(false, |c| c.address != load.address) {
+            connection = None;
+        }
+        let message = RemoteMessage::Load { bundle: load.bundle.clone() };
+        // If the device was restarted since the last preview, the write fails on the old
+        // connection, so try again once on a new connection
+        let mut result = Ok(());
+        for _ in 0..2 {
+            let c = match connection.take().map_or_else(|| connect(&load), Ok) {
+                Ok(c) => connection.insert(c),
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            };
+            *c.root.lock().unwrap() = load.root.clone();
+            result = write_message(&mut c.stream, &message);
+            if result.is_ok() {
+                break;
+            }
+            connection = None;
+        }
+        match result {
+            Ok(()) => send_notification(
+                &load.sender,
+                &format!("Preview sent to {}", load.address),
+                Health::Ok,
+            ),
+            Err(err) => send_notification(
+                &load.sender,
+                &format!("Cannot send the preview to {}: {}", load.address, err),
+                Health::Warning,
+            ),
+        }
+    }
+}
+
+/// Connect to the device, and start the thread that reads the diagnostics that it sends back
+fn connect(load: &Load) -> std::io::Result<Connection> {
+    let stream = TcpStream::connect(&load.address)?;
+    let root = Arc::new(Mutex::new(load.root.clone()));
+    let reader = stream.try_clone()?;
+    let address = load.address.clone();
+    let sender = load.sender.clone();
+    let reader_root = root.clone();
+    std::thread::spawn(move || receive_diagnostics(reader, &address, &reader_root, &sender));
+    Ok(Connection { address: load.address.clone(), stream, root })
+}
+
+fn receive_diagnostics(
+    stream: TcpStream,
+    address: &str,
+    root: &Mutex<PathBuf>,
+    sender: &crossbeam_channel::Sender<Message>,
+) {
+    let mut reader = std::io::BufReader::new(stream);
+    // The documents with diagnostics of the device, which are cleared by the next compilation
+    let mut published = HashSet::new();
+    loop {
+        let diagnostics = match read_message(&mut reader) {
+            Ok(Some(RemoteMessage::Diagnostics(diagnostics))) => diagnostics,
+            Ok(Some(_)) => continue,
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("Error reading the diagnostics of {}: {}", address, err);
+                break;
+            }
+        };
+        let root = root.lock().unwrap().clone();
+        let has_error =
+            diagnostics.iter().any(|d| d.level == slint_interpreter::DiagnosticLevel::Error);
+
+        let mut lsp_diags: HashMap<lsp_types::Url, Vec<lsp_types::Diagnostic>> =
+            published.drain().map(|uri| (uri, vec![])).collect();
+        for d in diagnostics {
+            let uri = match lsp_types::Url::from_file_path(root.join(&d.file)) {
+                Ok(uri) if !d.file.is_empty() => uri,
+                _ => continue,
+            };
+            lsp_diags.entry(uri).or_default().push(lsp_types::Diagnostic::new(
+                crate::util::to_range((d.line, d.column)),
+                Some(crate::util::to_lsp_diag_level(d.level)),
+                None,
+                Some(format!("device {}", address)),
+                d.message,
+                None,
+                None,
+            ));
+        }
+        for (uri, diagnostics) in lsp_diags {
+            if !diagnostics.is_empty() {
+                published.insert(uri.clone());
+            }
+            sender
+                .send(Message::Notification(lsp_server::Notification::new(
+                    "textDocument/publishDiagnostics".into(),
+                    lsp_types::PublishDiagnosticsParams { uri, diagnostics, version: None },
+                )))
+                .unwrap_or_else(|e| eprintln!("Error sending notification: {:?}", e));
+        }
+        if has_error {
+            send_notification(
+                sender,
+                &format!("The preview has errors on {}", address),
+                Health::Error,
+            );
+        }
+    }
+    send_notification(sender, &format!("Disconnected from {}", address), Health::Warning);
+}
+
+fn send_notification(sender: &crossbeam_channel::Sender<Message>, arg: &str, health: Health) {
+    sender
+        .send(Message::Notification(lsp_server::Notification::new(
+            ServerStatusNotification::METHOD.into(),
+            ServerStatusParams { health, quiescent: false, message: Some(arg.into()) },
+        )))
+        .unwrap_or_else(|e| eprintln!("Error sending notification: {:?}", e));
+}
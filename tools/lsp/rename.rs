@@ -0,0 +1,204 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Rename of the components, properties, and callbacks in all the loaded documents
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::goto::{find_definition_node, find_property_declaration_in_base};
+use crate::DocumentCache;
+use i_slint_compiler::langtype::Type;
+use i_slint_compiler::parser::{
+    identifier_text, normalize_identifier, syntax_nodes, SyntaxKind, SyntaxNode, SyntaxToken,
+};
+use lsp_types::{TextEdit, Url, WorkspaceEdit};
+
+/// Return the edits that rename the item under the token to `new_name`, in all the documents that
+/// use it, or an error message if the item cannot be renamed or if the new name is already used
+pub fn rename(
+    document_cache: &mut DocumentCache,
+    token: SyntaxToken,
+    new_name: &str,
+) -> Result<WorkspaceEdit, String> {
+    let cannot_rename = || "Only a component, a property, or a callback can be renamed".to_owned();
+    if token.kind() != SyntaxKind::Identifier {
+        return Err(cannot_rename());
+    }
+    let target = definition_node(document_cache, &token).ok_or_else(cannot_rename)?;
+    let is_component = match target.kind() {
+        SyntaxKind::Element
+            if target.parent().map_or(false, |p| p.kind() == SyntaxKind::Component) =>
+        {
+            true
+        }
+        SyntaxKind::PropertyDeclaration | SyntaxKind::CallbackDeclaration => false,
+        _ => return Err(cannot_rename()),
+    };
+    if is_builtin(target.source_file.path()) {
+        return Err("The items of the Slint library cannot be renamed".into());
+    }
+    if !is_identifier(new_name) {
+        return Err(format!("'{}' is not a valid identifier", new_name));
+    }
+
+    let old_name = normalize_identifier(token.text());
+    let normalized_new_name = normalize_identifier(new_name);
+    if old_name == normalized_new_name {
+        return Ok(WorkspaceEdit::default());
+    }
+
+    let mut usages = vec![];
+    for doc in document_cache.documents.all_documents() {
+        let doc_node = match &doc.node {
+            Some(node) if !is_builtin(node.source_file.path()) => node,
+            _ => continue,
+        };
+        for t in doc_node.descendants_with_tokens().filter_map(|t| t.into_token()) {
+            if t.kind() == SyntaxKind::Identifier
+                && normalize_identifier(t.text()) == old_name
+                && definition_node(document_cache, &t).map_or(false, |n| same_node(&n, &target))
+            {
+                usages.push(t);
+            }
+        }
+    }
+
+    if is_component {
+        check_component_conflicts(document_cache, &usages, &normalized_new_name)?;
+    } else {
+        check_property_conflicts(document_cache, &target, &old_name, &normalized_new_name)?;
+    }
+
+    let mut changes = HashMap::<Url, Vec<TextEdit>>::new();
+    for usage in usages {
+        let uri = match Url::from_file_path(usage.source_file.path()) {
+            Ok(uri) => uri,
+            Err(()) => continue,
+        };
+        let range = usage.text_range();
+        let start = document_cache.byte_offset_to_position(range.start().into(), &uri);
+        let end = document_cache.byte_offset_to_position(range.end().into(), &uri);
+        if let (Some(start), Some(end)) = (start, end) {
+            changes
+                .entry(uri)
+                .or_default()
+                .push(TextEdit::new(lsp_types::Range::new(start, end), new_name.to_owned()));
+        }
+    }
+    Ok(WorkspaceEdit { changes: Some(changes), ..Default::default() })
+}
+
+/// The node that declares the item referenced by the token, which is the token of a name
+/// in a declaration or of a use of the item
+fn definition_node(document_cache: &DocumentCache, token: &SyntaxToken) -> Option<SyntaxNode> {
+    let parent = token.parent();
+    if parent.kind() != SyntaxKind::DeclaredIdentifier {
+        return find_definition_node(document_cache, token.clone());
+    }
+    let declaration = parent.parent()?;
+    match declaration.kind() {
+        SyntaxKind::Component => Some(syntax_nodes::Component::from(declaration).Element().into()),
+        SyntaxKind::PropertyDeclaration | SyntaxKind::CallbackDeclaration => Some(declaration),
+        _ => None,
+    }
+}
+
+fn same_node(a: &SyntaxNode, b: &SyntaxNode) -> bool {
+    a.text_range() == b.text_range() && a.source_file.path() == b.source_file.path()
+}
+
+fn is_builtin(path: &Path) -> bool {
+    path.starts_with("builtin:/")
+}
+
+fn is_identifier(name: &str) -> bool {
+    let tokens = i_slint_compiler::lexer::lex(name);
+    tokens.len() == 1 && tokens[0].kind == SyntaxKind::Identifier
+}
+
+/// Check that no type named `new_name` is visible in the documents where the component is renamed
+fn check_component_conflicts(
+    document_cache: &DocumentCache,
+    usages: &[SyntaxToken],
+    new_name: &str,
+) -> Result<(), String> {
+    for usage in usages {
+        // `import { Foo as Bar }` doesn't make the new name visible in the document
+        let parent = usage.parent();
+        if parent.kind() == SyntaxKind::ExternalName
+            && parent.parent().map_or(false, |p| p.child_node(SyntaxKind::InternalName).is_some())
+        {
+            continue;
+        }
+        let path = usage.source_file.path();
+        let doc = match document_cache.documents.get_document(path) {
+            Some(doc) => doc,
+            None => continue,
+        };
+        if !matches!(doc.local_registry.lookup(new_name), Type::Invalid) {
+            return Err(format!(
+                "There is already a type named '{}' in {}",
+                new_name,
+                path.display()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that the element that declares the property, its base, and the elements that inherit
+/// the property don't already have a property or a callback named `new_name`
+fn check_property_conflicts(
+    document_cache: &DocumentCache,
+    target: &SyntaxNode,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), String> {
+    let conflict =
+        || Err(format!("There is already a property or a callback named '{}'", new_name));
+
+    let element = target.parent().and_then(syntax_nodes::Element::new);
+    if let Some(element) = element {
+        if declares(&element, new_name) {
+            return conflict();
+        }
+        let global_tr = document_cache.documents.global_type_registry.borrow();
+        let tr = document_cache
+            .documents
+            .get_document(target.source_file.path())
+            .map(|doc| &doc.local_registry)
+            .unwrap_or(&global_tr);
+        if crate::util::lookup_current_element_type((*element).clone(), tr)
+            .map_or(false, |ty| ty.lookup_property(new_name).is_valid())
+        {
+            return conflict();
+        }
+    }
+
+    for doc in document_cache.documents.all_documents() {
+        let doc_node = match &doc.node {
+            Some(node) if !is_builtin(node.source_file.path()) => node,
+            _ => continue,
+        };
+        for element in doc_node.descendants().filter_map(syntax_nodes::Element::new) {
+            if declares(&element, new_name)
+                && find_property_declaration_in_base(document_cache, element, old_name)
+                    .map_or(false, |n| same_node(&n, target))
+            {
+                return conflict();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the element declares a property, a callback, or a function with this name
+fn declares(element: &syntax_nodes::Element, name: &str) -> bool {
+    element
+        .PropertyDeclaration()
+        .map(|p| p.DeclaredIdentifier())
+        .chain(element.CallbackDeclaration().map(|c| c.DeclaredIdentifier()))
+        .chain(element.Function().map(|f| f.DeclaredIdentifier()))
+        .any(|d| identifier_text(&d).as_deref() == Some(name))
+}
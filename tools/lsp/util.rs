@@ -137,7 +137,7 @@ pub fn to_lsp_diag(d: &i_slint_compiler::diagnostics::Diagnostic) -> lsp_types::
     )
 }
 
-fn to_range(span: (usize, usize)) -> lsp_types::Range {
+pub fn to_range(span: (usize, usize)) -> lsp_types::Range {
     let pos = lsp_types::Position::new(
         (span.0 as u32).saturating_sub(1),
         (span.1 as u32).saturating_sub(1),
@@ -145,7 +145,7 @@ fn to_range(span: (usize, usize)) -> lsp_types::Range {
     lsp_types::Range::new(pos, pos)
 }
 
-fn to_lsp_diag_level(
+pub fn to_lsp_diag_level(
     level: i_slint_compiler::diagnostics::DiagnosticLevel,
 ) -> lsp_types::DiagnosticSeverity {
     match level {
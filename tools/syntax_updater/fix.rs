@@ -0,0 +1,68 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Apply the edits that the compiler attaches to some diagnostics, such as the new name of a
+//! deprecated property
+
+use i_slint_compiler::diagnostics::{BuildDiagnostics, SourceEdit};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_owned())
+}
+
+/// Compile the .slint files, and return the edits of the diagnostics of all the files they
+/// load, by canonical path. The edits of each file are computed before any file is modified.
+pub(crate) fn collect_edits(paths: &[PathBuf]) -> HashMap<PathBuf, Vec<SourceEdit>> {
+    let mut edits = HashMap::<PathBuf, Vec<SourceEdit>>::new();
+    for path in paths {
+        if !matches!(path.extension(), Some(ext) if ext == "slint" || ext == "60") {
+            continue;
+        }
+        let mut diag = BuildDiagnostics::default();
+        let syntax_node = match i_slint_compiler::parser::parse_file(path, &mut diag) {
+            Some(syntax_node) if !diag.has_error() => syntax_node,
+            _ => {
+                diag.print();
+                continue;
+            }
+        };
+        let compiler_config = i_slint_compiler::CompilerConfiguration::new(
+            i_slint_compiler::generator::OutputFormat::Interpreter,
+        );
+        let (_, diag) = spin_on::spin_on(i_slint_compiler::compile_syntax_node(
+            syntax_node,
+            diag,
+            compiler_config,
+        ));
+        for d in diag.iter() {
+            if let (Some(source_file), false) = (d.source_file(), d.edits().is_empty()) {
+                edits.entry(canonical(source_file)).or_default().extend_from_slice(d.edits());
+            }
+        }
+    }
+    edits
+}
+
+/// Apply the edits to the source. The edits can be in any order, the duplicated ones are only
+/// applied once, and the ones that overlap a previous edit are ignored.
+pub(crate) fn apply_edits(source: &str, edits: &[SourceEdit]) -> String {
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|e| (e.offset, e.length));
+    edits.dedup();
+
+    let mut result = String::with_capacity(source.len());
+    let mut position = 0;
+    for edit in edits {
+        let end = edit.offset + edit.length;
+        if edit.offset < position || source.get(edit.offset..end).is_none() {
+            continue;
+        }
+        result.push_str(&source[position..edit.offset]);
+        result.push_str(&edit.replacement);
+        position = end;
+    }
+    result.push_str(&source[position..]);
+    result
+}
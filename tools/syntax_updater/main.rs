@@ -14,6 +14,13 @@
 //! cargo run --bin syntax_updater -- --from 0.0.5 -i  **/*.rs
 //! cargo run --bin syntax_updater -- --from 0.0.5 -i  **/*.md
 //! ````
+//!
+//! With `--fix`, it applies the edits that the compiler suggests for its deprecation warnings,
+//! such as the new name of a deprecated property, to all the given .slint files:
+//!
+//! ````shell
+//! cargo run --bin syntax_updater -- --fix -i  **/*.slint
+//! ````
 
 use i_slint_compiler::diagnostics::BuildDiagnostics;
 use i_slint_compiler::object_tree;
@@ -23,6 +30,7 @@
 
 use clap::Parser;
 
+mod fix;
 mod from_0_0_5;
 mod from_0_0_6;
 mod from_0_1_0;
@@ -38,19 +46,30 @@ struct Cli {
     inline: bool,
 
     /// Version to update from
-    #[clap(long, name = "version")]
-    from: String,
+    #[clap(long, name = "version", required_unless_present = "fix")]
+    from: Option<String>,
+
+    /// Apply the fixes of the deprecation warnings of the compiler
+    #[clap(long)]
+    fix: bool,
 }
 
 fn main() -> std::io::Result<()> {
     let args = Cli::parse();
-    if !matches!(args.from.as_str(), "0.0.5" | "0.0.6") && !args.from.starts_with("0.1.") {
-        eprintln!("Invalid from version is supported, use `--from 0.1.x`");
-        std::process::exit(1);
+    if let Some(from) = &args.from {
+        if !matches!(from.as_str(), "0.0.5" | "0.0.6") && !from.starts_with("0.1.") {
+            eprintln!("Invalid from version is supported, use `--from 0.1.x`");
+            std::process::exit(1);
+        }
     }
 
+    let edits = if args.fix { fix::collect_edits(&args.paths) } else { Default::default() };
+
     for path in &args.paths {
-        let source = std::fs::read_to_string(path)?;
+        let mut source = std::fs::read_to_string(path)?;
+        if let Some(edits) = edits.get(&fix::canonical(path)) {
+            source = fix::apply_edits(&source, edits);
+        }
 
         if args.inline {
             let file = std::fs::File::create(path)?;
@@ -151,6 +170,10 @@ fn process_file(
     mut file: impl Write,
     args: &Cli,
 ) -> std::io::Result<()> {
+    if args.from.is_none() {
+        // Only the fixes are applied
+        return file.write_all(source.as_bytes());
+    }
     match path.extension() {
         Some(ext) if ext == "rs" => return process_rust_file(source, file, args),
         Some(ext) if ext == "md" => return process_markdown_file(source, file, args),
@@ -219,13 +242,14 @@ fn fold_node(
     state: &mut State,
     args: &Cli,
 ) -> std::io::Result<bool> {
-    if args.from == "0.0.5" && from_0_0_5::fold_node(node, file, state)? {
+    let from = args.from.as_deref().unwrap_or_default();
+    if from == "0.0.5" && from_0_0_5::fold_node(node, file, state)? {
         return Ok(true);
     }
-    if args.from.as_str() <= "0.0.6" && from_0_0_6::fold_node(node, file, state)? {
+    if from <= "0.0.6" && from_0_0_6::fold_node(node, file, state)? {
         return Ok(true);
     }
-    if args.from.as_str() <= "0.1.6" && from_0_1_0::fold_node(node, file, state)? {
+    if from <= "0.1.6" && from_0_1_0::fold_node(node, file, state)? {
         return Ok(true);
     }
     Ok(false)
@@ -3,10 +3,15 @@
 
 #![doc = include_str!("README.md")]
 
-use i_slint_core::model::{Model, ModelRc};
-use i_slint_core::SharedVector;
-use slint_interpreter::{ComponentHandle, ComponentInstance, SharedString, Value};
+use slint_interpreter::automation::value_from_json;
+use slint_interpreter::remote_preview::{
+    read_message, write_message, RemoteDiagnostic, RemoteMessage,
+};
+use slint_interpreter::{
+    ComponentDefinition, ComponentHandle, ComponentInstance, DiagnosticLevel, Value,
+};
 use std::future::Future;
+use std::net::TcpStream;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
@@ -29,8 +34,12 @@ struct Cli {
     include_paths: Vec<std::path::PathBuf>,
 
     /// The .slint file to load ('-' for stdin)
-    #[clap(name = "path to .slint file", parse(from_os_str))]
-    path: std::path::PathBuf,
+    #[clap(
+        name = "path to .slint file",
+        parse(from_os_str),
+        required_unless_present = "listen address"
+    )]
+    path: Option<std::path::PathBuf>,
 
     /// The style name ('native', 'fluent', or 'ugly')
     #[clap(long, name = "style name")]
@@ -40,7 +49,7 @@ struct Cli {
     #[clap(long, name = "backend")]
     backend: Option<String>,
 
-    /// Automatically watch the file system, and reload when it changes
+    /// Automatically watch the .slint files, images, and fonts, and reload when they change
     #[clap(long)]
     auto_reload: bool,
 
@@ -52,12 +61,41 @@ struct Cli {
     #[clap(long, name = "save data file", parse(from_os_str))]
     save_data: Option<std::path::PathBuf>,
 
+    /// Render the component into a png file and exit, instead of running it
+    #[clap(long, name = "screenshot file", parse(from_os_str))]
+    screenshot: Option<std::path::PathBuf>,
+
+    /// Write the scene of the component into an SVG file and exit, instead of running it
+    #[clap(long, name = "svg file", parse(from_os_str))]
+    export_svg: Option<std::path::PathBuf>,
+
+    /// The size of the window for the screenshot or the SVG file, such as '800x600'
+    #[clap(long, name = "size")]
+    size: Option<String>,
+
     /// Specify callbacks handler.
     /// The first argument is the callback name, and the second argument is a string that is going
     /// to be passed to the shell to be executed. Occurences of `$1` will be replaced by the first argument,
     /// and so on.
     #[clap(long, value_names(&["callback", "handler"]), number_of_values = 2)]
     on: Vec<String>,
+
+    /// Instead of loading a file, wait for the connection of an editor on this address, such as
+    /// '0.0.0.0:9320', and show the components that it sends
+    #[clap(long, name = "listen address")]
+    listen: Option<String>,
+
+    /// Let external tools, such as test frameworks, control the component with requests sent to
+    /// this address, such as '127.0.0.1:9321'
+    #[clap(long, name = "automation address")]
+    automation: Option<String>,
+}
+
+impl Cli {
+    /// The path of the .slint file, which is only missing with `--listen`
+    fn path(&self) -> &std::path::Path {
+        self.path.as_deref().expect("the path is required without --listen")
+    }
 }
 
 thread_local! {static CURRENT_INSTANCE: std::cell::RefCell<Option<ComponentInstance>> = Default::default();}
@@ -71,20 +109,59 @@ fn main() -> Result<()> {
         std::process::exit(-1);
     }
 
+    if args.auto_reload && (args.screenshot.is_some() || args.export_svg.is_some()) {
+        eprintln!("Cannot pass --auto-reload with --screenshot or --export-svg");
+        std::process::exit(-1);
+    }
+    if args.size.is_some() && args.screenshot.is_none() && args.export_svg.is_none() {
+        eprintln!("--size requires --screenshot or --export-svg");
+        std::process::exit(-1);
+    }
+    if args.listen.is_some()
+        && (args.auto_reload
+            || args.save_data.is_some()
+            || args.screenshot.is_some()
+            || args.export_svg.is_some())
+    {
+        eprintln!(
+            "Cannot pass --listen with --auto-reload, --save-data, --screenshot, or --export-svg"
+        );
+        std::process::exit(-1);
+    }
+    if args.automation.is_some() && (args.auto_reload || args.listen.is_some()) {
+        eprintln!("Cannot pass --automation with --auto-reload or --listen");
+        std::process::exit(-1);
+    }
+    let size = match args.size.as_deref().map(parse_size) {
+        Some(Some(size)) => Some(size),
+        Some(None) => {
+            eprintln!("Invalid --size: it must be like 800x600");
+            std::process::exit(-1);
+        }
+        None => None,
+    };
+
     if let Some(backend) = &args.backend {
         std::env::set_var("SLINT_BACKEND", backend);
     }
 
+    if let Some(address) = args.listen.clone() {
+        return listen(args, &address);
+    }
+
     let fswatcher = if args.auto_reload { Some(start_fswatch_thread(args.clone())?) } else { None };
-    let mut compiler = init_compiler(&args, fswatcher);
+    let mut compiler = init_compiler(&args, fswatcher.clone());
 
-    let c = spin_on::spin_on(compiler.build_from_path(args.path));
+    let c = spin_on::spin_on(compiler.build_from_path(args.path()));
     slint_interpreter::print_diagnostics(compiler.diagnostics());
 
     let c = match c {
         Some(c) => c,
         None => std::process::exit(-1),
     };
+    if let Some(watcher) = &fswatcher {
+        watch_dependencies(&c, watcher);
+    }
 
     let component = c.create();
     init_dialog(&component);
@@ -94,6 +171,25 @@ fn main() -> Result<()> {
     }
     install_callbacks(&component, &args.on);
 
+    if let Some(address) = &args.automation {
+        let address = slint_interpreter::automation::start_tcp_server(&component, address)?;
+        eprintln!("Waiting for automation requests on {}", address);
+    }
+
+    if let Some(svg_path) = args.export_svg {
+        if let Some((width, height)) = size {
+            use i_slint_core::window::WindowHandleAccess;
+            component.window().window_handle().set_window_item_geometry(width, height);
+        }
+        std::fs::write(svg_path, component.window().export_svg())?;
+        std::process::exit(0);
+    }
+
+    if let Some(screenshot_path) = args.screenshot {
+        take_screenshot(&component, &screenshot_path, size)?;
+        std::process::exit(0);
+    }
+
     if args.auto_reload {
         CURRENT_INSTANCE.with(|current| current.replace(Some(component.clone_strong())));
     }
@@ -101,44 +197,50 @@ fn main() -> Result<()> {
     component.run();
 
     if let Some(data_path) = args.save_data {
-        let mut obj = serde_json::Map::new();
-        for (name, _) in c.properties() {
-            fn to_json(val: slint_interpreter::Value) -> Option<serde_json::Value> {
-                match val {
-                    slint_interpreter::Value::Number(x) => Some(x.into()),
-                    slint_interpreter::Value::String(x) => Some(x.as_str().into()),
-                    slint_interpreter::Value::Bool(x) => Some(x.into()),
-                    slint_interpreter::Value::Model(model) => {
-                        let mut res = Vec::with_capacity(model.row_count());
-                        for i in 0..model.row_count() {
-                            res.push(to_json(model.row_data(i).unwrap())?);
-                        }
-                        Some(serde_json::Value::Array(res))
-                    }
-                    slint_interpreter::Value::Struct(st) => {
-                        let mut obj = serde_json::Map::new();
-                        for (k, v) in st.iter() {
-                            obj.insert(k.into(), to_json(v.clone())?);
-                        }
-                        Some(obj.into())
-                    }
-                    _ => None,
-                }
-            }
-            if let Some(v) = to_json(component.get_property(&name).unwrap()) {
-                obj.insert(name, v);
-            }
-        }
-        if data_path == std::path::Path::new("-") {
-            serde_json::to_writer_pretty(std::io::stdout(), &obj)?;
-        } else {
-            serde_json::to_writer_pretty(std::fs::File::create(data_path)?, &obj)?;
-        }
+        save_data(&component, &data_path)?;
     }
 
     std::process::exit(EXIT_CODE.load(std::sync::atomic::Ordering::Relaxed))
 }
 
+/// Show the component, and save the content of its window in a png file once it is rendered
+fn take_screenshot(
+    component: &ComponentInstance,
+    path: &std::path::Path,
+    size: Option<(f32, f32)>,
+) -> Result<()> {
+    use i_slint_core::window::WindowHandleAccess;
+    component.show();
+    let window = component.window().window_handle().clone();
+    if let Some((width, height)) = size {
+        window.set_window_item_geometry(width, height);
+    }
+
+    // Wait for the window to be mapped and resized by the windowing system before rendering it
+    let snapshot = std::rc::Rc::new(std::cell::RefCell::new(None));
+    i_slint_core::timers::Timer::single_shot(Duration::from_millis(200), {
+        let snapshot = snapshot.clone();
+        move || {
+            *snapshot.borrow_mut() = window.take_snapshot();
+            i_slint_backend_selector::backend().quit_event_loop();
+        }
+    });
+    slint_interpreter::run_event_loop();
+
+    let buffer = snapshot.take().ok_or("The rendering backend cannot take screenshots")?;
+    let image =
+        image::RgbaImage::from_raw(buffer.width(), buffer.height(), buffer.as_bytes().to_vec())
+            .ok_or("Invalid screenshot data")?;
+    image.save(path)?;
+    Ok(())
+}
+
+/// Parse a size such as `800x600`
+fn parse_size(size: &str) -> Option<(f32, f32)> {
+    let (width, height) = size.split_once('x')?;
+    Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+}
+
 fn init_compiler(
     args: &Cli,
     fswatcher: Option<Arc<Mutex<notify::RecommendedWatcher>>>,
@@ -151,11 +253,11 @@ fn init_compiler(
     if let Some(watcher) = fswatcher {
         notify::Watcher::watch(
             &mut *watcher.lock().unwrap(),
-            &args.path,
+            args.path(),
             notify::RecursiveMode::NonRecursive,
         )
         .unwrap_or_else(|err| {
-            eprintln!("Warning: error while watching {}: {:?}", args.path.display(), err)
+            eprintln!("Warning: error while watching {}: {:?}", args.path().display(), err)
         });
         if let Some(data_path) = &args.load_data {
             notify::Watcher::watch(
@@ -182,6 +284,24 @@ fn init_compiler(
     compiler
 }
 
+/// Watch the images and fonts of the component, in addition to the .slint files that the file
+/// loader of the compiler already watches
+fn watch_dependencies(
+    definition: &ComponentDefinition,
+    watcher: &Arc<Mutex<notify::RecommendedWatcher>>,
+) {
+    for path in definition.dependencies() {
+        notify::Watcher::watch(
+            &mut *watcher.lock().unwrap(),
+            &path,
+            notify::RecursiveMode::NonRecursive,
+        )
+        .unwrap_or_else(|err| {
+            eprintln!("Warning: error while watching {}: {:?}", path.display(), err)
+        });
+    }
+}
+
 fn init_dialog(instance: &ComponentInstance) {
     for cb in instance.definition().callbacks() {
         let exit_code = match cb.as_str() {
@@ -222,65 +342,138 @@ fn start_fswatch_thread(args: Cli) -> Result<Arc<Mutex<notify::RecommendedWatche
 }
 
 async fn reload(args: Cli, fswatcher: Arc<Mutex<notify::RecommendedWatcher>>) {
-    let mut compiler = init_compiler(&args, Some(fswatcher));
-    let c = compiler.build_from_path(&args.path).await;
+    let mut compiler = init_compiler(&args, Some(fswatcher.clone()));
+    let c = compiler.build_from_path(args.path()).await;
     slint_interpreter::print_diagnostics(compiler.diagnostics());
 
     if let Some(c) = c {
-        CURRENT_INSTANCE.with(|current| {
-            let mut current = current.borrow_mut();
-            if let Some(handle) = current.take() {
-                let window = handle.window();
-                let new_handle = c.create_with_existing_window(window);
-                init_dialog(&new_handle);
-                current.replace(new_handle);
-            } else {
-                let handle = c.create();
-                init_dialog(&handle);
-                handle.show();
-                current.replace(handle);
-            }
-            if let Some(data_path) = args.load_data {
-                let _ = load_data(current.as_ref().unwrap(), &data_path);
+        watch_dependencies(&c, &fswatcher);
+        let instance = replace_current_instance(&c);
+        if let Some(data_path) = &args.load_data {
+            if let Err(err) = load_data(&instance, data_path) {
+                eprintln!("Error while loading the data from {}: {}", data_path.display(), err);
             }
-            eprintln!("Successful reload of {}", args.path.display());
-        });
+        }
+        eprintln!("Successful reload of {}", args.path().display());
     }
 
     PENDING_EVENTS.fetch_sub(1, Ordering::SeqCst);
 }
 
+/// Show a new instance of the component in the window of the current instance, or in a new window
+fn replace_current_instance(definition: &ComponentDefinition) -> ComponentInstance {
+    CURRENT_INSTANCE.with(|current| {
+        let mut current = current.borrow_mut();
+        let instance = if let Some(handle) = current.take() {
+            definition.create_with_existing_window(handle.window())
+        } else {
+            let handle = definition.create();
+            handle.show();
+            handle
+        };
+        init_dialog(&instance);
+        current.replace(instance.clone_strong());
+        instance
+    })
+}
+
+/// Wait for the connections of the editors, and show the components of the bundles they send,
+/// as described in the `remote_preview` module of the interpreter
+fn listen(args: Cli, address: &str) -> Result<()> {
+    let listener = std::net::TcpListener::bind(address)?;
+    eprintln!("Waiting for an editor on {}", listener.local_addr()?);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let args = args.clone();
+                    std::thread::spawn(move || serve_editor(args, stream));
+                }
+                Err(err) => eprintln!("Error while accepting a connection: {}", err),
+            }
+        }
+    });
+    i_slint_backend_selector::backend()
+        .run_event_loop(i_slint_core::backend::EventLoopQuitBehavior::QuitOnlyExplicitly);
+    Ok(())
+}
+
+/// Read the messages of an editor until it disconnects
+fn serve_editor(args: Cli, stream: TcpStream) {
+    let peer = stream.peer_addr().map_or_else(|_| "an editor".into(), |a| a.to_string());
+    eprintln!("Connection from {}", peer);
+    let writer = match stream.try_clone() {
+        Ok(writer) => Arc::new(Mutex::new(writer)),
+        Err(err) => {
+            eprintln!("Error with the connection from {}: {}", peer, err);
+            return;
+        }
+    };
+    let mut reader = std::io::BufReader::new(stream);
+    loop {
+        match read_message(&mut reader) {
+            Ok(Some(RemoteMessage::Load { bundle })) => {
+                run_in_ui_thread(Box::pin(load_bundle(args.clone(), bundle, writer.clone())))
+            }
+            Ok(Some(message)) => eprintln!("Warning: unexpected message {:?}", message),
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("Error with the connection from {}: {}", peer, err);
+                break;
+            }
+        }
+    }
+    eprintln!("{} disconnected", peer);
+}
+
+/// Compile the component of a bundle sent by an editor, show it, and send back the diagnostics.
+/// An error while loading the `--load-data` file is sent as a diagnostic without a file.
+async fn load_bundle(args: Cli, bundle: Vec<u8>, writer: Arc<Mutex<TcpStream>>) {
+    let mut compiler = init_compiler(&args, None);
+    let c = compiler.build_from_bundle_data(&bundle).await;
+    slint_interpreter::print_diagnostics(compiler.diagnostics());
+
+    let mut diagnostics: Vec<_> =
+        compiler.diagnostics().iter().map(RemoteDiagnostic::from_diagnostic).collect();
+
+    if let Some(c) = c {
+        let instance = replace_current_instance(&c);
+        if let Some(data_path) = &args.load_data {
+            if let Err(err) = load_data(&instance, data_path) {
+                let message =
+                    format!("Error while loading the data from {}: {}", data_path.display(), err);
+                eprintln!("{}", message);
+                diagnostics.push(RemoteDiagnostic {
+                    level: DiagnosticLevel::Error,
+                    file: String::new(),
+                    line: 0,
+                    column: 0,
+                    message,
+                });
+            }
+        }
+        install_callbacks(&instance, &args.on);
+        instance.show();
+    }
+
+    write_message(&mut *writer.lock().unwrap(), &RemoteMessage::Diagnostics(diagnostics))
+        .unwrap_or_else(|err| eprintln!("Error while sending the diagnostics: {}", err));
+}
+
 fn load_data(instance: &ComponentInstance, data_path: &std::path::Path) -> Result<()> {
     let json: serde_json::Value = if data_path == std::path::Path::new("-") {
         serde_json::from_reader(std::io::stdin())?
     } else {
         serde_json::from_reader(std::fs::File::open(data_path)?)?
     };
+    // Relative image paths are relative to the data file
+    let base_dir = data_path.parent().unwrap_or_else(|| std::path::Path::new(""));
 
     let obj = json.as_object().ok_or("The data is not a JSON object")?;
     for (name, v) in obj {
-        fn from_json(v: &serde_json::Value) -> slint_interpreter::Value {
-            match v {
-                serde_json::Value::Null => slint_interpreter::Value::Void,
-                serde_json::Value::Bool(b) => (*b).into(),
-                serde_json::Value::Number(n) => {
-                    slint_interpreter::Value::Number(n.as_f64().unwrap_or(f64::NAN))
-                }
-                serde_json::Value::String(s) => SharedString::from(s.as_str()).into(),
-                serde_json::Value::Array(array) => slint_interpreter::Value::Model(ModelRc::new(
-                    i_slint_core::model::SharedVectorModel::from(
-                        array.iter().map(from_json).collect::<SharedVector<Value>>(),
-                    ),
-                )),
-                serde_json::Value::Object(obj) => obj
-                    .iter()
-                    .map(|(k, v)| (k.clone(), from_json(v)))
-                    .collect::<slint_interpreter::Struct>()
-                    .into(),
-            }
-        }
-
-        match instance.set_property(name, from_json(v)) {
+        // The current value tells how to convert the strings and the objects
+        let current = instance.get_property(name).unwrap_or_default();
+        match instance.set_property(name, value_from_json(v, &current, base_dir)) {
             Ok(()) => (),
             Err(e) => eprintln!("Warning: cannot set property '{}' from data file: {:?}", name, e),
         };
@@ -288,6 +481,21 @@ fn from_json(v: &serde_json::Value) -> slint_interpreter::Value {
     Ok(())
 }
 
+fn save_data(instance: &ComponentInstance, data_path: &std::path::Path) -> Result<()> {
+    let mut obj = serde_json::Map::new();
+    for (name, _) in instance.definition().properties() {
+        if let Ok(v) = serde_json::Value::try_from(instance.get_property(&name).unwrap()) {
+            obj.insert(name, v);
+        }
+    }
+    if data_path == std::path::Path::new("-") {
+        serde_json::to_writer_pretty(std::io::stdout(), &obj)?;
+    } else {
+        serde_json::to_writer_pretty(std::fs::File::create(data_path)?, &obj)?;
+    }
+    Ok(())
+}
+
 fn install_callbacks(instance: &ComponentInstance, callbacks: &[String]) {
     assert!(callbacks.len() % 2 == 0);
     for chunk in callbacks.chunks(2) {